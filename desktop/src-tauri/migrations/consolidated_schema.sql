@@ -96,6 +96,35 @@ CREATE INDEX IF NOT EXISTS idx_fsh_session_created_at ON file_selection_history
 CREATE INDEX IF NOT EXISTS idx_fsh_session_seq ON file_selection_history (session_id, sequence_number);
 
 
+-- Create session_lineage table (parent/child links for forked sessions)
+CREATE TABLE IF NOT EXISTS session_lineage (
+  session_id TEXT PRIMARY KEY,
+  parent_session_id TEXT DEFAULT NULL,
+  root_session_id TEXT NOT NULL,
+  forked_task_history_index INTEGER NOT NULL,
+  forked_file_history_index INTEGER NOT NULL,
+  created_at INTEGER NOT NULL,
+  FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE,
+  FOREIGN KEY (parent_session_id) REFERENCES sessions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_lineage_parent ON session_lineage(parent_session_id);
+CREATE INDEX IF NOT EXISTS idx_session_lineage_root ON session_lineage(root_session_id);
+
+-- Create task_description_ops table (text CRDT operations for task descriptions)
+CREATE TABLE IF NOT EXISTS task_description_ops (
+  session_id TEXT NOT NULL,
+  replica_id TEXT NOT NULL,
+  counter INTEGER NOT NULL,
+  op TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+  PRIMARY KEY (session_id, replica_id, counter),
+  FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_description_ops_session_counter ON task_description_ops(session_id, counter);
+
+
 -- Create key_value_store table
 CREATE TABLE IF NOT EXISTS key_value_store (
   key TEXT PRIMARY KEY,
@@ -157,6 +186,101 @@ CREATE INDEX IF NOT EXISTS idx_background_jobs_task_type ON background_jobs(task
 CREATE INDEX IF NOT EXISTS idx_background_jobs_request_id ON background_jobs(server_request_id) WHERE server_request_id IS NOT NULL;
 CREATE INDEX IF NOT EXISTS idx_background_jobs_finalized ON background_jobs(is_finalized) WHERE is_finalized = 0;
 
+-- Create job_provenance table (explicit parent -> child derivation between jobs)
+CREATE TABLE IF NOT EXISTS job_provenance (
+  parent_job_id TEXT NOT NULL,
+  child_job_id TEXT NOT NULL,
+  relation TEXT NOT NULL,
+  replay_id TEXT,
+  created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+  PRIMARY KEY (parent_job_id, child_job_id, relation),
+  FOREIGN KEY (parent_job_id) REFERENCES background_jobs(id) ON DELETE CASCADE,
+  FOREIGN KEY (child_job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_provenance_child ON job_provenance(child_job_id);
+CREATE INDEX IF NOT EXISTS idx_job_provenance_replay ON job_provenance(replay_id) WHERE replay_id IS NOT NULL;
+
+-- Create plan_revisions table (every edit of an implementation plan, sign-off per revision)
+CREATE TABLE IF NOT EXISTS plan_revisions (
+  job_id TEXT NOT NULL,
+  revision INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  source TEXT NOT NULL CHECK (source IN ('model', 'edit', 'restore', 'refinement')),
+  author_device_id TEXT,
+  restored_from INTEGER,
+  signoff_state TEXT,
+  signed_off_at INTEGER,
+  created_at INTEGER NOT NULL,
+  PRIMARY KEY (job_id, revision),
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_revisions_signed_off ON plan_revisions(job_id, signed_off_at) WHERE signed_off_at IS NOT NULL;
+
+-- Create plan_step_states table (per-step status and assignee of implementation plans)
+CREATE TABLE IF NOT EXISTS plan_step_states (
+  job_id TEXT NOT NULL,
+  step_number TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'in_progress', 'done', 'skipped')),
+  assignee TEXT,
+  updated_by_device_id TEXT,
+  updated_at INTEGER NOT NULL,
+  PRIMARY KEY (job_id, step_number),
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+-- Create plan_step_comments table (review comments anchored to plan steps)
+CREATE TABLE IF NOT EXISTS plan_step_comments (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  step_number TEXT NOT NULL,
+  plan_revision INTEGER,
+  body TEXT NOT NULL,
+  author_device_id TEXT,
+  resolved INTEGER NOT NULL DEFAULT 0 CHECK (resolved IN (0, 1)),
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL,
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_step_comments_job_step ON plan_step_comments(job_id, step_number, created_at);
+
+-- Create plan_conversation_messages table (follow-up conversations refining a plan)
+CREATE TABLE IF NOT EXISTS plan_conversation_messages (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  role TEXT NOT NULL CHECK (role IN ('user', 'assistant')),
+  content TEXT NOT NULL,
+  refinement_job_id TEXT,
+  plan_revision INTEGER,
+  author_device_id TEXT,
+  created_at INTEGER NOT NULL,
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_conversation_messages_job ON plan_conversation_messages(job_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_plan_conversation_messages_refinement ON plan_conversation_messages(refinement_job_id) WHERE refinement_job_id IS NOT NULL;
+
+-- Create workspaces table (groups of project directories for cross-repo sessions)
+CREATE TABLE IF NOT EXISTS workspaces (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL
+);
+
+-- Create workspace_repositories table (labelled member directories of a workspace)
+CREATE TABLE IF NOT EXISTS workspace_repositories (
+  workspace_id TEXT NOT NULL,
+  label TEXT NOT NULL,
+  project_directory TEXT NOT NULL UNIQUE,
+  position INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (workspace_id, label),
+  FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
 -- Task settings table removed in favor of server-side configuration
 -- All AI task configuration will be fetched exclusively from the server
 
@@ -212,6 +336,39 @@ CREATE INDEX IF NOT EXISTS idx_error_logs_platform
 ON error_logs(platform, error_type) 
 WHERE platform IS NOT NULL;
 
+-- =====================================================================
+-- Integration Hooks
+-- =====================================================================
+CREATE TABLE IF NOT EXISTS integration_hooks (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  events TEXT NOT NULL DEFAULT '[]',  -- JSON array of event types
+  target_type TEXT NOT NULL CHECK (target_type IN ('webhook', 'shell')),
+  target TEXT NOT NULL,               -- URL or command line
+  headers TEXT,                       -- JSON object (webhooks only)
+  max_attempts INTEGER NOT NULL DEFAULT 3 CHECK (max_attempts >= 1),
+  enabled INTEGER NOT NULL DEFAULT 1 CHECK (enabled IN (0, 1)),
+  created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+  updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS integration_hook_deliveries (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  hook_id TEXT NOT NULL,
+  event_type TEXT NOT NULL,
+  payload TEXT NOT NULL,
+  attempt INTEGER NOT NULL,
+  status TEXT NOT NULL CHECK (status IN ('success', 'failed')),
+  response_status INTEGER,            -- HTTP status or process exit code
+  error TEXT,
+  duration_ms INTEGER,
+  created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+  FOREIGN KEY (hook_id) REFERENCES integration_hooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_hook_deliveries_hook_created ON integration_hook_deliveries(hook_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_integration_hook_deliveries_created ON integration_hook_deliveries(created_at);
+
 -- =====================================================================
 -- Additional Performance Indexes
 -- =====================================================================
@@ -301,6 +458,104 @@ BEGIN
   WHERE id = NEW.id;
 END;
 
+-- =========================================================================
+-- Full-text search
+-- =========================================================================
+
+-- search_documents holds filterable metadata; search_index rowids match search_documents.id.
+-- Sessions and task history are indexed by triggers, completed jobs by the job repository.
+CREATE TABLE IF NOT EXISTS search_documents (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  source_type TEXT NOT NULL CHECK(source_type IN ('session', 'task_history', 'job')),
+  source_id TEXT NOT NULL,
+  session_id TEXT NOT NULL,
+  project_hash TEXT NOT NULL,
+  task_type TEXT DEFAULT NULL,
+  created_at INTEGER NOT NULL,
+  UNIQUE(source_type, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_documents_session_id ON search_documents(session_id);
+CREATE INDEX IF NOT EXISTS idx_search_documents_project_created ON search_documents(project_hash, created_at);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+  title,
+  content,
+  prompt,
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+DROP TRIGGER IF EXISTS trg_search_sessions_insert;
+CREATE TRIGGER trg_search_sessions_insert
+AFTER INSERT ON sessions
+BEGIN
+  INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+  VALUES ('session', NEW.id, NEW.id, NEW.project_hash, NULL, NEW.created_at);
+  INSERT INTO search_index (rowid, title, content, prompt)
+  SELECT id, NEW.name, COALESCE(NEW.task_description, ''), ''
+  FROM search_documents WHERE source_type = 'session' AND source_id = NEW.id;
+END;
+
+DROP TRIGGER IF EXISTS trg_search_sessions_update;
+CREATE TRIGGER trg_search_sessions_update
+AFTER UPDATE OF name, task_description, project_hash ON sessions
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE source_type = 'session' AND source_id = OLD.id
+  );
+  UPDATE search_documents SET project_hash = NEW.project_hash WHERE session_id = NEW.id;
+  INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+  VALUES ('session', NEW.id, NEW.id, NEW.project_hash, NULL, NEW.created_at)
+  ON CONFLICT(source_type, source_id) DO NOTHING;
+  INSERT INTO search_index (rowid, title, content, prompt)
+  SELECT id, NEW.name, COALESCE(NEW.task_description, ''), ''
+  FROM search_documents WHERE source_type = 'session' AND source_id = NEW.id;
+END;
+
+DROP TRIGGER IF EXISTS trg_search_sessions_delete;
+CREATE TRIGGER trg_search_sessions_delete
+AFTER DELETE ON sessions
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE session_id = OLD.id
+  );
+  DELETE FROM search_documents WHERE session_id = OLD.id;
+END;
+
+DROP TRIGGER IF EXISTS trg_search_task_history_insert;
+CREATE TRIGGER trg_search_task_history_insert
+AFTER INSERT ON task_description_history
+BEGIN
+  INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+  SELECT 'task_history', CAST(NEW.id AS TEXT), s.id, s.project_hash, NULL, NEW.created_at
+  FROM sessions s WHERE s.id = NEW.session_id
+  ON CONFLICT(source_type, source_id) DO NOTHING;
+  INSERT INTO search_index (rowid, title, content, prompt)
+  SELECT d.id, s.name, NEW.description, ''
+  FROM search_documents d JOIN sessions s ON s.id = d.session_id
+  WHERE d.source_type = 'task_history' AND d.source_id = CAST(NEW.id AS TEXT);
+END;
+
+DROP TRIGGER IF EXISTS trg_search_task_history_delete;
+CREATE TRIGGER trg_search_task_history_delete
+AFTER DELETE ON task_description_history
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE source_type = 'task_history' AND source_id = CAST(OLD.id AS TEXT)
+  );
+  DELETE FROM search_documents WHERE source_type = 'task_history' AND source_id = CAST(OLD.id AS TEXT);
+END;
+
+DROP TRIGGER IF EXISTS trg_search_background_jobs_delete;
+CREATE TRIGGER trg_search_background_jobs_delete
+AFTER DELETE ON background_jobs
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE source_type = 'job' AND source_id = OLD.id
+  );
+  DELETE FROM search_documents WHERE source_type = 'job' AND source_id = OLD.id;
+END;
+
 -- Record this consolidated schema in the key_value_store table
 -- =========================================================================
 -- Insert Default Configuration Data
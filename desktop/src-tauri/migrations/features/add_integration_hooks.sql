@@ -0,0 +1,36 @@
+-- Add integration hooks
+-- A hook is a webhook URL or shell command fired for chosen application events
+-- (job lifecycle, workflow stages, plan creation, terminal exit). Every delivery
+-- attempt is logged so failures and retries can be inspected from the UI.
+
+CREATE TABLE IF NOT EXISTS integration_hooks (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  events TEXT NOT NULL DEFAULT '[]',  -- JSON array of event types, e.g. ["plan.created"]
+  target_type TEXT NOT NULL CHECK (target_type IN ('webhook', 'shell')),
+  target TEXT NOT NULL,               -- URL for webhooks, command line for shell hooks
+  headers TEXT,                       -- JSON object of extra HTTP headers (webhooks only)
+  max_attempts INTEGER NOT NULL DEFAULT 3 CHECK (max_attempts >= 1),
+  enabled INTEGER NOT NULL DEFAULT 1 CHECK (enabled IN (0, 1)),
+  created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+  updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS integration_hook_deliveries (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  hook_id TEXT NOT NULL,
+  event_type TEXT NOT NULL,
+  payload TEXT NOT NULL,
+  attempt INTEGER NOT NULL,
+  status TEXT NOT NULL CHECK (status IN ('success', 'failed')),
+  response_status INTEGER,            -- HTTP status or process exit code
+  error TEXT,
+  duration_ms INTEGER,
+  created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+  FOREIGN KEY (hook_id) REFERENCES integration_hooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_hook_deliveries_hook_created ON integration_hook_deliveries(hook_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_integration_hook_deliveries_created ON integration_hook_deliveries(created_at);
+
+-- Migration tracking is handled automatically by the migration system
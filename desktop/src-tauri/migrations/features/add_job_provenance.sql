@@ -0,0 +1,42 @@
+-- Add explicit job provenance
+-- One row per parent -> child derivation between background jobs (workflow stage,
+-- merge source, selected files, research, re-run). replay_id groups the re-runs
+-- created by one "re-run from here" operation.
+
+CREATE TABLE IF NOT EXISTS job_provenance (
+  parent_job_id TEXT NOT NULL,
+  child_job_id TEXT NOT NULL,
+  relation TEXT NOT NULL,
+  replay_id TEXT,
+  created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+  PRIMARY KEY (parent_job_id, child_job_id, relation),
+  FOREIGN KEY (parent_job_id) REFERENCES background_jobs(id) ON DELETE CASCADE,
+  FOREIGN KEY (child_job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_provenance_child ON job_provenance(child_job_id);
+CREATE INDEX IF NOT EXISTS idx_job_provenance_replay ON job_provenance(replay_id) WHERE replay_id IS NOT NULL;
+
+-- Backfill the lineage that was previously only implicit in job metadata
+
+INSERT OR IGNORE INTO job_provenance (parent_job_id, child_job_id, relation, created_at)
+SELECT parent.id, j.id, 'workflow_stage', j.created_at
+FROM background_jobs j
+JOIN background_jobs parent
+  ON parent.id = json_extract(CASE WHEN json_valid(j.metadata) THEN j.metadata ELSE '{}' END, '$.workflowId')
+WHERE parent.id != j.id;
+
+INSERT OR IGNORE INTO job_provenance (parent_job_id, child_job_id, relation, created_at)
+SELECT parent.id, j.id, 'merge_source', j.created_at
+FROM background_jobs j,
+  json_each(CASE WHEN json_valid(j.metadata) THEN j.metadata ELSE '{}' END, '$.jobPayloadForWorker.data.sourceJobIds') source
+JOIN background_jobs parent ON parent.id = source.value
+WHERE j.task_type = 'implementation_plan_merge';
+
+INSERT OR IGNORE INTO job_provenance (parent_job_id, child_job_id, relation, created_at)
+SELECT parent.id, j.id, 'continued_from', j.created_at
+FROM background_jobs j
+JOIN background_jobs parent
+  ON parent.id = json_extract(CASE WHEN json_valid(j.metadata) THEN j.metadata ELSE '{}' END, '$.continuedFromJob');
+
+-- Migration tracking is handled automatically by the migration system
@@ -0,0 +1,22 @@
+-- Add follow-up conversations on implementation plans
+-- Each message belongs to the plan it refines. User messages record the revision they were
+-- asked against; assistant messages hold the revised plan and the revision it became. The
+-- refinement job is referenced without a foreign key so the conversation outlives job
+-- history cleanup.
+
+CREATE TABLE IF NOT EXISTS plan_conversation_messages (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  role TEXT NOT NULL CHECK (role IN ('user', 'assistant')),
+  content TEXT NOT NULL,
+  refinement_job_id TEXT,
+  plan_revision INTEGER,
+  author_device_id TEXT,
+  created_at INTEGER NOT NULL,
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_conversation_messages_job ON plan_conversation_messages(job_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_plan_conversation_messages_refinement ON plan_conversation_messages(refinement_job_id) WHERE refinement_job_id IS NOT NULL;
+
+-- Migration tracking is handled automatically by the migration system
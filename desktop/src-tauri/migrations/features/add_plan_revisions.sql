@@ -0,0 +1,24 @@
+-- Add implementation plan revisions
+-- Every edit of a plan's content is kept as a numbered revision so the original model
+-- output and earlier edits survive hand-editing. Revision 1 is the model output; it is
+-- captured lazily the first time a plan is edited or its history is read. Sign-off is
+-- recorded against a specific revision.
+
+CREATE TABLE IF NOT EXISTS plan_revisions (
+  job_id TEXT NOT NULL,
+  revision INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  source TEXT NOT NULL CHECK (source IN ('model', 'edit', 'restore')),
+  author_device_id TEXT,
+  restored_from INTEGER,
+  signoff_state TEXT,
+  signed_off_at INTEGER,
+  created_at INTEGER NOT NULL,
+  PRIMARY KEY (job_id, revision),
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_revisions_signed_off ON plan_revisions(job_id, signed_off_at) WHERE signed_off_at IS NOT NULL;
+
+-- Migration tracking is handled automatically by the migration system
@@ -0,0 +1,32 @@
+-- Add per-step review state for implementation plans
+-- Steps are identified by the `number` attribute of their <step> element (or their 1-based
+-- position when the attribute is missing), so status and comments follow a step across plan
+-- revisions as long as its number is kept.
+
+CREATE TABLE IF NOT EXISTS plan_step_states (
+  job_id TEXT NOT NULL,
+  step_number TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'in_progress', 'done', 'skipped')),
+  assignee TEXT,
+  updated_by_device_id TEXT,
+  updated_at INTEGER NOT NULL,
+  PRIMARY KEY (job_id, step_number),
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS plan_step_comments (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  step_number TEXT NOT NULL,
+  plan_revision INTEGER,
+  body TEXT NOT NULL,
+  author_device_id TEXT,
+  resolved INTEGER NOT NULL DEFAULT 0 CHECK (resolved IN (0, 1)),
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL,
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_step_comments_job_step ON plan_step_comments(job_id, step_number, created_at);
+
+-- Migration tracking is handled automatically by the migration system
@@ -0,0 +1,134 @@
+-- Add full-text search over sessions, task description history and job outputs
+-- search_documents holds the filterable metadata; search_index is the FTS5 table
+-- whose rowid matches search_documents.id. Sessions and history are kept in sync by
+-- triggers, completed jobs are indexed by the background job repository.
+
+CREATE TABLE IF NOT EXISTS search_documents (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  source_type TEXT NOT NULL CHECK(source_type IN ('session', 'task_history', 'job')),
+  source_id TEXT NOT NULL,
+  session_id TEXT NOT NULL,
+  project_hash TEXT NOT NULL,
+  task_type TEXT DEFAULT NULL,
+  created_at INTEGER NOT NULL,
+  UNIQUE(source_type, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_documents_session_id ON search_documents(session_id);
+CREATE INDEX IF NOT EXISTS idx_search_documents_project_created ON search_documents(project_hash, created_at);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+  title,
+  content,
+  prompt,
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+DROP TRIGGER IF EXISTS trg_search_sessions_insert;
+CREATE TRIGGER trg_search_sessions_insert
+AFTER INSERT ON sessions
+BEGIN
+  INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+  VALUES ('session', NEW.id, NEW.id, NEW.project_hash, NULL, NEW.created_at);
+  INSERT INTO search_index (rowid, title, content, prompt)
+  SELECT id, NEW.name, COALESCE(NEW.task_description, ''), ''
+  FROM search_documents WHERE source_type = 'session' AND source_id = NEW.id;
+END;
+
+DROP TRIGGER IF EXISTS trg_search_sessions_update;
+CREATE TRIGGER trg_search_sessions_update
+AFTER UPDATE OF name, task_description, project_hash ON sessions
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE source_type = 'session' AND source_id = OLD.id
+  );
+  UPDATE search_documents SET project_hash = NEW.project_hash WHERE session_id = NEW.id;
+  INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+  VALUES ('session', NEW.id, NEW.id, NEW.project_hash, NULL, NEW.created_at)
+  ON CONFLICT(source_type, source_id) DO NOTHING;
+  INSERT INTO search_index (rowid, title, content, prompt)
+  SELECT id, NEW.name, COALESCE(NEW.task_description, ''), ''
+  FROM search_documents WHERE source_type = 'session' AND source_id = NEW.id;
+END;
+
+DROP TRIGGER IF EXISTS trg_search_sessions_delete;
+CREATE TRIGGER trg_search_sessions_delete
+AFTER DELETE ON sessions
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE session_id = OLD.id
+  );
+  DELETE FROM search_documents WHERE session_id = OLD.id;
+END;
+
+DROP TRIGGER IF EXISTS trg_search_task_history_insert;
+CREATE TRIGGER trg_search_task_history_insert
+AFTER INSERT ON task_description_history
+BEGIN
+  INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+  SELECT 'task_history', CAST(NEW.id AS TEXT), s.id, s.project_hash, NULL, NEW.created_at
+  FROM sessions s WHERE s.id = NEW.session_id
+  ON CONFLICT(source_type, source_id) DO NOTHING;
+  INSERT INTO search_index (rowid, title, content, prompt)
+  SELECT d.id, s.name, NEW.description, ''
+  FROM search_documents d JOIN sessions s ON s.id = d.session_id
+  WHERE d.source_type = 'task_history' AND d.source_id = CAST(NEW.id AS TEXT);
+END;
+
+DROP TRIGGER IF EXISTS trg_search_task_history_delete;
+CREATE TRIGGER trg_search_task_history_delete
+AFTER DELETE ON task_description_history
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE source_type = 'task_history' AND source_id = CAST(OLD.id AS TEXT)
+  );
+  DELETE FROM search_documents WHERE source_type = 'task_history' AND source_id = CAST(OLD.id AS TEXT);
+END;
+
+DROP TRIGGER IF EXISTS trg_search_background_jobs_delete;
+CREATE TRIGGER trg_search_background_jobs_delete
+AFTER DELETE ON background_jobs
+BEGIN
+  DELETE FROM search_index WHERE rowid IN (
+    SELECT id FROM search_documents WHERE source_type = 'job' AND source_id = OLD.id
+  );
+  DELETE FROM search_documents WHERE source_type = 'job' AND source_id = OLD.id;
+END;
+
+-- Backfill existing data (idempotent)
+INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+SELECT 'session', s.id, s.id, s.project_hash, NULL, s.created_at
+FROM sessions s WHERE true
+ON CONFLICT(source_type, source_id) DO NOTHING;
+
+INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+SELECT 'task_history', CAST(h.id AS TEXT), s.id, s.project_hash, NULL, h.created_at
+FROM task_description_history h JOIN sessions s ON s.id = h.session_id WHERE true
+ON CONFLICT(source_type, source_id) DO NOTHING;
+
+INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+SELECT 'job', j.id, s.id, s.project_hash, j.task_type, j.created_at
+FROM background_jobs j JOIN sessions s ON s.id = j.session_id
+WHERE j.status IN ('completed', 'completed_by_tag')
+ON CONFLICT(source_type, source_id) DO NOTHING;
+
+INSERT INTO search_index (rowid, title, content, prompt)
+SELECT d.id, s.name, COALESCE(s.task_description, ''), ''
+FROM search_documents d JOIN sessions s ON s.id = d.source_id
+WHERE d.source_type = 'session' AND d.id NOT IN (SELECT rowid FROM search_index);
+
+INSERT INTO search_index (rowid, title, content, prompt)
+SELECT d.id, s.name, h.description, ''
+FROM search_documents d
+JOIN task_description_history h ON CAST(h.id AS TEXT) = d.source_id
+JOIN sessions s ON s.id = d.session_id
+WHERE d.source_type = 'task_history' AND d.id NOT IN (SELECT rowid FROM search_index);
+
+INSERT INTO search_index (rowid, title, content, prompt)
+SELECT d.id, s.name, COALESCE(j.response, ''), j.prompt
+FROM search_documents d
+JOIN background_jobs j ON j.id = d.source_id
+JOIN sessions s ON s.id = d.session_id
+WHERE d.source_type = 'job' AND d.id NOT IN (SELECT rowid FROM search_index);
+
+-- Migration tracking is handled automatically by the migration system
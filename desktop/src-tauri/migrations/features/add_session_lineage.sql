@@ -0,0 +1,19 @@
+-- Add session lineage for forked sessions
+-- Each forked session records its parent, the root of its fork tree and the
+-- history indices it was forked at. Sessions that were never forked have no row.
+
+CREATE TABLE IF NOT EXISTS session_lineage (
+  session_id TEXT PRIMARY KEY,
+  parent_session_id TEXT DEFAULT NULL,
+  root_session_id TEXT NOT NULL,
+  forked_task_history_index INTEGER NOT NULL,
+  forked_file_history_index INTEGER NOT NULL,
+  created_at INTEGER NOT NULL,
+  FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE,
+  FOREIGN KEY (parent_session_id) REFERENCES sessions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_lineage_parent ON session_lineage(parent_session_id);
+CREATE INDEX IF NOT EXISTS idx_session_lineage_root ON session_lineage(root_session_id);
+
+-- Migration tracking is handled automatically by the migration system
@@ -0,0 +1,18 @@
+-- Add replicated task description operations
+-- Every character insert/delete of a session's task description is stored as a
+-- text CRDT operation so offline edits from several devices merge per character.
+-- (replica_id, counter) is the operation id; ordering by counter is causal order.
+
+CREATE TABLE IF NOT EXISTS task_description_ops (
+  session_id TEXT NOT NULL,
+  replica_id TEXT NOT NULL,
+  counter INTEGER NOT NULL,
+  op TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+  PRIMARY KEY (session_id, replica_id, counter),
+  FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_description_ops_session_counter ON task_description_ops(session_id, counter);
+
+-- Migration tracking is handled automatically by the migration system
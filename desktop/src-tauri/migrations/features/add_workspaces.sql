@@ -0,0 +1,22 @@
+-- Add multi-repository workspaces
+-- A workspace groups several project directories (e.g. backend, frontend and a shared schema
+-- repo) under short labels. Files outside the session's own repository are referenced as
+-- "<label>:<path relative to that repository>". A directory belongs to at most one workspace.
+
+CREATE TABLE IF NOT EXISTS workspaces (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS workspace_repositories (
+  workspace_id TEXT NOT NULL,
+  label TEXT NOT NULL,
+  project_directory TEXT NOT NULL UNIQUE,
+  position INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (workspace_id, label),
+  FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+-- Migration tracking is handled automatically by the migration system
@@ -0,0 +1,30 @@
+-- Allow refinement replies in plan revision history
+-- Replies to follow-up requests in a plan's conversation are recorded as 'refinement'
+-- revisions. SQLite cannot alter a CHECK constraint in place, so the table is rebuilt
+-- with its rows copied over.
+
+CREATE TABLE plan_revisions_new (
+  job_id TEXT NOT NULL,
+  revision INTEGER NOT NULL,
+  content TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  source TEXT NOT NULL CHECK (source IN ('model', 'edit', 'restore', 'refinement')),
+  author_device_id TEXT,
+  restored_from INTEGER,
+  signoff_state TEXT,
+  signed_off_at INTEGER,
+  created_at INTEGER NOT NULL,
+  PRIMARY KEY (job_id, revision),
+  FOREIGN KEY (job_id) REFERENCES background_jobs(id) ON DELETE CASCADE
+);
+
+INSERT INTO plan_revisions_new (job_id, revision, content, content_hash, source, author_device_id, restored_from, signoff_state, signed_off_at, created_at)
+SELECT job_id, revision, content, content_hash, source, author_device_id, restored_from, signoff_state, signed_off_at, created_at
+FROM plan_revisions;
+
+DROP TABLE plan_revisions;
+ALTER TABLE plan_revisions_new RENAME TO plan_revisions;
+
+CREATE INDEX IF NOT EXISTS idx_plan_revisions_signed_off ON plan_revisions(job_id, signed_off_at) WHERE signed_off_at IS NOT NULL;
+
+-- Migration tracking is handled automatically by the migration system
@@ -16,6 +16,8 @@ pub struct ApiClientOptions {
     pub stream: bool,
     pub request_id: Option<String>,
     pub task_type: Option<String>,
+    /// Allow the server to answer from its response cache (only honored at temperature 0)
+    pub response_cache: bool,
 }
 
 // Default implementation removed to force explicit model configuration
@@ -5,6 +5,7 @@ use crate::auth::token_manager::TokenManager;
 use crate::constants::ErrorType;
 use crate::error::AppError;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Check if an error message indicates token limit exceeded
 fn is_token_limit_error(message: &str) -> bool {
@@ -134,7 +135,7 @@ pub fn map_server_proxy_error(status_code: u16, response_text: &str) -> AppError
             "stripe_error" => AppError::StripeError(message),
             "billing_error" => AppError::BillingError(message),
             "rate_limit_error" => {
-                AppError::NetworkError(format!("Rate limit exceeded: {}", message))
+                AppError::rate_limited(format!("Rate limit exceeded: {}", message), None)
             }
             "provider_error" | "external_service_error" => {
                 AppError::ExternalServiceError(format!("AI provider error: {}", message))
@@ -147,9 +148,10 @@ pub fn map_server_proxy_error(status_code: u16, response_text: &str) -> AppError
             "configuration_error" => {
                 AppError::ConfigError(format!("Server configuration error: {}", message))
             }
-            "internal_error" => {
-                AppError::ServerProxyError(format!("Server internal error: {}", message))
-            }
+            "internal_error" => AppError::server_proxy_status(
+                status_code,
+                format!("Server internal error: {}", message),
+            ),
             error_type if error_type == ErrorType::SerializationError.as_str() => {
                 AppError::SerializationError(format!("Server serialization error: {}", message))
             }
@@ -177,15 +179,18 @@ pub fn map_server_proxy_error(status_code: u16, response_text: &str) -> AppError
             "connection_error" => AppError::NetworkError(format!("Connection error: {}", message)),
             _ => {
                 if let Some(code_val) = error_response.code {
-                    AppError::ServerProxyError(format!(
-                        "{} (HTTP Status: {}, Server Code: {})",
-                        message, status_code, code_val
-                    ))
+                    AppError::server_proxy_status(
+                        status_code,
+                        format!(
+                            "{} (HTTP Status: {}, Server Code: {})",
+                            message, status_code, code_val
+                        ),
+                    )
                 } else {
-                    AppError::ServerProxyError(format!(
-                        "{} (HTTP Status: {})",
-                        message, status_code
-                    ))
+                    AppError::server_proxy_status(
+                        status_code,
+                        format!("{} (HTTP Status: {})", message, status_code),
+                    )
                 }
             }
         }
@@ -204,16 +209,63 @@ pub fn map_server_proxy_error(status_code: u16, response_text: &str) -> AppError
             402 => AppError::BillingError(format!("Payment required: {}", response_text)),
             403 => AppError::AccessDenied(format!("Access denied: {}", response_text)),
             404 => AppError::NotFoundError(format!("Resource not found: {}", response_text)),
-            429 => AppError::ServerProxyError(format!("Rate limit exceeded: {}", response_text)),
-            500..=599 => AppError::ServerProxyError(format!("Server error: {}", response_text)),
-            _ => AppError::ServerProxyError(format!(
-                "Server proxy error ({}): {}",
-                status_code, response_text
-            )),
+            429 => AppError::rate_limited(format!("Rate limit exceeded: {}", response_text), None),
+            500..=599 => AppError::server_proxy_status(
+                status_code,
+                format!(
+                    "Server error (HTTP Status: {}): {}",
+                    status_code, response_text
+                ),
+            ),
+            _ => AppError::server_proxy_status(
+                status_code,
+                format!("Server proxy error ({}): {}", status_code, response_text),
+            ),
         }
     }
 }
 
+/// Read a Retry-After header. Accepts both the delta-seconds and the HTTP-date forms;
+/// dates in the past yield zero.
+pub fn retry_after_from_headers(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.timestamp() - chrono::Utc::now().timestamp();
+    Some(Duration::from_secs(delta.max(0) as u64))
+}
+
+/// Map a failed server proxy response to an AppError, carrying the Retry-After hint on
+/// rate-limit and unavailable responses so the job system can honor it
+pub fn map_server_proxy_error_with_retry_after(
+    status_code: u16,
+    response_text: &str,
+    retry_after: Option<Duration>,
+) -> AppError {
+    match map_server_proxy_error(status_code, response_text) {
+        AppError::TooManyRequests { message, .. }
+        | AppError::ServerProxyError {
+            message,
+            status: Some(429),
+            ..
+        } => AppError::rate_limited(message, retry_after),
+        AppError::ServerProxyError {
+            message, status, ..
+        } => AppError::ServerProxyError {
+            message,
+            status,
+            retry_after,
+        },
+        error => error,
+    }
+}
+
 /// Handle API errors for USER-INITIATED requests only
 ///
 /// IMPORTANT: This function clears the authentication token on 401 errors.
@@ -247,3 +299,31 @@ pub async fn handle_api_error(
         map_server_proxy_error(status_code, error_text)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorClass;
+
+    #[test]
+    fn retry_after_and_status_are_kept_as_fields() {
+        let retry_after = Some(Duration::from_secs(30));
+
+        let unavailable =
+            map_server_proxy_error_with_retry_after(503, "upstream down", retry_after);
+        assert_eq!(unavailable.http_status(), Some(503));
+        assert_eq!(unavailable.retry_after(), retry_after);
+        assert_eq!(unavailable.classify(), ErrorClass::ProviderOverloaded);
+
+        let body = r#"{"message":"slow down","error_type":"rate_limit_error"}"#;
+        let limited = map_server_proxy_error_with_retry_after(429, body, retry_after);
+        assert_eq!(limited.classify(), ErrorClass::RateLimited);
+        assert_eq!(limited.retry_after(), retry_after);
+
+        // Escaped or reformatted messages no longer matter for classification
+        let body = r#"{"message":"status 200 \"ok\"","error_type":"something_new"}"#;
+        let rejected = map_server_proxy_error_with_retry_after(422, body, None);
+        assert_eq!(rejected.http_status(), Some(422));
+        assert_eq!(rejected.classify(), ErrorClass::BadRequest);
+    }
+}
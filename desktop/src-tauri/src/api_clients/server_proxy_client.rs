@@ -10,7 +10,10 @@ use tauri::{AppHandle, Manager};
 use uuid;
 
 use super::client_trait::{ApiClient, ApiClientOptions, TranscriptionClient};
-use super::error_handling::{handle_api_error, map_server_proxy_error};
+use super::error_handling::{
+    handle_api_error, map_server_proxy_error, map_server_proxy_error_with_retry_after,
+    retry_after_from_headers,
+};
 use crate::auth::{TokenManager, header_utils};
 use crate::auth::token_refresh::{ensure_fresh_token, refresh_with_dedup, contains_device_binding_mismatch};
 use crate::constants::{APP_HTTP_REFERER, APP_X_TITLE, SERVER_API_URL};
@@ -208,7 +211,7 @@ impl ServerProxyClient {
 
         let prompts: Vec<crate::models::DefaultSystemPrompt> =
             response.json().await.map_err(|e| {
-                AppError::ServerProxyError(format!(
+                AppError::server_proxy(format!(
                     "Failed to parse default system prompts response: {}",
                     e
                 ))
@@ -259,7 +262,7 @@ impl ServerProxyClient {
         }
 
         let prompt: crate::models::DefaultSystemPrompt = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!(
+            AppError::server_proxy(format!(
                 "Failed to parse default system prompt response: {}",
                 e
             ))
@@ -336,7 +339,7 @@ impl ServerProxyClient {
         response
             .json::<R>()
             .await
-            .map_err(|e| AppError::ServerProxyError(format!("Failed to parse response: {}", e)))
+            .map_err(|e| AppError::server_proxy(format!("Failed to parse response: {}", e)))
     }
 
     /// Get MIME type from file extension
@@ -477,6 +480,7 @@ impl ServerProxyClient {
             duration_ms: Some(estimated_duration_ms),
             request_id: options.request_id.clone(),
             task_type: options.task_type.clone(),
+            response_cache: options.response_cache.then_some(true),
         };
 
         // Create the server proxy endpoint URL for LLM chat completions
@@ -517,6 +521,7 @@ impl ServerProxyClient {
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = retry_after_from_headers(response.headers());
             let error_text = response
                 .text()
                 .await
@@ -558,7 +563,7 @@ impl ServerProxyClient {
                 }
 
                 let server_response: OpenRouterResponse = retry_response.json().await.map_err(|e| {
-                    AppError::ServerProxyError(format!(
+                    AppError::server_proxy(format!(
                         "Failed to parse server proxy response: {}",
                         e
                     ))
@@ -571,12 +576,20 @@ impl ServerProxyClient {
                 return Ok(server_response);
             }
 
+            if status.as_u16() == 429 || status.is_server_error() {
+                return Err(map_server_proxy_error_with_retry_after(
+                    status.as_u16(),
+                    &error_text,
+                    retry_after,
+                ));
+            }
+
             // Use enhanced auth error handling for other errors
             return Err(self.handle_auth_error(status.as_u16(), &error_text).await);
         }
 
         let server_response: OpenRouterResponse = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!("Failed to parse server proxy response: {}", e))
+            AppError::server_proxy(format!("Failed to parse server proxy response: {}", e))
         })?;
 
         trace!(
@@ -649,6 +662,7 @@ impl ServerProxyClient {
             duration_ms: Some(estimated_duration_ms),
             request_id: options.request_id.clone(),
             task_type: options.task_type.clone(),
+            response_cache: options.response_cache.then_some(true),
         };
 
         // Create the server proxy endpoint URL for streaming LLM chat completions
@@ -998,7 +1012,7 @@ impl ServerProxyClient {
                                 reqwest_eventsource::Error::Transport(e) => {
                                     AppError::HttpError(format!("Transport error: {}", e))
                                 }
-                                reqwest_eventsource::Error::InvalidStatusCode(code, _response) => {
+                                reqwest_eventsource::Error::InvalidStatusCode(code, response) => {
                                     if code == 401 {
                                         // Token expired during streaming - attempt refresh for next request
                                         warn!("SSE stream received 401 Unauthorized - attempting token refresh");
@@ -1014,8 +1028,22 @@ impl ServerProxyClient {
                                                 "Stream authentication failed - token has been refreshed, please retry".to_string()
                                             )
                                         }
+                                    } else if code == 429 || code.is_server_error() {
+                                        // Keep the Retry-After hint so the job retry policy and
+                                        // circuit breaker can back off as the server asked
+                                        let retry_after =
+                                            retry_after_from_headers(response.headers());
+                                        let body = response.text().await.unwrap_or_default();
+                                        map_server_proxy_error_with_retry_after(
+                                            code.as_u16(),
+                                            &body,
+                                            retry_after,
+                                        )
                                     } else {
-                                        AppError::HttpError(format!("Invalid status code: {}", code))
+                                        AppError::server_proxy_status(
+                                            code.as_u16(),
+                                            format!("Invalid status code: {}", code),
+                                        )
                                     }
                                 }
                                 reqwest_eventsource::Error::InvalidContentType(mime, _response) => {
@@ -1095,7 +1123,7 @@ impl ServerProxyClient {
         }
 
         let cost_response: serde_json::Value = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!("Failed to parse cost estimation response: {}", e))
+            AppError::server_proxy(format!("Failed to parse cost estimation response: {}", e))
         })?;
 
         info!("Cost estimation through server proxy successful");
@@ -1149,7 +1177,7 @@ impl ServerProxyClient {
         }
 
         let cost_response: serde_json::Value = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!(
+            AppError::server_proxy(format!(
                 "Failed to parse batch cost estimation response: {}",
                 e
             ))
@@ -1184,7 +1212,7 @@ impl ServerProxyClient {
         }
 
         let user_info: crate::models::FrontendUser = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!("Failed to parse user info response: {}", e))
+            AppError::server_proxy(format!("Failed to parse user info response: {}", e))
         })?;
 
         info!("Successfully fetched user info for: {}", user_info.email);
@@ -1262,7 +1290,7 @@ impl ServerProxyClient {
         }
 
         let cancel_response: serde_json::Value = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!("Failed to parse cancel response: {}", e))
+            AppError::server_proxy(format!("Failed to parse cancel response: {}", e))
         })?;
 
         if cancel_response
@@ -1358,7 +1386,7 @@ impl ServerProxyClient {
 
         // Parse the response
         let analysis_response: VideoAnalysisResponse = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!("Failed to parse video analysis response: {}", e))
+            AppError::server_proxy(format!("Failed to parse video analysis response: {}", e))
         })?;
 
         info!("Video analysis through server proxy successful");
@@ -1430,7 +1458,7 @@ impl TranscriptionClient for ServerProxyClient {
 
         // Parse the response
         let transcription_response: serde_json::Value = response.json().await.map_err(|e| {
-            AppError::ServerProxyError(format!("Failed to parse transcription response: {}", e))
+            AppError::server_proxy(format!("Failed to parse transcription response: {}", e))
         })?;
 
         let text = transcription_response["text"]
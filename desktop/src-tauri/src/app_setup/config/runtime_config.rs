@@ -111,6 +111,8 @@ fn validate_task_type_configurations(runtime_config: &RuntimeAIConfig) -> Result
     let required_task_types = [
         TaskType::ImplementationPlan,
         TaskType::ImplementationPlanMerge,
+        TaskType::ImplementationPlanRefinement,
+        TaskType::PlanTestGeneration,
         TaskType::VoiceTranscription,
         TaskType::TextImprovement,
         TaskType::TaskRefinement,
@@ -120,6 +122,7 @@ fn validate_task_type_configurations(runtime_config: &RuntimeAIConfig) -> Result
         TaskType::RootFolderSelection,
         TaskType::FileRelevanceAssessment,
         TaskType::ExtendedPathFinder,
+        TaskType::ImpactAnalysis,
         TaskType::WebSearchPromptsGeneration,
         TaskType::WebSearchExecution,
         TaskType::VideoAnalysis,
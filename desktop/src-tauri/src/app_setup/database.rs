@@ -270,19 +270,6 @@ pub async fn run_deferred_db_tasks(
                 ))
             })?;
             attempt_automatic_recovery(app_handle, &app_data_dir).await?;
-
-            // Re-check after recovery
-            match check_database_health(&*pool_arc).await {
-                Ok(true) => {
-                    info!("Deferred DB: quick check passed after recovery");
-                }
-                _ => {
-                    error!("Deferred DB: quick check still failed after recovery attempt");
-                    return Err(crate::error::AppError::DatabaseError(
-                        "Database quick check failed even after recovery".to_string(),
-                    ));
-                }
-            }
         }
         Err(e) => {
             error!("Deferred DB: quick check error: {}", e);
@@ -448,6 +435,15 @@ pub async fn run_deferred_db_tasks(
         info!("Deferred DB: TerminalRepository skipped (already managed)");
     }
 
+    if app_handle
+        .try_state::<Arc<crate::db_utils::WorkspaceRepository>>()
+        .is_none()
+    {
+        let workspace_repo = crate::db_utils::WorkspaceRepository::new(pool_arc.clone());
+        app_handle.manage(Arc::new(workspace_repo));
+        info!("Deferred DB: WorkspaceRepository wired.");
+    }
+
     Ok(())
 }
 
@@ -489,11 +485,12 @@ async fn attempt_automatic_recovery(
 
     match backup_service.auto_restore_latest_backup().await {
         Ok(Some(restored_backup)) => {
+            // Restoring closed the shared pool; restart to open the restored database
             info!(
-                "Successfully restored database from backup: {}",
+                "Successfully restored database from backup: {}, restarting",
                 restored_backup
             );
-            Ok(())
+            app_handle.restart()
         }
         Ok(None) => {
             warn!("No valid backups found for automatic recovery");
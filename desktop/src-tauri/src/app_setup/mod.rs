@@ -80,6 +80,10 @@ async fn run_deferred_initialization(app_handle: &AppHandle) -> Result<(), AppEr
         }
     });
 
+    // Track implementation progress of signed-off plans against git
+    crate::services::plan_progress::start_plan_progress_checker(app_handle);
+    info!("Plan progress checker started");
+
     // Terminal manager is now initialized in critical phase (skipped here)
 
     // Sync early in-memory values to DB if not already present
@@ -161,6 +165,9 @@ pub async fn run_critical_initialization(app_handle: &AppHandle) -> Result<(), A
     crate::app_setup::services::initialize_history_state_sequencer(app_handle).await?;
     info!("HistoryStateSequencer initialized");
 
+    // Start integration hooks before the job system so no job events are missed
+    crate::app_setup::services::initialize_integration_hooks(app_handle).await?;
+
     // System prompts initialization is deferred (requires API client)
 
     // Initialize file lock manager (moved to critical path)
@@ -241,6 +248,11 @@ pub async fn run_background_initialization(app_handle: AppHandle) -> Result<(),
         }
     }
 
+    // Local editor API only needs the database, but must not delay the UI
+    if let Err(e) = services::initialize_local_api(&app_handle).await {
+        warn!("Local API initialization failed: {}", e);
+    }
+
     // Initialize device link connection after API clients are ready
     if let Err(e) = crate::app_setup::services::initialize_device_link_connection(&app_handle).await
     {
@@ -532,6 +532,43 @@ pub async fn initialize_session_cache(app_handle: &tauri::AppHandle) -> crate::e
     Ok(())
 }
 
+/// Manage the local editor API server and start it if enabled in settings
+pub async fn initialize_local_api(app_handle: &AppHandle) -> AppResult<()> {
+    use crate::remote_api::local_server::LocalApiServer;
+
+    let server = Arc::new(LocalApiServer::new(app_handle.clone()));
+    app_handle.manage(server.clone());
+
+    let settings_repo = app_handle.state::<Arc<crate::db_utils::SettingsRepository>>();
+    let config = settings_repo.get_local_api_config().await.unwrap_or_else(|e| {
+        warn!("Failed to load local API config: {}, leaving it disabled", e);
+        Default::default()
+    });
+    if config.enabled {
+        server.start(config.port).await?;
+    }
+    Ok(())
+}
+
+/// Manage the integration hook repository and start delivering bus events to hooks
+pub async fn initialize_integration_hooks(app_handle: &AppHandle) -> AppResult<()> {
+    let pool = app_handle.state::<Arc<SqlitePool>>().inner().clone();
+    let repo = Arc::new(crate::db_utils::IntegrationHookRepository::new(pool));
+    app_handle.manage(repo.clone());
+
+    // Best-effort prune of the delivery log (30 days)
+    let repo_for_prune = repo.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = repo_for_prune.prune_deliveries_older_than_days(30).await {
+            warn!("Integration hook delivery log prune failed: {}", e);
+        }
+    });
+
+    crate::services::integration_hooks::start_hook_dispatcher(app_handle, repo);
+    info!("Integration hook dispatcher started");
+    Ok(())
+}
+
 pub async fn initialize_history_state_sequencer(app_handle: &tauri::AppHandle) -> crate::error::AppResult<()> {
     use crate::services::history_state_sequencer::HistoryStateSequencer;
     use crate::db_utils::session_repository::SessionRepository;
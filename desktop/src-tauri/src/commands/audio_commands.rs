@@ -202,7 +202,7 @@ async fn transcribe_with_extended_params(
 
     // Parse the response
     let transcription_response: serde_json::Value = response.json().await.map_err(|e| {
-        crate::error::AppError::ServerProxyError(format!(
+        crate::error::AppError::server_proxy(format!(
             "Failed to parse transcription response: {}",
             e
         ))
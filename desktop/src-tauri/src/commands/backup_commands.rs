@@ -1,9 +1,10 @@
+use crate::db_utils::SettingsRepository;
 use crate::error::AppResult;
-use crate::services::{BackupInfo, BackupService, BackupStats};
+use crate::services::{BackupConfig, BackupInfo, BackupService, BackupStats};
 use log::{error, info, warn};
 use std::path::Path;
 use std::sync::Arc;
-use tauri::{AppHandle, State, command};
+use tauri::{AppHandle, Manager, State, command};
 
 /// Get statistics about the backup system
 #[tauri::command]
@@ -211,3 +212,112 @@ pub async fn delete_backup_command(
         }
     }
 }
+
+/// Export a backup as a portable archive for another machine.
+/// Without `backup_filename` a fresh snapshot of the current database is exported.
+#[tauri::command]
+pub async fn export_backup_command(
+    backup_service: State<'_, Arc<BackupService>>,
+    backup_filename: Option<String>,
+    destination_path: String,
+    passphrase: Option<String>,
+) -> AppResult<serde_json::Value> {
+    info!("[BackupCommands] Exporting backup to: {}", destination_path);
+
+    let backup_path = match backup_filename.as_deref() {
+        Some(filename) => {
+            let backups = backup_service.get_backup_list().await?;
+            let backup_info = backups
+                .into_iter()
+                .find(|b| b.filename == filename)
+                .ok_or_else(|| {
+                    crate::error::AppError::NotFoundError(format!(
+                        "Backup file not found: {}",
+                        filename
+                    ))
+                })?;
+            Some(std::path::PathBuf::from(backup_info.full_path))
+        }
+        None => None,
+    };
+
+    let exported = backup_service
+        .export_backup(
+            backup_path.as_deref(),
+            Path::new(&destination_path),
+            passphrase.as_deref(),
+        )
+        .await
+        .inspect_err(|e| error!("[BackupCommands] Failed to export backup: {}", e))?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "exportPath": exported.to_string_lossy(),
+        "encrypted": passphrase.as_deref().is_some_and(|p| !p.is_empty()),
+        "message": "Backup exported successfully."
+    }))
+}
+
+/// Import a backup exported on another machine into the local backup directory
+#[tauri::command]
+pub async fn import_backup_command(
+    backup_service: State<'_, Arc<BackupService>>,
+    source_path: String,
+    passphrase: Option<String>,
+) -> AppResult<serde_json::Value> {
+    info!("[BackupCommands] Importing backup from: {}", source_path);
+
+    let imported = backup_service
+        .import_backup(Path::new(&source_path), passphrase.as_deref())
+        .await
+        .inspect_err(|e| error!("[BackupCommands] Failed to import backup: {}", e))?;
+
+    let filename = imported
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown");
+
+    Ok(serde_json::json!({
+        "success": true,
+        "backupFile": filename,
+        "message": "Backup imported and verified. It can now be restored."
+    }))
+}
+
+/// Get the backup configuration
+#[tauri::command]
+pub async fn get_backup_config_command(
+    backup_service: State<'_, Arc<BackupService>>,
+) -> AppResult<BackupConfig> {
+    Ok(backup_service.config())
+}
+
+/// Update and persist the backup configuration
+#[tauri::command]
+pub async fn update_backup_config_command(
+    app_handle: AppHandle,
+    backup_service: State<'_, Arc<BackupService>>,
+    config: BackupConfig,
+) -> AppResult<BackupConfig> {
+    if config.max_backup_count == 0 || config.backup_interval_minutes == 0 {
+        return Err(crate::error::AppError::ValidationError(
+            "Backup interval and retention count must be greater than zero".to_string(),
+        ));
+    }
+
+    // Create the keyring entry now so a keyring problem surfaces here rather
+    // than in the next scheduled backup
+    if config.encrypt {
+        crate::services::backup_archive::load_or_create_local_key()?;
+    }
+
+    let settings_repo = app_handle.state::<Arc<SettingsRepository>>();
+    settings_repo.save_backup_config(&config).await?;
+    backup_service.update_config(config.clone());
+
+    info!(
+        "[BackupCommands] Backup config updated (compress: {}, encrypt: {})",
+        config.compress, config.encrypt
+    );
+    Ok(config)
+}
@@ -1,10 +1,12 @@
+use crate::db_utils::background_job_repository::{PlanRevision, PlanRevisionSummary};
 use crate::db_utils::{BackgroundJobRepository, SessionRepository, SettingsRepository};
 use crate::error::{AppError, AppResult};
+use crate::jobs::processors::utils::path_resolution_utils::resolve_pipeline_path;
 use crate::jobs::types::{ImplementationPlanMergePayload, JobPayload};
 use crate::models::BackgroundJob;
 use crate::models::JobCommandResponse;
-use crate::models::JobStatus;
 use crate::models::TaskType;
+use crate::services::plan_revisions::{self, PlanRevisionDiff};
 use crate::utils::get_timestamp;
 use crate::utils::unified_prompt_system::{
     ComposedPrompt as UnifiedComposedPrompt, UnifiedPromptContextBuilder, UnifiedPromptProcessor,
@@ -12,7 +14,6 @@ use crate::utils::unified_prompt_system::{
 use futures::future::join_all;
 use log::info;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager, command};
 use uuid::Uuid;
@@ -236,12 +237,21 @@ pub async fn estimate_prompt_tokens_command(
             project_directory.clone()
         }
     };
+    let workspace = crate::services::workspaces::workspace_layout_for_job(
+        &app_handle,
+        &actual_project_directory,
+    )
+    .await;
 
     // Read file contents for relevant files - using parallel direct file reading to avoid lock contention
     let file_futures: Vec<_> = relevant_files
         .iter()
         .map(|relative_path_str| {
-            let full_path = std::path::Path::new(&actual_project_directory).join(relative_path_str);
+            let full_path = resolve_pipeline_path(
+                relative_path_str,
+                &actual_project_directory,
+                workspace.as_ref(),
+            );
             let relative_path_clone = relative_path_str.clone();
             async move {
                 let content_result = tokio::fs::read_to_string(&full_path).await;
@@ -283,8 +293,9 @@ pub async fn estimate_prompt_tokens_command(
                         e
                     );
                     // Fallback to full directory tree
-                    match crate::utils::directory_tree::get_directory_tree_with_defaults(
+                    match crate::utils::directory_tree::get_project_directory_tree(
                         &actual_project_directory,
+                        workspace.as_ref(),
                     )
                     .await
                     {
@@ -298,8 +309,9 @@ pub async fn estimate_prompt_tokens_command(
             }
         } else {
             // Empty root directories - use full tree
-            match crate::utils::directory_tree::get_directory_tree_with_defaults(
+            match crate::utils::directory_tree::get_project_directory_tree(
                 &actual_project_directory,
+                workspace.as_ref(),
             )
             .await
             {
@@ -315,8 +327,9 @@ pub async fn estimate_prompt_tokens_command(
         }
     } else {
         // No root directories specified - use full tree
-        match crate::utils::directory_tree::get_directory_tree_with_defaults(
+        match crate::utils::directory_tree::get_project_directory_tree(
             &actual_project_directory,
+            workspace.as_ref(),
         )
         .await
         {
@@ -422,6 +435,11 @@ pub async fn get_prompt_command(
             project_directory.clone()
         }
     };
+    let workspace = crate::services::workspaces::workspace_layout_for_job(
+        &app_handle,
+        &actual_project_directory,
+    )
+    .await;
 
     // Parse task_type string into TaskType enum using the FromStr implementation
     let parsed_task_type = task_type
@@ -432,7 +450,11 @@ pub async fn get_prompt_command(
     let file_futures: Vec<_> = relevant_files
         .iter()
         .map(|relative_path_str| {
-            let full_path = std::path::Path::new(&actual_project_directory).join(relative_path_str);
+            let full_path = resolve_pipeline_path(
+                relative_path_str,
+                &actual_project_directory,
+                workspace.as_ref(),
+            );
             let relative_path_clone = relative_path_str.clone();
             async move {
                 let content_result = tokio::fs::read_to_string(&full_path).await;
@@ -468,8 +490,9 @@ pub async fn get_prompt_command(
                         e
                     );
                     // Fallback to full directory tree
-                    match crate::utils::directory_tree::get_directory_tree_with_defaults(
+                    match crate::utils::directory_tree::get_project_directory_tree(
                         &actual_project_directory,
+                        workspace.as_ref(),
                     )
                     .await
                     {
@@ -483,8 +506,9 @@ pub async fn get_prompt_command(
             }
         } else {
             // Empty root directories - use full tree
-            match crate::utils::directory_tree::get_directory_tree_with_defaults(
+            match crate::utils::directory_tree::get_project_directory_tree(
                 &actual_project_directory,
+                workspace.as_ref(),
             )
             .await
             {
@@ -500,8 +524,9 @@ pub async fn get_prompt_command(
         }
     } else {
         // No root directories specified - use full tree
-        match crate::utils::directory_tree::get_directory_tree_with_defaults(
+        match crate::utils::directory_tree::get_project_directory_tree(
             &actual_project_directory,
+            workspace.as_ref(),
         )
         .await
         {
@@ -622,112 +647,67 @@ pub struct UpdateImplementationPlanContentArgs {
     pub new_content: String,
 }
 
-/// Updates the content of an implementation plan
+/// Updates the content of an implementation plan, keeping the previous content as a revision
 #[command]
 pub async fn update_implementation_plan_content_command(
     job_id: String,
     new_content: String,
     app_handle: AppHandle,
-) -> AppResult<()> {
+) -> AppResult<PlanRevisionSummary> {
     info!("Updating implementation plan content for job: {}", job_id);
-
-    // Validate required fields
-    if job_id.is_empty() {
-        return Err(AppError::ValidationError("Job ID is required".to_string()));
-    }
-
-    // Get the background job repository
-    let repo = app_handle
-        .state::<Arc<BackgroundJobRepository>>()
-        .inner()
-        .clone();
-
-    // Get the job from the database
-    let job = repo
-        .get_job_by_id(&job_id)
-        .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to get job: {}", e)))?
-        .ok_or_else(|| AppError::NotFoundError(format!("Job not found: {}", job_id)))?;
-
-    // Verify job type
-    if job.task_type != "implementation_plan" && job.task_type != "implementation_plan_merge" {
-        return Err(AppError::ValidationError(format!(
-            "Job is not an implementation plan: {}",
-            job_id
-        )));
-    }
-
-    repo.update_job_response(&job_id, &new_content, None, None, None, None, None)
-        .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to update job response: {}", e)))?;
-
-    app_handle.emit(
-        "device-link-event",
-        serde_json::json!({
-            "type": "PlanModified",
-            "payload": { "jobId": job_id }
-        })
-    ).ok();
-
-    crate::remote_api::handlers::jobs::invalidate_job_list_for_session(&app_handle, &job.session_id);
-
-    // Resolve project_hash from session and invalidate project cache
-    let session_repo = SessionRepository::new(repo.get_pool());
-    if let Ok(Some(session)) = session_repo.get_session_by_id(&job.session_id).await {
-        crate::remote_api::handlers::jobs::invalidate_job_list_for_project(&app_handle, &session.project_hash);
-    }
-
-    info!(
-        "Successfully updated implementation plan content for job: {}",
-        job_id
-    );
-    Ok(())
+    plan_revisions::edit_plan(&app_handle, &job_id, &new_content).await
 }
 
-/// Marks an implementation plan as signed off by the user
+/// Marks an implementation plan revision as signed off by the user. Without `revision`
+/// the plan's current revision is signed off.
 #[command]
 pub async fn mark_implementation_plan_signed_off_command(
     app_handle: AppHandle,
     job_id: String,
     state: Option<String>,
-) -> AppResult<()> {
-    use chrono::Utc;
-
-    let repo = app_handle
-        .state::<Arc<BackgroundJobRepository>>()
-        .inner()
-        .clone();
-
-    let job = repo
-        .get_job_by_id(&job_id)
-        .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to get job: {}", e)))?
-        .ok_or_else(|| AppError::NotFoundError(format!("Job not found: {}", job_id)))?;
-
-    let mut meta: serde_json::Value = if let Some(metadata_str) = job.metadata.as_ref() {
-        serde_json::from_str(metadata_str).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-
+    revision: Option<i64>,
+) -> AppResult<PlanRevisionSummary> {
     let state_value = state.unwrap_or_else(|| "accepted".to_string());
+    plan_revisions::sign_off_plan(&app_handle, &job_id, revision, &state_value).await
+}
 
-    let sign = serde_json::json!({
-        "state": state_value,
-        "timestamp": Utc::now().to_rfc3339(),
-    });
-    meta["userSignoff"] = sign;
-
-    let metadata_str = serde_json::to_string(&meta)
-        .map_err(|e| AppError::ValidationError(format!("Failed to serialize metadata: {}", e)))?;
+/// Lists every revision of an implementation plan, oldest first
+#[command]
+pub async fn list_plan_revisions_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<Vec<PlanRevisionSummary>> {
+    plan_revisions::list_plan_revisions(&app_handle, &job_id).await
+}
 
-    repo.update_job_status_with_metadata(&job_id, &JobStatus::from_str(&job.status).unwrap_or(JobStatus::Completed), None, metadata_str)
-        .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to update job metadata: {}", e)))?;
+#[command]
+pub async fn get_plan_revision_command(
+    app_handle: AppHandle,
+    job_id: String,
+    revision: i64,
+) -> AppResult<PlanRevision> {
+    plan_revisions::get_plan_revision(&app_handle, &job_id, revision).await
+}
 
-    info!("Marked implementation plan {} as signed off with state: {}", job_id, state_value);
+/// Unified diff between any two revisions of an implementation plan
+#[command]
+pub async fn diff_plan_revisions_command(
+    app_handle: AppHandle,
+    job_id: String,
+    from_revision: i64,
+    to_revision: i64,
+) -> AppResult<PlanRevisionDiff> {
+    plan_revisions::diff_plan_revisions(&app_handle, &job_id, from_revision, to_revision).await
+}
 
-    Ok(())
+/// Makes an earlier revision the plan's current content again
+#[command]
+pub async fn restore_plan_revision_command(
+    app_handle: AppHandle,
+    job_id: String,
+    revision: i64,
+) -> AppResult<PlanRevisionSummary> {
+    plan_revisions::restore_plan_revision(&app_handle, &job_id, revision).await
 }
 
 #[derive(Debug, Serialize)]
@@ -735,6 +715,7 @@ pub async fn mark_implementation_plan_signed_off_command(
 pub struct PlanMarkdownResponse {
     pub job_id: String,
     pub xml_content: String,
+    /// The plan followed by its test plan, when one was generated
     pub markdown: String,
 }
 
@@ -804,6 +785,7 @@ pub async fn generate_plan_markdown_command(
             &xml_content,
         )
         .await?;
+    let markdown = crate::services::plan_test_plans::append_test_plan_markdown(markdown, &job);
 
     let completed_patch = serde_json::json!({
         "markdownResponse": markdown.clone(),
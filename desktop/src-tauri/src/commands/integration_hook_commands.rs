@@ -0,0 +1,74 @@
+use crate::db_utils::IntegrationHookRepository;
+use crate::db_utils::integration_hook_repository::{HookDelivery, IntegrationHook};
+use crate::error::AppResult;
+use crate::events::AppEventKind;
+use crate::services::integration_hooks::{self, DeliveryOutcome};
+use log::info;
+use std::sync::Arc;
+use tauri::State;
+
+const DEFAULT_DELIVERY_LIMIT: u32 = 100;
+
+/// List all configured integration hooks
+#[tauri::command]
+pub async fn list_integration_hooks_command(
+    repo: State<'_, Arc<IntegrationHookRepository>>,
+) -> AppResult<Vec<IntegrationHook>> {
+    repo.list_hooks().await
+}
+
+/// Event types hooks can subscribe to
+#[tauri::command]
+pub async fn list_integration_hook_event_types_command() -> AppResult<Vec<String>> {
+    Ok(AppEventKind::ALL
+        .iter()
+        .map(|kind| kind.as_str().to_string())
+        .collect())
+}
+
+/// Create a hook, or update it when the id already exists. An empty id creates a new hook.
+#[tauri::command]
+pub async fn save_integration_hook_command(
+    repo: State<'_, Arc<IntegrationHookRepository>>,
+    mut hook: IntegrationHook,
+) -> AppResult<IntegrationHook> {
+    if hook.id.trim().is_empty() {
+        hook.id = uuid::Uuid::new_v4().to_string();
+    }
+    info!(
+        "[IntegrationHookCommands] Saving hook '{}' ({})",
+        hook.name, hook.id
+    );
+    repo.save_hook(&hook).await
+}
+
+/// Delete a hook and its delivery log
+#[tauri::command]
+pub async fn delete_integration_hook_command(
+    repo: State<'_, Arc<IntegrationHookRepository>>,
+    hook_id: String,
+) -> AppResult<bool> {
+    info!("[IntegrationHookCommands] Deleting hook {}", hook_id);
+    repo.delete_hook(&hook_id).await
+}
+
+/// Recent delivery attempts, newest first, optionally for a single hook
+#[tauri::command]
+pub async fn list_integration_hook_deliveries_command(
+    repo: State<'_, Arc<IntegrationHookRepository>>,
+    hook_id: Option<String>,
+    limit: Option<u32>,
+) -> AppResult<Vec<HookDelivery>> {
+    repo.list_deliveries(hook_id.as_deref(), limit.unwrap_or(DEFAULT_DELIVERY_LIMIT))
+        .await
+}
+
+/// Send a test event to a hook once and report the outcome
+#[tauri::command]
+pub async fn test_integration_hook_command(
+    repo: State<'_, Arc<IntegrationHookRepository>>,
+    hook_id: String,
+) -> AppResult<DeliveryOutcome> {
+    info!("[IntegrationHookCommands] Testing hook {}", hook_id);
+    integration_hooks::send_test_event(&repo, &hook_id).await
+}
@@ -230,3 +230,48 @@ pub async fn get_all_visible_jobs_command_with_content(
 
     Ok(jobs)
 }
+
+/// Every job a job was derived from and every job derived from it
+#[command]
+pub async fn get_job_provenance_command(
+    job_id: String,
+    app_handle: AppHandle,
+) -> AppResult<crate::db_utils::background_job_repository::JobDerivationGraph> {
+    let repo = app_handle
+        .state::<Arc<crate::db_utils::BackgroundJobRepository>>()
+        .inner()
+        .clone();
+
+    repo.get_job_derivation_graph(&job_id).await
+}
+
+/// Re-run a job with changed inputs, then every job derived from it
+#[command]
+pub async fn rerun_job_from_here_command(
+    job_id: String,
+    overrides: Option<crate::services::job_provenance::ReplayOverrides>,
+    app_handle: AppHandle,
+) -> AppResult<crate::services::job_provenance::ReplayStarted> {
+    info!("Re-running job {} and its derived jobs", job_id);
+
+    crate::services::job_provenance::start_replay(
+        &app_handle,
+        &job_id,
+        overrides.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Original and re-run jobs of a replay side by side
+#[command]
+pub async fn get_job_replay_comparison_command(
+    replay_id: String,
+    app_handle: AppHandle,
+) -> AppResult<crate::services::job_provenance::ReplayComparison> {
+    let repo = app_handle
+        .state::<Arc<crate::db_utils::BackgroundJobRepository>>()
+        .inner()
+        .clone();
+
+    crate::services::job_provenance::get_replay_comparison(&repo, &replay_id).await
+}
@@ -0,0 +1,132 @@
+use crate::db_utils::SettingsRepository;
+use crate::error::{AppError, AppResult};
+use crate::jobs::circuit_breaker::{self, CircuitBreakerStatus, get_job_circuit_breaker};
+use crate::jobs::retry_utils::RetryPolicy;
+use crate::models::TaskType;
+use log::info;
+use serde::Serialize;
+use std::str::FromStr;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+/// Task types that run as queued jobs and therefore go through the retry policy
+const JOB_TASK_TYPES: [TaskType; 16] = [
+    TaskType::ImplementationPlan,
+    TaskType::ImplementationPlanMerge,
+    TaskType::ImplementationPlanRefinement,
+    TaskType::PlanTestGeneration,
+    TaskType::VoiceTranscription,
+    TaskType::TextImprovement,
+    TaskType::TaskRefinement,
+    TaskType::GenericLlmStream,
+    TaskType::RegexFileFilter,
+    TaskType::RootFolderSelection,
+    TaskType::FileRelevanceAssessment,
+    TaskType::ExtendedPathFinder,
+    TaskType::ImpactAnalysis,
+    TaskType::WebSearchPromptsGeneration,
+    TaskType::WebSearchExecution,
+    TaskType::VideoAnalysis,
+];
+
+/// Retry policy in effect for one task type
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRetryPolicy {
+    pub task_type: String,
+    pub policy: RetryPolicy,
+    /// True when the user overrode the built-in default
+    pub customized: bool,
+}
+
+/// Effective retry policy for every job task type
+#[tauri::command]
+pub async fn get_job_retry_policies_command(
+    settings_repo: State<'_, Arc<SettingsRepository>>,
+) -> AppResult<Vec<TaskRetryPolicy>> {
+    let overrides = settings_repo.get_job_retry_policies().await?;
+    Ok(JOB_TASK_TYPES
+        .iter()
+        .map(|task_type| {
+            let key = task_type.to_string();
+            match overrides.get(&key) {
+                Some(policy) => TaskRetryPolicy {
+                    task_type: key,
+                    policy: policy.clone(),
+                    customized: true,
+                },
+                None => TaskRetryPolicy {
+                    task_type: key,
+                    policy: RetryPolicy::default_for_task_type(task_type),
+                    customized: false,
+                },
+            }
+        })
+        .collect())
+}
+
+/// Override the retry policy for a task type, or restore the default when `policy` is null
+#[tauri::command]
+pub async fn update_job_retry_policy_command(
+    settings_repo: State<'_, Arc<SettingsRepository>>,
+    task_type: String,
+    policy: Option<RetryPolicy>,
+) -> AppResult<TaskRetryPolicy> {
+    let parsed = TaskType::from_str(&task_type).map_err(AppError::ValidationError)?;
+    if !JOB_TASK_TYPES.contains(&parsed) {
+        return Err(AppError::ValidationError(format!(
+            "Task type '{}' does not run as a job",
+            task_type
+        )));
+    }
+
+    let mut overrides = settings_repo.get_job_retry_policies().await?;
+    let result = match policy {
+        Some(policy) => {
+            policy.validate()?;
+            info!(
+                "[JobRetryCommands] Setting retry policy for {}: {:?}",
+                task_type, policy
+            );
+            overrides.insert(task_type.clone(), policy.clone());
+            TaskRetryPolicy {
+                task_type,
+                policy,
+                customized: true,
+            }
+        }
+        None => {
+            info!(
+                "[JobRetryCommands] Restoring default retry policy for {}",
+                task_type
+            );
+            overrides.remove(&task_type);
+            TaskRetryPolicy {
+                task_type,
+                policy: RetryPolicy::default_for_task_type(&parsed),
+                customized: false,
+            }
+        }
+    };
+
+    settings_repo.save_job_retry_policies(&overrides).await?;
+    Ok(result)
+}
+
+/// Whether job dequeuing is currently paused by the circuit breaker
+#[tauri::command]
+pub async fn get_job_circuit_breaker_status_command() -> AppResult<CircuitBreakerStatus> {
+    Ok(get_job_circuit_breaker().status())
+}
+
+/// Resume job dequeuing immediately instead of waiting out the cooldown
+#[tauri::command]
+pub async fn reset_job_circuit_breaker_command(
+    app_handle: AppHandle,
+) -> AppResult<CircuitBreakerStatus> {
+    if get_job_circuit_breaker().reset().is_some() {
+        info!("[JobRetryCommands] Job circuit breaker reset by user");
+        circuit_breaker::emit_circuit_breaker_changed(&app_handle);
+    }
+    Ok(get_job_circuit_breaker().status())
+}
@@ -0,0 +1,47 @@
+use crate::db_utils::SettingsRepository;
+use crate::error::AppResult;
+use crate::remote_api::local_server::{LocalApiConfig, LocalApiServer, LocalApiStatus};
+use log::info;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get the saved local API settings
+#[tauri::command]
+pub async fn get_local_api_config_command(
+    settings_repo: State<'_, Arc<SettingsRepository>>,
+) -> AppResult<LocalApiConfig> {
+    settings_repo.get_local_api_config().await
+}
+
+/// Save local API settings and start, restart or stop the server to match
+#[tauri::command]
+pub async fn update_local_api_config_command(
+    settings_repo: State<'_, Arc<SettingsRepository>>,
+    server: State<'_, Arc<LocalApiServer>>,
+    config: LocalApiConfig,
+) -> AppResult<LocalApiStatus> {
+    info!(
+        "[LocalApiCommands] Updating local API config: enabled={}, port={}",
+        config.enabled, config.port
+    );
+    let status = server.apply_config(&config).await?;
+    settings_repo.save_local_api_config(&config).await?;
+    Ok(status)
+}
+
+/// Whether the local API is running, where, and where its token is stored
+#[tauri::command]
+pub async fn get_local_api_status_command(
+    server: State<'_, Arc<LocalApiServer>>,
+) -> AppResult<LocalApiStatus> {
+    server.status().await
+}
+
+/// Issue a new token, invalidating the one plugins currently use
+#[tauri::command]
+pub async fn regenerate_local_api_token_command(
+    server: State<'_, Arc<LocalApiServer>>,
+) -> AppResult<LocalApiStatus> {
+    info!("[LocalApiCommands] Regenerating local API token");
+    server.regenerate_token().await
+}
@@ -24,6 +24,14 @@ pub mod device_commands;
 pub mod error_recovery_commands;
 pub mod generic_task_commands;
 pub mod implementation_plan_commands;
+pub mod integration_hook_commands;
+pub mod job_retry_commands;
+pub mod local_api_commands;
+pub mod plan_conversation_commands;
+pub mod plan_export_commands;
+pub mod plan_review_commands;
+pub mod plan_template_commands;
+pub mod plan_test_commands;
 pub mod prompt_commands;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod screen_recording_commands;
@@ -46,6 +54,7 @@ pub mod video_analysis_commands;
 pub mod video_utils_commands;
 pub mod web_search_commands;
 pub mod workflow_commands;
+pub mod workspace_commands;
 
 // Re-export all command functions for easier imports
 pub use app_commands::{get_app_info, get_config_load_error, get_database_info_command};
@@ -74,6 +83,8 @@ pub use implementation_plan_commands::{
     create_implementation_plan_command, create_merged_implementation_plan_command,
     estimate_prompt_tokens_command, get_prompt_command, read_implementation_plan_command,
     update_implementation_plan_content_command, mark_implementation_plan_signed_off_command,
+    list_plan_revisions_command, get_plan_revision_command, diff_plan_revisions_command,
+    restore_plan_revision_command,
 };
 
 // Re-exports from workflow commands module
@@ -0,0 +1,22 @@
+use crate::error::AppResult;
+use crate::services::plan_conversations::{self, PlanConversation, PlanRefinementStarted};
+use tauri::{AppHandle, command};
+
+/// The follow-up conversation of a plan and the refinement job still answering it, if any
+#[command]
+pub async fn get_plan_conversation_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<PlanConversation> {
+    plan_conversations::get_plan_conversation(&app_handle, &job_id).await
+}
+
+/// Ask for a change to a plan; the reply becomes the plan's next revision
+#[command]
+pub async fn refine_implementation_plan_command(
+    app_handle: AppHandle,
+    job_id: String,
+    message: String,
+) -> AppResult<PlanRefinementStarted> {
+    plan_conversations::start_plan_refinement(&app_handle, &job_id, &message).await
+}
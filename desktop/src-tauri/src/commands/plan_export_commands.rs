@@ -0,0 +1,30 @@
+use crate::error::AppResult;
+use crate::services::plan_issue_exports::{self, PlanIssueExport};
+use crate::utils::plan_issue_export::PlanExportFormat;
+use tauri::{AppHandle, command};
+
+/// Issue-tracker formats plans can be exported to
+#[command]
+pub async fn list_plan_export_formats_command(
+    app_handle: AppHandle,
+) -> AppResult<Vec<PlanExportFormat>> {
+    Ok(plan_issue_exports::list_plan_export_formats(&app_handle))
+}
+
+/// Export a plan as a parent issue with one sub-task per step. Without an output directory the
+/// files go to the project's `.plantocode/issue_exports` directory.
+#[command]
+pub async fn export_plan_issues_command(
+    app_handle: AppHandle,
+    job_id: String,
+    format: String,
+    output_directory: Option<String>,
+) -> AppResult<PlanIssueExport> {
+    plan_issue_exports::export_plan_issues(
+        &app_handle,
+        &job_id,
+        &format,
+        output_directory.as_deref(),
+    )
+    .await
+}
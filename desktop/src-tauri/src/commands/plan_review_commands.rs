@@ -0,0 +1,87 @@
+use crate::db_utils::background_job_repository::{PlanStepComment, PlanStepState};
+use crate::error::AppResult;
+use crate::services::plan_progress::{self, PlanProgress};
+use crate::services::plan_step_reviews::{self, PlanStepReviews};
+use tauri::{AppHandle, command};
+
+/// Steps of an implementation plan with their status, assignee and review comments
+#[command]
+pub async fn get_plan_step_reviews_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<PlanStepReviews> {
+    plan_step_reviews::get_plan_step_reviews(&app_handle, &job_id).await
+}
+
+/// Set a step's status and/or assignee. Pass `clear_assignee` to unassign the step.
+#[command]
+pub async fn update_plan_step_command(
+    app_handle: AppHandle,
+    job_id: String,
+    step_number: String,
+    status: Option<String>,
+    assignee: Option<String>,
+    clear_assignee: Option<bool>,
+) -> AppResult<PlanStepState> {
+    let assignee = if clear_assignee.unwrap_or(false) {
+        Some(None)
+    } else {
+        assignee.as_deref().map(Some)
+    };
+    plan_step_reviews::update_plan_step(
+        &app_handle,
+        &job_id,
+        &step_number,
+        status.as_deref(),
+        assignee,
+    )
+    .await
+}
+
+#[command]
+pub async fn add_plan_step_comment_command(
+    app_handle: AppHandle,
+    job_id: String,
+    step_number: String,
+    body: String,
+) -> AppResult<PlanStepComment> {
+    plan_step_reviews::add_plan_step_comment(&app_handle, &job_id, &step_number, &body).await
+}
+
+#[command]
+pub async fn resolve_plan_step_comment_command(
+    app_handle: AppHandle,
+    job_id: String,
+    comment_id: String,
+    resolved: bool,
+) -> AppResult<PlanStepComment> {
+    plan_step_reviews::set_plan_step_comment_resolved(&app_handle, &job_id, &comment_id, resolved)
+        .await
+}
+
+#[command]
+pub async fn delete_plan_step_comment_command(
+    app_handle: AppHandle,
+    job_id: String,
+    comment_id: String,
+) -> AppResult<()> {
+    plan_step_reviews::delete_plan_step_comment(&app_handle, &job_id, &comment_id).await
+}
+
+/// Progress of a signed-off plan as of its last check, if it has been checked
+#[command]
+pub async fn get_plan_progress_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<Option<PlanProgress>> {
+    plan_progress::get_plan_progress(&app_handle, &job_id).await
+}
+
+/// Check a signed-off plan's progress against git now instead of waiting for the checker
+#[command]
+pub async fn check_plan_progress_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<PlanProgress> {
+    plan_progress::check_plan_progress(&app_handle, &job_id).await
+}
@@ -0,0 +1,42 @@
+use crate::error::AppResult;
+use crate::services::plan_templates::{self, SessionPlanTemplate};
+use crate::utils::plan_templates::{PlanTemplate, PlanTemplateCheck};
+use tauri::{AppHandle, command};
+
+/// Plan templates defined in the project file of `project_directory`
+#[command]
+pub async fn list_plan_templates_command(
+    project_directory: String,
+) -> AppResult<Vec<PlanTemplate>> {
+    Ok(plan_templates::list_plan_templates(&project_directory))
+}
+
+/// The session's template selection and the template it resolves to
+#[command]
+pub async fn get_session_plan_template_command(
+    app_handle: AppHandle,
+    session_id: String,
+) -> AppResult<SessionPlanTemplate> {
+    plan_templates::get_session_plan_template(&app_handle, &session_id).await
+}
+
+/// Select a template for a session: a template id, `"none"`, or null for the project default
+#[command]
+pub async fn set_session_plan_template_command(
+    app_handle: AppHandle,
+    session_id: String,
+    template_id: Option<String>,
+) -> AppResult<SessionPlanTemplate> {
+    plan_templates::set_session_plan_template(&app_handle, &session_id, template_id.as_deref())
+        .await
+}
+
+/// Check a plan against its session's template, or against `template_id` when given
+#[command]
+pub async fn check_plan_template_command(
+    app_handle: AppHandle,
+    job_id: String,
+    template_id: Option<String>,
+) -> AppResult<Option<PlanTemplateCheck>> {
+    plan_templates::check_plan_template(&app_handle, &job_id, template_id.as_deref()).await
+}
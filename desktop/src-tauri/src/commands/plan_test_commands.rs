@@ -0,0 +1,24 @@
+use crate::error::AppResult;
+use crate::models::JobCommandResponse;
+use crate::services::plan_test_plans;
+use crate::utils::test_plans::PlanTestPlan;
+use tauri::{AppHandle, command};
+
+/// Generate a test plan for the signed-off revision of a plan
+#[command]
+pub async fn generate_plan_tests_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<JobCommandResponse> {
+    let job_id = plan_test_plans::start_plan_test_generation(&app_handle, &job_id).await?;
+    Ok(JobCommandResponse { job_id })
+}
+
+/// The test plan stored with a plan, if one was generated
+#[command]
+pub async fn get_plan_test_plan_command(
+    app_handle: AppHandle,
+    job_id: String,
+) -> AppResult<Option<PlanTestPlan>> {
+    plan_test_plans::get_plan_test_plan(&app_handle, &job_id).await
+}
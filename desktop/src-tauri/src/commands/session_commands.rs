@@ -4,8 +4,11 @@ use crate::models::{
     JobStatus, Session,
 };
 use crate::utils::hash_utils::{hash_string, sha256_hash};
+use crate::db_utils::search_repository::{SearchHit, SearchQuery, SearchRepository};
 use crate::db_utils::session_repository::{SessionRepository, TaskHistoryState, FileHistoryState};
 use crate::services::history_state_sequencer::HistoryStateSequencer;
+use crate::services::session_bundle::{self, SessionBundleManifest, SessionImportResult};
+use crate::services::session_fork::{self, ForkSessionOptions, PlanComparison, SessionTree};
 use serde::Serialize;
 use serde_json::{Value, json};
 use std::collections::HashSet;
@@ -29,6 +32,13 @@ pub async fn create_session_command(
 
     let now = chrono::Utc::now().timestamp_millis();
 
+    // Start from the project's default file selection when none was given
+    let included_files = if session_data.included_files.is_empty() {
+        crate::utils::project_config::default_session_files(&session_data.project_directory).await
+    } else {
+        session_data.included_files
+    };
+
     // Build the complete session with defaults
     let session = Session {
         id: session_data
@@ -47,7 +57,7 @@ pub async fn create_session_command(
         model_used: session_data.model_used.filter(|s| !s.is_empty()),
         created_at: session_data.created_at.unwrap_or(now),
         updated_at: now,
-        included_files: session_data.included_files,
+        included_files,
         force_excluded_files: session_data.force_excluded_files,
         video_analysis_prompt: session_data.video_analysis_prompt,
         merge_instructions: session_data.merge_instructions,
@@ -398,6 +408,155 @@ pub async fn duplicate_session_command(
     Ok(new_session)
 }
 
+/// Fork a session at a point in its task/file history into a new linked session
+#[tauri::command]
+pub async fn fork_session_command(
+    app_handle: AppHandle,
+    session_id: String,
+    task_history_index: Option<i64>,
+    file_history_index: Option<i64>,
+    new_name: Option<String>,
+) -> AppResult<Session> {
+    let cache = app_handle.state::<std::sync::Arc<crate::services::SessionCache>>().inner().clone();
+    let repo = app_handle
+        .state::<Arc<crate::db_utils::session_repository::SessionRepository>>()
+        .inner()
+        .clone();
+
+    // Fork from what's in the database, including unsaved edits
+    cache.flush_session_if_dirty(&app_handle, &session_id).await?;
+    let source_session = cache.get_session(&app_handle, &session_id).await?;
+
+    let (fork, _lineage) = session_fork::fork_session(
+        &repo,
+        &source_session,
+        ForkSessionOptions {
+            task_history_index,
+            file_history_index,
+            name: new_name,
+        },
+    )
+    .await?;
+
+    cache.upsert_session(&app_handle, &fork).await?;
+    crate::events::session_events::emit_session_created(&app_handle, &fork)?;
+
+    Ok(fork)
+}
+
+/// Parent/child tree of forks containing the given session
+#[tauri::command]
+pub async fn get_session_tree_command(
+    app_handle: AppHandle,
+    session_id: String,
+) -> AppResult<SessionTree> {
+    let repo = app_handle
+        .state::<Arc<crate::db_utils::session_repository::SessionRepository>>()
+        .inner()
+        .clone();
+
+    session_fork::get_session_tree(&repo, &session_id).await
+}
+
+/// Diff the latest completed plans of two sessions (typically sibling forks)
+#[tauri::command]
+pub async fn compare_fork_plans_command(
+    app_handle: AppHandle,
+    base_session_id: String,
+    other_session_id: String,
+) -> AppResult<PlanComparison> {
+    let job_repo = app_handle
+        .state::<Arc<crate::db_utils::background_job_repository::BackgroundJobRepository>>()
+        .inner()
+        .clone();
+
+    session_fork::compare_fork_plans(&job_repo, &base_session_id, &other_session_id).await
+}
+
+/// Export a session with its history and finished jobs to a portable bundle file
+#[tauri::command]
+pub async fn export_session_bundle_command(
+    app_handle: AppHandle,
+    session_id: String,
+    output_path: String,
+    include_file_snapshots: bool,
+) -> AppResult<SessionBundleManifest> {
+    let cache = app_handle.state::<std::sync::Arc<crate::services::SessionCache>>().inner().clone();
+    let repo = app_handle
+        .state::<Arc<crate::db_utils::session_repository::SessionRepository>>()
+        .inner()
+        .clone();
+    let job_repo = app_handle
+        .state::<Arc<crate::db_utils::background_job_repository::BackgroundJobRepository>>()
+        .inner()
+        .clone();
+
+    // Make sure pending edits are in the database before reading it
+    cache.flush_session_if_dirty(&app_handle, &session_id).await?;
+
+    let bundle = session_bundle::export_session_bundle(
+        &repo,
+        &job_repo,
+        &session_id,
+        include_file_snapshots,
+    )
+    .await?;
+    session_bundle::write_bundle(&bundle, std::path::Path::new(&output_path))?;
+
+    Ok(bundle.manifest)
+}
+
+/// Import a session bundle as a new session in the given checkout
+#[tauri::command]
+pub async fn import_session_bundle_command(
+    app_handle: AppHandle,
+    bundle_path: String,
+    project_directory: String,
+) -> AppResult<SessionImportResult> {
+    let cache = app_handle.state::<std::sync::Arc<crate::services::SessionCache>>().inner().clone();
+    let repo = app_handle
+        .state::<Arc<crate::db_utils::session_repository::SessionRepository>>()
+        .inner()
+        .clone();
+
+    let bundle = session_bundle::read_bundle(std::path::Path::new(&bundle_path))?;
+    let result = session_bundle::import_session_bundle(&repo, bundle, &project_directory).await?;
+
+    cache.upsert_session(&app_handle, &result.session).await?;
+    crate::events::session_events::emit_session_created(&app_handle, &result.session)?;
+
+    Ok(result)
+}
+
+/// Full-text search over session task descriptions, their history and completed job outputs
+#[tauri::command]
+pub async fn search_sessions_command(
+    app_handle: AppHandle,
+    query: String,
+    project_directory: Option<String>,
+    task_types: Option<Vec<String>>,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+    limit: Option<u32>,
+) -> AppResult<Vec<SearchHit>> {
+    let cache = app_handle.state::<std::sync::Arc<crate::services::SessionCache>>().inner().clone();
+    let pool = app_handle.state::<Arc<sqlx::SqlitePool>>().inner().clone();
+
+    // Unsaved task description edits live in the cache until flushed
+    cache.flush_dirty_to_db(&app_handle).await?;
+
+    let search_query = SearchQuery {
+        query,
+        project_hash: project_directory.as_deref().map(hash_string),
+        task_types: task_types.unwrap_or_default(),
+        from_ms,
+        to_ms,
+        limit,
+        ..Default::default()
+    };
+    SearchRepository::new(pool).search(&search_query).await
+}
+
 /// Update session files with delta-based mutations (add/remove with mutual exclusivity)
 #[tauri::command]
 pub async fn update_session_files_command(
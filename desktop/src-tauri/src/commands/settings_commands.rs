@@ -7,6 +7,7 @@ use crate::models::RuntimeAIConfig;
 use crate::models::{DefaultSystemPrompt, ProjectSystemPrompt, TaskType};
 use crate::services::config_cache_service::ConfigCache;
 use crate::utils::hash_utils::hash_string;
+use crate::utils::project_config::{self, LoadedProjectConfig};
 use heck::{ToLowerCamelCase, ToSnakeCase};
 use log;
 use serde::{Deserialize, Serialize};
@@ -83,13 +84,32 @@ pub async fn validate_configuration_health(
         }
     }
 
-    // Project configuration is no longer stored locally - always using server defaults
-    if project_directory.is_some() {
-        report.project_config_status = ProjectConfigStatus::NoProjectConfig;
-        report.recommendations.push(
-            "Project settings are now managed by the server - no local configuration needed"
-                .to_string(),
-        );
+    // The only local project configuration is the .plantocode file in the project root
+    if let Some(project_directory) = project_directory.as_deref() {
+        match load_checked_project_config(&app_handle, project_directory).await {
+            None => report.project_config_status = ProjectConfigStatus::NoProjectConfig,
+            Some(loaded) => {
+                report.project_config_status = if loaded.validation.is_valid {
+                    ProjectConfigStatus::Complete
+                } else {
+                    ProjectConfigStatus::Invalid
+                };
+                report.validation_errors.extend(
+                    loaded
+                        .validation
+                        .errors
+                        .iter()
+                        .map(|e| format!("{}: {}", e.component, e.message)),
+                );
+                report.recommendations.extend(
+                    loaded
+                        .validation
+                        .warnings
+                        .iter()
+                        .map(|w| format!("{}: {}", w.component, w.message)),
+                );
+            }
+        }
     }
 
     if !report.server_connectivity {
@@ -107,6 +127,31 @@ pub async fn validate_configuration_health(
     Ok(report)
 }
 
+/// The project's `.plantocode` file with validation against the currently available models
+async fn load_checked_project_config(
+    app_handle: &AppHandle,
+    project_directory: &str,
+) -> Option<LoadedProjectConfig> {
+    let loaded = project_config::load_project_config(project_directory)?;
+    let mut checked = (*loaded).clone();
+    if let Ok(runtime_config) = get_runtime_ai_config_from_cache(app_handle).await {
+        checked.validation = project_config::validate_with_models(
+            &loaded,
+            &crate::validation::available_model_ids(&runtime_config),
+        );
+    }
+    Some(checked)
+}
+
+/// Get the project's `.plantocode` configuration file, if any, with its validation result
+#[tauri::command]
+pub async fn get_project_config_command(
+    app_handle: AppHandle,
+    project_directory: String,
+) -> AppResult<Option<LoadedProjectConfig>> {
+    Ok(load_checked_project_config(&app_handle, &project_directory).await)
+}
+
 #[tauri::command]
 pub async fn get_key_value_command(
     app_handle: AppHandle,
@@ -412,6 +457,7 @@ pub async fn get_project_task_model_settings_command(
             "temperature".to_string(),
             json!(server_task_config.temperature),
         );
+        task_config.insert("responseCache".to_string(), json!(false));
         if let Some(copy_buttons) = &server_task_config.copy_buttons {
             task_config.insert("copyButtons".to_string(), json!(copy_buttons));
         }
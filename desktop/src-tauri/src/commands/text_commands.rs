@@ -255,6 +255,7 @@ pub async fn generate_simple_text_command(
         stream: false,    // Non-streaming
         request_id: None, // No request tracking needed for non-streaming
         task_type: None,  // Not a workflow task, no task type needed
+        response_cache: false,
     };
 
     // Call chat completion (non-streaming)
@@ -351,6 +351,22 @@ pub async fn get_workflow_results_legacy(
         );
     }
 
+    // Extract impact analysis results, keeping the reason each dependent was added
+    if !workflow_result
+        .intermediate_data
+        .impacted_files
+        .is_empty()
+    {
+        stage_results.insert(
+            "ImpactAnalysis".to_string(),
+            serde_json::json!({
+                "files": workflow_result.intermediate_data.impacted_files,
+                "count": workflow_result.intermediate_data.impacted_files.len(),
+                "type": "impact_analysis_results"
+            }),
+        );
+    }
+
     Ok(WorkflowResultsResponse {
         workflow_id,
         selected_files: workflow_result.final_paths,
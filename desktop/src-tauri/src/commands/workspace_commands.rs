@@ -0,0 +1,63 @@
+use crate::db_utils::WorkspaceRepository;
+use crate::db_utils::workspace_repository::Workspace;
+use crate::error::AppResult;
+use crate::services::workspaces;
+use crate::utils::workspace_paths::{RepoFileSelection, WorkspaceLayout};
+use log::info;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+/// List all multi-repository workspaces
+#[tauri::command]
+pub async fn list_workspaces_command(
+    repo: State<'_, Arc<WorkspaceRepository>>,
+) -> AppResult<Vec<Workspace>> {
+    repo.list_workspaces().await
+}
+
+/// Create a workspace, or update it when the id already exists. An empty id creates a new
+/// workspace.
+#[tauri::command]
+pub async fn save_workspace_command(
+    repo: State<'_, Arc<WorkspaceRepository>>,
+    mut workspace: Workspace,
+) -> AppResult<Workspace> {
+    if workspace.id.trim().is_empty() {
+        workspace.id = uuid::Uuid::new_v4().to_string();
+    }
+    info!(
+        "[WorkspaceCommands] Saving workspace '{}' ({}) with {} repositories",
+        workspace.name,
+        workspace.id,
+        workspace.members.len()
+    );
+    repo.save_workspace(&workspace).await
+}
+
+/// Delete a workspace; its project directories and sessions are left untouched
+#[tauri::command]
+pub async fn delete_workspace_command(
+    repo: State<'_, Arc<WorkspaceRepository>>,
+    workspace_id: String,
+) -> AppResult<bool> {
+    info!("[WorkspaceCommands] Deleting workspace {}", workspace_id);
+    repo.delete_workspace(&workspace_id).await
+}
+
+/// Repositories of the workspace a project directory belongs to, or null when it has none
+#[tauri::command]
+pub async fn get_workspace_layout_command(
+    app_handle: AppHandle,
+    project_directory: String,
+) -> AppResult<Option<WorkspaceLayout>> {
+    workspaces::get_workspace_layout(&app_handle, &project_directory).await
+}
+
+/// A session's included files grouped per workspace repository
+#[tauri::command]
+pub async fn get_session_repo_file_selection_command(
+    app_handle: AppHandle,
+    session_id: String,
+) -> AppResult<Vec<RepoFileSelection>> {
+    workspaces::get_session_repo_file_selection(&app_handle, &session_id).await
+}
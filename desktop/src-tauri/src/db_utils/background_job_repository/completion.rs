@@ -1,7 +1,8 @@
 use super::base::BackgroundJobRepository;
 use crate::error::{AppError, AppResult};
 use crate::events::job_events::*;
-use crate::models::JobStatus;
+use crate::events::{AppEvent, publish_app_event};
+use crate::models::{JobStatus, TaskType};
 use crate::utils::get_timestamp;
 use log::{debug, info, warn};
 use serde_json::{Value, json};
@@ -163,6 +164,7 @@ impl BackgroundJobRepository {
 
         if result.rows_affected() > 0 {
             debug!("Successfully marked job {} as completed", job_id);
+            self.refresh_search_index(job_id).await;
             if let Some(cost) = actual_cost {
                 debug!("Cost ${:.6} stored in database for job {}", cost, job_id);
             }
@@ -197,6 +199,30 @@ impl BackgroundJobRepository {
                             },
                         );
                     }
+
+                    if matches!(
+                        TaskType::from_str(&job.task_type),
+                        Ok(TaskType::ImplementationPlan | TaskType::ImplementationPlanMerge)
+                    ) {
+                        let title = job
+                            .metadata
+                            .as_deref()
+                            .and_then(|m| serde_json::from_str::<Value>(m).ok())
+                            .and_then(|m| {
+                                m.get("planTitle")
+                                    .and_then(|t| t.as_str())
+                                    .map(str::to_string)
+                            })
+                            .unwrap_or_else(|| "Implementation Plan".to_string());
+                        publish_app_event(
+                            app_handle,
+                            AppEvent::PlanCreated {
+                                job_id: job_id.to_string(),
+                                session_id: job.session_id.clone(),
+                                title,
+                            },
+                        );
+                    }
                 }
             }
         } else {
@@ -312,6 +338,11 @@ impl BackgroundJobRepository {
             AppError::DatabaseError(format!("Failed to update job response: {}", e))
         })?;
 
+        // Streaming updates leave the status untouched; only reindex on transitions
+        if status.is_some() {
+            self.refresh_search_index(job_id).await;
+        }
+
         Ok(())
     }
 }
@@ -101,6 +101,8 @@ impl BackgroundJobRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to update job: {}", e)))?;
 
+        self.refresh_search_index(&job.id).await;
+
         if let Some(app_handle) = &self.app_handle {
             emit_job_status_changed(
                 app_handle,
@@ -11,5 +11,16 @@ pub mod metadata;
 pub mod cost;
 pub mod worker;
 pub mod cleanup;
+pub mod provenance;
+pub mod plan_revisions;
+pub mod plan_conversations;
+pub mod plan_steps;
+mod search;
 
 pub use base::BackgroundJobRepository;
+pub use provenance::{JobDerivationGraph, JobProvenanceEdge, JobProvenanceNode, relations};
+pub use plan_conversations::{
+    NewPlanConversationMessage, PlanConversationMessage, conversation_roles,
+};
+pub use plan_revisions::{NewPlanRevision, PlanRevision, PlanRevisionSummary, revision_sources};
+pub use plan_steps::{PlanStepComment, PlanStepState, PlanStepStateUpdate, step_statuses};
@@ -0,0 +1,219 @@
+use super::base::BackgroundJobRepository;
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Values stored in `plan_conversation_messages.role`
+pub mod conversation_roles {
+    /// A change the user asked for
+    pub const USER: &str = "user";
+    /// The revised plan the model replied with
+    pub const ASSISTANT: &str = "assistant";
+}
+
+/// One message of a follow-up conversation on a plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanConversationMessage {
+    pub id: String,
+    /// The plan being refined
+    pub job_id: String,
+    pub role: String,
+    pub content: String,
+    /// Refinement job that answers (user) or produced (assistant) this message
+    pub refinement_job_id: Option<String>,
+    /// Revision the request was made against (user) or the revision the reply became (assistant)
+    pub plan_revision: Option<i64>,
+    pub author_device_id: Option<String>,
+    pub created_at: i64,
+}
+
+/// A message to append to a plan's conversation
+#[derive(Debug, Clone)]
+pub struct NewPlanConversationMessage<'a> {
+    pub role: &'a str,
+    pub content: &'a str,
+    pub refinement_job_id: Option<&'a str>,
+    pub plan_revision: Option<i64>,
+    pub author_device_id: Option<&'a str>,
+}
+
+fn row_to_message(row: &SqliteRow) -> AppResult<PlanConversationMessage> {
+    Ok(PlanConversationMessage {
+        id: row.try_get("id")?,
+        job_id: row.try_get("job_id")?,
+        role: row.try_get("role")?,
+        content: row.try_get("content")?,
+        refinement_job_id: row.try_get("refinement_job_id")?,
+        plan_revision: row.try_get("plan_revision")?,
+        author_device_id: row.try_get("author_device_id")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+impl BackgroundJobRepository {
+    /// Messages of a plan's conversation in the order they were added
+    pub async fn list_plan_conversation(
+        &self,
+        job_id: &str,
+    ) -> AppResult<Vec<PlanConversationMessage>> {
+        let rows = sqlx::query(
+            "SELECT * FROM plan_conversation_messages WHERE job_id = $1 ORDER BY created_at, rowid",
+        )
+        .bind(job_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list plan conversation: {}", e)))?;
+
+        rows.iter().map(row_to_message).collect()
+    }
+
+    pub async fn add_plan_conversation_message(
+        &self,
+        job_id: &str,
+        message: NewPlanConversationMessage<'_>,
+    ) -> AppResult<PlanConversationMessage> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO plan_conversation_messages (id, job_id, role, content, refinement_job_id, plan_revision, author_device_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(job_id)
+        .bind(message.role)
+        .bind(message.content)
+        .bind(message.refinement_job_id)
+        .bind(message.plan_revision)
+        .bind(message.author_device_id)
+        .bind(crate::utils::date_utils::get_timestamp())
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to add plan conversation message: {}", e))
+        })?;
+
+        row_to_message(&row)
+    }
+
+    /// Link a user message to the refinement job created to answer it
+    pub async fn set_plan_conversation_refinement_job(
+        &self,
+        message_id: &str,
+        refinement_job_id: &str,
+    ) -> AppResult<()> {
+        sqlx::query("UPDATE plan_conversation_messages SET refinement_job_id = $1 WHERE id = $2")
+            .bind(refinement_job_id)
+            .bind(message_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to link plan conversation message: {}", e))
+            })?;
+        Ok(())
+    }
+
+    pub async fn delete_plan_conversation_message(&self, message_id: &str) -> AppResult<()> {
+        sqlx::query("DELETE FROM plan_conversation_messages WHERE id = $1")
+            .bind(message_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!(
+                    "Failed to delete plan conversation message: {}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{
+        insert_job, insert_session, test_job, test_pool, test_session,
+    };
+    use crate::models::BackgroundJob;
+
+    async fn test_repo() -> BackgroundJobRepository {
+        let pool = test_pool().await;
+        insert_session(&pool, &test_session("s1", "/work/shop")).await;
+        insert_job(
+            &pool,
+            &BackgroundJob {
+                response: Some("<plan>v1</plan>".to_string()),
+                ..test_job("plan", "s1", "implementation_plan")
+            },
+        )
+        .await;
+        BackgroundJobRepository::new(pool)
+    }
+
+    #[tokio::test]
+    async fn messages_keep_their_order_and_job_links() {
+        let repo = test_repo().await;
+
+        let request = repo
+            .add_plan_conversation_message(
+                "plan",
+                NewPlanConversationMessage {
+                    role: conversation_roles::USER,
+                    content: "Split step 3",
+                    refinement_job_id: None,
+                    plan_revision: Some(1),
+                    author_device_id: Some("device-a"),
+                },
+            )
+            .await
+            .unwrap();
+        repo.set_plan_conversation_refinement_job(&request.id, "refine-1")
+            .await
+            .unwrap();
+        repo.add_plan_conversation_message(
+            "plan",
+            NewPlanConversationMessage {
+                role: conversation_roles::ASSISTANT,
+                content: "<plan>v2</plan>",
+                refinement_job_id: Some("refine-1"),
+                plan_revision: Some(2),
+                author_device_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let messages = repo.list_plan_conversation("plan").await.unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|m| (m.role.as_str(), m.refinement_job_id.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (conversation_roles::USER, Some("refine-1")),
+                (conversation_roles::ASSISTANT, Some("refine-1")),
+            ]
+        );
+
+        repo.delete_plan_conversation_message(&request.id)
+            .await
+            .unwrap();
+        assert_eq!(repo.list_plan_conversation("plan").await.unwrap().len(), 1);
+
+        let invalid_role = repo
+            .add_plan_conversation_message(
+                "plan",
+                NewPlanConversationMessage {
+                    role: "system",
+                    content: "x",
+                    refinement_job_id: None,
+                    plan_revision: None,
+                    author_device_id: None,
+                },
+            )
+            .await;
+        assert!(invalid_role.is_err());
+    }
+}
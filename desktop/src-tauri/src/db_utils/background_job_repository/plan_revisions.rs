@@ -0,0 +1,464 @@
+use super::base::BackgroundJobRepository;
+use crate::error::{AppError, AppResult};
+use crate::utils::hash_utils::sha256_hash;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Values stored in `plan_revisions.source`
+pub mod revision_sources {
+    /// The plan as the model produced it
+    pub const MODEL: &str = "model";
+    /// A hand edit of the plan content
+    pub const EDIT: &str = "edit";
+    /// An earlier revision brought back as the current content
+    pub const RESTORE: &str = "restore";
+    /// The model's reply to a follow-up request in the plan's conversation
+    pub const REFINEMENT: &str = "refinement";
+}
+
+/// A plan revision without its content, for listing history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanRevisionSummary {
+    pub job_id: String,
+    pub revision: i64,
+    pub content_hash: String,
+    pub source: String,
+    pub author_device_id: Option<String>,
+    /// Revision this one was restored from, for `restore` revisions
+    pub restored_from: Option<i64>,
+    pub signoff_state: Option<String>,
+    pub signed_off_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanRevision {
+    #[serde(flatten)]
+    pub summary: PlanRevisionSummary,
+    pub content: String,
+}
+
+/// A new revision to append to a plan's history
+#[derive(Debug, Clone)]
+pub struct NewPlanRevision<'a> {
+    pub content: &'a str,
+    pub source: &'a str,
+    pub author_device_id: Option<&'a str>,
+    pub restored_from: Option<i64>,
+}
+
+const SUMMARY_COLUMNS: &str = "job_id, revision, content_hash, source, author_device_id, restored_from, signoff_state, signed_off_at, created_at";
+
+fn row_to_summary(row: &SqliteRow) -> AppResult<PlanRevisionSummary> {
+    Ok(PlanRevisionSummary {
+        job_id: row.try_get("job_id")?,
+        revision: row.try_get("revision")?,
+        content_hash: row.try_get("content_hash")?,
+        source: row.try_get("source")?,
+        author_device_id: row.try_get("author_device_id")?,
+        restored_from: row.try_get("restored_from")?,
+        signoff_state: row.try_get("signoff_state")?,
+        signed_off_at: row.try_get("signed_off_at")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn row_to_revision(row: &SqliteRow) -> AppResult<PlanRevision> {
+    Ok(PlanRevision {
+        summary: row_to_summary(row)?,
+        content: row.try_get("content")?,
+    })
+}
+
+impl BackgroundJobRepository {
+    /// Append a revision to a plan's history and return it. When the content is identical to
+    /// the latest revision nothing is written and the latest revision is returned instead.
+    pub async fn record_plan_revision(
+        &self,
+        job_id: &str,
+        new_revision: NewPlanRevision<'_>,
+    ) -> AppResult<PlanRevisionSummary> {
+        let content_hash = sha256_hash(new_revision.content);
+
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        let latest = sqlx::query(&format!(
+            "SELECT {} FROM plan_revisions WHERE job_id = $1 ORDER BY revision DESC LIMIT 1",
+            SUMMARY_COLUMNS
+        ))
+        .bind(job_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch latest plan revision: {}", e))
+        })?
+        .map(|row| row_to_summary(&row))
+        .transpose()?;
+
+        if let Some(latest) = &latest {
+            if latest.content_hash == content_hash {
+                return Ok(latest.clone());
+            }
+        }
+
+        let summary = PlanRevisionSummary {
+            job_id: job_id.to_string(),
+            revision: latest.map(|r| r.revision + 1).unwrap_or(1),
+            content_hash,
+            source: new_revision.source.to_string(),
+            author_device_id: new_revision.author_device_id.map(str::to_string),
+            restored_from: new_revision.restored_from,
+            signoff_state: None,
+            signed_off_at: None,
+            created_at: crate::utils::date_utils::get_timestamp(),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO plan_revisions (job_id, revision, content, content_hash, source, author_device_id, restored_from, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(&summary.job_id)
+        .bind(summary.revision)
+        .bind(new_revision.content)
+        .bind(&summary.content_hash)
+        .bind(&summary.source)
+        .bind(&summary.author_device_id)
+        .bind(summary.restored_from)
+        .bind(summary.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record plan revision: {}", e)))?;
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit plan revision: {}", e))
+        })?;
+        Ok(summary)
+    }
+
+    /// Capture the current plan content as revision 1 if the plan has no history yet
+    pub async fn ensure_initial_plan_revision(&self, job_id: &str, content: &str) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO plan_revisions (job_id, revision, content, content_hash, source, created_at)
+            SELECT $1, 1, $2, $3, $4, $5
+            WHERE NOT EXISTS (SELECT 1 FROM plan_revisions WHERE job_id = $1)
+            "#,
+        )
+        .bind(job_id)
+        .bind(content)
+        .bind(sha256_hash(content))
+        .bind(revision_sources::MODEL)
+        .bind(crate::utils::date_utils::get_timestamp())
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to capture initial plan revision: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// All revisions of a plan, oldest first
+    pub async fn list_plan_revisions(&self, job_id: &str) -> AppResult<Vec<PlanRevisionSummary>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM plan_revisions WHERE job_id = $1 ORDER BY revision",
+            SUMMARY_COLUMNS
+        ))
+        .bind(job_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list plan revisions: {}", e)))?;
+
+        rows.iter().map(row_to_summary).collect()
+    }
+
+    pub async fn get_plan_revision(
+        &self,
+        job_id: &str,
+        revision: i64,
+    ) -> AppResult<Option<PlanRevision>> {
+        let row = sqlx::query(&format!(
+            "SELECT {}, content FROM plan_revisions WHERE job_id = $1 AND revision = $2",
+            SUMMARY_COLUMNS
+        ))
+        .bind(job_id)
+        .bind(revision)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch plan revision: {}", e)))?;
+
+        row.as_ref().map(row_to_revision).transpose()
+    }
+
+    pub async fn get_latest_plan_revision(&self, job_id: &str) -> AppResult<Option<PlanRevision>> {
+        let row = sqlx::query(&format!(
+            "SELECT {}, content FROM plan_revisions WHERE job_id = $1 ORDER BY revision DESC LIMIT 1",
+            SUMMARY_COLUMNS
+        ))
+        .bind(job_id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch latest plan revision: {}", e))
+        })?;
+
+        row.as_ref().map(row_to_revision).transpose()
+    }
+
+    /// The most recent revision of each plan whose latest `state` sign-off happened at or
+    /// after `since`
+    pub async fn list_plan_signoffs_since(
+        &self,
+        state: &str,
+        since: i64,
+    ) -> AppResult<Vec<PlanRevisionSummary>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM (
+                SELECT *, ROW_NUMBER() OVER (
+                    PARTITION BY job_id ORDER BY signed_off_at DESC, revision DESC
+                ) AS signoff_rank
+                FROM plan_revisions WHERE signoff_state = $1
+            )
+            WHERE signoff_rank = 1 AND signed_off_at >= $2
+            ORDER BY signed_off_at DESC
+            "#,
+            SUMMARY_COLUMNS
+        ))
+        .bind(state)
+        .bind(since)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list plan sign-offs: {}", e)))?;
+
+        rows.iter().map(row_to_summary).collect()
+    }
+
+    /// Record a sign-off decision against one revision
+    pub async fn sign_off_plan_revision(
+        &self,
+        job_id: &str,
+        revision: i64,
+        state: &str,
+    ) -> AppResult<PlanRevisionSummary> {
+        let result = sqlx::query(
+            "UPDATE plan_revisions SET signoff_state = $1, signed_off_at = $2 WHERE job_id = $3 AND revision = $4",
+        )
+        .bind(state)
+        .bind(crate::utils::date_utils::get_timestamp())
+        .bind(job_id)
+        .bind(revision)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to sign off plan revision: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFoundError(format!(
+                "Revision {} of plan {} not found",
+                revision, job_id
+            )));
+        }
+
+        self.get_plan_revision(job_id, revision)
+            .await?
+            .map(|r| r.summary)
+            .ok_or_else(|| {
+                AppError::NotFoundError(format!(
+                    "Revision {} of plan {} not found",
+                    revision, job_id
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{
+        insert_job, insert_session, test_job, test_pool, test_session,
+    };
+    use crate::models::BackgroundJob;
+
+    async fn test_repo() -> BackgroundJobRepository {
+        let pool = test_pool().await;
+        insert_session(&pool, &test_session("s1", "/work/shop")).await;
+        insert_job(
+            &pool,
+            &BackgroundJob {
+                response: Some("<plan>v1</plan>".to_string()),
+                ..test_job("plan", "s1", "implementation_plan")
+            },
+        )
+        .await;
+        BackgroundJobRepository::new(pool)
+    }
+
+    fn edit(content: &str) -> NewPlanRevision<'_> {
+        NewPlanRevision {
+            content,
+            source: revision_sources::EDIT,
+            author_device_id: Some("device-a"),
+            restored_from: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn revisions_are_numbered_and_unchanged_content_is_skipped() {
+        let repo = test_repo().await;
+        repo.ensure_initial_plan_revision("plan", "<plan>v1</plan>")
+            .await
+            .unwrap();
+        repo.ensure_initial_plan_revision("plan", "<plan>ignored</plan>")
+            .await
+            .unwrap();
+
+        let second = repo
+            .record_plan_revision("plan", edit("<plan>v2</plan>"))
+            .await
+            .unwrap();
+        assert_eq!(second.revision, 2);
+        assert_eq!(second.author_device_id.as_deref(), Some("device-a"));
+
+        let unchanged = repo
+            .record_plan_revision("plan", edit("<plan>v2</plan>"))
+            .await
+            .unwrap();
+        assert_eq!(unchanged.revision, 2);
+
+        let revisions = repo.list_plan_revisions("plan").await.unwrap();
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|r| (r.revision, r.source.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, revision_sources::MODEL), (2, revision_sources::EDIT)]
+        );
+        assert_eq!(
+            repo.get_plan_revision("plan", 1)
+                .await
+                .unwrap()
+                .unwrap()
+                .content,
+            "<plan>v1</plan>"
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_off_targets_one_revision() {
+        let repo = test_repo().await;
+        repo.ensure_initial_plan_revision("plan", "<plan>v1</plan>")
+            .await
+            .unwrap();
+        repo.record_plan_revision("plan", edit("<plan>v2</plan>"))
+            .await
+            .unwrap();
+
+        let signed = repo
+            .sign_off_plan_revision("plan", 1, "accepted")
+            .await
+            .unwrap();
+        assert_eq!(signed.signoff_state.as_deref(), Some("accepted"));
+        assert!(
+            repo.get_latest_plan_revision("plan")
+                .await
+                .unwrap()
+                .unwrap()
+                .summary
+                .signoff_state
+                .is_none()
+        );
+        assert!(matches!(
+            repo.sign_off_plan_revision("plan", 9, "accepted").await,
+            Err(AppError::NotFoundError(_))
+        ));
+
+        repo.sign_off_plan_revision("plan", 2, "accepted")
+            .await
+            .unwrap();
+        let signoffs = repo.list_plan_signoffs_since("accepted", 0).await.unwrap();
+        assert_eq!(signoffs.len(), 1);
+        assert!(
+            repo.list_plan_signoffs_since("accepted", i64::MAX)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn refinement_revisions_are_recorded() {
+        let repo = test_repo().await;
+        repo.ensure_initial_plan_revision("plan", "<plan>v1</plan>")
+            .await
+            .unwrap();
+
+        let refined = repo
+            .record_plan_revision(
+                "plan",
+                NewPlanRevision {
+                    content: "<plan>refined</plan>",
+                    source: revision_sources::REFINEMENT,
+                    author_device_id: None,
+                    restored_from: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(refined.revision, 2);
+        assert_eq!(refined.source, revision_sources::REFINEMENT);
+    }
+
+    #[tokio::test]
+    async fn refinement_migration_keeps_existing_revisions() {
+        let repo = test_repo().await;
+        crate::db_utils::execute_script_in_transaction(
+            &repo.pool,
+            &format!(
+                "DROP TABLE plan_revisions;\n{}",
+                include_str!("../../../migrations/features/add_plan_revisions.sql")
+            ),
+        )
+        .await
+        .unwrap();
+        repo.ensure_initial_plan_revision("plan", "<plan>v1</plan>")
+            .await
+            .unwrap();
+
+        crate::db_utils::execute_script_in_transaction(
+            &repo.pool,
+            include_str!("../../../migrations/features/allow_plan_refinement_revisions.sql"),
+        )
+        .await
+        .unwrap();
+
+        repo.record_plan_revision(
+            "plan",
+            NewPlanRevision {
+                content: "<plan>refined</plan>",
+                source: revision_sources::REFINEMENT,
+                author_device_id: None,
+                restored_from: None,
+            },
+        )
+        .await
+        .unwrap();
+        let revisions = repo.list_plan_revisions("plan").await.unwrap();
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|r| (r.revision, r.source.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, revision_sources::MODEL),
+                (2, revision_sources::REFINEMENT)
+            ]
+        );
+    }
+}
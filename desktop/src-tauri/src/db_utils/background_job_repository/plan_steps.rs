@@ -0,0 +1,354 @@
+use super::base::BackgroundJobRepository;
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Values stored in `plan_step_states.status`
+pub mod step_statuses {
+    pub const PENDING: &str = "pending";
+    pub const IN_PROGRESS: &str = "in_progress";
+    pub const DONE: &str = "done";
+    pub const SKIPPED: &str = "skipped";
+
+    pub const ALL: &[&str] = &[PENDING, IN_PROGRESS, DONE, SKIPPED];
+
+    pub fn is_valid(status: &str) -> bool {
+        ALL.contains(&status)
+    }
+}
+
+/// Review status and assignee of one plan step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStepState {
+    pub job_id: String,
+    pub step_number: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub updated_by_device_id: Option<String>,
+    pub updated_at: i64,
+}
+
+/// A review comment anchored to one plan step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStepComment {
+    pub id: String,
+    pub job_id: String,
+    pub step_number: String,
+    /// Plan revision the comment was written against, if the plan had history then
+    pub plan_revision: Option<i64>,
+    pub body: String,
+    pub author_device_id: Option<String>,
+    pub resolved: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Change to a step's state; `None` fields keep their current value
+#[derive(Debug, Clone, Default)]
+pub struct PlanStepStateUpdate<'a> {
+    pub status: Option<&'a str>,
+    /// `Some(None)` clears the assignee
+    pub assignee: Option<Option<&'a str>>,
+    pub device_id: Option<&'a str>,
+}
+
+fn row_to_state(row: &SqliteRow) -> AppResult<PlanStepState> {
+    Ok(PlanStepState {
+        job_id: row.try_get("job_id")?,
+        step_number: row.try_get("step_number")?,
+        status: row.try_get("status")?,
+        assignee: row.try_get("assignee")?,
+        updated_by_device_id: row.try_get("updated_by_device_id")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn row_to_comment(row: &SqliteRow) -> AppResult<PlanStepComment> {
+    Ok(PlanStepComment {
+        id: row.try_get("id")?,
+        job_id: row.try_get("job_id")?,
+        step_number: row.try_get("step_number")?,
+        plan_revision: row.try_get("plan_revision")?,
+        body: row.try_get("body")?,
+        author_device_id: row.try_get("author_device_id")?,
+        resolved: row.try_get::<i64, _>("resolved")? != 0,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+impl BackgroundJobRepository {
+    /// States of every step that has been touched; untouched steps are implicitly pending
+    pub async fn list_plan_step_states(&self, job_id: &str) -> AppResult<Vec<PlanStepState>> {
+        let rows = sqlx::query("SELECT * FROM plan_step_states WHERE job_id = $1")
+            .bind(job_id)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to list plan step states: {}", e))
+            })?;
+
+        rows.iter().map(row_to_state).collect()
+    }
+
+    pub async fn update_plan_step_state(
+        &self,
+        job_id: &str,
+        step_number: &str,
+        update: PlanStepStateUpdate<'_>,
+    ) -> AppResult<PlanStepState> {
+        if let Some(status) = update.status {
+            if !step_statuses::is_valid(status) {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid step status '{}', expected one of: {}",
+                    status,
+                    step_statuses::ALL.join(", ")
+                )));
+            }
+        }
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO plan_step_states (job_id, step_number, status, assignee, updated_by_device_id, updated_at)
+            VALUES ($1, $2, COALESCE($3, 'pending'), $5, $6, $7)
+            ON CONFLICT(job_id, step_number) DO UPDATE SET
+                status = COALESCE($3, status),
+                assignee = CASE WHEN $4 THEN $5 ELSE assignee END,
+                updated_by_device_id = $6,
+                updated_at = $7
+            RETURNING *
+            "#,
+        )
+        .bind(job_id)
+        .bind(step_number)
+        .bind(update.status)
+        .bind(update.assignee.is_some())
+        .bind(update.assignee.flatten())
+        .bind(update.device_id)
+        .bind(crate::utils::date_utils::get_timestamp())
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update plan step state: {}", e)))?;
+
+        row_to_state(&row)
+    }
+
+    /// Comments on every step of a plan, oldest first
+    pub async fn list_plan_step_comments(&self, job_id: &str) -> AppResult<Vec<PlanStepComment>> {
+        let rows = sqlx::query(
+            "SELECT * FROM plan_step_comments WHERE job_id = $1 ORDER BY created_at, id",
+        )
+        .bind(job_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to list plan step comments: {}", e))
+        })?;
+
+        rows.iter().map(row_to_comment).collect()
+    }
+
+    pub async fn add_plan_step_comment(
+        &self,
+        job_id: &str,
+        step_number: &str,
+        plan_revision: Option<i64>,
+        body: &str,
+        author_device_id: Option<&str>,
+    ) -> AppResult<PlanStepComment> {
+        let now = crate::utils::date_utils::get_timestamp();
+        let comment = PlanStepComment {
+            id: uuid::Uuid::new_v4().to_string(),
+            job_id: job_id.to_string(),
+            step_number: step_number.to_string(),
+            plan_revision,
+            body: body.to_string(),
+            author_device_id: author_device_id.map(str::to_string),
+            resolved: false,
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO plan_step_comments (id, job_id, step_number, plan_revision, body, author_device_id, resolved, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, 0, $7, $7)
+            "#,
+        )
+        .bind(&comment.id)
+        .bind(&comment.job_id)
+        .bind(&comment.step_number)
+        .bind(comment.plan_revision)
+        .bind(&comment.body)
+        .bind(&comment.author_device_id)
+        .bind(now)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to add plan step comment: {}", e)))?;
+
+        Ok(comment)
+    }
+
+    pub async fn set_plan_step_comment_resolved(
+        &self,
+        job_id: &str,
+        comment_id: &str,
+        resolved: bool,
+    ) -> AppResult<PlanStepComment> {
+        let row = sqlx::query(
+            r#"
+            UPDATE plan_step_comments SET resolved = $1, updated_at = $2
+            WHERE job_id = $3 AND id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(resolved as i64)
+        .bind(crate::utils::date_utils::get_timestamp())
+        .bind(job_id)
+        .bind(comment_id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update plan step comment: {}", e)))?
+        .ok_or_else(|| AppError::NotFoundError(format!("Comment not found: {}", comment_id)))?;
+
+        row_to_comment(&row)
+    }
+
+    pub async fn delete_plan_step_comment(&self, job_id: &str, comment_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM plan_step_comments WHERE job_id = $1 AND id = $2")
+            .bind(job_id)
+            .bind(comment_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete plan step comment: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFoundError(format!(
+                "Comment not found: {}",
+                comment_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{
+        insert_job, insert_session, test_job, test_pool, test_session,
+    };
+    use crate::models::BackgroundJob;
+
+    async fn test_repo() -> BackgroundJobRepository {
+        let pool = test_pool().await;
+        insert_session(&pool, &test_session("s1", "/work/shop")).await;
+        insert_job(
+            &pool,
+            &BackgroundJob {
+                response: Some("<step number=\"1\"><title>A</title></step>".to_string()),
+                ..test_job("plan", "s1", "implementation_plan")
+            },
+        )
+        .await;
+        BackgroundJobRepository::new(pool)
+    }
+
+    #[tokio::test]
+    async fn step_state_updates_keep_unspecified_fields() {
+        let repo = test_repo().await;
+
+        let assigned = repo
+            .update_plan_step_state(
+                "plan",
+                "1",
+                PlanStepStateUpdate {
+                    assignee: Some(Some("dana")),
+                    device_id: Some("device-a"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(assigned.status, step_statuses::PENDING);
+        assert_eq!(assigned.assignee.as_deref(), Some("dana"));
+
+        let started = repo
+            .update_plan_step_state(
+                "plan",
+                "1",
+                PlanStepStateUpdate {
+                    status: Some(step_statuses::IN_PROGRESS),
+                    device_id: Some("device-b"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(started.status, step_statuses::IN_PROGRESS);
+        assert_eq!(started.assignee.as_deref(), Some("dana"));
+        assert_eq!(started.updated_by_device_id.as_deref(), Some("device-b"));
+
+        let unassigned = repo
+            .update_plan_step_state(
+                "plan",
+                "1",
+                PlanStepStateUpdate {
+                    assignee: Some(None),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(unassigned.status, step_statuses::IN_PROGRESS);
+        assert!(unassigned.assignee.is_none());
+
+        assert!(matches!(
+            repo.update_plan_step_state(
+                "plan",
+                "1",
+                PlanStepStateUpdate {
+                    status: Some("blocked"),
+                    ..Default::default()
+                },
+            )
+            .await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn comments_can_be_resolved_and_deleted() {
+        let repo = test_repo().await;
+        let comment = repo
+            .add_plan_step_comment("plan", "1", Some(2), "Needs a rollback", Some("device-a"))
+            .await
+            .unwrap();
+
+        let resolved = repo
+            .set_plan_step_comment_resolved("plan", &comment.id, true)
+            .await
+            .unwrap();
+        assert!(resolved.resolved);
+        assert_eq!(resolved.plan_revision, Some(2));
+
+        repo.delete_plan_step_comment("plan", &comment.id)
+            .await
+            .unwrap();
+        assert!(
+            repo.list_plan_step_comments("plan")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            repo.delete_plan_step_comment("plan", &comment.id).await,
+            Err(AppError::NotFoundError(_))
+        ));
+    }
+}
@@ -0,0 +1,371 @@
+use super::base::BackgroundJobRepository;
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use std::collections::HashSet;
+
+/// Relation names stored in `job_provenance.relation`
+pub mod relations {
+    /// Workflow job -> one of its stage jobs
+    pub const WORKFLOW_STAGE: &str = "workflow_stage";
+    /// Source plan -> merged plan
+    pub const MERGE_SOURCE: &str = "merge_source";
+    /// Plan -> follow-up job that refined it into a new revision
+    pub const REFINEMENT: &str = "refinement";
+    /// Signed-off plan -> job that generated its test plan
+    pub const TEST_PLAN: &str = "test_plan";
+    /// File-finding job -> plan that used the files it selected
+    pub const SELECTED_FILES: &str = "selected_files";
+    /// Web research job -> plan whose task description includes its findings
+    pub const RESEARCH: &str = "research";
+    /// Workflow stage job -> the next stage that consumes its output
+    pub const STAGE_INPUT: &str = "stage_input";
+    /// Job -> job started from its output
+    pub const CONTINUED_FROM: &str = "continued_from";
+    /// Original job -> its re-run
+    pub const RERUN_OF: &str = "rerun_of";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobProvenanceEdge {
+    pub parent_job_id: String,
+    pub child_job_id: String,
+    pub relation: String,
+    pub replay_id: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobProvenanceNode {
+    pub job_id: String,
+    pub session_id: String,
+    pub task_type: String,
+    pub status: String,
+    pub model_used: Option<String>,
+    pub title: Option<String>,
+    pub actual_cost: Option<f64>,
+    pub created_at: i64,
+}
+
+/// Every job a job was derived from and every job derived from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobDerivationGraph {
+    pub root_job_id: String,
+    pub nodes: Vec<JobProvenanceNode>,
+    pub edges: Vec<JobProvenanceEdge>,
+}
+
+fn row_to_edge(row: &SqliteRow) -> AppResult<JobProvenanceEdge> {
+    Ok(JobProvenanceEdge {
+        parent_job_id: row.try_get("parent_job_id")?,
+        child_job_id: row.try_get("child_job_id")?,
+        relation: row.try_get("relation")?,
+        replay_id: row.try_get("replay_id")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+impl BackgroundJobRepository {
+    /// Record that `child_job_id` was derived from each `(parent_job_id, relation)`.
+    /// Parents that no longer exist are skipped; recording the same edge twice is a no-op.
+    pub async fn record_job_provenance(
+        &self,
+        child_job_id: &str,
+        parents: &[(String, &str)],
+        replay_id: Option<&str>,
+    ) -> AppResult<()> {
+        if parents.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        for (parent_job_id, relation) in parents {
+            if parent_job_id == child_job_id {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO job_provenance (parent_job_id, child_job_id, relation, replay_id, created_at)
+                SELECT $1, $2, $3, $4, $5
+                WHERE EXISTS (SELECT 1 FROM background_jobs WHERE id = $1)
+                "#,
+            )
+            .bind(parent_job_id)
+            .bind(child_job_id)
+            .bind(relation)
+            .bind(replay_id)
+            .bind(crate::utils::date_utils::get_timestamp())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to record job provenance: {}", e))
+            })?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit job provenance: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Edges on every path leading into `job_id`
+    pub async fn get_job_ancestor_edges(&self, job_id: &str) -> AppResult<Vec<JobProvenanceEdge>> {
+        let rows = sqlx::query(
+            r#"
+            WITH RECURSIVE ancestors(id) AS (
+                SELECT $1
+                UNION
+                SELECT p.parent_job_id FROM job_provenance p JOIN ancestors a ON p.child_job_id = a.id
+            )
+            SELECT p.* FROM job_provenance p
+            WHERE p.child_job_id IN (SELECT id FROM ancestors)
+            ORDER BY p.created_at, p.parent_job_id
+            "#,
+        )
+        .bind(job_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch job ancestors: {}", e)))?;
+
+        rows.iter().map(row_to_edge).collect()
+    }
+
+    /// Edges on every path leading out of `job_id`. Re-runs are only followed
+    /// when `include_reruns` is set.
+    pub async fn get_job_descendant_edges(
+        &self,
+        job_id: &str,
+        include_reruns: bool,
+    ) -> AppResult<Vec<JobProvenanceEdge>> {
+        let rows = sqlx::query(
+            r#"
+            WITH RECURSIVE descendants(id) AS (
+                SELECT $1
+                UNION
+                SELECT p.child_job_id FROM job_provenance p JOIN descendants d ON p.parent_job_id = d.id
+                WHERE $2 OR p.relation != $3
+            )
+            SELECT p.* FROM job_provenance p
+            WHERE p.parent_job_id IN (SELECT id FROM descendants)
+              AND ($2 OR p.relation != $3)
+            ORDER BY p.created_at, p.child_job_id
+            "#,
+        )
+        .bind(job_id)
+        .bind(include_reruns)
+        .bind(relations::RERUN_OF)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch job descendants: {}", e)))?;
+
+        rows.iter().map(row_to_edge).collect()
+    }
+
+    /// Full derivation graph around a job: what it was built from and what was built from it
+    pub async fn get_job_derivation_graph(&self, job_id: &str) -> AppResult<JobDerivationGraph> {
+        if self.get_job_by_id(job_id).await?.is_none() {
+            return Err(AppError::NotFoundError(format!("Job {} not found", job_id)));
+        }
+
+        let mut edges = self.get_job_ancestor_edges(job_id).await?;
+        let mut seen: HashSet<(String, String, String)> = edges
+            .iter()
+            .map(|e| {
+                (
+                    e.parent_job_id.clone(),
+                    e.child_job_id.clone(),
+                    e.relation.clone(),
+                )
+            })
+            .collect();
+        for edge in self.get_job_descendant_edges(job_id, true).await? {
+            let key = (
+                edge.parent_job_id.clone(),
+                edge.child_job_id.clone(),
+                edge.relation.clone(),
+            );
+            if seen.insert(key) {
+                edges.push(edge);
+            }
+        }
+
+        let mut ids: Vec<&str> = vec![job_id];
+        for edge in &edges {
+            ids.push(&edge.parent_job_id);
+            ids.push(&edge.child_job_id);
+        }
+        let ids_json = serde_json::to_string(&ids).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize job ids: {}", e))
+        })?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, session_id, task_type, status, model_used, actual_cost, created_at,
+                   CASE WHEN json_valid(metadata) THEN json_extract(metadata, '$.planTitle') END AS title
+            FROM background_jobs
+            WHERE id IN (SELECT value FROM json_each($1))
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(ids_json)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch provenance jobs: {}", e)))?;
+
+        let nodes = rows
+            .iter()
+            .map(|row| {
+                Ok(JobProvenanceNode {
+                    job_id: row.try_get("id")?,
+                    session_id: row.try_get("session_id")?,
+                    task_type: row.try_get("task_type")?,
+                    status: row.try_get("status")?,
+                    model_used: row.try_get("model_used")?,
+                    title: row.try_get("title")?,
+                    actual_cost: row.try_get("actual_cost")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(JobDerivationGraph {
+            root_job_id: job_id.to_string(),
+            nodes,
+            edges,
+        })
+    }
+
+    /// `(original, re-run)` job pairs created by one replay, in creation order
+    pub async fn get_replay_pairs(&self, replay_id: &str) -> AppResult<Vec<(String, String)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT parent_job_id, child_job_id FROM job_provenance
+            WHERE replay_id = $1 AND relation = $2
+            ORDER BY created_at, child_job_id
+            "#,
+        )
+        .bind(replay_id)
+        .bind(relations::RERUN_OF)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch replay jobs: {}", e)))?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("parent_job_id")?, row.try_get("child_job_id")?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{insert_session, test_job, test_pool, test_session};
+
+    async fn test_repo() -> BackgroundJobRepository {
+        let pool = test_pool().await;
+        insert_session(&pool, &test_session("s1", "/work/shop")).await;
+        BackgroundJobRepository::new(pool)
+    }
+
+    async fn add_job(repo: &BackgroundJobRepository, id: &str, task_type: &str) {
+        repo.create_job(&test_job(id, "s1", task_type))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn derivation_graph_spans_ancestors_and_descendants() {
+        let repo = test_repo().await;
+        for (id, task_type) in [
+            ("workflow", "file_finder_workflow"),
+            ("finder", "extended_path_finder"),
+            ("research", "web_search_execution"),
+            ("plan_a", "implementation_plan"),
+            ("plan_b", "implementation_plan"),
+            ("merge", "implementation_plan_merge"),
+            ("plan_a_rerun", "implementation_plan"),
+            ("unrelated", "implementation_plan"),
+        ] {
+            add_job(&repo, id, task_type).await;
+        }
+
+        repo.record_job_provenance(
+            "finder",
+            &[("workflow".to_string(), relations::WORKFLOW_STAGE)],
+            None,
+        )
+        .await
+        .unwrap();
+        repo.record_job_provenance(
+            "plan_a",
+            &[
+                ("finder".to_string(), relations::SELECTED_FILES),
+                ("research".to_string(), relations::RESEARCH),
+                ("missing".to_string(), relations::RESEARCH),
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+        repo.record_job_provenance(
+            "merge",
+            &[
+                ("plan_a".to_string(), relations::MERGE_SOURCE),
+                ("plan_b".to_string(), relations::MERGE_SOURCE),
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+        repo.record_job_provenance(
+            "plan_a_rerun",
+            &[("plan_a".to_string(), relations::RERUN_OF)],
+            Some("replay-1"),
+        )
+        .await
+        .unwrap();
+
+        let graph = repo.get_job_derivation_graph("plan_a").await.unwrap();
+        let mut ids: Vec<&str> = graph.nodes.iter().map(|n| n.job_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec![
+                "finder",
+                "merge",
+                "plan_a",
+                "plan_a_rerun",
+                "research",
+                "workflow"
+            ]
+        );
+        assert_eq!(graph.edges.len(), 5);
+
+        let without_reruns = repo
+            .get_job_descendant_edges("plan_a", false)
+            .await
+            .unwrap();
+        // plan_b -> merge is not a descendant edge: plan_b was not built from plan_a
+        assert_eq!(without_reruns.len(), 1);
+        assert_eq!(
+            (
+                without_reruns[0].parent_job_id.as_str(),
+                without_reruns[0].child_job_id.as_str()
+            ),
+            ("plan_a", "merge")
+        );
+
+        assert_eq!(
+            repo.get_replay_pairs("replay-1").await.unwrap(),
+            vec![("plan_a".to_string(), "plan_a_rerun".to_string())]
+        );
+    }
+}
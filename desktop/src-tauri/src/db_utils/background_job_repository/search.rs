@@ -0,0 +1,16 @@
+use super::base::BackgroundJobRepository;
+use crate::db_utils::search_repository::SearchRepository;
+use log::warn;
+
+impl BackgroundJobRepository {
+    /// Refresh the job's full-text search entry after a status or response change.
+    /// Only completed jobs are indexed; failures are logged and never fail the caller.
+    pub(super) async fn refresh_search_index(&self, job_id: &str) {
+        if let Err(e) = SearchRepository::new(self.pool.clone())
+            .index_job(job_id)
+            .await
+        {
+            warn!("Failed to update search index for job {}: {}", job_id, e);
+        }
+    }
+}
@@ -36,6 +36,8 @@ impl BackgroundJobRepository {
         result
             .map_err(|e| AppError::DatabaseError(format!("Failed to update job status: {}", e)))?;
 
+        self.refresh_search_index(job_id).await;
+
         if let Some(app_handle) = &self.app_handle {
             if let Ok(Some(job)) = self.get_job_by_id(job_id).await {
                 emit_job_status_changed(
@@ -80,6 +82,8 @@ impl BackgroundJobRepository {
             AppError::DatabaseError(format!("Failed to update job status with metadata: {}", e))
         })?;
 
+        self.refresh_search_index(job_id).await;
+
         if let Some(app_handle) = &self.app_handle {
             if let Ok(Some(job)) = self.get_job_by_id(job_id).await {
                 emit_job_status_changed(
@@ -0,0 +1,451 @@
+use crate::error::{AppError, AppResult};
+use crate::events::AppEventKind;
+use crate::utils::get_timestamp;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Most attempts a hook may make per event, so a dead endpoint cannot back up deliveries for hours
+pub const MAX_HOOK_ATTEMPTS: u32 = 10;
+
+/// Where a hook delivers events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum HookTarget {
+    /// POST the event as JSON to a URL
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    /// Run a command through the platform shell with the event JSON on stdin
+    Shell { command: String },
+}
+
+impl HookTarget {
+    fn type_str(&self) -> &'static str {
+        match self {
+            HookTarget::Webhook { .. } => "webhook",
+            HookTarget::Shell { .. } => "shell",
+        }
+    }
+}
+
+/// A persistent subscription that forwards chosen events outside the app
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationHook {
+    pub id: String,
+    pub name: String,
+    pub events: Vec<AppEventKind>,
+    pub target: HookTarget,
+    pub max_attempts: u32,
+    pub enabled: bool,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl IntegrationHook {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Hook name cannot be empty".to_string(),
+            ));
+        }
+        if self.events.is_empty() {
+            return Err(AppError::ValidationError(
+                "Hook must subscribe to at least one event".to_string(),
+            ));
+        }
+        if self.max_attempts == 0 || self.max_attempts > MAX_HOOK_ATTEMPTS {
+            return Err(AppError::ValidationError(format!(
+                "Max attempts must be between 1 and {}",
+                MAX_HOOK_ATTEMPTS
+            )));
+        }
+        match &self.target {
+            HookTarget::Webhook { url, .. } => {
+                let parsed = url::Url::parse(url).map_err(|e| {
+                    AppError::ValidationError(format!("Invalid webhook URL: {}", e))
+                })?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err(AppError::ValidationError(
+                        "Webhook URL must use http or https".to_string(),
+                    ));
+                }
+            }
+            HookTarget::Shell { command } => {
+                if command.trim().is_empty() {
+                    return Err(AppError::ValidationError(
+                        "Shell command cannot be empty".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One logged delivery attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookDelivery {
+    pub id: i64,
+    pub hook_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempt: u32,
+    pub status: String,
+    pub response_status: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub created_at: i64,
+}
+
+/// Outcome of a single attempt, as recorded in the delivery log
+#[derive(Debug, Clone)]
+pub struct DeliveryAttempt<'a> {
+    pub hook_id: &'a str,
+    pub event_type: &'a str,
+    pub payload: &'a serde_json::Value,
+    pub attempt: u32,
+    pub success: bool,
+    pub response_status: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+#[derive(Clone)]
+pub struct IntegrationHookRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl IntegrationHookRepository {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_hooks(&self) -> AppResult<Vec<IntegrationHook>> {
+        let rows = sqlx::query(
+            "SELECT id, name, events, target_type, target, headers, max_attempts, enabled, created_at, updated_at
+             FROM integration_hooks ORDER BY created_at, name",
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(row_to_hook).collect()
+    }
+
+    pub async fn get_hook(&self, hook_id: &str) -> AppResult<Option<IntegrationHook>> {
+        let row = sqlx::query(
+            "SELECT id, name, events, target_type, target, headers, max_attempts, enabled, created_at, updated_at
+             FROM integration_hooks WHERE id = $1",
+        )
+        .bind(hook_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(row_to_hook).transpose()
+    }
+
+    /// Enabled hooks subscribed to an event type
+    pub async fn hooks_for_event(&self, kind: AppEventKind) -> AppResult<Vec<IntegrationHook>> {
+        let rows = sqlx::query(
+            "SELECT id, name, events, target_type, target, headers, max_attempts, enabled, created_at, updated_at
+             FROM integration_hooks
+             WHERE enabled = 1 AND EXISTS (SELECT 1 FROM json_each(integration_hooks.events) WHERE value = $1)",
+        )
+        .bind(kind.as_str())
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(row_to_hook).collect()
+    }
+
+    /// Insert a hook, or update it in place if the id already exists
+    pub async fn save_hook(&self, hook: &IntegrationHook) -> AppResult<IntegrationHook> {
+        hook.validate()?;
+
+        let events = serde_json::to_string(&hook.events)?;
+        let (target, headers) = match &hook.target {
+            HookTarget::Webhook { url, headers } => {
+                (url.clone(), Some(serde_json::to_string(headers)?))
+            }
+            HookTarget::Shell { command } => (command.clone(), None),
+        };
+        let now = get_timestamp();
+
+        sqlx::query(
+            r#"
+            INSERT INTO integration_hooks
+                (id, name, events, target_type, target, headers, max_attempts, enabled, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                events = excluded.events,
+                target_type = excluded.target_type,
+                target = excluded.target,
+                headers = excluded.headers,
+                max_attempts = excluded.max_attempts,
+                enabled = excluded.enabled,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&hook.id)
+        .bind(hook.name.trim())
+        .bind(events)
+        .bind(hook.target.type_str())
+        .bind(target)
+        .bind(headers)
+        .bind(hook.max_attempts as i64)
+        .bind(hook.enabled)
+        .bind(now)
+        .execute(&*self.pool)
+        .await?;
+
+        self.get_hook(&hook.id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(format!("Hook {} not found", hook.id)))
+    }
+
+    pub async fn delete_hook(&self, hook_id: &str) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM integration_hooks WHERE id = $1")
+            .bind(hook_id)
+            .execute(&*self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn record_delivery(&self, attempt: &DeliveryAttempt<'_>) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO integration_hook_deliveries
+                (hook_id, event_type, payload, attempt, status, response_status, error, duration_ms)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(attempt.hook_id)
+        .bind(attempt.event_type)
+        .bind(attempt.payload.to_string())
+        .bind(attempt.attempt as i64)
+        .bind(if attempt.success { "success" } else { "failed" })
+        .bind(attempt.response_status)
+        .bind(attempt.error.as_deref())
+        .bind(attempt.duration_ms)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent delivery attempts, newest first, optionally for one hook
+    pub async fn list_deliveries(
+        &self,
+        hook_id: Option<&str>,
+        limit: u32,
+    ) -> AppResult<Vec<HookDelivery>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, hook_id, event_type, payload, attempt, status, response_status, error, duration_ms, created_at
+            FROM integration_hook_deliveries
+            WHERE $1 IS NULL OR hook_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(hook_id)
+        .bind(limit as i64)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let payload: String = row.try_get("payload")?;
+                Ok(HookDelivery {
+                    id: row.try_get("id")?,
+                    hook_id: row.try_get("hook_id")?,
+                    event_type: row.try_get("event_type")?,
+                    payload: serde_json::from_str(&payload)
+                        .unwrap_or(serde_json::Value::String(payload)),
+                    attempt: row.try_get::<i64, _>("attempt")? as u32,
+                    status: row.try_get("status")?,
+                    response_status: row.try_get("response_status")?,
+                    error: row.try_get("error")?,
+                    duration_ms: row.try_get("duration_ms")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn prune_deliveries_older_than_days(&self, days: i64) -> AppResult<u64> {
+        let res = sqlx::query(
+            "DELETE FROM integration_hook_deliveries WHERE created_at < strftime('%s','now') - ($1 * 86400)",
+        )
+        .bind(days)
+        .execute(&*self.pool)
+        .await?;
+        Ok(res.rows_affected())
+    }
+}
+
+fn row_to_hook(row: &SqliteRow) -> AppResult<IntegrationHook> {
+    let events_json: String = row.try_get("events")?;
+    let target_type: String = row.try_get("target_type")?;
+    let target_value: String = row.try_get("target")?;
+
+    // Unknown event names (e.g. written by a newer version) are skipped rather than failing the hook
+    let events = serde_json::from_str::<Vec<String>>(&events_json)?
+        .iter()
+        .filter_map(|name| name.parse::<AppEventKind>().ok())
+        .collect();
+
+    let target = match target_type.as_str() {
+        "webhook" => {
+            let headers: Option<String> = row.try_get("headers")?;
+            HookTarget::Webhook {
+                url: target_value,
+                headers: headers
+                    .map(|h| serde_json::from_str(&h))
+                    .transpose()?
+                    .unwrap_or_default(),
+            }
+        }
+        "shell" => HookTarget::Shell {
+            command: target_value,
+        },
+        other => {
+            return Err(AppError::DatabaseError(format!(
+                "Unknown hook target type: {}",
+                other
+            )));
+        }
+    };
+
+    Ok(IntegrationHook {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        events,
+        target,
+        max_attempts: row.try_get::<i64, _>("max_attempts")?.max(1) as u32,
+        enabled: row.try_get("enabled")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::test_pool;
+    use serde_json::json;
+
+    fn webhook(id: &str, events: Vec<AppEventKind>) -> IntegrationHook {
+        IntegrationHook {
+            id: id.to_string(),
+            name: format!("Hook {}", id),
+            events,
+            target: HookTarget::Webhook {
+                url: "https://chat.example.com/hooks/abc".to_string(),
+                headers: BTreeMap::from([("X-Token".to_string(), "secret".to_string())]),
+            },
+            max_attempts: 3,
+            enabled: true,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn saves_hooks_and_logs_deliveries() {
+        let repo = IntegrationHookRepository::new(test_pool().await);
+
+        let plan_hook = repo
+            .save_hook(&webhook("plan", vec![AppEventKind::PlanCreated]))
+            .await
+            .unwrap();
+        assert!(plan_hook.created_at > 0);
+        let mut shell_hook = webhook("shell", vec![AppEventKind::TerminalExited]);
+        shell_hook.target = HookTarget::Shell {
+            command: "notify-send done".to_string(),
+        };
+        repo.save_hook(&shell_hook).await.unwrap();
+
+        let matched = repo
+            .hooks_for_event(AppEventKind::PlanCreated)
+            .await
+            .unwrap();
+        assert_eq!(matched, vec![plan_hook.clone()]);
+
+        // Disabling via upsert removes the hook from matching
+        let mut disabled = plan_hook.clone();
+        disabled.enabled = false;
+        repo.save_hook(&disabled).await.unwrap();
+        assert!(
+            repo.hooks_for_event(AppEventKind::PlanCreated)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(repo.list_hooks().await.unwrap().len(), 2);
+
+        let payload = json!({ "type": "plan.created" });
+        for attempt in 1..=2 {
+            repo.record_delivery(&DeliveryAttempt {
+                hook_id: "plan",
+                event_type: "plan.created",
+                payload: &payload,
+                attempt,
+                success: attempt == 2,
+                response_status: Some(if attempt == 2 { 200 } else { 502 }),
+                error: None,
+                duration_ms: 12,
+            })
+            .await
+            .unwrap();
+        }
+        let deliveries = repo.list_deliveries(Some("plan"), 10).await.unwrap();
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].attempt, 2);
+        assert_eq!(deliveries[0].status, "success");
+        assert_eq!(deliveries[0].payload, payload);
+        assert!(
+            repo.list_deliveries(Some("shell"), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Deleting a hook drops its delivery log
+        assert!(repo.delete_hook("plan").await.unwrap());
+        assert!(repo.list_deliveries(None, 10).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_hooks() {
+        assert!(
+            webhook("ok", vec![AppEventKind::JobFinalized])
+                .validate()
+                .is_ok()
+        );
+        assert!(webhook("none", vec![]).validate().is_err());
+
+        let mut bad_url = webhook("url", vec![AppEventKind::JobFinalized]);
+        bad_url.target = HookTarget::Webhook {
+            url: "file:///etc/passwd".to_string(),
+            headers: BTreeMap::new(),
+        };
+        assert!(bad_url.validate().is_err());
+
+        let mut too_many = webhook("retries", vec![AppEventKind::JobFinalized]);
+        too_many.max_attempts = MAX_HOOK_ATTEMPTS + 1;
+        assert!(too_many.validate().is_err());
+    }
+}
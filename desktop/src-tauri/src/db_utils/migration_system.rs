@@ -496,6 +496,116 @@ impl MigrationSystem {
                     column: "task_history_version".to_string(),
                 }),
             },
+            // Full-text search index over sessions, task history and job outputs
+            MigrationRule {
+                id: "add_search_index".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_search_index.sql".to_string(),
+                description: Some("Add FTS5 search index".to_string()),
+                required: false,
+                priority: 60,
+                run_if_absent_column: None,
+            },
+            // Parent/child links for forked sessions
+            MigrationRule {
+                id: "add_session_lineage".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_session_lineage.sql".to_string(),
+                description: Some("Add session lineage table".to_string()),
+                required: false,
+                priority: 61,
+                run_if_absent_column: None,
+            },
+            // Character-level operations for collaborative task editing
+            MigrationRule {
+                id: "add_task_description_ops".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_task_description_ops.sql".to_string(),
+                description: Some("Add task description CRDT operations".to_string()),
+                required: false,
+                priority: 62,
+                run_if_absent_column: None,
+            },
+            // Explicit job lineage for derivation graphs and re-runs
+            MigrationRule {
+                id: "add_job_provenance".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_job_provenance.sql".to_string(),
+                description: Some("Add job provenance".to_string()),
+                required: false,
+                priority: 63,
+                run_if_absent_column: None,
+            },
+            // Webhook and shell command hooks fired on application events
+            MigrationRule {
+                id: "add_integration_hooks".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_integration_hooks.sql".to_string(),
+                description: Some("Add integration hooks".to_string()),
+                required: false,
+                priority: 64,
+                run_if_absent_column: None,
+            },
+            // Versioned implementation plan edits
+            MigrationRule {
+                id: "add_plan_revisions".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_plan_revisions.sql".to_string(),
+                description: Some("Add plan revisions".to_string()),
+                required: false,
+                priority: 65,
+                run_if_absent_column: None,
+            },
+            // Per-step review state for implementation plans
+            MigrationRule {
+                id: "add_plan_step_reviews".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_plan_step_reviews.sql".to_string(),
+                description: Some("Add plan step reviews".to_string()),
+                required: false,
+                priority: 66,
+                run_if_absent_column: None,
+            },
+            // Multi-repository workspaces
+            MigrationRule {
+                id: "add_workspaces".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_workspaces.sql".to_string(),
+                description: Some("Add workspaces".to_string()),
+                required: false,
+                priority: 67,
+                run_if_absent_column: None,
+            },
+            // Follow-up conversations refining implementation plans
+            MigrationRule {
+                id: "add_plan_conversations".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/add_plan_conversations.sql".to_string(),
+                description: Some("Add plan conversations".to_string()),
+                required: false,
+                priority: 68,
+                run_if_absent_column: None,
+            },
+            // Refinement replies recorded in plan revision history
+            MigrationRule {
+                id: "allow_plan_refinement_revisions".to_string(),
+                from_version: "*".to_string(),
+                to_version: ">=1.0.0".to_string(),
+                migration_file: "migrations/features/allow_plan_refinement_revisions.sql".to_string(),
+                description: Some("Allow refinement plan revisions".to_string()),
+                required: false,
+                priority: 69,
+                run_if_absent_column: None,
+            },
             // Performance indexes for any version upgrading to 1.2.0 or later
             MigrationRule {
                 id: "performance_indexes".to_string(),
@@ -1,22 +1,30 @@
 pub mod background_job_repository;
 pub mod connection_manager;
 pub mod error_log_repository;
+pub mod integration_hook_repository;
 pub mod job_metadata_updates;
 pub mod migration_system;
 pub mod migration_utils;
+pub mod search_repository;
 pub mod session_repository;
 pub mod settings_repository;
 pub mod terminal_repository;
+#[cfg(test)]
+pub mod test_support;
+pub mod workspace_repository;
 
 // Re-export modules
 pub use background_job_repository::BackgroundJobRepository;
 pub use connection_manager::*;
 pub use error_log_repository::ErrorLogRepository;
+pub use integration_hook_repository::IntegrationHookRepository;
 pub use migration_system::MigrationSystem;
 pub use migration_utils::{execute_script_in_transaction, has_column, split_sqlite_script, trigger_exists};
+pub use search_repository::SearchRepository;
 pub use session_repository::SessionRepository;
 pub use settings_repository::SettingsRepository;
 pub use terminal_repository::TerminalRepository;
+pub use workspace_repository::WorkspaceRepository;
 
 use crate::error::{AppError, AppResult};
 use crate::models::{DatabaseInfo, TableInfo};
@@ -0,0 +1,427 @@
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, Sqlite, SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Default markers wrapped around matched terms in snippets
+pub const DEFAULT_HIGHLIGHT_START: &str = "<mark>";
+pub const DEFAULT_HIGHLIGHT_END: &str = "</mark>";
+
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+const MAX_SEARCH_LIMIT: u32 = 200;
+/// Approximate number of tokens shown around each match
+const SNIPPET_TOKENS: i64 = 16;
+
+/// What kind of record a search hit points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSourceType {
+    Session,
+    TaskHistory,
+    Job,
+}
+
+impl SearchSourceType {
+    fn from_db(value: &str) -> AppResult<Self> {
+        match value {
+            "session" => Ok(Self::Session),
+            "task_history" => Ok(Self::TaskHistory),
+            "job" => Ok(Self::Job),
+            other => Err(AppError::DatabaseError(format!(
+                "Unknown search source type: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    pub query: String,
+    /// Restrict to one project (hash of the project directory)
+    pub project_hash: Option<String>,
+    /// Restrict to jobs of these task types; sessions and history are excluded when set
+    #[serde(default)]
+    pub task_types: Vec<String>,
+    /// Inclusive lower bound on creation time (ms)
+    pub from_ms: Option<i64>,
+    /// Inclusive upper bound on creation time (ms)
+    pub to_ms: Option<i64>,
+    pub limit: Option<u32>,
+    pub highlight_start: Option<String>,
+    pub highlight_end: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub source_type: SearchSourceType,
+    pub source_id: String,
+    pub session_id: String,
+    pub session_name: String,
+    pub task_type: Option<String>,
+    pub created_at: i64,
+    /// Matched text with terms wrapped in the highlight markers. The text itself is
+    /// not escaped.
+    pub snippet: String,
+    /// bm25 score, lower is better
+    pub rank: f64,
+}
+
+#[derive(Clone)]
+pub struct SearchRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SearchRepository {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self { pool }
+    }
+
+    /// Full-text search across sessions, task description history and completed jobs.
+    ///
+    /// Only the best-ranked history entry per session is returned, and none when the
+    /// session itself matched, since history entries are mostly near-duplicates.
+    pub async fn search(&self, query: &SearchQuery) -> AppResult<Vec<SearchHit>> {
+        let Some(match_expr) = build_match_expression(&query.query) else {
+            return Ok(Vec::new());
+        };
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+
+        let mut sql = String::from(
+            r#"
+            SELECT d.source_type, d.source_id, d.session_id, s.name AS session_name,
+                   d.task_type, d.created_at,
+                   snippet(search_index, -1, $1, $2, '…', $3) AS snippet,
+                   bm25(search_index, 5.0, 1.0, 0.5) AS rank
+            FROM search_index
+            JOIN search_documents d ON d.id = search_index.rowid
+            JOIN sessions s ON s.id = d.session_id
+            WHERE search_index MATCH $4
+            "#,
+        );
+        let mut param_index = 5;
+
+        if query.project_hash.is_some() {
+            sql.push_str(&format!(" AND d.project_hash = ${}", param_index));
+            param_index += 1;
+        }
+        if !query.task_types.is_empty() {
+            let placeholders: Vec<String> = (0..query.task_types.len())
+                .map(|i| format!("${}", param_index + i))
+                .collect();
+            sql.push_str(&format!(
+                " AND d.source_type = 'job' AND d.task_type IN ({})",
+                placeholders.join(", ")
+            ));
+            param_index += query.task_types.len();
+        }
+        if query.from_ms.is_some() {
+            sql.push_str(&format!(" AND d.created_at >= ${}", param_index));
+            param_index += 1;
+        }
+        if query.to_ms.is_some() {
+            sql.push_str(&format!(" AND d.created_at <= ${}", param_index));
+            param_index += 1;
+        }
+        // Over-fetch so collapsing history entries still fills the page
+        sql.push_str(&format!(" ORDER BY rank LIMIT ${}", param_index));
+
+        let mut query_obj = sqlx::query::<Sqlite>(&sql)
+            .bind(
+                query
+                    .highlight_start
+                    .as_deref()
+                    .unwrap_or(DEFAULT_HIGHLIGHT_START),
+            )
+            .bind(
+                query
+                    .highlight_end
+                    .as_deref()
+                    .unwrap_or(DEFAULT_HIGHLIGHT_END),
+            )
+            .bind(SNIPPET_TOKENS)
+            .bind(match_expr);
+        if let Some(project_hash) = &query.project_hash {
+            query_obj = query_obj.bind(project_hash);
+        }
+        for task_type in &query.task_types {
+            query_obj = query_obj.bind(task_type);
+        }
+        if let Some(from_ms) = query.from_ms {
+            query_obj = query_obj.bind(from_ms);
+        }
+        if let Some(to_ms) = query.to_ms {
+            query_obj = query_obj.bind(to_ms);
+        }
+        query_obj = query_obj.bind(i64::from(limit) * 4);
+
+        let rows = query_obj
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to search: {}", e)))?;
+
+        let mut hits = Vec::with_capacity(rows.len());
+        for row in rows {
+            let source_type: String = row.try_get("source_type")?;
+            hits.push(SearchHit {
+                source_type: SearchSourceType::from_db(&source_type)?,
+                source_id: row.try_get("source_id")?,
+                session_id: row.try_get("session_id")?,
+                session_name: row.try_get("session_name")?,
+                task_type: row.try_get("task_type")?,
+                created_at: row.try_get("created_at")?,
+                snippet: row.try_get("snippet")?,
+                rank: row.try_get("rank")?,
+            });
+        }
+
+        let mut hits = collapse_history_hits(hits);
+        hits.truncate(limit as usize);
+        Ok(hits)
+    }
+
+    /// (Re)index a single job; no-op unless the job is completed
+    pub async fn index_job(&self, job_id: &str) -> AppResult<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        index_job(&mut tx, job_id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Replace the search entry for a job inside the caller's transaction.
+///
+/// Jobs are indexed here rather than by trigger so that streaming updates to
+/// `response` don't rewrite the index on every chunk.
+pub(crate) async fn index_job(conn: &mut SqliteConnection, job_id: &str) -> AppResult<()> {
+    let map_err = |e: sqlx::Error| {
+        AppError::DatabaseError(format!("Failed to index job {} for search: {}", job_id, e))
+    };
+
+    sqlx::query(
+        "DELETE FROM search_index WHERE rowid IN (
+            SELECT id FROM search_documents WHERE source_type = 'job' AND source_id = $1
+        )",
+    )
+    .bind(job_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(map_err)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO search_documents (source_type, source_id, session_id, project_hash, task_type, created_at)
+        SELECT 'job', j.id, s.id, s.project_hash, j.task_type, j.created_at
+        FROM background_jobs j JOIN sessions s ON s.id = j.session_id
+        WHERE j.id = $1 AND j.status IN ('completed', 'completed_by_tag')
+        ON CONFLICT(source_type, source_id) DO UPDATE SET
+            project_hash = excluded.project_hash,
+            task_type = excluded.task_type
+        "#,
+    )
+    .bind(job_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(map_err)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO search_index (rowid, title, content, prompt)
+        SELECT d.id, s.name, COALESCE(j.response, ''), j.prompt
+        FROM search_documents d
+        JOIN background_jobs j ON j.id = d.source_id
+        JOIN sessions s ON s.id = d.session_id
+        WHERE d.source_type = 'job' AND d.source_id = $1
+          AND j.status IN ('completed', 'completed_by_tag')
+        "#,
+    )
+    .bind(job_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(map_err)?;
+
+    Ok(())
+}
+
+/// Turn free text into an FTS5 expression: every word must match, and the last one
+/// is treated as a prefix so results update while typing. Returns `None` when the
+/// input has no searchable characters.
+fn build_match_expression(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"", term))
+        .collect();
+
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+fn collapse_history_hits(hits: Vec<SearchHit>) -> Vec<SearchHit> {
+    let matched_sessions: HashSet<String> = hits
+        .iter()
+        .filter(|hit| hit.source_type == SearchSourceType::Session)
+        .map(|hit| hit.session_id.clone())
+        .collect();
+    let mut seen_history = HashSet::new();
+
+    hits.into_iter()
+        .filter(|hit| {
+            hit.source_type != SearchSourceType::TaskHistory
+                || (!matched_sessions.contains(&hit.session_id)
+                    && seen_history.insert(hit.session_id.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{
+        insert_job, insert_session, test_job, test_pool, test_session,
+    };
+    use crate::db_utils::{BackgroundJobRepository, SessionRepository};
+    use crate::models::{BackgroundJob, Session};
+    use crate::utils::hash_utils::hash_string;
+
+    fn described_session(id: &str, project_directory: &str, description: &str) -> Session {
+        Session {
+            task_description: Some(description.to_string()),
+            ..test_session(id, project_directory)
+        }
+    }
+
+    fn running_job(id: &str, session_id: &str, task_type: &str, response: &str) -> BackgroundJob {
+        BackgroundJob {
+            status: "running".to_string(),
+            prompt: "Write a plan".to_string(),
+            response: Some(response.to_string()),
+            ..test_job(id, session_id, task_type)
+        }
+    }
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn sessions_and_history_are_indexed_by_triggers() {
+        let pool = test_pool().await;
+        let sessions = SessionRepository::new(pool.clone());
+        let search = SearchRepository::new(pool.clone());
+
+        let session = described_session("s1", "/work/shop", "Migrate the billing table");
+        insert_session(&pool, &session).await;
+        sessions
+            .sync_task_description_history(
+                &session.id,
+                &[
+                    "Migrate billing".to_string(),
+                    "Migrate the billing table".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let hits = search.search(&query("billing")).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_type, SearchSourceType::Session);
+        assert!(hits[0].snippet.contains("<mark>billing</mark>"));
+
+        // Prefix match on the last term; the history entry wins once the session no longer matches
+        let mut renamed = session.clone();
+        renamed.task_description = Some("Something else".to_string());
+        sessions.update_session(&renamed).await.unwrap();
+        let hits = search.search(&query("migrate bill")).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_type, SearchSourceType::TaskHistory);
+
+        sessions.delete_session(&session.id).await.unwrap();
+        assert!(search.search(&query("billing")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn completed_jobs_are_indexed_and_filtered() {
+        let pool = test_pool().await;
+        let jobs = BackgroundJobRepository::new(pool.clone());
+        let search = SearchRepository::new(pool.clone());
+
+        insert_session(&pool, &described_session("s1", "/work/shop", "Checkout")).await;
+        insert_session(&pool, &described_session("s2", "/work/blog", "Comments")).await;
+
+        insert_job(
+            &pool,
+            &running_job("plan-1", "s1", "implementation_plan", "Add a ledger table"),
+        )
+        .await;
+        insert_job(
+            &pool,
+            &running_job("plan-2", "s2", "implementation_plan", "Add a ledger view"),
+        )
+        .await;
+        insert_job(
+            &pool,
+            &running_job("search-1", "s1", "web_search_execution", "ledger docs"),
+        )
+        .await;
+
+        // Running jobs are not searchable yet
+        assert!(search.search(&query("ledger")).await.unwrap().is_empty());
+
+        for (id, response) in [
+            ("plan-1", "Add a ledger table"),
+            ("plan-2", "Add a ledger view"),
+            ("search-1", "ledger docs"),
+        ] {
+            jobs.mark_job_completed(id, response, None, None, None, None, None, None, None, None)
+                .await
+                .unwrap();
+        }
+        assert_eq!(search.search(&query("ledger")).await.unwrap().len(), 3);
+
+        let shop = SearchQuery {
+            project_hash: Some(hash_string("/work/shop")),
+            task_types: vec!["implementation_plan".to_string()],
+            ..query("ledger")
+        };
+        let hits = search.search(&shop).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_id, "plan-1");
+        assert_eq!(hits[0].task_type.as_deref(), Some("implementation_plan"));
+
+        let too_late = SearchQuery {
+            from_ms: Some(4_000_000_000_000),
+            ..query("ledger")
+        };
+        assert!(search.search(&too_late).await.unwrap().is_empty());
+
+        jobs.delete_job("plan-1").await.unwrap();
+        assert_eq!(search.search(&query("ledger")).await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn match_expression_quotes_terms_and_prefixes_the_last() {
+        assert_eq!(
+            build_match_expression(r#"billing "table" OR"#).as_deref(),
+            Some(r#""billing" "table" "OR"*"#)
+        );
+        assert_eq!(build_match_expression("  -- ** "), None);
+    }
+}
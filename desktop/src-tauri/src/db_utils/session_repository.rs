@@ -2,8 +2,9 @@ use crate::error::{AppError, AppResult};
 use crate::models::Session;
 use crate::utils::date_utils;
 use crate::utils::hash_utils::sha256_hash;
+use crate::utils::text_crdt::TextOp;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+use sqlx::{Row, SqliteConnection, SqlitePool, sqlite::SqliteRow};
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -51,6 +52,29 @@ pub struct FileHistoryState {
     pub checksum: String,
 }
 
+/// Parent link of a forked session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLineage {
+    pub session_id: String,
+    /// None once the parent session has been deleted
+    pub parent_session_id: Option<String>,
+    pub root_session_id: String,
+    pub forked_task_history_index: i64,
+    pub forked_file_history_index: i64,
+    pub created_at: i64,
+}
+
+/// A session in a fork tree, with its lineage row if it was forked
+#[derive(Debug, Clone)]
+pub struct SessionFamilyMember {
+    pub session_id: String,
+    pub name: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub lineage: Option<SessionLineage>,
+}
+
 #[derive(Debug)]
 pub struct SessionRepository {
     pool: Arc<SqlitePool>,
@@ -233,6 +257,212 @@ impl SessionRepository {
         Ok(())
     }
 
+    /// Insert an imported session together with its history and jobs in one transaction.
+    /// Ids and project paths must already be remapped for this checkout.
+    pub async fn import_session_bundle(
+        &self,
+        session: &Session,
+        task_history: &TaskHistoryState,
+        file_history: &FileHistoryState,
+        jobs: &[crate::models::BackgroundJob],
+    ) -> AppResult<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        insert_session_with_history(&mut tx, session, task_history, file_history).await?;
+
+        for job in jobs {
+            sqlx::query(
+                r#"
+                INSERT INTO background_jobs (
+                    id, session_id, task_type, status, prompt, response, error_message,
+                    tokens_sent, tokens_received, cache_write_tokens, cache_read_tokens,
+                    model_used, actual_cost, metadata, system_prompt_template,
+                    created_at, updated_at, start_time, end_time, is_finalized
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+                "#,
+            )
+            .bind(&job.id)
+            .bind(&session.id)
+            .bind(&job.task_type)
+            .bind(&job.status)
+            .bind(&job.prompt)
+            .bind(&job.response)
+            .bind(&job.error_message)
+            .bind(job.tokens_sent.map(|v| v as i64))
+            .bind(job.tokens_received.map(|v| v as i64))
+            .bind(job.cache_write_tokens)
+            .bind(job.cache_read_tokens)
+            .bind(&job.model_used)
+            .bind(job.actual_cost)
+            .bind(&job.metadata)
+            .bind(&job.system_prompt_template)
+            .bind(job.created_at)
+            .bind(job.updated_at)
+            .bind(job.start_time)
+            .bind(job.end_time)
+            .bind(job.is_finalized)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to insert imported job {}: {}", job.id, e))
+            })?;
+
+            crate::db_utils::search_repository::index_job(&mut tx, &job.id).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Insert a forked session with its (truncated) history and lineage in one transaction
+    pub async fn create_forked_session(
+        &self,
+        session: &Session,
+        task_history: &TaskHistoryState,
+        file_history: &FileHistoryState,
+        lineage: &SessionLineage,
+    ) -> AppResult<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        insert_session_with_history(&mut tx, session, task_history, file_history).await?;
+
+        sqlx::query(
+            "INSERT INTO session_lineage (session_id, parent_session_id, root_session_id, forked_task_history_index, forked_file_history_index, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&lineage.session_id)
+        .bind(&lineage.parent_session_id)
+        .bind(&lineage.root_session_id)
+        .bind(lineage.forked_task_history_index)
+        .bind(lineage.forked_file_history_index)
+        .bind(lineage.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to insert session lineage: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Lineage of a forked session; None for sessions that were not forked
+    pub async fn get_session_lineage(&self, session_id: &str) -> AppResult<Option<SessionLineage>> {
+        let row = sqlx::query("SELECT * FROM session_lineage WHERE session_id = $1")
+            .bind(session_id)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch session lineage: {}", e)))?;
+
+        row.as_ref().map(row_to_lineage).transpose()
+    }
+
+    /// The root session and every session forked from it, directly or transitively
+    pub async fn get_session_family(&self, root_session_id: &str) -> AppResult<Vec<SessionFamilyMember>> {
+        let rows = sqlx::query(
+            "SELECT s.id, s.name, s.created_at, s.updated_at,
+                    l.session_id AS lineage_session_id, l.parent_session_id, l.root_session_id,
+                    l.forked_task_history_index, l.forked_file_history_index, l.created_at AS forked_at
+             FROM sessions s
+             LEFT JOIN session_lineage l ON l.session_id = s.id
+             WHERE s.id = $1 OR l.root_session_id = $1
+             ORDER BY s.created_at ASC",
+        )
+        .bind(root_session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch session family: {}", e)))?;
+
+        let mut members = Vec::with_capacity(rows.len());
+        for row in rows {
+            let lineage_session_id: Option<String> = row.try_get("lineage_session_id")?;
+            let lineage = match lineage_session_id {
+                Some(session_id) => Some(SessionLineage {
+                    session_id,
+                    parent_session_id: row.try_get("parent_session_id")?,
+                    root_session_id: row.try_get("root_session_id")?,
+                    forked_task_history_index: row.try_get("forked_task_history_index")?,
+                    forked_file_history_index: row.try_get("forked_file_history_index")?,
+                    created_at: row.try_get("forked_at")?,
+                }),
+                None => None,
+            };
+            members.push(SessionFamilyMember {
+                session_id: row.try_get("id")?,
+                name: row.try_get("name")?,
+                created_at: row.try_get("created_at")?,
+                updated_at: row.try_get("updated_at")?,
+                lineage,
+            });
+        }
+
+        Ok(members)
+    }
+
+    /// Persist text CRDT operations for a session's task description.
+    /// Operations already stored (same replica and counter) are ignored.
+    pub async fn append_task_description_ops(&self, session_id: &str, ops: &[TextOp]) -> AppResult<()> {
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        let now = date_utils::get_timestamp();
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to begin transaction: {}", e)))?;
+
+        for op in ops {
+            let id = op.id();
+            let encoded = serde_json::to_string(op)
+                .map_err(|e| AppError::SerializationError(format!("Failed to encode task op: {}", e)))?;
+            sqlx::query(
+                "INSERT OR IGNORE INTO task_description_ops (session_id, replica_id, counter, op, created_at)
+                 VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(session_id)
+            .bind(&id.replica)
+            .bind(id.counter as i64)
+            .bind(encoded)
+            .bind(now)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to store task op: {}", e)))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit task ops: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// All stored text CRDT operations of a session in causal (Lamport) order
+    pub async fn get_task_description_ops(&self, session_id: &str) -> AppResult<Vec<TextOp>> {
+        let rows = sqlx::query(
+            "SELECT op FROM task_description_ops WHERE session_id = $1 ORDER BY counter ASC, replica_id ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch task ops: {}", e)))?;
+
+        rows.iter()
+            .map(|row| {
+                let encoded: String = row.try_get("op")?;
+                serde_json::from_str(&encoded)
+                    .map_err(|e| AppError::SerializationError(format!("Failed to decode task op: {}", e)))
+            })
+            .collect()
+    }
+
     /// Update an existing session with conflict-aware merge
     /// If DB has been updated since client's last read (updated_at comparison),
     /// merge changes: excluded_final = db_excluded ∪ client_excluded,
@@ -1655,6 +1885,103 @@ fn validate_file_history_entries(
     (trimmed, clamped_index)
 }
 
+fn row_to_lineage(row: &SqliteRow) -> AppResult<SessionLineage> {
+    Ok(SessionLineage {
+        session_id: row.try_get("session_id")?,
+        parent_session_id: row.try_get("parent_session_id")?,
+        root_session_id: row.try_get("root_session_id")?,
+        forked_task_history_index: row.try_get("forked_task_history_index")?,
+        forked_file_history_index: row.try_get("forked_file_history_index")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+/// Insert a new session row together with its task and file history.
+/// The history version and current index columns are taken from the given states.
+async fn insert_session_with_history(
+    conn: &mut SqliteConnection,
+    session: &Session,
+    task_history: &TaskHistoryState,
+    file_history: &FileHistoryState,
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO sessions (
+            id, name, project_directory, project_hash,
+            task_description, search_term, search_selected_files_only, model_used,
+            included_files, force_excluded_files,
+            created_at, updated_at, video_analysis_prompt, merge_instructions,
+            task_history_version, task_history_current_index,
+            file_history_version, file_history_current_index
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+        "#,
+    )
+    .bind(&session.id)
+    .bind(&session.name)
+    .bind(&session.project_directory)
+    .bind(&session.project_hash)
+    .bind(&session.task_description)
+    .bind(&session.search_term)
+    .bind(if session.search_selected_files_only {
+        1i64
+    } else {
+        0i64
+    })
+    .bind(&session.model_used)
+    .bind(session.included_files.join("\n"))
+    .bind(session.force_excluded_files.join("\n"))
+    .bind(session.created_at)
+    .bind(session.updated_at)
+    .bind(&session.video_analysis_prompt)
+    .bind(&session.merge_instructions)
+    .bind(task_history.version)
+    .bind(task_history.current_index)
+    .bind(file_history.version)
+    .bind(file_history.current_index)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| AppError::DatabaseError(format!("Failed to insert session: {}", e)))?;
+
+    for entry in &task_history.entries {
+        sqlx::query(
+            "INSERT INTO task_description_history (session_id, description, created_at, device_id, sequence_number, version)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&session.id)
+        .bind(&entry.description)
+        .bind(entry.created_at)
+        .bind(&entry.device_id)
+        .bind(entry.sequence_number)
+        .bind(entry.version)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to insert task history: {}", e))
+        })?;
+    }
+
+    for entry in &file_history.entries {
+        sqlx::query(
+            "INSERT INTO file_selection_history (session_id, included_files, force_excluded_files, created_at, device_id, sequence_number, version)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(&session.id)
+        .bind(&entry.included_files)
+        .bind(&entry.force_excluded_files)
+        .bind(entry.created_at)
+        .bind(&entry.device_id)
+        .bind(entry.sequence_number)
+        .bind(entry.version)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to insert file history: {}", e))
+        })?;
+    }
+
+    Ok(())
+}
+
 fn compute_task_history_checksum(entries: &[TaskHistoryEntry], current_index: i64, version: i64) -> String {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -1,5 +1,7 @@
 use crate::error::{AppError, AppResult};
+use crate::jobs::retry_utils::RetryPolicyOverrides;
 use crate::models::{DeviceSettings, ProjectSystemPrompt, Settings};
+use crate::remote_api::local_server::LocalApiConfig;
 use crate::services::BackupConfig;
 use crate::utils::get_timestamp;
 use serde::{Deserialize, Serialize};
@@ -201,6 +203,48 @@ impl SettingsRepository {
         self.set_value("backup_config", &json_str).await
     }
 
+    /// Get local API configuration, disabled by default
+    pub async fn get_local_api_config(&self) -> AppResult<LocalApiConfig> {
+        match self.get_value("local_api_config").await? {
+            Some(json_str) => serde_json::from_str(&json_str).map_err(|e| {
+                AppError::SerializationError(format!(
+                    "Failed to deserialize local API config: {}",
+                    e
+                ))
+            }),
+            None => Ok(LocalApiConfig::default()),
+        }
+    }
+
+    /// Save local API configuration
+    pub async fn save_local_api_config(&self, config: &LocalApiConfig) -> AppResult<()> {
+        let json_str = serde_json::to_string(config).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize local API config: {}", e))
+        })?;
+        self.set_value("local_api_config", &json_str).await
+    }
+
+    /// Get per-task-type job retry policy overrides, empty when none are configured
+    pub async fn get_job_retry_policies(&self) -> AppResult<RetryPolicyOverrides> {
+        match self.get_value("job_retry_policies").await? {
+            Some(json_str) => serde_json::from_str(&json_str).map_err(|e| {
+                AppError::SerializationError(format!(
+                    "Failed to deserialize job retry policies: {}",
+                    e
+                ))
+            }),
+            None => Ok(RetryPolicyOverrides::new()),
+        }
+    }
+
+    /// Save per-task-type job retry policy overrides
+    pub async fn save_job_retry_policies(&self, policies: &RetryPolicyOverrides) -> AppResult<()> {
+        let json_str = serde_json::to_string(policies).map_err(|e| {
+            AppError::SerializationError(format!("Failed to serialize job retry policies: {}", e))
+        })?;
+        self.set_value("job_retry_policies", &json_str).await
+    }
+
     /// Get workflow setting value
     pub async fn get_workflow_setting(
         &self,
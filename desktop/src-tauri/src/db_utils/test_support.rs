@@ -0,0 +1,86 @@
+//! Fixtures for tests that run against the full schema in an in-memory database
+
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use sqlx::sqlite::SqlitePoolOptions;
+
+use crate::app_setup::embedded_schema::CONSOLIDATED_SCHEMA;
+use crate::db_utils::{BackgroundJobRepository, SessionRepository, execute_script_in_transaction};
+use crate::models::{BackgroundJob, Session};
+use crate::utils::hash_utils::hash_string;
+
+/// A single-connection in-memory database with the consolidated schema applied
+pub async fn test_pool() -> Arc<SqlitePool> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    execute_script_in_transaction(&pool, CONSOLIDATED_SCHEMA)
+        .await
+        .unwrap();
+    Arc::new(pool)
+}
+
+/// A session without task description or files; override fields with struct update syntax
+pub fn test_session(id: &str, project_directory: &str) -> Session {
+    Session {
+        id: id.to_string(),
+        name: format!("Session {}", id),
+        project_directory: project_directory.to_string(),
+        project_hash: hash_string(project_directory),
+        task_description: None,
+        search_term: None,
+        search_selected_files_only: false,
+        model_used: None,
+        created_at: 1_700_000_000_000,
+        updated_at: 1_700_000_000_000,
+        included_files: vec![],
+        force_excluded_files: vec![],
+        video_analysis_prompt: None,
+        merge_instructions: None,
+    }
+}
+
+/// A completed job without response, usage or metadata, created after [`test_session`]
+pub fn test_job(id: &str, session_id: &str, task_type: &str) -> BackgroundJob {
+    BackgroundJob {
+        id: id.to_string(),
+        session_id: session_id.to_string(),
+        task_type: task_type.to_string(),
+        status: "completed".to_string(),
+        prompt: "prompt".to_string(),
+        response: None,
+        error_message: None,
+        tokens_sent: None,
+        tokens_received: None,
+        cache_write_tokens: None,
+        cache_read_tokens: None,
+        model_used: None,
+        actual_cost: None,
+        duration_ms: None,
+        metadata: None,
+        system_prompt_template: None,
+        created_at: 1_700_000_100_000,
+        updated_at: None,
+        start_time: None,
+        end_time: None,
+        is_finalized: None,
+        error_details: None,
+    }
+}
+
+pub async fn insert_session(pool: &Arc<SqlitePool>, session: &Session) {
+    SessionRepository::new(pool.clone())
+        .create_session(session)
+        .await
+        .unwrap();
+}
+
+pub async fn insert_job(pool: &Arc<SqlitePool>, job: &BackgroundJob) {
+    BackgroundJobRepository::new(pool.clone())
+        .create_job(job)
+        .await
+        .unwrap();
+}
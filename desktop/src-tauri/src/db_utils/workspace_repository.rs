@@ -0,0 +1,347 @@
+use crate::error::{AppError, AppResult};
+use crate::utils::get_timestamp;
+use crate::utils::workspace_paths::is_valid_repo_label;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A project directory in a workspace, with the label its files are qualified with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub label: String,
+    pub project_directory: String,
+}
+
+/// A group of project directories (e.g. backend, frontend and shared schema repositories)
+/// that sessions and plans can span
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    /// In display order
+    pub members: Vec<WorkspaceMember>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+fn normalize_directory(directory: &str) -> String {
+    let trimmed = directory.trim();
+    match trimmed.trim_end_matches(['/', '\\']) {
+        "" => trimmed.to_string(),
+        normalized => normalized.to_string(),
+    }
+}
+
+impl Workspace {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Workspace name cannot be empty".to_string(),
+            ));
+        }
+        if self.members.is_empty() {
+            return Err(AppError::ValidationError(
+                "Workspace must contain at least one project directory".to_string(),
+            ));
+        }
+
+        let mut labels = HashSet::new();
+        let mut directories = HashSet::new();
+        for member in &self.members {
+            if !is_valid_repo_label(&member.label) {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid repository label '{}': use 2-32 lowercase letters, digits, '-' or '_'",
+                    member.label
+                )));
+            }
+            if !labels.insert(member.label.as_str()) {
+                return Err(AppError::ValidationError(format!(
+                    "Repository label '{}' is used twice",
+                    member.label
+                )));
+            }
+            let directory = normalize_directory(&member.project_directory);
+            if directory.is_empty() {
+                return Err(AppError::ValidationError(format!(
+                    "Repository '{}' has no project directory",
+                    member.label
+                )));
+            }
+            if !directories.insert(directory) {
+                return Err(AppError::ValidationError(format!(
+                    "Project directory {} is added twice",
+                    member.project_directory
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct WorkspaceRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl WorkspaceRepository {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_workspaces(&self) -> AppResult<Vec<Workspace>> {
+        let rows = sqlx::query(
+            "SELECT id, name, created_at, updated_at FROM workspaces ORDER BY name, created_at",
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let mut workspaces = Vec::with_capacity(rows.len());
+        for row in &rows {
+            workspaces.push(self.row_to_workspace(row).await?);
+        }
+        Ok(workspaces)
+    }
+
+    pub async fn get_workspace(&self, workspace_id: &str) -> AppResult<Option<Workspace>> {
+        let row =
+            sqlx::query("SELECT id, name, created_at, updated_at FROM workspaces WHERE id = $1")
+                .bind(workspace_id)
+                .fetch_optional(&*self.pool)
+                .await?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_workspace(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The workspace a project directory belongs to, if any
+    pub async fn find_workspace_for_directory(
+        &self,
+        project_directory: &str,
+    ) -> AppResult<Option<Workspace>> {
+        let workspace_id: Option<String> = sqlx::query_scalar(
+            "SELECT workspace_id FROM workspace_repositories WHERE project_directory = $1",
+        )
+        .bind(normalize_directory(project_directory))
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        match workspace_id {
+            Some(id) => self.get_workspace(&id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Insert a workspace, or update it in place if the id already exists. Members are
+    /// replaced as a whole.
+    pub async fn save_workspace(&self, workspace: &Workspace) -> AppResult<Workspace> {
+        workspace.validate()?;
+        let now = get_timestamp();
+        let mut tx = self.pool.begin().await?;
+
+        for member in &workspace.members {
+            let directory = normalize_directory(&member.project_directory);
+            let other: Option<String> = sqlx::query_scalar(
+                r#"
+                SELECT w.name FROM workspace_repositories r
+                JOIN workspaces w ON w.id = r.workspace_id
+                WHERE r.project_directory = $1 AND r.workspace_id != $2
+                "#,
+            )
+            .bind(&directory)
+            .bind(&workspace.id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(other) = other {
+                return Err(AppError::ValidationError(format!(
+                    "{} already belongs to workspace '{}'",
+                    directory, other
+                )));
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO workspaces (id, name, created_at, updated_at)
+            VALUES ($1, $2, $3, $3)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&workspace.id)
+        .bind(workspace.name.trim())
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM workspace_repositories WHERE workspace_id = $1")
+            .bind(&workspace.id)
+            .execute(&mut *tx)
+            .await?;
+        for (position, member) in workspace.members.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO workspace_repositories (workspace_id, label, project_directory, position)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(&workspace.id)
+            .bind(&member.label)
+            .bind(normalize_directory(&member.project_directory))
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        self.get_workspace(&workspace.id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(format!("Workspace {} not found", workspace.id)))
+    }
+
+    pub async fn delete_workspace(&self, workspace_id: &str) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM workspace_repositories WHERE workspace_id = $1")
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM workspaces WHERE id = $1")
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn row_to_workspace(&self, row: &SqliteRow) -> AppResult<Workspace> {
+        let id: String = row.try_get("id")?;
+        let members = sqlx::query(
+            r#"
+            SELECT label, project_directory FROM workspace_repositories
+            WHERE workspace_id = $1 ORDER BY position, label
+            "#,
+        )
+        .bind(&id)
+        .fetch_all(&*self.pool)
+        .await?
+        .iter()
+        .map(|member| {
+            Ok(WorkspaceMember {
+                label: member.try_get("label")?,
+                project_directory: member.try_get("project_directory")?,
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(Workspace {
+            id,
+            name: row.try_get("name")?,
+            members,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::test_pool;
+
+    fn workspace(id: &str, members: &[(&str, &str)]) -> Workspace {
+        Workspace {
+            id: id.to_string(),
+            name: format!("Workspace {}", id),
+            members: members
+                .iter()
+                .map(|(label, directory)| WorkspaceMember {
+                    label: label.to_string(),
+                    project_directory: directory.to_string(),
+                })
+                .collect(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn saves_workspaces_and_finds_them_by_member_directory() {
+        let repo = WorkspaceRepository::new(test_pool().await);
+
+        let saved = repo
+            .save_workspace(&workspace(
+                "shop",
+                &[("backend", "/src/backend/"), ("frontend", "/src/frontend")],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(saved.members[0].project_directory, "/src/backend");
+        assert!(saved.created_at > 0);
+
+        let found = repo
+            .find_workspace_for_directory("/src/frontend/")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, "shop");
+        assert_eq!(
+            found
+                .members
+                .iter()
+                .map(|m| m.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["backend", "frontend"]
+        );
+
+        // Members are replaced as a whole
+        repo.save_workspace(&workspace("shop", &[("schema", "/src/schema")]))
+            .await
+            .unwrap();
+        assert!(
+            repo.find_workspace_for_directory("/src/backend")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // A directory belongs to at most one workspace
+        let err = repo
+            .save_workspace(&workspace("other", &[("schema", "/src/schema")]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::ValidationError(_)));
+
+        assert!(repo.delete_workspace("shop").await.unwrap());
+        assert!(repo.list_workspaces().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_labels_and_duplicates() {
+        assert!(
+            workspace("w", &[("api", "/a"), ("web", "/b")])
+                .validate()
+                .is_ok()
+        );
+        assert!(workspace("w", &[("C", "/a")]).validate().is_err());
+        assert!(
+            workspace("w", &[("api", "/a"), ("api", "/b")])
+                .validate()
+                .is_err()
+        );
+        assert!(
+            workspace("w", &[("api", "/a"), ("web", "/a/")])
+                .validate()
+                .is_err()
+        );
+        assert!(workspace("w", &[]).validate().is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize, Serializer};
 use std::fmt;
+use std::time::Duration;
 use tauri::Manager;
 use thiserror::Error;
 
@@ -17,8 +18,14 @@ pub enum AppError {
     #[error("OpenRouter API error: {0}")]
     OpenRouterError(String),
 
-    #[error("Server proxy error: {0}")]
-    ServerProxyError(String),
+    #[error("Server proxy error: {message}")]
+    ServerProxyError {
+        message: String,
+        /// HTTP status of the failed response, when the error came from one
+        status: Option<u16>,
+        /// Server-provided Retry-After hint
+        retry_after: Option<Duration>,
+    },
 
     #[error("HTTP client error: {0}")]
     HttpError(String),
@@ -170,8 +177,12 @@ pub enum AppError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
-    #[error("Too many requests: {0}")]
-    TooManyRequests(String),
+    #[error("Too many requests: {message}")]
+    TooManyRequests {
+        message: String,
+        /// Server-provided Retry-After hint
+        retry_after: Option<Duration>,
+    },
 
     #[error("Not implemented: {0}")]
     NotImplemented(String),
@@ -288,7 +299,7 @@ impl From<AppError> for SerializableError {
             AppError::SerdeError(_) => "SERDE_ERROR",
             AppError::DatabaseError(_) => "DATABASE_ERROR",
             AppError::OpenRouterError(_) => "OPENROUTER_ERROR",
-            AppError::ServerProxyError(_) => "SERVER_PROXY_ERROR",
+            AppError::ServerProxyError { .. } => "SERVER_PROXY_ERROR",
             AppError::HttpError(_) => "HTTP_ERROR",
             AppError::TauriError(_) => "TAURI_ERROR",
             AppError::KeyringError(_) => "KEYRING_ERROR",
@@ -337,7 +348,7 @@ impl From<AppError> for SerializableError {
             AppError::Unauthorized(_) => "UNAUTHORIZED",
             AppError::Forbidden(_) => "FORBIDDEN",
             AppError::BadRequest(_) => "BAD_REQUEST",
-            AppError::TooManyRequests(_) => "TOO_MANY_REQUESTS",
+            AppError::TooManyRequests { .. } => "TOO_MANY_REQUESTS",
             AppError::NotImplemented(_) => "NOT_IMPLEMENTED",
             AppError::LockPoisoned(_) => "LOCK_POISONED",
             AppError::TaskInitiationFailed(_) => "TASK_INITIATION_FAILED",
@@ -561,3 +572,234 @@ impl AppError {
         AppError::ConfigurationError(message)
     }
 }
+
+/// Coarse classification of an error, used by the job system to decide whether a failure is
+/// worth retrying and whether it indicates the server is under pressure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    /// HTTP 429 or an explicit rate-limit error from the server
+    RateLimited,
+    /// HTTP 5xx, or the AI provider reporting it is overloaded or unavailable
+    ProviderOverloaded,
+    /// Connection failures, timeouts and dropped streams
+    TransientNetwork,
+    /// The request itself is invalid; repeating it will fail the same way
+    BadRequest,
+    /// Authentication or authorization failure
+    Auth,
+    /// Credits, payment or spending-limit problems
+    Billing,
+    /// Local configuration or data errors that a retry cannot fix
+    Permanent,
+    /// Anything not covered above
+    Other,
+}
+
+impl ErrorClass {
+    /// Whether the server is signalling it cannot take more work right now
+    pub fn is_server_pressure(self) -> bool {
+        matches!(
+            self,
+            ErrorClass::RateLimited | ErrorClass::ProviderOverloaded
+        )
+    }
+}
+
+impl AppError {
+    /// Create a rate-limit error, keeping the server's Retry-After hint when present
+    pub fn rate_limited(message: impl Into<String>, retry_after: Option<Duration>) -> Self {
+        AppError::TooManyRequests {
+            message: message.into(),
+            retry_after,
+        }
+    }
+
+    /// Create a server proxy error that did not come from an HTTP response status
+    pub fn server_proxy(message: impl Into<String>) -> Self {
+        AppError::ServerProxyError {
+            message: message.into(),
+            status: None,
+            retry_after: None,
+        }
+    }
+
+    /// Create a server proxy error for a failed response with the given HTTP status
+    pub fn server_proxy_status(status: u16, message: impl Into<String>) -> Self {
+        AppError::ServerProxyError {
+            message: message.into(),
+            status: Some(status),
+            retry_after: None,
+        }
+    }
+
+    /// HTTP status of the failed response behind this error, if known
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            AppError::ServerProxyError { status, .. } => *status,
+            AppError::TooManyRequests { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// How long the server asked us to wait before retrying, if it said so
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            AppError::ServerProxyError { retry_after, .. }
+            | AppError::TooManyRequests { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Classify this error for retry and circuit-breaker decisions
+    pub fn classify(&self) -> ErrorClass {
+        match self {
+            AppError::TooManyRequests { .. } => ErrorClass::RateLimited,
+            AppError::AuthError(_)
+            | AppError::Unauthorized(_)
+            | AppError::Forbidden(_)
+            | AppError::AccessDenied(_) => ErrorClass::Auth,
+            AppError::BillingError(_)
+            | AppError::PaymentFailed(_)
+            | AppError::PaymentDeclined(_)
+            | AppError::PaymentAuthenticationRequired(_)
+            | AppError::BillingExpired(_)
+            | AppError::BillingCancelled(_)
+            | AppError::CreditInsufficient(_)
+            | AppError::CreditPurchaseRequired(_)
+            | AppError::PaymentMethodRequired(_)
+            | AppError::BillingAddressRequired(_)
+            | AppError::BillingConflict(_)
+            | AppError::SpendingLimitExceeded(_)
+            | AppError::InvoiceError(_)
+            | AppError::StripeError(_)
+            | AppError::CheckoutError(_)
+            | AppError::PaymentRequired(_)
+            | AppError::PaymentError(_) => ErrorClass::Billing,
+            AppError::BadRequest(_)
+            | AppError::ValidationError(_)
+            | AppError::InvalidArgument(_)
+            | AppError::TokenLimitExceededError(_) => ErrorClass::BadRequest,
+            AppError::ConfigError(_)
+            | AppError::ConfigurationError(_)
+            | AppError::InvalidTaskTypeError(_)
+            | AppError::SerializationError(_)
+            | AppError::NotFoundError(_)
+            | AppError::InvalidPath(_) => ErrorClass::Permanent,
+            AppError::ServerProxyError {
+                status: Some(status),
+                ..
+            } => classify_status(*status),
+            AppError::NetworkError(msg)
+            | AppError::HttpError(msg)
+            | AppError::StreamError(msg)
+            | AppError::ServerProxyError { message: msg, .. }
+            | AppError::OpenRouterError(msg)
+            | AppError::ExternalServiceError(msg) => self.classify_remote(msg),
+            _ => ErrorClass::Other,
+        }
+    }
+
+    /// Classify a remote failure that carries no HTTP status by what the provider reported
+    fn classify_remote(&self, message: &str) -> ErrorClass {
+        let lower = message.to_lowercase();
+        if lower.contains("rate limit") || lower.contains("too many requests") {
+            ErrorClass::RateLimited
+        } else if lower.contains("overloaded")
+            || lower.contains("service unavailable")
+            || lower.contains("server error")
+            || lower.contains("internal error")
+        {
+            ErrorClass::ProviderOverloaded
+        } else if lower.starts_with("bad request") {
+            ErrorClass::BadRequest
+        } else if matches!(
+            self,
+            AppError::NetworkError(_) | AppError::HttpError(_) | AppError::StreamError(_)
+        ) {
+            ErrorClass::TransientNetwork
+        } else {
+            ErrorClass::Other
+        }
+    }
+}
+
+fn classify_status(status: u16) -> ErrorClass {
+    match status {
+        429 => ErrorClass::RateLimited,
+        408 => ErrorClass::TransientNetwork,
+        401 | 403 => ErrorClass::Auth,
+        402 => ErrorClass::Billing,
+        400..=499 => ErrorClass::BadRequest,
+        500..=599 => ErrorClass::ProviderOverloaded,
+        _ => ErrorClass::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_response_statuses() {
+        assert_eq!(
+            AppError::server_proxy_status(429, "Slow down").classify(),
+            ErrorClass::RateLimited
+        );
+        assert_eq!(
+            AppError::server_proxy_status(502, "Bad gateway").classify(),
+            ErrorClass::ProviderOverloaded
+        );
+        assert_eq!(
+            AppError::server_proxy_status(400, "Invalid model").classify(),
+            ErrorClass::BadRequest
+        );
+        assert_eq!(
+            AppError::HttpError("connection reset by peer".to_string()).classify(),
+            ErrorClass::TransientNetwork
+        );
+    }
+
+    #[test]
+    fn does_not_read_statuses_from_message_text() {
+        let error = AppError::server_proxy("upstream said status 429");
+        assert_eq!(error.http_status(), None);
+        assert_eq!(error.classify(), ErrorClass::Other);
+        assert_eq!(
+            AppError::HttpError("Invalid status code: 400".to_string()).http_status(),
+            None
+        );
+    }
+
+    #[test]
+    fn classifies_typed_variants() {
+        assert_eq!(
+            AppError::AuthError("expired".to_string()).classify(),
+            ErrorClass::Auth
+        );
+        assert_eq!(
+            AppError::CreditInsufficient("empty".to_string()).classify(),
+            ErrorClass::Billing
+        );
+        assert_eq!(
+            AppError::ValidationError("missing field".to_string()).classify(),
+            ErrorClass::BadRequest
+        );
+        assert_eq!(
+            AppError::JobError("processor panicked".to_string()).classify(),
+            ErrorClass::Other
+        );
+    }
+
+    #[test]
+    fn carries_retry_after_hint() {
+        let error = AppError::rate_limited("Rate limit exceeded", Some(Duration::from_secs(42)));
+        assert_eq!(error.classify(), ErrorClass::RateLimited);
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(42)));
+        assert_eq!(error.to_string(), "Too many requests: Rate limit exceeded");
+        assert_eq!(
+            AppError::rate_limited("Rate limit exceeded", None).retry_after(),
+            None
+        );
+    }
+}
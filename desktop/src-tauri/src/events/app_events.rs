@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Typed application events published on the internal [`EventBus`](super::EventBus).
+///
+/// Serialized as `{ "type": "job.created", "payload": { ... } }` so integration
+/// hooks receive a stable, documented shape independent of the UI event names.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "payload", rename_all_fields = "camelCase")]
+pub enum AppEvent {
+    #[serde(rename = "job.created")]
+    JobCreated {
+        job_id: String,
+        session_id: String,
+        task_type: String,
+    },
+    #[serde(rename = "job.status_changed")]
+    JobStatusChanged {
+        job_id: String,
+        session_id: String,
+        status: String,
+    },
+    #[serde(rename = "job.finalized")]
+    JobFinalized {
+        job_id: String,
+        session_id: String,
+        status: String,
+        actual_cost: f64,
+    },
+    #[serde(rename = "workflow.status_changed")]
+    WorkflowStatusChanged {
+        workflow_id: String,
+        status: String,
+        progress: f32,
+        message: String,
+        error_message: Option<String>,
+    },
+    #[serde(rename = "workflow.stage_changed")]
+    WorkflowStageChanged {
+        workflow_id: String,
+        stage_name: String,
+        task_type: String,
+        job_id: String,
+        status: String,
+        error_message: Option<String>,
+    },
+    #[serde(rename = "plan.created")]
+    PlanCreated {
+        job_id: String,
+        session_id: String,
+        title: String,
+    },
+    #[serde(rename = "plan.progress_updated")]
+    PlanProgressUpdated {
+        job_id: String,
+        session_id: String,
+        steps_touched: usize,
+        total_steps: usize,
+        unplanned_files: usize,
+    },
+    #[serde(rename = "terminal.exited")]
+    TerminalExited { session_id: String, exit_code: i32 },
+}
+
+/// Event type discriminant, used to select which events a hook subscribes to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AppEventKind {
+    #[serde(rename = "job.created")]
+    JobCreated,
+    #[serde(rename = "job.status_changed")]
+    JobStatusChanged,
+    #[serde(rename = "job.finalized")]
+    JobFinalized,
+    #[serde(rename = "workflow.status_changed")]
+    WorkflowStatusChanged,
+    #[serde(rename = "workflow.stage_changed")]
+    WorkflowStageChanged,
+    #[serde(rename = "plan.created")]
+    PlanCreated,
+    #[serde(rename = "plan.progress_updated")]
+    PlanProgressUpdated,
+    #[serde(rename = "terminal.exited")]
+    TerminalExited,
+}
+
+impl AppEventKind {
+    pub const ALL: [AppEventKind; 8] = [
+        AppEventKind::JobCreated,
+        AppEventKind::JobStatusChanged,
+        AppEventKind::JobFinalized,
+        AppEventKind::WorkflowStatusChanged,
+        AppEventKind::WorkflowStageChanged,
+        AppEventKind::PlanCreated,
+        AppEventKind::PlanProgressUpdated,
+        AppEventKind::TerminalExited,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppEventKind::JobCreated => "job.created",
+            AppEventKind::JobStatusChanged => "job.status_changed",
+            AppEventKind::JobFinalized => "job.finalized",
+            AppEventKind::WorkflowStatusChanged => "workflow.status_changed",
+            AppEventKind::WorkflowStageChanged => "workflow.stage_changed",
+            AppEventKind::PlanCreated => "plan.created",
+            AppEventKind::PlanProgressUpdated => "plan.progress_updated",
+            AppEventKind::TerminalExited => "terminal.exited",
+        }
+    }
+}
+
+impl fmt::Display for AppEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AppEventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AppEventKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Unknown event type: {}", s))
+    }
+}
+
+impl AppEvent {
+    pub fn kind(&self) -> AppEventKind {
+        match self {
+            AppEvent::JobCreated { .. } => AppEventKind::JobCreated,
+            AppEvent::JobStatusChanged { .. } => AppEventKind::JobStatusChanged,
+            AppEvent::JobFinalized { .. } => AppEventKind::JobFinalized,
+            AppEvent::WorkflowStatusChanged { .. } => AppEventKind::WorkflowStatusChanged,
+            AppEvent::WorkflowStageChanged { .. } => AppEventKind::WorkflowStageChanged,
+            AppEvent::PlanCreated { .. } => AppEventKind::PlanCreated,
+            AppEvent::PlanProgressUpdated { .. } => AppEventKind::PlanProgressUpdated,
+            AppEvent::TerminalExited { .. } => AppEventKind::TerminalExited,
+        }
+    }
+
+    /// One-line human readable description, sent as `text` so chat webhooks render it directly
+    pub fn summary(&self) -> String {
+        match self {
+            AppEvent::JobCreated {
+                job_id, task_type, ..
+            } => format!("Job {} ({}) created", job_id, task_type),
+            AppEvent::JobStatusChanged { job_id, status, .. } => {
+                format!("Job {} is now {}", job_id, status)
+            }
+            AppEvent::JobFinalized {
+                job_id,
+                status,
+                actual_cost,
+                ..
+            } => format!(
+                "Job {} finished as {} (${:.4})",
+                job_id, status, actual_cost
+            ),
+            AppEvent::WorkflowStatusChanged {
+                workflow_id,
+                status,
+                message,
+                ..
+            } => format!("Workflow {} is {}: {}", workflow_id, status, message),
+            AppEvent::WorkflowStageChanged {
+                workflow_id,
+                stage_name,
+                status,
+                ..
+            } => format!(
+                "Workflow {} stage {} is {}",
+                workflow_id, stage_name, status
+            ),
+            AppEvent::PlanCreated { title, .. } => format!("Implementation plan ready: {}", title),
+            AppEvent::PlanProgressUpdated {
+                job_id,
+                steps_touched,
+                total_steps,
+                unplanned_files,
+                ..
+            } => format!(
+                "Plan {}: {}/{} steps touched, {} unplanned files changed",
+                job_id, steps_touched, total_steps, unplanned_files
+            ),
+            AppEvent::TerminalExited {
+                session_id,
+                exit_code,
+            } => format!(
+                "Terminal for session {} exited with code {}",
+                session_id, exit_code
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_with_type_tag_and_camel_case_payload() {
+        let event = AppEvent::PlanCreated {
+            job_id: "job-1".to_string(),
+            session_id: "session-1".to_string(),
+            title: "Add login".to_string(),
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "type": "plan.created",
+                "payload": { "jobId": "job-1", "sessionId": "session-1", "title": "Add login" }
+            })
+        );
+        assert_eq!(serde_json::from_value::<AppEvent>(value).unwrap(), event);
+    }
+
+    #[test]
+    fn kind_names_round_trip() {
+        for kind in AppEventKind::ALL {
+            assert_eq!(kind.as_str().parse::<AppEventKind>().unwrap(), kind);
+            assert_eq!(serde_json::to_value(kind).unwrap(), json!(kind.as_str()));
+        }
+        assert!("job.unknown".parse::<AppEventKind>().is_err());
+    }
+}
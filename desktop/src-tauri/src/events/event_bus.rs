@@ -1,43 +1,38 @@
+use super::app_events::AppEvent;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use tauri::{AppHandle, Manager};
 use tokio::sync::broadcast;
 
 /// Event bus for handling internal application events
 #[derive(Debug, Clone)]
 pub struct EventBus {
-    channels: Arc<RwLock<HashMap<String, broadcast::Sender<Event>>>>,
+    sender: broadcast::Sender<Event>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
-    pub event_type: String,
-    pub payload: serde_json::Value,
+    #[serde(flatten)]
+    pub event: AppEvent,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
 impl EventBus {
     pub fn new() -> Self {
         Self {
-            channels: Arc::new(RwLock::new(HashMap::new())),
+            sender: broadcast::channel(256).0,
         }
     }
 
-    pub async fn subscribe(&self, event_type: &str) -> broadcast::Receiver<Event> {
-        let mut channels = self.channels.write().await;
-        let sender = channels
-            .entry(event_type.to_string())
-            .or_insert_with(|| broadcast::channel(100).0);
-        sender.subscribe()
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
     }
 
-    pub async fn publish(&self, event: Event) -> Result<(), String> {
-        let channels = self.channels.read().await;
-        if let Some(sender) = channels.get(&event.event_type) {
-            sender.send(event).map_err(|e| e.to_string())?;
-        }
-        Ok(())
+    /// Publish an event to all subscribers. Having no subscribers is not an error.
+    pub fn publish(&self, event: AppEvent) {
+        let _ = self.sender.send(Event {
+            event,
+            timestamp: chrono::Utc::now(),
+        });
     }
 }
 
@@ -46,3 +41,10 @@ impl Default for EventBus {
         Self::new()
     }
 }
+
+/// Publish an event on the app's bus, if it has been set up
+pub fn publish_app_event(app_handle: &AppHandle, event: AppEvent) {
+    if let Some(bus) = app_handle.try_state::<EventBus>() {
+        bus.publish(event);
+    }
+}
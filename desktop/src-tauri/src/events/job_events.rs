@@ -3,6 +3,8 @@ use serde::Serialize;
 use serde_json::json;
 use tauri::{AppHandle, Emitter};
 
+use super::{AppEvent, publish_app_event};
+
 // Job event payloads are UI-facing; serialize with camelCase via serde(rename_all = "camelCase").
 // Internal Rust code and DB remain snake_case.
 
@@ -116,6 +118,14 @@ pub struct JobMetadataUpdatedEvent {
 
 // Helper emit functions
 pub fn emit_job_created(app_handle: &AppHandle, payload: JobCreatedEvent) {
+    publish_app_event(
+        app_handle,
+        AppEvent::JobCreated {
+            job_id: payload.job_id.clone(),
+            session_id: payload.session_id.clone(),
+            task_type: payload.job.task_type.clone(),
+        },
+    );
     if let Err(e) = app_handle.emit(JOB_CREATED, &payload) {
         warn!("Failed to emit {} event: {}", JOB_CREATED, e);
     }
@@ -142,6 +152,14 @@ pub fn emit_job_deleted(app_handle: &AppHandle, payload: JobDeletedEvent) {
 }
 
 pub fn emit_job_status_changed(app_handle: &AppHandle, payload: JobStatusChangedEvent) {
+    publish_app_event(
+        app_handle,
+        AppEvent::JobStatusChanged {
+            job_id: payload.job_id.clone(),
+            session_id: payload.session_id.clone(),
+            status: payload.status.clone(),
+        },
+    );
     if let Err(e) = app_handle.emit(JOB_STATUS_CHANGED, &payload) {
         warn!("Failed to emit {} event: {}", JOB_STATUS_CHANGED, e);
     }
@@ -215,6 +233,15 @@ pub fn emit_job_error_details(app_handle: &AppHandle, payload: JobErrorDetailsEv
 }
 
 pub fn emit_job_finalized(app_handle: &AppHandle, payload: JobFinalizedEvent) {
+    publish_app_event(
+        app_handle,
+        AppEvent::JobFinalized {
+            job_id: payload.job_id.clone(),
+            session_id: payload.session_id.clone(),
+            status: payload.status.clone(),
+            actual_cost: payload.actual_cost,
+        },
+    );
     if let Err(e) = app_handle.emit(JOB_FINALIZED, &payload) {
         warn!("Failed to emit {} event: {}", JOB_FINALIZED, e);
     }
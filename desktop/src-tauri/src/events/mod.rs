@@ -1,5 +1,7 @@
+pub mod app_events;
 pub mod event_bus;
 pub mod job_events;
 pub mod session_events;
 
+pub use app_events::*;
 pub use event_bus::*;
@@ -143,23 +143,27 @@ pub fn emit_session_deleted(app: &AppHandle, session_id: &str) -> Result<(), Str
     Ok(())
 }
 
-pub fn emit_history_state_changed<T: Serialize>(
-    app: &AppHandle,
-    session_id: &str,
-    kind: &str,
-    state: &T,
-) {
+fn history_state_payload<T: Serialize>(session_id: &str, kind: &str, state: &T) -> serde_json::Value {
     let state_value = serde_json::to_value(state).unwrap_or(serde_json::json!({}));
     let version = state_value.get("version").cloned().unwrap_or(serde_json::Value::Null);
     let checksum = state_value.get("checksum").cloned().unwrap_or(serde_json::Value::Null);
 
-    let payload = serde_json::json!({
+    serde_json::json!({
         "sessionId": session_id,
         "kind": kind,
         "state": state_value,
         "version": version,
         "checksum": checksum
-    });
+    })
+}
+
+pub fn emit_history_state_changed<T: Serialize>(
+    app: &AppHandle,
+    session_id: &str,
+    kind: &str,
+    state: &T,
+) {
+    let payload = history_state_payload(session_id, kind, state);
 
     if let Err(e) = app.emit("history-state-changed", payload.clone()) {
         eprintln!("Failed to emit history-state-changed locally: {}", e);
@@ -175,6 +179,55 @@ pub fn emit_history_state_changed<T: Serialize>(
     }
 }
 
+/// Same as `emit_history_state_changed` without forwarding to the device link,
+/// for state that other devices already derive themselves.
+pub fn emit_history_state_changed_locally<T: Serialize>(
+    app: &AppHandle,
+    session_id: &str,
+    kind: &str,
+    state: &T,
+) {
+    let payload = history_state_payload(session_id, kind, state);
+
+    if let Err(e) = app.emit("history-state-changed", payload) {
+        eprintln!("Failed to emit history-state-changed locally: {}", e);
+    }
+}
+
+/// Character-level task description edits, sent alongside the snapshot events
+/// so other devices can merge concurrent edits instead of replacing the text.
+/// event: "history-state-changed" with kind "task-ops"
+pub fn emit_task_description_ops(
+    app: &AppHandle,
+    session_id: &str,
+    replica_id: &str,
+    ops: &[crate::utils::text_crdt::TextOp],
+) {
+    if ops.is_empty() {
+        return;
+    }
+
+    let payload = serde_json::json!({
+        "sessionId": session_id,
+        "kind": "task-ops",
+        "replicaId": replica_id,
+        "ops": ops,
+    });
+
+    if let Err(e) = app.emit("history-state-changed", payload.clone()) {
+        eprintln!("Failed to emit task-ops locally: {}", e);
+    }
+
+    let device_link_payload = serde_json::json!({
+        "type": "history-state-changed",
+        "payload": payload,
+    });
+
+    if let Err(e) = app.emit("device-link-event", device_link_payload) {
+        eprintln!("Failed to emit task-ops to device-link: {}", e);
+    }
+}
+
 pub fn emit_history_state_validated(
     app: &AppHandle,
     session_id: &str,
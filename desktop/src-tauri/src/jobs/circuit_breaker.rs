@@ -0,0 +1,396 @@
+//! Circuit breaker in front of the job queue.
+//!
+//! When the server keeps answering with 429 or 5xx, every queued job would otherwise be
+//! dequeued, fail and burn through its retries. Instead, after a run of such failures the
+//! breaker opens and workers stop dequeuing until a cooldown passes. A single probe job is
+//! then let through; its outcome either closes the breaker or re-opens it for longer.
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+
+/// Server-pressure failures within the window that open the breaker
+const FAILURE_THRESHOLD: usize = 5;
+const FAILURE_WINDOW: Duration = Duration::from_secs(60);
+/// Cooldown after the first trip; doubles on each consecutive trip
+const BASE_COOLDOWN: Duration = Duration::from_secs(30);
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+
+static JOB_CIRCUIT_BREAKER: Lazy<JobCircuitBreaker> = Lazy::new(JobCircuitBreaker::new);
+
+/// The breaker shared by all job workers
+pub fn get_job_circuit_breaker() -> &'static JobCircuitBreaker {
+    &JOB_CIRCUIT_BREAKER
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Jobs are dequeued normally
+    Closed,
+    /// Dequeuing is paused until the cooldown ends
+    Open,
+    /// One probe job is allowed through to test the server
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    /// Seconds until a probe job is allowed, while open
+    pub retry_in_secs: Option<u64>,
+    pub recent_failures: usize,
+    pub consecutive_trips: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    failures: VecDeque<Instant>,
+    open_until: Option<Instant>,
+    consecutive_trips: u32,
+    probe_in_flight: bool,
+    last_error: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct JobCircuitBreaker {
+    inner: Mutex<BreakerState>,
+}
+
+/// Permission to dequeue one job. Report the job's outcome through it so the breaker can
+/// count failures; dropping it unreported (e.g. the queue was empty) frees a probe slot.
+#[must_use]
+pub struct BreakerPermit<'a> {
+    breaker: &'a JobCircuitBreaker,
+    probe: bool,
+    reported: bool,
+}
+
+impl BreakerPermit<'_> {
+    /// Record a successful job. Returns the new state if this changed it.
+    pub fn record_success(mut self) -> Option<CircuitState> {
+        self.reported = true;
+        self.breaker.on_healthy_response(self.probe)
+    }
+
+    /// Record a failed job. Only rate-limit and overload failures count against the
+    /// server; any other failure still proves the server answered.
+    pub fn record_failure(self, error: &AppError) -> Option<CircuitState> {
+        self.record_failure_at(error, Instant::now())
+    }
+
+    fn record_failure_at(mut self, error: &AppError, now: Instant) -> Option<CircuitState> {
+        self.reported = true;
+        if error.classify().is_server_pressure() {
+            self.breaker.on_server_pressure(error, self.probe, now)
+        } else {
+            self.breaker.on_healthy_response(self.probe)
+        }
+    }
+}
+
+impl Drop for BreakerPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.reported {
+            self.breaker.lock().probe_in_flight = false;
+        }
+    }
+}
+
+impl Default for JobCircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobCircuitBreaker {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                failures: VecDeque::new(),
+                open_until: None,
+                consecutive_trips: 0,
+                probe_in_flight: false,
+                last_error: None,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Ask to dequeue a job. `None` means dequeuing is paused.
+    pub fn try_acquire(&self) -> Option<BreakerPermit<'_>> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Option<BreakerPermit<'_>> {
+        let mut state = self.lock();
+        let probe = match state.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => {
+                if state.open_until.is_some_and(|until| now < until) {
+                    return None;
+                }
+                info!("Job circuit breaker cooldown elapsed; letting one probe job through");
+                state.state = CircuitState::HalfOpen;
+                state.probe_in_flight = true;
+                true
+            }
+            CircuitState::HalfOpen => {
+                if state.probe_in_flight {
+                    return None;
+                }
+                state.probe_in_flight = true;
+                true
+            }
+        };
+
+        Some(BreakerPermit {
+            breaker: self,
+            probe,
+            reported: false,
+        })
+    }
+
+    fn on_healthy_response(&self, probe: bool) -> Option<CircuitState> {
+        let mut state = self.lock();
+        state.failures.clear();
+        if probe {
+            state.probe_in_flight = false;
+        }
+        if state.state == CircuitState::Closed {
+            return None;
+        }
+        if !probe {
+            // A job dequeued before the breaker opened finished; wait for the probe itself
+            return None;
+        }
+
+        info!("Job circuit breaker closed; server is responding again");
+        state.state = CircuitState::Closed;
+        state.open_until = None;
+        state.consecutive_trips = 0;
+        state.last_error = None;
+        Some(CircuitState::Closed)
+    }
+
+    fn on_server_pressure(
+        &self,
+        error: &AppError,
+        probe: bool,
+        now: Instant,
+    ) -> Option<CircuitState> {
+        let mut state = self.lock();
+        state.last_error = Some(error.to_string());
+        if probe {
+            state.probe_in_flight = false;
+        }
+
+        let should_open = match state.state {
+            CircuitState::Closed => {
+                while state
+                    .failures
+                    .front()
+                    .is_some_and(|at| now.duration_since(*at) > FAILURE_WINDOW)
+                {
+                    state.failures.pop_front();
+                }
+                state.failures.push_back(now);
+                state.failures.len() >= FAILURE_THRESHOLD
+            }
+            CircuitState::HalfOpen => probe,
+            CircuitState::Open => false,
+        };
+        if !should_open {
+            return None;
+        }
+
+        state.consecutive_trips += 1;
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(2u32.saturating_pow(state.consecutive_trips - 1))
+            .min(MAX_COOLDOWN);
+        let retry_after = error
+            .retry_after()
+            .map(|retry_after| retry_after.min(MAX_COOLDOWN))
+            .unwrap_or_default();
+        let cooldown = cooldown.max(retry_after);
+
+        warn!(
+            "Job circuit breaker opened after repeated server errors; pausing job dequeue for {}s. Last error: {}",
+            cooldown.as_secs(),
+            error
+        );
+        state.state = CircuitState::Open;
+        state.open_until = Some(now + cooldown);
+        state.failures.clear();
+        Some(CircuitState::Open)
+    }
+
+    /// Close the breaker immediately, e.g. when the user asks to resume
+    pub fn reset(&self) -> Option<CircuitState> {
+        let mut state = self.lock();
+        let was_closed = state.state == CircuitState::Closed;
+        state.state = CircuitState::Closed;
+        state.failures.clear();
+        state.open_until = None;
+        state.consecutive_trips = 0;
+        state.probe_in_flight = false;
+        state.last_error = None;
+        (!was_closed).then_some(CircuitState::Closed)
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        let state = self.lock();
+        let now = Instant::now();
+        CircuitBreakerStatus {
+            state: state.state,
+            retry_in_secs: match state.state {
+                CircuitState::Open => state
+                    .open_until
+                    .map(|until| until.saturating_duration_since(now).as_secs()),
+                _ => None,
+            },
+            recent_failures: state.failures.len(),
+            consecutive_trips: state.consecutive_trips,
+            last_error: state.last_error.clone(),
+        }
+    }
+}
+
+/// Tell the frontend the breaker changed state so it can explain why jobs are waiting
+pub fn emit_circuit_breaker_changed(app_handle: &AppHandle) {
+    let status = get_job_circuit_breaker().status();
+    if let Err(e) = app_handle.emit("job-circuit-breaker-changed", &status) {
+        warn!("Failed to emit job circuit breaker status: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overloaded() -> AppError {
+        AppError::server_proxy_status(503, "Server error (HTTP Status: 503): overloaded")
+    }
+
+    fn trip(breaker: &JobCircuitBreaker, now: Instant) {
+        for _ in 0..FAILURE_THRESHOLD {
+            let permit = breaker
+                .try_acquire_at(now)
+                .expect("closed breaker admits jobs");
+            permit.record_failure_at(&overloaded(), now);
+        }
+    }
+
+    #[test]
+    fn opens_after_threshold_and_pauses_dequeue() {
+        let breaker = JobCircuitBreaker::new();
+        let now = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            assert_eq!(
+                breaker
+                    .try_acquire_at(now)
+                    .unwrap()
+                    .record_failure_at(&overloaded(), now),
+                None
+            );
+        }
+        assert_eq!(
+            breaker
+                .try_acquire_at(now)
+                .unwrap()
+                .record_failure_at(&overloaded(), now),
+            Some(CircuitState::Open)
+        );
+        assert!(
+            breaker
+                .try_acquire_at(now + Duration::from_secs(1))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn client_errors_do_not_count_against_the_server() {
+        let breaker = JobCircuitBreaker::new();
+        let now = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD * 2 {
+            let permit = breaker.try_acquire_at(now).unwrap();
+            permit.record_failure_at(&AppError::ValidationError("bad input".to_string()), now);
+        }
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn probe_success_closes_and_probe_failure_reopens_longer() {
+        let breaker = JobCircuitBreaker::new();
+        let now = Instant::now();
+        trip(&breaker, now);
+
+        let after_cooldown = now + BASE_COOLDOWN;
+        let probe = breaker.try_acquire_at(after_cooldown).unwrap();
+        assert!(
+            breaker.try_acquire_at(after_cooldown).is_none(),
+            "only one probe at a time"
+        );
+        assert_eq!(
+            probe.record_failure_at(&overloaded(), after_cooldown),
+            Some(CircuitState::Open)
+        );
+        assert!(
+            breaker
+                .try_acquire_at(after_cooldown + BASE_COOLDOWN)
+                .is_none(),
+            "second trip doubles the cooldown"
+        );
+
+        let probe = breaker
+            .try_acquire_at(after_cooldown + BASE_COOLDOWN * 2)
+            .unwrap();
+        assert_eq!(probe.record_success(), Some(CircuitState::Closed));
+        assert_eq!(breaker.status().consecutive_trips, 0);
+    }
+
+    #[test]
+    fn unreported_probe_frees_the_slot() {
+        let breaker = JobCircuitBreaker::new();
+        let now = Instant::now();
+        trip(&breaker, now);
+
+        let after_cooldown = now + BASE_COOLDOWN;
+        drop(breaker.try_acquire_at(after_cooldown).unwrap());
+        assert!(breaker.try_acquire_at(after_cooldown).is_some());
+    }
+
+    #[test]
+    fn honors_longer_retry_after_when_opening() {
+        let breaker = JobCircuitBreaker::new();
+        let now = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD {
+            breaker.try_acquire_at(now).unwrap().record_failure_at(
+                &AppError::rate_limited("Rate limit exceeded", Some(Duration::from_secs(120))),
+                now,
+            );
+        }
+        assert!(breaker.try_acquire_at(now + BASE_COOLDOWN).is_none());
+        assert!(
+            breaker
+                .try_acquire_at(now + Duration::from_secs(120))
+                .is_some()
+        );
+    }
+}
@@ -9,6 +9,7 @@ use crate::constants::DEFAULT_JOB_TIMEOUT_SECONDS;
 use crate::db_utils::background_job_repository::BackgroundJobRepository;
 use crate::db_utils::session_repository::SessionRepository;
 use crate::error::{AppError, AppResult};
+use crate::jobs::circuit_breaker::{self, CircuitState, get_job_circuit_breaker};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processor_trait;
 use crate::jobs::queue::{JobPriority, get_job_queue};
@@ -28,6 +29,7 @@ fn is_file_finding_task(task_type: &TaskType) -> bool {
         TaskType::RegexFileFilter
             | TaskType::FileRelevanceAssessment
             | TaskType::ExtendedPathFinder
+            | TaskType::ImpactAnalysis
     )
 }
 
@@ -56,6 +58,11 @@ fn standardize_file_finding_response(response: Value, task_type: &TaskType) -> V
             // No transformation needed
             response
         }
+        TaskType::ImpactAnalysis => {
+            // Impact Analysis returns the standardized format plus the "dependents" with reasons
+            // No transformation needed
+            response
+        }
         _ => response,
     };
 
@@ -110,6 +117,15 @@ pub async fn process_next_job(app_handle: AppHandle) -> AppResult<Option<JobProc
         }
     };
 
+    // Hold off dequeuing while the server keeps rejecting work
+    let breaker_permit = match get_job_circuit_breaker().try_acquire() {
+        Some(breaker_permit) => breaker_permit,
+        None => {
+            drop(permit);
+            return Ok(None);
+        }
+    };
+
     // Dequeue a job
     let job = match queue.dequeue().await {
         Some(job) => job,
@@ -215,6 +231,8 @@ pub async fn process_next_job(app_handle: AppHandle) -> AppResult<Option<JobProc
                 error!("Failed to reset retry count for job {}: {}", job_id, e);
             }
 
+            report_circuit_transition(&app_handle, breaker_permit.record_success());
+
             // Handle successful job completion
             handle_job_success(&app_handle, &background_job_repo, &job_id, &result).await?;
 
@@ -227,6 +245,8 @@ pub async fn process_next_job(app_handle: AppHandle) -> AppResult<Option<JobProc
                 job_id, task_type, app_error
             );
 
+            report_circuit_transition(&app_handle, breaker_permit.record_failure(&app_error));
+
             // Handle job failure or retry
             let handled =
                 handle_job_failure_or_retry(&app_handle, &background_job_repo, &job_id, &app_error)
@@ -249,6 +269,13 @@ pub async fn process_next_job(app_handle: AppHandle) -> AppResult<Option<JobProc
     }
 }
 
+/// Let the frontend know when the job circuit breaker opened or closed
+fn report_circuit_transition(app_handle: &AppHandle, transition: Option<CircuitState>) {
+    if transition.is_some() {
+        circuit_breaker::emit_circuit_breaker_changed(app_handle);
+    }
+}
+
 /// Execute a job with the given processor and handle timeout
 async fn execute_job_with_processor(
     job_id: &str,
@@ -778,22 +805,24 @@ async fn handle_job_failure_or_retry_internal(
         )));
     }
 
-    // Check if this error type is retryable and get current retry count
-    let (is_retryable, current_retry_count) =
-        retry_utils::get_retry_info(job_copy, Some(error)).await;
+    // Apply the task type's retry policy to the classified error
+    let task_type = TaskType::from_str(&job_copy.task_type).unwrap_or(TaskType::Unknown);
+    let retry_policy = retry_utils::resolve_retry_policy(app_handle, &task_type).await;
+    let decision = retry_utils::decide_retry(job_copy, &retry_policy, error);
+    let current_retry_count = decision.retry_count;
 
     // Check if we can retry this job
-    if is_retryable && current_retry_count < retry_utils::MAX_RETRY_COUNT {
-        // Calculate exponential backoff delay
-        let retry_delay = retry_utils::calculate_retry_delay(current_retry_count);
+    if decision.should_retry {
+        let retry_delay = decision.delay.as_secs();
         let next_retry_count = current_retry_count + 1;
 
         warn!(
-            "Job {} failed with retryable error [Type: {:?}]. Scheduling retry #{}/{} in {} seconds: {}",
+            "Job {} failed with retryable {:?} error [Type: {:?}]. Scheduling retry #{}/{} in {} seconds: {}",
             job_id,
+            decision.error_class,
             error,
             next_retry_count,
-            retry_utils::MAX_RETRY_COUNT,
+            decision.max_retries,
             retry_delay,
             error.to_string()
         );
@@ -863,7 +892,7 @@ async fn handle_job_failure_or_retry_internal(
 
         // Re-queue the job with delay instead of sleeping
         if let Err(e) =
-            re_queue_job_with_delay(app_handle, job_copy, retry_delay as u64 * 1000).await
+            re_queue_job_with_delay(app_handle, job_copy, decision.delay.as_millis() as u64).await
         {
             error!("Failed to re-queue job {} for retry: {}", job_id, e);
             // If re-queueing fails, treat as permanent failure
@@ -876,11 +905,11 @@ async fn handle_job_failure_or_retry_internal(
         Ok(JobFailureHandlingResult::Retrying)
     } else {
         // Job is not retryable or max retries reached - provide detailed failure logging with enhanced context
-        let failure_reason = if !is_retryable {
+        let failure_reason = if !decision.retryable {
             let user_friendly_reason = format_user_friendly_error(error);
             let detailed_reason = format!(
-                "Non-retryable error [{:?}]: {}",
-                error, user_friendly_reason
+                "Non-retryable {:?} error [{:?}]: {}",
+                decision.error_class, error, user_friendly_reason
             );
 
             error!(
@@ -907,10 +936,7 @@ async fn handle_job_failure_or_retry_internal(
             let user_friendly_reason = format_user_friendly_error(error);
             let detailed_reason = format!(
                 "Failed after {}/{} retries [{:?}]: {}",
-                current_retry_count,
-                retry_utils::MAX_RETRY_COUNT,
-                error,
-                user_friendly_reason
+                current_retry_count, decision.max_retries, error, user_friendly_reason
             );
 
             error!(
@@ -920,7 +946,7 @@ async fn handle_job_failure_or_retry_internal(
                 job_copy.session_id,
                 job_copy.created_at,
                 current_retry_count,
-                retry_utils::MAX_RETRY_COUNT,
+                decision.max_retries,
                 error,
                 error.to_string()
             );
@@ -968,8 +994,10 @@ async fn handle_job_failure_or_retry_internal(
 fn format_user_friendly_error(error: &AppError) -> String {
     match error {
         AppError::NetworkError(_) => "Network connection issue. Please check your internet connection and try again.".to_string(),
-        AppError::HttpError(msg) => {
-            if let Some(status) = extract_http_status_code(msg) {
+        AppError::TooManyRequests { .. } => "Rate limit exceeded. Please wait a moment before trying again.".to_string(),
+        AppError::HttpError(_) => "Network request failed. Please try again.".to_string(),
+        AppError::ServerProxyError { .. } => {
+            if let Some(status) = error.http_status() {
                 match status {
                     429 => "Rate limit exceeded. Please wait a moment before trying again.".to_string(),
                     500..=599 => "Server is temporarily unavailable. Please try again later.".to_string(),
@@ -978,7 +1006,7 @@ fn format_user_friendly_error(error: &AppError) -> String {
                     _ => format!("HTTP error ({}). Please try again.", status),
                 }
             } else {
-                "Network request failed. Please try again.".to_string()
+                truncate_error_for_display(&error.to_string(), 150)
             }
         },
         AppError::OpenRouterError(_) => "AI service temporarily unavailable. Please try again in a moment.".to_string(),
@@ -998,8 +1026,8 @@ fn format_user_error(error: &AppError) -> String {
 
     // For some error types, add the technical detail in a user-friendly way
     match error {
-        AppError::HttpError(msg) => {
-            if let Some(status) = extract_http_status_code(msg) {
+        AppError::ServerProxyError { .. } => {
+            if let Some(status) = error.http_status() {
                 format!("{} (Error code: {})", friendly_message, status)
             } else {
                 friendly_message
@@ -1082,20 +1110,6 @@ fn log_retry_history_context(job_id: &str, metadata: &Option<String>) {
     }
 }
 
-// Helper functions that need to be accessible in this module
-fn extract_http_status_code(error_msg: &str) -> Option<u16> {
-    // Simple pattern matching for HTTP status codes
-    if let Some(start) = error_msg.find("status ") {
-        let start_idx = start + 7; // Skip "status "
-        if let Some(end) = error_msg[start_idx..].find(|c: char| !c.is_ascii_digit()) {
-            if let Ok(status) = error_msg[start_idx..start_idx + end].parse::<u16>() {
-                return Some(status);
-            }
-        }
-    }
-    None
-}
-
 /// Re-queue a job with a delay for retry processing
 async fn re_queue_job_with_delay(
     app_handle: &AppHandle,
@@ -40,8 +40,9 @@ pub fn deserialize_value_to_job_payload(
 ) -> AppResult<JobPayload> {
     use crate::jobs::types::{
         ExtendedPathFinderPayload, FileFinderWorkflowPayload, FileRelevanceAssessmentPayload,
-        GenericLlmStreamPayload, ImplementationPlanMergePayload, ImplementationPlanPayload,
-        JobPayload, OpenRouterLlmPayload, RegexFileFilterPayload,
+        GenericLlmStreamPayload, ImpactAnalysisPayload, ImplementationPlanMergePayload,
+        ImplementationPlanPayload, ImplementationPlanRefinementPayload, JobPayload,
+        OpenRouterLlmPayload, PlanTestGenerationPayload, RegexFileFilterPayload,
         RootFolderSelectionPayload, TaskRefinementPayload, TextImprovementPayload,
         VideoAnalysisPayload, WebSearchExecutionPayload, WebSearchPromptsGenerationPayload,
         WebSearchWorkflowPayload,
@@ -69,6 +70,16 @@ pub fn deserialize_value_to_job_payload(
                 })?;
             Ok(JobPayload::ExtendedPathFinder(payload))
         }
+        TaskType::ImpactAnalysis => {
+            let payload: ImpactAnalysisPayload = serde_json::from_value(json_value.clone())
+                .map_err(|e| {
+                    AppError::JobError(format!(
+                        "Failed to deserialize ImpactAnalysisPayload: {}",
+                        e
+                    ))
+                })?;
+            Ok(JobPayload::ImpactAnalysis(payload))
+        }
         TaskType::ImplementationPlan => {
             let payload: ImplementationPlanPayload = serde_json::from_value(json_value.clone())
                 .map_err(|e| {
@@ -176,6 +187,26 @@ pub fn deserialize_value_to_job_payload(
                 })?;
             Ok(JobPayload::ImplementationPlanMerge(payload))
         }
+        TaskType::ImplementationPlanRefinement => {
+            let payload: ImplementationPlanRefinementPayload =
+                serde_json::from_value(json_value.clone()).map_err(|e| {
+                    AppError::JobError(format!(
+                        "Failed to deserialize ImplementationPlanRefinementPayload: {}",
+                        e
+                    ))
+                })?;
+            Ok(JobPayload::ImplementationPlanRefinement(payload))
+        }
+        TaskType::PlanTestGeneration => {
+            let payload: PlanTestGenerationPayload = serde_json::from_value(json_value.clone())
+                .map_err(|e| {
+                    AppError::JobError(format!(
+                        "Failed to deserialize PlanTestGenerationPayload: {}",
+                        e
+                    ))
+                })?;
+            Ok(JobPayload::PlanTestGeneration(payload))
+        }
         TaskType::VideoAnalysis => {
             let payload: VideoAnalysisPayload = serde_json::from_value(json_value.clone())
                 .map_err(|e| {
@@ -1,3 +1,4 @@
+pub mod circuit_breaker;
 pub mod dispatcher;
 pub mod embedded_workflows;
 pub mod job_payload_utils;
@@ -27,8 +28,11 @@ use self::processors::{
     // File relevance assessment processor
     FileRelevanceAssessmentProcessor,
     GenericLlmStreamProcessor,
+    ImpactAnalysisProcessor,
     ImplementationPlanMergeProcessor,
     ImplementationPlanProcessor,
+    ImplementationPlanRefinementProcessor,
+    PlanTestGenerationProcessor,
     RegexFileFilterProcessor,
     RootFolderSelectionProcessor,
     TaskRefinementProcessor,
@@ -75,10 +79,14 @@ pub async fn register_job_processors(app_handle: &AppHandle) -> AppResult<()> {
     let extended_path_finder_processor = Arc::new(ExtendedPathFinderProcessor::new());
     // File relevance assessment processor
     let file_relevance_assessment_processor = Arc::new(FileRelevanceAssessmentProcessor::new());
+    let impact_analysis_processor = Arc::new(ImpactAnalysisProcessor::new());
 
     let web_search_prompts_generator = Arc::new(WebSearchPromptsGeneratorProcessor::new());
     let web_search_executor = Arc::new(WebSearchExecutorProcessor::new());
     let implementation_plan_merge_processor = Arc::new(ImplementationPlanMergeProcessor::new());
+    let implementation_plan_refinement_processor =
+        Arc::new(ImplementationPlanRefinementProcessor::new());
+    let plan_test_generation_processor = Arc::new(PlanTestGenerationProcessor::new());
     let video_analysis_processor = Arc::new(VideoAnalysisProcessor);
 
     // Register processors
@@ -92,9 +100,12 @@ pub async fn register_job_processors(app_handle: &AppHandle) -> AppResult<()> {
     registry.register(extended_path_finder_processor).await;
     // File relevance assessment processor
     registry.register(file_relevance_assessment_processor).await;
+    registry.register(impact_analysis_processor).await;
     registry.register(web_search_prompts_generator).await;
     registry.register(web_search_executor).await;
     registry.register(implementation_plan_merge_processor).await;
+    registry.register(implementation_plan_refinement_processor).await;
+    registry.register(plan_test_generation_processor).await;
     registry.register(video_analysis_processor).await;
 
     debug!("Job processors registered");
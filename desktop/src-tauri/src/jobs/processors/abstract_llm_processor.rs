@@ -10,7 +10,7 @@ use crate::error::{AppError, AppResult};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processors::utils::{llm_api_utils, prompt_utils};
 use crate::jobs::types::Job;
-use crate::models::OpenRouterUsage;
+use crate::models::{OpenRouterRequestMessage, OpenRouterUsage};
 use crate::utils::unified_prompt_system::ComposedPrompt;
 
 /// Configuration for an LLM task execution
@@ -20,6 +20,8 @@ pub struct LlmTaskConfig {
     pub temperature: f32,
     pub max_tokens: u32,
     pub stream: bool,
+    /// Explicit response cache choice; None defers to the project's per-task setting
+    pub response_cache: Option<bool>,
 }
 
 // Configuration must be explicitly provided to ensure no silent fallbacks
@@ -109,6 +111,45 @@ impl LlmTaskRunner {
         }
     }
 
+    /// Resolve whether this task's LLM request may be served from the server response cache
+    async fn response_cache_enabled(&self) -> bool {
+        if let Some(enabled) = self.config.response_cache {
+            return enabled;
+        }
+
+        let Some(session_repo) = self
+            .app_handle
+            .try_state::<Arc<crate::db_utils::SessionRepository>>()
+        else {
+            return false;
+        };
+        let project_directory = match session_repo.get_session_by_id(&self.job.session_id).await {
+            Ok(Some(session)) => session.project_directory,
+            Ok(None) => return false,
+            Err(e) => {
+                warn!(
+                    "Failed to load session for response cache setting of job {}: {}",
+                    self.job.id, e
+                );
+                return false;
+            }
+        };
+
+        crate::utils::config_resolver::resolve_response_cache_setting(
+            &self.app_handle,
+            self.job.task_type,
+            &project_directory,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            warn!(
+                "Failed to resolve response cache setting for job {}: {}",
+                self.job.id, e
+            );
+            false
+        })
+    }
+
     /// Execute a non-streaming LLM task with unified prompt building
     pub async fn execute_llm_task(
         &self,
@@ -166,6 +207,7 @@ impl LlmTaskRunner {
                 .clone()
                 .unwrap_or_else(|| self.job.task_type.to_string()),
         );
+        api_options.response_cache = self.response_cache_enabled().await;
 
         // Execute the LLM call
         let response =
@@ -206,6 +248,26 @@ impl LlmTaskRunner {
         settings_repo: &crate::db_utils::SettingsRepository,
         repo: &Arc<BackgroundJobRepository>,
         job_id: &str,
+    ) -> AppResult<LlmTaskResult> {
+        self.execute_streaming_llm_task_with_history(
+            context,
+            Vec::new(),
+            settings_repo,
+            repo,
+            job_id,
+        )
+        .await
+    }
+
+    /// Execute a streaming LLM task whose unified prompt is followed by earlier conversation
+    /// turns; `history` is sent after the composed system and user messages
+    pub async fn execute_streaming_llm_task_with_history(
+        &self,
+        context: LlmPromptContext,
+        history: Vec<OpenRouterRequestMessage>,
+        settings_repo: &crate::db_utils::SettingsRepository,
+        repo: &Arc<BackgroundJobRepository>,
+        job_id: &str,
     ) -> AppResult<LlmTaskResult> {
         debug!("Executing streaming LLM task for job {}", self.job.id);
         self.annotate_codex_cli_usage(settings_repo).await;
@@ -261,12 +323,14 @@ impl LlmTaskRunner {
                 .clone()
                 .unwrap_or_else(|| self.job.task_type.to_string()),
         );
+        api_options.response_cache = self.response_cache_enabled().await;
 
         // Get API client
         let llm_client = llm_api_utils::get_api_client(&self.app_handle).await?;
 
         // Create messages for structured streaming (preferred approach)
-        let messages = llm_api_utils::create_openrouter_messages(&system_prompt, &user_prompt);
+        let mut messages = llm_api_utils::create_openrouter_messages(&system_prompt, &user_prompt);
+        messages.extend(history);
 
         // Create streaming handler configuration
         let stream_config = crate::jobs::streaming_handler::create_stream_config(
@@ -365,6 +429,7 @@ impl LlmTaskConfigBuilder {
                 temperature,
                 max_tokens,
                 stream: false, // Only acceptable default since it's boolean operational parameter
+                response_cache: None,
             },
         }
     }
@@ -389,6 +454,11 @@ impl LlmTaskConfigBuilder {
         self
     }
 
+    pub fn response_cache(mut self, enabled: bool) -> Self {
+        self.config.response_cache = Some(enabled);
+        self
+    }
+
     pub fn build(self) -> LlmTaskConfig {
         self.config
     }
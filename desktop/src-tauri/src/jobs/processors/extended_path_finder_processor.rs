@@ -7,7 +7,9 @@ use crate::db_utils::BackgroundJobRepository;
 use crate::error::{AppError, AppResult};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processor_trait::JobProcessor;
-use crate::jobs::processors::utils::path_resolution_utils::to_absolute_path;
+use crate::jobs::processors::utils::path_resolution_utils::{
+    resolve_pipeline_path, workspace_pipeline_path,
+};
 use crate::jobs::processors::utils::{parsing_utils, prompt_utils};
 use crate::jobs::processors::{LlmPromptContext, LlmTaskConfigBuilder, LlmTaskRunner};
 use std::sync::Arc;
@@ -15,7 +17,7 @@ use crate::jobs::types::{
     ExtendedPathFinderPayload, Job, JobPayload, JobProcessResult, JobResultData,
 };
 use crate::models::TaskType;
-use crate::utils::directory_tree::get_directory_tree_with_defaults;
+use crate::utils::directory_tree::get_project_directory_tree;
 use crate::utils::path_utils::{make_relative_to, to_forward_slashes};
 use std::path::PathBuf;
 
@@ -72,6 +74,9 @@ impl JobProcessor for ExtendedPathFinderProcessor {
 
         // Get project directory from session
         let project_directory = &session.project_directory;
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
 
         // Generate directory tree scoped to selected root directories if available
         let directory_tree = if !payload.selected_root_directories.is_empty() {
@@ -93,7 +98,7 @@ impl JobProcessor for ExtendedPathFinderProcessor {
                         e
                     );
                     // Fallback to full directory tree
-                    match get_directory_tree_with_defaults(project_directory).await {
+                    match get_project_directory_tree(project_directory, workspace.as_ref()).await {
                         Ok(tree) => tree,
                         Err(e) => {
                             warn!(
@@ -108,7 +113,7 @@ impl JobProcessor for ExtendedPathFinderProcessor {
         } else {
             // No selected roots, use full project directory tree
             debug!("No selected root directories, using full project tree");
-            match get_directory_tree_with_defaults(project_directory).await {
+            match get_project_directory_tree(project_directory, workspace.as_ref()).await {
                 Ok(tree) => tree,
                 Err(e) => {
                     warn!(
@@ -123,7 +128,8 @@ impl JobProcessor for ExtendedPathFinderProcessor {
         // Read file contents for all initial paths to provide complete context
         let mut file_contents = std::collections::HashMap::new();
         for path in &payload.initial_paths {
-            let absolute_path = to_absolute_path(path, &session.project_directory);
+            let absolute_path =
+                resolve_pipeline_path(path, &session.project_directory, workspace.as_ref());
             match fs::read_to_string(&absolute_path).await {
                 Ok(content) => {
                     file_contents.insert(path.clone(), content);
@@ -238,12 +244,21 @@ impl JobProcessor for ExtendedPathFinderProcessor {
                 path_from_llm.clone()
             };
 
-            let absolute_path = to_absolute_path(&corrected_path, &session.project_directory);
+            let absolute_path = resolve_pipeline_path(
+                &corrected_path,
+                &session.project_directory,
+                workspace.as_ref(),
+            );
             match fs::metadata(&absolute_path).await {
                 Ok(metadata) if metadata.is_file() => {
-                    // Normalize the path: convert to relative if within project, keep absolute if external
+                    // Normalize the path: qualify it through the workspace if there is one,
+                    // otherwise convert to relative if within project and keep absolute outside
                     // Always use forward slashes for cross-platform consistency
-                    let normalized_path = if absolute_path.starts_with(&project_dir) {
+                    let normalized_path = if let Some(qualified) =
+                        workspace_pipeline_path(&absolute_path, workspace.as_ref())
+                    {
+                        qualified
+                    } else if absolute_path.starts_with(&project_dir) {
                         // File is within project directory - convert to relative path
                         match make_relative_to(&absolute_path, &project_dir) {
                             Ok(rel_path) => to_forward_slashes(&rel_path.to_string_lossy()),
@@ -256,7 +271,7 @@ impl JobProcessor for ExtendedPathFinderProcessor {
                             }
                         }
                     } else {
-                        // File is external - keep as absolute path (still normalize slashes)
+                        // File is external (still normalize slashes)
                         to_forward_slashes(&absolute_path.to_string_lossy())
                     };
 
@@ -11,6 +11,9 @@ use crate::jobs::processors::abstract_llm_processor::{
     LlmPromptContext, LlmTaskConfig, LlmTaskConfigBuilder, LlmTaskRunner,
 };
 use crate::jobs::processors::utils::parsing_utils;
+use crate::jobs::processors::utils::path_resolution_utils::{
+    resolve_pipeline_path, workspace_pipeline_path,
+};
 use std::sync::Arc;
 use crate::jobs::types::{
     FileRelevanceAssessmentPayload, FileRelevanceAssessmentProcessingDetails,
@@ -18,6 +21,7 @@ use crate::jobs::types::{
     JobProcessResult, JobResultData,
 };
 use crate::utils::path_utils::{make_relative_to, to_forward_slashes};
+use crate::utils::workspace_paths::WorkspaceLayout;
 use std::path::PathBuf;
 
 pub struct FileRelevanceAssessmentProcessor;
@@ -36,13 +40,14 @@ impl FileRelevanceAssessmentProcessor {
 
     /// Estimate tokens for a batch of files
     async fn estimate_tokens_for_file_batch(
-        project_dir: &std::path::Path,
+        project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
         file_paths: &[String],
     ) -> AppResult<u32> {
         let mut total_tokens = 0u32;
 
         for file_path in file_paths {
-            let full_path = project_dir.join(file_path);
+            let full_path = resolve_pipeline_path(file_path, project_directory, workspace);
             if let Ok(content) = fs::read_to_string(&full_path).await {
                 let extension = full_path
                     .extension()
@@ -73,6 +78,7 @@ impl FileRelevanceAssessmentProcessor {
     async fn create_content_aware_chunks(
         files: &[String],
         project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
         max_chunk_tokens: u32,
     ) -> AppResult<Vec<Vec<String>>> {
         let mut chunks = Vec::new();
@@ -86,7 +92,7 @@ impl FileRelevanceAssessmentProcessor {
 
         for file_path in files {
             // Get content-based token estimation using standard utilities
-            let full_path = std::path::Path::new(project_directory).join(file_path);
+            let full_path = resolve_pipeline_path(file_path, project_directory, workspace);
             let file_tokens = match fs::read_to_string(&full_path).await {
                 Ok(content) => {
                     let extension = std::path::Path::new(file_path)
@@ -168,6 +174,7 @@ impl FileRelevanceAssessmentProcessor {
         total_chunks: usize,
         task_description: &str,
         project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
         task_runner: &LlmTaskRunner,
         settings_repo: &crate::db_utils::SettingsRepository,
         repo: &crate::db_utils::BackgroundJobRepository,
@@ -192,7 +199,7 @@ impl FileRelevanceAssessmentProcessor {
         // Load content for files in this chunk
         let mut file_contents = std::collections::HashMap::new();
         for relative_path_str in chunk {
-            let full_path = std::path::Path::new(project_directory).join(relative_path_str);
+            let full_path = resolve_pipeline_path(relative_path_str, project_directory, workspace);
             match fs::read_to_string(&full_path).await {
                 Ok(content) => {
                     file_contents.insert(relative_path_str.clone(), content);
@@ -292,6 +299,9 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
             .await?
             .ok_or_else(|| AppError::JobError(format!("Session {} not found", job.session_id)))?;
         let project_directory = &session.project_directory;
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
 
         // Get model settings using project-aware configuration
         let model_settings =
@@ -336,7 +346,7 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
         // Estimate total tokens for all files first
         let mut total_estimated_tokens = 0u32;
         for file_path in &payload.locally_filtered_files {
-            let full_path = std::path::Path::new(project_directory).join(file_path);
+            let full_path = resolve_pipeline_path(file_path, project_directory, workspace.as_ref());
             if let Ok(content) = fs::read_to_string(&full_path).await {
                 total_estimated_tokens += Self::estimate_tokens(&content, 4); // ~4 chars per token average
             }
@@ -363,6 +373,7 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
             match Self::create_content_aware_chunks(
                 &payload.locally_filtered_files,
                 project_directory,
+                workspace.as_ref(),
                 CHUNKING_THRESHOLD,
             )
             .await
@@ -406,6 +417,7 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
                 let chunk = chunk.clone();
                 let task_description = payload.task_description.clone();
                 let project_directory = project_directory.to_string();
+                let workspace = workspace.clone();
                 let task_runner = task_runner.clone();
                 let settings_repo = settings_repo.clone();
                 let repo = repo.clone();
@@ -420,6 +432,7 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
                         total_chunks,
                         &task_description,
                         &project_directory,
+                        workspace.as_ref(),
                         &task_runner,
                         &settings_repo,
                         &repo,
@@ -544,15 +557,20 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
                     // Already absolute
                     PathBuf::from(corrected_path)
                 } else {
-                    // Relative path
-                    project_dir.join(&corrected_path)
+                    // Relative or repository-qualified path
+                    resolve_pipeline_path(&corrected_path, project_directory, workspace.as_ref())
                 };
 
             match tokio::fs::metadata(&absolute_path).await {
                 Ok(metadata) if metadata.is_file() => {
-                    // Normalize the path: convert to relative if within project, keep absolute if external
+                    // Normalize the path: qualify it through the workspace if there is one,
+                    // otherwise convert to relative if within project and keep absolute outside
                     // Always use forward slashes for cross-platform consistency
-                    let normalized_path = if absolute_path.starts_with(&project_dir) {
+                    let normalized_path = if let Some(qualified) =
+                        workspace_pipeline_path(&absolute_path, workspace.as_ref())
+                    {
+                        qualified
+                    } else if absolute_path.starts_with(&project_dir) {
                         // File is within project directory - convert to relative path
                         match make_relative_to(&absolute_path, &project_dir) {
                             Ok(rel_path) => to_forward_slashes(&rel_path.to_string_lossy()),
@@ -565,7 +583,7 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
                             }
                         }
                     } else {
-                        // File is external - keep as absolute path (still normalize slashes)
+                        // File is external (still normalize slashes)
                         to_forward_slashes(&absolute_path.to_string_lossy())
                     };
 
@@ -583,7 +601,8 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
 
         // Calculate token count for validated relevant paths
         let token_count = match Self::estimate_tokens_for_file_batch(
-            &std::path::Path::new(project_directory),
+            project_directory,
+            workspace.as_ref(),
             &validated_relevant_paths,
         )
         .await
@@ -609,7 +628,7 @@ impl JobProcessor for FileRelevanceAssessmentProcessor {
         for (i, chunk) in chunks.iter().enumerate() {
             let mut total_tokens = 0u32;
             for f in chunk {
-                let full_path = std::path::Path::new(project_directory).join(f);
+                let full_path = resolve_pipeline_path(f, project_directory, workspace.as_ref());
                 if let Ok(content) = fs::read_to_string(&full_path).await {
                     let extension = std::path::Path::new(f)
                         .extension()
@@ -0,0 +1,189 @@
+use log::{info, warn};
+use serde_json::json;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::error::{AppError, AppResult};
+use crate::jobs::job_processor_utils;
+use crate::jobs::processor_trait::JobProcessor;
+use crate::jobs::types::{Job, JobPayload, JobProcessResult, JobResultData};
+use crate::utils::dependency_graph::{DependencyGraph, DependentFile};
+use crate::utils::git_utils;
+use crate::utils::repo_map::SourceLanguage;
+use crate::utils::workspace_paths::{WorkspaceLayout, repo_qualified_path, split_repo_path};
+
+/// Source files larger than this are left out of the dependency graph
+const MAX_SOURCE_FILE_BYTES: u64 = 512 * 1024;
+/// Dependents added to the selection at most
+const MAX_DEPENDENT_FILES: usize = 40;
+
+pub struct ImpactAnalysisProcessor;
+
+impl ImpactAnalysisProcessor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Dependents of the selected files of one repository, with paths relative to it
+    fn repository_dependents(directory: &Path, selected: &[String]) -> Vec<DependentFile> {
+        let files: Vec<String> = match git_utils::get_all_non_ignored_files(directory) {
+            Ok((files, _)) => files
+                .into_iter()
+                .map(|path| path.to_string_lossy().replace('\\', "/"))
+                .collect(),
+            Err(e) => {
+                warn!("Failed to list files of {}: {}", directory.display(), e);
+                return Vec::new();
+            }
+        };
+
+        let sources: Vec<(String, String)> = files
+            .iter()
+            .filter(|path| {
+                SourceLanguage::from_path(path).is_some()
+                    || path.rsplit('/').next() == Some("go.mod")
+            })
+            .filter_map(|path| {
+                let full_path = directory.join(path);
+                let size = std::fs::metadata(&full_path).ok()?.len();
+                if size > MAX_SOURCE_FILE_BYTES {
+                    return None;
+                }
+                std::fs::read_to_string(&full_path)
+                    .ok()
+                    .map(|content| (path.clone(), content))
+            })
+            .collect();
+
+        DependencyGraph::build(&files, &sources).find_dependents(selected, MAX_DEPENDENT_FILES)
+    }
+
+    /// Dependents of the selected files in every repository of the workspace they belong to.
+    /// Paths keep the pipeline form: relative for the session's repository, qualified for the
+    /// others.
+    fn find_dependents(
+        project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
+        selected_paths: &[String],
+    ) -> Vec<DependentFile> {
+        let Some(workspace) = workspace else {
+            let selected: Vec<String> = selected_paths
+                .iter()
+                .filter(|path| {
+                    split_repo_path(path).0.is_none() && !Path::new(path.as_str()).is_absolute()
+                })
+                .cloned()
+                .collect();
+            return Self::repository_dependents(Path::new(project_directory), &selected);
+        };
+
+        let mut dependents = Vec::new();
+        for group in workspace.group_by_repository(selected_paths) {
+            if group.files.is_empty() {
+                continue;
+            }
+            let Some(repository) = workspace.repository(&group.label) else {
+                continue;
+            };
+            let qualify = |path: String| {
+                if group.is_current {
+                    path
+                } else {
+                    repo_qualified_path(&group.label, &path)
+                }
+            };
+            dependents.extend(
+                Self::repository_dependents(&repository.directory, &group.files)
+                    .into_iter()
+                    .map(|dependent| DependentFile {
+                        path: qualify(dependent.path),
+                        depends_on: qualify(dependent.depends_on),
+                        reason: dependent.reason,
+                    }),
+            );
+        }
+        dependents.truncate(MAX_DEPENDENT_FILES);
+        dependents
+    }
+}
+
+#[async_trait::async_trait]
+impl JobProcessor for ImpactAnalysisProcessor {
+    fn name(&self) -> &'static str {
+        "ImpactAnalysis"
+    }
+
+    fn can_handle(&self, job: &Job) -> bool {
+        matches!(job.payload, JobPayload::ImpactAnalysis(_))
+    }
+
+    async fn process(&self, job: Job, app_handle: AppHandle) -> AppResult<JobProcessResult> {
+        let payload = match &job.payload {
+            JobPayload::ImpactAnalysis(p) => p,
+            _ => return Err(AppError::JobError("Invalid payload type".to_string())),
+        };
+
+        let (repo, session_repo, _settings_repo, _db_job) =
+            job_processor_utils::setup_job_processing(&job.id, &app_handle).await?;
+
+        let session = session_repo
+            .get_session_by_id(&job.session_id)
+            .await?
+            .ok_or_else(|| AppError::JobError(format!("Session {} not found", job.session_id)))?;
+
+        job_processor_utils::log_job_start(&job.id, "impact analysis");
+
+        if job_processor_utils::check_job_canceled(&repo, &job.id).await? {
+            return Ok(JobProcessResult::canceled(
+                job.id.clone(),
+                "Job was canceled by user".to_string(),
+            ));
+        }
+
+        let project_directory = session.project_directory.clone();
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, &project_directory)
+                .await;
+        let selected_paths = payload.selected_paths.clone();
+        let dependents = tokio::task::spawn_blocking(move || {
+            Self::find_dependents(&project_directory, workspace.as_ref(), &selected_paths)
+        })
+        .await
+        .map_err(|e| AppError::JobError(format!("Failed to analyze dependencies: {}", e)))?;
+
+        if job_processor_utils::check_job_canceled(&repo, &job.id).await? {
+            return Ok(JobProcessResult::canceled(
+                job.id.clone(),
+                "Job was canceled by user".to_string(),
+            ));
+        }
+
+        info!(
+            "Impact analysis job {}: {} dependents of {} selected files",
+            job.id,
+            dependents.len(),
+            payload.selected_paths.len()
+        );
+
+        // Only the dependents are returned; the selected files are already in the session
+        let files: Vec<&str> = dependents.iter().map(|d| d.path.as_str()).collect();
+        let summary = if dependents.is_empty() {
+            "No dependent files found".to_string()
+        } else {
+            format!("Found {} dependent files", dependents.len())
+        };
+        Ok(JobProcessResult::success(
+            job.id.clone(),
+            JobResultData::Json(json!({
+                "files": files,
+                "count": files.len(),
+                "summary": summary,
+                "dependents": dependents,
+            })),
+        )
+        .with_metadata(json!({
+            "selectedPaths": payload.selected_paths.len(),
+            "dependentFiles": dependents.len(),
+        })))
+    }
+}
@@ -8,6 +8,7 @@ use crate::error::{AppError, AppResult};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processor_trait::JobProcessor;
 use crate::jobs::processors::utils::parsing_utils;
+use crate::jobs::processors::utils::path_resolution_utils;
 use crate::jobs::processors::utils::prompt_utils;
 use crate::jobs::processors::{LlmPromptContext, LlmTaskConfigBuilder, LlmTaskRunner};
 use crate::jobs::types::{
@@ -121,11 +122,18 @@ impl JobProcessor for ImplementationPlanMergeProcessor {
         // The LLM will work with the raw text directly
 
         // Load file contents for context (similar to implementation plan processor)
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
         let mut file_contents_map = std::collections::HashMap::new();
         let relevant_files_vec: Vec<String> = all_relevant_files.into_iter().collect();
 
         for relative_path_str in &relevant_files_vec {
-            let full_path = std::path::Path::new(project_directory).join(relative_path_str);
+            let full_path = path_resolution_utils::resolve_pipeline_path(
+                relative_path_str,
+                project_directory,
+                workspace.as_ref(),
+            );
             match fs::read_to_string(&full_path).await {
                 Ok(content) => {
                     file_contents_map.insert(relative_path_str.clone(), content);
@@ -137,16 +145,18 @@ impl JobProcessor for ImplementationPlanMergeProcessor {
         }
 
         // Generate directory tree for better context
-        let directory_tree =
-            match crate::utils::directory_tree::get_directory_tree_with_defaults(project_directory)
-                .await
-            {
-                Ok(tree) => Some(tree),
-                Err(e) => {
-                    warn!("Failed to generate directory tree: {}", e);
-                    None
-                }
-            };
+        let directory_tree = match crate::utils::directory_tree::get_project_directory_tree(
+            project_directory,
+            workspace.as_ref(),
+        )
+        .await
+        {
+            Ok(tree) => Some(tree),
+            Err(e) => {
+                warn!("Failed to generate directory tree: {}", e);
+                None
+            }
+        };
 
         // Construct the user prompt with task description, source plans and instructions
         let mut prompt_content = String::new();
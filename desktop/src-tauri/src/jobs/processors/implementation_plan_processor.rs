@@ -9,17 +9,21 @@ use crate::error::{AppError, AppResult};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processor_trait::JobProcessor;
 use crate::jobs::processors::utils::parsing_utils;
+use crate::jobs::processors::utils::path_resolution_utils;
 use crate::jobs::processors::utils::prompt_utils;
 use crate::jobs::processors::{
     LlmPromptContext, LlmTaskConfigBuilder, LlmTaskResult, LlmTaskRunner,
 };
 use crate::jobs::types::{
-    Job, JobPayload, JobProcessResult, JobResultData, StructuredImplementationPlan,
-    StructuredImplementationPlanStep,
+    ImplementationPlanPayload, Job, JobPayload, JobProcessResult, JobResultData,
+    StructuredImplementationPlan, StructuredImplementationPlanStep,
 };
 use crate::models::{JobStatus, OpenRouterContent, OpenRouterRequestMessage, TaskType};
+use crate::services::plan_templates;
 use crate::utils::job_metadata_builder::JobMetadataBuilder;
-use crate::utils::{get_timestamp, path_utils};
+use crate::utils::repo_map::{self, RepoMapContext};
+use crate::utils::workspace_paths::WorkspaceLayout;
+use crate::utils::{estimate_tokens, get_model_context_window, get_timestamp, path_utils};
 
 pub struct ImplementationPlanProcessor;
 
@@ -27,6 +31,118 @@ impl ImplementationPlanProcessor {
     pub fn new() -> Self {
         Self {}
     }
+
+    /// Read the relevant files in rank order; unreadable files are skipped
+    pub(crate) async fn load_ranked_files(
+        relevant_files: &[String],
+        task_description: &str,
+        project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
+    ) -> Vec<(String, String)> {
+        let mut loaded_files = Vec::new();
+        for relative_path_str in repo_map::rank_files(relevant_files, task_description) {
+            let full_path = path_resolution_utils::resolve_pipeline_path(
+                &relative_path_str,
+                project_directory,
+                workspace,
+            );
+            match fs::read_to_string(&full_path).await {
+                Ok(content) => {
+                    loaded_files.push((relative_path_str, content));
+                }
+                Err(e) => {
+                    warn!("Failed to read file {}: {}", full_path.display(), e);
+                }
+            }
+        }
+        loaded_files
+    }
+
+    pub(crate) async fn full_directory_tree(
+        project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
+    ) -> Option<String> {
+        match crate::utils::directory_tree::get_project_directory_tree(project_directory, workspace)
+            .await
+        {
+            Ok(tree) => Some(tree),
+            Err(e) => {
+                warn!("Failed to generate directory tree: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Directory tree for a plan: none when project structure is excluded, scoped to the
+    /// selected root directories when there are any, the full project tree otherwise
+    pub(crate) async fn plan_directory_tree(
+        payload: &ImplementationPlanPayload,
+        project_directory: &str,
+        workspace: Option<&WorkspaceLayout>,
+    ) -> Option<String> {
+        if !payload.include_project_structure {
+            debug!("Skipping directory tree generation as include_project_structure is false");
+            return None;
+        }
+        match payload.selected_root_directories.as_ref() {
+            Some(root_dirs) if !root_dirs.is_empty() => {
+                debug!(
+                    "Using scoped directory tree for {} root directories",
+                    root_dirs.len()
+                );
+                match crate::utils::directory_tree::get_combined_directory_tree_for_roots(root_dirs)
+                    .await
+                {
+                    Ok(tree) => Some(tree),
+                    Err(e) => {
+                        warn!(
+                            "Failed to generate scoped directory tree: {}, falling back to full tree",
+                            e
+                        );
+                        Self::full_directory_tree(project_directory, workspace).await
+                    }
+                }
+            }
+            _ => Self::full_directory_tree(project_directory, workspace).await,
+        }
+    }
+
+    /// Fit the relevant files into the model's context window: the highest-ranked files keep
+    /// their full text and the rest are sent as a repository map. Without a known context
+    /// window every file is sent in full.
+    pub(crate) async fn build_file_context(
+        app_handle: &AppHandle,
+        model: &str,
+        max_output_tokens: u32,
+        task_description: &str,
+        directory_tree: Option<&str>,
+        files: Vec<(String, String)>,
+    ) -> AppResult<RepoMapContext> {
+        let context_window = match get_model_context_window(model, app_handle).await {
+            Ok(context_window) => context_window,
+            Err(e) => {
+                warn!(
+                    "Unknown context window for {}, sending full file contents: {}",
+                    model, e
+                );
+                return Ok(RepoMapContext::full(files));
+            }
+        };
+
+        let model = model.to_string();
+        let prompt_text = format!(
+            "{}\n{}",
+            task_description,
+            directory_tree.unwrap_or_default()
+        );
+        tokio::task::spawn_blocking(move || {
+            let reserved = max_output_tokens.saturating_add(estimate_tokens(&prompt_text, &model));
+            let budget = repo_map::file_token_budget(context_window, reserved);
+            repo_map::build_repo_map_context(files, budget, &model)
+        })
+        .await
+        .map_err(|e| AppError::JobError(format!("Failed to build file context: {}", e)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -69,87 +185,82 @@ impl JobProcessor for ImplementationPlanProcessor {
 
         job_processor_utils::log_job_start(&job_id, "implementation plan");
         let project_directory = &session.project_directory;
-
-        // Load file contents and generate directory tree - FULL CONTENT WITHOUT TRUNCATION
-        let mut file_contents_map = std::collections::HashMap::new();
-        for relative_path_str in &payload.relevant_files {
-            let full_path = std::path::Path::new(project_directory).join(relative_path_str);
-            match fs::read_to_string(&full_path).await {
-                Ok(content) => {
-                    file_contents_map.insert(relative_path_str.clone(), content);
-                }
-                Err(e) => {
-                    warn!("Failed to read file {}: {}", full_path.display(), e);
-                }
-            }
-        }
-        let file_contents = Some(file_contents_map);
-
-        // Generate directory tree only if include_project_structure is true
-        let directory_tree = if !payload.include_project_structure {
-            debug!("Skipping directory tree generation as include_project_structure is false");
-            None
-        } else if let Some(ref root_dirs) = payload.selected_root_directories {
-            if !root_dirs.is_empty() {
-                debug!(
-                    "Using scoped directory tree for {} root directories",
-                    root_dirs.len()
-                );
-                match crate::utils::directory_tree::get_combined_directory_tree_for_roots(root_dirs)
-                    .await
-                {
-                    Ok(tree) => Some(tree),
-                    Err(e) => {
-                        warn!(
-                            "Failed to generate scoped directory tree: {}, falling back to full tree",
-                            e
-                        );
-                        // Fallback to full directory tree
-                        match crate::utils::directory_tree::get_directory_tree_with_defaults(
-                            project_directory,
-                        )
-                        .await
-                        {
-                            Ok(tree) => Some(tree),
-                            Err(e) => {
-                                warn!("Failed to generate fallback directory tree: {}", e);
-                                None
-                            }
-                        }
-                    }
-                }
-            } else {
-                // Empty root directories - use full tree
-                match crate::utils::directory_tree::get_directory_tree_with_defaults(
-                    project_directory,
-                )
-                .await
-                {
-                    Ok(tree) => Some(tree),
-                    Err(e) => {
-                        warn!("Failed to generate directory tree: {}", e);
-                        None
-                    }
-                }
-            }
-        } else {
-            // No root directories specified - use full tree
-            match crate::utils::directory_tree::get_directory_tree_with_defaults(project_directory)
-                .await
-            {
-                Ok(tree) => Some(tree),
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
+
+        // Team plan template of the session: its sections are asked for in the prompt and the
+        // generated plan is checked against it
+        let plan_template =
+            match plan_templates::get_session_plan_template(&app_handle, &job.session_id).await {
+                Ok(selection) => selection.template,
                 Err(e) => {
-                    warn!("Failed to generate directory tree: {}", e);
+                    warn!("Failed to resolve plan template for job {}: {}", job.id, e);
                     None
                 }
-            }
+            };
+        let prompt_task_description = match &plan_template {
+            Some(template) => format!(
+                "{}\n\n{}",
+                payload.task_description,
+                template.prompt_instructions()
+            ),
+            None => payload.task_description.clone(),
         };
 
-        // Build unified prompt using full content without preemptive truncation
+        // Load file contents in rank order; files beyond the context budget become a repo map
+        let loaded_files = Self::load_ranked_files(
+            &payload.relevant_files,
+            &payload.task_description,
+            project_directory,
+            workspace.as_ref(),
+        )
+        .await;
+
+        let directory_tree =
+            Self::plan_directory_tree(payload, project_directory, workspace.as_ref()).await;
+
+        let file_context = Self::build_file_context(
+            &app_handle,
+            &model_used,
+            max_output_tokens,
+            &payload.task_description,
+            directory_tree.as_deref(),
+            loaded_files,
+        )
+        .await?;
+        if !file_context.mapped_files.is_empty() {
+            info!(
+                "Implementation plan job {}: {} files in full, {} as repository map (budget {} tokens)",
+                job.id,
+                file_context.full_files.len(),
+                file_context.mapped_files.len(),
+                file_context.budget_tokens.unwrap_or_default()
+            );
+        }
+        if let Err(e) = repo
+            .update_job_metadata(
+                &job.id,
+                &json!({
+                    "fileContext": {
+                        "budgetTokens": file_context.budget_tokens,
+                        "estimatedTokens": file_context.estimated_tokens,
+                        "fullFiles": file_context.full_files,
+                        "mappedFiles": file_context.mapped_files,
+                    }
+                }),
+            )
+            .await
+        {
+            warn!("Failed to record file context for job {}: {}", job.id, e);
+        }
+        let file_contents = Some(file_context.file_contents);
+
+        // Build unified prompt from the budgeted file context
         let composed_prompt = prompt_utils::build_unified_prompt(
             &job,
             &app_handle,
-            payload.task_description.clone(),
+            prompt_task_description.clone(),
             file_contents.clone(),
             directory_tree.clone(),
             &model_used,
@@ -221,7 +332,7 @@ impl JobProcessor for ImplementationPlanProcessor {
 
         // Create prompt context - reuse the file contents we already loaded
         let prompt_context = LlmPromptContext {
-            task_description: payload.task_description.clone(),
+            task_description: prompt_task_description,
             file_contents,
             directory_tree, // Use the same tree we generated above
         };
@@ -248,7 +359,8 @@ impl JobProcessor for ImplementationPlanProcessor {
         };
 
         // Use the response from the task runner
-        let response_content = llm_result.response.clone();
+        let mut response_content = llm_result.response.clone();
+        let mut follow_up_usage = None;
 
         // Continue with regular processing using the collected response
         if response_content.is_empty() {
@@ -268,6 +380,81 @@ impl JobProcessor for ImplementationPlanProcessor {
             ));
         }
 
+        // Check the plan against the session's template, asking once for missing sections
+        let plan_template_check = match &plan_template {
+            Some(template) => {
+                let mut check =
+                    plan_templates::check_plan_against_template(&response_content, template);
+                let follow_up = template
+                    .request_missing_sections
+                    .then(|| {
+                        plan_templates::missing_sections_request(
+                            &response_content,
+                            template,
+                            &check,
+                        )
+                    })
+                    .flatten();
+                if let Some(request) = follow_up {
+                    info!(
+                        "Implementation plan job {} is missing {} template sections, requesting them",
+                        job.id,
+                        check.missing_required().count()
+                    );
+                    let follow_up_config = LlmTaskConfigBuilder::new(
+                        model_used.clone(),
+                        temperature,
+                        max_output_tokens,
+                    )
+                    .stream(false)
+                    .build();
+                    let follow_up_context = LlmPromptContext {
+                        task_description: request,
+                        file_contents: None,
+                        directory_tree: None,
+                    };
+                    match LlmTaskRunner::new(app_handle.clone(), job.clone(), follow_up_config)
+                        .execute_llm_task(follow_up_context, &settings_repo)
+                        .await
+                    {
+                        Ok(result) => {
+                            let missing: Vec<String> =
+                                check.missing_required().map(|s| s.id.clone()).collect();
+                            let missing: Vec<&str> = missing.iter().map(String::as_str).collect();
+                            response_content = plan_templates::merge_plan_sections(
+                                &response_content,
+                                &result.response,
+                                &missing,
+                            );
+                            follow_up_usage = result.usage;
+                            check = plan_templates::check_plan_against_template(
+                                &response_content,
+                                template,
+                            );
+                        }
+                        Err(e) => warn!(
+                            "Follow-up for missing plan sections failed for job {}: {}",
+                            job.id, e
+                        ),
+                    }
+                    check.follow_up_requested = true;
+                }
+                if !check.passed {
+                    warn!(
+                        "Implementation plan job {} is missing required template sections: {}",
+                        job.id,
+                        check
+                            .missing_required()
+                            .map(|s| s.id.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+                Some(check)
+            }
+            None => None,
+        };
+
         // Use the raw LLM response directly
 
         // Create a simple structured plan for UI compatibility
@@ -316,6 +503,9 @@ impl JobProcessor for ImplementationPlanProcessor {
             "isStreaming": false,
             "sessionName": session_name
         });
+        if let Some(check) = &plan_template_check {
+            impl_plan_additional_params["planTemplateCheck"] = json!(check);
+        }
 
         // Remove streaming-specific fields that should not persist for completed jobs
         if let Some(obj) = impl_plan_additional_params.as_object_mut() {
@@ -328,12 +518,22 @@ impl JobProcessor for ImplementationPlanProcessor {
 
         // Extract system prompt template, usage and cost
         let system_prompt_template = llm_result.system_prompt_template.clone();
-        let usage_for_result = llm_result.usage.clone();
-        let actual_cost = llm_result
+        let mut usage_for_result = llm_result.usage.clone();
+        let mut actual_cost = llm_result
             .usage
             .as_ref()
             .and_then(|u| u.cost)
             .unwrap_or(0.0);
+        // Include the follow-up turn for missing template sections
+        if let Some(extra) = follow_up_usage {
+            actual_cost += extra.cost.unwrap_or(0.0);
+            if let Some(usage) = usage_for_result.as_mut() {
+                usage.prompt_tokens += extra.prompt_tokens;
+                usage.completion_tokens += extra.completion_tokens;
+                usage.cache_write_tokens += extra.cache_write_tokens;
+                usage.cache_read_tokens += extra.cache_read_tokens;
+            }
+        }
 
         Ok(
             JobProcessResult::success(job.id.clone(), JobResultData::Text(response_content))
@@ -0,0 +1,253 @@
+use log::{error, info, warn};
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::error::{AppError, AppResult};
+use crate::jobs::job_payload_utils;
+use crate::jobs::job_processor_utils;
+use crate::jobs::processor_trait::JobProcessor;
+use crate::jobs::processors::{
+    ImplementationPlanProcessor, LlmPromptContext, LlmTaskConfigBuilder, LlmTaskRunner,
+};
+use crate::jobs::types::{Job, JobPayload, JobProcessResult, JobResultData};
+use crate::models::OpenRouterContent;
+use crate::services::{plan_conversations, plan_revisions};
+use crate::utils::get_timestamp;
+
+pub struct ImplementationPlanRefinementProcessor;
+
+impl ImplementationPlanRefinementProcessor {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait::async_trait]
+impl JobProcessor for ImplementationPlanRefinementProcessor {
+    fn name(&self) -> &'static str {
+        "ImplementationPlanRefinementProcessor"
+    }
+
+    fn can_handle(&self, job: &Job) -> bool {
+        matches!(job.payload, JobPayload::ImplementationPlanRefinement(_))
+    }
+
+    async fn process(&self, job: Job, app_handle: AppHandle) -> AppResult<JobProcessResult> {
+        // Extract payload
+        let payload = match &job.payload {
+            JobPayload::ImplementationPlanRefinement(p) => p,
+            _ => return Err(AppError::JobError("Invalid payload type".to_string())),
+        };
+
+        // Setup job processing
+        let (repo, session_repo, settings_repo, db_job) =
+            job_processor_utils::setup_job_processing(&job.id, &app_handle).await?;
+
+        let session = session_repo
+            .get_session_by_id(&job.session_id)
+            .await?
+            .ok_or_else(|| AppError::JobError(format!("Session {} not found", job.session_id)))?;
+
+        let (model_used, temperature, max_output_tokens) =
+            job_processor_utils::get_llm_task_config(&db_job, &app_handle, &session).await?;
+
+        job_processor_utils::log_job_start(&job.id, "implementation plan refinement");
+        let project_directory = &session.project_directory;
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
+
+        let plan_job = repo
+            .get_job_by_id(&payload.plan_job_id)
+            .await?
+            .ok_or_else(|| AppError::JobError(format!("Plan {} not found", payload.plan_job_id)))?;
+        let current_plan = plan_job.response.clone().unwrap_or_default();
+        let initial_plan =
+            plan_revisions::get_plan_revision(&app_handle, &payload.plan_job_id, 1).await?;
+
+        // The plan and the earlier turns follow the original prompt as conversation history
+        let conversation = repo.list_plan_conversation(&payload.plan_job_id).await?;
+        let turns = plan_conversations::completed_turns(&conversation, Some(job.id.as_str()));
+        let history = plan_conversations::build_refinement_history(
+            &initial_plan.content,
+            &turns,
+            &current_plan,
+            &payload.message,
+        );
+
+        // Rebuild the context the plan was generated from; merged plans only have the session
+        let (task_description, loaded_files, directory_tree) =
+            match job_payload_utils::convert_db_job_to_job(&plan_job).map(|j| j.payload) {
+                Ok(JobPayload::ImplementationPlan(original)) => (
+                    original.task_description.clone(),
+                    ImplementationPlanProcessor::load_ranked_files(
+                        &original.relevant_files,
+                        &original.task_description,
+                        project_directory,
+                        workspace.as_ref(),
+                    )
+                    .await,
+                    ImplementationPlanProcessor::plan_directory_tree(
+                        &original,
+                        project_directory,
+                        workspace.as_ref(),
+                    )
+                    .await,
+                ),
+                _ => (
+                    session.task_description.clone().unwrap_or_default(),
+                    Vec::new(),
+                    ImplementationPlanProcessor::full_directory_tree(
+                        project_directory,
+                        workspace.as_ref(),
+                    )
+                    .await,
+                ),
+            };
+
+        // The history takes part of the context window, so it counts against the file budget
+        let history_text = history
+            .iter()
+            .flat_map(|message| &message.content)
+            .filter_map(|content| match content {
+                OpenRouterContent::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let file_context = ImplementationPlanProcessor::build_file_context(
+            &app_handle,
+            &model_used,
+            max_output_tokens,
+            &format!("{}\n{}", task_description, history_text),
+            directory_tree.as_deref(),
+            loaded_files,
+        )
+        .await?;
+
+        let llm_config =
+            LlmTaskConfigBuilder::new(model_used.clone(), temperature, max_output_tokens)
+                .stream(true)
+                .build();
+        let task_runner = LlmTaskRunner::new(app_handle.clone(), job.clone(), llm_config);
+        let prompt_context = LlmPromptContext {
+            task_description,
+            file_contents: if file_context.file_contents.is_empty() {
+                None
+            } else {
+                Some(file_context.file_contents)
+            },
+            directory_tree,
+        };
+
+        // Check if job has been canceled before calling the LLM
+        if job_processor_utils::check_job_canceled(&repo, &job.id).await? {
+            return Ok(JobProcessResult::canceled(
+                job.id.clone(),
+                "Job was canceled by user".to_string(),
+            ));
+        }
+
+        let llm_result = match task_runner
+            .execute_streaming_llm_task_with_history(
+                prompt_context,
+                history,
+                &settings_repo,
+                &repo,
+                &job.id,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                error!("Streaming LLM task execution failed: {}", e);
+                let error_msg = format!("Streaming LLM task execution failed: {}", e);
+                return Ok(JobProcessResult::failure(job.id.clone(), error_msg));
+            }
+        };
+
+        let response_content = llm_result.response.clone();
+        if response_content.trim().is_empty() {
+            let error_msg = "No content received from LLM stream";
+            error!(
+                "Implementation plan refinement job {} failed: {}",
+                job.id, error_msg
+            );
+            return Ok(JobProcessResult::failure(
+                job.id.clone(),
+                error_msg.to_string(),
+            ));
+        }
+
+        // Check if job has been canceled after LLM call but before the plan is changed
+        if job_processor_utils::check_job_canceled(&repo, &job.id).await? {
+            return Ok(JobProcessResult::canceled(
+                job.id.clone(),
+                "Job was canceled by user".to_string(),
+            ));
+        }
+
+        let reply = match plan_conversations::record_refinement_reply(
+            &app_handle,
+            &payload.plan_job_id,
+            &job.id,
+            &response_content,
+        )
+        .await
+        {
+            Ok(reply) => reply,
+            Err(e) => {
+                warn!(
+                    "Failed to store refined plan {} from job {}: {}",
+                    payload.plan_job_id, job.id, e
+                );
+                return Ok(JobProcessResult::failure(
+                    job.id.clone(),
+                    format!("Failed to store refined plan: {}", e),
+                ));
+            }
+        };
+        info!(
+            "Refinement job {} produced revision {:?} of plan {}",
+            job.id, reply.plan_revision, payload.plan_job_id
+        );
+
+        let refinement_metadata = json!({
+            "planJobId": payload.plan_job_id,
+            "planRevision": reply.plan_revision,
+            "refinementMessage": payload.message,
+            "conversationTurns": turns.len() + 1,
+            "refinedAt": get_timestamp(),
+            "sessionName": session.name,
+        });
+
+        let system_prompt_template = llm_result.system_prompt_template.clone();
+        let usage_for_result = llm_result.usage.clone();
+        let actual_cost = llm_result
+            .usage
+            .as_ref()
+            .and_then(|u| u.cost)
+            .unwrap_or(0.0);
+
+        Ok(
+            JobProcessResult::success(job.id.clone(), JobResultData::Text(response_content))
+                .with_tokens(
+                    usage_for_result.as_ref().map(|u| u.prompt_tokens as u32),
+                    usage_for_result
+                        .as_ref()
+                        .map(|u| u.completion_tokens as u32),
+                )
+                .with_cache_tokens(
+                    usage_for_result
+                        .as_ref()
+                        .map(|u| u.cache_write_tokens as i64),
+                    usage_for_result
+                        .as_ref()
+                        .map(|u| u.cache_read_tokens as i64),
+                )
+                .with_system_prompt_template(system_prompt_template)
+                .with_actual_cost(actual_cost)
+                .with_metadata(refinement_metadata),
+        )
+    }
+}
@@ -10,7 +10,10 @@ pub mod utils;
 // Individual workflow stage processors
 pub mod extended_path_finder_processor;
 pub mod file_relevance_assessment_processor;
+pub mod impact_analysis_processor;
 pub mod implementation_plan_merge_processor;
+pub mod implementation_plan_refinement_processor;
+pub mod plan_test_generation_processor;
 pub mod root_folder_selection_processor;
 pub mod video_analysis_processor;
 pub mod web_search_executor_processor;
@@ -28,7 +31,10 @@ pub use text_improvement_processor::TextImprovementProcessor;
 // Individual workflow stage processors
 pub use extended_path_finder_processor::ExtendedPathFinderProcessor;
 pub use file_relevance_assessment_processor::FileRelevanceAssessmentProcessor;
+pub use impact_analysis_processor::ImpactAnalysisProcessor;
 pub use implementation_plan_merge_processor::ImplementationPlanMergeProcessor;
+pub use implementation_plan_refinement_processor::ImplementationPlanRefinementProcessor;
+pub use plan_test_generation_processor::PlanTestGenerationProcessor;
 pub use root_folder_selection_processor::RootFolderSelectionProcessor;
 pub use video_analysis_processor::VideoAnalysisProcessor;
 pub use web_search_executor_processor::WebSearchExecutorProcessor;
@@ -0,0 +1,317 @@
+use log::{error, info, warn};
+use serde_json::json;
+use std::collections::BTreeSet;
+use tauri::AppHandle;
+use tokio::fs;
+
+use crate::error::{AppError, AppResult};
+use crate::jobs::job_processor_utils;
+use crate::jobs::processor_trait::JobProcessor;
+use crate::jobs::processors::utils::parsing_utils::extract_step_headers_from_xml;
+use crate::jobs::processors::utils::path_resolution_utils;
+use crate::jobs::processors::{
+    ImplementationPlanProcessor, LlmPromptContext, LlmTaskConfigBuilder, LlmTaskRunner,
+};
+use crate::jobs::types::{Job, JobPayload, JobProcessResult, JobResultData};
+use crate::services::{plan_progress, plan_test_plans};
+use crate::utils::test_plans::{self, PlanTestPlan};
+use crate::utils::workspace_paths::{WorkspaceLayout, repo_qualified_path};
+use crate::utils::{get_timestamp, git_utils};
+
+/// Existing test files sent as examples of the project's test style
+const MAX_REFERENCE_TEST_FILES: usize = 12;
+
+pub struct PlanTestGenerationProcessor;
+
+impl PlanTestGenerationProcessor {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Files of the project relative to it, and files of the workspace's other repositories
+    /// repository-qualified
+    fn project_files(project_directory: &str, workspace: Option<&WorkspaceLayout>) -> Vec<String> {
+        let list =
+            |directory: &std::path::Path| match git_utils::get_all_non_ignored_files(directory) {
+                Ok((files, _)) => files
+                    .into_iter()
+                    .map(|path| path.to_string_lossy().replace('\\', "/"))
+                    .collect(),
+                Err(e) => {
+                    warn!("Failed to list files of {}: {}", directory.display(), e);
+                    Vec::new()
+                }
+            };
+
+        let mut files: Vec<String> = list(std::path::Path::new(project_directory));
+        for repository in workspace
+            .map(|w| w.repositories.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| !r.is_current)
+        {
+            files.extend(
+                list(&repository.directory)
+                    .into_iter()
+                    .map(|path| repo_qualified_path(&repository.label, &path)),
+            );
+        }
+        files
+    }
+}
+
+#[async_trait::async_trait]
+impl JobProcessor for PlanTestGenerationProcessor {
+    fn name(&self) -> &'static str {
+        "PlanTestGenerationProcessor"
+    }
+
+    fn can_handle(&self, job: &Job) -> bool {
+        matches!(job.payload, JobPayload::PlanTestGeneration(_))
+    }
+
+    async fn process(&self, job: Job, app_handle: AppHandle) -> AppResult<JobProcessResult> {
+        // Extract payload
+        let payload = match &job.payload {
+            JobPayload::PlanTestGeneration(p) => p,
+            _ => return Err(AppError::JobError("Invalid payload type".to_string())),
+        };
+
+        // Setup job processing
+        let (repo, session_repo, settings_repo, db_job) =
+            job_processor_utils::setup_job_processing(&job.id, &app_handle).await?;
+
+        let session = session_repo
+            .get_session_by_id(&job.session_id)
+            .await?
+            .ok_or_else(|| AppError::JobError(format!("Session {} not found", job.session_id)))?;
+
+        let (model_used, temperature, max_output_tokens) =
+            job_processor_utils::get_llm_task_config(&db_job, &app_handle, &session).await?;
+
+        job_processor_utils::log_job_start(&job.id, "plan test generation");
+        let project_directory = &session.project_directory;
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
+
+        // The revision that was signed off when the job was queued
+        let plan_xml = match payload.plan_revision {
+            Some(revision) => repo
+                .get_plan_revision(&payload.plan_job_id, revision)
+                .await?
+                .map(|r| r.content),
+            None => None,
+        };
+        let plan_xml = match plan_xml {
+            Some(content) => content,
+            None => repo
+                .get_job_by_id(&payload.plan_job_id)
+                .await?
+                .and_then(|plan| plan.response)
+                .ok_or_else(|| {
+                    AppError::JobError(format!("Plan {} has no content", payload.plan_job_id))
+                })?,
+        };
+
+        // Existing tests near the touched files show the project's test style
+        let touched_files: Vec<String> = plan_progress::plan_steps(&plan_xml, project_directory)
+            .into_iter()
+            .flat_map(|(_, _, paths)| paths)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let listing_directory = project_directory.clone();
+        let listing_workspace = workspace.clone();
+        let project_files = tokio::task::spawn_blocking(move || {
+            Self::project_files(&listing_directory, listing_workspace.as_ref())
+        })
+        .await
+        .map_err(|e| AppError::JobError(format!("Failed to list project files: {}", e)))?;
+        let mut reference_files = test_plans::find_related_test_files(
+            &touched_files,
+            &project_files,
+            MAX_REFERENCE_TEST_FILES,
+        );
+
+        let mut loaded_files = Vec::new();
+        for path in &reference_files {
+            let full_path = path_resolution_utils::resolve_pipeline_path(
+                path,
+                project_directory,
+                workspace.as_ref(),
+            );
+            match fs::read_to_string(&full_path).await {
+                Ok(content) => loaded_files.push((path.clone(), content)),
+                Err(e) => warn!("Failed to read test file {}: {}", full_path.display(), e),
+            }
+        }
+        // Sources with inline test modules are their own test files
+        for path in touched_files
+            .iter()
+            .filter(|p| !reference_files.contains(p))
+        {
+            if loaded_files.len() >= MAX_REFERENCE_TEST_FILES {
+                break;
+            }
+            let full_path = path_resolution_utils::resolve_pipeline_path(
+                path,
+                project_directory,
+                workspace.as_ref(),
+            );
+            if let Ok(content) = fs::read_to_string(&full_path).await {
+                if test_plans::has_inline_tests(&content) {
+                    loaded_files.push((path.clone(), content));
+                }
+            }
+        }
+        reference_files = loaded_files.iter().map(|(path, _)| path.clone()).collect();
+        info!(
+            "Plan test generation job {}: {} touched files, {} reference test files",
+            job.id,
+            touched_files.len(),
+            reference_files.len()
+        );
+
+        let mut task_description = format!(
+            "<implementation_plan>\n{}\n</implementation_plan>\n\n<touched_files>\n{}\n</touched_files>",
+            plan_xml,
+            touched_files.join("\n")
+        );
+        if reference_files.is_empty() {
+            task_description.push_str(
+                "\n\nThe project has no existing tests near these files; follow the conventions of its language and framework.",
+            );
+        } else {
+            task_description.push_str(&format!(
+                "\n\n<reference_test_files>\n{}\n</reference_test_files>",
+                reference_files.join("\n")
+            ));
+        }
+
+        let directory_tree =
+            ImplementationPlanProcessor::full_directory_tree(project_directory, workspace.as_ref())
+                .await;
+        let file_context = ImplementationPlanProcessor::build_file_context(
+            &app_handle,
+            &model_used,
+            max_output_tokens,
+            &task_description,
+            directory_tree.as_deref(),
+            loaded_files,
+        )
+        .await?;
+
+        let llm_config =
+            LlmTaskConfigBuilder::new(model_used.clone(), temperature, max_output_tokens)
+                .stream(true)
+                .build();
+        let task_runner = LlmTaskRunner::new(app_handle.clone(), job.clone(), llm_config);
+        let prompt_context = LlmPromptContext {
+            task_description,
+            file_contents: if file_context.file_contents.is_empty() {
+                None
+            } else {
+                Some(file_context.file_contents)
+            },
+            directory_tree,
+        };
+
+        // Check if job has been canceled before calling the LLM
+        if job_processor_utils::check_job_canceled(&repo, &job.id).await? {
+            return Ok(JobProcessResult::canceled(
+                job.id.clone(),
+                "Job was canceled by user".to_string(),
+            ));
+        }
+
+        let llm_result = match task_runner
+            .execute_streaming_llm_task(prompt_context, &settings_repo, &repo, &job.id)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                error!("Streaming LLM task execution failed: {}", e);
+                let error_msg = format!("Streaming LLM task execution failed: {}", e);
+                return Ok(JobProcessResult::failure(job.id.clone(), error_msg));
+            }
+        };
+
+        let response_content = llm_result.response.clone();
+        let steps = test_plans::parse_test_plan_xml(
+            &response_content,
+            &extract_step_headers_from_xml(&plan_xml),
+        );
+        if steps.is_empty() {
+            let error_msg = "No test cases found in the model response";
+            error!("Plan test generation job {} failed: {}", job.id, error_msg);
+            return Ok(JobProcessResult::failure(
+                job.id.clone(),
+                error_msg.to_string(),
+            ));
+        }
+
+        // Check if job has been canceled after LLM call but before the test plan is stored
+        if job_processor_utils::check_job_canceled(&repo, &job.id).await? {
+            return Ok(JobProcessResult::canceled(
+                job.id.clone(),
+                "Job was canceled by user".to_string(),
+            ));
+        }
+
+        let test_plan = PlanTestPlan {
+            test_job_id: job.id.clone(),
+            plan_revision: payload.plan_revision,
+            generated_at: get_timestamp(),
+            reference_files,
+            steps,
+        };
+        if let Err(e) =
+            plan_test_plans::store_plan_test_plan(&app_handle, &payload.plan_job_id, &test_plan)
+                .await
+        {
+            return Ok(JobProcessResult::failure(
+                job.id.clone(),
+                format!("Failed to store test plan: {}", e),
+            ));
+        }
+
+        let test_plan_metadata = json!({
+            "planJobId": payload.plan_job_id,
+            "planRevision": payload.plan_revision,
+            "testCount": test_plan.test_count(),
+            "stepsWithTests": test_plan.steps.len(),
+            "referenceTestFiles": test_plan.reference_files,
+            "sessionName": session.name,
+        });
+
+        let system_prompt_template = llm_result.system_prompt_template.clone();
+        let usage_for_result = llm_result.usage.clone();
+        let actual_cost = llm_result
+            .usage
+            .as_ref()
+            .and_then(|u| u.cost)
+            .unwrap_or(0.0);
+
+        Ok(
+            JobProcessResult::success(job.id.clone(), JobResultData::Text(response_content))
+                .with_tokens(
+                    usage_for_result.as_ref().map(|u| u.prompt_tokens as u32),
+                    usage_for_result
+                        .as_ref()
+                        .map(|u| u.completion_tokens as u32),
+                )
+                .with_cache_tokens(
+                    usage_for_result
+                        .as_ref()
+                        .map(|u| u.cache_write_tokens as i64),
+                    usage_for_result
+                        .as_ref()
+                        .map(|u| u.cache_read_tokens as i64),
+                )
+                .with_system_prompt_template(system_prompt_template)
+                .with_actual_cost(actual_cost)
+                .with_metadata(test_plan_metadata),
+        )
+    }
+}
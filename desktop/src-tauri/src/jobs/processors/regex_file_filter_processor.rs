@@ -15,6 +15,7 @@ use tokio::time::{Duration, Instant, sleep};
 use crate::error::{AppError, AppResult};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processor_trait::JobProcessor;
+use crate::jobs::processors::utils::path_resolution_utils::workspace_pipeline_path;
 use crate::jobs::processors::{LlmPromptContext, LlmTaskConfigBuilder, LlmTaskRunner};
 use crate::jobs::types::{Job, JobPayload, JobProcessResult, JobResultData, PatternGroup};
 use crate::utils::directory_tree::get_directory_tree_with_defaults;
@@ -367,6 +368,8 @@ impl JobProcessor for RegexFileFilterProcessor {
         );
 
         let project_dir = session.project_directory.clone();
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, &project_dir).await;
         let selected_roots = roots.clone();
 
         let root_results = stream::iter(selected_roots.into_iter().enumerate())
@@ -377,6 +380,7 @@ impl JobProcessor for RegexFileFilterProcessor {
                 let job_clone = job.clone();
                 let settings_repo_clone = settings_repo.clone();
                 let project_dir_clone = project_dir.clone();
+                let workspace_clone = workspace.clone();
                 let total_roots = roots.len();
 
                 async move {
@@ -566,7 +570,10 @@ impl JobProcessor for RegexFileFilterProcessor {
                     let mut absolute_paths_kept = 0;
 
                     for file_path in root_matches {
-                        let normalized = if let (Ok(project_path), Ok(file_path_buf)) = (
+                        let normalized = if let Some(qualified) = workspace_pipeline_path(Path::new(&file_path), workspace_clone.as_ref()) {
+                            // Qualified through the workspace, which knows nested repositories
+                            qualified
+                        } else if let (Ok(project_path), Ok(file_path_buf)) = (
                             std::path::Path::new(&project_dir_clone).canonicalize(),
                             std::path::Path::new(&file_path).canonicalize()
                         ) {
@@ -8,6 +8,7 @@ use crate::utils::config_resolver;
 use crate::error::{AppError, AppResult};
 use crate::jobs::job_processor_utils;
 use crate::jobs::processor_trait::JobProcessor;
+use crate::jobs::processors::utils::path_resolution_utils;
 use crate::jobs::processors::{LlmPromptContext, LlmTaskConfigBuilder, LlmTaskRunner};
 use crate::jobs::types::{Job, JobPayload, JobProcessResult, JobResultData};
 use crate::models::TaskType;
@@ -76,11 +77,18 @@ impl JobProcessor for TaskRefinementProcessor {
 
         job_processor_utils::log_job_start(&job_id, "task refinement");
         let project_directory = &session.project_directory;
+        let workspace =
+            crate::services::workspaces::workspace_layout_for_job(&app_handle, project_directory)
+                .await;
 
         // Load content of files in payload.relevant_files
         let mut file_contents = std::collections::HashMap::new();
         for relative_path_str in &payload.relevant_files {
-            let full_path = std::path::Path::new(project_directory).join(relative_path_str);
+            let full_path = path_resolution_utils::resolve_pipeline_path(
+                relative_path_str,
+                project_directory,
+                workspace.as_ref(),
+            );
             match fs::read_to_string(&full_path).await {
                 Ok(content) => {
                     file_contents.insert(relative_path_str.clone(), content);
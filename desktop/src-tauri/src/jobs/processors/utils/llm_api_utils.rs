@@ -51,6 +51,7 @@ pub fn create_api_client_options(
         stream,
         request_id: None, // Will be set by calling code if needed
         task_type: None,  // Will be set by calling code if needed
+        response_cache: false,
     })
 }
 
@@ -25,6 +25,56 @@ pub fn extract_agent_instructions_from_xml(xml_content: &str) -> Option<String>
         .map(|m| m.as_str().trim().to_string())
 }
 
+/// Number of a <step> block: its `number` attribute, or `idx + 1` when the attribute is missing
+fn step_number_of(step_xml: &str, idx: usize) -> String {
+    let number_re = Regex::new(r#"^<step[^>]*\bnumber\s*=\s*["']([^"']+)["']"#).unwrap();
+    number_re
+        .captures(step_xml)
+        .map(|cap| cap[1].trim().to_string())
+        .unwrap_or_else(|| (idx + 1).to_string())
+}
+
+/// Extract the number and title of each <step> in implementation plan XML.
+/// Steps without a `number` attribute are numbered by their 1-based position.
+pub fn extract_step_headers_from_xml(xml_content: &str) -> Vec<(String, String)> {
+    let title_re = Regex::new(r"(?s)<title>\s*(.*?)\s*</title>").unwrap();
+
+    extract_steps_from_xml(xml_content)
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| {
+            let title = title_re
+                .captures(step)
+                .map(|cap| cap[1].to_string())
+                .unwrap_or_default();
+            (step_number_of(step, idx), title)
+        })
+        .collect()
+}
+
+/// Extract the file paths named by each step's <file_operations>, keyed by step number.
+/// Move operations written as `old → new` (or `old -> new`) yield both paths.
+pub fn extract_step_file_paths_from_xml(xml_content: &str) -> Vec<(String, Vec<String>)> {
+    let path_re = Regex::new(r"(?s)<operation[^>]*>.*?<path>\s*(.*?)\s*</path>").unwrap();
+
+    extract_steps_from_xml(xml_content)
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| {
+            let mut paths: Vec<String> = Vec::new();
+            for cap in path_re.captures_iter(step) {
+                for path in cap[1].split('→').flat_map(|p| p.split("->")) {
+                    let path = path.trim();
+                    if !path.is_empty() && !paths.iter().any(|p| p == path) {
+                        paths.push(path.to_string());
+                    }
+                }
+            }
+            (step_number_of(step, idx), paths)
+        })
+        .collect()
+}
+
 // Common words that indicate natural language sentences rather than paths
 const COMMON_WORDS: &[&str] = &[
     "the", "is", "are", "was", "were", "been", "have", "has", "had", "do", "does", "did", "will",
@@ -350,6 +400,60 @@ fn post_process_paths(paths: Vec<String>) -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_extract_step_file_paths() {
+        let xml = r#"<plan>
+            <step number="1">
+                <title>Rename module</title>
+                <file_operations>
+                    <operation type="move"><path>src/old.rs → src/new.rs</path></operation>
+                    <operation type="modify">
+                        <path> src/lib.rs </path>
+                        <changes>Update mod declaration</changes>
+                    </operation>
+                </file_operations>
+            </step>
+            <step number="2"><title>Docs only</title></step>
+        </plan>"#;
+
+        assert_eq!(
+            extract_step_file_paths_from_xml(xml),
+            vec![
+                (
+                    "1".to_string(),
+                    vec![
+                        "src/old.rs".to_string(),
+                        "src/new.rs".to_string(),
+                        "src/lib.rs".to_string(),
+                    ]
+                ),
+                ("2".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_step_headers() {
+        let xml = r#"<plan>
+            <step number="1"><title>Add table</title></step>
+            <step number='2a'>
+                <title>
+                    Backfill rows
+                </title>
+            </step>
+            <step><description>No title</description></step>
+        </plan>"#;
+
+        assert_eq!(
+            extract_step_headers_from_xml(xml),
+            vec![
+                ("1".to_string(), "Add table".to_string()),
+                ("2a".to_string(), "Backfill rows".to_string()),
+                ("3".to_string(), String::new()),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_simple_paths() {
         let response = "src/main.rs\nlib/utils.js\nREADME.md";
@@ -1,5 +1,8 @@
 use std::path::{Path, PathBuf};
 
+use crate::utils::path_utils::to_forward_slashes;
+use crate::utils::workspace_paths::{WorkspaceLayout, split_repo_path};
+
 pub fn to_absolute_path(candidate: &str, project_directory: &str) -> PathBuf {
     let p = Path::new(candidate);
     if p.is_absolute() {
@@ -8,3 +11,69 @@ pub fn to_absolute_path(candidate: &str, project_directory: &str) -> PathBuf {
         Path::new(project_directory).join(p)
     }
 }
+
+/// Like `to_absolute_path`, but also resolves `label:path` paths into the other repositories of
+/// the session's workspace
+pub fn resolve_pipeline_path(
+    candidate: &str,
+    project_directory: &str,
+    workspace: Option<&WorkspaceLayout>,
+) -> PathBuf {
+    let (label, relative) = split_repo_path(candidate);
+    match label.and_then(|label| workspace?.repository(label)) {
+        Some(repository) => repository.directory.join(relative.trim_start_matches('/')),
+        None => to_absolute_path(candidate, project_directory),
+    }
+}
+
+/// Pipeline form of a file through the session's workspace: relative for the session's
+/// repository, `label:path` for the other repositories, nested repositories winning. `None`
+/// without a workspace or outside it.
+pub fn workspace_pipeline_path(
+    absolute_path: &Path,
+    workspace: Option<&WorkspaceLayout>,
+) -> Option<String> {
+    let workspace = workspace?;
+    // Repository directories of the layout are canonical
+    let canonical = absolute_path.canonicalize().ok();
+    workspace.qualify(canonical.as_deref().unwrap_or(absolute_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::workspace_paths::WorkspaceRepoRoot;
+
+    #[test]
+    fn nested_workspace_repositories_are_qualified_inside_the_project() {
+        let repo = |label: &str, directory: &str, is_current: bool| WorkspaceRepoRoot {
+            label: label.to_string(),
+            directory: PathBuf::from(directory),
+            is_current,
+        };
+        let workspace = WorkspaceLayout {
+            workspace_id: "ws".to_string(),
+            workspace_name: "Shop".to_string(),
+            repositories: vec![
+                repo("backend", "/src/shop/backend", true),
+                repo("schema", "/src/shop/backend/vendor/schema", false),
+            ],
+        };
+
+        let nested = Path::new("/src/shop/backend/vendor/schema/user.proto");
+        assert_eq!(
+            workspace_pipeline_path(nested, Some(&workspace)).as_deref(),
+            Some("schema:user.proto")
+        );
+        assert_eq!(
+            workspace_pipeline_path(Path::new("/src/shop/backend/src/main.rs"), Some(&workspace))
+                .as_deref(),
+            Some("src/main.rs")
+        );
+        assert_eq!(workspace_pipeline_path(nested, None), None);
+        assert_eq!(
+            workspace_pipeline_path(Path::new("/etc/hosts"), Some(&workspace)),
+            None
+        );
+    }
+}
@@ -1,12 +1,205 @@
-use crate::error::{AppError, AppResult};
+use crate::db_utils::SettingsRepository;
+use crate::error::{AppError, AppResult, ErrorClass};
 use crate::jobs::types::JobUIMetadata;
-use crate::models::BackgroundJob;
-use log::{debug, error};
+use crate::models::{BackgroundJob, TaskType};
+use log::warn;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
 
-/// Maximum number of job retries
-pub const MAX_RETRY_COUNT: u32 = 3;
+/// Number of retries used when a task type has no specific policy
+pub const DEFAULT_MAX_RETRY_COUNT: u32 = 3;
+
+/// Upper bound on configurable retries so a bad setting cannot loop a job forever
+pub const MAX_POLICY_RETRY_COUNT: u32 = 10;
+
+/// Retry-After values beyond this are clamped; the circuit breaker covers longer outages
+const MAX_RETRY_AFTER_SECS: u64 = 600;
+
+/// How the delay grows between retries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackoffCurve {
+    Fixed,
+    Linear,
+    Exponential,
+}
+
+/// Retry behaviour for one task type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    pub backoff: BackoffCurve,
+    pub base_delay_secs: u32,
+    pub max_delay_secs: u32,
+    /// Random extra delay as a fraction of the backoff, 0.0 to 1.0
+    pub jitter_ratio: f64,
+    /// Wait at least as long as the server's Retry-After hint
+    pub honor_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRY_COUNT,
+            backoff: BackoffCurve::Exponential,
+            base_delay_secs: 2,
+            max_delay_secs: 60,
+            jitter_ratio: 0.1,
+            honor_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Built-in policy for a task type, used unless the user configured an override
+    pub fn default_for_task_type(task_type: &TaskType) -> Self {
+        let default = Self::default();
+        match task_type {
+            // Long, expensive generations: retry less often and give the provider more room
+            TaskType::ImplementationPlan
+            | TaskType::ImplementationPlanMerge
+            | TaskType::ImplementationPlanRefinement
+            | TaskType::PlanTestGeneration => Self {
+                max_retries: 2,
+                base_delay_secs: 5,
+                max_delay_secs: 120,
+                ..default
+            },
+            // Interactive edits the user is waiting on: fail fast
+            TaskType::TextImprovement | TaskType::TaskRefinement | TaskType::VoiceTranscription => {
+                Self {
+                    max_retries: 1,
+                    base_delay_secs: 1,
+                    max_delay_secs: 10,
+                    ..default
+                }
+            }
+            TaskType::WebSearchExecution => Self {
+                max_retries: 2,
+                backoff: BackoffCurve::Linear,
+                base_delay_secs: 5,
+                ..default
+            },
+            TaskType::VideoAnalysis => Self {
+                max_retries: 1,
+                backoff: BackoffCurve::Fixed,
+                base_delay_secs: 10,
+                ..default
+            },
+            _ => default,
+        }
+    }
+
+    pub fn validate(&self) -> AppResult<()> {
+        if self.max_retries > MAX_POLICY_RETRY_COUNT {
+            return Err(AppError::ValidationError(format!(
+                "maxRetries must be at most {}",
+                MAX_POLICY_RETRY_COUNT
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.jitter_ratio) {
+            return Err(AppError::ValidationError(
+                "jitterRatio must be between 0 and 1".to_string(),
+            ));
+        }
+        if self.base_delay_secs > self.max_delay_secs {
+            return Err(AppError::ValidationError(
+                "baseDelaySecs must not exceed maxDelaySecs".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Backoff for the given retry (0 for the first retry), before jitter
+    pub fn backoff_secs(&self, retry_count: u32) -> u32 {
+        let delay = match self.backoff {
+            BackoffCurve::Fixed => self.base_delay_secs,
+            BackoffCurve::Linear => self.base_delay_secs.saturating_mul(retry_count + 1),
+            BackoffCurve::Exponential => self
+                .base_delay_secs
+                .saturating_mul(2u32.saturating_pow(retry_count)),
+        };
+        delay.min(self.max_delay_secs)
+    }
+
+    /// Delay before the given retry, with jitter and the error's Retry-After hint applied
+    pub fn retry_delay(&self, retry_count: u32, error: &AppError) -> Duration {
+        let backoff_ms = self.backoff_secs(retry_count) as u64 * 1000;
+        let jitter_ms = (backoff_ms as f64 * self.jitter_ratio * rand::random::<f64>()) as u64;
+        let mut delay_ms = backoff_ms + jitter_ms;
+
+        if self.honor_retry_after {
+            if let Some(retry_after) = error.retry_after() {
+                delay_ms = delay_ms.max(retry_after.as_secs().min(MAX_RETRY_AFTER_SECS) * 1000);
+            }
+        }
+
+        Duration::from_millis(delay_ms)
+    }
+}
+
+/// User overrides keyed by task type
+pub type RetryPolicyOverrides = BTreeMap<String, RetryPolicy>;
+
+/// Policy for a task type: the user's override if one is saved, otherwise the built-in default
+pub async fn resolve_retry_policy(app_handle: &AppHandle, task_type: &TaskType) -> RetryPolicy {
+    if let Some(settings_repo) = app_handle.try_state::<Arc<SettingsRepository>>() {
+        match settings_repo.get_job_retry_policies().await {
+            Ok(overrides) => {
+                if let Some(policy) = overrides.get(&task_type.to_string()) {
+                    return policy.clone();
+                }
+            }
+            Err(e) => warn!("Failed to load job retry policies, using defaults: {}", e),
+        }
+    }
+    RetryPolicy::default_for_task_type(task_type)
+}
+
+/// Outcome of applying a retry policy to a failed job
+#[derive(Debug, Clone)]
+pub struct RetryDecision {
+    /// Whether this kind of error is worth retrying at all
+    pub retryable: bool,
+    /// Retryable and retries are not yet exhausted
+    pub should_retry: bool,
+    /// Retries already made before this failure
+    pub retry_count: u32,
+    pub max_retries: u32,
+    pub delay: Duration,
+    pub error_class: ErrorClass,
+}
+
+/// Decide whether a failed job is retried under `policy`, and after how long
+pub fn decide_retry(job: &BackgroundJob, policy: &RetryPolicy, error: &AppError) -> RetryDecision {
+    let retry_count = get_retry_count_from_job(job).unwrap_or(0);
+    let error_class = error.classify();
+
+    let retryable = match error_class {
+        ErrorClass::RateLimited
+        | ErrorClass::ProviderOverloaded
+        | ErrorClass::TransientNetwork
+        | ErrorClass::Other => true,
+        // The stream client refreshes the token on 401, so one more attempt can succeed
+        ErrorClass::Auth => retry_count == 0,
+        ErrorClass::BadRequest | ErrorClass::Billing | ErrorClass::Permanent => false,
+    };
+
+    RetryDecision {
+        retryable,
+        should_retry: retryable && retry_count < policy.max_retries,
+        retry_count,
+        max_retries: policy.max_retries,
+        delay: policy.retry_delay(retry_count, error),
+        error_class,
+    }
+}
 
 /// Get retry count from job metadata
 pub fn get_retry_count_from_job(job: &BackgroundJob) -> Option<u32> {
@@ -20,15 +213,6 @@ pub fn get_retry_count_from_job(job: &BackgroundJob) -> Option<u32> {
     None
 }
 
-/// Calculate retry delay using exponential backoff
-pub fn calculate_retry_delay(retry_count: u32) -> u32 {
-    let base_delay_seconds = 2;
-    let max_delay_seconds = 60;
-    let exp_delay = base_delay_seconds * (2u32.pow(retry_count));
-    let jitter = (retry_count as f64 * 0.1).round() as u32;
-    std::cmp::min(exp_delay + jitter, max_delay_seconds)
-}
-
 /// Prepare updated metadata for job retry
 pub async fn prepare_retry_metadata(
     job: &BackgroundJob,
@@ -47,6 +231,7 @@ pub async fn prepare_retry_metadata(
     let retry_info = json!({
         "retryCount": new_retry_count,
         "errorType": format!("{:?}", error),
+        "errorClass": error.classify(),
         "errorMessage": error.to_string(),
         "lastRetryAt": chrono::Utc::now().to_rfc3339()
     });
@@ -66,19 +251,84 @@ pub async fn prepare_retry_metadata(
     })
 }
 
-/// Get retry information from job metadata
-pub async fn get_retry_info(job: &BackgroundJob, error_opt: Option<&AppError>) -> (bool, u32) {
-    let current_retry_count = get_retry_count_from_job(job).unwrap_or(0);
-
-    let is_retryable = match error_opt {
-        Some(error) => match error {
-            AppError::ConfigError(_)
-            | AppError::ValidationError(_)
-            | AppError::SerializationError(_) => false,
-            _ => true,
-        },
-        None => true,
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(backoff: BackoffCurve) -> RetryPolicy {
+        RetryPolicy {
+            backoff,
+            base_delay_secs: 2,
+            max_delay_secs: 30,
+            jitter_ratio: 0.0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn backoff_curves_grow_and_cap() {
+        let fixed = policy(BackoffCurve::Fixed);
+        let linear = policy(BackoffCurve::Linear);
+        let exponential = policy(BackoffCurve::Exponential);
+
+        assert_eq!(
+            (0..4).map(|n| fixed.backoff_secs(n)).collect::<Vec<_>>(),
+            [2, 2, 2, 2]
+        );
+        assert_eq!(
+            (0..4).map(|n| linear.backoff_secs(n)).collect::<Vec<_>>(),
+            [2, 4, 6, 8]
+        );
+        assert_eq!(
+            (0..6)
+                .map(|n| exponential.backoff_secs(n))
+                .collect::<Vec<_>>(),
+            [2, 4, 8, 16, 30, 30]
+        );
+    }
+
+    #[test]
+    fn retry_after_extends_the_delay_when_honored() {
+        let error = AppError::rate_limited("Rate limit exceeded", Some(Duration::from_secs(45)));
+        let mut policy = policy(BackoffCurve::Fixed);
 
-    (is_retryable, current_retry_count)
+        assert_eq!(policy.retry_delay(0, &error), Duration::from_secs(45));
+
+        policy.honor_retry_after = false;
+        assert_eq!(policy.retry_delay(0, &error), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn jitter_stays_within_ratio() {
+        let policy = RetryPolicy {
+            jitter_ratio: 0.5,
+            ..policy(BackoffCurve::Fixed)
+        };
+        let error = AppError::NetworkError("reset".to_string());
+        for _ in 0..50 {
+            let delay = policy.retry_delay(0, &error);
+            assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(3));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_values() {
+        assert!(RetryPolicy::default().validate().is_ok());
+        assert!(
+            RetryPolicy {
+                max_retries: MAX_POLICY_RETRY_COUNT + 1,
+                ..RetryPolicy::default()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            RetryPolicy {
+                jitter_ratio: 1.5,
+                ..RetryPolicy::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
 }
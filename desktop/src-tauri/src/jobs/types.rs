@@ -87,6 +87,14 @@ pub struct ExtendedPathFinderPayload {
     pub selected_root_directories: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactAnalysisPayload {
+    pub task_description: String,
+    /// Files selected by the earlier stages whose dependents are looked up
+    pub selected_paths: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RegexFileFilterPayload {
@@ -215,18 +223,37 @@ pub struct ImplementationPlanMergePayload {
     pub merge_instructions: Option<String>,
 }
 
+/// One turn of a follow-up conversation that revises an existing plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImplementationPlanRefinementPayload {
+    pub plan_job_id: String,
+    pub message: String,
+}
+
+/// Test plan for a signed-off plan, generated from the revision that was signed off
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTestGenerationPayload {
+    pub plan_job_id: String,
+    pub plan_revision: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
 pub enum JobPayload {
     OpenRouterLlm(OpenRouterLlmPayload),
     ImplementationPlan(ImplementationPlanPayload),
     ImplementationPlanMerge(ImplementationPlanMergePayload),
+    ImplementationPlanRefinement(ImplementationPlanRefinementPayload),
+    PlanTestGeneration(PlanTestGenerationPayload),
     TaskRefinement(TaskRefinementPayload),
     TextImprovement(TextImprovementPayload),
     GenericLlmStream(GenericLlmStreamPayload),
     // Individual workflow stage payloads
     RootFolderSelection(RootFolderSelectionPayload),
     ExtendedPathFinder(ExtendedPathFinderPayload),
+    ImpactAnalysis(ImpactAnalysisPayload),
     RegexFileFilter(RegexFileFilterPayload),
     FileRelevanceAssessment(FileRelevanceAssessmentPayload),
     WebSearchPromptsGeneration(WebSearchPromptsGenerationPayload),
@@ -174,6 +174,42 @@ pub(super) async fn extract_and_store_stage_data_internal(
 
                 serde_json::json!({ "files": files })
             }
+            TaskType::ImpactAnalysis => {
+                let response_json = match job_result_data {
+                    Some(crate::jobs::types::JobResultData::Json(json_data)) => json_data,
+                    Some(crate::jobs::types::JobResultData::Text(text_data)) => {
+                        serde_json::from_str(&text_data).map_err(|e| {
+                            warn!(
+                                "Failed to parse text response as JSON for {:?} job {}: {}",
+                                stage_job.task_type, job_id, e
+                            );
+                            AppError::JobError(format!(
+                                "Invalid response format for {:?} job {}",
+                                stage_job.task_type, job_id
+                            ))
+                        })?
+                    }
+                    None => {
+                        return Err(AppError::JobError(format!(
+                            "No response data found for {:?} job {}",
+                            stage_job.task_type, job_id
+                        )));
+                    }
+                };
+
+                // No dependents is a valid outcome, so a missing list is treated as empty
+                let dependents = response_json
+                    .get("dependents")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!([]));
+
+                debug!(
+                    "Extracted {} dependent files from job {}",
+                    dependents.as_array().map_or(0, |d| d.len()),
+                    job_id
+                );
+                serde_json::json!({ "dependents": dependents })
+            }
             TaskType::FileRelevanceAssessment => {
                 // Extract files from standardized response
                 let response_json = match job_result_data {
@@ -1,6 +1,7 @@
 use log::warn;
 use tauri::{AppHandle, Emitter};
 
+use crate::events::{AppEvent, publish_app_event};
 use crate::jobs::workflow_types::{
     WorkflowStageEvent, WorkflowStageJob, WorkflowState, WorkflowStatusEvent,
 };
@@ -25,6 +26,20 @@ pub(super) async fn emit_workflow_status_event_internal(
         error_message: workflow_state.error_message.clone(),
     };
 
+    publish_app_event(
+        app_handle,
+        AppEvent::WorkflowStatusChanged {
+            workflow_id: event.workflow_id.clone(),
+            status: serde_json::to_value(&event.status)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            progress: event.progress,
+            message: event.message.clone(),
+            error_message: event.error_message.clone(),
+        },
+    );
+
     if let Err(e) = app_handle.emit("workflow-status", &event) {
         warn!("Failed to emit workflow status event: {}", e);
     }
@@ -49,6 +64,18 @@ pub(super) async fn emit_workflow_stage_event_internal(
         data: None,
     };
 
+    publish_app_event(
+        app_handle,
+        AppEvent::WorkflowStageChanged {
+            workflow_id: event.workflow_id.clone(),
+            stage_name: event.stage_name.clone(),
+            task_type: event.task_type.to_string(),
+            job_id: event.job_id.clone(),
+            status: event.status.to_string(),
+            error_message: event.error_message.clone(),
+        },
+    );
+
     if let Err(e) = app_handle.emit("workflow-stage", &event) {
         warn!("Failed to emit workflow stage event: {}", e);
     }
@@ -454,6 +454,9 @@ impl WorkflowOrchestrator {
                                         crate::models::TaskType::ExtendedPathFinder => {
                                             "ExtendedPathFinder"
                                         }
+                                        crate::models::TaskType::ImpactAnalysis => {
+                                            "ImpactAnalysis"
+                                        }
                                         _ => continue,
                                     };
 
@@ -560,6 +563,7 @@ impl WorkflowOrchestrator {
                                 "FileRelevanceAssessment"
                             }
                             crate::models::TaskType::ExtendedPathFinder => "ExtendedPathFinder",
+                            crate::models::TaskType::ImpactAnalysis => "ImpactAnalysis",
                             _ => continue,
                         };
 
@@ -1330,6 +1334,7 @@ impl WorkflowOrchestrator {
                 WorkflowStage::FileRelevanceAssessment
             }
             "ExtendedPathFinder" | "Extended Path Finder" => WorkflowStage::ExtendedPathFinder,
+            "ImpactAnalysis" | "Impact Analysis" => WorkflowStage::ImpactAnalysis,
             _ => {
                 return Err(AppError::JobError(format!(
                     "Unknown stage name: {}",
@@ -101,6 +101,28 @@ pub(super) async fn create_abstract_stage_payload(
 
             Ok(JobPayload::ExtendedPathFinder(payload))
         }
+        TaskType::ImpactAnalysis => {
+            use crate::jobs::types::ImpactAnalysisPayload;
+
+            // Look up dependents of everything selected so far
+            let intermediate_data = &workflow_state.intermediate_data;
+            let mut selected_paths = intermediate_data.ai_filtered_files.clone();
+            selected_paths.extend(intermediate_data.extended_paths.iter().cloned());
+            selected_paths.sort_unstable();
+            selected_paths.dedup();
+
+            debug!(
+                "Using {} selected files for ImpactAnalysis payload",
+                selected_paths.len()
+            );
+
+            let payload = ImpactAnalysisPayload {
+                task_description: workflow_state.task_description.clone(),
+                selected_paths,
+            };
+
+            Ok(JobPayload::ImpactAnalysis(payload))
+        }
         TaskType::FileRelevanceAssessment => {
             use crate::jobs::types::FileRelevanceAssessmentPayload;
 
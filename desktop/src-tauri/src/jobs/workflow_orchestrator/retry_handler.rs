@@ -327,6 +327,7 @@ fn stage_to_task_type_for_retry(stage: &WorkflowStage) -> TaskType {
         WorkflowStage::RegexFileFilter => TaskType::RegexFileFilter,
         WorkflowStage::FileRelevanceAssessment => TaskType::FileRelevanceAssessment,
         WorkflowStage::ExtendedPathFinder => TaskType::ExtendedPathFinder,
+        WorkflowStage::ImpactAnalysis => TaskType::ImpactAnalysis,
         WorkflowStage::WebSearchPromptsGeneration => TaskType::WebSearchPromptsGeneration,
         WorkflowStage::WebSearchExecution => TaskType::WebSearchExecution,
     }
@@ -339,6 +340,7 @@ fn task_type_to_workflow_stage(task_type: TaskType) -> Option<WorkflowStage> {
         TaskType::RegexFileFilter => Some(WorkflowStage::RegexFileFilter),
         TaskType::FileRelevanceAssessment => Some(WorkflowStage::FileRelevanceAssessment),
         TaskType::ExtendedPathFinder => Some(WorkflowStage::ExtendedPathFinder),
+        TaskType::ImpactAnalysis => Some(WorkflowStage::ImpactAnalysis),
         TaskType::WebSearchPromptsGeneration => Some(WorkflowStage::WebSearchPromptsGeneration),
         TaskType::WebSearchExecution => Some(WorkflowStage::WebSearchExecution),
         _ => None, // Other task types don't correspond to workflow stages
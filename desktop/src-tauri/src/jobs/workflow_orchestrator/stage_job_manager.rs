@@ -40,6 +40,7 @@ pub async fn create_abstract_stage_job_with_lock_internal(
         TaskType::RegexFileFilter => Some(WorkflowStage::RegexFileFilter),
         TaskType::FileRelevanceAssessment => Some(WorkflowStage::FileRelevanceAssessment),
         TaskType::ExtendedPathFinder => Some(WorkflowStage::ExtendedPathFinder),
+        TaskType::ImpactAnalysis => Some(WorkflowStage::ImpactAnalysis),
         TaskType::WebSearchPromptsGeneration => Some(WorkflowStage::WebSearchPromptsGeneration),
         TaskType::WebSearchExecution => Some(WorkflowStage::WebSearchExecution),
         _ => {
@@ -108,6 +109,14 @@ pub async fn create_abstract_stage_job_with_lock_internal(
                 .map(|job| job.job_id.clone())
         };
 
+        if let Some(dependency_job_id) = &depends_on {
+            crate::services::job_provenance::record_stage_dependency(
+                app_handle,
+                dependency_job_id,
+                &job_id,
+            );
+        }
+
         workflow.add_stage_job(
             stage_definition.stage_name.clone(),
             stage_definition.task_type,
@@ -171,6 +171,7 @@ pub(super) fn stage_to_task_type_internal(stage: &WorkflowStage) -> TaskType {
         WorkflowStage::RegexFileFilter => TaskType::RegexFileFilter,
         WorkflowStage::FileRelevanceAssessment => TaskType::FileRelevanceAssessment,
         WorkflowStage::ExtendedPathFinder => TaskType::ExtendedPathFinder,
+        WorkflowStage::ImpactAnalysis => TaskType::ImpactAnalysis,
         WorkflowStage::WebSearchPromptsGeneration => TaskType::WebSearchPromptsGeneration,
         WorkflowStage::WebSearchExecution => TaskType::WebSearchExecution,
     }
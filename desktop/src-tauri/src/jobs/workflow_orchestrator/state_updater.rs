@@ -6,6 +6,7 @@ use tokio::sync::Mutex;
 use crate::error::{AppError, AppResult};
 use crate::jobs::workflow_types::{WorkflowStage, WorkflowState, WorkflowStatus};
 use crate::models::JobStatus;
+use crate::utils::dependency_graph::DependentFile;
 
 /// Update intermediate data in workflow state based on stage completion
 pub(super) fn update_intermediate_data_internal(
@@ -95,6 +96,24 @@ pub(super) fn update_intermediate_data_internal(
                 );
             }
         }
+        WorkflowStage::ImpactAnalysis => {
+            if let Some(dependents) = stage_data.get("dependents") {
+                match serde_json::from_value::<Vec<DependentFile>>(dependents.clone()) {
+                    Ok(impacted_files) => {
+                        workflow_state.intermediate_data.impacted_files = impacted_files;
+                        debug!(
+                            "Stored {} impacted files in intermediate_data",
+                            workflow_state.intermediate_data.impacted_files.len()
+                        );
+                    }
+                    Err(e) => warn!("Failed to parse ImpactAnalysis dependents: {}", e),
+                }
+            } else {
+                warn!(
+                    "ImpactAnalysis stage_data missing 'dependents' field, keeping existing data"
+                );
+            }
+        }
         WorkflowStage::WebSearchPromptsGeneration => {
             // Extract prompts from the prompts generation stage
             if let Some(prompts_array) = stage_data.get("prompts").and_then(|v| v.as_array()) {
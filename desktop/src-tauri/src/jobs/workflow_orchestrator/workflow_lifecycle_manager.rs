@@ -328,6 +328,7 @@ async fn create_abstract_stage_job_with_lock(
         TaskType::RegexFileFilter => WorkflowStage::RegexFileFilter,
         TaskType::FileRelevanceAssessment => WorkflowStage::FileRelevanceAssessment,
         TaskType::ExtendedPathFinder => WorkflowStage::ExtendedPathFinder,
+        TaskType::ImpactAnalysis => WorkflowStage::ImpactAnalysis,
         TaskType::WebSearchPromptsGeneration => WorkflowStage::WebSearchPromptsGeneration,
         TaskType::WebSearchExecution => WorkflowStage::WebSearchExecution,
         _ => {
@@ -407,6 +408,14 @@ async fn create_abstract_stage_job_with_lock(
             stage_definition.stage_name, workflow_state.workflow_id, depends_on
         );
 
+        if let Some(dependency_job_id) = &depends_on {
+            crate::services::job_provenance::record_stage_dependency(
+                app_handle,
+                dependency_job_id,
+                &job_id,
+            );
+        }
+
         workflow.add_stage_job(
             stage_definition.stage_name.clone(),
             task_type,
@@ -28,11 +28,22 @@ pub(super) async fn get_stage_model_config(
                 _ => "",
             };
 
+            // The project's .plantocode file takes precedence over app-wide workflow settings
             let workflow_model = if !stage_name.is_empty() {
-                settings_repo
-                    .get_workflow_setting("FileFinderWorkflow", stage_name)
-                    .await
-                    .unwrap_or(None)
+                let file_model =
+                    crate::utils::project_config::active_project_config(project_directory)
+                        .and_then(|loaded| {
+                            loaded
+                                .config
+                                .workflow_setting("FileFinderWorkflow", stage_name)
+                        });
+                match file_model {
+                    Some(model) => Some(model),
+                    None => settings_repo
+                        .get_workflow_setting("FileFinderWorkflow", stage_name)
+                        .await
+                        .unwrap_or(None),
+                }
             } else {
                 None
             };
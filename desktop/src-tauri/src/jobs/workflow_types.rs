@@ -1,5 +1,6 @@
 use crate::models::JobStatus;
 use crate::models::TaskType;
+use crate::utils::dependency_graph::DependentFile;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -29,6 +30,7 @@ pub enum WorkflowStage {
     RegexFileFilter,
     FileRelevanceAssessment,
     ExtendedPathFinder,
+    ImpactAnalysis,
     WebSearchPromptsGeneration,
     WebSearchExecution,
 }
@@ -46,7 +48,8 @@ impl WorkflowStage {
             WorkflowStage::RootFolderSelection => Some(WorkflowStage::RegexFileFilter),
             WorkflowStage::RegexFileFilter => Some(WorkflowStage::FileRelevanceAssessment),
             WorkflowStage::FileRelevanceAssessment => Some(WorkflowStage::ExtendedPathFinder),
-            WorkflowStage::ExtendedPathFinder => Some(WorkflowStage::WebSearchPromptsGeneration),
+            WorkflowStage::ExtendedPathFinder => Some(WorkflowStage::ImpactAnalysis),
+            WorkflowStage::ImpactAnalysis => Some(WorkflowStage::WebSearchPromptsGeneration),
             WorkflowStage::WebSearchPromptsGeneration => Some(WorkflowStage::WebSearchExecution),
             WorkflowStage::WebSearchExecution => None,
         }
@@ -59,7 +62,8 @@ impl WorkflowStage {
             WorkflowStage::RegexFileFilter => Some(WorkflowStage::RootFolderSelection),
             WorkflowStage::FileRelevanceAssessment => Some(WorkflowStage::RegexFileFilter),
             WorkflowStage::ExtendedPathFinder => Some(WorkflowStage::FileRelevanceAssessment),
-            WorkflowStage::WebSearchPromptsGeneration => Some(WorkflowStage::ExtendedPathFinder),
+            WorkflowStage::ImpactAnalysis => Some(WorkflowStage::ExtendedPathFinder),
+            WorkflowStage::WebSearchPromptsGeneration => Some(WorkflowStage::ImpactAnalysis),
             WorkflowStage::WebSearchExecution => Some(WorkflowStage::WebSearchPromptsGeneration),
         }
     }
@@ -71,8 +75,9 @@ impl WorkflowStage {
             WorkflowStage::RegexFileFilter => 1,
             WorkflowStage::FileRelevanceAssessment => 2,
             WorkflowStage::ExtendedPathFinder => 3,
-            WorkflowStage::WebSearchPromptsGeneration => 4,
-            WorkflowStage::WebSearchExecution => 5,
+            WorkflowStage::ImpactAnalysis => 4,
+            WorkflowStage::WebSearchPromptsGeneration => 5,
+            WorkflowStage::WebSearchExecution => 6,
         }
     }
 
@@ -83,6 +88,7 @@ impl WorkflowStage {
             WorkflowStage::RegexFileFilter => "Regex File Filtering",
             WorkflowStage::FileRelevanceAssessment => "AI File Relevance Assessment",
             WorkflowStage::ExtendedPathFinder => "Extended Path Finding",
+            WorkflowStage::ImpactAnalysis => "Impact Analysis",
             WorkflowStage::WebSearchPromptsGeneration => "Web Search Prompts Generation",
             WorkflowStage::WebSearchExecution => "Web Search Execution",
         }
@@ -99,6 +105,8 @@ impl WorkflowStage {
             "FileRelevanceAssessment" => Some(WorkflowStage::FileRelevanceAssessment), // Handle enum variant name
             "Extended Path Finding" => Some(WorkflowStage::ExtendedPathFinder),
             "ExtendedPathFinder" => Some(WorkflowStage::ExtendedPathFinder), // Handle enum variant name
+            "Impact Analysis" => Some(WorkflowStage::ImpactAnalysis),
+            "ImpactAnalysis" => Some(WorkflowStage::ImpactAnalysis), // Handle enum variant name
             "Web Search Prompts Generation" => Some(WorkflowStage::WebSearchPromptsGeneration),
             "WebSearchPromptsGeneration" => Some(WorkflowStage::WebSearchPromptsGeneration), // Handle enum variant name
             "Web Search Execution" => Some(WorkflowStage::WebSearchExecution),
@@ -114,6 +122,7 @@ impl WorkflowStage {
             TaskType::RegexFileFilter => Some(WorkflowStage::RegexFileFilter),
             TaskType::FileRelevanceAssessment => Some(WorkflowStage::FileRelevanceAssessment),
             TaskType::ExtendedPathFinder => Some(WorkflowStage::ExtendedPathFinder),
+            TaskType::ImpactAnalysis => Some(WorkflowStage::ImpactAnalysis),
             TaskType::WebSearchPromptsGeneration => Some(WorkflowStage::WebSearchPromptsGeneration),
             TaskType::WebSearchExecution => Some(WorkflowStage::WebSearchExecution),
             _ => None,
@@ -421,6 +430,9 @@ pub struct WorkflowIntermediateData {
     pub ai_filtered_files: Vec<String>,
     pub ai_filtered_files_token_count: Option<u32>,
     pub extended_paths: Vec<String>,
+    /// Files depending on the selection, with the reason each one was added
+    #[serde(default)]
+    pub impacted_files: Vec<DependentFile>,
     pub web_search_prompts: Vec<String>,
     pub web_search_results: Vec<String>,
     pub workflow_completion_message: Option<String>,
@@ -442,6 +454,7 @@ impl WorkflowIntermediateData {
             ai_filtered_files: Vec::new(),
             ai_filtered_files_token_count: None,
             extended_paths: Vec::new(),
+            impacted_files: Vec::new(),
             web_search_prompts: Vec::new(),
             web_search_results: Vec::new(),
             workflow_completion_message: None,
@@ -450,8 +463,9 @@ impl WorkflowIntermediateData {
 
     /// Get all final selected files from the workflow
     pub fn get_final_selected_files(&self) -> Vec<String> {
-        // Simply return the extended paths from ExtendedPathFinder
+        // Extended paths from ExtendedPathFinder plus the dependents found by ImpactAnalysis
         let mut files = self.extended_paths.clone();
+        files.extend(self.impacted_files.iter().map(|file| file.path.clone()));
 
         // Remove duplicates and sort
         files.sort_unstable();
@@ -601,6 +615,13 @@ impl Default for ErrorRecoveryConfig {
                 delay_ms: 5000,
             },
         );
+        strategy_map.insert(
+            "ImpactAnalysis".to_string(),
+            RecoveryStrategy::RetryStage {
+                max_attempts: 2,
+                delay_ms: 1000,
+            },
+        );
 
         Self {
             strategy_map,
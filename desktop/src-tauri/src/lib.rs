@@ -143,6 +143,9 @@ pub fn run() {
         .manage(Arc::new(TokenManager::new()))
         .manage(ConfigCache::new(Mutex::new(HashMap::new())))
         .manage(Arc::new(crate::services::SessionCache::new()))
+        .manage(crate::events::EventBus::new())
+        // Plan issue-tracker formats; register additional exporters here
+        .manage(Arc::new(crate::utils::plan_issue_export::PlanExporterRegistry::new()))
         .manage(Arc::new(RwLock::new(
             Option::<Arc<crate::api_clients::server_proxy_client::ServerProxyClient>>::None,
         )))
@@ -508,6 +511,9 @@ pub fn run() {
             commands::job_commands::cancel_session_jobs_command,
             commands::job_commands::delete_background_job_command,
             commands::job_commands::get_background_job_by_id_command,
+            commands::job_commands::get_job_provenance_command,
+            commands::job_commands::rerun_job_from_here_command,
+            commands::job_commands::get_job_replay_comparison_command,
             commands::video_analysis_commands::start_video_analysis_job,
             commands::video_utils_commands::get_video_metadata_command,
             commands::screen_recording_commands::stop_screen_recording,
@@ -549,6 +555,27 @@ pub fn run() {
             commands::implementation_plan_commands::create_merged_implementation_plan_command,
             commands::implementation_plan_commands::mark_implementation_plan_signed_off_command,
             commands::implementation_plan_commands::generate_plan_markdown_command,
+            commands::implementation_plan_commands::list_plan_revisions_command,
+            commands::implementation_plan_commands::get_plan_revision_command,
+            commands::implementation_plan_commands::diff_plan_revisions_command,
+            commands::implementation_plan_commands::restore_plan_revision_command,
+            commands::plan_conversation_commands::get_plan_conversation_command,
+            commands::plan_conversation_commands::refine_implementation_plan_command,
+            commands::plan_export_commands::list_plan_export_formats_command,
+            commands::plan_export_commands::export_plan_issues_command,
+            commands::plan_review_commands::get_plan_step_reviews_command,
+            commands::plan_review_commands::update_plan_step_command,
+            commands::plan_review_commands::add_plan_step_comment_command,
+            commands::plan_review_commands::resolve_plan_step_comment_command,
+            commands::plan_review_commands::delete_plan_step_comment_command,
+            commands::plan_review_commands::get_plan_progress_command,
+            commands::plan_review_commands::check_plan_progress_command,
+            commands::plan_template_commands::list_plan_templates_command,
+            commands::plan_template_commands::get_session_plan_template_command,
+            commands::plan_template_commands::set_session_plan_template_command,
+            commands::plan_template_commands::check_plan_template_command,
+            commands::plan_test_commands::generate_plan_tests_command,
+            commands::plan_test_commands::get_plan_test_plan_command,
             commands::workflow_commands::start_file_finder_workflow,
             commands::workflow_commands::get_file_finder_roots_for_session,
             commands::web_search_commands::start_web_search_workflow,
@@ -577,6 +604,7 @@ pub fn run() {
             commands::settings_commands::set_key_value_command,
             commands::settings_commands::get_server_default_task_model_settings_command,
             commands::settings_commands::validate_configuration_health,
+            commands::settings_commands::get_project_config_command,
             commands::settings_commands::set_onboarding_completed_command,
             commands::settings_commands::is_onboarding_completed_command,
             commands::settings_commands::get_workflow_setting_command,
@@ -616,6 +644,12 @@ pub fn run() {
             commands::session_commands::delete_session_command,
             commands::session_commands::rename_session_command,
             commands::session_commands::duplicate_session_command,
+            commands::session_commands::fork_session_command,
+            commands::session_commands::get_session_tree_command,
+            commands::session_commands::compare_fork_plans_command,
+            commands::session_commands::export_session_bundle_command,
+            commands::session_commands::import_session_bundle_command,
+            commands::session_commands::search_sessions_command,
             commands::session_commands::update_session_project_directory_command,
             commands::session_commands::clear_all_project_sessions_command,
             commands::session_commands::update_session_fields_command,
@@ -647,6 +681,29 @@ pub fn run() {
             commands::backup_commands::create_manual_backup_command,
             commands::backup_commands::verify_backup_command,
             commands::backup_commands::delete_backup_command,
+            commands::backup_commands::export_backup_command,
+            commands::backup_commands::import_backup_command,
+            commands::backup_commands::get_backup_config_command,
+            commands::backup_commands::update_backup_config_command,
+            commands::integration_hook_commands::list_integration_hooks_command,
+            commands::integration_hook_commands::list_integration_hook_event_types_command,
+            commands::integration_hook_commands::save_integration_hook_command,
+            commands::integration_hook_commands::delete_integration_hook_command,
+            commands::integration_hook_commands::list_integration_hook_deliveries_command,
+            commands::integration_hook_commands::test_integration_hook_command,
+            commands::workspace_commands::list_workspaces_command,
+            commands::workspace_commands::save_workspace_command,
+            commands::workspace_commands::delete_workspace_command,
+            commands::workspace_commands::get_workspace_layout_command,
+            commands::workspace_commands::get_session_repo_file_selection_command,
+            commands::local_api_commands::get_local_api_config_command,
+            commands::local_api_commands::update_local_api_config_command,
+            commands::local_api_commands::get_local_api_status_command,
+            commands::local_api_commands::regenerate_local_api_token_command,
+            commands::job_retry_commands::get_job_retry_policies_command,
+            commands::job_retry_commands::update_job_retry_policy_command,
+            commands::job_retry_commands::get_job_circuit_breaker_status_command,
+            commands::job_retry_commands::reset_job_circuit_breaker_command,
             commands::logging_commands::log_client_error,
             commands::logging_commands::append_to_log_file,
             commands::terminal_commands::start_terminal_session_command,
@@ -188,6 +188,8 @@ impl ToString for ApiType {
 pub enum TaskType {
     ImplementationPlan,
     ImplementationPlanMerge,
+    ImplementationPlanRefinement,
+    PlanTestGeneration,
     VoiceTranscription,
     TextImprovement,
     TaskRefinement,
@@ -198,6 +200,7 @@ pub enum TaskType {
     RootFolderSelection,
     FileRelevanceAssessment,
     ExtendedPathFinder,
+    ImpactAnalysis,
     WebSearchPromptsGeneration,
     WebSearchExecution,
     WebSearchWorkflow,
@@ -211,6 +214,8 @@ impl ToString for TaskType {
         match self {
             TaskType::ImplementationPlan => "implementation_plan".to_string(),
             TaskType::ImplementationPlanMerge => "implementation_plan_merge".to_string(),
+            TaskType::ImplementationPlanRefinement => "implementation_plan_refinement".to_string(),
+            TaskType::PlanTestGeneration => "plan_test_generation".to_string(),
             TaskType::VoiceTranscription => "voice_transcription".to_string(),
             TaskType::TextImprovement => "text_improvement".to_string(),
             TaskType::TaskRefinement => "task_refinement".to_string(),
@@ -220,6 +225,7 @@ impl ToString for TaskType {
             TaskType::RootFolderSelection => "root_folder_selection".to_string(),
             TaskType::FileRelevanceAssessment => "file_relevance_assessment".to_string(),
             TaskType::ExtendedPathFinder => "extended_path_finder".to_string(),
+            TaskType::ImpactAnalysis => "impact_analysis".to_string(),
             TaskType::WebSearchPromptsGeneration => "web_search_prompts_generation".to_string(),
             TaskType::WebSearchExecution => "web_search_execution".to_string(),
             TaskType::WebSearchWorkflow => "web_search_workflow".to_string(),
@@ -237,6 +243,8 @@ impl std::str::FromStr for TaskType {
         match s {
             "implementation_plan" => Ok(TaskType::ImplementationPlan),
             "implementation_plan_merge" => Ok(TaskType::ImplementationPlanMerge),
+            "implementation_plan_refinement" => Ok(TaskType::ImplementationPlanRefinement),
+            "plan_test_generation" => Ok(TaskType::PlanTestGeneration),
             "voice_transcription" => Ok(TaskType::VoiceTranscription),
             "text_improvement" => Ok(TaskType::TextImprovement),
             "task_refinement" => Ok(TaskType::TaskRefinement),
@@ -246,6 +254,7 @@ impl std::str::FromStr for TaskType {
             "root_folder_selection" => Ok(TaskType::RootFolderSelection),
             "file_relevance_assessment" => Ok(TaskType::FileRelevanceAssessment),
             "extended_path_finder" => Ok(TaskType::ExtendedPathFinder),
+            "impact_analysis" => Ok(TaskType::ImpactAnalysis),
             "web_search_prompts_generation" => Ok(TaskType::WebSearchPromptsGeneration),
             "web_search_execution" => Ok(TaskType::WebSearchExecution),
             "web_search_workflow" => Ok(TaskType::WebSearchWorkflow),
@@ -267,6 +276,8 @@ impl TaskType {
             | TaskType::ExtendedPathFinder
             | TaskType::ImplementationPlan
             | TaskType::ImplementationPlanMerge
+            | TaskType::ImplementationPlanRefinement
+            | TaskType::PlanTestGeneration
             | TaskType::TextImprovement
             | TaskType::TaskRefinement
             | TaskType::GenericLlmStream
@@ -276,7 +287,9 @@ impl TaskType {
             | TaskType::RootFolderSelection
             | TaskType::VideoAnalysis => true,
             // Workflows and local filesystem operations don't require LLM
-            TaskType::FileFinderWorkflow | TaskType::WebSearchWorkflow => false,
+            TaskType::FileFinderWorkflow
+            | TaskType::WebSearchWorkflow
+            | TaskType::ImpactAnalysis => false,
             // Streaming and Unknown default to true for safety
             TaskType::Streaming | TaskType::Unknown => true,
         }
@@ -286,7 +299,7 @@ impl TaskType {
     pub fn api_type(&self) -> ApiType {
         match self {
             // Local/filesystem tasks use filesystem API
-            TaskType::VoiceTranscription => ApiType::FileSystem,
+            TaskType::VoiceTranscription | TaskType::ImpactAnalysis => ApiType::FileSystem,
             // Extended workflow stages use OpenRouter API
             TaskType::FileRelevanceAssessment
             | TaskType::ExtendedPathFinder
@@ -476,6 +489,8 @@ pub struct OpenRouterRequest {
     pub duration_ms: Option<i64>,
     pub request_id: Option<String>,
     pub task_type: Option<String>,
+    /// Opt in to the server-side response cache for deterministic requests
+    pub response_cache: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -217,7 +217,7 @@ impl From<AppError> for RpcError {
             // External service errors
             AppError::ExternalServiceError(_)
             | AppError::OpenRouterError(_)
-            | AppError::ServerProxyError(_)
+            | AppError::ServerProxyError { .. }
             | AppError::HttpError(_)
             | AppError::NetworkError(_)
             | AppError::StripeError(_) => Self {
@@ -4,8 +4,9 @@ use serde::Deserialize;
 use crate::remote_api::types::RpcRequest;
 use crate::remote_api::error::{RpcError, RpcResult};
 use crate::commands::{
-    workflow_commands, implementation_plan_commands,
-    web_search_commands, generic_task_commands
+    workflow_commands, implementation_plan_commands, plan_review_commands,
+    plan_conversation_commands, plan_export_commands, plan_test_commands, web_search_commands,
+    generic_task_commands
 };
 use crate::utils::token_estimator;
 
@@ -20,6 +21,74 @@ struct FindRelevantFilesParams {
     timeout_ms: Option<u64>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlanRevisionParams {
+    job_id: String,
+    revision: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiffPlanRevisionsParams {
+    job_id: String,
+    from_revision: i64,
+    to_revision: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignOffPlanParams {
+    job_id: String,
+    state: Option<String>,
+    revision: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdatePlanStepParams {
+    job_id: String,
+    step_number: String,
+    status: Option<String>,
+    assignee: Option<String>,
+    clear_assignee: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddPlanStepCommentParams {
+    job_id: String,
+    step_number: String,
+    body: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlanStepCommentParams {
+    job_id: String,
+    comment_id: String,
+    #[serde(default = "default_true")]
+    resolved: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RefinePlanParams {
+    job_id: String,
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportPlanIssuesParams {
+    job_id: String,
+    format: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CreatePlanParams {
@@ -50,6 +119,24 @@ pub async fn dispatch(app_handle: AppHandle, req: RpcRequest) -> RpcResult<Value
         "actions.getImplementationPlanPrompt" => handle_actions_get_implementation_plan_prompt(&app_handle, req).await,
         "actions.estimatePromptTokens" => handle_actions_estimate_prompt_tokens(&app_handle, req).await,
         "plan.generateMarkdown" => handle_generate_plan_markdown(&app_handle, req).await,
+        "plan.listRevisions" => handle_plan_list_revisions(&app_handle, req).await,
+        "plan.getRevision" => handle_plan_get_revision(&app_handle, req).await,
+        "plan.diffRevisions" => handle_plan_diff_revisions(&app_handle, req).await,
+        "plan.restoreRevision" => handle_plan_restore_revision(&app_handle, req).await,
+        "plan.signOff" => handle_plan_sign_off(&app_handle, req).await,
+        "plan.getStepReviews" => handle_plan_get_step_reviews(&app_handle, req).await,
+        "plan.updateStep" => handle_plan_update_step(&app_handle, req).await,
+        "plan.addStepComment" => handle_plan_add_step_comment(&app_handle, req).await,
+        "plan.resolveStepComment" => handle_plan_resolve_step_comment(&app_handle, req).await,
+        "plan.deleteStepComment" => handle_plan_delete_step_comment(&app_handle, req).await,
+        "plan.getProgress" => handle_plan_get_progress(&app_handle, req).await,
+        "plan.checkProgress" => handle_plan_check_progress(&app_handle, req).await,
+        "plan.getConversation" => handle_plan_get_conversation(&app_handle, req).await,
+        "plan.refine" => handle_plan_refine(&app_handle, req).await,
+        "plan.generateTests" => handle_plan_generate_tests(&app_handle, req).await,
+        "plan.getTestPlan" => handle_plan_get_test_plan(&app_handle, req).await,
+        "plan.listExportFormats" => handle_plan_list_export_formats().await,
+        "plan.exportIssues" => handle_plan_export_issues(&app_handle, req).await,
         _ => Err(RpcError::method_not_found(&req.method)),
     }
 }
@@ -440,3 +527,360 @@ async fn handle_generate_plan_markdown(
         "markdown": resp.markdown,
     }))
 }
+
+fn plan_params<T: serde::de::DeserializeOwned>(request: &RpcRequest) -> RpcResult<T> {
+    serde_json::from_value(request.params.clone())
+        .map_err(|e| RpcError::invalid_params(format!("Invalid parameters: {}", e)))
+}
+
+/// Handle plan.listRevisions request
+/// Params: jobId (String)
+/// Response: {"revisions": [PlanRevisionSummary]}
+async fn handle_plan_list_revisions(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let revisions =
+        implementation_plan_commands::list_plan_revisions_command(app_handle.clone(), job_id)
+            .await
+            .map_err(RpcError::from)?;
+
+    Ok(json!({ "revisions": revisions }))
+}
+
+/// Handle plan.getRevision request
+/// Params: jobId (String), revision (i64)
+/// Response: PlanRevision
+async fn handle_plan_get_revision(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let params: PlanRevisionParams = plan_params(&request)?;
+
+    let revision = implementation_plan_commands::get_plan_revision_command(
+        app_handle.clone(),
+        params.job_id,
+        params.revision,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!(revision))
+}
+
+/// Handle plan.diffRevisions request
+/// Params: jobId (String), fromRevision (i64), toRevision (i64)
+/// Response: PlanRevisionDiff
+async fn handle_plan_diff_revisions(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let params: DiffPlanRevisionsParams = plan_params(&request)?;
+
+    let diff = implementation_plan_commands::diff_plan_revisions_command(
+        app_handle.clone(),
+        params.job_id,
+        params.from_revision,
+        params.to_revision,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!(diff))
+}
+
+/// Handle plan.restoreRevision request
+/// Params: jobId (String), revision (i64)
+/// Response: {"revision": PlanRevisionSummary}
+async fn handle_plan_restore_revision(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let params: PlanRevisionParams = plan_params(&request)?;
+
+    let restored = implementation_plan_commands::restore_plan_revision_command(
+        app_handle.clone(),
+        params.job_id,
+        params.revision,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "revision": restored }))
+}
+
+/// Handle plan.signOff request
+/// Params: jobId (String), state (Option<String>), revision (Option<i64>)
+/// Response: {"revision": PlanRevisionSummary}
+async fn handle_plan_sign_off(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let params: SignOffPlanParams = plan_params(&request)?;
+
+    let signed = implementation_plan_commands::mark_implementation_plan_signed_off_command(
+        app_handle.clone(),
+        params.job_id,
+        params.state,
+        params.revision,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "revision": signed }))
+}
+
+/// Handle plan.getStepReviews request
+/// Params: jobId (String)
+/// Response: PlanStepReviews
+async fn handle_plan_get_step_reviews(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let reviews = plan_review_commands::get_plan_step_reviews_command(app_handle.clone(), job_id)
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!(reviews))
+}
+
+/// Handle plan.updateStep request
+/// Params: jobId (String), stepNumber (String), status (Option<String>), assignee (Option<String>), clearAssignee (Option<bool>)
+/// Response: {"step": PlanStepState}
+async fn handle_plan_update_step(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let params: UpdatePlanStepParams = plan_params(&request)?;
+
+    let state = plan_review_commands::update_plan_step_command(
+        app_handle.clone(),
+        params.job_id,
+        params.step_number,
+        params.status,
+        params.assignee,
+        params.clear_assignee,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "step": state }))
+}
+
+/// Handle plan.addStepComment request
+/// Params: jobId (String), stepNumber (String), body (String)
+/// Response: {"comment": PlanStepComment}
+async fn handle_plan_add_step_comment(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let params: AddPlanStepCommentParams = plan_params(&request)?;
+
+    let comment = plan_review_commands::add_plan_step_comment_command(
+        app_handle.clone(),
+        params.job_id,
+        params.step_number,
+        params.body,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "comment": comment }))
+}
+
+/// Handle plan.resolveStepComment request
+/// Params: jobId (String), commentId (String), resolved (Option<bool>, default true)
+/// Response: {"comment": PlanStepComment}
+async fn handle_plan_resolve_step_comment(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let params: PlanStepCommentParams = plan_params(&request)?;
+
+    let comment = plan_review_commands::resolve_plan_step_comment_command(
+        app_handle.clone(),
+        params.job_id,
+        params.comment_id,
+        params.resolved,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "comment": comment }))
+}
+
+/// Handle plan.deleteStepComment request
+/// Params: jobId (String), commentId (String)
+/// Response: {"success": true}
+async fn handle_plan_delete_step_comment(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let params: PlanStepCommentParams = plan_params(&request)?;
+
+    plan_review_commands::delete_plan_step_comment_command(
+        app_handle.clone(),
+        params.job_id,
+        params.comment_id,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "success": true }))
+}
+
+/// Handle plan.getProgress request
+/// Params: jobId (String)
+/// Response: {"progress": PlanProgress | null}
+async fn handle_plan_get_progress(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let progress = plan_review_commands::get_plan_progress_command(app_handle.clone(), job_id)
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "progress": progress }))
+}
+
+/// Handle plan.checkProgress request
+/// Params: jobId (String)
+/// Response: {"progress": PlanProgress}
+async fn handle_plan_check_progress(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let progress = plan_review_commands::check_plan_progress_command(app_handle.clone(), job_id)
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "progress": progress }))
+}
+
+/// Handle plan.getConversation request
+/// Params: jobId (String)
+/// Response: {"conversation": PlanConversation}
+async fn handle_plan_get_conversation(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let conversation =
+        plan_conversation_commands::get_plan_conversation_command(app_handle.clone(), job_id)
+            .await
+            .map_err(RpcError::from)?;
+
+    Ok(json!({ "conversation": conversation }))
+}
+
+/// Handle plan.refine request
+/// Params: jobId (String), message (String)
+/// Response: {"jobId": String, "message": PlanConversationMessage}
+async fn handle_plan_refine(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let params: RefinePlanParams = plan_params(&request)?;
+
+    let started = plan_conversation_commands::refine_implementation_plan_command(
+        app_handle.clone(),
+        params.job_id,
+        params.message,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "jobId": started.job_id, "message": started.message }))
+}
+
+/// Handle plan.generateTests request
+/// Params: jobId (String)
+/// Response: {"jobId": String}
+async fn handle_plan_generate_tests(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let response = plan_test_commands::generate_plan_tests_command(app_handle.clone(), job_id)
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "jobId": response.job_id }))
+}
+
+/// Handle plan.getTestPlan request
+/// Params: jobId (String)
+/// Response: {"testPlan": PlanTestPlan | null}
+async fn handle_plan_get_test_plan(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let job_id = request
+        .params
+        .get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let test_plan = plan_test_commands::get_plan_test_plan_command(app_handle.clone(), job_id)
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "testPlan": test_plan }))
+}
+
+/// Handle plan.listExportFormats request
+/// Response: {"formats": [PlanExportFormat]}
+async fn handle_plan_list_export_formats() -> RpcResult<Value> {
+    let formats = plan_export_commands::list_plan_export_formats_command()
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "formats": formats }))
+}
+
+/// Handle plan.exportIssues request
+/// Params: jobId (String), format (String)
+/// Response: {"export": PlanIssueExport}
+async fn handle_plan_export_issues(
+    app_handle: &AppHandle,
+    request: RpcRequest,
+) -> RpcResult<Value> {
+    let params: ExportPlanIssuesParams = plan_params(&request)?;
+
+    let export = plan_export_commands::export_plan_issues_command(
+        app_handle.clone(),
+        params.job_id,
+        params.format,
+        None,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "export": export }))
+}
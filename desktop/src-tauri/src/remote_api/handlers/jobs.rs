@@ -125,6 +125,9 @@ pub async fn dispatch(app_handle: AppHandle, req: RpcRequest) -> RpcResponse {
         "job.cancel" => handle_job_cancel(&app_handle, req).await,
         "job.delete" => handle_job_delete(&app_handle, req).await,
         "job.updateContent" => handle_job_update_content(&app_handle, req).await,
+        "job.getProvenance" => handle_job_get_provenance(&app_handle, req).await,
+        "job.rerunFromHere" => handle_job_rerun_from_here(&app_handle, req).await,
+        "job.getReplayComparison" => handle_job_get_replay_comparison(&app_handle, req).await,
         _ => Err(RpcError::method_not_found(&req.method)),
     };
 
@@ -317,6 +320,52 @@ async fn handle_job_get(app_handle: &AppHandle, request: RpcRequest) -> RpcResul
     Ok(json!({ "job": job }))
 }
 
+async fn handle_job_get_provenance(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let job_id = request.params.get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+
+    let graph = job_commands::get_job_provenance_command(job_id, app_handle.clone())
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "graph": graph }))
+}
+
+async fn handle_job_rerun_from_here(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let job_id = request.params.get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: jobId"))?
+        .to_string();
+    let overrides = match request.params.get("overrides") {
+        None | Some(Value::Null) => None,
+        Some(v) => Some(
+            serde_json::from_value(v.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid overrides: {}", e)))?,
+        ),
+    };
+
+    let replay = job_commands::rerun_job_from_here_command(job_id, overrides, app_handle.clone())
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "replay": replay }))
+}
+
+async fn handle_job_get_replay_comparison(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let replay_id = request.params.get("replayId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: replayId"))?
+        .to_string();
+
+    let comparison = job_commands::get_job_replay_comparison_command(replay_id, app_handle.clone())
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "comparison": comparison }))
+}
+
 async fn handle_job_cancel(app_handle: &AppHandle, request: RpcRequest) -> RpcResult<Value> {
     let job_id = request.params.get("jobId")
         .and_then(|v| v.as_str())
@@ -355,7 +404,7 @@ async fn handle_job_update_content(app_handle: &AppHandle, request: RpcRequest)
         .ok_or_else(|| RpcError::invalid_params("Missing param: content"))?
         .to_string();
 
-    crate::commands::implementation_plan_commands::update_implementation_plan_content_command(
+    let revision = crate::commands::implementation_plan_commands::update_implementation_plan_content_command(
         job_id,
         content,
         app_handle.clone(),
@@ -363,5 +412,5 @@ async fn handle_job_update_content(app_handle: &AppHandle, request: RpcRequest)
     .await
     .map_err(RpcError::from)?;
 
-    Ok(json!({ "success": true }))
+    Ok(json!({ "success": true, "revision": revision.revision }))
 }
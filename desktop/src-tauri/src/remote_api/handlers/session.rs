@@ -6,6 +6,7 @@ use crate::commands::session_commands;
 use crate::models::CreateSessionRequest;
 use crate::db_utils::session_repository::{SessionRepository, TaskHistoryState, FileHistoryState};
 use crate::services::history_state_sequencer::HistoryStateSequencer;
+use crate::utils::text_crdt::{KnownOps, TextCrdt, TextOp};
 use std::sync::Arc;
 
 const ALLOWED_SESSION_UPDATE_FIELDS: [&str; 7] = [
@@ -27,6 +28,10 @@ pub async fn dispatch(app_handle: AppHandle, req: RpcRequest) -> RpcResponse {
         "session.update" => handle_session_update(app_handle, req).await,
         "session.delete" => handle_session_delete(app_handle, req).await,
         "session.duplicate" => handle_session_duplicate(app_handle, req).await,
+        "session.search" => handle_session_search(app_handle, req).await,
+        "session.fork" => handle_session_fork(app_handle, req).await,
+        "session.getTree" => handle_session_get_tree(app_handle, req).await,
+        "session.compareForks" => handle_session_compare_forks(app_handle, req).await,
         "session.rename" => handle_session_rename(app_handle, req).await,
         "session.getFileRelationships" => handle_session_get_file_relationships(app_handle, req).await,
         "session.getOverview" => handle_session_get_overview(app_handle, req).await,
@@ -35,6 +40,8 @@ pub async fn dispatch(app_handle: AppHandle, req: RpcRequest) -> RpcResponse {
         "session.getHistoryState" => handle_session_get_history_state_rpc(app_handle, req).await,
         "session.syncHistoryState" => handle_session_sync_history_state_rpc(app_handle, req).await,
         "session.mergeHistoryState" => handle_session_merge_history_state_rpc(app_handle, req).await,
+        "session.getTaskOps" => handle_session_get_task_ops(app_handle, req).await,
+        "session.applyTaskOps" => handle_session_apply_task_ops(app_handle, req).await,
         _ => Err(RpcError::method_not_found(&req.method)),
     };
 
@@ -189,6 +196,108 @@ pub async fn handle_session_duplicate(app_handle: AppHandle, request: RpcRequest
     Ok(json!({ "session": session }))
 }
 
+pub async fn handle_session_fork(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let session_id = request.params.get("sessionId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: sessionId"))?
+        .to_string();
+
+    let task_history_index = request.params.get("taskHistoryIndex").and_then(|v| v.as_i64());
+    let file_history_index = request.params.get("fileHistoryIndex").and_then(|v| v.as_i64());
+    let new_name = request.params.get("newName")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    let session = session_commands::fork_session_command(
+        app_handle,
+        session_id,
+        task_history_index,
+        file_history_index,
+        new_name,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "session": session }))
+}
+
+pub async fn handle_session_get_tree(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let session_id = request.params.get("sessionId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: sessionId"))?
+        .to_string();
+
+    let tree = session_commands::get_session_tree_command(app_handle, session_id)
+        .await
+        .map_err(RpcError::from)?;
+
+    Ok(json!({ "tree": tree }))
+}
+
+pub async fn handle_session_compare_forks(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let base_session_id = request.params.get("baseSessionId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: baseSessionId"))?
+        .to_string();
+
+    let other_session_id = request.params.get("otherSessionId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: otherSessionId"))?
+        .to_string();
+
+    let comparison = session_commands::compare_fork_plans_command(
+        app_handle,
+        base_session_id,
+        other_session_id,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "comparison": comparison }))
+}
+
+pub async fn handle_session_search(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let query = request.params.get("query")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: query"))?
+        .to_string();
+
+    let project_directory = request.params.get("projectDirectory")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    let task_types = match request.params.get("taskTypes") {
+        Some(Value::Null) | None => None,
+        Some(value) => Some(
+            serde_json::from_value::<Vec<String>>(value.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid taskTypes: {}", e)))?,
+        ),
+    };
+
+    let from_ms = request.params.get("fromMs").and_then(|v| v.as_i64());
+    let to_ms = request.params.get("toMs").and_then(|v| v.as_i64());
+
+    // Cap results to keep mobile payloads bounded
+    let limit = request.params.get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|n| n.min(100) as u32)
+        .or(Some(50));
+
+    let hits = session_commands::search_sessions_command(
+        app_handle,
+        query,
+        project_directory,
+        task_types,
+        from_ms,
+        to_ms,
+        limit,
+    )
+    .await
+    .map_err(RpcError::from)?;
+
+    Ok(json!({ "results": hits }))
+}
+
 pub async fn handle_session_rename(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
     let session_id = request.params.get("sessionId")
         .and_then(|v| v.as_str())
@@ -485,3 +594,54 @@ pub async fn handle_session_merge_history_state_rpc(
 ) -> RpcResult<Value> {
     handle_session_merge_history_state(app_handle, request.params).await
 }
+
+/// Replicated task description operations, optionally only those the caller is
+/// missing (`knownOps`: replica id -> inclusive counter ranges it has integrated)
+pub async fn handle_session_get_task_ops(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let session_id = request.params.get("sessionId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: sessionId"))?
+        .to_string();
+    let known_ops: KnownOps = match request.params.get("knownOps") {
+        Some(v) if !v.is_null() => serde_json::from_value(v.clone())
+            .map_err(|e| RpcError::invalid_params(format!("Invalid knownOps: {}", e)))?,
+        _ => KnownOps::new(),
+    };
+
+    // Applying an empty batch seeds the operation log from the current description
+    let sequencer = app_handle.state::<Arc<HistoryStateSequencer>>().inner().clone();
+    sequencer.enqueue_apply_task_ops(session_id.clone(), Vec::new())
+        .await
+        .map_err(RpcError::internal_error)?;
+
+    let repo = app_handle.state::<Arc<SessionRepository>>().inner().clone();
+    let ops = repo.get_task_description_ops(&session_id)
+        .await
+        .map_err(RpcError::from)?;
+    let doc = TextCrdt::from_ops("reader", ops);
+
+    Ok(json!({
+        "ops": doc.ops_missing_from(&known_ops),
+        "knownOps": doc.known_ops(),
+        "text": doc.text(),
+    }))
+}
+
+pub async fn handle_session_apply_task_ops(app_handle: AppHandle, request: RpcRequest) -> RpcResult<Value> {
+    let session_id = request.params.get("sessionId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::invalid_params("Missing param: sessionId"))?
+        .to_string();
+    let ops: Vec<TextOp> = request.params.get("ops")
+        .cloned()
+        .ok_or_else(|| RpcError::invalid_params("Missing param: ops"))
+        .and_then(|v| serde_json::from_value(v)
+            .map_err(|e| RpcError::invalid_params(format!("Invalid ops: {}", e))))?;
+
+    let sequencer = app_handle.state::<Arc<HistoryStateSequencer>>().inner().clone();
+    let text = sequencer.enqueue_apply_task_ops(session_id, ops)
+        .await
+        .map_err(RpcError::internal_error)?;
+
+    Ok(json!({ "text": text }))
+}
@@ -0,0 +1,637 @@
+//! Localhost HTTP + WebSocket endpoint exposing the RPC router to editor plugins.
+//!
+//! The same `dispatch` used by the device-link relay is served on `127.0.0.1` only:
+//! - `POST /rpc` takes a JSON-RPC 2.0 request and returns its response
+//! - `GET /ws` accepts JSON-RPC requests over a WebSocket and pushes every
+//!   `device-link-event` (job, session, workflow and terminal updates) as an `event`
+//!   notification, optionally filtered with `events.subscribe`
+//!
+//! Every request must carry the per-install token from `local_api_token` in the app data
+//! dir, as `Authorization: Bearer <token>` or, for WebSocket clients that cannot set
+//! headers, a `token` query parameter. The server is disabled by default.
+
+use crate::auth::device_id_manager;
+use crate::error::{AppError, AppResult};
+use crate::remote_api::desktop_command_handler;
+use crate::remote_api::error::RpcError;
+use crate::remote_api::types::{RpcRequest, RpcResponse, UserContext};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, EventId, Listener, Manager};
+use tokio::sync::{Mutex, broadcast, mpsc, watch};
+
+pub const DEFAULT_LOCAL_API_PORT: u16 = 47615;
+const TOKEN_FILE: &str = "local_api_token";
+const DISCOVERY_FILE: &str = "local_api.json";
+/// Messages queued for one WebSocket client; a client this far behind is disconnected
+const CLIENT_QUEUE_LIMIT: usize = 256;
+
+/// Persisted settings for the local API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiConfig {
+    pub enabled: bool,
+    /// Port to listen on; 0 lets the OS pick one (published in `local_api.json`)
+    pub port: u16,
+}
+
+impl Default for LocalApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_LOCAL_API_PORT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiStatus {
+    pub running: bool,
+    pub url: Option<String>,
+    pub port: Option<u16>,
+    pub token_path: String,
+    pub discovery_path: String,
+}
+
+struct RunningServer {
+    port: u16,
+    shutdown: watch::Sender<bool>,
+    listener_id: EventId,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    app_handle: AppHandle,
+    token: Arc<String>,
+    events: broadcast::Sender<Value>,
+    shutdown: watch::Receiver<bool>,
+}
+
+/// Owns the lifecycle of the localhost API server; managed as `Arc<LocalApiServer>`
+pub struct LocalApiServer {
+    app_handle: AppHandle,
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl LocalApiServer {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Start listening on `port`, stopping any server already running. Returns the bound port.
+    pub async fn start(&self, port: u16) -> AppResult<u16> {
+        let mut running = self.running.lock().await;
+        if let Some(server) = running.take() {
+            shutdown_server(&self.app_handle, server);
+        }
+
+        let token = load_or_create_token(&self.app_handle)?;
+        let listener = bind_localhost(port).await?;
+        let bound_port = listener.local_addr()?.port();
+
+        let (events_tx, _) = broadcast::channel::<Value>(512);
+        let forward_tx = events_tx.clone();
+        let listener_id = self.app_handle.listen("device-link-event", move |event| {
+            if forward_tx.receiver_count() == 0 {
+                return;
+            }
+            if let Ok(value) = serde_json::from_str::<Value>(event.payload()) {
+                let _ = forward_tx.send(value);
+            }
+        });
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let state = ServerState {
+            app_handle: self.app_handle.clone(),
+            token: Arc::new(token),
+            events: events_tx,
+            shutdown: shutdown_rx.clone(),
+        };
+        let router = Router::new()
+            .route("/health", get(health))
+            .route("/rpc", post(rpc_http))
+            .route("/ws", get(rpc_ws))
+            .with_state(state);
+
+        let mut graceful = shutdown_rx;
+        tauri::async_runtime::spawn(async move {
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async move {
+                    let _ = graceful.wait_for(|stopped| *stopped).await;
+                })
+                .await;
+            if let Err(e) = result {
+                warn!("Local API server stopped with error: {}", e);
+            }
+        });
+
+        write_discovery_file(&self.app_handle, bound_port);
+        info!("Local API listening on http://127.0.0.1:{}", bound_port);
+
+        *running = Some(RunningServer {
+            port: bound_port,
+            shutdown: shutdown_tx,
+            listener_id,
+        });
+        Ok(bound_port)
+    }
+
+    pub async fn stop(&self) {
+        if let Some(server) = self.running.lock().await.take() {
+            shutdown_server(&self.app_handle, server);
+            info!("Local API stopped");
+        }
+    }
+
+    /// Start, restart or stop the server to match the config
+    pub async fn apply_config(&self, config: &LocalApiConfig) -> AppResult<LocalApiStatus> {
+        if config.enabled {
+            self.start(config.port).await?;
+        } else {
+            self.stop().await;
+        }
+        self.status().await
+    }
+
+    pub async fn status(&self) -> AppResult<LocalApiStatus> {
+        let port = self.running.lock().await.as_ref().map(|s| s.port);
+        Ok(LocalApiStatus {
+            running: port.is_some(),
+            url: port.map(|p| format!("http://127.0.0.1:{}", p)),
+            port,
+            token_path: data_file(&self.app_handle, TOKEN_FILE)?
+                .to_string_lossy()
+                .to_string(),
+            discovery_path: data_file(&self.app_handle, DISCOVERY_FILE)?
+                .to_string_lossy()
+                .to_string(),
+        })
+    }
+
+    /// Replace the token; a running server is restarted so old tokens stop working immediately
+    pub async fn regenerate_token(&self) -> AppResult<LocalApiStatus> {
+        let path = data_file(&self.app_handle, TOKEN_FILE)?;
+        write_private_file(&path, &generate_token())?;
+
+        let port = self.running.lock().await.as_ref().map(|s| s.port);
+        if let Some(port) = port {
+            self.start(port).await?;
+        }
+        self.status().await
+    }
+}
+
+/// Bind 127.0.0.1, retrying briefly while a just-stopped server releases the port
+async fn bind_localhost(port: u16) -> AppResult<tokio::net::TcpListener> {
+    let mut attempts = 0;
+    loop {
+        match tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse && attempts < 20 => {
+                attempts += 1;
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+            Err(e) => {
+                return Err(AppError::NetworkError(format!(
+                    "Failed to bind local API to 127.0.0.1:{}: {}",
+                    port, e
+                )));
+            }
+        }
+    }
+}
+
+fn shutdown_server(app_handle: &AppHandle, server: RunningServer) {
+    app_handle.unlisten(server.listener_id);
+    let _ = server.shutdown.send(true);
+    if let Ok(path) = data_file(app_handle, DISCOVERY_FILE) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn data_file(app_handle: &AppHandle, name: &str) -> AppResult<PathBuf> {
+    let dir = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| AppError::ConfigError(format!("Failed to get app data dir: {}", e)))?;
+    Ok(dir.join(name))
+}
+
+fn generate_token() -> String {
+    rand::random::<[u8; 32]>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn load_or_create_token(app_handle: &AppHandle) -> AppResult<String> {
+    let path = data_file(app_handle, TOKEN_FILE)?;
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if existing.len() >= 32 {
+            return Ok(existing.to_string());
+        }
+    }
+    let token = generate_token();
+    write_private_file(&path, &token)?;
+    Ok(token)
+}
+
+/// Write a file readable only by the current user
+fn write_private_file(path: &Path, contents: &str) -> AppResult<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files; restrict an existing one before writing to it
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// Publish where the server is listening so plugins can find it without configuration
+fn write_discovery_file(app_handle: &AppHandle, port: u16) {
+    let result = data_file(app_handle, DISCOVERY_FILE).and_then(|path| {
+        let info = json!({
+            "url": format!("http://127.0.0.1:{}", port),
+            "wsUrl": format!("ws://127.0.0.1:{}/ws", port),
+            "port": port,
+            "pid": std::process::id(),
+            "tokenPath": data_file(app_handle, TOKEN_FILE)?.to_string_lossy(),
+        });
+        write_private_file(&path, &serde_json::to_string_pretty(&info)?)
+    });
+    if let Err(e) = result {
+        warn!("Failed to write local API discovery file: {}", e);
+    }
+}
+
+/// Compare without short-circuiting so response timing does not leak the token prefix
+fn tokens_match(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn is_authorized(
+    state: &ServerState,
+    headers: &HeaderMap,
+    query: &HashMap<String, String>,
+) -> bool {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    bearer
+        .or_else(|| query.get("token").map(String::as_str))
+        .is_some_and(|provided| tokens_match(&state.token, provided.trim()))
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": RpcError::unauthorized("Missing or invalid local API token"),
+        })),
+    )
+        .into_response()
+}
+
+/// JSON-RPC 2.0 request as sent by plugins
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRpcRequest {
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    idempotency_key: Option<String>,
+}
+
+impl JsonRpcRequest {
+    fn into_rpc_request(self) -> (Option<Value>, RpcRequest) {
+        let correlation_id = match &self.id {
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => uuid::Uuid::new_v4().to_string(),
+        };
+        let params = if self.params.is_null() {
+            json!({})
+        } else {
+            self.params
+        };
+        (
+            self.id,
+            RpcRequest {
+                method: self.method,
+                params,
+                correlation_id,
+                idempotency_key: self.idempotency_key,
+            },
+        )
+    }
+}
+
+fn json_rpc_response(id: Option<Value>, response: RpcResponse) -> Value {
+    let mut body = json!({ "jsonrpc": "2.0", "id": id.unwrap_or(Value::Null) });
+    match response.error {
+        Some(error) => body["error"] = json!(error),
+        None => body["result"] = response.result.unwrap_or(Value::Null),
+    }
+    body
+}
+
+fn parse_error(message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": RpcError::new(RpcError::PARSE_ERROR, message),
+    })
+}
+
+async fn dispatch_local(app_handle: &AppHandle, request: RpcRequest) -> RpcResponse {
+    let device_id =
+        device_id_manager::get_or_create(app_handle).unwrap_or_else(|_| "unknown".to_string());
+    let user_context = UserContext {
+        user_id: "local".to_string(),
+        device_id,
+        permissions: vec!["rpc".to_string()],
+    };
+    desktop_command_handler::dispatch_remote_command(app_handle, request, &user_context).await
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+async fn rpc_http(
+    State(state): State<ServerState>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    if !is_authorized(&state, &headers, &query) {
+        return unauthorized();
+    }
+
+    let request: JsonRpcRequest = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(parse_error(e.to_string()))).into_response();
+        }
+    };
+    let (id, request) = request.into_rpc_request();
+    debug!("Local API request: {}", request.method);
+    let response = dispatch_local(&state.app_handle, request).await;
+    Json(json_rpc_response(id, response)).into_response()
+}
+
+async fn rpc_ws(
+    State(state): State<ServerState>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if !is_authorized(&state, &headers, &query) {
+        return unauthorized();
+    }
+    ws.on_upgrade(move |socket| handle_socket(state, socket))
+}
+
+/// Whether an event type passes the client's `events.subscribe` prefixes (none = everything)
+fn event_matches(filters: &[String], event_type: &str) -> bool {
+    filters.is_empty() || filters.iter().any(|prefix| event_type.starts_with(prefix))
+}
+
+async fn handle_socket(state: ServerState, socket: WebSocket) {
+    let (mut sink, mut stream) = socket.split();
+    let (out_tx, mut out_rx) = mpsc::channel::<Value>(CLIENT_QUEUE_LIMIT);
+    let mut events = state.events.subscribe();
+    let mut shutdown = state.shutdown.clone();
+    let mut filters: Vec<String> = Vec::new();
+
+    let writer = tauri::async_runtime::spawn(async move {
+        while let Some(message) = out_rx.recv().await {
+            if sink
+                .send(Message::Text(message.to_string().into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    // Set when the client stops reading and its queue fills up
+    let mut stalled = false;
+    loop {
+        tokio::select! {
+            _ = shutdown.wait_for(|stopped| *stopped) => break,
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let event_type = event.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+                    if event_matches(&filters, event_type) && out_tx.try_send(json!({
+                        "jsonrpc": "2.0",
+                        "method": "event",
+                        "params": event,
+                    })).is_err() {
+                        stalled = true;
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    if out_tx.try_send(json!({
+                        "jsonrpc": "2.0",
+                        "method": "eventsDropped",
+                        "params": { "count": skipped },
+                    })).is_err() {
+                        stalled = true;
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = stream.next() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                };
+                let request: JsonRpcRequest = match serde_json::from_str(text.as_str()) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        if out_tx.try_send(parse_error(e.to_string())).is_err() {
+                            stalled = true;
+                            break;
+                        }
+                        continue;
+                    }
+                };
+
+                // Subscription filters are connection state, handled here rather than by the router
+                if request.method == "events.subscribe" {
+                    filters = request
+                        .params
+                        .get("types")
+                        .and_then(|t| t.as_array())
+                        .map(|types| {
+                            types
+                                .iter()
+                                .filter_map(|t| t.as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    if out_tx.try_send(json!({
+                        "jsonrpc": "2.0",
+                        "id": request.id,
+                        "result": { "types": filters },
+                    })).is_err() {
+                        stalled = true;
+                        break;
+                    }
+                    continue;
+                }
+
+                let app_handle = state.app_handle.clone();
+                let out_tx = out_tx.clone();
+                tauri::async_runtime::spawn(async move {
+                    let is_notification = request.id.is_none();
+                    let (id, request) = request.into_rpc_request();
+                    let response = dispatch_local(&app_handle, request).await;
+                    if !is_notification {
+                        let _ = out_tx.send(json_rpc_response(id, response)).await;
+                    }
+                });
+            }
+        }
+    }
+
+    if stalled {
+        // Queued messages would never be read; drop them with the connection
+        warn!("Closing local API WebSocket client that stopped reading");
+        writer.abort();
+    } else {
+        drop(out_tx);
+        let _ = writer.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_comparison_requires_exact_match() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert!(tokens_match(&token, &token.clone()));
+        assert!(!tokens_match(&token, &token[..63]));
+        assert!(!tokens_match(&token, &generate_token()));
+    }
+
+    #[test]
+    fn converts_json_rpc_requests_and_responses() {
+        let request: JsonRpcRequest = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "session.get",
+            "params": { "sessionId": "s1" },
+        }))
+        .unwrap();
+        let (id, rpc) = request.into_rpc_request();
+        assert_eq!(id, Some(json!(7)));
+        assert_eq!(rpc.correlation_id, "7");
+        assert_eq!(rpc.params["sessionId"], "s1");
+
+        let ok = json_rpc_response(
+            id.clone(),
+            RpcResponse {
+                correlation_id: rpc.correlation_id.clone(),
+                result: Some(json!({ "ok": true })),
+                error: None,
+                is_final: true,
+            },
+        );
+        assert_eq!(
+            ok,
+            json!({ "jsonrpc": "2.0", "id": 7, "result": { "ok": true } })
+        );
+
+        let failed = json_rpc_response(
+            id,
+            RpcResponse {
+                correlation_id: rpc.correlation_id,
+                result: None,
+                error: Some(RpcError::method_not_found("nope")),
+                is_final: true,
+            },
+        );
+        assert_eq!(failed["error"]["code"], RpcError::METHOD_NOT_FOUND);
+        assert!(failed.get("result").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_only_readable_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("local_api_{}", generate_token()));
+        let path = dir.join(TOKEN_FILE);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, "secret").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "secret");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn event_filters_match_by_prefix() {
+        assert!(event_matches(&[], "job:created"));
+        let filters = vec!["job:".to_string(), "terminal.".to_string()];
+        assert!(event_matches(&filters, "job:status-changed"));
+        assert!(event_matches(&filters, "terminal.exit"));
+        assert!(!event_matches(&filters, "session-updated"));
+    }
+}
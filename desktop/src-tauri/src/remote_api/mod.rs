@@ -1,5 +1,6 @@
 pub mod desktop_command_handler;
 pub mod error;
+pub mod local_server;
 pub mod router;
 pub mod types;
 pub mod handlers;
@@ -0,0 +1,346 @@
+//! On-disk format for database backups.
+//!
+//! ```text
+//! magic "PCBAK\x01" | flags u8 | sha256(snapshot) [32]
+//!   | salt [16]   (passphrase-protected exports only)
+//!   | nonce [12]  (encrypted archives only)
+//!   | body
+//! ```
+//!
+//! The body is the SQLite snapshot, gzip-compressed when `FLAG_COMPRESSED` is
+//! set and sealed with ChaCha20-Poly1305 when `FLAG_ENCRYPTED` is set. The
+//! header is authenticated as associated data, and the snapshot hash lets a
+//! backup be verified (and deduplicated) without opening it as a database.
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use keyring::{Entry, Error as KeyringError};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+
+use crate::auth::token_persistence::SERVICE_NAME_FOR_KEYRING;
+use crate::error::{AppError, AppResult};
+
+const MAGIC: &[u8; 6] = b"PCBAK\x01";
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+const HASH_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+pub const FLAG_COMPRESSED: u8 = 0b001;
+pub const FLAG_ENCRYPTED: u8 = 0b010;
+pub const FLAG_PASSPHRASE: u8 = 0b100;
+
+/// Keyring account holding the machine-local backup key
+const BACKUP_KEY_ACCOUNT: &str = "backup-encryption-key";
+
+/// Key used to seal or open an archive
+pub enum BackupKey {
+    /// Random key kept in the OS keyring; only readable on this machine
+    Local([u8; 32]),
+    /// User-supplied passphrase for backups moved to another machine
+    Passphrase(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveHeader {
+    pub flags: u8,
+    pub content_hash: [u8; HASH_LEN],
+    salt: Option<[u8; SALT_LEN]>,
+    nonce: Option<[u8; NONCE_LEN]>,
+    len: usize,
+}
+
+impl ArchiveHeader {
+    pub fn is_compressed(&self) -> bool {
+        self.flags & FLAG_COMPRESSED != 0
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.flags & FLAG_ENCRYPTED != 0
+    }
+
+    pub fn needs_passphrase(&self) -> bool {
+        self.flags & FLAG_PASSPHRASE != 0
+    }
+}
+
+/// Whether `data` is a plain SQLite file (backups made before archives existed)
+pub fn is_raw_sqlite(data: &[u8]) -> bool {
+    data.starts_with(SQLITE_MAGIC)
+}
+
+pub fn snapshot_hash(snapshot: &[u8]) -> [u8; HASH_LEN] {
+    Sha256::digest(snapshot).into()
+}
+
+pub fn read_header(data: &[u8]) -> AppResult<ArchiveHeader> {
+    let invalid = || AppError::ValidationError("Not a PlanToCode backup archive".to_string());
+
+    if !data.starts_with(MAGIC) {
+        return Err(invalid());
+    }
+    let mut pos = MAGIC.len();
+    let flags = *data.get(pos).ok_or_else(invalid)?;
+    pos += 1;
+
+    let content_hash: [u8; HASH_LEN] = take(data, &mut pos).ok_or_else(invalid)?;
+    let salt = if flags & FLAG_PASSPHRASE != 0 {
+        Some(take(data, &mut pos).ok_or_else(invalid)?)
+    } else {
+        None
+    };
+    let nonce = if flags & FLAG_ENCRYPTED != 0 {
+        Some(take(data, &mut pos).ok_or_else(invalid)?)
+    } else {
+        None
+    };
+
+    Ok(ArchiveHeader {
+        flags,
+        content_hash,
+        salt,
+        nonce,
+        len: pos,
+    })
+}
+
+/// Wrap a SQLite snapshot into an archive
+pub fn encode(snapshot: &[u8], compress: bool, key: Option<&BackupKey>) -> AppResult<Vec<u8>> {
+    let mut flags = 0;
+    if compress {
+        flags |= FLAG_COMPRESSED;
+    }
+    let salt = match key {
+        Some(BackupKey::Passphrase(_)) => Some(rand::random::<[u8; SALT_LEN]>()),
+        _ => None,
+    };
+    if key.is_some() {
+        flags |= FLAG_ENCRYPTED;
+    }
+    if salt.is_some() {
+        flags |= FLAG_PASSPHRASE;
+    }
+    let nonce = key.map(|_| rand::random::<[u8; NONCE_LEN]>());
+
+    let mut out = Vec::with_capacity(snapshot.len() / 2);
+    out.extend_from_slice(MAGIC);
+    out.push(flags);
+    out.extend_from_slice(&snapshot_hash(snapshot));
+    if let Some(salt) = salt {
+        out.extend_from_slice(&salt);
+    }
+    if let Some(nonce) = nonce {
+        out.extend_from_slice(&nonce);
+    }
+
+    let body = if compress {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(snapshot)
+            .map_err(|e| AppError::InternalError(format!("Failed to compress backup: {}", e)))?;
+        encoder
+            .finish()
+            .map_err(|e| AppError::InternalError(format!("Failed to compress backup: {}", e)))?
+    } else {
+        snapshot.to_vec()
+    };
+
+    let body = match (key, nonce) {
+        (Some(key), Some(nonce)) => {
+            let cipher = cipher_for(key, salt.as_ref())?;
+            cipher
+                .encrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &body,
+                        aad: &out,
+                    },
+                )
+                .map_err(|_| AppError::InternalError("Failed to encrypt backup".to_string()))?
+        }
+        _ => body,
+    };
+
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+/// Unwrap an archive back into the SQLite snapshot, verifying its hash.
+/// `key` is only consulted for encrypted archives.
+pub fn decode(data: &[u8], key: Option<&BackupKey>) -> AppResult<Vec<u8>> {
+    let header = read_header(data)?;
+    let (head, body) = data.split_at(header.len);
+
+    let body = match header.nonce {
+        Some(nonce) => {
+            let key = key.ok_or_else(|| {
+                AppError::ValidationError("Backup is encrypted and no key was provided".to_string())
+            })?;
+            if header.needs_passphrase() != matches!(key, BackupKey::Passphrase(_)) {
+                return Err(AppError::ValidationError(if header.needs_passphrase() {
+                    "Backup was exported with a passphrase".to_string()
+                } else {
+                    "Backup is encrypted with this machine's backup key".to_string()
+                }));
+            }
+            let cipher = cipher_for(key, header.salt.as_ref())?;
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: body,
+                        aad: head,
+                    },
+                )
+                .map_err(|_| {
+                    AppError::ValidationError(
+                        "Backup could not be decrypted (wrong key or corrupted file)".to_string(),
+                    )
+                })?
+        }
+        None => body.to_vec(),
+    };
+
+    let snapshot = if header.is_compressed() {
+        let mut out = Vec::with_capacity(body.len() * 3);
+        GzDecoder::new(body.as_slice())
+            .read_to_end(&mut out)
+            .map_err(|e| AppError::ValidationError(format!("Backup is corrupted: {}", e)))?;
+        out
+    } else {
+        body
+    };
+
+    if snapshot_hash(&snapshot) != header.content_hash {
+        return Err(AppError::ValidationError(
+            "Backup content does not match its checksum".to_string(),
+        ));
+    }
+    if !is_raw_sqlite(&snapshot) {
+        return Err(AppError::ValidationError(
+            "Backup does not contain a SQLite database".to_string(),
+        ));
+    }
+
+    Ok(snapshot)
+}
+
+/// Load the machine-local backup key from the OS keyring, creating it on first use
+pub fn load_or_create_local_key() -> AppResult<[u8; 32]> {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+
+    let entry = Entry::new(SERVICE_NAME_FOR_KEYRING, BACKUP_KEY_ACCOUNT)
+        .map_err(|e| AppError::StorageError(format!("Failed to create keyring entry: {}", e)))?;
+
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = STANDARD.decode(encoded.trim()).map_err(|e| {
+                AppError::StorageError(format!("Invalid backup key in keyring: {}", e))
+            })?;
+            bytes.try_into().map_err(|_| {
+                AppError::StorageError("Invalid backup key length in keyring".to_string())
+            })
+        }
+        Err(KeyringError::NoEntry) => {
+            let key = rand::random::<[u8; 32]>();
+            entry.set_password(&STANDARD.encode(key)).map_err(|e| {
+                AppError::StorageError(format!("Failed to store backup key: {}", e))
+            })?;
+            Ok(key)
+        }
+        Err(e) => Err(AppError::StorageError(format!(
+            "Failed to read backup key from keyring: {}",
+            e
+        ))),
+    }
+}
+
+fn cipher_for(key: &BackupKey, salt: Option<&[u8; SALT_LEN]>) -> AppResult<ChaCha20Poly1305> {
+    let key_bytes = match key {
+        BackupKey::Local(bytes) => *bytes,
+        BackupKey::Passphrase(passphrase) => {
+            let salt = salt.ok_or_else(|| {
+                AppError::ValidationError("Passphrase archive is missing its salt".to_string())
+            })?;
+            let mut derived = [0u8; 32];
+            Argon2::default()
+                .hash_password_into(passphrase.as_bytes(), salt, &mut derived)
+                .map_err(|e| {
+                    AppError::InternalError(format!("Failed to derive backup key: {}", e))
+                })?;
+            derived
+        }
+    };
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key_bytes)))
+}
+
+fn take<const N: usize>(data: &[u8], pos: &mut usize) -> Option<[u8; N]> {
+    let bytes = data.get(*pos..*pos + N)?.try_into().ok()?;
+    *pos += N;
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_snapshot() -> Vec<u8> {
+        let mut data = SQLITE_MAGIC.to_vec();
+        data.extend(std::iter::repeat_n(b"session task description ".as_slice(), 200).flatten());
+        data
+    }
+
+    #[test]
+    fn test_round_trip_for_every_mode() {
+        let snapshot = fake_snapshot();
+        let local = BackupKey::Local([7u8; 32]);
+        let passphrase = BackupKey::Passphrase("correct horse".to_string());
+
+        for compress in [false, true] {
+            for key in [None, Some(&local), Some(&passphrase)] {
+                let archive = encode(&snapshot, compress, key).unwrap();
+                let header = read_header(&archive).unwrap();
+                assert_eq!(header.is_compressed(), compress);
+                assert_eq!(header.is_encrypted(), key.is_some());
+                assert_eq!(header.content_hash, snapshot_hash(&snapshot));
+                assert_eq!(decode(&archive, key).unwrap(), snapshot);
+            }
+        }
+
+        let compressed = encode(&snapshot, true, None).unwrap();
+        assert!(compressed.len() < snapshot.len() / 4);
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_key_and_tampering() {
+        let snapshot = fake_snapshot();
+        let archive = encode(&snapshot, true, Some(&BackupKey::Local([1u8; 32]))).unwrap();
+
+        assert!(decode(&archive, None).is_err());
+        assert!(decode(&archive, Some(&BackupKey::Local([2u8; 32]))).is_err());
+        assert!(decode(&archive, Some(&BackupKey::Passphrase("x".to_string()))).is_err());
+
+        let mut tampered = archive.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0xff;
+        assert!(decode(&tampered, Some(&BackupKey::Local([1u8; 32]))).is_err());
+
+        // Flipping the compression flag is caught by the authenticated header
+        let mut flipped = archive;
+        flipped[MAGIC.len()] ^= FLAG_COMPRESSED;
+        assert!(decode(&flipped, Some(&BackupKey::Local([1u8; 32]))).is_err());
+    }
+
+    #[test]
+    fn test_plain_sqlite_is_not_an_archive() {
+        let snapshot = fake_snapshot();
+        assert!(is_raw_sqlite(&snapshot));
+        assert!(read_header(&snapshot).is_err());
+    }
+}
@@ -1,13 +1,22 @@
 use crate::constants::DB_FILENAME;
 use crate::error::AppResult;
-use chrono::{DateTime, Utc};
+use crate::services::backup_archive::{self, BackupKey};
+use chrono::Utc;
 use log::{error, info, warn};
 use sqlx::SqlitePool;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time;
 
+const BACKUP_PREFIX: &str = "appdata_backup_";
+const PRE_RESTORE_PREFIX: &str = "pre_restore_backup_";
+const ARCHIVE_EXTENSION: &str = "pcbak";
+/// Backups created before archives were introduced are plain SQLite copies
+const LEGACY_EXTENSION: &str = "db";
+
 /// Configuration for the backup service
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct BackupConfig {
@@ -17,6 +26,16 @@ pub struct BackupConfig {
     pub max_backup_count: usize,
     /// Whether automatic backups are enabled
     pub enabled: bool,
+    /// Gzip-compress backup archives
+    #[serde(default = "default_compress")]
+    pub compress: bool,
+    /// Encrypt backup archives with a key stored in the OS keyring
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+fn default_compress() -> bool {
+    true
 }
 
 impl Default for BackupConfig {
@@ -25,13 +44,15 @@ impl Default for BackupConfig {
             backup_interval_minutes: 15, // 15 minute backups by default
             max_backup_count: 96,        // Keep 96 backups (24 hours worth at 15min intervals)
             enabled: true,
+            compress: true,
+            encrypt: false,
         }
     }
 }
 
 /// Service responsible for automatic database backups
 pub struct BackupService {
-    config: BackupConfig,
+    config: RwLock<BackupConfig>,
     app_data_dir: PathBuf,
     backup_dir: PathBuf,
     db_pool: SqlitePool,
@@ -43,16 +64,29 @@ impl BackupService {
         let backup_dir = app_data_dir.join("backups");
 
         Self {
-            config,
+            config: RwLock::new(config),
             app_data_dir,
             backup_dir,
             db_pool,
         }
     }
 
+    /// Current configuration
+    pub fn config(&self) -> BackupConfig {
+        self.config.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Replace the configuration. Compression, encryption and retention apply to
+    /// the next backup; a changed interval takes effect after a restart.
+    pub fn update_config(&self, config: BackupConfig) {
+        if let Ok(mut current) = self.config.write() {
+            *current = config;
+        }
+    }
+
     /// Initialize the backup service and start automatic backups
     pub async fn initialize(&self) -> AppResult<()> {
-        if !self.config.enabled {
+        if !self.config().enabled {
             info!("Backup service is disabled");
             return Ok(());
         }
@@ -77,21 +111,26 @@ impl BackupService {
 
     /// Start the automatic backup scheduler
     pub async fn start_scheduler(&self) {
-        if !self.config.enabled {
+        let config = self.config();
+        if !config.enabled {
             return;
         }
 
-        let interval_duration = Duration::from_secs(self.config.backup_interval_minutes * 60);
+        let interval_duration = Duration::from_secs(config.backup_interval_minutes * 60);
         let mut interval = time::interval(interval_duration);
 
         info!(
             "Starting backup scheduler with interval: {} minutes",
-            self.config.backup_interval_minutes
+            config.backup_interval_minutes
         );
 
         loop {
             interval.tick().await;
 
+            if !self.config().enabled {
+                continue;
+            }
+
             match self.should_create_backup().await {
                 Ok(should_backup) => {
                     if should_backup {
@@ -126,7 +165,7 @@ impl BackupService {
             Some(backup_path) => {
                 // Check if the latest backup is older than our interval
                 let backup_age = self.get_file_age(&backup_path)?;
-                let interval_seconds = self.config.backup_interval_minutes * 60;
+                let interval_seconds = self.config().backup_interval_minutes * 60;
                 Ok(backup_age > interval_seconds)
             }
             None => {
@@ -141,7 +180,12 @@ impl BackupService {
         self.create_backup().await
     }
 
-    /// Create a backup of the database (internal method)
+    /// Create a backup of the database (internal method).
+    ///
+    /// The snapshot is taken with `VACUUM INTO`, which is consistent while other
+    /// connections keep writing and drops free pages. When the content is
+    /// identical to the latest backup no new file is written; the latest backup
+    /// is marked as current instead.
     async fn create_backup(&self) -> AppResult<PathBuf> {
         let db_path = self.app_data_dir.join(DB_FILENAME);
 
@@ -151,36 +195,140 @@ impl BackupService {
             ));
         }
 
-        // Generate backup filename with timestamp
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let backup_filename = format!("appdata_backup_{}.db", timestamp);
-        let backup_path = self.backup_dir.join(&backup_filename);
+        let snapshot = self.snapshot_database().await?;
+        let content_hash = backup_archive::snapshot_hash(&snapshot);
+
+        if let Some(latest) = self.get_latest_backup()? {
+            if self.read_content_hash(&latest) == Some(content_hash) {
+                fs::File::options()
+                    .append(true)
+                    .open(&latest)?
+                    .set_modified(SystemTime::now())?;
+                info!(
+                    "Database unchanged since {}, skipping backup",
+                    latest.display()
+                );
+                return Ok(latest);
+            }
+        }
+
+        let backup_path = self.write_archive(BACKUP_PREFIX, &snapshot)?;
 
-        // Ensure WAL checkpoint before backup to get all data
-        match sqlx::query("PRAGMA wal_checkpoint(FULL)")
+        info!(
+            "Database backup created successfully: {} ({} bytes snapshot)",
+            backup_path.display(),
+            snapshot.len()
+        );
+        Ok(backup_path)
+    }
+
+    /// Take a consistent, verified copy of the live database
+    async fn snapshot_database(&self) -> AppResult<Vec<u8>> {
+        if !self.backup_dir.exists() {
+            fs::create_dir_all(&self.backup_dir)?;
+        }
+
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S_%3f");
+        let snapshot_path = self.backup_dir.join(format!(".snapshot_{}.tmp", timestamp));
+
+        sqlx::query("VACUUM INTO $1")
+            .bind(snapshot_path.to_string_lossy().to_string())
             .execute(&self.db_pool)
             .await
-        {
-            Ok(_) => info!("WAL checkpoint completed before backup"),
-            Err(e) => warn!("WAL checkpoint failed before backup: {}", e),
-        }
+            .map_err(|e| {
+                crate::error::AppError::DatabaseError(format!("Failed to snapshot database: {}", e))
+            })?;
 
-        // Copy the database file
-        fs::copy(&db_path, &backup_path)?;
+        let result = match self.verify_backup(&snapshot_path).await {
+            Ok(()) => fs::read(&snapshot_path).map_err(Into::into),
+            Err(e) => Err(e),
+        };
+        let _ = fs::remove_file(&snapshot_path);
+        result
+    }
 
-        // Verify backup integrity
-        self.verify_backup(&backup_path).await?;
+    /// Encode a snapshot with the current settings and write it atomically
+    fn write_archive(&self, prefix: &str, snapshot: &[u8]) -> AppResult<PathBuf> {
+        let config = self.config();
+        let key = if config.encrypt {
+            Some(BackupKey::Local(backup_archive::load_or_create_local_key()?))
+        } else {
+            None
+        };
+        let archive = backup_archive::encode(snapshot, config.compress, key.as_ref())?;
+
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let backup_path = self
+            .backup_dir
+            .join(format!("{}{}.{}", prefix, timestamp, ARCHIVE_EXTENSION));
+        let tmp_path = backup_path.with_extension("tmp");
+
+        fs::write(&tmp_path, &archive)?;
+        fs::rename(&tmp_path, &backup_path)?;
 
-        info!(
-            "Database backup created successfully: {}",
-            backup_path.display()
-        );
         Ok(backup_path)
     }
 
+    /// Archive header of a backup file; None for legacy plain copies
+    fn read_header(&self, backup_path: &Path) -> Option<backup_archive::ArchiveHeader> {
+        let mut head = Vec::with_capacity(128);
+        fs::File::open(backup_path)
+            .ok()?
+            .take(128)
+            .read_to_end(&mut head)
+            .ok()?;
+        backup_archive::read_header(&head).ok()
+    }
+
+    /// Snapshot hash recorded in an archive header, if the file is an archive
+    fn read_content_hash(&self, backup_path: &Path) -> Option<[u8; 32]> {
+        self.read_header(backup_path)
+            .map(|header| header.content_hash)
+    }
+
+    /// Read a backup file (archive or legacy copy) back into a SQLite snapshot
+    fn read_snapshot(&self, backup_path: &Path, passphrase: Option<&str>) -> AppResult<Vec<u8>> {
+        let data = fs::read(backup_path)?;
+        if backup_archive::is_raw_sqlite(&data) {
+            return Ok(data);
+        }
+
+        let header = backup_archive::read_header(&data)?;
+        let key = if header.needs_passphrase() {
+            let passphrase = passphrase.ok_or_else(|| {
+                crate::error::AppError::ValidationError(
+                    "This backup is protected with a passphrase".to_string(),
+                )
+            })?;
+            Some(BackupKey::Passphrase(passphrase.to_string()))
+        } else if header.is_encrypted() {
+            Some(BackupKey::Local(backup_archive::load_or_create_local_key()?))
+        } else {
+            None
+        };
+
+        backup_archive::decode(&data, key.as_ref())
+    }
+
+    /// Write a snapshot next to the database and check it opens cleanly
+    async fn stage_snapshot(&self, snapshot: &[u8]) -> AppResult<PathBuf> {
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S_%3f");
+        let staged_path = self
+            .app_data_dir
+            .join(format!(".restore_{}.tmp", timestamp));
+        fs::write(&staged_path, snapshot)?;
+
+        if let Err(e) = self.verify_backup(&staged_path).await {
+            let _ = fs::remove_file(&staged_path);
+            return Err(e);
+        }
+
+        Ok(staged_path)
+    }
+
     /// Verify backup integrity
     async fn verify_backup(&self, backup_path: &Path) -> AppResult<()> {
-        let backup_url = format!("sqlite:{}", backup_path.display());
+        let backup_url = format!("sqlite:{}?mode=ro", backup_path.display());
 
         // Create a temporary connection to the backup
         let backup_pool = SqlitePool::connect(&backup_url).await.map_err(|e| {
@@ -199,26 +347,39 @@ impl BackupService {
                     "Backup integrity check failed: {}",
                     e
                 ))
-            })?;
+            });
+
+        let has_sessions = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'sessions'",
+        )
+        .fetch_one(&backup_pool)
+        .await
+        .unwrap_or(0);
 
         backup_pool.close().await;
 
+        let result = result?;
         if result != "ok" {
             return Err(crate::error::AppError::DatabaseError(format!(
                 "Backup integrity check failed: {}",
                 result
             )));
         }
+        if has_sessions == 0 {
+            return Err(crate::error::AppError::DatabaseError(
+                "Backup does not contain a PlanToCode database".to_string(),
+            ));
+        }
 
-        info!("Backup integrity verified successfully");
         Ok(())
     }
 
     /// Clean up old backups beyond the retention limit
     async fn cleanup_old_backups(&self) -> AppResult<()> {
         let mut backup_files = self.get_all_backups()?;
+        let max_backup_count = self.config().max_backup_count;
 
-        if backup_files.len() <= self.config.max_backup_count {
+        if backup_files.len() <= max_backup_count {
             return Ok(()); // Nothing to clean up
         }
 
@@ -234,7 +395,7 @@ impl BackupService {
         });
 
         // Remove old backups beyond the retention limit
-        let files_to_remove = backup_files.into_iter().skip(self.config.max_backup_count);
+        let files_to_remove = backup_files.into_iter().skip(max_backup_count);
 
         for file_path in files_to_remove {
             match fs::remove_file(&file_path) {
@@ -258,13 +419,18 @@ impl BackupService {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_file()
+            let is_backup = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|s| s.starts_with(BACKUP_PREFIX))
+                .unwrap_or(false)
                 && path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .map(|s| s.starts_with("appdata_backup_") && s.ends_with(".db"))
-                    .unwrap_or(false)
-            {
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| e == ARCHIVE_EXTENSION || e == LEGACY_EXTENSION)
+                    .unwrap_or(false);
+
+            if path.is_file() && is_backup {
                 backups.push(path);
             }
         }
@@ -355,6 +521,7 @@ impl BackupService {
 
             // Verify backup integrity
             let is_valid = self.verify_backup_file(&backup_path).await.is_ok();
+            let header = self.read_header(&backup_path);
 
             backup_info.push(BackupInfo {
                 filename,
@@ -362,6 +529,8 @@ impl BackupService {
                 size_bytes: metadata.len(),
                 created_timestamp,
                 is_valid,
+                is_compressed: header.as_ref().map(|h| h.is_compressed()).unwrap_or(false),
+                is_encrypted: header.as_ref().map(|h| h.is_encrypted()).unwrap_or(false),
             });
         }
 
@@ -371,42 +540,29 @@ impl BackupService {
         Ok(backup_info)
     }
 
-    /// Verify a backup file without opening the main database
+    /// Verify a backup file without opening the main database.
+    /// Archives are checked against their recorded snapshot hash; legacy
+    /// copies are opened and integrity-checked.
     async fn verify_backup_file(&self, backup_path: &Path) -> AppResult<()> {
-        let backup_url = format!("sqlite:{}", backup_path.display());
-
-        // Create a temporary connection to the backup
-        let backup_pool = SqlitePool::connect(&backup_url).await.map_err(|e| {
-            crate::error::AppError::DatabaseError(format!(
-                "Failed to connect to backup for verification: {}",
-                e
-            ))
-        })?;
-
-        // Run integrity check on backup
-        let result = sqlx::query_scalar::<_, String>("PRAGMA quick_check")
-            .fetch_one(&backup_pool)
-            .await
-            .map_err(|e| {
-                crate::error::AppError::DatabaseError(format!(
-                    "Backup integrity check failed: {}",
-                    e
-                ))
-            })?;
-
-        backup_pool.close().await;
-
-        if result != "ok" {
-            return Err(crate::error::AppError::DatabaseError(format!(
-                "Backup integrity check failed: {}",
-                result
-            )));
+        let is_archive = backup_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e == ARCHIVE_EXTENSION)
+            .unwrap_or(false);
+
+        if is_archive {
+            self.read_snapshot(backup_path, None).map(|_| ())
+        } else {
+            self.verify_backup(backup_path).await
         }
-
-        Ok(())
     }
 
-    /// Restore database from a backup file
+    /// Restore database from a backup file.
+    ///
+    /// The backup is decoded and integrity-checked in a staging file first; the
+    /// live database is only replaced once that succeeds. The job queue is
+    /// stopped and the connection pool closed before the swap, so the
+    /// application must be restarted afterwards.
     pub async fn restore_from_backup(&self, backup_path: &Path) -> AppResult<()> {
         if !backup_path.exists() {
             return Err(crate::error::AppError::DatabaseError(
@@ -414,40 +570,141 @@ impl BackupService {
             ));
         }
 
-        // Verify the backup before restoring
-        self.verify_backup_file(backup_path).await?;
+        let snapshot = self.read_snapshot(backup_path, None)?;
+        let staged_path = self.stage_snapshot(&snapshot).await?;
 
         let main_db_path = self.app_data_dir.join(DB_FILENAME);
 
-        // Create a backup of the current database before restoring
+        // Keep the current database before replacing it
         if main_db_path.exists() {
-            let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-            let pre_restore_backup = self
-                .backup_dir
-                .join(format!("pre_restore_backup_{}.db", timestamp));
-            fs::copy(&main_db_path, &pre_restore_backup)?;
-            info!(
-                "Created pre-restore backup: {}",
-                pre_restore_backup.display()
-            );
+            match self.snapshot_database().await {
+                Ok(current) => {
+                    let pre_restore_backup = self.write_archive(PRE_RESTORE_PREFIX, &current)?;
+                    info!(
+                        "Created pre-restore backup: {}",
+                        pre_restore_backup.display()
+                    );
+                }
+                Err(e) => {
+                    // The live database may be the reason for restoring
+                    warn!("Could not snapshot current database before restore: {}", e);
+                    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+                    let pre_restore_backup = self.backup_dir.join(format!(
+                        "{}{}.{}",
+                        PRE_RESTORE_PREFIX, timestamp, LEGACY_EXTENSION
+                    ));
+                    fs::copy(&main_db_path, &pre_restore_backup)?;
+                }
+            }
         }
 
-        // Perform WAL checkpoint on current database before replacement
-        match sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
-            .execute(&self.db_pool)
-            .await
-        {
-            Ok(_) => info!("WAL checkpoint completed before restore"),
-            Err(e) => warn!("WAL checkpoint failed before restore: {}", e),
+        // Open connections would keep writing to the replaced file through its
+        // WAL, which SQLite finds by path and would replay onto the restored copy
+        if let Some(queue) = crate::jobs::queue::JOB_QUEUE.get() {
+            queue.shutdown().await;
+        }
+        self.db_pool.close().await;
+        for suffix in ["-wal", "-shm"] {
+            let mut sidecar = main_db_path.clone().into_os_string();
+            sidecar.push(suffix);
+            match fs::remove_file(&sidecar) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    let _ = fs::remove_file(&staged_path);
+                    return Err(e.into());
+                }
+            }
         }
 
-        // Copy backup to replace main database
-        fs::copy(backup_path, &main_db_path)?;
+        // Swap the verified copy in; rename is atomic on the same filesystem
+        if let Err(e) = fs::rename(&staged_path, &main_db_path) {
+            warn!(
+                "Atomic swap failed ({}), copying restored database instead",
+                e
+            );
+            let copied = fs::copy(&staged_path, &main_db_path);
+            let _ = fs::remove_file(&staged_path);
+            copied?;
+        }
 
         info!("Database restored from backup: {}", backup_path.display());
         Ok(())
     }
 
+    /// Write a portable copy of a backup (or of the current database when no
+    /// backup is named) for use on another machine. Archives encrypted with the
+    /// local keyring key are re-encrypted with `passphrase`, or exported
+    /// unencrypted when none is given. An existing file at the destination is
+    /// never replaced.
+    pub async fn export_backup(
+        &self,
+        backup_path: Option<&Path>,
+        destination: &Path,
+        passphrase: Option<&str>,
+    ) -> AppResult<PathBuf> {
+        let snapshot = match backup_path {
+            Some(path) => self.read_snapshot(path, None)?,
+            None => self.snapshot_database().await?,
+        };
+
+        let key = passphrase
+            .filter(|p| !p.is_empty())
+            .map(|p| BackupKey::Passphrase(p.to_string()));
+        let archive = backup_archive::encode(&snapshot, true, key.as_ref())?;
+
+        let target = if destination.is_dir() {
+            let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+            destination.join(format!(
+                "plantocode_backup_{}.{}",
+                timestamp, ARCHIVE_EXTENSION
+            ))
+        } else {
+            destination.to_path_buf()
+        };
+        // Never overwrite an existing file with the export
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&target)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::AlreadyExists => crate::error::AppError::ValidationError(
+                    format!("{} already exists", target.display()),
+                ),
+                _ => e.into(),
+            })?;
+        file.write_all(&archive)?;
+
+        info!("Exported backup to {}", target.display());
+        Ok(target)
+    }
+
+    /// Bring a backup from another machine into the local backup directory,
+    /// re-encoded with this machine's settings. It can then be restored like
+    /// any other backup.
+    pub async fn import_backup(
+        &self,
+        source: &Path,
+        passphrase: Option<&str>,
+    ) -> AppResult<PathBuf> {
+        let snapshot = self.read_snapshot(source, passphrase)?;
+
+        let staged_path = self.stage_snapshot(&snapshot).await?;
+        let _ = fs::remove_file(&staged_path);
+
+        if !self.backup_dir.exists() {
+            fs::create_dir_all(&self.backup_dir)?;
+        }
+        let backup_path = self.write_archive(BACKUP_PREFIX, &snapshot)?;
+
+        info!(
+            "Imported backup {} as {}",
+            source.display(),
+            backup_path.display()
+        );
+        Ok(backup_path)
+    }
+
     /// Find and restore from the latest valid backup automatically
     pub async fn auto_restore_latest_backup(&self) -> AppResult<Option<String>> {
         let backups = self.get_backup_list().await?;
@@ -484,4 +741,86 @@ pub struct BackupInfo {
     pub size_bytes: u64,
     pub created_timestamp: u64,
     pub is_valid: bool,
+    pub is_compressed: bool,
+    pub is_encrypted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+
+    async fn open_pool(db_path: &Path) -> SqlitePool {
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await
+            .unwrap()
+    }
+
+    async fn session_names(pool: &SqlitePool) -> Vec<String> {
+        sqlx::query_scalar("SELECT name FROM sessions ORDER BY name")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_restore_replaces_database_and_discards_its_wal() {
+        let dir = std::env::temp_dir().join(format!("backup-service-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join(DB_FILENAME);
+        let pool = open_pool(&db_path).await;
+        sqlx::query("CREATE TABLE sessions (name TEXT NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO sessions (name) VALUES ('kept')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = BackupService::new(dir.clone(), pool.clone(), BackupConfig::default());
+        let backup = service.create_manual_backup().await.unwrap();
+
+        // Left in the WAL, which must not reach the restored file
+        sqlx::query("INSERT INTO sessions (name) VALUES ('undone')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        service.restore_from_backup(&backup).await.unwrap();
+        assert!(pool.is_closed());
+        assert!(!dir.join(format!("{}-wal", DB_FILENAME)).exists());
+
+        let reopened = open_pool(&db_path).await;
+        assert_eq!(session_names(&reopened).await, vec!["kept".to_string()]);
+        reopened.close().await;
+
+        let pre_restore = fs::read_dir(&service.backup_dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .find(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(PRE_RESTORE_PREFIX)
+            })
+            .unwrap();
+        let snapshot = service.read_snapshot(&pre_restore.path(), None).unwrap();
+        let snapshot_path = dir.join("pre_restore.db");
+        fs::write(&snapshot_path, snapshot).unwrap();
+        let previous = open_pool(&snapshot_path).await;
+        assert_eq!(
+            session_names(&previous).await,
+            vec!["kept".to_string(), "undone".to_string()]
+        );
+        previous.close().await;
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
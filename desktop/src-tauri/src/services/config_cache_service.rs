@@ -359,6 +359,8 @@ pub fn validate_all_task_types_have_configs(
     let required_task_types = [
         TaskType::ImplementationPlan,
         TaskType::ImplementationPlanMerge,
+        TaskType::ImplementationPlanRefinement,
+        TaskType::PlanTestGeneration,
         TaskType::VoiceTranscription,
         TaskType::TextImprovement,
         TaskType::TaskRefinement,
@@ -432,6 +432,34 @@ fn delete_session(app_handle: &tauri::AppHandle) {
     }
 }
 
+/// Hand remote task description operations to the history sequencer, which
+/// merges them into the session's replicated text off the receive loop
+fn enqueue_remote_task_ops(app_handle: &AppHandle, payload: &Value) {
+    let Some(session_id) = payload.get("sessionId").and_then(|v| v.as_str()).map(String::from) else {
+        return;
+    };
+    let ops: Vec<crate::utils::text_crdt::TextOp> = match payload.get("ops").cloned().map(serde_json::from_value) {
+        Some(Ok(ops)) => ops,
+        Some(Err(e)) => {
+            warn!("Ignoring malformed task ops for session {}: {}", session_id, e);
+            return;
+        }
+        None => return,
+    };
+    let Some(sequencer) = app_handle
+        .try_state::<Arc<crate::services::history_state_sequencer::HistoryStateSequencer>>()
+        .map(|s| s.inner().clone())
+    else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        if let Err(e) = sequencer.enqueue_apply_task_ops(session_id.clone(), ops).await {
+            warn!("Failed to apply remote task ops for session {}: {}", session_id, e);
+        }
+    });
+}
+
 // ==================================================
 
 /// Global bound session IDs - shared across all DeviceLinkClient instances
@@ -1171,6 +1199,9 @@ impl DeviceLinkClient {
                                         error!("Failed to emit project-directory-updated device-link-event: {}", e);
                                     }
                                 } else if event_type == "history-state-changed" {
+                                    if inner_payload.get("kind").and_then(|v| v.as_str()) == Some("task-ops") {
+                                        enqueue_remote_task_ops(&app_handle, &inner_payload);
+                                    }
                                     if let Err(e) = app_handle.emit("device-link-event", json!({
                                         "type": "history-state-changed",
                                         "payload": inner_payload,
@@ -1,6 +1,7 @@
 use serde_json::Value;
 use sqlx::SqlitePool;
 use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 use tauri::Manager;
 
@@ -13,7 +14,7 @@ use crate::services::{HistoryStateSequencer, SessionCache};
 ///   items in excluded are never re-included by backend jobs.
 /// - Concurrency is hardened in repository with BEGIN IMMEDIATE.
 /// - Persists into sessions.included_files / sessions.force_excluded_files (newline-delimited TEXT).
-/// - Only for file_relevance_assessment and extended_path_finder jobs.
+/// - Only for file_relevance_assessment, extended_path_finder and impact_analysis jobs.
 #[derive(Debug)]
 pub struct AutoApplyOutcome {
     pub session_id: String,
@@ -123,7 +124,7 @@ pub async fn auto_apply_files_for_job(
     // Limit to supported types
     let is_supported = matches!(
         task_type,
-        "file_relevance_assessment" | "extended_path_finder"
+        "file_relevance_assessment" | "extended_path_finder" | "impact_analysis"
     );
     if !is_supported {
         return Ok(None);
@@ -175,6 +176,15 @@ pub async fn auto_apply_files_for_job(
     let mut included_set: HashSet<String> = current_included.iter().cloned().collect();
     let mut actually_applied = Vec::new();
 
+    // Files excluded by the project's .plantocode file are never auto-selected
+    let project_directory = session_repo
+        .get_session_by_id(session_id)
+        .await?
+        .map(|session| session.project_directory);
+    let project_config = project_directory
+        .as_deref()
+        .and_then(crate::utils::project_config::active_project_config);
+
     for file in files_to_add {
         let trimmed = file.trim();
         if trimmed.is_empty() {
@@ -183,6 +193,13 @@ pub async fn auto_apply_files_for_job(
         if excluded_set.contains(trimmed) {
             continue;
         }
+        if let (Some(loaded), Some(project_directory)) = (&project_config, &project_directory) {
+            let path = Path::new(trimmed);
+            let relative = path.strip_prefix(project_directory).unwrap_or(path);
+            if loaded.config.is_file_excluded(relative) {
+                continue;
+            }
+        }
         if included_set.insert(trimmed.to_string()) {
             actually_applied.push(trimmed.to_string());
         }
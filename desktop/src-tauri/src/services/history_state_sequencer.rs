@@ -1,9 +1,11 @@
+use crate::auth::device_id_manager;
 use crate::db_utils::session_repository::{
-    SessionRepository, TaskHistoryState, FileHistoryState
+    SessionRepository, TaskHistoryEntry, TaskHistoryState, FileHistoryState
 };
 use crate::error::AppError;
 use crate::events::session_events;
 use crate::utils::hash_utils::sha256_hash;
+use crate::utils::text_crdt::{self, TextCrdt, TextOp};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -36,6 +38,11 @@ enum HistoryOp {
         remote_state: FileHistoryState,
         respond_to: oneshot::Sender<Result<FileHistoryState, String>>,
     },
+    ApplyTaskOps {
+        session_id: String,
+        ops: Vec<TextOp>,
+        respond_to: oneshot::Sender<Result<String, String>>,
+    },
 }
 
 pub struct HistoryStateSequencer {
@@ -149,6 +156,31 @@ impl HistoryStateSequencer {
             .map_err(|_| "Channel closed".to_string())?
     }
 
+    /// Merge character-level task description edits from another device.
+    /// Returns the merged description.
+    pub async fn enqueue_apply_task_ops(
+        &self,
+        session_id: String,
+        ops: Vec<TextOp>,
+    ) -> Result<String, String> {
+        let (tx, rx) = oneshot::channel();
+        self.enqueue_op(
+            session_id.clone(),
+            HistoryOp::ApplyTaskOps {
+                session_id,
+                ops,
+                respond_to: tx,
+            },
+        ).await;
+
+        tokio::time::timeout(
+            std::time::Duration::from_secs(HISTORY_OP_TIMEOUT_SECS),
+            rx
+        ).await
+            .map_err(|_| "Task ops timeout".to_string())?
+            .map_err(|_| "Channel closed".to_string())?
+    }
+
     async fn enqueue_op(&self, session_id: String, op: HistoryOp) {
         let mut queues = self.queues.lock().await;
         let tx = queues.entry(session_id.clone()).or_insert_with(|| {
@@ -165,9 +197,15 @@ impl HistoryStateSequencer {
         let queues = self.queues.clone();
 
         tokio::spawn(async move {
+            // Replicated task description; loaded on the first task operation
+            let mut task_doc: Option<TextCrdt> = None;
+
             while let Some(op) = rx.recv().await {
                 match op {
                     HistoryOp::SyncTask { session_id, state, expected_version, respond_to } => {
+                        ensure_task_doc(&app_handle, &repository, &session_id, &mut task_doc).await;
+                        let stale_edit = stale_snapshot_edit(&repository, &session_id, task_doc.as_ref(), &state).await;
+
                         let recomputed_checksum = compute_task_history_checksum(&state.entries, state.current_index, state.version);
                         if recomputed_checksum != state.checksum && !state.checksum.is_empty() {
                             eprintln!(
@@ -226,6 +264,11 @@ impl HistoryStateSequencer {
                             }
                         };
 
+                        let response = match response {
+                            Ok(new_state) => Ok(record_local_task_edit(&app_handle, &repository, &session_id, &mut task_doc, stale_edit.as_ref(), new_state).await),
+                            Err(e) => Err(e),
+                        };
+
                         let _ = respond_to.send(response);
                     }
                     HistoryOp::SyncFiles { session_id, state, expected_version, respond_to } => {
@@ -322,7 +365,13 @@ impl HistoryStateSequencer {
                         let _ = respond_to.send(response);
                     }
                     HistoryOp::MergeTask { session_id, remote_state, respond_to } => {
+                        ensure_task_doc(&app_handle, &repository, &session_id, &mut task_doc).await;
+
                         let local = repository.get_task_history_state(&session_id).await;
+                        let stale_edit = match (&local, task_doc.as_ref()) {
+                            (Ok(local_state), Some(doc)) => snapshot_edit(local_state, &remote_state, &doc.text()),
+                            _ => None,
+                        };
                         let response = match local {
                             Ok(local_state) => {
                                 let merged = repository.merge_task_history_states(&local_state, &remote_state);
@@ -349,6 +398,11 @@ impl HistoryStateSequencer {
                             );
                         }
 
+                        let response = match response {
+                            Ok(new_state) => Ok(record_local_task_edit(&app_handle, &repository, &session_id, &mut task_doc, stale_edit.as_ref(), new_state).await),
+                            Err(e) => Err(e),
+                        };
+
                         let _ = respond_to.send(response);
                     }
                     HistoryOp::MergeFiles { session_id, remote_state, respond_to } => {
@@ -395,6 +449,18 @@ impl HistoryStateSequencer {
                             );
                         }
 
+                        let _ = respond_to.send(response);
+                    }
+                    HistoryOp::ApplyTaskOps { session_id, ops, respond_to } => {
+                        ensure_task_doc(&app_handle, &repository, &session_id, &mut task_doc).await;
+
+                        let response = match task_doc.as_mut() {
+                            Some(doc) => apply_remote_task_ops(&app_handle, &repository, &session_id, doc, ops)
+                                .await
+                                .map_err(|e| e.to_string()),
+                            None => Err("Task description document unavailable".to_string()),
+                        };
+
                         let _ = respond_to.send(response);
                     }
                 }
@@ -405,6 +471,227 @@ impl HistoryStateSequencer {
     }
 }
 
+/// Load the session's replicated task description, seeding it from the current
+/// description when no operations have been stored yet. Must run before a task
+/// write so the seed reflects the text the incoming edit was based on.
+async fn ensure_task_doc(
+    app_handle: &AppHandle,
+    repository: &SessionRepository,
+    session_id: &str,
+    task_doc: &mut Option<TextCrdt>,
+) {
+    if task_doc.is_some() {
+        return;
+    }
+
+    match load_task_doc(app_handle, repository, session_id).await {
+        Ok(doc) => *task_doc = Some(doc),
+        Err(e) => eprintln!("[HistorySync] Failed to load task ops for session {}: {}", session_id, e),
+    }
+}
+
+async fn load_task_doc(
+    app_handle: &AppHandle,
+    repository: &SessionRepository,
+    session_id: &str,
+) -> Result<TextCrdt, AppError> {
+    let replica = device_id_manager::get_or_create(app_handle)?;
+    let ops = repository.get_task_description_ops(session_id).await?;
+    if !ops.is_empty() {
+        return Ok(TextCrdt::from_ops(replica, ops));
+    }
+
+    let state = repository.get_task_history_state(session_id).await?;
+    let current = match state.entries.get(state.current_index as usize) {
+        Some(entry) => entry.description.clone(),
+        None => repository
+            .get_session_by_id(session_id)
+            .await?
+            .and_then(|session| session.task_description)
+            .unwrap_or_default(),
+    };
+
+    // Seed ops are deterministic, so devices that start from the same text agree
+    let seed = TextCrdt::snapshot_ops(&current);
+    repository.append_task_description_ops(session_id, &seed).await?;
+    Ok(TextCrdt::from_ops(replica, seed))
+}
+
+/// A task snapshot edited from a description that is no longer current
+#[derive(Debug, PartialEq)]
+struct SnapshotEdit {
+    base: String,
+    edited: String,
+}
+
+/// Read before an incoming snapshot is written, while the history still shows
+/// what this device had
+async fn stale_snapshot_edit(
+    repository: &SessionRepository,
+    session_id: &str,
+    task_doc: Option<&TextCrdt>,
+    snapshot: &TaskHistoryState,
+) -> Option<SnapshotEdit> {
+    let doc = task_doc?;
+    match repository.get_task_history_state(session_id).await {
+        Ok(local_state) => snapshot_edit(&local_state, snapshot, &doc.text()),
+        Err(e) => {
+            eprintln!("[HistorySync] Failed to get local task state for session {}: {}", session_id, e);
+            None
+        }
+    }
+}
+
+/// The snapshot's new description and the entry it was edited from: the newest
+/// entry before its head that this device also has. None when the head is
+/// itself a known entry (undo/redo picks it as is), when the snapshot was made
+/// from the current text, or when the histories share nothing.
+fn snapshot_edit(local: &TaskHistoryState, snapshot: &TaskHistoryState, current: &str) -> Option<SnapshotEdit> {
+    let is_known = |entry: &TaskHistoryEntry| {
+        local
+            .entries
+            .iter()
+            .any(|e| e.created_at == entry.created_at && e.description == entry.description)
+    };
+
+    let head = usize::try_from(snapshot.current_index).ok()?;
+    let edited = snapshot.entries.get(head)?;
+    if is_known(edited) {
+        return None;
+    }
+    let base = snapshot.entries[..head].iter().rev().find(|entry| is_known(entry))?;
+    if base.description == current {
+        return None;
+    }
+    Some(SnapshotEdit {
+        base: base.description.clone(),
+        edited: edited.description.clone(),
+    })
+}
+
+/// Turn a committed description into CRDT operations and broadcast them.
+///
+/// A stale snapshot edit is merged three ways with the text it replaced, so
+/// concurrent edits it never saw are kept; the merged description is recorded
+/// as a new entry and the resulting state returned.
+async fn record_local_task_edit(
+    app_handle: &AppHandle,
+    repository: &SessionRepository,
+    session_id: &str,
+    task_doc: &mut Option<TextCrdt>,
+    stale_edit: Option<&SnapshotEdit>,
+    state: TaskHistoryState,
+) -> TaskHistoryState {
+    let (Some(doc), Some(entry)) = (task_doc.as_mut(), state.entries.get(state.current_index as usize)) else {
+        return state;
+    };
+
+    // The document still holds the description from before this write
+    let merged = match stale_edit {
+        Some(edit) => text_crdt::merge_three_way(&edit.base, &doc.text(), &edit.edited),
+        None => entry.description.clone(),
+    };
+    let ops = doc.set_text(&merged);
+    if !ops.is_empty() {
+        if let Err(e) = repository.append_task_description_ops(session_id, &ops).await {
+            eprintln!("[HistorySync] Failed to store task ops for session {}: {}", session_id, e);
+        }
+        session_events::emit_task_description_ops(app_handle, session_id, doc.replica(), &ops);
+    }
+
+    if merged == entry.description {
+        return state;
+    }
+    match persist_merged_task_description(app_handle, repository, session_id, &merged, doc.replica()).await {
+        Ok(persisted) => {
+            // The device that sent the snapshot has to see the merge too
+            session_events::emit_history_state_changed(app_handle, session_id, "task", &persisted);
+            persisted
+        }
+        Err(e) => {
+            eprintln!("[HistorySync] Failed to record merged task for session {}: {}", session_id, e);
+            state
+        }
+    }
+}
+
+/// Integrate remote operations and, when the text changed, record the merged
+/// description as a new history entry so the UI and snapshot history follow.
+async fn apply_remote_task_ops(
+    app_handle: &AppHandle,
+    repository: &SessionRepository,
+    session_id: &str,
+    doc: &mut TextCrdt,
+    ops: Vec<TextOp>,
+) -> Result<String, AppError> {
+    let before = doc.text();
+    for op in &ops {
+        doc.apply(op.clone());
+    }
+    // Parked operations are stored too so they survive a restart
+    repository.append_task_description_ops(session_id, &ops).await?;
+
+    let merged = doc.text();
+    if merged == before {
+        return Ok(merged);
+    }
+
+    let persisted = persist_merged_task_description(app_handle, repository, session_id, &merged, doc.replica()).await?;
+    // The ops already reached the other devices; only refresh this UI
+    session_events::emit_history_state_changed_locally(app_handle, session_id, "task", &persisted);
+
+    Ok(merged)
+}
+
+/// Append a merged description to the task history and update the cached session
+async fn persist_merged_task_description(
+    app_handle: &AppHandle,
+    repository: &SessionRepository,
+    session_id: &str,
+    description: &str,
+    device_id: &str,
+) -> Result<TaskHistoryState, AppError> {
+    let local_state = repository.get_task_history_state(session_id).await?;
+    let next_state = with_appended_task_entry(&local_state, description, device_id);
+    let persisted = repository
+        .sync_task_history_state(session_id, &next_state, local_state.version)
+        .await?;
+
+    if let Some(cache) = app_handle.try_state::<std::sync::Arc<crate::services::session_cache::SessionCache>>() {
+        let _ = cache.update_task_description_canonical(app_handle, session_id, description).await;
+        let _ = session_events::emit_session_task_updated(app_handle, session_id, description);
+    }
+
+    Ok(persisted)
+}
+
+fn with_appended_task_entry(state: &TaskHistoryState, description: &str, device_id: &str) -> TaskHistoryState {
+    // A new entry discards the redo tail, like a local edit does
+    let keep = if state.entries.is_empty() { 0 } else { state.current_index as usize + 1 };
+    let mut entries: Vec<TaskHistoryEntry> = state.entries.iter().take(keep).cloned().collect();
+    let sequence_number = entries.iter().map(|e| e.sequence_number).max().unwrap_or(-1) + 1;
+
+    entries.push(TaskHistoryEntry {
+        description: description.to_string(),
+        created_at: crate::utils::date_utils::get_timestamp(),
+        device_id: Some(device_id.to_lowercase()),
+        op_type: Some("merge".to_string()),
+        sequence_number,
+        version: 1,
+    });
+
+    let entries: Vec<TaskHistoryEntry> = entries.into_iter().rev().take(200).rev().collect();
+    let current_index = entries.len() as i64 - 1;
+    let checksum = compute_task_history_checksum(&entries, current_index, state.version);
+
+    TaskHistoryState {
+        entries,
+        current_index,
+        version: state.version,
+        checksum,
+    }
+}
+
 fn compute_task_history_checksum(entries: &[crate::db_utils::session_repository::TaskHistoryEntry], current_index: i64, version: i64) -> String {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -488,3 +775,55 @@ fn compute_file_history_checksum(entries: &[crate::db_utils::session_repository:
     let json = serde_json::to_string(&data).unwrap_or_default();
     sha256_hash(&json)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(description: &str, created_at: i64) -> TaskHistoryEntry {
+        TaskHistoryEntry {
+            description: description.to_string(),
+            created_at,
+            device_id: None,
+            op_type: None,
+            sequence_number: created_at,
+            version: 1,
+        }
+    }
+
+    fn state(entries: Vec<TaskHistoryEntry>) -> TaskHistoryState {
+        let current_index = entries.len() as i64 - 1;
+        TaskHistoryState { entries, current_index, version: 1, checksum: String::new() }
+    }
+
+    #[test]
+    fn stale_snapshot_is_merged_against_the_entry_it_was_edited_from() {
+        let local = state(vec![entry("Fix the bug", 1), entry("Fix the login bug", 2)]);
+        let phone = state(vec![entry("Fix the bug", 1), entry("Fix the bug quickly", 3)]);
+
+        let edit = snapshot_edit(&local, &phone, "Fix the login bug").unwrap();
+        assert_eq!(edit, SnapshotEdit { base: "Fix the bug".to_string(), edited: "Fix the bug quickly".to_string() });
+        assert_eq!(
+            text_crdt::merge_three_way(&edit.base, "Fix the login bug", &edit.edited),
+            "Fix the login bug quickly"
+        );
+    }
+
+    #[test]
+    fn current_or_known_snapshots_are_taken_as_is() {
+        let local = state(vec![entry("one", 1), entry("two", 2)]);
+
+        // Edited from the current text
+        let phone = state(vec![entry("one", 1), entry("two", 2), entry("three", 3)]);
+        assert_eq!(snapshot_edit(&local, &phone, "two"), None);
+
+        // Undo back to a known entry
+        let mut undone = local.clone();
+        undone.current_index = 0;
+        assert_eq!(snapshot_edit(&local, &undone, "two"), None);
+
+        // Nothing in common
+        let unrelated = state(vec![entry("other", 5), entry("other again", 6)]);
+        assert_eq!(snapshot_edit(&local, &unrelated, "two"), None);
+    }
+}
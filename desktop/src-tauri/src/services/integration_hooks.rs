@@ -0,0 +1,344 @@
+//! Delivers application events to user-configured webhooks and shell commands.
+//!
+//! A single dispatcher task subscribes to the [`EventBus`] and, for every event, looks up the
+//! enabled hooks subscribed to its type. Each hook is delivered independently with exponential
+//! backoff between attempts, and every attempt is written to the delivery log.
+
+use crate::db_utils::IntegrationHookRepository;
+use crate::db_utils::integration_hook_repository::{DeliveryAttempt, HookTarget, IntegrationHook};
+use crate::error::{AppError, AppResult};
+use crate::events::{Event, EventBus};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast::error::RecvError;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+const SHELL_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const ERROR_OUTPUT_LIMIT: usize = 2000;
+
+static HOOK_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .expect("Failed to create hook HTTP client")
+});
+
+/// Result of a single delivery attempt
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryOutcome {
+    pub success: bool,
+    pub response_status: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+/// Subscribe to the event bus and deliver matching events to hooks until the bus closes
+pub fn start_hook_dispatcher(app_handle: &AppHandle, repo: Arc<IntegrationHookRepository>) {
+    let mut receiver = app_handle.state::<EventBus>().subscribe();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => dispatch_event(&repo, event).await,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "Integration hook dispatcher lagged, {} events were not delivered",
+                        skipped
+                    );
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+        info!("Integration hook dispatcher stopped");
+    });
+}
+
+async fn dispatch_event(repo: &Arc<IntegrationHookRepository>, event: Event) {
+    let kind = event.event.kind();
+    let hooks = match repo.hooks_for_event(kind).await {
+        Ok(hooks) => hooks,
+        Err(e) => {
+            warn!("Failed to load integration hooks for {}: {}", kind, e);
+            return;
+        }
+    };
+    if hooks.is_empty() {
+        return;
+    }
+
+    let body = event_body(&event);
+    for hook in hooks {
+        let repo = repo.clone();
+        let body = body.clone();
+        tauri::async_runtime::spawn(async move {
+            deliver_with_retry(&repo, &hook, kind.as_str(), &body).await;
+        });
+    }
+}
+
+/// JSON sent to hooks: the typed event plus a `text` line chat webhooks can display as-is
+fn event_body(event: &Event) -> Value {
+    let mut body = serde_json::to_value(event).unwrap_or_else(|_| json!({}));
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("text".to_string(), Value::String(event.event.summary()));
+    }
+    body
+}
+
+fn backoff_for_attempt(attempt: u32) -> Duration {
+    let secs = 2u64.saturating_pow(attempt.min(16));
+    Duration::from_secs(secs).min(MAX_BACKOFF)
+}
+
+async fn deliver_with_retry(
+    repo: &IntegrationHookRepository,
+    hook: &IntegrationHook,
+    event_type: &str,
+    body: &Value,
+) {
+    for attempt in 1..=hook.max_attempts {
+        let outcome = deliver_and_log(repo, hook, event_type, body, attempt).await;
+        if outcome.success {
+            debug!(
+                "Delivered {} to hook '{}' on attempt {}",
+                event_type, hook.name, attempt
+            );
+            return;
+        }
+        if attempt < hook.max_attempts {
+            tokio::time::sleep(backoff_for_attempt(attempt)).await;
+        }
+    }
+    warn!(
+        "Giving up delivering {} to hook '{}' after {} attempts",
+        event_type, hook.name, hook.max_attempts
+    );
+}
+
+async fn deliver_and_log(
+    repo: &IntegrationHookRepository,
+    hook: &IntegrationHook,
+    event_type: &str,
+    body: &Value,
+    attempt: u32,
+) -> DeliveryOutcome {
+    let outcome = deliver_once(&hook.target, event_type, body).await;
+    if let Err(e) = repo
+        .record_delivery(&DeliveryAttempt {
+            hook_id: &hook.id,
+            event_type,
+            payload: body,
+            attempt,
+            success: outcome.success,
+            response_status: outcome.response_status,
+            error: outcome.error.clone(),
+            duration_ms: outcome.duration_ms,
+        })
+        .await
+    {
+        warn!("Failed to log delivery for hook '{}': {}", hook.name, e);
+    }
+    outcome
+}
+
+async fn deliver_once(target: &HookTarget, event_type: &str, body: &Value) -> DeliveryOutcome {
+    let started = Instant::now();
+    let result = match target {
+        HookTarget::Webhook { url, headers } => post_webhook(url, headers, body).await,
+        HookTarget::Shell { command } => run_shell_hook(command, event_type, body).await,
+    };
+    let duration_ms = started.elapsed().as_millis() as i64;
+
+    match result {
+        Ok(status) => DeliveryOutcome {
+            success: true,
+            response_status: Some(status),
+            error: None,
+            duration_ms,
+        },
+        Err((status, error)) => DeliveryOutcome {
+            success: false,
+            response_status: status,
+            error: Some(error),
+            duration_ms,
+        },
+    }
+}
+
+type AttemptResult = Result<i64, (Option<i64>, String)>;
+
+async fn post_webhook(
+    url: &str,
+    headers: &std::collections::BTreeMap<String, String>,
+    body: &Value,
+) -> AttemptResult {
+    let mut request = HOOK_HTTP_CLIENT.post(url).json(body);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let response = request.send().await.map_err(|e| (None, e.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16() as i64)
+    } else {
+        let text = response.text().await.unwrap_or_default();
+        Err((
+            Some(status.as_u16() as i64),
+            truncate(&format!("HTTP {}: {}", status, text)),
+        ))
+    }
+}
+
+/// Run the command through the platform shell. The event JSON is written to stdin and the
+/// event type is exported as `PLANTOCODE_EVENT_TYPE`; a zero exit code counts as delivered.
+async fn run_shell_hook(command: &str, event_type: &str, body: &Value) -> AttemptResult {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+
+    let mut child = cmd
+        .env("PLANTOCODE_EVENT_TYPE", event_type)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| (None, format!("Failed to start command: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores stdin may close it early; that is not a delivery failure
+        let _ = stdin.write_all(body.to_string().as_bytes()).await;
+    }
+
+    let output = tokio::time::timeout(SHELL_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            (
+                None,
+                format!("Command timed out after {}s", SHELL_TIMEOUT.as_secs()),
+            )
+        })?
+        .map_err(|e| (None, format!("Failed to wait for command: {}", e)))?;
+
+    let code = output.status.code().map(|c| c as i64);
+    if output.status.success() {
+        Ok(code.unwrap_or(0))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err((
+            code,
+            truncate(&format!(
+                "Command exited with {}: {}",
+                output.status,
+                stderr.trim()
+            )),
+        ))
+    }
+}
+
+fn truncate(message: &str) -> String {
+    if message.len() <= ERROR_OUTPUT_LIMIT {
+        return message.to_string();
+    }
+    let mut end = ERROR_OUTPUT_LIMIT;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &message[..end])
+}
+
+/// Deliver a synthetic `hook.test` event once, without retries, and log the attempt
+pub async fn send_test_event(
+    repo: &IntegrationHookRepository,
+    hook_id: &str,
+) -> AppResult<DeliveryOutcome> {
+    let hook = repo.get_hook(hook_id).await?.ok_or_else(|| {
+        AppError::NotFoundError(format!("Integration hook {} not found", hook_id))
+    })?;
+
+    let body = json!({
+        "type": "hook.test",
+        "payload": { "hookId": hook.id, "name": hook.name },
+        "timestamp": chrono::Utc::now(),
+        "text": format!("Test event for hook '{}'", hook.name),
+    });
+    Ok(deliver_and_log(repo, &hook, "hook.test", &body, 1).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::AppEvent;
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        assert_eq!(backoff_for_attempt(1), Duration::from_secs(2));
+        assert_eq!(backoff_for_attempt(3), Duration::from_secs(8));
+        assert_eq!(backoff_for_attempt(9), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn body_carries_event_and_summary_text() {
+        let event = Event {
+            event: AppEvent::TerminalExited {
+                session_id: "s1".to_string(),
+                exit_code: 1,
+            },
+            timestamp: chrono::Utc::now(),
+        };
+
+        let body = event_body(&event);
+        assert_eq!(body["type"], "terminal.exited");
+        assert_eq!(body["payload"]["exitCode"], 1);
+        assert!(body["timestamp"].is_string());
+        assert_eq!(body["text"], "Terminal for session s1 exited with code 1");
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[tokio::test]
+    async fn shell_hook_receives_event_on_stdin() {
+        let body = json!({ "type": "plan.created" });
+
+        let ok = deliver_once(
+            &HookTarget::Shell {
+                command: r#"grep -q plan.created && test "$PLANTOCODE_EVENT_TYPE" = plan.created"#
+                    .to_string(),
+            },
+            "plan.created",
+            &body,
+        )
+        .await;
+        assert!(ok.success, "{:?}", ok.error);
+
+        let failed = deliver_once(
+            &HookTarget::Shell {
+                command: "echo boom >&2; exit 3".to_string(),
+            },
+            "plan.created",
+            &body,
+        )
+        .await;
+        assert!(!failed.success);
+        assert_eq!(failed.response_status, Some(3));
+        assert!(failed.error.unwrap().contains("boom"));
+    }
+}
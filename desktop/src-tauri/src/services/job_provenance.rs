@@ -0,0 +1,870 @@
+//! Explicit parent/child provenance between background jobs and "re-run from here":
+//! replaying a job with changed inputs and then every job derived from it, so the
+//! new results can be compared side by side with the originals.
+
+use crate::db_utils::background_job_repository::{JobProvenanceEdge, relations};
+use crate::db_utils::{BackgroundJobRepository, SessionRepository};
+use crate::error::{AppError, AppResult};
+use crate::jobs::job_payload_utils::convert_db_job_to_job;
+use crate::jobs::types::JobPayload;
+use crate::models::{BackgroundJob, JobStatus, TaskType};
+use crate::utils::path_utils::to_forward_slashes;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+/// Task types whose `files` output can become a plan's selected files
+const FILE_SELECTION_TASK_TYPES: [&str; 3] = [
+    "extended_path_finder",
+    "file_relevance_assessment",
+    "impact_analysis",
+];
+
+/// Findings shorter than this are too generic to attribute a plan to the research
+const MIN_FINDING_MATCH_LEN: usize = 40;
+
+const REPLAY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const REPLAY_STAGE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Inputs to change when re-running a job; unset fields keep the original values
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayOverrides {
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    /// Only applies to implementation plan jobs
+    pub task_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStarted {
+    pub replay_id: String,
+    pub original_job_id: String,
+    pub rerun_job_id: String,
+    /// Derived jobs that will be re-run, in order, once their inputs complete
+    pub scheduled_job_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayJobSummary {
+    pub job_id: String,
+    pub task_type: String,
+    pub status: String,
+    pub model_used: Option<String>,
+    pub tokens_sent: Option<i32>,
+    pub tokens_received: Option<i32>,
+    pub actual_cost: Option<f64>,
+    pub response: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayComparisonPair {
+    pub original: ReplayJobSummary,
+    pub rerun: ReplayJobSummary,
+    /// Unified diff from the original response to the re-run response once both exist
+    pub response_diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayComparison {
+    pub replay_id: String,
+    pub pairs: Vec<ReplayComparisonPair>,
+}
+
+impl From<BackgroundJob> for ReplayJobSummary {
+    fn from(job: BackgroundJob) -> Self {
+        ReplayJobSummary {
+            job_id: job.id,
+            task_type: job.task_type,
+            status: job.status,
+            model_used: job.model_used,
+            tokens_sent: job.tokens_sent,
+            tokens_received: job.tokens_received,
+            actual_cost: job.actual_cost,
+            response: job.response,
+            error_message: job.error_message,
+        }
+    }
+}
+
+/// Record the parents of a newly created job. Lineage is best effort and never
+/// fails job creation.
+pub async fn record_creation_provenance(
+    repo: &BackgroundJobRepository,
+    job_id: &str,
+    session_id: &str,
+    payload: &JobPayload,
+    workflow_id: Option<&str>,
+    additional_params: Option<&Value>,
+) {
+    if let Err(e) = try_record_creation_provenance(
+        repo,
+        job_id,
+        session_id,
+        payload,
+        workflow_id,
+        additional_params,
+    )
+    .await
+    {
+        warn!("Failed to record provenance for job {}: {}", job_id, e);
+    }
+}
+
+async fn try_record_creation_provenance(
+    repo: &BackgroundJobRepository,
+    job_id: &str,
+    session_id: &str,
+    payload: &JobPayload,
+    workflow_id: Option<&str>,
+    additional_params: Option<&Value>,
+) -> AppResult<()> {
+    let param = |key: &str| {
+        additional_params
+            .and_then(|params| params.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+
+    let mut parents: Vec<(String, &str)> = Vec::new();
+    if let Some(workflow_id) = workflow_id {
+        parents.push((workflow_id.to_string(), relations::WORKFLOW_STAGE));
+    }
+    if let Some(continued_from) = param("continuedFromJob") {
+        parents.push((continued_from, relations::CONTINUED_FROM));
+    }
+
+    match payload {
+        JobPayload::ImplementationPlanMerge(merge) => {
+            parents.extend(
+                merge
+                    .source_job_ids
+                    .iter()
+                    .map(|id| (id.clone(), relations::MERGE_SOURCE)),
+            );
+        }
+        JobPayload::ImplementationPlanRefinement(refinement) => {
+            parents.push((refinement.plan_job_id.clone(), relations::REFINEMENT));
+        }
+        JobPayload::PlanTestGeneration(tests) => {
+            parents.push((tests.plan_job_id.clone(), relations::TEST_PLAN));
+        }
+        JobPayload::ImplementationPlan(plan) => {
+            let session_jobs = repo.get_jobs_by_session_id(session_id).await?;
+            parents.extend(
+                file_selection_parents(&plan.relevant_files, &session_jobs)
+                    .into_iter()
+                    .map(|id| (id, relations::SELECTED_FILES)),
+            );
+            parents.extend(
+                research_parents(&plan.task_description, &session_jobs)
+                    .into_iter()
+                    .map(|id| (id, relations::RESEARCH)),
+            );
+        }
+        _ => {}
+    }
+
+    repo.record_job_provenance(job_id, &parents, None).await?;
+
+    if let Some(rerun_of) = param("rerunOf") {
+        repo.record_job_provenance(
+            job_id,
+            &[(rerun_of, relations::RERUN_OF)],
+            param("replayId").as_deref(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Record that a workflow stage job consumes the output of the stage it depends on.
+/// Runs in the background so it can be called while workflow state is locked.
+pub fn record_stage_dependency(app_handle: &AppHandle, dependency_job_id: &str, job_id: &str) {
+    let repo = app_handle
+        .state::<Arc<BackgroundJobRepository>>()
+        .inner()
+        .clone();
+    let parents = vec![(dependency_job_id.to_string(), relations::STAGE_INPUT)];
+    let job_id = job_id.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = repo.record_job_provenance(&job_id, &parents, None).await {
+            warn!(
+                "Failed to record stage dependency for job {}: {}",
+                job_id, e
+            );
+        }
+    });
+}
+
+fn normalize_file(path: &str) -> String {
+    let path = to_forward_slashes(path.trim());
+    path.strip_prefix("./").map(str::to_string).unwrap_or(path)
+}
+
+fn is_completed(job: &BackgroundJob) -> bool {
+    JobStatus::from_str(&job.status)
+        .is_ok_and(|s| matches!(s, JobStatus::Completed | JobStatus::CompletedByTag))
+}
+
+/// Files a file-finding job selected, from its standardized `{ files: [...] }` response
+fn job_output_files(job: &BackgroundJob) -> Vec<String> {
+    job.response
+        .as_deref()
+        .and_then(|r| serde_json::from_str::<Value>(r).ok())
+        .and_then(|v| v.get("files").and_then(|f| f.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|f| f.as_str())
+        .map(normalize_file)
+        .filter(|f| !f.is_empty())
+        .collect()
+}
+
+/// Findings of a web search execution job, in result order
+fn job_findings(job: &BackgroundJob) -> Vec<String> {
+    job.response
+        .as_deref()
+        .and_then(|r| serde_json::from_str::<Value>(r).ok())
+        .and_then(|v| v.get("searchResults").and_then(|r| r.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|r| r.get("findings").and_then(|f| f.as_str()))
+        .map(|f| f.trim().to_string())
+        .collect()
+}
+
+/// The completed file-finding jobs that selected a plan's files. Newest jobs win;
+/// an older job is only linked when it contributed a file no newer job did.
+pub fn file_selection_parents(
+    plan_files: &[String],
+    session_jobs: &[BackgroundJob],
+) -> Vec<String> {
+    let mut uncovered: HashSet<String> = plan_files.iter().map(|f| normalize_file(f)).collect();
+    let mut candidates: Vec<&BackgroundJob> = session_jobs
+        .iter()
+        .filter(|job| FILE_SELECTION_TASK_TYPES.contains(&job.task_type.as_str()))
+        .filter(|job| is_completed(job))
+        .collect();
+    candidates.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let mut parents = Vec::new();
+    for job in candidates {
+        if uncovered.is_empty() {
+            break;
+        }
+        let before = uncovered.len();
+        for file in job_output_files(job) {
+            uncovered.remove(&file);
+        }
+        if uncovered.len() < before {
+            parents.push(job.id.clone());
+        }
+    }
+    parents
+}
+
+/// The completed web research jobs whose findings were pasted into a task description
+pub fn research_parents(task_description: &str, session_jobs: &[BackgroundJob]) -> Vec<String> {
+    session_jobs
+        .iter()
+        .filter(|job| job.task_type == TaskType::WebSearchExecution.to_string())
+        .filter(|job| is_completed(job))
+        .filter(|job| {
+            job_findings(job).iter().any(|finding| {
+                finding.len() >= MIN_FINDING_MATCH_LEN
+                    && task_description.contains(finding_excerpt(finding))
+            })
+        })
+        .map(|job| job.id.clone())
+        .collect()
+}
+
+/// Leading part of a finding, which survives light editing of the rest
+fn finding_excerpt(finding: &str) -> &str {
+    let end = finding
+        .char_indices()
+        .nth(200)
+        .map(|(i, _)| i)
+        .unwrap_or(finding.len());
+    &finding[..end]
+}
+
+/// Jobs reachable from `root` in dependency order (every job after all of its
+/// parents within the subgraph), excluding `root` itself
+pub fn replay_order(root: &str, edges: &[JobProvenanceEdge]) -> Vec<String> {
+    let mut pending_parents: HashMap<&str, usize> = HashMap::new();
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges {
+        *pending_parents.entry(&edge.child_job_id).or_default() += 1;
+        children
+            .entry(&edge.parent_job_id)
+            .or_default()
+            .push(&edge.child_job_id);
+    }
+
+    let mut order = Vec::new();
+    let mut ready: VecDeque<&str> = VecDeque::from([root]);
+    let mut visited: HashSet<&str> = HashSet::from([root]);
+    while let Some(job_id) = ready.pop_front() {
+        for child in children.get(job_id).into_iter().flatten() {
+            let remaining = pending_parents.entry(child).or_default();
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 && visited.insert(child) {
+                order.push(child.to_string());
+                ready.push_back(child);
+            }
+        }
+    }
+    order
+}
+
+/// Rewrite a derived job's payload so it consumes the re-run outputs of its parents
+fn remap_payload(
+    payload: JobPayload,
+    parent_edges: &[&JobProvenanceEdge],
+    reruns: &HashMap<String, String>,
+    jobs: &HashMap<String, BackgroundJob>,
+) -> JobPayload {
+    match payload {
+        JobPayload::ImplementationPlanMerge(mut merge) => {
+            for source in merge.source_job_ids.iter_mut() {
+                if let Some(rerun) = reruns.get(source) {
+                    *source = rerun.clone();
+                }
+            }
+            JobPayload::ImplementationPlanMerge(merge)
+        }
+        JobPayload::ImplementationPlanRefinement(mut refinement) => {
+            if let Some(rerun) = reruns.get(&refinement.plan_job_id) {
+                refinement.plan_job_id = rerun.clone();
+            }
+            JobPayload::ImplementationPlanRefinement(refinement)
+        }
+        JobPayload::PlanTestGeneration(mut tests) => {
+            // A re-run plan has its own revisions, so its current content is used
+            if let Some(rerun) = reruns.get(&tests.plan_job_id) {
+                tests.plan_job_id = rerun.clone();
+                tests.plan_revision = None;
+            }
+            JobPayload::PlanTestGeneration(tests)
+        }
+        JobPayload::ImplementationPlan(mut plan) => {
+            for edge in parent_edges {
+                let (Some(original), Some(rerun)) = (
+                    jobs.get(&edge.parent_job_id),
+                    reruns.get(&edge.parent_job_id).and_then(|id| jobs.get(id)),
+                ) else {
+                    continue;
+                };
+                match edge.relation.as_str() {
+                    relations::SELECTED_FILES => {
+                        plan.relevant_files = swap_files(
+                            &plan.relevant_files,
+                            &job_output_files(original),
+                            &job_output_files(rerun),
+                        );
+                    }
+                    relations::RESEARCH => {
+                        for (old, new) in job_findings(original).iter().zip(job_findings(rerun)) {
+                            if !old.is_empty() {
+                                plan.task_description = plan.task_description.replace(old, &new);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            JobPayload::ImplementationPlan(plan)
+        }
+        other => other,
+    }
+}
+
+/// Replace the files one input contributed with the files its re-run produced
+fn swap_files(current: &[String], removed: &[String], added: &[String]) -> Vec<String> {
+    let removed: HashSet<String> = removed.iter().map(|f| normalize_file(f)).collect();
+    let mut seen = HashSet::new();
+    current
+        .iter()
+        .filter(|f| !removed.contains(&normalize_file(f)))
+        .cloned()
+        .chain(added.iter().cloned())
+        .filter(|f| seen.insert(normalize_file(f)))
+        .collect()
+}
+
+/// Model settings a job ran with, read from the job row and its metadata
+fn original_model_settings(job: &BackgroundJob) -> (Option<String>, Option<f32>, Option<u32>) {
+    let task_data = job
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .and_then(|m| m.get("taskData").cloned())
+        .unwrap_or(Value::Null);
+    (
+        task_data
+            .get("model")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or_else(|| job.model_used.clone()),
+        task_data
+            .get("temperature")
+            .and_then(|v| v.as_f64())
+            .map(|t| t as f32),
+        task_data
+            .get("maxTokens")
+            .and_then(|v| v.as_u64())
+            .map(|t| t as u32),
+    )
+}
+
+async fn create_rerun(
+    app_handle: &AppHandle,
+    original: &BackgroundJob,
+    task_type: TaskType,
+    payload: JobPayload,
+    project_directory: &str,
+    overrides: &ReplayOverrides,
+    replay_id: &str,
+) -> AppResult<String> {
+    let model_settings = if task_type.requires_llm() {
+        let (model, temperature, max_tokens) = original_model_settings(original);
+        crate::utils::config_resolver::resolve_model_settings(
+            app_handle,
+            task_type,
+            project_directory,
+            overrides.model.clone().or(model),
+            overrides.temperature.or(temperature),
+            overrides.max_tokens.or(max_tokens),
+        )
+        .await?
+    } else {
+        None
+    };
+    let prompt = match &payload {
+        JobPayload::ImplementationPlan(plan) => plan.task_description.clone(),
+        _ => original.prompt.clone(),
+    };
+    let api_type = if model_settings.is_some() {
+        "openrouter"
+    } else {
+        "filesystem"
+    };
+
+    crate::utils::job_creation_utils::create_and_queue_background_job(
+        &original.session_id,
+        project_directory,
+        api_type,
+        task_type,
+        &task_type.to_string().to_uppercase(),
+        &prompt,
+        model_settings,
+        payload,
+        2,
+        None,
+        None,
+        Some(json!({
+            "rerunOf": original.id,
+            "replayId": replay_id,
+        })),
+        app_handle,
+    )
+    .await
+}
+
+/// Re-run `job_id` with `overrides`, then re-run every job derived from it once
+/// its re-run inputs complete
+pub async fn start_replay(
+    app_handle: &AppHandle,
+    job_id: &str,
+    overrides: ReplayOverrides,
+) -> AppResult<ReplayStarted> {
+    let repo = app_handle
+        .state::<Arc<BackgroundJobRepository>>()
+        .inner()
+        .clone();
+    let session_repo = app_handle.state::<Arc<SessionRepository>>().inner().clone();
+
+    let original = repo
+        .get_job_by_id(job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Job {} not found", job_id)))?;
+    let task_type = TaskType::from_str(&original.task_type)?;
+    if matches!(
+        task_type,
+        TaskType::FileFinderWorkflow | TaskType::WebSearchWorkflow
+    ) {
+        return Err(AppError::ValidationError(
+            "Workflow jobs cannot be re-run directly; re-run one of their stages instead"
+                .to_string(),
+        ));
+    }
+    if JobStatus::from_str(&original.status).is_ok_and(|s| s.is_active()) {
+        return Err(AppError::ValidationError(format!(
+            "Job {} is still running",
+            job_id
+        )));
+    }
+
+    let project_directory = session_repo
+        .get_session_by_id(&original.session_id)
+        .await?
+        .map(|session| session.project_directory)
+        .ok_or_else(|| {
+            AppError::NotFoundError(format!("Session {} not found", original.session_id))
+        })?;
+
+    let payload = match convert_db_job_to_job(&original)?.payload {
+        JobPayload::ImplementationPlan(mut plan) => {
+            if let Some(task_description) = &overrides.task_description {
+                plan.task_description = task_description.clone();
+            }
+            JobPayload::ImplementationPlan(plan)
+        }
+        _ if overrides.task_description.is_some() => {
+            return Err(AppError::ValidationError(
+                "A task description override only applies to implementation plan jobs".to_string(),
+            ));
+        }
+        other => other,
+    };
+
+    let edges = repo.get_job_descendant_edges(job_id, false).await?;
+    let scheduled_job_ids = replay_order(job_id, &edges);
+
+    let replay_id = Uuid::new_v4().to_string();
+    let rerun_job_id = create_rerun(
+        app_handle,
+        &original,
+        task_type,
+        payload,
+        &project_directory,
+        &overrides,
+        &replay_id,
+    )
+    .await?;
+
+    info!(
+        "Started replay {} of job {} as {} with {} derived jobs",
+        replay_id,
+        job_id,
+        rerun_job_id,
+        scheduled_job_ids.len()
+    );
+
+    if !scheduled_job_ids.is_empty() {
+        let app_handle = app_handle.clone();
+        let replay_id = replay_id.clone();
+        let order = scheduled_job_ids.clone();
+        let reruns = HashMap::from([(job_id.to_string(), rerun_job_id.clone())]);
+        tokio::spawn(async move {
+            if let Err(e) = replay_descendants(
+                &app_handle,
+                &repo,
+                &replay_id,
+                &project_directory,
+                order,
+                edges,
+                reruns,
+            )
+            .await
+            {
+                warn!("Replay {} stopped: {}", replay_id, e);
+            }
+        });
+    }
+
+    Ok(ReplayStarted {
+        replay_id,
+        original_job_id: job_id.to_string(),
+        rerun_job_id,
+        scheduled_job_ids,
+    })
+}
+
+async fn replay_descendants(
+    app_handle: &AppHandle,
+    repo: &BackgroundJobRepository,
+    replay_id: &str,
+    project_directory: &str,
+    order: Vec<String>,
+    edges: Vec<JobProvenanceEdge>,
+    mut reruns: HashMap<String, String>,
+) -> AppResult<()> {
+    let mut jobs: HashMap<String, BackgroundJob> = HashMap::new();
+
+    for job_id in order {
+        let parent_edges: Vec<&JobProvenanceEdge> = edges
+            .iter()
+            .filter(|e| e.child_job_id == job_id && reruns.contains_key(&e.parent_job_id))
+            .collect();
+
+        for edge in &parent_edges {
+            let rerun_id = reruns[&edge.parent_job_id].clone();
+            let finished = wait_for_job(repo, &rerun_id).await?;
+            if !is_completed(&finished) {
+                return Err(AppError::JobError(format!(
+                    "Re-run {} ended as {}; not re-running {}",
+                    rerun_id, finished.status, job_id
+                )));
+            }
+            jobs.insert(rerun_id, finished);
+            if !jobs.contains_key(&edge.parent_job_id) {
+                if let Some(parent) = repo.get_job_by_id(&edge.parent_job_id).await? {
+                    jobs.insert(parent.id.clone(), parent);
+                }
+            }
+        }
+
+        let Some(original) = repo.get_job_by_id(&job_id).await? else {
+            warn!("Replay {}: job {} no longer exists", replay_id, job_id);
+            continue;
+        };
+        let task_type = TaskType::from_str(&original.task_type)?;
+        let payload = remap_payload(
+            convert_db_job_to_job(&original)?.payload,
+            &parent_edges,
+            &reruns,
+            &jobs,
+        );
+
+        let rerun_id = create_rerun(
+            app_handle,
+            &original,
+            task_type,
+            payload,
+            project_directory,
+            &ReplayOverrides::default(),
+            replay_id,
+        )
+        .await?;
+        info!(
+            "Replay {}: re-running {} as {}",
+            replay_id, job_id, rerun_id
+        );
+        reruns.insert(job_id, rerun_id);
+    }
+    Ok(())
+}
+
+async fn wait_for_job(repo: &BackgroundJobRepository, job_id: &str) -> AppResult<BackgroundJob> {
+    let deadline = tokio::time::Instant::now() + REPLAY_STAGE_TIMEOUT;
+    loop {
+        let job = repo
+            .get_job_by_id(job_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(format!("Job {} not found", job_id)))?;
+        if JobStatus::from_str(&job.status).is_ok_and(|s| s.is_terminal()) {
+            return Ok(job);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(AppError::JobError(format!(
+                "Timed out waiting for job {}",
+                job_id
+            )));
+        }
+        tokio::time::sleep(REPLAY_POLL_INTERVAL).await;
+    }
+}
+
+/// Original and re-run jobs of a replay, paired for side-by-side display
+pub async fn get_replay_comparison(
+    repo: &BackgroundJobRepository,
+    replay_id: &str,
+) -> AppResult<ReplayComparison> {
+    let mut pairs = Vec::new();
+    for (original_id, rerun_id) in repo.get_replay_pairs(replay_id).await? {
+        let (Some(original), Some(rerun)) = (
+            repo.get_job_by_id(&original_id).await?,
+            repo.get_job_by_id(&rerun_id).await?,
+        ) else {
+            continue;
+        };
+        let response_diff = match (&original.response, &rerun.response) {
+            (Some(before), Some(after)) if is_completed(&rerun) => {
+                Some(diffy::create_patch(before, after).to_string())
+            }
+            _ => None,
+        };
+        pairs.push(ReplayComparisonPair {
+            original: original.into(),
+            rerun: rerun.into(),
+            response_diff,
+        });
+    }
+
+    if pairs.is_empty() {
+        return Err(AppError::NotFoundError(format!(
+            "Replay {} not found",
+            replay_id
+        )));
+    }
+    Ok(ReplayComparison {
+        replay_id: replay_id.to_string(),
+        pairs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jobs::types::{ImplementationPlanMergePayload, ImplementationPlanPayload};
+
+    fn job(id: &str, task_type: &str, created_at: i64, response: Value) -> BackgroundJob {
+        BackgroundJob {
+            id: id.to_string(),
+            session_id: "s1".to_string(),
+            task_type: task_type.to_string(),
+            status: "completed".to_string(),
+            prompt: "prompt".to_string(),
+            response: Some(response.to_string()),
+            error_message: None,
+            tokens_sent: None,
+            tokens_received: None,
+            cache_write_tokens: None,
+            cache_read_tokens: None,
+            model_used: None,
+            actual_cost: None,
+            duration_ms: None,
+            metadata: None,
+            system_prompt_template: None,
+            created_at,
+            updated_at: None,
+            start_time: None,
+            end_time: None,
+            is_finalized: None,
+            error_details: None,
+        }
+    }
+
+    fn edge(parent: &str, child: &str, relation: &str) -> JobProvenanceEdge {
+        JobProvenanceEdge {
+            parent_job_id: parent.to_string(),
+            child_job_id: child.to_string(),
+            relation: relation.to_string(),
+            replay_id: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_infers_file_and_research_parents() {
+        let finding = "The billing API paginates with opaque cursors and a page size of 100.";
+        let jobs = vec![
+            job(
+                "old",
+                "extended_path_finder",
+                1,
+                json!({ "files": ["src/a.rs", "src/b.rs"] }),
+            ),
+            job(
+                "new",
+                "file_relevance_assessment",
+                2,
+                json!({ "files": ["./src/a.rs"] }),
+            ),
+            job(
+                "stale",
+                "extended_path_finder",
+                0,
+                json!({ "files": ["src/b.rs"] }),
+            ),
+            job(
+                "unrelated",
+                "extended_path_finder",
+                3,
+                json!({ "files": ["docs/x.md"] }),
+            ),
+            job(
+                "search",
+                "web_search_execution",
+                4,
+                json!({ "searchResults": [{ "findings": finding }] }),
+            ),
+        ];
+
+        let files = vec!["src/a.rs".to_string(), "src/b.rs".to_string()];
+        assert_eq!(file_selection_parents(&files, &jobs), vec!["new", "old"]);
+
+        let task = format!("Add paging.\n\nResearch:\n{}", finding);
+        assert_eq!(research_parents(&task, &jobs), vec!["search"]);
+        assert!(research_parents("Add paging.", &jobs).is_empty());
+    }
+
+    #[test]
+    fn test_replay_order_waits_for_all_parents() {
+        let edges = vec![
+            edge("finder", "plan_a", relations::SELECTED_FILES),
+            edge("finder", "plan_b", relations::SELECTED_FILES),
+            edge("plan_a", "merge", relations::MERGE_SOURCE),
+            edge("plan_b", "merge", relations::MERGE_SOURCE),
+        ];
+        let order = replay_order("finder", &edges);
+        assert_eq!(order.len(), 3);
+        assert_eq!(order.last().map(String::as_str), Some("merge"));
+    }
+
+    #[test]
+    fn test_remap_payload_uses_rerun_outputs() {
+        let jobs: HashMap<String, BackgroundJob> = [
+            job(
+                "finder",
+                "extended_path_finder",
+                1,
+                json!({ "files": ["src/a.rs"] }),
+            ),
+            job(
+                "finder2",
+                "extended_path_finder",
+                2,
+                json!({ "files": ["src/c.rs"] }),
+            ),
+        ]
+        .into_iter()
+        .map(|j| (j.id.clone(), j))
+        .collect();
+        let reruns = HashMap::from([
+            ("finder".to_string(), "finder2".to_string()),
+            ("plan_a".to_string(), "plan_a2".to_string()),
+        ]);
+
+        let plan = JobPayload::ImplementationPlan(ImplementationPlanPayload {
+            task_description: "Task".to_string(),
+            relevant_files: vec!["src/a.rs".to_string(), "README.md".to_string()],
+            selected_root_directories: None,
+            enable_web_search: false,
+            include_project_structure: true,
+        });
+        let files_edge = edge("finder", "plan", relations::SELECTED_FILES);
+        match remap_payload(plan, &[&files_edge], &reruns, &jobs) {
+            JobPayload::ImplementationPlan(plan) => {
+                assert_eq!(plan.relevant_files, vec!["README.md", "src/c.rs"])
+            }
+            _ => unreachable!(),
+        }
+
+        let merge = JobPayload::ImplementationPlanMerge(ImplementationPlanMergePayload {
+            source_job_ids: vec!["plan_a".to_string(), "plan_b".to_string()],
+            merge_instructions: None,
+        });
+        match remap_payload(merge, &[], &reruns, &jobs) {
+            JobPayload::ImplementationPlanMerge(merge) => {
+                assert_eq!(merge.source_job_ids, vec!["plan_a2", "plan_b"])
+            }
+            _ => unreachable!(),
+        }
+    }
+}
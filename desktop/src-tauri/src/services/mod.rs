@@ -1,5 +1,6 @@
 // Module for service-layer functionality
 pub mod account_deletion_service;
+pub mod backup_archive;
 pub mod backup_service;
 pub mod cache_health_monitor;
 pub mod config_cache_service;
@@ -8,10 +9,22 @@ pub mod file_selection_auto_apply;
 pub mod file_service;
 pub mod history_metrics;
 pub mod history_state_sequencer;
+pub mod integration_hooks;
+pub mod job_provenance;
+pub mod plan_conversations;
+pub mod plan_issue_exports;
+pub mod plan_progress;
+pub mod plan_revisions;
+pub mod plan_step_reviews;
+pub mod plan_templates;
+pub mod plan_test_plans;
+pub mod session_bundle;
 pub mod session_cache;
+pub mod session_fork;
 pub mod system_prompt_cache_service;
 pub mod task_services;
 pub mod terminal_manager;
+pub mod workspaces;
 
 // Re-export service modules
 pub use account_deletion_service::*;
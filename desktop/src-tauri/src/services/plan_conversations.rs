@@ -0,0 +1,344 @@
+//! Follow-up conversations on implementation plans. Every user message becomes a refinement
+//! job that sees the plan's original prompt context with the plan and the earlier turns as
+//! history, and the model's reply becomes a new plan revision. Conversations are stored per
+//! plan, so they can be continued later from the desktop or a linked mobile device.
+
+use crate::db_utils::SessionRepository;
+use crate::db_utils::background_job_repository::{
+    NewPlanConversationMessage, PlanConversationMessage, conversation_roles,
+};
+use crate::error::{AppError, AppResult};
+use crate::jobs::types::{ImplementationPlanRefinementPayload, JobPayload};
+use crate::models::{JobStatus, OpenRouterContent, OpenRouterRequestMessage, TaskType};
+use crate::services::plan_revisions::{self, get_plan_job, job_repo};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanConversation {
+    pub job_id: String,
+    pub messages: Vec<PlanConversationMessage>,
+    /// Refinement job still working on the latest request, if any
+    pub pending_job_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanRefinementStarted {
+    /// The refinement job answering the request
+    pub job_id: String,
+    pub message: PlanConversationMessage,
+}
+
+/// Pairs of a request and the reply that answered it, in conversation order. Requests without
+/// a reply (failed or still running) and the turn of `current_job_id` are left out.
+pub(crate) fn completed_turns<'a>(
+    messages: &'a [PlanConversationMessage],
+    current_job_id: Option<&str>,
+) -> Vec<(&'a PlanConversationMessage, &'a PlanConversationMessage)> {
+    messages
+        .iter()
+        .filter(|m| m.role == conversation_roles::ASSISTANT)
+        .filter_map(|reply| {
+            let job_id = reply.refinement_job_id.as_deref()?;
+            if Some(job_id) == current_job_id {
+                return None;
+            }
+            messages
+                .iter()
+                .find(|m| {
+                    m.role == conversation_roles::USER
+                        && m.refinement_job_id.as_deref() == Some(job_id)
+                })
+                .map(|request| (request, reply))
+        })
+        .collect()
+}
+
+fn text_message(role: &str, text: &str) -> OpenRouterRequestMessage {
+    OpenRouterRequestMessage {
+        role: role.to_string(),
+        content: vec![OpenRouterContent::Text {
+            content_type: "text".to_string(),
+            text: text.to_string(),
+        }],
+    }
+}
+
+/// Messages that follow the plan's original prompt: the first plan as the assistant's answer,
+/// the earlier turns, then the new request. When the plan was changed outside the
+/// conversation (hand edit or restore), the current plan is included with the request.
+pub(crate) fn build_refinement_history(
+    initial_plan: &str,
+    turns: &[(&PlanConversationMessage, &PlanConversationMessage)],
+    current_plan: &str,
+    request: &str,
+) -> Vec<OpenRouterRequestMessage> {
+    let mut history = vec![text_message(conversation_roles::ASSISTANT, initial_plan)];
+    for (user, assistant) in turns {
+        history.push(text_message(conversation_roles::USER, &user.content));
+        history.push(text_message(
+            conversation_roles::ASSISTANT,
+            &assistant.content,
+        ));
+    }
+
+    let last_reply = turns
+        .last()
+        .map(|(_, assistant)| assistant.content.as_str())
+        .unwrap_or(initial_plan);
+    let request = if last_reply.trim() == current_plan.trim() {
+        request.to_string()
+    } else {
+        format!(
+            "The plan was edited since your last reply. This is the current plan:\n<current_plan>\n{}\n</current_plan>\n\n{}",
+            current_plan, request
+        )
+    };
+    history.push(text_message(conversation_roles::USER, &request));
+    history
+}
+
+/// Refinement job of the latest request when it is still queued or running
+async fn pending_refinement_job(
+    app_handle: &AppHandle,
+    messages: &[PlanConversationMessage],
+) -> AppResult<Option<String>> {
+    let Some(last) = messages.last() else {
+        return Ok(None);
+    };
+    let Some(job_id) = last.refinement_job_id.as_deref() else {
+        return Ok(None);
+    };
+    if last.role != conversation_roles::USER {
+        return Ok(None);
+    }
+
+    let job = job_repo(app_handle).get_job_by_id(job_id).await?;
+    let active = job
+        .and_then(|job| JobStatus::from_str(&job.status).ok())
+        .is_some_and(|status| status.is_active());
+    Ok(active.then(|| job_id.to_string()))
+}
+
+pub async fn get_plan_conversation(
+    app_handle: &AppHandle,
+    job_id: &str,
+) -> AppResult<PlanConversation> {
+    let repo = job_repo(app_handle);
+    get_plan_job(&repo, job_id).await?;
+
+    let messages = repo.list_plan_conversation(job_id).await?;
+    let pending_job_id = pending_refinement_job(app_handle, &messages).await?;
+    Ok(PlanConversation {
+        job_id: job_id.to_string(),
+        messages,
+        pending_job_id,
+    })
+}
+
+/// Ask for a change to a plan. The request is stored in the plan's conversation and answered
+/// by a queued refinement job; one request per plan is worked on at a time.
+pub async fn start_plan_refinement(
+    app_handle: &AppHandle,
+    job_id: &str,
+    message: &str,
+) -> AppResult<PlanRefinementStarted> {
+    let message = message.trim();
+    if message.is_empty() {
+        return Err(AppError::ValidationError(
+            "Refinement request cannot be empty".to_string(),
+        ));
+    }
+
+    let repo = job_repo(app_handle);
+    let plan = get_plan_job(&repo, job_id).await?;
+    if plan.response.as_deref().is_none_or(|r| r.trim().is_empty()) {
+        return Err(AppError::ValidationError(
+            "Implementation plan has no content to refine yet".to_string(),
+        ));
+    }
+    let conversation = repo.list_plan_conversation(job_id).await?;
+    if let Some(pending) = pending_refinement_job(app_handle, &conversation).await? {
+        return Err(AppError::ValidationError(format!(
+            "Plan {} is already being refined by job {}",
+            job_id, pending
+        )));
+    }
+
+    let session = SessionRepository::new(repo.get_pool())
+        .get_session_by_id(&plan.session_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFoundError(format!("Session not found: {}", plan.session_id))
+        })?;
+    let plan_revision = plan_revisions::list_plan_revisions(app_handle, job_id)
+        .await?
+        .last()
+        .map(|r| r.revision);
+    let device_id = crate::auth::header_utils::get_device_id(app_handle)
+        .map_err(|e| warn!("Recording plan refinement request without device id: {}", e))
+        .ok();
+
+    let mut request = repo
+        .add_plan_conversation_message(
+            job_id,
+            NewPlanConversationMessage {
+                role: conversation_roles::USER,
+                content: message,
+                refinement_job_id: None,
+                plan_revision,
+                author_device_id: device_id.as_deref(),
+            },
+        )
+        .await?;
+
+    let queued = async {
+        let model_settings = crate::utils::config_resolver::resolve_model_settings(
+            app_handle,
+            TaskType::ImplementationPlanRefinement,
+            &session.project_directory,
+            None,
+            None,
+            None,
+        )
+        .await?;
+        crate::utils::job_creation_utils::create_and_queue_background_job(
+            &session.id,
+            &session.project_directory,
+            "openrouter",
+            TaskType::ImplementationPlanRefinement,
+            "IMPLEMENTATION_PLAN_REFINEMENT",
+            message,
+            model_settings,
+            JobPayload::ImplementationPlanRefinement(ImplementationPlanRefinementPayload {
+                plan_job_id: job_id.to_string(),
+                message: message.to_string(),
+            }),
+            2,
+            None,
+            None,
+            Some(serde_json::json!({ "planJobId": job_id })),
+            app_handle,
+        )
+        .await
+    }
+    .await;
+
+    let refinement_job_id = match queued {
+        Ok(refinement_job_id) => refinement_job_id,
+        Err(e) => {
+            if let Err(cleanup) = repo.delete_plan_conversation_message(&request.id).await {
+                warn!(
+                    "Failed to remove refinement request {} after job creation failed: {}",
+                    request.id, cleanup
+                );
+            }
+            return Err(e);
+        }
+    };
+    repo.set_plan_conversation_refinement_job(&request.id, &refinement_job_id)
+        .await?;
+    request.refinement_job_id = Some(refinement_job_id.clone());
+
+    info!(
+        "Queued refinement job {} for plan {}",
+        refinement_job_id, job_id
+    );
+    Ok(PlanRefinementStarted {
+        job_id: refinement_job_id,
+        message: request,
+    })
+}
+
+/// Store a refinement job's reply as the plan's new revision and as the assistant message of
+/// its turn
+pub(crate) async fn record_refinement_reply(
+    app_handle: &AppHandle,
+    plan_job_id: &str,
+    refinement_job_id: &str,
+    content: &str,
+) -> AppResult<PlanConversationMessage> {
+    let revision = plan_revisions::record_refined_plan(app_handle, plan_job_id, content).await?;
+    job_repo(app_handle)
+        .add_plan_conversation_message(
+            plan_job_id,
+            NewPlanConversationMessage {
+                role: conversation_roles::ASSISTANT,
+                content,
+                refinement_job_id: Some(refinement_job_id),
+                plan_revision: Some(revision.revision),
+                author_device_id: None,
+            },
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str, job: Option<&str>) -> PlanConversationMessage {
+        PlanConversationMessage {
+            id: format!("{}-{}", role, content),
+            job_id: "plan".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            refinement_job_id: job.map(str::to_string),
+            plan_revision: None,
+            author_device_id: None,
+            created_at: 0,
+        }
+    }
+
+    fn texts(history: &[OpenRouterRequestMessage]) -> Vec<(&str, &str)> {
+        history
+            .iter()
+            .map(|m| match &m.content[0] {
+                OpenRouterContent::Text { text, .. } => (m.role.as_str(), text.as_str()),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn history_replays_answered_turns_only() {
+        let messages = vec![
+            message("user", "split step 3", Some("r1")),
+            message("assistant", "<plan>v2</plan>", Some("r1")),
+            message("user", "failed request", Some("r2")),
+            message("user", "use the repository pattern", Some("r3")),
+        ];
+        let turns = completed_turns(&messages, Some("r3"));
+        assert_eq!(turns.len(), 1);
+
+        let history = build_refinement_history(
+            "<plan>v1</plan>",
+            &turns,
+            "<plan>v2</plan>\n",
+            "use the repository pattern",
+        );
+        assert_eq!(
+            texts(&history),
+            vec![
+                ("assistant", "<plan>v1</plan>"),
+                ("user", "split step 3"),
+                ("assistant", "<plan>v2</plan>"),
+                ("user", "use the repository pattern"),
+            ]
+        );
+    }
+
+    #[test]
+    fn hand_edits_since_the_last_reply_are_sent_with_the_request() {
+        let history =
+            build_refinement_history("<plan>v1</plan>", &[], "<plan>edited</plan>", "shorten it");
+        let (role, request) = texts(&history)[1];
+        assert_eq!(role, "user");
+        assert!(request.contains("<current_plan>\n<plan>edited</plan>\n</current_plan>"));
+        assert!(request.ends_with("shorten it"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Exports of implementation plans to issue-tracker import files. The files are written to the
+//! chosen directory, by default `.plantocode/issue_exports/<plan>` in the project, and are
+//! returned as well so remote clients can share them directly. Exporting again in the same
+//! format replaces the files of the previous export.
+
+use crate::db_utils::SessionRepository;
+use crate::error::{AppError, AppResult};
+use crate::services::plan_revisions::{get_plan_job, job_repo};
+use crate::services::plan_test_plans::stored_test_plan;
+use crate::utils::plan_issue_export::{
+    ExportedFile, PlanExportFormat, PlanExporterRegistry, build_plan_issues,
+};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+const ISSUE_EXPORTS_DIRECTORY: &str = "issue_exports";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanIssueExport {
+    pub job_id: String,
+    pub format: String,
+    /// Directory the files were written to
+    pub output_directory: String,
+    pub issue_count: usize,
+    pub files: Vec<ExportedFile>,
+}
+
+/// The exporter registry built at startup
+fn exporter_registry(app_handle: &AppHandle) -> Arc<PlanExporterRegistry> {
+    app_handle
+        .state::<Arc<PlanExporterRegistry>>()
+        .inner()
+        .clone()
+}
+
+pub fn list_plan_export_formats(app_handle: &AppHandle) -> Vec<PlanExportFormat> {
+    exporter_registry(app_handle).formats()
+}
+
+/// Title of a plan for its parent issue: the generated plan title, else the session name
+fn plan_title(metadata: Option<&str>, session_name: &str) -> String {
+    metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| {
+            m.get("planTitle")
+                .and_then(|t| t.as_str())
+                .map(str::to_string)
+        })
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| session_name.to_string())
+}
+
+/// Names of the files the last export in `format` wrote to a directory, so re-exporting a
+/// revised plan does not leave sub-tasks of removed or renamed steps behind
+fn export_manifest_path(directory: &Path, format: &str) -> PathBuf {
+    directory.join(format!(".{}-export.json", format))
+}
+
+fn remove_previous_export(directory: &Path, format: &str) -> AppResult<()> {
+    let Ok(manifest) = std::fs::read_to_string(export_manifest_path(directory, format)) else {
+        return Ok(());
+    };
+    let previous: Vec<String> = serde_json::from_str(&manifest).unwrap_or_default();
+    for file_name in previous {
+        // Only plain file names are removed, never paths out of the directory
+        if Path::new(&file_name).file_name().and_then(|n| n.to_str()) != Some(file_name.as_str()) {
+            continue;
+        }
+        let path = directory.join(&file_name);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(AppError::FileSystemError(format!(
+                    "Failed to remove previous export file {}: {}",
+                    path.display(),
+                    e
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_export_files(directory: &Path, format: &str, files: &[ExportedFile]) -> AppResult<()> {
+    std::fs::create_dir_all(directory).map_err(|e| {
+        AppError::FileSystemError(format!(
+            "Failed to create export directory {}: {}",
+            directory.display(),
+            e
+        ))
+    })?;
+    remove_previous_export(directory, format)?;
+    for file in files {
+        let path = directory.join(&file.file_name);
+        std::fs::write(&path, &file.content).map_err(|e| {
+            AppError::FileSystemError(format!(
+                "Failed to write export file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+    }
+
+    let file_names: Vec<&str> = files.iter().map(|f| f.file_name.as_str()).collect();
+    let manifest_path = export_manifest_path(directory, format);
+    std::fs::write(&manifest_path, serde_json::to_string(&file_names)?).map_err(|e| {
+        AppError::FileSystemError(format!(
+            "Failed to write export manifest {}: {}",
+            manifest_path.display(),
+            e
+        ))
+    })?;
+    Ok(())
+}
+
+/// Export a plan as a parent issue with one sub-task per step in the given format
+pub async fn export_plan_issues(
+    app_handle: &AppHandle,
+    job_id: &str,
+    format: &str,
+    output_directory: Option<&str>,
+) -> AppResult<PlanIssueExport> {
+    let repo = job_repo(app_handle);
+    let plan = get_plan_job(&repo, job_id).await?;
+    let plan_xml = plan.response.clone().unwrap_or_default();
+    if plan_xml.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Implementation plan has no content".to_string(),
+        ));
+    }
+
+    let session = SessionRepository::new(repo.get_pool())
+        .get_session_by_id(&plan.session_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFoundError(format!("Session not found: {}", plan.session_id))
+        })?;
+    let title = plan_title(plan.metadata.as_deref(), &session.name);
+    let test_plan = stored_test_plan(&plan);
+    let issues = build_plan_issues(job_id, &title, &plan_xml, test_plan.as_ref());
+    if issues.sub_tasks.is_empty() {
+        return Err(AppError::ValidationError(
+            "Implementation plan has no steps to export".to_string(),
+        ));
+    }
+
+    let files = exporter_registry(app_handle).export(format, &issues)?;
+    let directory = match output_directory {
+        Some(directory) => PathBuf::from(directory),
+        None => Path::new(&session.project_directory)
+            .join(".plantocode")
+            .join(ISSUE_EXPORTS_DIRECTORY)
+            .join(issues.slug()),
+    };
+    write_export_files(&directory, format, &files)?;
+
+    info!(
+        "Exported plan {} as {} {} issues to {}",
+        job_id,
+        issues.sub_tasks.len() + 1,
+        format,
+        directory.display()
+    );
+    Ok(PlanIssueExport {
+        job_id: job_id.to_string(),
+        format: format.to_string(),
+        output_directory: directory.to_string_lossy().to_string(),
+        issue_count: issues.sub_tasks.len() + 1,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(file_name: &str) -> ExportedFile {
+        ExportedFile {
+            file_name: file_name.to_string(),
+            media_type: "text/markdown".to_string(),
+            content: format!("# {}", file_name),
+        }
+    }
+
+    #[test]
+    fn re_export_removes_files_of_the_previous_export() {
+        let dir = std::env::temp_dir().join(format!("plan-issue-export-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("notes.md"), "kept").unwrap();
+
+        write_export_files(
+            &dir,
+            "markdown",
+            &[
+                file("00-plan.md"),
+                file("01-step-a.md"),
+                file("02-step-b.md"),
+            ],
+        )
+        .unwrap();
+        write_export_files(&dir, "jira", &[file("plan.jira.csv")]).unwrap();
+        write_export_files(
+            &dir,
+            "markdown",
+            &[file("00-plan.md"), file("01-step-c.md")],
+        )
+        .unwrap();
+
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["00-plan.md", "01-step-c.md", "notes.md", "plan.jira.csv"]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,443 @@
+//! Implementation progress of signed-off plans, inferred from git. Commits made since the
+//! sign-off and uncommitted changes are matched against the files each step names in its
+//! `<file_operations>`; changed files that no step mentions are reported as unplanned.
+
+use crate::db_utils::{BackgroundJobRepository, SessionRepository};
+use crate::error::{AppError, AppResult};
+use crate::events::{AppEvent, publish_app_event};
+use crate::jobs::processors::utils::parsing_utils::{
+    extract_step_file_paths_from_xml, extract_step_headers_from_xml,
+};
+use crate::models::BackgroundJob;
+use crate::services::plan_revisions::{get_plan_job, job_repo};
+use crate::utils::date_utils::get_timestamp;
+use crate::utils::git_utils;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+const SIGNOFF_STATE_ACCEPTED: &str = "accepted";
+/// How often the background checker re-reads git for recently signed-off plans
+const CHECK_INTERVAL_SECS: u64 = 5 * 60;
+/// Plans are tracked for this long after their sign-off
+const TRACKING_WINDOW_MS: i64 = 14 * 24 * 60 * 60 * 1000;
+
+/// How much of one step's planned files have been changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepFileProgress {
+    pub step_number: String,
+    pub title: String,
+    pub planned_files: Vec<String>,
+    pub touched_files: Vec<String>,
+    /// Share of planned files touched, 0.0 to 1.0; 0.0 for steps without file operations
+    pub completion: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanProgress {
+    pub job_id: String,
+    pub signed_off_at: i64,
+    pub signed_off_revision: Option<i64>,
+    pub checked_at: i64,
+    /// Commits since the sign-off that were taken into account, newest first
+    pub commits: Vec<String>,
+    pub total_steps: usize,
+    /// Steps with at least one planned file changed
+    pub steps_touched: usize,
+    /// Steps with every planned file changed
+    pub steps_completed: usize,
+    pub steps: Vec<StepFileProgress>,
+    /// Changed files, relative to the project directory, that no step mentions
+    pub unplanned_files: Vec<String>,
+}
+
+impl PlanProgress {
+    /// Whether anything but the check time differs from `other`
+    fn same_result(&self, other: &PlanProgress) -> bool {
+        self.signed_off_at == other.signed_off_at
+            && self.commits == other.commits
+            && self.steps == other.steps
+            && self.unplanned_files == other.unplanned_files
+    }
+}
+
+pub(crate) struct SignOff {
+    pub at: i64,
+    pub revision: Option<i64>,
+}
+
+/// Latest accepted sign-off: per-revision sign-offs first, then the `userSignoff` metadata
+/// written before plans had revisions
+async fn latest_signoff(
+    repo: &BackgroundJobRepository,
+    job: &BackgroundJob,
+) -> AppResult<Option<SignOff>> {
+    let from_revisions = repo
+        .list_plan_revisions(&job.id)
+        .await?
+        .into_iter()
+        .filter(|r| r.signoff_state.as_deref() == Some(SIGNOFF_STATE_ACCEPTED))
+        .filter_map(|r| r.signed_off_at.map(|at| (at, r.revision)))
+        .max();
+    if let Some((at, revision)) = from_revisions {
+        return Ok(Some(SignOff {
+            at,
+            revision: Some(revision),
+        }));
+    }
+
+    let metadata: serde_json::Value = job
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_default();
+    let signoff = &metadata["userSignoff"];
+    if signoff["state"].as_str() != Some(SIGNOFF_STATE_ACCEPTED) {
+        return Ok(None);
+    }
+    Ok(signoff["timestamp"]
+        .as_str()
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| SignOff {
+            at: ts.timestamp_millis(),
+            revision: None,
+        }))
+}
+
+/// Make a path from a plan comparable to git paths: `/` separators, relative to the project
+fn normalize_plan_path(path: &str, project_directory: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    let project = project_directory.replace('\\', "/");
+    let project = project.trim_end_matches('/');
+    let relative = if project.is_empty() {
+        path.as_str()
+    } else {
+        path.strip_prefix(project)
+            .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+            .unwrap_or(&path)
+    };
+    relative
+        .trim_start_matches("./")
+        .trim_start_matches('/')
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// A changed file matches a planned path when it is that file or lies under that directory
+fn path_matches(changed: &str, planned: &str) -> bool {
+    changed == planned
+        || changed
+            .strip_prefix(planned)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Match changed files (relative to the project) against each step's planned files
+fn compute_step_progress(
+    steps: Vec<(String, String, Vec<String>)>,
+    changed: &BTreeSet<String>,
+) -> (Vec<StepFileProgress>, Vec<String>) {
+    let steps: Vec<StepFileProgress> = steps
+        .into_iter()
+        .map(|(step_number, title, planned_files)| {
+            let touched_files: Vec<String> = planned_files
+                .iter()
+                .filter(|planned| changed.iter().any(|c| path_matches(c, planned)))
+                .cloned()
+                .collect();
+            let completion = if planned_files.is_empty() {
+                0.0
+            } else {
+                touched_files.len() as f64 / planned_files.len() as f64
+            };
+            StepFileProgress {
+                step_number,
+                title,
+                planned_files,
+                touched_files,
+                completion,
+            }
+        })
+        .collect();
+
+    let unplanned_files = changed
+        .iter()
+        .filter(|c| {
+            !steps
+                .iter()
+                .flat_map(|s| &s.planned_files)
+                .any(|planned| path_matches(c, planned))
+        })
+        .cloned()
+        .collect();
+
+    (steps, unplanned_files)
+}
+
+/// Planned files of each step of `plan_xml`, with titles, relative to the project
+pub(crate) fn plan_steps(
+    plan_xml: &str,
+    project_directory: &str,
+) -> Vec<(String, String, Vec<String>)> {
+    let mut titles = extract_step_headers_from_xml(plan_xml).into_iter();
+    extract_step_file_paths_from_xml(plan_xml)
+        .into_iter()
+        .map(|(number, paths)| {
+            let title = titles.next().map(|(_, title)| title).unwrap_or_default();
+            let paths = paths
+                .iter()
+                .map(|p| normalize_plan_path(p, project_directory))
+                .filter(|p| !p.is_empty())
+                .collect();
+            (number, title, paths)
+        })
+        .collect()
+}
+
+/// Read git changes since `since_ms` and return them relative to `project_directory`
+fn project_changes_since(
+    project_directory: &str,
+    since_ms: i64,
+) -> AppResult<(Vec<String>, BTreeSet<String>)> {
+    let changes = git_utils::get_changes_since(project_directory, since_ms)?;
+
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let prefix = canonical(Path::new(project_directory))
+        .strip_prefix(canonical(&changes.workdir))
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| PathBuf::new())
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    let paths = changes
+        .paths
+        .into_iter()
+        .filter_map(|path| {
+            if prefix.is_empty() {
+                Some(path)
+            } else {
+                path.strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .map(str::to_string)
+            }
+        })
+        .collect();
+    Ok((changes.commits, paths))
+}
+
+/// The latest accepted sign-off of a plan and the content that was signed off; the current
+/// content for sign-offs made before plans had revisions
+pub(crate) async fn signed_off_plan(
+    repo: &BackgroundJobRepository,
+    job: &BackgroundJob,
+) -> AppResult<(SignOff, String)> {
+    let signoff = latest_signoff(repo, job).await?.ok_or_else(|| {
+        AppError::ValidationError(format!("Plan {} has not been signed off", job.id))
+    })?;
+
+    let plan_xml = match signoff.revision {
+        Some(revision) => repo
+            .get_plan_revision(&job.id, revision)
+            .await?
+            .map(|r| r.content),
+        None => None,
+    }
+    .or_else(|| job.response.clone())
+    .unwrap_or_default();
+    Ok((signoff, plan_xml))
+}
+
+fn stored_progress(job: &BackgroundJob) -> Option<PlanProgress> {
+    let metadata: serde_json::Value = serde_json::from_str(job.metadata.as_deref()?).ok()?;
+    serde_json::from_value(metadata.get("planProgress")?.clone()).ok()
+}
+
+/// Last stored progress of a plan, if it has been checked
+pub async fn get_plan_progress(
+    app_handle: &AppHandle,
+    job_id: &str,
+) -> AppResult<Option<PlanProgress>> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    Ok(stored_progress(&job))
+}
+
+/// Re-read git for a signed-off plan, store the result as `planProgress` job metadata and
+/// announce it when it changed since the last check
+pub async fn check_plan_progress(app_handle: &AppHandle, job_id: &str) -> AppResult<PlanProgress> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    let (signoff, plan_xml) = signed_off_plan(&repo, &job).await?;
+
+    let session = SessionRepository::new(repo.get_pool())
+        .get_session_by_id(&job.session_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Session not found: {}", job.session_id)))?;
+    let project_directory = session.project_directory.clone();
+
+    let since = signoff.at;
+    let dir = project_directory.clone();
+    let (commits, changed) =
+        tokio::task::spawn_blocking(move || project_changes_since(&dir, since))
+            .await
+            .map_err(|e| AppError::InternalError(format!("Git check task failed: {}", e)))??;
+
+    let (steps, unplanned_files) =
+        compute_step_progress(plan_steps(&plan_xml, &project_directory), &changed);
+    let progress = PlanProgress {
+        job_id: job_id.to_string(),
+        signed_off_at: signoff.at,
+        signed_off_revision: signoff.revision,
+        checked_at: get_timestamp(),
+        commits,
+        total_steps: steps.len(),
+        steps_touched: steps.iter().filter(|s| !s.touched_files.is_empty()).count(),
+        steps_completed: steps
+            .iter()
+            .filter(|s| {
+                !s.planned_files.is_empty() && s.touched_files.len() == s.planned_files.len()
+            })
+            .count(),
+        steps,
+        unplanned_files,
+    };
+
+    let changed_since_last_check = stored_progress(&job)
+        .map(|previous| !previous.same_result(&progress))
+        .unwrap_or(true);
+
+    repo.update_job_metadata(job_id, &serde_json::json!({ "planProgress": progress }))
+        .await?;
+
+    if changed_since_last_check {
+        info!(
+            "Plan {} progress: {}/{} steps touched, {} unplanned files",
+            job_id,
+            progress.steps_touched,
+            progress.total_steps,
+            progress.unplanned_files.len()
+        );
+        if let Err(e) = app_handle.emit(
+            "device-link-event",
+            serde_json::json!({
+                "type": "PlanProgressUpdated",
+                "payload": {
+                    "jobId": job_id,
+                    "sessionId": job.session_id,
+                    "stepsTouched": progress.steps_touched,
+                    "stepsCompleted": progress.steps_completed,
+                    "totalSteps": progress.total_steps,
+                    "unplannedFileCount": progress.unplanned_files.len(),
+                    "checkedAt": progress.checked_at,
+                }
+            }),
+        ) {
+            warn!(
+                "Failed to emit PlanProgressUpdated device-link-event: {}",
+                e
+            );
+        }
+        publish_app_event(
+            app_handle,
+            AppEvent::PlanProgressUpdated {
+                job_id: job_id.to_string(),
+                session_id: job.session_id.clone(),
+                steps_touched: progress.steps_touched,
+                total_steps: progress.total_steps,
+                unplanned_files: progress.unplanned_files.len(),
+            },
+        );
+    }
+
+    Ok(progress)
+}
+
+/// Periodically check every plan accepted within the tracking window
+pub fn start_plan_progress_checker(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            let repo = job_repo(&app_handle);
+            let signoffs = match repo
+                .list_plan_signoffs_since(
+                    SIGNOFF_STATE_ACCEPTED,
+                    get_timestamp() - TRACKING_WINDOW_MS,
+                )
+                .await
+            {
+                Ok(signoffs) => signoffs,
+                Err(e) => {
+                    warn!("Plan progress checker could not list sign-offs: {}", e);
+                    continue;
+                }
+            };
+
+            for signoff in signoffs {
+                if let Err(e) = check_plan_progress(&app_handle, &signoff.job_id).await {
+                    debug!("Plan progress check for {} skipped: {}", signoff.job_id, e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_paths_are_made_project_relative() {
+        assert_eq!(
+            normalize_plan_path("/work/shop/src/cart.rs", "/work/shop/"),
+            "src/cart.rs"
+        );
+        assert_eq!(
+            normalize_plan_path("./src\\cart.rs", "/work/shop"),
+            "src/cart.rs"
+        );
+        assert_eq!(
+            normalize_plan_path("src/models/", "/work/shop"),
+            "src/models"
+        );
+    }
+
+    #[test]
+    fn steps_are_matched_against_changed_files() {
+        let steps = vec![
+            (
+                "1".to_string(),
+                "Schema".to_string(),
+                vec!["migrations/001.sql".to_string(), "src/models".to_string()],
+            ),
+            (
+                "2".to_string(),
+                "Cart".to_string(),
+                vec!["src/cart.rs".to_string()],
+            ),
+            ("3".to_string(), "Verify".to_string(), vec![]),
+        ];
+        let changed: BTreeSet<String> = ["src/models/order.rs", "src/models_old.rs", "README.md"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        let (steps, unplanned) = compute_step_progress(steps, &changed);
+        assert_eq!(steps[0].touched_files, vec!["src/models".to_string()]);
+        assert_eq!(steps[0].completion, 0.5);
+        assert!(steps[1].touched_files.is_empty());
+        assert_eq!(steps[2].completion, 0.0);
+        assert_eq!(
+            unplanned,
+            vec!["README.md".to_string(), "src/models_old.rs".to_string()]
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Versioned implementation plan content. Every edit becomes a numbered revision so the
+//! original model output is never lost, any two revisions can be diffed, earlier content
+//! can be restored, and sign-off applies to exactly the revision that was reviewed.
+
+use crate::db_utils::background_job_repository::{
+    NewPlanRevision, PlanRevision, PlanRevisionSummary, revision_sources,
+};
+use crate::db_utils::{BackgroundJobRepository, SessionRepository};
+use crate::error::{AppError, AppResult};
+use crate::models::{BackgroundJob, JobStatus};
+use crate::services::session_fork::count_changed_lines;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Unified diff between two revisions of a plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanRevisionDiff {
+    pub job_id: String,
+    pub from_revision: i64,
+    pub to_revision: i64,
+    pub patch: String,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+pub(crate) fn job_repo(app_handle: &AppHandle) -> Arc<BackgroundJobRepository> {
+    app_handle
+        .state::<Arc<BackgroundJobRepository>>()
+        .inner()
+        .clone()
+}
+
+pub(crate) async fn get_plan_job(
+    repo: &BackgroundJobRepository,
+    job_id: &str,
+) -> AppResult<BackgroundJob> {
+    if job_id.is_empty() {
+        return Err(AppError::ValidationError("Job ID is required".to_string()));
+    }
+
+    let job = repo
+        .get_job_by_id(job_id)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get job: {}", e)))?
+        .ok_or_else(|| AppError::NotFoundError(format!("Job not found: {}", job_id)))?;
+
+    if job.task_type != "implementation_plan" && job.task_type != "implementation_plan_merge" {
+        return Err(AppError::ValidationError(format!(
+            "Job is not an implementation plan: {}",
+            job_id
+        )));
+    }
+    Ok(job)
+}
+
+/// Make sure the plan's current content is captured as revision 1 before anything else
+async fn ensure_history(repo: &BackgroundJobRepository, job: &BackgroundJob) -> AppResult<()> {
+    match job.response.as_deref() {
+        Some(content) if !content.is_empty() => {
+            repo.ensure_initial_plan_revision(&job.id, content).await
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn require_revision(
+    repo: &BackgroundJobRepository,
+    job_id: &str,
+    revision: i64,
+) -> AppResult<PlanRevision> {
+    repo.get_plan_revision(job_id, revision)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFoundError(format!(
+                "Revision {} of plan {} not found",
+                revision, job_id
+            ))
+        })
+}
+
+/// Store `new_content` as a new revision and make it the plan's current content
+async fn apply_plan_content(
+    app_handle: &AppHandle,
+    job_id: &str,
+    new_content: &str,
+    source: &str,
+    restored_from: Option<i64>,
+) -> AppResult<PlanRevisionSummary> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    ensure_history(&repo, &job).await?;
+
+    let device_id = crate::auth::header_utils::get_device_id(app_handle)
+        .map_err(|e| warn!("Recording plan revision without device id: {}", e))
+        .ok();
+    let revision = repo
+        .record_plan_revision(
+            job_id,
+            NewPlanRevision {
+                content: new_content,
+                source,
+                author_device_id: device_id.as_deref(),
+                restored_from,
+            },
+        )
+        .await?;
+
+    repo.update_job_response(job_id, new_content, None, None, None, None, None)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update job response: {}", e)))?;
+
+    app_handle
+        .emit(
+            "device-link-event",
+            serde_json::json!({
+                "type": "PlanModified",
+                "payload": { "jobId": job_id, "revision": revision.revision }
+            }),
+        )
+        .ok();
+
+    crate::remote_api::handlers::jobs::invalidate_job_list_for_session(app_handle, &job.session_id);
+
+    // Resolve project_hash from session and invalidate project cache
+    let session_repo = SessionRepository::new(repo.get_pool());
+    if let Ok(Some(session)) = session_repo.get_session_by_id(&job.session_id).await {
+        crate::remote_api::handlers::jobs::invalidate_job_list_for_project(
+            app_handle,
+            &session.project_hash,
+        );
+    }
+
+    Ok(revision)
+}
+
+/// Record a hand edit of a plan as a new revision
+pub async fn edit_plan(
+    app_handle: &AppHandle,
+    job_id: &str,
+    new_content: &str,
+) -> AppResult<PlanRevisionSummary> {
+    let revision = apply_plan_content(
+        app_handle,
+        job_id,
+        new_content,
+        revision_sources::EDIT,
+        None,
+    )
+    .await?;
+    info!(
+        "Plan {} content is now revision {}",
+        job_id, revision.revision
+    );
+    Ok(revision)
+}
+
+/// Record a plan revised in a follow-up conversation as a new revision
+pub async fn record_refined_plan(
+    app_handle: &AppHandle,
+    job_id: &str,
+    new_content: &str,
+) -> AppResult<PlanRevisionSummary> {
+    let revision = apply_plan_content(
+        app_handle,
+        job_id,
+        new_content,
+        revision_sources::REFINEMENT,
+        None,
+    )
+    .await?;
+    info!(
+        "Refined plan {} is now revision {}",
+        job_id, revision.revision
+    );
+    Ok(revision)
+}
+
+/// Bring back an earlier revision's content as a new revision on top of the history
+pub async fn restore_plan_revision(
+    app_handle: &AppHandle,
+    job_id: &str,
+    revision: i64,
+) -> AppResult<PlanRevisionSummary> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    ensure_history(&repo, &job).await?;
+    let source = require_revision(&repo, job_id, revision).await?;
+
+    let restored = apply_plan_content(
+        app_handle,
+        job_id,
+        &source.content,
+        revision_sources::RESTORE,
+        Some(revision),
+    )
+    .await?;
+    info!(
+        "Restored plan {} revision {} as revision {}",
+        job_id, revision, restored.revision
+    );
+    Ok(restored)
+}
+
+pub async fn list_plan_revisions(
+    app_handle: &AppHandle,
+    job_id: &str,
+) -> AppResult<Vec<PlanRevisionSummary>> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    ensure_history(&repo, &job).await?;
+    repo.list_plan_revisions(job_id).await
+}
+
+pub async fn get_plan_revision(
+    app_handle: &AppHandle,
+    job_id: &str,
+    revision: i64,
+) -> AppResult<PlanRevision> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    ensure_history(&repo, &job).await?;
+    require_revision(&repo, job_id, revision).await
+}
+
+/// Unified diff from one revision to another; either order is allowed
+pub async fn diff_plan_revisions(
+    app_handle: &AppHandle,
+    job_id: &str,
+    from_revision: i64,
+    to_revision: i64,
+) -> AppResult<PlanRevisionDiff> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    ensure_history(&repo, &job).await?;
+
+    let from = require_revision(&repo, job_id, from_revision).await?;
+    let to = require_revision(&repo, job_id, to_revision).await?;
+    Ok(diff_revisions(&from, &to))
+}
+
+fn diff_revisions(from: &PlanRevision, to: &PlanRevision) -> PlanRevisionDiff {
+    let patch = diffy::create_patch(&from.content, &to.content);
+    let (lines_added, lines_removed) = count_changed_lines(&patch);
+    PlanRevisionDiff {
+        job_id: to.summary.job_id.clone(),
+        from_revision: from.summary.revision,
+        to_revision: to.summary.revision,
+        patch: patch.to_string(),
+        lines_added,
+        lines_removed,
+    }
+}
+
+/// Sign off a specific revision, or the current one when `revision` is `None`. The job
+/// metadata keeps a `userSignoff` summary pointing at the signed revision.
+pub async fn sign_off_plan(
+    app_handle: &AppHandle,
+    job_id: &str,
+    revision: Option<i64>,
+    state: &str,
+) -> AppResult<PlanRevisionSummary> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    ensure_history(&repo, &job).await?;
+
+    let revision = match revision {
+        Some(revision) => revision,
+        None => {
+            repo.get_latest_plan_revision(job_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::ValidationError(
+                        "Implementation plan has no content to sign off".to_string(),
+                    )
+                })?
+                .summary
+                .revision
+        }
+    };
+    let signed = repo.sign_off_plan_revision(job_id, revision, state).await?;
+
+    let mut meta: serde_json::Value = job
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    meta["userSignoff"] = serde_json::json!({
+        "state": state,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "revision": signed.revision,
+        "contentHash": signed.content_hash,
+    });
+    let metadata_str = serde_json::to_string(&meta)
+        .map_err(|e| AppError::ValidationError(format!("Failed to serialize metadata: {}", e)))?;
+
+    repo.update_job_status_with_metadata(
+        job_id,
+        &JobStatus::from_str(&job.status).unwrap_or(JobStatus::Completed),
+        None,
+        metadata_str,
+    )
+    .await
+    .map_err(|e| AppError::DatabaseError(format!("Failed to update job metadata: {}", e)))?;
+
+    info!(
+        "Marked implementation plan {} revision {} as signed off with state: {}",
+        job_id, signed.revision, state
+    );
+    Ok(signed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(number: i64, content: &str) -> PlanRevision {
+        PlanRevision {
+            summary: PlanRevisionSummary {
+                job_id: "plan".to_string(),
+                revision: number,
+                content_hash: String::new(),
+                source: revision_sources::EDIT.to_string(),
+                author_device_id: None,
+                restored_from: None,
+                signoff_state: None,
+                signed_off_at: None,
+                created_at: 0,
+            },
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_counts_changed_lines_in_either_direction() {
+        let v1 = revision(1, "<step>Add table</step>\n<step>Backfill</step>\n");
+        let v3 = revision(
+            3,
+            "<step>Add table</step>\n<step>Backfill in batches</step>\n<step>Drop old</step>\n",
+        );
+
+        let forward = diff_revisions(&v1, &v3);
+        assert_eq!((forward.from_revision, forward.to_revision), (1, 3));
+        assert_eq!((forward.lines_added, forward.lines_removed), (2, 1));
+        assert!(forward.patch.contains("+<step>Drop old</step>"));
+
+        let backward = diff_revisions(&v3, &v1);
+        assert_eq!((backward.lines_added, backward.lines_removed), (1, 2));
+    }
+}
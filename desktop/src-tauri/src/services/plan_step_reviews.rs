@@ -0,0 +1,366 @@
+//! Per-step review of implementation plans: status, assignee and comments anchored to the
+//! steps extracted from the plan XML. Every change is broadcast as a `device-link-event` so
+//! paired mobile devices can follow implementation progress.
+
+use crate::db_utils::background_job_repository::{
+    PlanStepComment, PlanStepState, PlanStepStateUpdate, step_statuses,
+};
+use crate::error::{AppError, AppResult};
+use crate::jobs::processors::utils::parsing_utils::extract_step_headers_from_xml;
+use crate::models::BackgroundJob;
+use crate::services::plan_revisions::{get_plan_job, job_repo};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// Review state of one plan step, with its comments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStepReview {
+    pub step_number: String,
+    pub title: String,
+    /// False when the step has review state but no longer appears in the plan content
+    pub in_plan: bool,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub updated_by_device_id: Option<String>,
+    pub updated_at: Option<i64>,
+    pub comments: Vec<PlanStepComment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStepProgress {
+    pub total: usize,
+    pub pending: usize,
+    pub in_progress: usize,
+    pub done: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStepReviews {
+    pub job_id: String,
+    pub steps: Vec<PlanStepReview>,
+    /// Counts over the steps that are still in the plan
+    pub progress: PlanStepProgress,
+}
+
+fn emit_step_event(app_handle: &AppHandle, event_type: &str, payload: serde_json::Value) {
+    if let Err(e) = app_handle.emit(
+        "device-link-event",
+        serde_json::json!({ "type": event_type, "payload": payload }),
+    ) {
+        warn!("Failed to emit {} device-link-event: {}", event_type, e);
+    }
+}
+
+fn device_id(app_handle: &AppHandle) -> Option<String> {
+    crate::auth::header_utils::get_device_id(app_handle)
+        .map_err(|e| warn!("Recording plan review change without device id: {}", e))
+        .ok()
+}
+
+fn step_headers(job: &BackgroundJob) -> Vec<(String, String)> {
+    job.response
+        .as_deref()
+        .map(extract_step_headers_from_xml)
+        .unwrap_or_default()
+}
+
+fn require_step(job: &BackgroundJob, step_number: &str) -> AppResult<()> {
+    if step_headers(job).iter().any(|(n, _)| n == step_number) {
+        Ok(())
+    } else {
+        Err(AppError::NotFoundError(format!(
+            "Step {} not found in plan {}",
+            step_number, job.id
+        )))
+    }
+}
+
+/// Steps of the plan in plan order, followed by steps that only exist in review state
+fn build_step_reviews(
+    job_id: &str,
+    headers: Vec<(String, String)>,
+    states: Vec<PlanStepState>,
+    comments: Vec<PlanStepComment>,
+) -> PlanStepReviews {
+    let mut steps: Vec<PlanStepReview> = headers
+        .into_iter()
+        .map(|(step_number, title)| PlanStepReview {
+            step_number,
+            title,
+            in_plan: true,
+            status: step_statuses::PENDING.to_string(),
+            assignee: None,
+            updated_by_device_id: None,
+            updated_at: None,
+            comments: Vec::new(),
+        })
+        .collect();
+
+    fn step_mut<'a>(steps: &'a mut Vec<PlanStepReview>, number: &str) -> &'a mut PlanStepReview {
+        match steps.iter().position(|s| s.step_number == number) {
+            Some(idx) => &mut steps[idx],
+            None => {
+                steps.push(PlanStepReview {
+                    step_number: number.to_string(),
+                    title: String::new(),
+                    in_plan: false,
+                    status: step_statuses::PENDING.to_string(),
+                    assignee: None,
+                    updated_by_device_id: None,
+                    updated_at: None,
+                    comments: Vec::new(),
+                });
+                steps.last_mut().expect("step was just pushed")
+            }
+        }
+    }
+
+    for state in states {
+        let step = step_mut(&mut steps, &state.step_number);
+        step.status = state.status;
+        step.assignee = state.assignee;
+        step.updated_by_device_id = state.updated_by_device_id;
+        step.updated_at = Some(state.updated_at);
+    }
+    for comment in comments {
+        step_mut(&mut steps, &comment.step_number)
+            .comments
+            .push(comment);
+    }
+
+    let mut progress = PlanStepProgress::default();
+    for step in steps.iter().filter(|s| s.in_plan) {
+        progress.total += 1;
+        match step.status.as_str() {
+            step_statuses::IN_PROGRESS => progress.in_progress += 1,
+            step_statuses::DONE => progress.done += 1,
+            step_statuses::SKIPPED => progress.skipped += 1,
+            _ => progress.pending += 1,
+        }
+    }
+
+    PlanStepReviews {
+        job_id: job_id.to_string(),
+        steps,
+        progress,
+    }
+}
+
+pub async fn get_plan_step_reviews(
+    app_handle: &AppHandle,
+    job_id: &str,
+) -> AppResult<PlanStepReviews> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    let states = repo.list_plan_step_states(job_id).await?;
+    let comments = repo.list_plan_step_comments(job_id).await?;
+    Ok(build_step_reviews(
+        job_id,
+        step_headers(&job),
+        states,
+        comments,
+    ))
+}
+
+/// Change a step's status and/or assignee. `assignee: Some(None)` (or an empty name)
+/// unassigns the step.
+pub async fn update_plan_step(
+    app_handle: &AppHandle,
+    job_id: &str,
+    step_number: &str,
+    status: Option<&str>,
+    assignee: Option<Option<&str>>,
+) -> AppResult<PlanStepState> {
+    if status.is_none() && assignee.is_none() {
+        return Err(AppError::ValidationError(
+            "Nothing to update: provide a status or an assignee".to_string(),
+        ));
+    }
+
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    require_step(&job, step_number)?;
+
+    let device_id = device_id(app_handle);
+    let state = repo
+        .update_plan_step_state(
+            job_id,
+            step_number,
+            PlanStepStateUpdate {
+                status,
+                assignee: assignee.map(|a| a.map(str::trim).filter(|a| !a.is_empty())),
+                device_id: device_id.as_deref(),
+            },
+        )
+        .await?;
+
+    emit_step_event(
+        app_handle,
+        "PlanStepUpdated",
+        serde_json::json!({
+            "jobId": job_id,
+            "stepNumber": state.step_number,
+            "status": state.status,
+            "assignee": state.assignee,
+            "updatedAt": state.updated_at,
+        }),
+    );
+    Ok(state)
+}
+
+pub async fn add_plan_step_comment(
+    app_handle: &AppHandle,
+    job_id: &str,
+    step_number: &str,
+    body: &str,
+) -> AppResult<PlanStepComment> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(AppError::ValidationError(
+            "Comment cannot be empty".to_string(),
+        ));
+    }
+
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    require_step(&job, step_number)?;
+
+    let plan_revision = repo
+        .get_latest_plan_revision(job_id)
+        .await?
+        .map(|r| r.summary.revision);
+    let device_id = device_id(app_handle);
+    let comment = repo
+        .add_plan_step_comment(
+            job_id,
+            step_number,
+            plan_revision,
+            body,
+            device_id.as_deref(),
+        )
+        .await?;
+
+    emit_step_event(
+        app_handle,
+        "PlanStepCommentAdded",
+        serde_json::json!({ "jobId": job_id, "comment": comment }),
+    );
+    Ok(comment)
+}
+
+pub async fn set_plan_step_comment_resolved(
+    app_handle: &AppHandle,
+    job_id: &str,
+    comment_id: &str,
+    resolved: bool,
+) -> AppResult<PlanStepComment> {
+    let repo = job_repo(app_handle);
+    get_plan_job(&repo, job_id).await?;
+    let comment = repo
+        .set_plan_step_comment_resolved(job_id, comment_id, resolved)
+        .await?;
+
+    emit_step_event(
+        app_handle,
+        "PlanStepCommentUpdated",
+        serde_json::json!({ "jobId": job_id, "comment": comment }),
+    );
+    Ok(comment)
+}
+
+pub async fn delete_plan_step_comment(
+    app_handle: &AppHandle,
+    job_id: &str,
+    comment_id: &str,
+) -> AppResult<()> {
+    let repo = job_repo(app_handle);
+    get_plan_job(&repo, job_id).await?;
+    repo.delete_plan_step_comment(job_id, comment_id).await?;
+
+    emit_step_event(
+        app_handle,
+        "PlanStepCommentDeleted",
+        serde_json::json!({ "jobId": job_id, "commentId": comment_id }),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(step_number: &str, status: &str) -> PlanStepState {
+        PlanStepState {
+            job_id: "plan".to_string(),
+            step_number: step_number.to_string(),
+            status: status.to_string(),
+            assignee: Some("dana".to_string()),
+            updated_by_device_id: None,
+            updated_at: 10,
+        }
+    }
+
+    fn comment(step_number: &str) -> PlanStepComment {
+        PlanStepComment {
+            id: format!("c-{}", step_number),
+            job_id: "plan".to_string(),
+            step_number: step_number.to_string(),
+            plan_revision: Some(1),
+            body: "Check this".to_string(),
+            author_device_id: None,
+            resolved: false,
+            created_at: 10,
+            updated_at: 10,
+        }
+    }
+
+    #[test]
+    fn reviews_follow_plan_order_and_keep_removed_steps() {
+        let headers = vec![
+            ("1".to_string(), "Add table".to_string()),
+            ("2".to_string(), "Backfill".to_string()),
+            ("3".to_string(), "Drop old".to_string()),
+        ];
+        let reviews = build_step_reviews(
+            "plan",
+            headers,
+            vec![
+                state("2", step_statuses::DONE),
+                state("4", step_statuses::IN_PROGRESS),
+            ],
+            vec![comment("1"), comment("4")],
+        );
+
+        let steps: Vec<_> = reviews
+            .steps
+            .iter()
+            .map(|s| (s.step_number.as_str(), s.status.as_str(), s.in_plan))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("1", step_statuses::PENDING, true),
+                ("2", step_statuses::DONE, true),
+                ("3", step_statuses::PENDING, true),
+                ("4", step_statuses::IN_PROGRESS, false),
+            ]
+        );
+        assert_eq!(reviews.steps[0].comments.len(), 1);
+        assert_eq!(reviews.steps[3].comments.len(), 1);
+        assert_eq!(
+            reviews.progress,
+            PlanStepProgress {
+                total: 3,
+                pending: 2,
+                in_progress: 0,
+                done: 1,
+                skipped: 0,
+            }
+        );
+    }
+}
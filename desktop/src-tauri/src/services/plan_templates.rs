@@ -0,0 +1,153 @@
+//! Plan templates selected per session. Templates come from the project file; a session uses
+//! the one it selected, or the project's default template when it has not selected any.
+//! The selection is kept in the key-value store.
+
+use crate::db_utils::{SessionRepository, SettingsRepository};
+use crate::error::{AppError, AppResult};
+use crate::services::plan_revisions::{get_plan_job, job_repo};
+use crate::utils::plan_templates::{
+    NO_PLAN_TEMPLATE, PlanTemplate, PlanTemplateCheck, check_plan_against_template,
+    project_plan_templates,
+};
+use crate::utils::project_config::active_project_config;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+fn selection_key(session_id: &str) -> String {
+    format!("plan_template:{}", session_id)
+}
+
+/// Plan templates of a project, empty when it has no valid project file
+pub fn list_plan_templates(project_directory: &str) -> Vec<PlanTemplate> {
+    active_project_config(project_directory)
+        .map(|loaded| project_plan_templates(&loaded.config))
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPlanTemplate {
+    /// Template id, `"none"` to opt out of the project default, `None` to use the default
+    pub selection: Option<String>,
+    /// Template plans of the session are generated with and checked against
+    pub template: Option<PlanTemplate>,
+}
+
+fn effective_template(
+    templates: Vec<PlanTemplate>,
+    selection: Option<&str>,
+) -> Option<PlanTemplate> {
+    match selection {
+        Some(NO_PLAN_TEMPLATE) => None,
+        Some(id) => templates.into_iter().find(|t| t.id == id),
+        None => templates.into_iter().find(|t| t.is_default),
+    }
+}
+
+pub async fn get_session_plan_template(
+    app_handle: &AppHandle,
+    session_id: &str,
+) -> AppResult<SessionPlanTemplate> {
+    let session = app_handle
+        .state::<Arc<SessionRepository>>()
+        .get_session_by_id(session_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Session not found: {}", session_id)))?;
+    let selection = app_handle
+        .state::<Arc<SettingsRepository>>()
+        .get_value(&selection_key(session_id))
+        .await?;
+
+    let template = effective_template(
+        list_plan_templates(&session.project_directory),
+        selection.as_deref(),
+    );
+    Ok(SessionPlanTemplate {
+        selection,
+        template,
+    })
+}
+
+/// Select a template for a session: a template id, `"none"`, or `None` for the project default
+pub async fn set_session_plan_template(
+    app_handle: &AppHandle,
+    session_id: &str,
+    template_id: Option<&str>,
+) -> AppResult<SessionPlanTemplate> {
+    let settings_repo = app_handle.state::<Arc<SettingsRepository>>();
+    match template_id.map(str::trim).filter(|id| !id.is_empty()) {
+        None => {
+            settings_repo
+                .delete_value(&selection_key(session_id))
+                .await?
+        }
+        Some(id) => {
+            if id != NO_PLAN_TEMPLATE {
+                let session = app_handle
+                    .state::<Arc<SessionRepository>>()
+                    .get_session_by_id(session_id)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::NotFoundError(format!("Session not found: {}", session_id))
+                    })?;
+                if !list_plan_templates(&session.project_directory)
+                    .iter()
+                    .any(|t| t.id == id)
+                {
+                    return Err(AppError::ValidationError(format!(
+                        "Project has no plan template named '{}'",
+                        id
+                    )));
+                }
+            }
+            settings_repo
+                .set_value(&selection_key(session_id), id)
+                .await?
+        }
+    }
+    get_session_plan_template(app_handle, session_id).await
+}
+
+/// Check a plan's current content against its session's template, or against `template_id`,
+/// and store the result as `planTemplateCheck` job metadata. `None` when no template applies.
+pub async fn check_plan_template(
+    app_handle: &AppHandle,
+    job_id: &str,
+    template_id: Option<&str>,
+) -> AppResult<Option<PlanTemplateCheck>> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+
+    let template = match template_id {
+        Some(id) => {
+            let session = SessionRepository::new(repo.get_pool())
+                .get_session_by_id(&job.session_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFoundError(format!("Session not found: {}", job.session_id))
+                })?;
+            Some(
+                list_plan_templates(&session.project_directory)
+                    .into_iter()
+                    .find(|t| t.id == id)
+                    .ok_or_else(|| {
+                        AppError::NotFoundError(format!("Plan template not found: {}", id))
+                    })?,
+            )
+        }
+        None => {
+            get_session_plan_template(app_handle, &job.session_id)
+                .await?
+                .template
+        }
+    };
+    let Some(template) = template else {
+        return Ok(None);
+    };
+
+    let check = check_plan_against_template(job.response.as_deref().unwrap_or_default(), &template);
+    repo.update_job_metadata(job_id, &serde_json::json!({ "planTemplateCheck": check }))
+        .await?;
+    Ok(Some(check))
+}
@@ -0,0 +1,169 @@
+//! Test plans for signed-off implementation plans. A generation job reads the signed-off
+//! revision and the existing tests near the files it touches; the resulting test plan is stored
+//! with the plan as its `testPlan` metadata, so it travels with session bundles and is appended
+//! to the plan's Markdown export.
+
+use crate::db_utils::SessionRepository;
+use crate::error::{AppError, AppResult};
+use crate::events::job_events::{JobMetadataUpdatedEvent, emit_job_metadata_updated};
+use crate::jobs::types::{JobPayload, PlanTestGenerationPayload};
+use crate::models::{BackgroundJob, TaskType};
+use crate::services::plan_progress;
+use crate::services::plan_revisions::{get_plan_job, job_repo};
+use crate::utils::test_plans::PlanTestPlan;
+use log::info;
+use tauri::AppHandle;
+
+/// Metadata key of a plan's test plan
+pub const TEST_PLAN_METADATA_KEY: &str = "testPlan";
+
+/// The test plan stored with a plan job, if one was generated
+pub fn stored_test_plan(job: &BackgroundJob) -> Option<PlanTestPlan> {
+    let metadata: serde_json::Value = serde_json::from_str(job.metadata.as_deref()?).ok()?;
+    serde_json::from_value(metadata.get(TEST_PLAN_METADATA_KEY)?.clone()).ok()
+}
+
+/// The plan's Markdown with its test plan appended, when one was generated
+pub fn append_test_plan_markdown(markdown: String, job: &BackgroundJob) -> String {
+    match stored_test_plan(job) {
+        Some(test_plan) => format!("{}\n\n{}", markdown.trim_end(), test_plan.to_markdown()),
+        None => markdown,
+    }
+}
+
+pub async fn get_plan_test_plan(
+    app_handle: &AppHandle,
+    job_id: &str,
+) -> AppResult<Option<PlanTestPlan>> {
+    let repo = job_repo(app_handle);
+    let job = get_plan_job(&repo, job_id).await?;
+    Ok(stored_test_plan(&job))
+}
+
+/// Queue a test plan generation for the signed-off revision of a plan
+pub async fn start_plan_test_generation(app_handle: &AppHandle, job_id: &str) -> AppResult<String> {
+    let repo = job_repo(app_handle);
+    let plan = get_plan_job(&repo, job_id).await?;
+    let (signoff, plan_xml) = plan_progress::signed_off_plan(&repo, &plan).await?;
+    if plan_xml.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Signed-off plan has no content".to_string(),
+        ));
+    }
+
+    let session = SessionRepository::new(repo.get_pool())
+        .get_session_by_id(&plan.session_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFoundError(format!("Session not found: {}", plan.session_id))
+        })?;
+    let model_settings = crate::utils::config_resolver::resolve_model_settings(
+        app_handle,
+        TaskType::PlanTestGeneration,
+        &session.project_directory,
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    let test_job_id = crate::utils::job_creation_utils::create_and_queue_background_job(
+        &session.id,
+        &session.project_directory,
+        "openrouter",
+        TaskType::PlanTestGeneration,
+        "PLAN_TEST_GENERATION",
+        &format!("Test plan for implementation plan {}", job_id),
+        model_settings,
+        JobPayload::PlanTestGeneration(PlanTestGenerationPayload {
+            plan_job_id: job_id.to_string(),
+            plan_revision: signoff.revision,
+        }),
+        2,
+        None,
+        None,
+        Some(serde_json::json!({ "planJobId": job_id })),
+        app_handle,
+    )
+    .await?;
+
+    info!(
+        "Queued test plan generation {} for plan {} (revision {:?})",
+        test_job_id, job_id, signoff.revision
+    );
+    Ok(test_job_id)
+}
+
+/// Store a generated test plan with its plan, replacing any earlier one
+pub(crate) async fn store_plan_test_plan(
+    app_handle: &AppHandle,
+    plan_job_id: &str,
+    test_plan: &PlanTestPlan,
+) -> AppResult<()> {
+    let repo = job_repo(app_handle);
+    let plan = get_plan_job(&repo, plan_job_id).await?;
+
+    // The cached Markdown export predates this test plan; it is rebuilt on the next export
+    let patch = serde_json::json!({
+        TEST_PLAN_METADATA_KEY: test_plan,
+        "markdownResponse": null,
+        "markdownConversionStatus": null,
+    });
+    repo.update_job_metadata(plan_job_id, &patch).await?;
+    emit_job_metadata_updated(
+        app_handle,
+        JobMetadataUpdatedEvent {
+            job_id: plan_job_id.to_string(),
+            session_id: plan.session_id,
+            metadata_patch: patch,
+        },
+    );
+
+    info!(
+        "Stored test plan with {} tests for plan {}",
+        test_plan.test_count(),
+        plan_job_id
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::test_job;
+    use crate::utils::test_plans::{PlanStepTests, PlanTestCase};
+
+    #[test]
+    fn test_plan_is_appended_to_the_plan_markdown() {
+        let test_plan = PlanTestPlan {
+            test_job_id: "tests".to_string(),
+            plan_revision: Some(2),
+            generated_at: 1_700_000_000_000,
+            reference_files: vec![],
+            steps: vec![PlanStepTests {
+                step_number: "1".to_string(),
+                title: "Add parser".to_string(),
+                tests: vec![PlanTestCase {
+                    name: "parses_empty_input".to_string(),
+                    file: "src/parser.rs".to_string(),
+                    description: None,
+                    code: String::new(),
+                }],
+            }],
+        };
+        let plan = test_job("plan", "s1", "implementation_plan");
+        assert_eq!(
+            append_test_plan_markdown("# Plan\n".to_string(), &plan),
+            "# Plan\n"
+        );
+
+        let plan = BackgroundJob {
+            metadata: Some(serde_json::json!({ TEST_PLAN_METADATA_KEY: test_plan }).to_string()),
+            ..plan
+        };
+        assert_eq!(
+            append_test_plan_markdown("# Plan\n".to_string(), &plan),
+            format!("# Plan\n\n{}", test_plan.to_markdown())
+        );
+    }
+}
@@ -0,0 +1,755 @@
+//! Portable session bundles: a single versioned JSON archive holding a session, its
+//! task-description and file-selection history, its finished jobs (plans, file finder
+//! results, web research) and optional snapshots of the files the session references.
+
+use crate::db_utils::session_repository::{FileHistoryState, SessionRepository, TaskHistoryState};
+use crate::db_utils::BackgroundJobRepository;
+use crate::error::{AppError, AppResult};
+use crate::models::{BackgroundJob, JobStatus, Session, TaskType};
+use crate::utils::hash_utils::{hash_string, sha256_hash};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Identifies the file as a session bundle
+pub const SESSION_BUNDLE_FORMAT: &str = "plantocode-session-bundle";
+
+/// Bumped whenever the bundle layout changes incompatibly
+pub const SESSION_BUNDLE_VERSION: u32 = 1;
+
+/// Files larger than this are left out of the snapshots
+const MAX_SNAPSHOT_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Job types whose results travel with an exported session
+const BUNDLED_TASK_TYPES: [TaskType; 12] = [
+    TaskType::ImplementationPlan,
+    TaskType::ImplementationPlanMerge,
+    TaskType::FileFinderWorkflow,
+    TaskType::RegexFileFilter,
+    TaskType::RootFolderSelection,
+    TaskType::FileRelevanceAssessment,
+    TaskType::ExtendedPathFinder,
+    TaskType::ImpactAnalysis,
+    TaskType::WebSearchPromptsGeneration,
+    TaskType::WebSearchExecution,
+    TaskType::WebSearchWorkflow,
+    TaskType::VideoAnalysis,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionBundleManifest {
+    pub format: String,
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: i64,
+    pub source_session_id: String,
+    pub source_project_directory: String,
+    pub source_project_hash: String,
+    pub job_count: usize,
+    pub file_snapshot_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSnapshot {
+    /// Path relative to the project directory
+    pub path: String,
+    pub sha256: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionBundle {
+    pub manifest: SessionBundleManifest,
+    pub session: Session,
+    pub task_history: TaskHistoryState,
+    pub file_history: FileHistoryState,
+    pub jobs: Vec<BackgroundJob>,
+    #[serde(default)]
+    pub file_snapshots: Vec<FileSnapshot>,
+}
+
+/// Session data rewritten for the importing checkout, ready to insert
+#[derive(Debug, Clone)]
+pub struct RemappedSessionBundle {
+    pub session: Session,
+    pub task_history: TaskHistoryState,
+    pub file_history: FileHistoryState,
+    pub jobs: Vec<BackgroundJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FileDriftStatus {
+    Missing,
+    Modified,
+    /// The path is absolute or leaves the project, so the file was not checked
+    Rejected,
+}
+
+/// A snapshotted file that differs in the importing checkout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDrift {
+    pub path: String,
+    pub status: FileDriftStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionImportResult {
+    pub session: Session,
+    pub imported_job_count: usize,
+    pub file_drift: Vec<FileDrift>,
+}
+
+/// Collect a session and its related data into a bundle
+pub async fn export_session_bundle(
+    session_repo: &SessionRepository,
+    job_repo: &BackgroundJobRepository,
+    session_id: &str,
+    include_file_snapshots: bool,
+) -> AppResult<SessionBundle> {
+    let session = session_repo
+        .get_session_by_id(session_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Session not found: {}", session_id)))?;
+
+    let task_history = session_repo.get_task_history_state(session_id).await?;
+    let file_history = session_repo.get_file_history_state(session_id).await?;
+
+    let mut jobs: Vec<BackgroundJob> = job_repo
+        .get_jobs_by_session_id(session_id)
+        .await?
+        .into_iter()
+        .filter(is_bundled_job)
+        .collect();
+    // Oldest first so the import order matches the original history
+    jobs.sort_by_key(|job| job.created_at);
+
+    let file_snapshots = if include_file_snapshots {
+        snapshot_files(&session.project_directory, &session.included_files)
+    } else {
+        Vec::new()
+    };
+
+    info!(
+        "Exporting session {} with {} jobs and {} file snapshots",
+        session_id,
+        jobs.len(),
+        file_snapshots.len()
+    );
+
+    Ok(SessionBundle {
+        manifest: SessionBundleManifest {
+            format: SESSION_BUNDLE_FORMAT.to_string(),
+            format_version: SESSION_BUNDLE_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: chrono::Utc::now().timestamp_millis(),
+            source_session_id: session.id.clone(),
+            source_project_directory: session.project_directory.clone(),
+            source_project_hash: session.project_hash.clone(),
+            job_count: jobs.len(),
+            file_snapshot_count: file_snapshots.len(),
+        },
+        session,
+        task_history,
+        file_history,
+        jobs,
+        file_snapshots,
+    })
+}
+
+/// Import a bundle as a new session rooted at `target_project_directory`
+pub async fn import_session_bundle(
+    session_repo: &SessionRepository,
+    bundle: SessionBundle,
+    target_project_directory: &str,
+) -> AppResult<SessionImportResult> {
+    validate_manifest(&bundle.manifest)?;
+
+    let file_drift = detect_file_drift(target_project_directory, &bundle.file_snapshots);
+    let remapped = remap_bundle(bundle, target_project_directory);
+
+    session_repo
+        .import_session_bundle(
+            &remapped.session,
+            &remapped.task_history,
+            &remapped.file_history,
+            &remapped.jobs,
+        )
+        .await?;
+
+    info!(
+        "Imported session bundle as {} into {} ({} jobs, {} drifted files)",
+        remapped.session.id,
+        target_project_directory,
+        remapped.jobs.len(),
+        file_drift.len()
+    );
+
+    Ok(SessionImportResult {
+        imported_job_count: remapped.jobs.len(),
+        session: remapped.session,
+        file_drift,
+    })
+}
+
+pub fn read_bundle(path: &Path) -> AppResult<SessionBundle> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        AppError::FileSystemError(format!(
+            "Failed to read session bundle {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let bundle: SessionBundle = serde_json::from_slice(&bytes).map_err(|e| {
+        AppError::ValidationError(format!("Invalid session bundle {}: {}", path.display(), e))
+    })?;
+    validate_manifest(&bundle.manifest)?;
+    Ok(bundle)
+}
+
+pub fn write_bundle(bundle: &SessionBundle, path: &Path) -> AppResult<()> {
+    let json = serde_json::to_vec_pretty(bundle)
+        .map_err(|e| AppError::SerializationError(e.to_string()))?;
+    std::fs::write(path, json).map_err(|e| {
+        AppError::FileSystemError(format!(
+            "Failed to write session bundle {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn validate_manifest(manifest: &SessionBundleManifest) -> AppResult<()> {
+    if manifest.format != SESSION_BUNDLE_FORMAT {
+        return Err(AppError::ValidationError(format!(
+            "Not a session bundle (format '{}')",
+            manifest.format
+        )));
+    }
+    if manifest.format_version == 0 || manifest.format_version > SESSION_BUNDLE_VERSION {
+        return Err(AppError::ValidationError(format!(
+            "Session bundle version {} is not supported (this app reads up to version {})",
+            manifest.format_version, SESSION_BUNDLE_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn is_bundled_job(job: &BackgroundJob) -> bool {
+    let finished = JobStatus::from_str(&job.status)
+        .map(|status| matches!(status, JobStatus::Completed | JobStatus::CompletedByTag))
+        .unwrap_or(false);
+    let bundled_type = TaskType::from_str(&job.task_type)
+        .map(|task_type| BUNDLED_TASK_TYPES.contains(&task_type))
+        .unwrap_or(false);
+    finished && bundled_type
+}
+
+/// Ids and the checkout directory to rewrite when a bundle becomes a new session
+struct BundleRemap<'a> {
+    ids: HashMap<String, String>,
+    source_directory: &'a str,
+    target_directory: &'a str,
+}
+
+impl BundleRemap<'_> {
+    /// Metadata is rewritten value by value so escaped strings (e.g. Windows paths) match;
+    /// metadata that isn't JSON is treated as free text
+    fn remap_metadata(&self, metadata: &str) -> String {
+        match serde_json::from_str::<serde_json::Value>(metadata) {
+            Ok(mut value) => {
+                self.remap_value(&mut value);
+                serde_json::to_string(&value).unwrap_or_else(|_| metadata.to_string())
+            }
+            Err(_) => self.remap_text(metadata),
+        }
+    }
+
+    fn remap_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(text) => *text = self.remap_string(text),
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.remap_value(item);
+                }
+            }
+            serde_json::Value::Object(fields) => {
+                for field in fields.values_mut() {
+                    self.remap_value(field);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Ids and paths are replaced whole; other strings are remapped as free text
+    fn remap_string(&self, text: &str) -> String {
+        if let Some(id) = self.ids.get(text) {
+            return id.clone();
+        }
+        let remapped = remap_path(text, self.source_directory, self.target_directory);
+        if remapped != text {
+            return remapped;
+        }
+        self.remap_text(text)
+    }
+
+    /// Free text such as prompts: ids and the checkout directory are replaced where they occur
+    fn remap_text(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (from, to) in &self.ids {
+            text = text.replace(from.as_str(), to);
+        }
+        if self.source_directory.is_empty() || self.source_directory == self.target_directory {
+            text
+        } else {
+            text.replace(self.source_directory, self.target_directory)
+        }
+    }
+}
+
+/// Assign fresh ids and move the session onto a different checkout. Job ids, the
+/// session id and the old project directory are rewritten in job metadata and
+/// prompts so workflow links between jobs stay intact.
+pub fn remap_bundle(bundle: SessionBundle, target_project_directory: &str) -> RemappedSessionBundle {
+    let now = chrono::Utc::now().timestamp_millis();
+    let source_directory = bundle.session.project_directory.clone();
+    let new_session_id = format!("session_{}_{}", now, Uuid::new_v4());
+
+    let job_ids: HashMap<String, String> = bundle
+        .jobs
+        .iter()
+        .map(|job| (job.id.clone(), Uuid::new_v4().to_string()))
+        .collect();
+    let mut ids = job_ids.clone();
+    ids.insert(bundle.session.id.clone(), new_session_id.clone());
+    let remap = BundleRemap {
+        ids,
+        source_directory: &source_directory,
+        target_directory: target_project_directory,
+    };
+
+    let remap_file = |path: &String| remap_path(path, &source_directory, target_project_directory);
+
+    let session = Session {
+        id: new_session_id.clone(),
+        project_directory: target_project_directory.to_string(),
+        project_hash: hash_string(target_project_directory),
+        updated_at: now,
+        included_files: bundle.session.included_files.iter().map(remap_file).collect(),
+        force_excluded_files: bundle
+            .session
+            .force_excluded_files
+            .iter()
+            .map(remap_file)
+            .collect(),
+        ..bundle.session
+    };
+
+    let file_history = FileHistoryState {
+        entries: bundle
+            .file_history
+            .entries
+            .into_iter()
+            .map(|mut entry| {
+                entry.included_files =
+                    remap_file_list(&entry.included_files, &source_directory, target_project_directory);
+                entry.force_excluded_files = remap_file_list(
+                    &entry.force_excluded_files,
+                    &source_directory,
+                    target_project_directory,
+                );
+                entry
+            })
+            .collect(),
+        ..bundle.file_history
+    };
+
+    let jobs = bundle
+        .jobs
+        .into_iter()
+        .map(|job| BackgroundJob {
+            id: job_ids[&job.id].clone(),
+            session_id: new_session_id.clone(),
+            prompt: remap.remap_text(&job.prompt),
+            metadata: job.metadata.map(|metadata| remap.remap_metadata(&metadata)),
+            ..job
+        })
+        .collect();
+
+    RemappedSessionBundle {
+        session,
+        task_history: bundle.task_history,
+        file_history,
+        jobs,
+    }
+}
+
+/// Relative paths are kept; absolute paths under the source checkout are moved
+fn remap_path(path: &str, source_directory: &str, target_directory: &str) -> String {
+    match Path::new(path).strip_prefix(source_directory) {
+        Ok(relative) if !source_directory.is_empty() && relative.as_os_str().is_empty() => {
+            target_directory.to_string()
+        }
+        Ok(relative) if !source_directory.is_empty() => Path::new(target_directory)
+            .join(relative)
+            .to_string_lossy()
+            .into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+/// File history lists are stored as JSON arrays; anything else is kept as-is
+fn remap_file_list(list: &str, source_directory: &str, target_directory: &str) -> String {
+    match serde_json::from_str::<Vec<String>>(list) {
+        Ok(paths) => {
+            let remapped: Vec<String> = paths
+                .iter()
+                .map(|path| remap_path(path, source_directory, target_directory))
+                .collect();
+            serde_json::to_string(&remapped).unwrap_or_else(|_| list.to_string())
+        }
+        Err(_) => list.to_string(),
+    }
+}
+
+fn resolve_in_project(project_directory: &str, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        Path::new(project_directory).join(path)
+    }
+}
+
+/// Resolve a path taken from a bundle inside the project. Bundles are untrusted, so
+/// absolute paths, `..` components and symlinks leading out of the project are refused.
+fn resolve_bundle_path(project_directory: &str, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path);
+    let stays_inside = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if path.is_empty() || !stays_inside {
+        return None;
+    }
+
+    let root = Path::new(project_directory);
+    let full_path = root.join(relative);
+    match (full_path.canonicalize(), root.canonicalize()) {
+        (Ok(resolved), Ok(root)) if !resolved.starts_with(&root) => None,
+        _ => Some(full_path),
+    }
+}
+
+fn relative_to_project(project_directory: &str, path: &str) -> String {
+    Path::new(path)
+        .strip_prefix(project_directory)
+        .map(|relative| relative.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// Read the session's included files; binary, oversized or unreadable files are skipped
+fn snapshot_files(project_directory: &str, files: &[String]) -> Vec<FileSnapshot> {
+    let mut snapshots = Vec::new();
+    for file in files {
+        let full_path = resolve_in_project(project_directory, file);
+        match std::fs::metadata(&full_path) {
+            Ok(meta) if meta.len() > MAX_SNAPSHOT_FILE_BYTES => {
+                warn!(
+                    "Skipping snapshot of {} ({} bytes exceeds limit)",
+                    full_path.display(),
+                    meta.len()
+                );
+                continue;
+            }
+            Err(e) => {
+                warn!("Skipping snapshot of {}: {}", full_path.display(), e);
+                continue;
+            }
+            Ok(_) => {}
+        }
+
+        match std::fs::read_to_string(&full_path) {
+            Ok(content) => snapshots.push(FileSnapshot {
+                path: relative_to_project(project_directory, file),
+                sha256: sha256_hash(&content),
+                content,
+            }),
+            Err(e) => warn!("Skipping snapshot of {}: {}", full_path.display(), e),
+        }
+    }
+    snapshots
+}
+
+/// Compare snapshots against the importing checkout without touching any files
+fn detect_file_drift(target_project_directory: &str, snapshots: &[FileSnapshot]) -> Vec<FileDrift> {
+    snapshots
+        .iter()
+        .filter_map(|snapshot| {
+            let Some(path) = resolve_bundle_path(target_project_directory, &snapshot.path) else {
+                warn!("Skipping bundle snapshot with unsafe path {}", snapshot.path);
+                return Some(FileDrift {
+                    path: snapshot.path.clone(),
+                    status: FileDriftStatus::Rejected,
+                });
+            };
+            let status = match std::fs::read_to_string(&path) {
+                Ok(content) if sha256_hash(&content) == snapshot.sha256 => return None,
+                Ok(_) => FileDriftStatus::Modified,
+                Err(_) => FileDriftStatus::Missing,
+            };
+            Some(FileDrift {
+                path: snapshot.path.clone(),
+                status,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{insert_job, insert_session, test_job, test_pool, test_session};
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("session-bundle-{}-{}", name, Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        dir
+    }
+
+    fn bundle_session(id: &str, project_directory: &str) -> Session {
+        Session {
+            name: "Checkout flow".to_string(),
+            task_description: Some("Add express checkout".to_string()),
+            included_files: vec!["src/main.rs".to_string()],
+            ..test_session(id, project_directory)
+        }
+    }
+
+    fn bundle_job(id: &str, session_id: &str, task_type: &str, status: &str, metadata: &str) -> BackgroundJob {
+        BackgroundJob {
+            status: status.to_string(),
+            response: Some(format!("response of {}", id)),
+            tokens_sent: Some(10),
+            tokens_received: Some(20),
+            cache_write_tokens: Some(0),
+            cache_read_tokens: Some(0),
+            model_used: Some("openai/gpt-5".to_string()),
+            actual_cost: Some(0.01),
+            metadata: Some(metadata.to_string()),
+            created_at: 1_700_000_000,
+            updated_at: Some(1_700_000_000),
+            is_finalized: Some(true),
+            ..test_job(id, session_id, task_type)
+        }
+    }
+
+    #[tokio::test]
+    async fn export_then_import_round_trips_onto_new_checkout() {
+        let pool = test_pool().await;
+        let session_repo = SessionRepository::new(pool.clone());
+        let job_repo = BackgroundJobRepository::new(pool.clone());
+
+        let source_dir = temp_project("source");
+        let source = source_dir.to_string_lossy().into_owned();
+        std::fs::write(source_dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let session = bundle_session("session_source", &source);
+        insert_session(&pool, &session).await;
+        session_repo
+            .sync_task_description_history(&session.id, &["Add checkout".to_string(), "Add express checkout".to_string()])
+            .await
+            .unwrap();
+        session_repo
+            .sync_file_selection_history(&session.id, &[(r#"["src/main.rs"]"#.to_string(), "[]".to_string(), 1_700_000_000_000)])
+            .await
+            .unwrap();
+
+        let finder = bundle_job(
+            "job-finder",
+            &session.id,
+            "file_finder_workflow",
+            "completed",
+            &format!(r#"{{"projectDirectory":"{}"}}"#, source),
+        );
+        let plan = bundle_job(
+            "job-plan",
+            &session.id,
+            "implementation_plan",
+            "completed",
+            r#"{"sourceJobId":"job-finder"}"#,
+        );
+        let running = bundle_job("job-running", &session.id, "implementation_plan", "running", "{}");
+        for job in [&finder, &plan, &running] {
+            insert_job(&pool, job).await;
+        }
+
+        let bundle = export_session_bundle(&session_repo, &job_repo, &session.id, true)
+            .await
+            .unwrap();
+        assert_eq!(bundle.manifest.job_count, 2);
+        assert_eq!(bundle.manifest.file_snapshot_count, 1);
+
+        let bundle_path = source_dir.join("session.bundle.json");
+        write_bundle(&bundle, &bundle_path).unwrap();
+        let bundle = read_bundle(&bundle_path).unwrap();
+
+        let target_dir = temp_project("target");
+        let target = target_dir.to_string_lossy().into_owned();
+        std::fs::write(target_dir.join("src/main.rs"), "fn main() { checkout(); }\n").unwrap();
+
+        let result = import_session_bundle(&session_repo, bundle, &target).await.unwrap();
+        let imported = session_repo
+            .get_session_by_id(&result.session.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(imported.id, session.id);
+        assert_eq!(imported.project_directory, target);
+        assert_eq!(imported.project_hash, hash_string(&target));
+        assert_eq!(imported.included_files, session.included_files);
+        assert_eq!(imported.task_description, session.task_description);
+
+        let task_history = session_repo.get_task_history_state(&imported.id).await.unwrap();
+        let descriptions: Vec<_> = task_history.entries.iter().map(|e| e.description.as_str()).collect();
+        assert_eq!(descriptions, ["Add checkout", "Add express checkout"]);
+        let file_history = session_repo.get_file_history_state(&imported.id).await.unwrap();
+        assert_eq!(file_history.entries.len(), 1);
+
+        let jobs = job_repo.get_jobs_by_session_id(&imported.id).await.unwrap();
+        assert_eq!(result.imported_job_count, 2);
+        assert_eq!(jobs.len(), 2);
+        let imported_finder = jobs.iter().find(|j| j.task_type == "file_finder_workflow").unwrap();
+        let imported_plan = jobs.iter().find(|j| j.task_type == "implementation_plan").unwrap();
+        assert_ne!(imported_finder.id, finder.id);
+        assert_eq!(imported_plan.response, plan.response);
+        assert!(imported_plan.metadata.as_deref().unwrap().contains(&imported_finder.id));
+        assert!(imported_finder.metadata.as_deref().unwrap().contains(&target));
+
+        assert_eq!(result.file_drift.len(), 1);
+        assert_eq!(result.file_drift[0].status, FileDriftStatus::Modified);
+
+        // The source session is left untouched
+        assert!(session_repo.get_session_by_id(&session.id).await.unwrap().is_some());
+        assert_eq!(job_repo.get_jobs_by_session_id(&session.id).await.unwrap().len(), 3);
+
+        let _ = std::fs::remove_dir_all(source_dir);
+        let _ = std::fs::remove_dir_all(target_dir);
+    }
+
+    #[test]
+    fn rejects_bundles_from_newer_versions() {
+        let manifest = SessionBundleManifest {
+            format: SESSION_BUNDLE_FORMAT.to_string(),
+            format_version: SESSION_BUNDLE_VERSION + 1,
+            app_version: "9.9.9".to_string(),
+            exported_at: 0,
+            source_session_id: "s".to_string(),
+            source_project_directory: "/tmp/a".to_string(),
+            source_project_hash: hash_string("/tmp/a"),
+            job_count: 0,
+            file_snapshot_count: 0,
+        };
+        assert!(validate_manifest(&manifest).is_err());
+
+        let other_format = SessionBundleManifest {
+            format: "something-else".to_string(),
+            format_version: SESSION_BUNDLE_VERSION,
+            ..manifest
+        };
+        assert!(validate_manifest(&other_format).is_err());
+    }
+
+    #[test]
+    fn drift_detection_rejects_paths_outside_the_project() {
+        let project_dir = temp_project("drift");
+        let project = project_dir.to_string_lossy().into_owned();
+        let outside = project_dir.parent().unwrap().join(format!("outside-{}.txt", Uuid::new_v4()));
+        std::fs::write(&outside, "secret").unwrap();
+        std::fs::write(project_dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let snapshot = |path: String| FileSnapshot {
+            path,
+            sha256: sha256_hash("secret"),
+            content: "secret".to_string(),
+        };
+        let outside_name = outside.file_name().unwrap().to_string_lossy().into_owned();
+        let snapshots = vec![
+            snapshot(outside.to_string_lossy().into_owned()),
+            snapshot(format!("../{}", outside_name)),
+            snapshot(format!("src/../../{}", outside_name)),
+            snapshot("src/main.rs".to_string()),
+        ];
+
+        let drift = detect_file_drift(&project, &snapshots);
+        let statuses: Vec<_> = drift.iter().map(|d| d.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            [
+                FileDriftStatus::Rejected,
+                FileDriftStatus::Rejected,
+                FileDriftStatus::Rejected,
+                FileDriftStatus::Modified,
+            ]
+        );
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&outside, project_dir.join("src/link.txt")).unwrap();
+            let drift = detect_file_drift(&project, &[snapshot("src/link.txt".to_string())]);
+            assert_eq!(drift[0].status, FileDriftStatus::Rejected);
+        }
+
+        let _ = std::fs::remove_file(outside);
+        let _ = std::fs::remove_dir_all(project_dir);
+    }
+
+    #[test]
+    fn remap_path_moves_absolute_paths_and_keeps_relative_ones() {
+        assert_eq!(remap_path("src/lib.rs", "/home/a/proj", "/work/proj"), "src/lib.rs");
+        assert_eq!(
+            remap_path("/home/a/proj/src/lib.rs", "/home/a/proj", "/work/proj"),
+            "/work/proj/src/lib.rs"
+        );
+        assert_eq!(remap_path("/etc/hosts", "/home/a/proj", "/work/proj"), "/etc/hosts");
+    }
+
+    #[test]
+    fn metadata_and_prompts_are_remapped_including_escaped_paths() {
+        let remap = BundleRemap {
+            ids: HashMap::from([("job-old".to_string(), "job-new".to_string())]),
+            source_directory: r"C:\Users\a\proj",
+            target_directory: "/work/proj",
+        };
+
+        let metadata = r#"{"sourceJobId":"job-old","payload":{"projectDirectory":"C:\\Users\\a\\proj","notes":["see C:\\Users\\a\\proj\\src"]},"count":2}"#;
+        let remapped: serde_json::Value =
+            serde_json::from_str(&remap.remap_metadata(metadata)).unwrap();
+        assert_eq!(remapped["sourceJobId"], "job-new");
+        assert_eq!(remapped["payload"]["projectDirectory"], "/work/proj");
+        assert_eq!(remapped["payload"]["notes"][0], r"see /work/proj\src");
+        assert_eq!(remapped["count"], 2);
+
+        assert_eq!(
+            remap.remap_text(r"Files in C:\Users\a\proj for job-old"),
+            "Files in /work/proj for job-new"
+        );
+    }
+
+    #[test]
+    fn remap_file_list_rewrites_json_encoded_history_lists() {
+        assert_eq!(
+            remap_file_list(r#"["src/a.rs","/home/a/proj/b.rs"]"#, "/home/a/proj", "/work/proj"),
+            r#"["src/a.rs","/work/proj/b.rs"]"#
+        );
+        assert_eq!(remap_file_list("", "/home/a/proj", "/work/proj"), "");
+    }
+}
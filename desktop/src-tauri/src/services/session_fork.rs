@@ -0,0 +1,511 @@
+//! Forking sessions at a point in their history, the parent/child tree of forks and
+//! comparison of the plans produced on each branch.
+
+use crate::db_utils::session_repository::{
+    FileHistoryState, SessionFamilyMember, SessionLineage, SessionRepository, TaskHistoryState,
+};
+use crate::db_utils::BackgroundJobRepository;
+use crate::error::{AppError, AppResult};
+use crate::models::{BackgroundJob, JobStatus, Session, TaskType};
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkSessionOptions {
+    /// Task history entry to fork at; defaults to the current entry
+    pub task_history_index: Option<i64>,
+    /// File selection entry to fork at; defaults to the entry that was current when
+    /// the chosen task entry was written
+    pub file_history_index: Option<i64>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTreeNode {
+    pub session_id: String,
+    pub name: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub forked_task_history_index: Option<i64>,
+    pub children: Vec<SessionTreeNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTree {
+    pub root_session_id: String,
+    /// Usually a single root; forks whose parent was deleted become extra roots
+    pub roots: Vec<SessionTreeNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkPlan {
+    pub session_id: String,
+    pub job_id: String,
+    pub plan_title: Option<String>,
+    pub created_at: i64,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanComparison {
+    pub base: Option<ForkPlan>,
+    pub other: Option<ForkPlan>,
+    /// Unified diff from the base plan to the other plan; None unless both exist
+    pub diff: Option<String>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+/// Create a new session from `source` as it was at the chosen history entries
+pub async fn fork_session(
+    session_repo: &SessionRepository,
+    source: &Session,
+    options: ForkSessionOptions,
+) -> AppResult<(Session, SessionLineage)> {
+    let task_history = session_repo.get_task_history_state(&source.id).await?;
+    let file_history = session_repo.get_file_history_state(&source.id).await?;
+
+    let task_index = resolve_index(
+        options.task_history_index,
+        task_history.current_index,
+        task_history.entries.len(),
+        "task history",
+    )?;
+    let file_index = match options.file_history_index {
+        Some(index) => resolve_index(Some(index), 0, file_history.entries.len(), "file history")?,
+        None if options.task_history_index.is_some() => task_history
+            .entries
+            .get(task_index as usize)
+            .map(|entry| file_index_at(&file_history, entry.created_at))
+            .unwrap_or(file_history.current_index),
+        None => file_history.current_index,
+    };
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let fork_id = format!("session_{}_{}", now, Uuid::new_v4());
+
+    let forked_task_history = truncate_task_history(&task_history, task_index);
+    let forked_file_history = truncate_file_history(&file_history, file_index);
+
+    let task_description = forked_task_history
+        .entries
+        .last()
+        .map(|entry| entry.description.clone())
+        .or_else(|| source.task_description.clone());
+    let (included_files, force_excluded_files) = match forked_file_history.entries.last() {
+        Some(entry) => (
+            parse_history_file_list(&entry.included_files),
+            parse_history_file_list(&entry.force_excluded_files),
+        ),
+        None => (
+            source.included_files.clone(),
+            source.force_excluded_files.clone(),
+        ),
+    };
+
+    let fork = Session {
+        id: fork_id.clone(),
+        name: options
+            .name
+            .unwrap_or_else(|| format!("{} (Fork)", source.name)),
+        task_description,
+        included_files,
+        force_excluded_files,
+        created_at: now,
+        updated_at: now,
+        ..source.clone()
+    };
+
+    let root_session_id = session_repo
+        .get_session_lineage(&source.id)
+        .await?
+        .map(|lineage| lineage.root_session_id)
+        .unwrap_or_else(|| source.id.clone());
+    let lineage = SessionLineage {
+        session_id: fork_id,
+        parent_session_id: Some(source.id.clone()),
+        root_session_id,
+        forked_task_history_index: task_index,
+        forked_file_history_index: file_index,
+        created_at: now,
+    };
+
+    session_repo
+        .create_forked_session(&fork, &forked_task_history, &forked_file_history, &lineage)
+        .await?;
+
+    info!(
+        "Forked session {} into {} at task history index {}",
+        source.id, fork.id, task_index
+    );
+
+    Ok((fork, lineage))
+}
+
+/// The fork tree containing `session_id`
+pub async fn get_session_tree(
+    session_repo: &SessionRepository,
+    session_id: &str,
+) -> AppResult<SessionTree> {
+    let root_session_id = session_repo
+        .get_session_lineage(session_id)
+        .await?
+        .map(|lineage| lineage.root_session_id)
+        .unwrap_or_else(|| session_id.to_string());
+
+    let members = session_repo.get_session_family(&root_session_id).await?;
+    if members.is_empty() {
+        return Err(AppError::NotFoundError(format!(
+            "Session not found: {}",
+            session_id
+        )));
+    }
+
+    Ok(SessionTree {
+        roots: build_tree(members),
+        root_session_id,
+    })
+}
+
+/// Compare the latest completed plan of two sessions, typically sibling forks
+pub async fn compare_fork_plans(
+    job_repo: &BackgroundJobRepository,
+    base_session_id: &str,
+    other_session_id: &str,
+) -> AppResult<PlanComparison> {
+    let base = latest_plan(job_repo, base_session_id).await?;
+    let other = latest_plan(job_repo, other_session_id).await?;
+
+    let (diff, lines_added, lines_removed) = match (&base, &other) {
+        (Some(base), Some(other)) => {
+            let patch = diffy::create_patch(&base.content, &other.content);
+            let (added, removed) = count_changed_lines(&patch);
+            (Some(patch.to_string()), added, removed)
+        }
+        _ => (None, 0, 0),
+    };
+
+    Ok(PlanComparison {
+        base,
+        other,
+        diff,
+        lines_added,
+        lines_removed,
+    })
+}
+
+fn resolve_index(requested: Option<i64>, current: i64, len: usize, label: &str) -> AppResult<i64> {
+    let index = requested.unwrap_or(current);
+    if len == 0 {
+        return match requested {
+            Some(index) if index != 0 => Err(AppError::ValidationError(format!(
+                "Cannot fork at {} index {}: history is empty",
+                label, index
+            ))),
+            _ => Ok(0),
+        };
+    }
+    if index < 0 || index as usize >= len {
+        return Err(AppError::ValidationError(format!(
+            "{} index {} is out of range (0..{})",
+            label, index, len
+        )));
+    }
+    Ok(index)
+}
+
+/// Index of the last file selection recorded at or before `timestamp_ms`
+fn file_index_at(file_history: &FileHistoryState, timestamp_ms: i64) -> i64 {
+    file_history
+        .entries
+        .iter()
+        .rposition(|entry| entry.created_at <= timestamp_ms)
+        .unwrap_or(0) as i64
+}
+
+fn truncate_task_history(history: &TaskHistoryState, index: i64) -> TaskHistoryState {
+    let entries: Vec<_> = history
+        .entries
+        .iter()
+        .take(index as usize + 1)
+        .cloned()
+        .collect();
+    TaskHistoryState {
+        current_index: (entries.len() as i64 - 1).max(0),
+        entries,
+        version: 1,
+        checksum: String::new(),
+    }
+}
+
+fn truncate_file_history(history: &FileHistoryState, index: i64) -> FileHistoryState {
+    let entries: Vec<_> = history
+        .entries
+        .iter()
+        .take(index as usize + 1)
+        .cloned()
+        .collect();
+    FileHistoryState {
+        current_index: (entries.len() as i64 - 1).max(0),
+        entries,
+        version: 1,
+        checksum: String::new(),
+    }
+}
+
+/// File history lists are stored as JSON arrays
+fn parse_history_file_list(raw: &str) -> Vec<String> {
+    serde_json::from_str::<Vec<String>>(raw)
+        .map(|files| files.into_iter().filter(|f| !f.trim().is_empty()).collect())
+        .unwrap_or_default()
+}
+
+fn build_tree(members: Vec<SessionFamilyMember>) -> Vec<SessionTreeNode> {
+    let ids: HashSet<String> = members.iter().map(|m| m.session_id.clone()).collect();
+    let mut children_by_parent: HashMap<String, Vec<SessionFamilyMember>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for member in members {
+        match member
+            .lineage
+            .as_ref()
+            .and_then(|lineage| lineage.parent_session_id.clone())
+            .filter(|parent| ids.contains(parent))
+        {
+            Some(parent) => children_by_parent.entry(parent).or_default().push(member),
+            None => roots.push(member),
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|member| build_node(member, &mut children_by_parent))
+        .collect()
+}
+
+fn build_node(
+    member: SessionFamilyMember,
+    children_by_parent: &mut HashMap<String, Vec<SessionFamilyMember>>,
+) -> SessionTreeNode {
+    let children = children_by_parent
+        .remove(&member.session_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_node(child, children_by_parent))
+        .collect();
+
+    SessionTreeNode {
+        forked_task_history_index: member
+            .lineage
+            .as_ref()
+            .map(|lineage| lineage.forked_task_history_index),
+        session_id: member.session_id,
+        name: member.name,
+        created_at: member.created_at,
+        updated_at: member.updated_at,
+        children,
+    }
+}
+
+async fn latest_plan(
+    job_repo: &BackgroundJobRepository,
+    session_id: &str,
+) -> AppResult<Option<ForkPlan>> {
+    let plan = job_repo
+        .get_jobs_by_session_id(session_id)
+        .await?
+        .into_iter()
+        .filter(is_completed_plan)
+        .max_by_key(|job| job.created_at);
+
+    Ok(plan.map(|job| ForkPlan {
+        plan_title: plan_title(&job),
+        session_id: job.session_id,
+        job_id: job.id,
+        created_at: job.created_at,
+        content: job.response.unwrap_or_default(),
+    }))
+}
+
+fn is_completed_plan(job: &BackgroundJob) -> bool {
+    let is_plan = matches!(
+        TaskType::from_str(&job.task_type),
+        Ok(TaskType::ImplementationPlan | TaskType::ImplementationPlanMerge)
+    );
+    let is_completed = matches!(
+        JobStatus::from_str(&job.status),
+        Ok(JobStatus::Completed | JobStatus::CompletedByTag)
+    );
+    is_plan && is_completed && job.response.is_some()
+}
+
+fn plan_title(job: &BackgroundJob) -> Option<String> {
+    let metadata: Value = serde_json::from_str(job.metadata.as_deref()?).ok()?;
+    ["/planTitle", "/generatedTitle", "/taskData/planTitle"]
+        .iter()
+        .find_map(|pointer| metadata.pointer(pointer).and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+pub(crate) fn count_changed_lines(patch: &diffy::Patch<'_, str>) -> (usize, usize) {
+    patch
+        .hunks()
+        .iter()
+        .flat_map(|hunk| hunk.lines())
+        .fold((0, 0), |(added, removed), line| match line {
+            diffy::Line::Insert(_) => (added + 1, removed),
+            diffy::Line::Delete(_) => (added, removed + 1),
+            diffy::Line::Context(_) => (added, removed),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_utils::test_support::{
+        insert_job, insert_session, test_job, test_pool, test_session,
+    };
+
+    fn billing_session(id: &str) -> Session {
+        Session {
+            name: "Billing".to_string(),
+            task_description: Some("Move billing to a ledger table".to_string()),
+            included_files: vec!["src/ledger.rs".to_string()],
+            ..test_session(id, "/work/shop")
+        }
+    }
+
+    fn plan_job(id: &str, session_id: &str, response: &str, created_at: i64) -> BackgroundJob {
+        BackgroundJob {
+            prompt: "plan".to_string(),
+            response: Some(response.to_string()),
+            metadata: Some(r#"{"planTitle":"Ledger"}"#.to_string()),
+            created_at,
+            ..test_job(id, session_id, "implementation_plan")
+        }
+    }
+
+    #[tokio::test]
+    async fn fork_copies_history_up_to_chosen_index_and_builds_tree() {
+        let pool = test_pool().await;
+        let repo = SessionRepository::new(pool.clone());
+
+        let source = billing_session("root");
+        insert_session(&pool, &source).await;
+        repo.sync_task_description_history(
+            &source.id,
+            &[
+                "Move billing".to_string(),
+                "Move billing to a ledger table".to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        repo.sync_file_selection_history(
+            &source.id,
+            &[
+                (r#"["src/billing.rs"]"#.to_string(), "[]".to_string(), 1),
+                (
+                    r#"["src/ledger.rs"]"#.to_string(),
+                    "[]".to_string(),
+                    4_000_000_000_000,
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let (fork, lineage) = fork_session(
+            &repo,
+            &source,
+            ForkSessionOptions {
+                task_history_index: Some(0),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fork.task_description.as_deref(), Some("Move billing"));
+        assert_eq!(fork.included_files, vec!["src/billing.rs".to_string()]);
+        assert_eq!(fork.name, "Billing (Fork)");
+        assert_eq!(lineage.root_session_id, source.id);
+
+        let fork_history = repo.get_task_history_state(&fork.id).await.unwrap();
+        assert_eq!(fork_history.entries.len(), 1);
+        assert_eq!(fork_history.current_index, 0);
+
+        // Fork of a fork keeps the original root
+        let (grandchild, lineage) = fork_session(&repo, &fork, ForkSessionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(lineage.root_session_id, source.id);
+
+        let tree = get_session_tree(&repo, &grandchild.id).await.unwrap();
+        assert_eq!(tree.root_session_id, source.id);
+        assert_eq!(tree.roots.len(), 1);
+        assert_eq!(tree.roots[0].children[0].session_id, fork.id);
+        assert_eq!(
+            tree.roots[0].children[0].children[0].session_id,
+            grandchild.id
+        );
+
+        // Deleting the middle fork orphans the grandchild but keeps it in the tree
+        repo.delete_session(&fork.id).await.unwrap();
+        let tree = get_session_tree(&repo, &source.id).await.unwrap();
+        let ids: Vec<_> = tree.roots.iter().map(|n| n.session_id.as_str()).collect();
+        assert_eq!(ids, vec![source.id.as_str(), grandchild.id.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn fork_rejects_out_of_range_index() {
+        let pool = test_pool().await;
+        let repo = SessionRepository::new(pool.clone());
+        let source = billing_session("root");
+        insert_session(&pool, &source).await;
+        repo.sync_task_description_history(&source.id, &["Only entry".to_string()])
+            .await
+            .unwrap();
+
+        let result = fork_session(
+            &repo,
+            &source,
+            ForkSessionOptions {
+                task_history_index: Some(3),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn compare_diffs_latest_completed_plans() {
+        let pool = test_pool().await;
+        let jobs = BackgroundJobRepository::new(pool.clone());
+
+        insert_session(&pool, &billing_session("a")).await;
+        insert_session(&pool, &billing_session("b")).await;
+        insert_job(&pool, &plan_job("a-old", "a", "step 1\n", 1)).await;
+        insert_job(&pool, &plan_job("a-new", "a", "step 1\nstep 2\n", 2)).await;
+        insert_job(&pool, &plan_job("b-1", "b", "step 1\nstep 3\n", 3)).await;
+
+        let comparison = compare_fork_plans(&jobs, "a", "b").await.unwrap();
+        assert_eq!(comparison.base.as_ref().unwrap().job_id, "a-new");
+        assert_eq!(
+            comparison.other.as_ref().unwrap().plan_title.as_deref(),
+            Some("Ledger")
+        );
+        assert_eq!((comparison.lines_added, comparison.lines_removed), (1, 1));
+        assert!(comparison.diff.unwrap().contains("+step 3"));
+    }
+}
@@ -484,6 +484,13 @@ impl TerminalManager {
                 }),
             )
             .ok();
+            crate::events::publish_app_event(
+                &app,
+                crate::events::AppEvent::TerminalExited {
+                    session_id: sid.clone(),
+                    exit_code,
+                },
+            );
 
             // Force flush all pending output on EOF (ignoring backoff timers)
             // This ensures durability before marking session as completed
@@ -0,0 +1,109 @@
+//! Multi-repository workspaces as seen by sessions and jobs. A session keeps a single project
+//! directory; when that directory belongs to a workspace, the other repositories of the
+//! workspace are searched and planned against too, with their files written as
+//! `label:path` (see `utils::workspace_paths`).
+
+use crate::db_utils::{SessionRepository, WorkspaceRepository};
+use crate::error::{AppError, AppResult};
+use crate::utils::workspace_paths::{RepoFileSelection, WorkspaceLayout, WorkspaceRepoRoot};
+use log::warn;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+fn canonical_directory(directory: &str) -> PathBuf {
+    std::fs::canonicalize(directory).unwrap_or_else(|_| PathBuf::from(directory))
+}
+
+/// Layout of the workspace `project_directory` belongs to, with that directory as the current
+/// repository. `None` when it is not part of a workspace.
+pub async fn get_workspace_layout(
+    app_handle: &AppHandle,
+    project_directory: &str,
+) -> AppResult<Option<WorkspaceLayout>> {
+    let Some(repo) = app_handle.try_state::<Arc<WorkspaceRepository>>() else {
+        return Ok(None);
+    };
+    let Some(workspace) = repo.find_workspace_for_directory(project_directory).await? else {
+        return Ok(None);
+    };
+
+    let current = canonical_directory(project_directory);
+    let repositories = workspace
+        .members
+        .iter()
+        .map(|member| {
+            let directory = canonical_directory(&member.project_directory);
+            WorkspaceRepoRoot {
+                label: member.label.clone(),
+                is_current: directory == current,
+                directory,
+            }
+        })
+        .collect();
+
+    Ok(Some(WorkspaceLayout {
+        workspace_id: workspace.id,
+        workspace_name: workspace.name,
+        repositories,
+    }))
+}
+
+/// Workspace layout for job processing; lookup failures are logged and treated as "no
+/// workspace" so single-repository behaviour is kept
+pub async fn workspace_layout_for_job(
+    app_handle: &AppHandle,
+    project_directory: &str,
+) -> Option<WorkspaceLayout> {
+    get_workspace_layout(app_handle, project_directory)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(
+                "Failed to look up workspace for {}: {}",
+                project_directory, e
+            );
+            None
+        })
+}
+
+/// Directories of the workspace's other repositories, for file discovery
+pub async fn other_workspace_directories(
+    app_handle: &AppHandle,
+    project_directory: &str,
+) -> Vec<PathBuf> {
+    workspace_layout_for_job(app_handle, project_directory)
+        .await
+        .map(|layout| {
+            layout
+                .other_directories()
+                .filter(|directory| directory.is_dir())
+                .map(Path::to_path_buf)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A session's included files split per workspace repository. Sessions outside a workspace
+/// get a single group for their project directory.
+pub async fn get_session_repo_file_selection(
+    app_handle: &AppHandle,
+    session_id: &str,
+) -> AppResult<Vec<RepoFileSelection>> {
+    let session_repo = app_handle.state::<Arc<SessionRepository>>().inner().clone();
+    let session = session_repo
+        .get_session_by_id(session_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Session not found: {}", session_id)))?;
+
+    match get_workspace_layout(app_handle, &session.project_directory).await? {
+        Some(layout) => Ok(layout.group_by_repository(&session.included_files)),
+        None => Ok(vec![RepoFileSelection {
+            label: Path::new(&session.project_directory)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            is_current: true,
+            files: session.included_files,
+        }]),
+    }
+}
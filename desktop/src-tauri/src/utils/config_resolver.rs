@@ -3,15 +3,20 @@ use crate::error::{AppError, AppResult};
 use crate::models::{RuntimeAIConfig, TaskType};
 use crate::services::config_cache_service::ConfigCache;
 use crate::utils::hash_utils::generate_project_hash;
-use crate::validation::ConfigValidator;
+use crate::utils::project_config;
+use crate::validation::{ConfigValidator, available_model_ids};
 use heck::ToSnakeCase;
 use log::{error, info, warn};
 use serde_json;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
-/// Validates that resolved settings are valid and consistent
-async fn validate_resolved_settings(task_type: TaskType, app_handle: &AppHandle) -> AppResult<()> {
+/// Validates that resolved settings are valid and consistent, returning the available model ids
+async fn validate_resolved_settings(
+    task_type: TaskType,
+    app_handle: &AppHandle,
+) -> AppResult<HashSet<String>> {
     info!(
         "Validating resolved settings for task type: {:?}",
         task_type
@@ -66,11 +71,7 @@ async fn validate_resolved_settings(task_type: TaskType, app_handle: &AppHandle)
     }
 
     // Validate the model exists in available providers
-    let available_models: std::collections::HashSet<String> = runtime_config
-        .providers
-        .iter()
-        .flat_map(|p| p.models.iter().map(|m| m.id.clone()))
-        .collect();
+    let available_models = available_model_ids(&runtime_config);
 
     if !available_models.contains(&task_config.model) {
         return Err(AppError::ConfigError(format!(
@@ -83,9 +84,14 @@ async fn validate_resolved_settings(task_type: TaskType, app_handle: &AppHandle)
         "Resolved settings validation passed for task type: {:?}",
         task_type
     );
-    Ok(())
+    Ok(available_models)
 }
 
+/// Resolve model, temperature and max tokens for a task.
+///
+/// Each value is taken from the first source that sets it: the explicit
+/// override, the app's saved settings for this project, the project's
+/// `.plantocode` file, then the server defaults.
 pub async fn resolve_model_settings(
     app_handle: &AppHandle,
     task_type: TaskType,
@@ -108,7 +114,22 @@ pub async fn resolve_model_settings(
     }
 
     // Add validation at the beginning of model resolution
-    validate_resolved_settings(task_type, app_handle).await?;
+    let available_models = validate_resolved_settings(task_type, app_handle).await?;
+
+    let project_file = project_config::active_project_config(project_directory);
+    let file_task = project_file
+        .as_ref()
+        .and_then(|loaded| loaded.config.task(task_type));
+    let file_model = file_task.and_then(|t| t.model.clone()).filter(|model| {
+        let available = available_models.contains(model);
+        if !available {
+            warn!(
+                "Ignoring model '{}' from project config for {:?}: not offered by any provider",
+                model, task_type
+            );
+        }
+        available
+    });
 
     let project_hash = generate_project_hash(project_directory);
     let task_type_snake = task_type.to_string().to_snake_case();
@@ -133,14 +154,17 @@ pub async fn resolve_model_settings(
             Some(value) => serde_json::from_str::<String>(&value).map_err(|e| {
                 AppError::ConfigError(format!("Failed to parse project model setting: {}", e))
             })?,
-            None => crate::utils::config_helpers::get_model_for_task(task_type, app_handle)
-                .await
-                .map_err(|e| {
-                    AppError::ConfigError(format!(
-                        "Failed to get model for task {:?}: {}",
-                        task_type, e
-                    ))
-                })?,
+            None => match file_model {
+                Some(model) => model,
+                None => crate::utils::config_helpers::get_model_for_task(task_type, app_handle)
+                    .await
+                    .map_err(|e| {
+                        AppError::ConfigError(format!(
+                            "Failed to get model for task {:?}: {}",
+                            task_type, e
+                        ))
+                    })?,
+            },
         }
     };
 
@@ -158,17 +182,20 @@ pub async fn resolve_model_settings(
                     e
                 ))
             })?,
-            None => crate::utils::config_helpers::get_default_temperature_for_task(
-                Some(task_type),
-                app_handle,
-            )
-            .await
-            .map_err(|e| {
-                AppError::ConfigError(format!(
-                    "Failed to get temperature for task {:?}: {}",
-                    task_type, e
-                ))
-            })?,
+            None => match file_task.and_then(|t| t.temperature) {
+                Some(temp) => temp,
+                None => crate::utils::config_helpers::get_default_temperature_for_task(
+                    Some(task_type),
+                    app_handle,
+                )
+                .await
+                .map_err(|e| {
+                    AppError::ConfigError(format!(
+                        "Failed to get temperature for task {:?}: {}",
+                        task_type, e
+                    ))
+                })?,
+            },
         }
     };
 
@@ -183,19 +210,60 @@ pub async fn resolve_model_settings(
             Some(value) => serde_json::from_str::<u32>(&value).map_err(|e| {
                 AppError::ConfigError(format!("Failed to parse project max_tokens setting: {}", e))
             })?,
-            None => crate::utils::config_helpers::get_default_max_tokens_for_task(
-                Some(task_type),
-                app_handle,
-            )
-            .await
-            .map_err(|e| {
-                AppError::ConfigError(format!(
-                    "Failed to get max_tokens for task {:?}: {}",
-                    task_type, e
-                ))
-            })?,
+            None => match file_task.and_then(|t| t.max_tokens) {
+                Some(tokens) => tokens,
+                None => crate::utils::config_helpers::get_default_max_tokens_for_task(
+                    Some(task_type),
+                    app_handle,
+                )
+                .await
+                .map_err(|e| {
+                    AppError::ConfigError(format!(
+                        "Failed to get max_tokens for task {:?}: {}",
+                        task_type, e
+                    ))
+                })?,
+            },
         }
     };
 
     Ok(Some((model, temperature, max_tokens)))
 }
+
+/// Whether LLM requests for this task may be answered from the server response cache.
+/// Opt-in per project and task via the `responseCache` task setting; defaults to bypass.
+pub async fn resolve_response_cache_setting(
+    app_handle: &AppHandle,
+    task_type: TaskType,
+    project_directory: &str,
+) -> AppResult<bool> {
+    if !task_type.requires_llm() {
+        return Ok(false);
+    }
+
+    let pool = match app_handle.try_state::<Arc<sqlx::SqlitePool>>() {
+        Some(pool) => pool.inner().clone(),
+        None => {
+            return Err(AppError::InitializationError(
+                "Database pool not yet initialized. Please wait for app initialization to complete.".to_string()
+            ));
+        }
+    };
+    let settings_repo = SettingsRepository::new(pool);
+
+    let project_key = format!(
+        "project_task_settings:{}:{}:{}",
+        generate_project_hash(project_directory),
+        task_type.to_string().to_snake_case(),
+        "responseCache"
+    );
+    match settings_repo.get_value(&project_key).await? {
+        Some(value) => serde_json::from_str::<bool>(&value).map_err(|e| {
+            AppError::ConfigError(format!(
+                "Failed to parse project response cache setting: {}",
+                e
+            ))
+        }),
+        None => Ok(false),
+    }
+}
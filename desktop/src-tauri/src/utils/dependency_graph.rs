@@ -0,0 +1,1232 @@
+//! Lightweight import graph of a project, used to find the files that depend on a selection.
+//!
+//! Imports are read with line-level patterns for Rust (`use`, `mod` and `crate::` paths),
+//! TypeScript/JavaScript (`import`, `export … from`, `require`), Python (`import`,
+//! `from … import`) and Go (`import`) and resolved against the project's file list; imports of
+//! packages outside the project are dropped. The dependents of a file are the files importing
+//! it directly, the files importing one of its items through a re-exporting module (`mod.rs`,
+//! `index.ts`, `__init__.py`) and the modules declaring it.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::utils::repo_map::SourceLanguage;
+
+/// Re-exporting modules followed between a file and the files importing it through them
+const MAX_REEXPORT_DEPTH: usize = 3;
+/// Imported names spelled out in a reason
+const MAX_NAMES_IN_REASON: usize = 3;
+const TS_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mts", "cts", "mjs", "cjs"];
+const GO_MOD_FILE: &str = "go.mod";
+
+static RUST_USE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*(pub(?:\([^)]*\))?[ \t]+)?use[ \t]+([^;]+);")
+        .expect("Valid Rust use regex")
+});
+static RUST_COMMENT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)//[^\n]*|/\*.*?\*/").expect("Valid Rust comment regex"));
+static RUST_INLINE_PATH_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b((?:crate|super|self)(?:::[A-Za-z_]\w*)+)").expect("Valid Rust path regex")
+});
+static RUST_MOD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?[ \t]+)?mod[ \t]+([A-Za-z_]\w*)[ \t]*;")
+        .expect("Valid Rust mod regex")
+});
+static TS_FROM_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(import|export)[ \t]+(?:type[ \t]+)?([^;'"]*?)\s*from\s*['"]([^'"]+)['"]"#,
+    )
+    .expect("Valid TypeScript import regex")
+});
+static TS_SIDE_EFFECT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^[ \t]*import[ \t]*['"]([^'"]+)['"]"#)
+        .expect("Valid TypeScript side effect import regex")
+});
+static TS_CALL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:require|import)\s*\(\s*['"]([^'"]+)['"]\s*\)"#)
+        .expect("Valid TypeScript require regex")
+});
+static PY_FROM_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*from[ \t]+(\.*)([\w.]*)[ \t]+import[ \t]+(\([^)]*\)|[^\n#;]+)")
+        .expect("Valid Python from import regex")
+});
+static PY_IMPORT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*import[ \t]+([\w.]+(?:[ \t]+as[ \t]+\w+)?(?:[ \t]*,[ \t]*[\w.]+(?:[ \t]+as[ \t]+\w+)?)*)")
+        .expect("Valid Python import regex")
+});
+static RUST_PUB_ITEM_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*pub(?:\([^)]*\))?[ \t]+(?:(?:async|unsafe|const|extern(?:[ \t]+"[^"]*")?)[ \t]+)*(?:fn|struct|enum|trait|type|const|static|mod|union)[ \t]+([A-Za-z_]\w*)"#,
+    )
+    .expect("Valid Rust public item regex")
+});
+static TS_EXPORT_DECL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*export[ \t]+(?:declare[ \t]+)?(?:abstract[ \t]+)?(?:async[ \t]+)?(?:function\*?|class|const|let|var|interface|type|enum|namespace)[ \t]+([\w$]+)",
+    )
+    .expect("Valid TypeScript export declaration regex")
+});
+static TS_EXPORT_DEFAULT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*export[ \t]+default\b").expect("Valid TypeScript default export regex")
+});
+static TS_EXPORT_LIST_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*export[ \t]+(?:type[ \t]+)?\{([^}]*)\}")
+        .expect("Valid TypeScript export list regex")
+});
+static PY_ALL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?ms)^__all__[ \t]*(?::[^=\n]*)?=[ \t]*[\[(]([^\])]*)[\])]")
+        .expect("Valid Python __all__ regex")
+});
+static PY_QUOTED_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"['"](\w+)['"]"#).expect("Valid Python quoted name regex"));
+static PY_TOP_LEVEL_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^(?:(?:async[ \t]+)?(?:def|class)[ \t]+([A-Za-z]\w*)|([A-Za-z]\w*)[ \t]*(?::[^=\n]+)?=[^=])")
+        .expect("Valid Python top level name regex")
+});
+static GO_IMPORT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^[ \t]*import[ \t]+(?:[\w.]+[ \t]+)?"([^"]+)""#)
+        .expect("Valid Go import regex")
+});
+static GO_IMPORT_BLOCK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*import[ \t]*\(([^)]*)\)").expect("Valid Go import block regex")
+});
+static GO_IMPORT_PATH_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""([^"]+)""#).expect("Valid Go import path regex"));
+static GO_MODULE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^module[ \t]+(\S+)").expect("Valid Go module regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum EdgeKind {
+    Import,
+    /// `pub use`, `export … from` and imports of a Python package's `__init__.py`
+    ReExport,
+    /// Go imports name a package, that is every file of a directory
+    PackageImport,
+    /// Rust `mod x;`
+    ModuleDeclaration,
+}
+
+/// Items imported from a file; `None` when the whole module is imported
+type ImportedNames = Option<BTreeSet<String>>;
+
+#[derive(Debug)]
+struct ResolvedImport {
+    target: String,
+    kind: EdgeKind,
+    names: ImportedNames,
+}
+
+impl ResolvedImport {
+    fn new(target: String, kind: EdgeKind, names: ImportedNames) -> Self {
+        Self {
+            target,
+            kind,
+            names,
+        }
+    }
+}
+
+/// A file that depends on one of the selected files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentFile {
+    pub path: String,
+    /// Selected file it depends on
+    pub depends_on: String,
+    pub reason: String,
+}
+
+/// Project files the imports are resolved against
+struct FileIndex<'a> {
+    files: HashSet<&'a str>,
+    /// Go module paths with the directories of their `go.mod`, longest module first
+    go_modules: Vec<(String, String)>,
+    /// Non-test Go files per directory
+    go_packages: HashMap<String, Vec<String>>,
+}
+
+impl<'a> FileIndex<'a> {
+    fn new(files: &'a [String], sources: &[(String, String)]) -> Self {
+        let mut go_modules: Vec<(String, String)> = sources
+            .iter()
+            .filter(|(path, _)| file_name(path) == GO_MOD_FILE)
+            .filter_map(|(path, content)| {
+                GO_MODULE_REGEX
+                    .captures(content)
+                    .map(|c| (c[1].to_string(), parent_dir(path).to_string()))
+            })
+            .collect();
+        go_modules.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+
+        let mut go_packages: HashMap<String, Vec<String>> = HashMap::new();
+        for path in files
+            .iter()
+            .filter(|p| p.ends_with(".go") && !p.ends_with("_test.go"))
+        {
+            go_packages
+                .entry(parent_dir(path).to_string())
+                .or_default()
+                .push(path.clone());
+        }
+
+        Self {
+            files: files.iter().map(String::as_str).collect(),
+            go_modules,
+            go_packages,
+        }
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        self.files.contains(path)
+    }
+
+    fn first_existing(&self, candidates: impl IntoIterator<Item = String>) -> Option<String> {
+        candidates.into_iter().find(|c| self.contains(c))
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit_once('/').map(|(_, name)| name).unwrap_or(path)
+}
+
+fn join(dir: &str, relative: &str) -> String {
+    if dir.is_empty() {
+        relative.to_string()
+    } else if relative.is_empty() {
+        dir.to_string()
+    } else {
+        format!("{}/{}", dir, relative)
+    }
+}
+
+/// Resolve `.` and `..` components; `None` when the path leaves the project
+fn normalize(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn names_of<I: IntoIterator<Item = String>>(names: I) -> ImportedNames {
+    let names: BTreeSet<String> = names.into_iter().filter(|n| !n.is_empty()).collect();
+    if names.is_empty() { None } else { Some(names) }
+}
+
+// --- Rust ---
+
+/// Split a `use` tree such as `crate::a::{b::C, d as e, self}` into its full paths, each with
+/// its alias
+fn expand_rust_use_tree(prefix: &str, tree: &str, out: &mut Vec<(String, Option<String>)>) {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut items = Vec::new();
+    for (i, c) in tree.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&tree[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&tree[start..]);
+
+    for item in items.into_iter().map(str::trim).filter(|i| !i.is_empty()) {
+        match (item.find('{'), item.rfind('}')) {
+            (Some(open), Some(close)) if open < close => {
+                let head = item[..open].trim().trim_end_matches("::");
+                expand_rust_use_tree(&join_rust(prefix, head), &item[open + 1..close], out);
+            }
+            _ => {
+                let (path, alias) = match item.split_once(" as ") {
+                    Some((path, alias)) => (path.trim(), Some(alias.trim().to_string())),
+                    None => (item, None),
+                };
+                out.push((join_rust(prefix, path), alias));
+            }
+        }
+    }
+}
+
+fn join_rust(prefix: &str, path: &str) -> String {
+    match (prefix.is_empty(), path.is_empty()) {
+        (true, _) => path.to_string(),
+        (_, true) => prefix.to_string(),
+        _ => format!("{}::{}", prefix, path),
+    }
+}
+
+/// Directory holding the child modules of a Rust file
+fn rust_module_dir(path: &str) -> String {
+    let name = file_name(path);
+    match name {
+        "mod.rs" | "lib.rs" | "main.rs" => parent_dir(path).to_string(),
+        _ => join(parent_dir(path), name.trim_end_matches(".rs")),
+    }
+}
+
+fn rust_child_module(index: &FileIndex, dir: &str, name: &str) -> Option<String> {
+    index.first_existing([
+        join(dir, &format!("{}.rs", name)),
+        join(dir, &format!("{}/mod.rs", name)),
+    ])
+}
+
+/// File of the module whose children live in `dir`
+fn rust_module_file_for_dir(index: &FileIndex, dir: &str) -> Option<String> {
+    index.first_existing([
+        join(dir, "mod.rs"),
+        format!("{}.rs", dir),
+        join(dir, "lib.rs"),
+        join(dir, "main.rs"),
+    ])
+}
+
+/// Source directory and root file of the crate a file belongs to
+fn rust_crate_root(index: &FileIndex, path: &str) -> Option<(String, String)> {
+    let mut dir = parent_dir(path);
+    loop {
+        if let Some(root) = index.first_existing([join(dir, "lib.rs"), join(dir, "main.rs")]) {
+            return Some((dir.to_string(), root));
+        }
+        if dir.is_empty() {
+            return None;
+        }
+        dir = parent_dir(dir);
+    }
+}
+
+fn resolve_rust_use(
+    index: &FileIndex,
+    importer: &str,
+    use_path: &str,
+) -> Option<(String, ImportedNames)> {
+    let segments: Vec<&str> = use_path
+        .split("::")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let first = *segments.first()?;
+
+    let (mut dir, mut target, rest) = match first {
+        "crate" => {
+            let (dir, root) = rust_crate_root(index, importer)?;
+            (dir, root, &segments[1..])
+        }
+        "self" => (
+            rust_module_dir(importer),
+            importer.to_string(),
+            &segments[1..],
+        ),
+        "super" => {
+            let supers = segments.iter().take_while(|s| **s == "super").count();
+            let mut dir = rust_module_dir(importer);
+            for _ in 0..supers {
+                if dir.is_empty() {
+                    return None;
+                }
+                dir = parent_dir(&dir).to_string();
+            }
+            let file = rust_module_file_for_dir(index, &dir)?;
+            (dir, file, &segments[supers..])
+        }
+        // A child module in scope; anything else is an external crate
+        child => {
+            let dir = rust_module_dir(importer);
+            rust_child_module(index, &dir, child)?;
+            (dir, importer.to_string(), &segments[..])
+        }
+    };
+
+    let mut consumed = 0;
+    for segment in rest {
+        match rust_child_module(index, &dir, segment) {
+            Some(file) => {
+                target = file;
+                dir = join(&dir, segment);
+                consumed += 1;
+            }
+            None => break,
+        }
+    }
+    if target == importer {
+        return None;
+    }
+
+    let names = match rest.get(consumed) {
+        Some(&"*") | Some(&"self") | None => None,
+        Some(name) => names_of([name.to_string()]),
+    };
+    Some((target, names))
+}
+
+fn rust_imports(index: &FileIndex, path: &str, content: &str) -> Vec<ResolvedImport> {
+    let mut imports = Vec::new();
+    for captures in RUST_USE_REGEX.captures_iter(content) {
+        let kind = if captures.get(1).is_some() {
+            EdgeKind::ReExport
+        } else {
+            EdgeKind::Import
+        };
+        let mut paths = Vec::new();
+        expand_rust_use_tree(
+            "",
+            &RUST_COMMENT_REGEX.replace_all(&captures[2], ""),
+            &mut paths,
+        );
+        for (use_path, alias) in paths {
+            if let Some((target, names)) = resolve_rust_use(index, path, &use_path) {
+                // Files importing through a re-export use the name it is exported under
+                let names = match alias {
+                    Some(alias) if kind == EdgeKind::ReExport && names.is_some() => {
+                        names_of([alias])
+                    }
+                    _ => names,
+                };
+                imports.push(ResolvedImport::new(target, kind, names));
+            }
+        }
+    }
+
+    // Items used by their full path without a `use`
+    let code = RUST_USE_REGEX.replace_all(content, "");
+    let code = RUST_COMMENT_REGEX.replace_all(&code, "");
+    for captures in RUST_INLINE_PATH_REGEX.captures_iter(&code) {
+        if let Some((target, names)) = resolve_rust_use(index, path, &captures[1]) {
+            imports.push(ResolvedImport::new(target, EdgeKind::Import, names));
+        }
+    }
+
+    let module_dir = rust_module_dir(path);
+    for captures in RUST_MOD_REGEX.captures_iter(content) {
+        if let Some(target) = rust_child_module(index, &module_dir, &captures[1]) {
+            imports.push(ResolvedImport::new(
+                target,
+                EdgeKind::ModuleDeclaration,
+                None,
+            ));
+        }
+    }
+    imports
+}
+
+// --- TypeScript / JavaScript ---
+
+fn resolve_ts_specifier(index: &FileIndex, importer: &str, specifier: &str) -> Option<String> {
+    let base = if specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier == "."
+        || specifier == ".."
+    {
+        normalize(&join(parent_dir(importer), specifier))?
+    } else if let Some(rest) = specifier
+        .strip_prefix("@/")
+        .or_else(|| specifier.strip_prefix("~/"))
+    {
+        // `@/` and `~/` aliases point at the nearest enclosing `src` directory
+        let segments: Vec<&str> = parent_dir(importer).split('/').collect();
+        let src_dir = match segments.iter().rposition(|s| *s == "src") {
+            Some(position) => segments[..=position].join("/"),
+            None => "src".to_string(),
+        };
+        normalize(&join(&src_dir, rest))?
+    } else {
+        return None;
+    };
+
+    let mut candidates = vec![base.clone()];
+    candidates.extend(TS_EXTENSIONS.iter().map(|ext| format!("{}.{}", base, ext)));
+    candidates.extend(
+        TS_EXTENSIONS
+            .iter()
+            .map(|ext| join(&base, &format!("index.{}", ext))),
+    );
+    // ES module imports of TypeScript sources name the compiled `.js` file
+    if let Some((stem, _)) = base
+        .rsplit_once('.')
+        .filter(|(_, extension)| matches!(*extension, "js" | "jsx" | "mjs" | "cjs"))
+    {
+        candidates.extend(TS_EXTENSIONS.iter().map(|ext| format!("{}.{}", stem, ext)));
+    }
+    index.first_existing(candidates)
+}
+
+/// Names taken by an import or export clause: `{ a, b as c }`, `* as ns`, `Default`. Exports
+/// are named by their aliases, imports by the names in the imported file.
+fn ts_clause_names(clause: &str, export: bool) -> ImportedNames {
+    let clause = clause.trim();
+    if clause.is_empty() {
+        return None;
+    }
+    let mut names = Vec::new();
+    let (default_part, braced) = match (clause.find('{'), clause.rfind('}')) {
+        (Some(open), Some(close)) if open < close => (&clause[..open], &clause[open + 1..close]),
+        _ => (clause, ""),
+    };
+    if default_part
+        .trim()
+        .trim_end_matches(',')
+        .trim()
+        .split(',')
+        .any(|part| part.trim().starts_with('*'))
+    {
+        return None;
+    }
+    if !default_part.trim().trim_end_matches(',').trim().is_empty() {
+        names.push("default".to_string());
+    }
+    for item in braced.split(',') {
+        let item = item.trim().trim_start_matches("type ");
+        let name = match item.split_once(" as ") {
+            Some((_, alias)) if export => alias,
+            Some((name, _)) => name,
+            None => item,
+        };
+        names.push(name.trim().to_string());
+    }
+    names_of(names)
+}
+
+fn ts_imports(index: &FileIndex, path: &str, content: &str) -> Vec<ResolvedImport> {
+    let mut imports = Vec::new();
+    for captures in TS_FROM_REGEX.captures_iter(content) {
+        if let Some(target) = resolve_ts_specifier(index, path, &captures[3]) {
+            let export = &captures[1] == "export";
+            let kind = if export {
+                EdgeKind::ReExport
+            } else {
+                EdgeKind::Import
+            };
+            imports.push(ResolvedImport::new(
+                target,
+                kind,
+                ts_clause_names(&captures[2], export),
+            ));
+        }
+    }
+    for captures in TS_SIDE_EFFECT_REGEX
+        .captures_iter(content)
+        .chain(TS_CALL_REGEX.captures_iter(content))
+    {
+        if let Some(target) = resolve_ts_specifier(index, path, &captures[1]) {
+            imports.push(ResolvedImport::new(target, EdgeKind::Import, None));
+        }
+    }
+    imports
+}
+
+// --- Python ---
+
+/// File of a dotted module below `root`, preferring the longest matching prefix; the returned
+/// count is the number of segments that named modules
+fn resolve_python_module(
+    index: &FileIndex,
+    root: &str,
+    segments: &[&str],
+) -> Option<(String, usize)> {
+    (0..=segments.len()).rev().find_map(|count| {
+        let dir = join(root, &segments[..count].join("/"));
+        let module = if count == 0 {
+            index.first_existing([join(&dir, "__init__.py")])
+        } else {
+            index.first_existing([format!("{}.py", dir), join(&dir, "__init__.py")])
+        };
+        module.map(|file| (file, count))
+    })
+}
+
+/// Roots absolute imports are tried against, nearest first
+fn python_roots(importer: &str) -> Vec<String> {
+    let mut roots = Vec::new();
+    let mut dir = parent_dir(importer);
+    loop {
+        roots.push(dir.to_string());
+        if dir.is_empty() {
+            return roots;
+        }
+        dir = parent_dir(dir);
+    }
+}
+
+fn python_imports(index: &FileIndex, path: &str, content: &str) -> Vec<ResolvedImport> {
+    let mut imports = Vec::new();
+    let in_package_init = file_name(path) == "__init__.py";
+
+    for captures in PY_FROM_REGEX.captures_iter(content) {
+        let dots = captures[1].len();
+        let module: Vec<&str> = captures[2].split('.').filter(|s| !s.is_empty()).collect();
+        let imported: Vec<String> = captures[3]
+            .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+            .split(',')
+            .filter_map(|item| item.split_whitespace().next())
+            .map(str::to_string)
+            .collect();
+
+        let roots = if dots > 0 {
+            let mut dir = parent_dir(path).to_string();
+            for _ in 1..dots {
+                if dir.is_empty() {
+                    break;
+                }
+                dir = parent_dir(&dir).to_string();
+            }
+            vec![dir]
+        } else {
+            python_roots(path)
+        };
+        let kind = if in_package_init {
+            EdgeKind::ReExport
+        } else {
+            EdgeKind::Import
+        };
+
+        for root in roots {
+            // Absolute imports of unknown top-level packages are third-party
+            let Some((file, count)) = resolve_python_module(index, &root, &module) else {
+                continue;
+            };
+            if count < module.len() || (dots == 0 && count == 0) {
+                continue;
+            }
+            let module_dir = join(&root, &module.join("/"));
+            let mut whole_module_names = Vec::new();
+            for name in &imported {
+                // `from package import submodule` imports the submodule's file
+                match index.first_existing([join(&module_dir, &format!("{}.py", name))]) {
+                    Some(submodule) if submodule != path => {
+                        imports.push(ResolvedImport::new(submodule, kind, None))
+                    }
+                    _ => whole_module_names.push(name.clone()),
+                }
+            }
+            if !whole_module_names.is_empty() && file != path {
+                let names = if whole_module_names.iter().any(|n| n == "*") {
+                    None
+                } else {
+                    names_of(whole_module_names)
+                };
+                imports.push(ResolvedImport::new(file, kind, names));
+            }
+            break;
+        }
+    }
+
+    for captures in PY_IMPORT_REGEX.captures_iter(content) {
+        for item in captures[1].split(',') {
+            let Some(dotted) = item.split_whitespace().next() else {
+                continue;
+            };
+            let module: Vec<&str> = dotted.split('.').collect();
+            if let Some((file, _)) = python_roots(path)
+                .iter()
+                .filter_map(|root| resolve_python_module(index, root, &module))
+                .find(|(_, count)| *count > 0)
+                .filter(|(file, _)| file != path)
+            {
+                imports.push(ResolvedImport::new(file, EdgeKind::Import, None));
+            }
+        }
+    }
+    imports
+}
+
+// --- Go ---
+
+fn go_imports(index: &FileIndex, path: &str, content: &str) -> Vec<ResolvedImport> {
+    let mut import_paths: Vec<String> = GO_IMPORT_REGEX
+        .captures_iter(content)
+        .map(|c| c[1].to_string())
+        .collect();
+    for block in GO_IMPORT_BLOCK_REGEX.captures_iter(content) {
+        import_paths.extend(
+            GO_IMPORT_PATH_REGEX
+                .captures_iter(&block[1])
+                .map(|c| c[1].to_string()),
+        );
+    }
+
+    let mut imports = Vec::new();
+    for import_path in import_paths {
+        let package_dir = index.go_modules.iter().find_map(|(module, dir)| {
+            if import_path == *module {
+                Some(dir.clone())
+            } else {
+                import_path
+                    .strip_prefix(module.as_str())
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .map(|rest| join(dir, rest))
+            }
+        });
+        let Some(package_files) = package_dir.and_then(|dir| index.go_packages.get(&dir)) else {
+            continue;
+        };
+        for file in package_files.iter().filter(|f| f.as_str() != path) {
+            imports.push(ResolvedImport::new(
+                file.clone(),
+                EdgeKind::PackageImport,
+                None,
+            ));
+        }
+    }
+    imports
+}
+
+// --- Exported names ---
+
+fn rust_exports(content: &str) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = RUST_PUB_ITEM_REGEX
+        .captures_iter(content)
+        .map(|c| c[1].to_string())
+        .collect();
+    for captures in RUST_USE_REGEX.captures_iter(content) {
+        if captures.get(1).is_none() {
+            continue;
+        }
+        let mut paths = Vec::new();
+        expand_rust_use_tree(
+            "",
+            &RUST_COMMENT_REGEX.replace_all(&captures[2], ""),
+            &mut paths,
+        );
+        names.extend(paths.into_iter().filter_map(|(path, alias)| {
+            alias.or_else(|| path.rsplit("::").next().map(str::to_string))
+        }));
+    }
+    names.retain(|name| name != "*" && name != "self" && name != "_");
+    names
+}
+
+fn ts_exports(content: &str) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = TS_EXPORT_DECL_REGEX
+        .captures_iter(content)
+        .map(|c| c[1].to_string())
+        .collect();
+    if TS_EXPORT_DEFAULT_REGEX.is_match(content) {
+        names.insert("default".to_string());
+    }
+    for captures in TS_EXPORT_LIST_REGEX.captures_iter(content) {
+        names.extend(ts_clause_names(&format!("{{{}}}", &captures[1]), true).unwrap_or_default());
+    }
+    names
+}
+
+fn python_exports(path: &str, content: &str) -> BTreeSet<String> {
+    // `__all__` lists the names a module exports
+    if let Some(all) = PY_ALL_REGEX.captures(content) {
+        return PY_QUOTED_NAME_REGEX
+            .captures_iter(&all[1])
+            .map(|c| c[1].to_string())
+            .collect();
+    }
+    let mut names: BTreeSet<String> = PY_TOP_LEVEL_NAME_REGEX
+        .captures_iter(content)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().to_string())
+        .collect();
+    if file_name(path) == "__init__.py" {
+        for captures in PY_FROM_REGEX.captures_iter(content) {
+            names.extend(
+                captures[3]
+                    .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+                    .split(',')
+                    .filter_map(|item| item.split_whitespace().last())
+                    .filter(|name| *name != "*")
+                    .map(str::to_string),
+            );
+        }
+    }
+    names
+}
+
+/// Names a file makes importable by other files, without the ones of its wildcard re-exports
+fn file_exports(path: &str, content: &str) -> BTreeSet<String> {
+    match SourceLanguage::from_path(path) {
+        Some(SourceLanguage::Rust) => rust_exports(content),
+        Some(SourceLanguage::TypeScript) => ts_exports(content),
+        Some(SourceLanguage::Python) => python_exports(path, content),
+        // Go files are imported as packages
+        Some(SourceLanguage::Go) | None => BTreeSet::new(),
+    }
+}
+
+fn file_imports(index: &FileIndex, path: &str, content: &str) -> Vec<ResolvedImport> {
+    match SourceLanguage::from_path(path) {
+        Some(SourceLanguage::Rust) => rust_imports(index, path, content),
+        Some(SourceLanguage::TypeScript) => ts_imports(index, path, content),
+        Some(SourceLanguage::Python) => python_imports(index, path, content),
+        Some(SourceLanguage::Go) => go_imports(index, path, content),
+        None => Vec::new(),
+    }
+}
+
+fn describe_names(names: &BTreeSet<String>) -> String {
+    let shown: Vec<&str> = names
+        .iter()
+        .take(MAX_NAMES_IN_REASON)
+        .map(String::as_str)
+        .collect();
+    match names.len().saturating_sub(MAX_NAMES_IN_REASON) {
+        0 => shown.join(", "),
+        more => format!("{} and {} more", shown.join(", "), more),
+    }
+}
+
+fn describe_edge(kind: EdgeKind, names: &ImportedNames, target: &str) -> String {
+    match (kind, names) {
+        (EdgeKind::Import, Some(names)) => {
+            format!("imports {} from {}", describe_names(names), target)
+        }
+        (EdgeKind::Import, None) => format!("imports {}", target),
+        (EdgeKind::ReExport, Some(names)) => {
+            format!("re-exports {} from {}", describe_names(names), target)
+        }
+        (EdgeKind::ReExport, None) => format!("re-exports {}", target),
+        (EdgeKind::PackageImport, _) => format!("imports the package of {}", target),
+        (EdgeKind::ModuleDeclaration, _) => format!("declares module {}", target),
+    }
+}
+
+/// Names imported through a re-export: `None` when the import does not use anything the
+/// re-export passes on
+fn shared_names(exported: &ImportedNames, imported: &ImportedNames) -> Option<ImportedNames> {
+    match (exported, imported) {
+        (None, names) | (names, None) => Some(names.clone()),
+        (Some(exported), Some(imported)) => {
+            let shared: BTreeSet<String> = exported.intersection(imported).cloned().collect();
+            if shared.is_empty() {
+                None
+            } else {
+                Some(Some(shared))
+            }
+        }
+    }
+}
+
+/// Reverse import graph of a project
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// Imported file -> (importing file, kind) -> imported names
+    importers: HashMap<String, BTreeMap<(String, EdgeKind), ImportedNames>>,
+    /// Names each source file exports itself
+    exports: HashMap<String, BTreeSet<String>>,
+    /// Files each source file re-exports all items of (`export *`, `pub use x::*`)
+    wildcard_reexports: HashMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from the project's file list (relative, forward slashes) and the contents
+    /// of its source files and `go.mod` files
+    pub fn build(files: &[String], sources: &[(String, String)]) -> Self {
+        let index = FileIndex::new(files, sources);
+        let mut graph = Self::default();
+        for (path, content) in sources {
+            let exports = file_exports(path, content);
+            if !exports.is_empty() {
+                graph.exports.insert(path.clone(), exports);
+            }
+            for import in file_imports(&index, path, content) {
+                if import.kind == EdgeKind::ReExport && import.names.is_none() {
+                    graph
+                        .wildcard_reexports
+                        .entry(path.clone())
+                        .or_default()
+                        .push(import.target.clone());
+                }
+                let entry = graph
+                    .importers
+                    .entry(import.target)
+                    .or_default()
+                    .entry((path.clone(), import.kind))
+                    .or_insert_with(|| Some(BTreeSet::new()));
+                match (entry.as_mut(), import.names) {
+                    (Some(existing), Some(names)) => existing.extend(names),
+                    _ => *entry = None,
+                }
+            }
+        }
+        graph
+    }
+
+    /// Files importing `path` directly
+    pub fn importers_of(&self, path: &str) -> Vec<&str> {
+        self.importers
+            .get(path)
+            .map(|importers| {
+                importers
+                    .keys()
+                    .map(|(importer, _)| importer.as_str())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Names a file makes importable, including the items of its wildcard re-exports; `None`
+    /// when they are unknown
+    fn exported_names(&self, path: &str) -> ImportedNames {
+        let mut names = BTreeSet::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([(path, 0)]);
+        while let Some((file, depth)) = queue.pop_front() {
+            if !visited.insert(file) {
+                continue;
+            }
+            names.extend(self.exports.get(file).into_iter().flatten().cloned());
+            if depth < MAX_REEXPORT_DEPTH {
+                queue.extend(
+                    self.wildcard_reexports
+                        .get(file)
+                        .into_iter()
+                        .flatten()
+                        .map(|target| (target.as_str(), depth + 1)),
+                );
+            }
+        }
+        if names.is_empty() { None } else { Some(names) }
+    }
+
+    fn edges(&self, path: &str) -> impl Iterator<Item = (&str, EdgeKind, &ImportedNames)> {
+        self.importers
+            .get(path)
+            .into_iter()
+            .flatten()
+            .map(|((importer, kind), names)| (importer.as_str(), *kind, names))
+    }
+
+    /// Files depending on the selected files, each with the reason it was added: direct
+    /// importers first, then importers through re-exports, then declaring modules
+    pub fn find_dependents(&self, selected: &[String], max_files: usize) -> Vec<DependentFile> {
+        // Candidates in order of preference; the first reason found for a file is kept
+        let mut candidates: Vec<(&str, &str, String)> = Vec::new();
+
+        let mut reexports: VecDeque<(&str, ImportedNames, &str, usize)> = VecDeque::new();
+        for target in selected {
+            for (importer, kind, names) in self.edges(target) {
+                if kind == EdgeKind::ReExport {
+                    // A wildcard re-export passes on everything the file exports
+                    let exported = names.clone().or_else(|| self.exported_names(target));
+                    reexports.push_back((importer, exported, target.as_str(), 1));
+                }
+                if kind != EdgeKind::ModuleDeclaration {
+                    candidates.push((importer, target, describe_edge(kind, names, target)));
+                }
+            }
+        }
+
+        let mut followed: HashSet<(&str, &str)> = HashSet::new();
+        while let Some((module, exported, origin, depth)) = reexports.pop_front() {
+            if !followed.insert((module, origin)) {
+                continue;
+            }
+            for (importer, kind, names) in self.edges(module) {
+                if kind == EdgeKind::ModuleDeclaration || importer == origin {
+                    continue;
+                }
+                let Some(shared) = shared_names(&exported, names) else {
+                    continue;
+                };
+                let reason = match (names, &shared) {
+                    (Some(_), Some(shared)) => format!(
+                        "imports {} from {}, which re-exports {}",
+                        describe_names(shared),
+                        module,
+                        origin
+                    ),
+                    _ => format!("imports {}, which re-exports {}", module, origin),
+                };
+                candidates.push((importer, origin, reason));
+                if kind == EdgeKind::ReExport && depth < MAX_REEXPORT_DEPTH {
+                    reexports.push_back((importer, shared, origin, depth + 1));
+                }
+            }
+        }
+
+        for target in selected {
+            for (importer, kind, names) in self.edges(target) {
+                if kind == EdgeKind::ModuleDeclaration {
+                    candidates.push((importer, target, describe_edge(kind, names, target)));
+                }
+            }
+        }
+
+        let mut seen: HashSet<&str> = selected.iter().map(String::as_str).collect();
+        candidates
+            .into_iter()
+            .filter(|(path, _, _)| seen.insert(path))
+            .take(max_files)
+            .map(|(path, depends_on, reason)| DependentFile {
+                path: path.to_string(),
+                depends_on: depends_on.to_string(),
+                reason,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(sources: &[(&str, &str)], other_files: &[&str]) -> DependencyGraph {
+        let sources: Vec<(String, String)> = sources
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect();
+        let files: Vec<String> = sources
+            .iter()
+            .map(|(path, _)| path.clone())
+            .chain(other_files.iter().map(|p| p.to_string()))
+            .collect();
+        DependencyGraph::build(&files, &sources)
+    }
+
+    fn dependents(graph: &DependencyGraph, selected: &[&str]) -> Vec<(String, String)> {
+        let selected: Vec<String> = selected.iter().map(|s| s.to_string()).collect();
+        graph
+            .find_dependents(&selected, 50)
+            .into_iter()
+            .map(|d| (d.path, d.reason))
+            .collect()
+    }
+
+    #[test]
+    fn rust_dependents_include_callers_through_reexports() {
+        let graph = graph(
+            &[
+                ("src/lib.rs", "pub mod jobs;\nmod utils;\n"),
+                (
+                    "src/jobs/mod.rs",
+                    "pub mod processor;\npub use processor::{Processor, run as run_job};\n",
+                ),
+                (
+                    "src/jobs/processor.rs",
+                    "use crate::utils::helpers::format;\n",
+                ),
+                ("src/utils/mod.rs", "pub mod helpers;\n"),
+                ("src/utils/helpers.rs", "use std::fmt;\n"),
+                (
+                    "src/api.rs",
+                    "use crate::jobs::{\n    // Stage processors\n    Processor,\n    other::Thing,\n};\nuse serde::Serialize;\n",
+                ),
+                ("src/cli.rs", "use crate::jobs::Unrelated;\n"),
+                (
+                    "src/main.rs",
+                    "fn main() {\n    // crate::jobs::processor::run()\n    crate::jobs::processor::run();\n}\n",
+                ),
+                ("src/jobs/queue.rs", "use super::processor::Processor;\n"),
+            ],
+            &[],
+        );
+
+        assert_eq!(
+            graph.importers_of("src/utils/helpers.rs"),
+            vec!["src/jobs/processor.rs", "src/utils/mod.rs"]
+        );
+        assert_eq!(
+            dependents(&graph, &["src/jobs/processor.rs"]),
+            vec![
+                (
+                    "src/jobs/mod.rs".to_string(),
+                    "re-exports Processor, run_job from src/jobs/processor.rs".to_string()
+                ),
+                (
+                    "src/jobs/queue.rs".to_string(),
+                    "imports Processor from src/jobs/processor.rs".to_string()
+                ),
+                (
+                    "src/main.rs".to_string(),
+                    "imports run from src/jobs/processor.rs".to_string()
+                ),
+                (
+                    "src/api.rs".to_string(),
+                    "imports Processor from src/jobs/mod.rs, which re-exports src/jobs/processor.rs"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_imports_resolve_extensions_index_files_and_aliases() {
+        let graph = graph(
+            &[
+                ("web/src/api/client.ts", "export function get() {}\n"),
+                (
+                    "web/src/api/index.ts",
+                    "export { get as fetchJson } from './client';\nexport * from \"./types\";\n",
+                ),
+                ("web/src/api/types.ts", "export type Id = string;\n"),
+                (
+                    "web/src/app/page.tsx",
+                    "import React from 'react';\nimport {\n  get,\n} from '../api/client.js';\n",
+                ),
+                ("web/src/app/list.tsx", "import type { Id } from '@/api';\n"),
+                (
+                    "web/src/app/detail.tsx",
+                    "import { fetchJson, Other } from '@/api';\n",
+                ),
+                (
+                    "web/src/app/legacy.js",
+                    "const api = require('../api/client');\n",
+                ),
+            ],
+            &["web/src/styles.css"],
+        );
+
+        assert_eq!(
+            dependents(&graph, &["web/src/api/client.ts"]),
+            vec![
+                (
+                    "web/src/api/index.ts".to_string(),
+                    "re-exports fetchJson from web/src/api/client.ts".to_string()
+                ),
+                (
+                    "web/src/app/legacy.js".to_string(),
+                    "imports web/src/api/client.ts".to_string()
+                ),
+                (
+                    "web/src/app/page.tsx".to_string(),
+                    "imports get from web/src/api/client.ts".to_string()
+                ),
+                (
+                    "web/src/app/detail.tsx".to_string(),
+                    "imports fetchJson from web/src/api/index.ts, which re-exports web/src/api/client.ts"
+                        .to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            dependents(&graph, &["web/src/api/types.ts"]),
+            vec![
+                (
+                    "web/src/api/index.ts".to_string(),
+                    "re-exports web/src/api/types.ts".to_string()
+                ),
+                (
+                    "web/src/app/list.tsx".to_string(),
+                    "imports Id from web/src/api/index.ts, which re-exports web/src/api/types.ts"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_imports_resolve_relative_absolute_and_package_imports() {
+        let graph = graph(
+            &[
+                ("app/__init__.py", "from .models import User\n"),
+                ("app/models.py", "import os\n"),
+                (
+                    "app/views.py",
+                    "from .models import (\n    User,\n    Group,\n)\n",
+                ),
+                ("app/admin.py", "from app import User\n"),
+                (
+                    "scripts/seed.py",
+                    "import app.models as m\nimport requests\n",
+                ),
+                ("tests/test_models.py", "from app import models\n"),
+            ],
+            &[],
+        );
+
+        assert_eq!(
+            dependents(&graph, &["app/models.py"]),
+            vec![
+                (
+                    "app/__init__.py".to_string(),
+                    "re-exports User from app/models.py".to_string()
+                ),
+                (
+                    "app/views.py".to_string(),
+                    "imports Group, User from app/models.py".to_string()
+                ),
+                (
+                    "scripts/seed.py".to_string(),
+                    "imports app/models.py".to_string()
+                ),
+                (
+                    "tests/test_models.py".to_string(),
+                    "imports app/models.py".to_string()
+                ),
+                (
+                    "app/admin.py".to_string(),
+                    "imports User from app/__init__.py, which re-exports app/models.py".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_imports_resolve_packages_of_the_module() {
+        let graph = graph(
+            &[
+                ("go.mod", "module example.com/shop\n\ngo 1.22\n"),
+                ("billing/invoice.go", "package billing\n"),
+                ("billing/tax.go", "package billing\n"),
+                (
+                    "cmd/server/main.go",
+                    "package main\n\nimport (\n\t\"fmt\"\n\tb \"example.com/shop/billing\"\n)\n",
+                ),
+                (
+                    "orders/orders.go",
+                    "package orders\n\nimport \"example.com/shop/billing\"\n",
+                ),
+            ],
+            &["billing/invoice_test.go"],
+        );
+
+        assert_eq!(
+            dependents(&graph, &["billing/invoice.go"]),
+            vec![
+                (
+                    "cmd/server/main.go".to_string(),
+                    "imports the package of billing/invoice.go".to_string()
+                ),
+                (
+                    "orders/orders.go".to_string(),
+                    "imports the package of billing/invoice.go".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn dependents_skip_selected_files_and_respect_the_limit() {
+        let graph = graph(
+            &[
+                ("src/lib.rs", "mod a;\nmod b;\nmod c;\n"),
+                ("src/a.rs", ""),
+                ("src/b.rs", "use crate::a::Thing;\n"),
+                ("src/c.rs", "use crate::a;\n"),
+            ],
+            &[],
+        );
+        let selected = vec!["src/a.rs".to_string(), "src/b.rs".to_string()];
+
+        let all = graph.find_dependents(&selected, 10);
+        assert_eq!(
+            all.iter().map(|d| d.path.as_str()).collect::<Vec<_>>(),
+            vec!["src/c.rs", "src/lib.rs"]
+        );
+        assert_eq!(all[1].reason, "declares module src/a.rs");
+        assert_eq!(graph.find_dependents(&selected, 1).len(), 1);
+    }
+}
@@ -1,71 +1,53 @@
 use log::debug;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::error::{AppError, AppResult};
 use crate::utils::fs_utils;
 use crate::utils::git_utils;
+use crate::utils::project_config;
+use crate::utils::workspace_paths::WorkspaceLayout;
+
+/// Git repository containing `path`, found by walking up from it
+fn find_git_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|candidate| git_utils::is_git_repository(candidate))
+        .map(Path::to_path_buf)
+}
 
+/// Tree of each root under a `===== ROOT: <root> =====` header. Roots may live in different git
+/// repositories; each repository's file list is read once and shared by its roots.
 pub async fn get_combined_directory_tree_for_roots(roots: &[String]) -> AppResult<String> {
-    use std::collections::HashSet;
-
-    // Find the common git repository root for all roots
-    let git_root = {
-        let mut common_git_root: Option<PathBuf> = None;
-
-        for root_str in roots {
-            let mut current = Path::new(root_str);
-
-            // Walk up to find git root
-            loop {
-                if git_utils::is_git_repository(current) {
-                    let git_path = current.to_path_buf();
-
-                    // Check if this matches our previously found git root
-                    if let Some(ref existing_root) = common_git_root {
-                        if existing_root != &git_path {
-                            // Different git roots - fall back to individual processing
-                            return get_combined_directory_tree_for_roots_fallback(roots).await;
-                        }
-                    } else {
-                        common_git_root = Some(git_path);
-                    }
-                    break;
-                }
-
-                if let Some(parent) = current.parent() {
-                    current = parent;
-                } else {
-                    // No git root found - fall back to individual processing
-                    return get_combined_directory_tree_for_roots_fallback(roots).await;
-                }
-            }
-        }
-
-        common_git_root
-            .ok_or_else(|| AppError::JobError("No git repository found for roots".to_string()))?
-    };
-
-    // Get ALL non-ignored files from the git repository
-    let (all_git_files, _) = git_utils::get_all_non_ignored_files(&git_root)?;
-
-    // Convert to absolute paths and filter by roots
+    let mut repository_files: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
     let mut sections = Vec::new();
 
     for root_str in roots {
         let root_path = Path::new(root_str);
         let header = format!("===== ROOT: {} =====\n", root_str);
 
+        let Some(git_root) = find_git_root(root_path) else {
+            // Not inside a git repository - walk the root itself
+            let body = get_directory_tree_with_defaults(root_str).await?;
+            sections.push(format!("{}{}", header, body));
+            continue;
+        };
+        if !repository_files.contains_key(&git_root) {
+            // Get ALL non-ignored files from the git repository
+            let (files, _) = git_utils::get_all_non_ignored_files(&git_root)?;
+            repository_files.insert(git_root.clone(), files);
+        }
+        let all_git_files = &repository_files[&git_root];
+
         // Filter files that belong to this root
         let mut root_files = Vec::new();
-        for rel_path in &all_git_files {
+        for rel_path in all_git_files {
             let abs_path = git_root.join(rel_path);
 
             // Check if this file is under the current root
-            if abs_path.starts_with(&root_path) {
+            if abs_path.starts_with(root_path) {
                 // Make path relative to this root (not git root)
-                if let Ok(root_relative) = abs_path.strip_prefix(&root_path) {
+                if let Ok(root_relative) = abs_path.strip_prefix(root_path) {
                     root_files.push(root_relative.to_path_buf());
                 }
             }
@@ -99,13 +81,23 @@ pub async fn get_combined_directory_tree_for_roots(roots: &[String]) -> AppResul
     Ok(sections.join("\n\n"))
 }
 
-// Fallback for when roots have different git repositories or no git
-async fn get_combined_directory_tree_for_roots_fallback(roots: &[String]) -> AppResult<String> {
+/// Tree of every repository of a workspace, each under a header that tells the model how to
+/// reference its files: plain paths for the session's repository, `label:path` for the others
+pub async fn get_workspace_directory_tree(workspace: &WorkspaceLayout) -> AppResult<String> {
     let mut sections = Vec::new();
 
-    for r in roots {
-        let header = format!("===== ROOT: {} =====\n", r);
-        let body = get_directory_tree_with_defaults(r).await?;
+    for repository in &workspace.repositories {
+        let directory = repository.directory.to_string_lossy().to_string();
+        let reference = if repository.is_current {
+            "paths without prefix".to_string()
+        } else {
+            format!("paths as \"{}:<path>\"", repository.label)
+        };
+        let header = format!(
+            "===== REPOSITORY {}: {} ({}) =====\n",
+            repository.label, directory, reference
+        );
+        let body = get_directory_tree_with_defaults(&directory).await?;
         sections.push(format!("{}{}", header, body));
     }
 
@@ -244,10 +236,17 @@ pub async fn generate_directory_tree(
     // Sort paths
     filtered_paths.sort();
 
-    // Convert to relative paths
+    // Convert to relative paths, dropping anything the project's .plantocode file excludes
+    let project_config = project_config::active_project_config(&project_dir_str);
     let mut relative_paths = Vec::new();
     for path in filtered_paths {
         if let Ok(rel_path) = path.strip_prefix(project_dir_path) {
+            if project_config
+                .as_ref()
+                .is_some_and(|loaded| loaded.config.is_tree_excluded(rel_path))
+            {
+                continue;
+            }
             relative_paths.push(rel_path.to_path_buf());
         }
     }
@@ -498,3 +497,17 @@ pub async fn get_directory_tree_with_defaults(project_directory: &str) -> AppRes
 
     get_directory_tree_for_processor(project_directory, Some(&default_excluded)).await
 }
+
+/// Directory tree of a session's project: the labelled tree of every repository when the
+/// project belongs to a multi-repository workspace, the project's own tree otherwise
+pub async fn get_project_directory_tree(
+    project_directory: &str,
+    workspace: Option<&WorkspaceLayout>,
+) -> AppResult<String> {
+    match workspace {
+        Some(workspace) if workspace.repositories.len() > 1 => {
+            get_workspace_directory_tree(workspace).await
+        }
+        _ => get_directory_tree_with_defaults(project_directory).await,
+    }
+}
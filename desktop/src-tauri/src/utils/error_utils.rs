@@ -19,7 +19,7 @@ pub fn format_user_error(error: &AppError) -> String {
                 format!("AI service error: {}", msg)
             }
         },
-        AppError::ServerProxyError(msg) => {
+        AppError::ServerProxyError { message: msg, .. } => {
             if msg.contains("connection") || msg.contains("timeout") {
                 "Unable to connect to server. Please check your internet connection.".to_string()
             } else if msg.contains("unauthorized") || msg.contains("401") {
@@ -142,7 +142,7 @@ pub fn format_user_error(error: &AppError) -> String {
         AppError::Unauthorized(_) => "Authentication required. Please log in again.".to_string(),
         AppError::Forbidden(_) => "Access denied. You don't have permission to perform this action.".to_string(),
         AppError::BadRequest(_) => "Invalid request. Please check your input and try again.".to_string(),
-        AppError::TooManyRequests(_) => "Too many requests. Please wait a moment before trying again.".to_string(),
+        AppError::TooManyRequests { .. } => "Too many requests. Please wait a moment before trying again.".to_string(),
         AppError::NotImplemented(_) => "This feature is not yet implemented. Please contact support.".to_string(),
         AppError::LockPoisoned(_) => "System resource lock error. Please try again.".to_string(),
         AppError::ConfigurationError(msg) => format!("Configuration error: {}", msg),
@@ -251,7 +251,7 @@ pub async fn log_error_to_db(error: &AppError, context: &str, metadata: Option<V
                 AppError::DatabaseError(_) => "DATABASE_ERROR",
                 AppError::SqlxError(_) => "SQLX_ERROR",
                 AppError::OpenRouterError(_) => "OPENROUTER_ERROR",
-                AppError::ServerProxyError(_) => "SERVER_PROXY_ERROR",
+                AppError::ServerProxyError { .. } => "SERVER_PROXY_ERROR",
                 AppError::HttpError(_) => "HTTP_ERROR",
                 AppError::TauriError(_) => "TAURI_ERROR",
                 AppError::KeyringError(_) => "KEYRING_ERROR",
@@ -299,7 +299,7 @@ pub async fn log_error_to_db(error: &AppError, context: &str, metadata: Option<V
                 AppError::Unauthorized(_) => "UNAUTHORIZED",
                 AppError::Forbidden(_) => "FORBIDDEN",
                 AppError::BadRequest(_) => "BAD_REQUEST",
-                AppError::TooManyRequests(_) => "TOO_MANY_REQUESTS",
+                AppError::TooManyRequests { .. } => "TOO_MANY_REQUESTS",
                 AppError::NotImplemented(_) => "NOT_IMPLEMENTED",
                 AppError::LockPoisoned(_) => "LOCK_POISONED",
                 AppError::TaskInitiationFailed(_) => "TASK_INITIATION_FAILED",
@@ -1,6 +1,7 @@
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use git2::{Repository, Status, StatusOptions};
+use git2::{Diff, DiffOptions, Repository, Sort, Status, StatusOptions};
 use log::{debug, error, info};
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 use crate::error::{AppError, AppResult};
@@ -279,3 +280,91 @@ pub fn is_ignored(repo_path: impl AsRef<Path>, file_path: impl AsRef<Path>) -> b
         }
     }
 }
+
+/// Files changed since a point in time
+#[derive(Debug, Clone, Default)]
+pub struct ChangesSince {
+    /// Working directory of the repository the changes were read from
+    pub workdir: PathBuf,
+    /// Ids of non-merge commits made after the cutoff, newest first
+    pub commits: Vec<String>,
+    /// Changed paths relative to the repository root, with `/` separators
+    pub paths: BTreeSet<String>,
+}
+
+/// Collect the files touched by commits on HEAD made at or after `since_ms` (milliseconds
+/// since the epoch), plus uncommitted changes in the index and working tree. Merge commits
+/// are skipped since their first-parent diff repeats the merged commits.
+pub fn get_changes_since(path: impl AsRef<Path>, since_ms: i64) -> AppResult<ChangesSince> {
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        let _ = (path, since_ms);
+        Err(AppError::GitError(
+            "Git operations not supported on mobile".to_string(),
+        ))
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        let repo = Repository::discover(path.as_ref())?;
+        let mut changes = ChangesSince {
+            workdir: repo.workdir().map(Path::to_path_buf).ok_or_else(|| {
+                AppError::GitError("Repository has no working directory".to_string())
+            })?,
+            ..Default::default()
+        };
+        let since_secs = since_ms / 1000;
+
+        // An unborn HEAD has no commits yet; only the working tree can have changes
+        if let Some(head_oid) = repo.head().ok().and_then(|head| head.target()) {
+            let mut walk = repo.revwalk()?;
+            walk.push(head_oid)?;
+            walk.set_sorting(Sort::TIME)?;
+
+            for oid in walk {
+                let commit = repo.find_commit(oid?)?;
+                if commit.time().seconds() < since_secs {
+                    break;
+                }
+                if commit.parent_count() > 1 {
+                    continue;
+                }
+
+                let parent_tree = match commit.parent_count() {
+                    0 => None,
+                    _ => Some(commit.parent(0)?.tree()?),
+                };
+                let diff =
+                    repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+                collect_diff_paths(&diff, &mut changes.paths);
+                changes.commits.push(commit.id().to_string());
+            }
+        }
+
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        let mut options = DiffOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        let diff = repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))?;
+        collect_diff_paths(&diff, &mut changes.paths);
+
+        debug!(
+            "Found {} changed paths in {} commits since {}",
+            changes.paths.len(),
+            changes.commits.len(),
+            since_ms
+        );
+        Ok(changes)
+    }
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn collect_diff_paths(diff: &Diff<'_>, paths: &mut BTreeSet<String>) {
+    for delta in diff.deltas() {
+        for file_path in [delta.old_file().path(), delta.new_file().path()]
+            .into_iter()
+            .flatten()
+        {
+            paths.insert(file_path.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
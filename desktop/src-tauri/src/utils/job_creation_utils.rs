@@ -437,6 +437,17 @@ pub async fn create_and_queue_background_job(
 
     // Job creation event already emitted by repository method above
 
+    // Record which jobs this one was derived from before it can run
+    crate::services::job_provenance::record_creation_provenance(
+        &repo,
+        &job_id,
+        session_id,
+        &typed_job_payload,
+        workflow_id.as_deref(),
+        additional_params.as_ref(),
+    )
+    .await;
+
     // Create a Job struct for the queue using the already typed payload
     let job_for_queue = crate::jobs::types::Job {
         id: job_id.clone(),
@@ -547,6 +558,7 @@ fn inject_job_id_into_payload(payload: &mut JobPayload, job_id: &str) {
 
         // Workflow stage payloads
         JobPayload::ExtendedPathFinder(_) => {}
+        JobPayload::ImpactAnalysis(_) => {}
         JobPayload::FileRelevanceAssessment(_) => {}
         JobPayload::WebSearchPromptsGeneration(_) => {}
         JobPayload::WebSearchExecution(_) => {}
@@ -564,6 +576,12 @@ fn inject_job_id_into_payload(payload: &mut JobPayload, job_id: &str) {
         // Implementation plan merge payload
         JobPayload::ImplementationPlanMerge(_) => {}
 
+        // Implementation plan refinement payload
+        JobPayload::ImplementationPlanRefinement(_) => {}
+
+        // Plan test generation payload
+        JobPayload::PlanTestGeneration(_) => {}
+
         // Video analysis payload
         JobPayload::VideoAnalysis(_) => {}
 
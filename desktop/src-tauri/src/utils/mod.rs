@@ -2,6 +2,7 @@ pub mod config_helpers;
 pub mod config_resolver;
 pub mod context_resolver;
 pub mod date_utils;
+pub mod dependency_graph;
 pub mod device_name;
 pub mod directory_tree;
 pub mod disk_utils;
@@ -19,6 +20,12 @@ pub mod job_ui_metadata_builder;
 pub mod markdown_utils;
 pub mod path_extraction;
 pub mod path_utils;
+pub mod plan_issue_export;
+pub mod plan_templates;
+pub mod project_config;
+pub mod repo_map;
+pub mod test_plans;
+pub mod text_crdt;
 pub mod title_generation;
 pub mod token_estimator;
 pub mod xml_markdown_converter;
@@ -72,4 +79,5 @@ pub use unified_prompt_system::{
     get_template_for_display, substitute_placeholders,
 };
 pub mod stream_debug_logger;
+pub mod workspace_paths;
 pub mod workspace_roots;
@@ -0,0 +1,698 @@
+//! Issue-tracker exports of implementation plans. A plan becomes a parent issue plus one
+//! sub-task per step; each sub-task carries a checklist of the step's file operations and
+//! commands, the files it touches, and acceptance criteria taken from the step's validation
+//! notes and the tests planned for it.
+//!
+//! Formats are produced by [`PlanExporter`]s looked up by id in a [`PlanExporterRegistry`].
+//! One registry is built at startup and kept in managed state, so an exporter registered there
+//! is offered next to the built-in ones.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::error::{AppError, AppResult};
+use crate::jobs::processors::utils::parsing_utils::{
+    extract_agent_instructions_from_xml, extract_step_headers_from_xml, extract_steps_from_xml,
+};
+use crate::utils::test_plans::{PlanTestPlan, unescape_xml};
+
+/// Label put on every exported issue
+pub const PLAN_ISSUE_LABEL: &str = "implementation-plan";
+const MAX_SLUG_LENGTH: usize = 60;
+
+static DESCRIPTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<description>\s*(.*?)\s*</description>").expect("Valid description regex")
+});
+static ASSUMPTIONS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<assumptions>\s*(.*?)\s*</assumptions>").expect("Valid assumptions regex")
+});
+static OPERATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<operation\b([^>]*)>(.*?)</operation>").expect("Valid operation regex")
+});
+static TYPE_ATTR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\btype\s*=\s*["']([^"']*)["']"#).expect("Valid type attribute regex")
+});
+static PATH_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<path>\s*(.*?)\s*</path>").expect("Valid path regex"));
+static CHANGES_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<changes>\s*(.*?)\s*</changes>").expect("Valid changes regex"));
+static VALIDATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<validation>\s*(.*?)\s*</validation>").expect("Valid validation regex")
+});
+static BASH_COMMANDS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<bash_commands>\s*(.*?)\s*</bash_commands>")
+        .expect("Valid bash commands regex")
+});
+static INLINE_CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"`([^`\n]+)`").expect("Valid inline code regex"));
+
+/// One issue of an export, in tracker-neutral form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanIssue {
+    /// Plan step the issue covers; `None` for the parent issue
+    pub step_number: Option<String>,
+    pub title: String,
+    pub description: String,
+    /// Checklist items; inline code is written in backticks
+    pub checklist: Vec<String>,
+    pub files: Vec<String>,
+    pub acceptance_criteria: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+impl PlanIssue {
+    /// The issue body as Markdown, as used by GitHub and GitLab
+    pub fn markdown_body(&self) -> String {
+        let mut sections = Vec::new();
+        if !self.description.is_empty() {
+            sections.push(self.description.clone());
+        }
+        if !self.checklist.is_empty() {
+            let items: Vec<String> = self
+                .checklist
+                .iter()
+                .map(|item| format!("- [ ] {}", item))
+                .collect();
+            sections.push(format!("### Checklist\n\n{}", items.join("\n")));
+        }
+        if !self.files.is_empty() {
+            let items: Vec<String> = self.files.iter().map(|f| format!("- `{}`", f)).collect();
+            sections.push(format!("### Files\n\n{}", items.join("\n")));
+        }
+        if !self.acceptance_criteria.is_empty() {
+            let items: Vec<String> = self
+                .acceptance_criteria
+                .iter()
+                .map(|item| format!("- {}", item))
+                .collect();
+            sections.push(format!("### Acceptance criteria\n\n{}", items.join("\n")));
+        }
+        sections.join("\n\n")
+    }
+
+    /// The issue body in Jira wiki markup
+    pub fn jira_body(&self) -> String {
+        let jira_text = |text: &str| INLINE_CODE_REGEX.replace_all(text, "{{$1}}").into_owned();
+        let list = |items: &[String]| {
+            items
+                .iter()
+                .map(|item| format!("* {}", jira_text(item)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let mut sections = Vec::new();
+        if !self.description.is_empty() {
+            sections.push(jira_text(&self.description));
+        }
+        if !self.checklist.is_empty() {
+            sections.push(format!("h3. Checklist\n{}", list(&self.checklist)));
+        }
+        if !self.files.is_empty() {
+            let files: Vec<String> = self.files.iter().map(|f| format!("`{}`", f)).collect();
+            sections.push(format!("h3. Files\n{}", list(&files)));
+        }
+        if !self.acceptance_criteria.is_empty() {
+            sections.push(format!(
+                "h3. Acceptance criteria\n{}",
+                list(&self.acceptance_criteria)
+            ));
+        }
+        sections.join("\n\n")
+    }
+}
+
+/// A plan as a parent issue and its sub-tasks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanIssueSet {
+    pub plan_job_id: String,
+    pub parent: PlanIssue,
+    pub sub_tasks: Vec<PlanIssue>,
+}
+
+impl PlanIssueSet {
+    /// File-name friendly form of the plan title
+    pub fn slug(&self) -> String {
+        slugify(&self.parent.title)
+    }
+}
+
+/// A file produced by an exporter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedFile {
+    pub file_name: String,
+    pub media_type: String,
+    pub content: String,
+}
+
+/// A format offered by the registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanExportFormat {
+    pub id: String,
+    pub display_name: String,
+    pub description: String,
+}
+
+/// Turns a plan's issues into files in one tracker's import format
+pub trait PlanExporter: Send + Sync {
+    /// Id the format is requested by
+    fn id(&self) -> &'static str;
+    fn display_name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn export(&self, issues: &PlanIssueSet) -> AppResult<Vec<ExportedFile>>;
+}
+
+/// Exporters by format id, in registration order
+pub struct PlanExporterRegistry {
+    exporters: Vec<Box<dyn PlanExporter>>,
+}
+
+impl PlanExporterRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            exporters: Vec::new(),
+        };
+
+        // Register the built-in formats
+        registry.register(Box::new(MarkdownIssueExporter));
+        registry.register(Box::new(GitHubIssueExporter));
+        registry.register(Box::new(JiraCsvExporter));
+
+        registry
+    }
+
+    /// Add an exporter, replacing any registered with the same id
+    pub fn register(&mut self, exporter: Box<dyn PlanExporter>) {
+        self.exporters.retain(|e| e.id() != exporter.id());
+        self.exporters.push(exporter);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn PlanExporter> {
+        self.exporters
+            .iter()
+            .find(|e| e.id() == id)
+            .map(|e| e.as_ref())
+    }
+
+    pub fn formats(&self) -> Vec<PlanExportFormat> {
+        self.exporters
+            .iter()
+            .map(|e| PlanExportFormat {
+                id: e.id().to_string(),
+                display_name: e.display_name().to_string(),
+                description: e.description().to_string(),
+            })
+            .collect()
+    }
+
+    pub fn export(&self, id: &str, issues: &PlanIssueSet) -> AppResult<Vec<ExportedFile>> {
+        let exporter = self.get(id).ok_or_else(|| {
+            AppError::ValidationError(format!("Unknown plan export format: {}", id))
+        })?;
+        exporter.export(issues)
+    }
+}
+
+impl Default for PlanExporterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One Markdown file per issue with YAML front matter
+pub struct MarkdownIssueExporter;
+
+impl PlanExporter for MarkdownIssueExporter {
+    fn id(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Markdown with front matter"
+    }
+
+    fn description(&self) -> &'static str {
+        "One Markdown file per issue with title, labels and parent in YAML front matter"
+    }
+
+    fn export(&self, issues: &PlanIssueSet) -> AppResult<Vec<ExportedFile>> {
+        let parent_file = format!("00-{}.md", issues.slug());
+        let mut files = vec![ExportedFile {
+            file_name: parent_file.clone(),
+            media_type: "text/markdown".to_string(),
+            content: markdown_issue_file(&issues.parent, None),
+        }];
+        for (idx, issue) in issues.sub_tasks.iter().enumerate() {
+            files.push(ExportedFile {
+                file_name: format!("{:02}-{}.md", idx + 1, slugify(&issue.title)),
+                media_type: "text/markdown".to_string(),
+                content: markdown_issue_file(issue, Some(&parent_file)),
+            });
+        }
+        Ok(files)
+    }
+}
+
+fn markdown_issue_file(issue: &PlanIssue, parent_file: Option<&str>) -> String {
+    // JSON strings are valid YAML scalars, so serde_json does the quoting
+    let quoted = |value: &str| serde_json::Value::from(value).to_string();
+
+    let mut front_matter = vec![
+        "---".to_string(),
+        format!("title: {}", quoted(&issue.title)),
+        format!("labels: {}", json!(issue.labels)),
+    ];
+    if let Some(step_number) = &issue.step_number {
+        front_matter.push(format!("step: {}", quoted(step_number)));
+    }
+    if let Some(parent_file) = parent_file {
+        front_matter.push(format!("parent: {}", quoted(parent_file)));
+    }
+    front_matter.push("---".to_string());
+
+    format!("{}\n\n{}\n", front_matter.join("\n"), issue.markdown_body())
+}
+
+/// A JSON file with the parent and its sub-issues in the fields of GitHub's create-issue API
+/// (`title`, `body`, `labels`), for scripts that create the issues and link the sub-issues
+pub struct GitHubIssueExporter;
+
+impl PlanExporter for GitHubIssueExporter {
+    fn id(&self) -> &'static str {
+        "github"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "GitHub issues JSON"
+    }
+
+    fn description(&self) -> &'static str {
+        "Parent issue and sub-issues as GitHub issue JSON for bulk creation"
+    }
+
+    fn export(&self, issues: &PlanIssueSet) -> AppResult<Vec<ExportedFile>> {
+        let github_issue = |issue: &PlanIssue| {
+            json!({
+                "title": issue.title,
+                "body": issue.markdown_body(),
+                "labels": issue.labels,
+            })
+        };
+        let document = json!({
+            "planJobId": issues.plan_job_id,
+            "parent": github_issue(&issues.parent),
+            "subIssues": issues.sub_tasks.iter().map(github_issue).collect::<Vec<_>>(),
+        });
+        let content = serde_json::to_string_pretty(&document)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        Ok(vec![ExportedFile {
+            file_name: format!("{}.github-issues.json", issues.slug()),
+            media_type: "application/json".to_string(),
+            content,
+        }])
+    }
+}
+
+/// A CSV file for Jira's CSV importer; sub-tasks point at the parent through `Parent Id`
+pub struct JiraCsvExporter;
+
+impl PlanExporter for JiraCsvExporter {
+    fn id(&self) -> &'static str {
+        "jira_csv"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Jira CSV"
+    }
+
+    fn description(&self) -> &'static str {
+        "Task and sub-tasks in Jira's CSV import format"
+    }
+
+    fn export(&self, issues: &PlanIssueSet) -> AppResult<Vec<ExportedFile>> {
+        let row = |id: usize, parent_id: Option<usize>, issue_type: &str, issue: &PlanIssue| {
+            [
+                id.to_string(),
+                parent_id.map(|p| p.to_string()).unwrap_or_default(),
+                issue_type.to_string(),
+                issue.title.clone(),
+                issue.jira_body(),
+                issue.labels.join(" "),
+            ]
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",")
+        };
+
+        let mut lines =
+            vec!["Issue Id,Parent Id,Issue Type,Summary,Description,Labels".to_string()];
+        lines.push(row(1, None, "Task", &issues.parent));
+        for (idx, issue) in issues.sub_tasks.iter().enumerate() {
+            lines.push(row(idx + 2, Some(1), "Sub-task", issue));
+        }
+
+        Ok(vec![ExportedFile {
+            file_name: format!("{}.jira.csv", issues.slug()),
+            media_type: "text/csv".to_string(),
+            content: format!("{}\n", lines.join("\n")),
+        }])
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= MAX_SLUG_LENGTH {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "plan".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Element text with entities decoded, indentation removed and blank lines collapsed
+fn clean_text(raw: &str) -> String {
+    let text = unescape_xml(raw);
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
+
+fn tag_text(regex: &Regex, xml: &str) -> Option<String> {
+    regex
+        .captures(xml)
+        .map(|cap| clean_text(&cap[1]))
+        .filter(|text| !text.is_empty())
+}
+
+fn step_issue(
+    step_number: String,
+    title: &str,
+    step_xml: &str,
+    test_plan: Option<&PlanTestPlan>,
+) -> PlanIssue {
+    let mut description = tag_text(&DESCRIPTION_REGEX, step_xml).unwrap_or_default();
+    if let Some(assumptions) = tag_text(&ASSUMPTIONS_REGEX, step_xml) {
+        description = format!("{}\n\n**Assumptions:** {}", description, assumptions)
+            .trim()
+            .to_string();
+    }
+
+    let mut checklist = Vec::new();
+    let mut files: Vec<String> = Vec::new();
+    let mut acceptance_criteria = Vec::new();
+    for operation in OPERATION_REGEX.captures_iter(step_xml) {
+        let Some(path) = tag_text(&PATH_REGEX, &operation[2]) else {
+            continue;
+        };
+        let operation_type = TYPE_ATTR_REGEX
+            .captures(&operation[1])
+            .map(|cap| cap[1].trim().to_string())
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| "modify".to_string());
+        let mut item = format!("{} `{}`", capitalize(&operation_type), path);
+        if let Some(changes) = tag_text(&CHANGES_REGEX, &operation[2]) {
+            item.push_str(&format!(": {}", changes));
+        }
+        checklist.push(item);
+
+        for file in path.split('→').flat_map(|p| p.split("->")).map(str::trim) {
+            if !file.is_empty() && !files.iter().any(|f| f == file) {
+                files.push(file.to_string());
+            }
+        }
+        if let Some(validation) = tag_text(&VALIDATION_REGEX, &operation[2]) {
+            acceptance_criteria.push(validation);
+        }
+    }
+    if let Some(commands) = tag_text(&BASH_COMMANDS_REGEX, step_xml) {
+        checklist.extend(
+            commands
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|command| format!("Run `{}`", command)),
+        );
+    }
+
+    let planned_tests = test_plan
+        .into_iter()
+        .flat_map(|plan| &plan.steps)
+        .filter(|step| step.step_number == step_number)
+        .flat_map(|step| &step.tests);
+    for test in planned_tests {
+        acceptance_criteria.push(format!("Test `{}` in `{}` passes", test.name, test.file));
+    }
+
+    PlanIssue {
+        title: format!("Step {}: {}", step_number, title),
+        step_number: Some(step_number),
+        description,
+        checklist,
+        files,
+        acceptance_criteria,
+        labels: vec![PLAN_ISSUE_LABEL.to_string()],
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Split a plan into a parent issue and one sub-task per step. Tests from the plan's test
+/// plan become acceptance criteria of their steps.
+pub fn build_plan_issues(
+    plan_job_id: &str,
+    title: &str,
+    plan_xml: &str,
+    test_plan: Option<&PlanTestPlan>,
+) -> PlanIssueSet {
+    let sub_tasks: Vec<PlanIssue> = extract_step_headers_from_xml(plan_xml)
+        .into_iter()
+        .zip(extract_steps_from_xml(plan_xml))
+        .map(|((number, step_title), step_xml)| {
+            step_issue(number, &clean_text(&step_title), &step_xml, test_plan)
+        })
+        .collect();
+
+    let mut description = format!("Implementation plan with {} steps.", sub_tasks.len());
+    if let Some(instructions) = extract_agent_instructions_from_xml(plan_xml) {
+        description.push_str(&format!("\n\n{}", clean_text(&instructions)));
+    }
+    let mut files: Vec<String> = Vec::new();
+    for file in sub_tasks.iter().flat_map(|issue| &issue.files) {
+        if !files.contains(file) {
+            files.push(file.clone());
+        }
+    }
+
+    PlanIssueSet {
+        plan_job_id: plan_job_id.to_string(),
+        parent: PlanIssue {
+            step_number: None,
+            title: title.trim().to_string(),
+            description,
+            checklist: sub_tasks.iter().map(|issue| issue.title.clone()).collect(),
+            files,
+            acceptance_criteria: Vec::new(),
+            labels: vec![PLAN_ISSUE_LABEL.to_string()],
+        },
+        sub_tasks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_plans::{PlanStepTests, PlanTestCase};
+
+    const PLAN: &str = r#"<implementation_plan>
+  <agent_instructions>Keep the public API stable.</agent_instructions>
+  <steps>
+    <step number="1">
+      <title>Add invoice model</title>
+      <description>Store invoices &amp; their lines.</description>
+      <file_operations>
+        <operation type="create">
+          <path>src/billing/invoice.rs</path>
+          <changes>Add `Invoice` with "number" and lines</changes>
+          <validation>cargo check passes</validation>
+        </operation>
+        <operation type="move">
+          <path>src/old.rs → src/billing/legacy.rs</path>
+        </operation>
+      </file_operations>
+      <bash_commands>cargo fmt</bash_commands>
+    </step>
+    <step number="2">
+      <title>Wire routes</title>
+      <description>Expose invoices over HTTP.</description>
+    </step>
+  </steps>
+</implementation_plan>"#;
+
+    fn issues() -> PlanIssueSet {
+        let test_plan = PlanTestPlan {
+            test_job_id: "tests".to_string(),
+            plan_revision: Some(1),
+            generated_at: 0,
+            reference_files: Vec::new(),
+            steps: vec![PlanStepTests {
+                step_number: "1".to_string(),
+                title: "Add invoice model".to_string(),
+                tests: vec![PlanTestCase {
+                    name: "creates_invoice".to_string(),
+                    file: "src/billing/invoice.rs".to_string(),
+                    description: None,
+                    code: String::new(),
+                }],
+            }],
+        };
+        build_plan_issues("job-1", "Billing: invoices", PLAN, Some(&test_plan))
+    }
+
+    #[test]
+    fn steps_become_sub_tasks_with_checklists_and_criteria() {
+        let issues = issues();
+        assert_eq!(
+            issues.parent.checklist,
+            vec!["Step 1: Add invoice model", "Step 2: Wire routes"]
+        );
+        assert!(
+            issues
+                .parent
+                .description
+                .ends_with("Keep the public API stable.")
+        );
+
+        let step = &issues.sub_tasks[0];
+        assert_eq!(step.description, "Store invoices & their lines.");
+        assert_eq!(
+            step.checklist,
+            vec![
+                "Create `src/billing/invoice.rs`: Add `Invoice` with \"number\" and lines",
+                "Move `src/old.rs → src/billing/legacy.rs`",
+                "Run `cargo fmt`",
+            ]
+        );
+        assert_eq!(
+            step.files,
+            vec![
+                "src/billing/invoice.rs",
+                "src/old.rs",
+                "src/billing/legacy.rs"
+            ]
+        );
+        assert_eq!(
+            step.acceptance_criteria,
+            vec![
+                "cargo check passes",
+                "Test `creates_invoice` in `src/billing/invoice.rs` passes",
+            ]
+        );
+        assert!(issues.sub_tasks[1].checklist.is_empty());
+    }
+
+    #[test]
+    fn markdown_files_link_sub_tasks_to_the_parent() {
+        let files = PlanExporterRegistry::new()
+            .export("markdown", &issues())
+            .unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.file_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "00-billing-invoices.md",
+                "01-step-1-add-invoice-model.md",
+                "02-step-2-wire-routes.md"
+            ]
+        );
+        assert!(files[1].content.starts_with(
+            "---\ntitle: \"Step 1: Add invoice model\"\nlabels: [\"implementation-plan\"]\nstep: \"1\"\nparent: \"00-billing-invoices.md\"\n---\n"
+        ));
+        assert!(files[1].content.contains("- [ ] Run `cargo fmt`"));
+    }
+
+    #[test]
+    fn jira_csv_quotes_fields_and_sets_parent_ids() {
+        let files = PlanExporterRegistry::new()
+            .export("jira_csv", &issues())
+            .unwrap();
+        let lines: Vec<&str> = files[0].content.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Issue Id,Parent Id,Issue Type,Summary,Description,Labels"
+        );
+        assert!(lines[1].starts_with("\"1\",\"\",\"Task\",\"Billing: invoices\","));
+        assert!(
+            files[0]
+                .content
+                .contains("\"2\",\"1\",\"Sub-task\",\"Step 1: Add invoice model\"")
+        );
+        assert!(files[0].content.contains(
+            "* Create {{src/billing/invoice.rs}}: Add {{Invoice}} with \"\"number\"\" and lines"
+        ));
+    }
+
+    #[test]
+    fn registered_exporters_replace_built_ins_by_id() {
+        struct PlainExporter;
+        impl PlanExporter for PlainExporter {
+            fn id(&self) -> &'static str {
+                "markdown"
+            }
+            fn display_name(&self) -> &'static str {
+                "Plain"
+            }
+            fn description(&self) -> &'static str {
+                "Titles only"
+            }
+            fn export(&self, issues: &PlanIssueSet) -> AppResult<Vec<ExportedFile>> {
+                Ok(vec![ExportedFile {
+                    file_name: "plan.txt".to_string(),
+                    media_type: "text/plain".to_string(),
+                    content: issues.parent.title.clone(),
+                }])
+            }
+        }
+
+        let mut registry = PlanExporterRegistry::new();
+        registry.register(Box::new(PlainExporter));
+        let ids: Vec<String> = registry.formats().into_iter().map(|f| f.id).collect();
+        assert_eq!(ids, vec!["github", "jira_csv", "markdown"]);
+        assert_eq!(
+            registry.export("markdown", &issues()).unwrap()[0].content,
+            "Billing: invoices"
+        );
+        assert!(registry.export("asana", &issues()).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+//! Team-defined implementation plan templates (`[plans]` in the project file) and the check of
+//! generated plans against them.
+//!
+//! Plans keep their usual `<steps>`; a template adds named sections the plan has to contain,
+//! written inside `<implementation_plan>` as
+//!
+//! ```xml
+//! <sections>
+//!   <section id="rollback">
+//!     <title>Rollback</title>
+//!     <content>...</content>
+//!   </section>
+//! </sections>
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::utils::get_timestamp;
+use crate::utils::project_config::{PlanTemplateConfig, ProjectConfig};
+
+/// Session selection that turns the project's default template off
+pub const NO_PLAN_TEMPLATE: &str = "none";
+
+static SECTION_ID_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z0-9][a-z0-9_-]{0,63}$").expect("Valid section id regex"));
+static SECTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<section\b([^>]*)>(.*?)</section>").expect("Valid section regex")
+});
+static ID_ATTR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bid\s*=\s*["']([^"']*)["']"#).expect("Valid id attribute regex"));
+static TITLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<title>\s*(.*?)\s*</title>").expect("Valid title regex"));
+static CONTENT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<content>\s*(.*?)\s*</content>").expect("Valid content regex"));
+
+pub fn is_valid_section_id(id: &str) -> bool {
+    SECTION_ID_REGEX.is_match(id)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTemplateSection {
+    pub id: String,
+    pub title: String,
+    pub instructions: Option<String>,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub sections: Vec<PlanTemplateSection>,
+    pub request_missing_sections: bool,
+    /// The project's `plans.default_template`
+    pub is_default: bool,
+}
+
+impl PlanTemplate {
+    pub fn from_config(id: &str, config: &PlanTemplateConfig, is_default: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            name: config
+                .name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| id.to_string()),
+            description: config.description.clone(),
+            sections: config
+                .sections
+                .iter()
+                .map(|section| PlanTemplateSection {
+                    id: section.id.clone(),
+                    title: section.title.clone(),
+                    instructions: section.instructions.clone(),
+                    required: !section.optional,
+                })
+                .collect(),
+            request_missing_sections: config.request_missing_sections,
+            is_default,
+        }
+    }
+
+    /// Block appended to the task description so the model writes the template's sections
+    pub fn prompt_instructions(&self) -> String {
+        let mut block = format!(
+            "<plan_template name=\"{}\">\nBesides <steps>, the plan MUST contain a <sections> element inside <implementation_plan> with one <section id=\"...\"><title>...</title><content>...</content></section> per section below, using these ids:\n",
+            self.name
+        );
+        for section in &self.sections {
+            block.push_str(&format!(
+                "- id=\"{}\" ({}){}",
+                section.id,
+                section.title,
+                if section.required { "" } else { " [optional]" }
+            ));
+            if let Some(instructions) = section.instructions.as_ref().filter(|i| !i.is_empty()) {
+                block.push_str(&format!(": {}", instructions.trim()));
+            }
+            block.push('\n');
+        }
+        block.push_str("</plan_template>");
+        block
+    }
+}
+
+/// Templates defined in a project file, in id order
+pub fn project_plan_templates(config: &ProjectConfig) -> Vec<PlanTemplate> {
+    config
+        .plans
+        .templates
+        .iter()
+        .map(|(id, template)| {
+            let is_default = config.plans.default_template.as_deref() == Some(id.as_str());
+            PlanTemplate::from_config(id, template, is_default)
+        })
+        .collect()
+}
+
+/// A `<section>` of a generated plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanSection {
+    pub id: String,
+    pub title: Option<String>,
+    pub content: String,
+    /// The complete `<section>` element
+    pub xml: String,
+}
+
+/// Sections with an `id` attribute, in document order. Without a `<content>` element the
+/// section's text besides its title counts as content.
+pub fn extract_plan_sections(plan_xml: &str) -> Vec<PlanSection> {
+    SECTION_REGEX
+        .captures_iter(plan_xml)
+        .filter_map(|cap| {
+            let id = ID_ATTR_REGEX.captures(&cap[1])?[1].trim().to_string();
+            let inner = &cap[2];
+            let content = match CONTENT_REGEX.captures(inner) {
+                Some(content) => content[1].to_string(),
+                None => TITLE_REGEX.replace(inner, "").trim().to_string(),
+            };
+            Some(PlanSection {
+                id,
+                title: TITLE_REGEX
+                    .captures(inner)
+                    .map(|title| title[1].to_string()),
+                content,
+                xml: cap[0].to_string(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingPlanSection {
+    pub id: String,
+    pub title: String,
+    pub required: bool,
+    /// The section is there, but has no content
+    pub empty: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTemplateCheck {
+    pub template_id: String,
+    pub template_name: String,
+    /// Template sections the plan contains, in template order
+    pub present_sections: Vec<String>,
+    pub missing_sections: Vec<MissingPlanSection>,
+    /// Every required section is present
+    pub passed: bool,
+    /// Missing sections were asked for in a follow-up turn
+    pub follow_up_requested: bool,
+    pub checked_at: i64,
+}
+
+impl PlanTemplateCheck {
+    pub fn missing_required(&self) -> impl Iterator<Item = &MissingPlanSection> {
+        self.missing_sections.iter().filter(|s| s.required)
+    }
+}
+
+pub fn check_plan_against_template(plan_xml: &str, template: &PlanTemplate) -> PlanTemplateCheck {
+    let sections = extract_plan_sections(plan_xml);
+    let mut present_sections = Vec::new();
+    let mut missing_sections = Vec::new();
+
+    for expected in &template.sections {
+        let found = sections.iter().find(|s| s.id == expected.id);
+        match found {
+            Some(section) if !section.content.trim().is_empty() => {
+                present_sections.push(expected.id.clone())
+            }
+            found => missing_sections.push(MissingPlanSection {
+                id: expected.id.clone(),
+                title: expected.title.clone(),
+                required: expected.required,
+                empty: found.is_some(),
+            }),
+        }
+    }
+
+    PlanTemplateCheck {
+        template_id: template.id.clone(),
+        template_name: template.name.clone(),
+        passed: missing_sections.iter().all(|s| !s.required),
+        present_sections,
+        missing_sections,
+        follow_up_requested: false,
+        checked_at: get_timestamp(),
+    }
+}
+
+/// Follow-up request for the required sections a plan lacks, `None` when none are missing
+pub fn missing_sections_request(
+    plan_xml: &str,
+    template: &PlanTemplate,
+    check: &PlanTemplateCheck,
+) -> Option<String> {
+    let missing: HashSet<&str> = check.missing_required().map(|s| s.id.as_str()).collect();
+    if missing.is_empty() {
+        return None;
+    }
+
+    let wanted = PlanTemplate {
+        sections: template
+            .sections
+            .iter()
+            .filter(|s| missing.contains(s.id.as_str()))
+            .cloned()
+            .collect(),
+        ..template.clone()
+    };
+    Some(format!(
+        "The implementation plan below is missing sections the team's plan template requires. Write ONLY a <sections> element containing the missing sections, consistent with the plan's steps. Do not repeat the steps.\n\n{}\n\n<existing_plan>\n{}\n</existing_plan>",
+        wanted.prompt_instructions(),
+        plan_xml
+    ))
+}
+
+/// Add the sections listed in `section_ids` from a follow-up `response` to the plan. Empty
+/// sections of the plan with those ids are replaced; the rest go into the plan's `<sections>`
+/// element, which is created before `</implementation_plan>` when missing.
+pub fn merge_plan_sections(plan_xml: &str, response: &str, section_ids: &[&str]) -> String {
+    let additions: Vec<PlanSection> = extract_plan_sections(response)
+        .into_iter()
+        .filter(|s| section_ids.contains(&s.id.as_str()) && !s.content.trim().is_empty())
+        .collect();
+    if additions.is_empty() {
+        return plan_xml.to_string();
+    }
+
+    let mut merged = plan_xml.to_string();
+    for existing in extract_plan_sections(plan_xml) {
+        if existing.content.trim().is_empty() && additions.iter().any(|a| a.id == existing.id) {
+            merged = merged.replacen(&existing.xml, "", 1);
+        }
+    }
+
+    let added = additions
+        .iter()
+        .map(|s| s.xml.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Some(pos) = merged.rfind("</sections>") {
+        merged.insert_str(pos, &format!("{}\n", added));
+    } else if let Some(pos) = merged.rfind("</implementation_plan>") {
+        merged.insert_str(pos, &format!("<sections>\n{}\n</sections>\n", added));
+    } else {
+        merged.push_str(&format!("\n<sections>\n{}\n</sections>", added));
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::project_config::PlanSectionConfig;
+
+    fn template() -> PlanTemplate {
+        let section = |id: &str, title: &str, optional: bool| PlanSectionConfig {
+            id: id.to_string(),
+            title: title.to_string(),
+            instructions: None,
+            optional,
+        };
+        PlanTemplate::from_config(
+            "backend",
+            &PlanTemplateConfig {
+                name: Some("Backend change".to_string()),
+                description: None,
+                sections: vec![
+                    section("migration", "Migration steps", false),
+                    section("rollback", "Rollback", false),
+                    section("security", "Security considerations", true),
+                ],
+                request_missing_sections: true,
+            },
+            true,
+        )
+    }
+
+    const PLAN: &str = r#"<implementation_plan>
+  <steps>
+    <step number="1"><title>Add column</title><description>...</description></step>
+  </steps>
+  <sections>
+    <section id="migration">
+      <title>Migration steps</title>
+      <content>Run the add_orders_status migration before deploying.</content>
+    </section>
+    <section id="rollback"><title>Rollback</title><content>  </content></section>
+  </sections>
+</implementation_plan>"#;
+
+    #[test]
+    fn reports_missing_and_empty_sections() {
+        let check = check_plan_against_template(PLAN, &template());
+
+        assert_eq!(check.present_sections, vec!["migration"]);
+        assert!(!check.passed);
+        let missing: Vec<(&str, bool, bool)> = check
+            .missing_sections
+            .iter()
+            .map(|s| (s.id.as_str(), s.required, s.empty))
+            .collect();
+        assert_eq!(
+            missing,
+            vec![("rollback", true, true), ("security", false, false)]
+        );
+
+        let request = missing_sections_request(PLAN, &template(), &check).unwrap();
+        assert!(request.contains("id=\"rollback\""));
+        assert!(!request.contains("id=\"security\""));
+    }
+
+    #[test]
+    fn merges_follow_up_sections_into_the_plan() {
+        let response = r#"<sections>
+  <section id="rollback"><title>Rollback</title><content>Revert the deploy and drop the column.</content></section>
+  <section id="migration"><title>Migration steps</title><content>Different text</content></section>
+</sections>"#;
+
+        let merged = merge_plan_sections(PLAN, response, &["rollback"]);
+        let check = check_plan_against_template(&merged, &template());
+        assert!(check.passed);
+        assert_eq!(check.present_sections, vec!["migration", "rollback"]);
+        assert_eq!(merged.matches("<section id=\"migration\">").count(), 1);
+        assert_eq!(merged.matches("<section id=\"rollback\">").count(), 1);
+
+        let without_sections = "<implementation_plan><steps></steps></implementation_plan>";
+        let merged = merge_plan_sections(without_sections, response, &["rollback"]);
+        assert!(merged.ends_with("</sections>\n</implementation_plan>"));
+        assert_eq!(extract_plan_sections(&merged).len(), 1);
+    }
+}
@@ -0,0 +1,503 @@
+//! Per-project configuration checked into the repository as `.plantocode.toml`
+//! (or `.plantocode.json`) in the project root.
+//!
+//! Precedence when resolving a setting, highest first:
+//! 1. Explicit overrides passed with the request
+//! 2. Settings saved in the app for this project (`project_task_settings`,
+//!    custom `project_system_prompts`)
+//! 3. This project file
+//! 4. App-wide settings (`workflow_settings`) and server defaults (`runtime_ai_config`)
+//!
+//! The file is re-read whenever its modification time or size changes, so
+//! edits apply to the next job without restarting. A file that fails
+//! validation is ignored as a whole and its errors are reported by
+//! `get_project_config_command`.
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::models::TaskType;
+use crate::validation::{ValidationResult, validate_project_config};
+
+/// Looked up in this order; the first existing file wins
+pub const PROJECT_CONFIG_FILENAMES: [&str; 2] = [".plantocode.toml", ".plantocode.json"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Keyed by task type, e.g. `implementation_plan`
+    pub tasks: BTreeMap<String, ProjectTaskConfig>,
+    pub directory_tree: DirectoryTreeConfig,
+    pub files: FileSelectionConfig,
+    /// Workflow name -> setting key -> value, e.g.
+    /// `workflows.FileFinderWorkflow.ExtendedPathFinder_model`
+    pub workflows: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+    pub plans: PlansConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectTaskConfig {
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub system_prompt: Option<String>,
+    /// Project-relative path to a file holding the system prompt
+    pub system_prompt_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DirectoryTreeConfig {
+    /// Globs (relative to the project root) hidden from generated directory trees
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileSelectionConfig {
+    /// Files selected by default in new sessions
+    pub include: Vec<String>,
+    /// Files never selected automatically
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlansConfig {
+    /// Template applied to sessions that have not selected one
+    pub default_template: Option<String>,
+    /// Keyed by template id, e.g. `plans.templates.backend`
+    pub templates: BTreeMap<String, PlanTemplateConfig>,
+}
+
+/// Sections a team requires in implementation plans, besides the steps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlanTemplateConfig {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub sections: Vec<PlanSectionConfig>,
+    /// Ask the model for missing required sections in a follow-up turn
+    pub request_missing_sections: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlanSectionConfig {
+    /// Written as `<section id="...">` in the plan
+    pub id: String,
+    pub title: String,
+    /// What the section has to cover
+    pub instructions: Option<String>,
+    /// Reported when missing, but does not fail the check
+    pub optional: bool,
+}
+
+impl ProjectConfig {
+    pub fn parse(path: &Path, content: &str) -> Result<Self, String> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(content).map_err(|e| e.to_string()),
+            _ => toml::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub fn task(&self, task_type: TaskType) -> Option<&ProjectTaskConfig> {
+        self.tasks.get(&task_type.to_string())
+    }
+
+    /// System prompt for a task, reading `system_prompt_file` relative to the project root
+    pub fn system_prompt(&self, project_directory: &Path, task_type: TaskType) -> Option<String> {
+        let task = self.task(task_type)?;
+        if let Some(prompt) = task.system_prompt.as_ref().filter(|p| !p.trim().is_empty()) {
+            return Some(prompt.clone());
+        }
+        let relative = task.system_prompt_file.as_ref()?;
+        match std::fs::read_to_string(project_directory.join(relative)) {
+            Ok(prompt) if !prompt.trim().is_empty() => Some(prompt),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("Failed to read system prompt file {}: {}", relative, e);
+                None
+            }
+        }
+    }
+
+    pub fn workflow_setting(&self, workflow_name: &str, setting_key: &str) -> Option<String> {
+        match self.workflows.get(workflow_name)?.get(setting_key)? {
+            serde_json::Value::String(s) if s.trim().is_empty() => None,
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    pub fn is_tree_excluded(&self, relative_path: &Path) -> bool {
+        matches_path_or_ancestor(relative_path, &self.directory_tree.exclude)
+    }
+
+    pub fn is_file_excluded(&self, relative_path: &Path) -> bool {
+        matches_path_or_ancestor(relative_path, &self.files.exclude)
+    }
+
+    /// Files from `candidates` selected by the `files.include`/`files.exclude` globs
+    pub fn default_included_files<'a>(
+        &self,
+        candidates: impl IntoIterator<Item = &'a Path>,
+    ) -> Vec<String> {
+        if self.files.include.is_empty() {
+            return Vec::new();
+        }
+        candidates
+            .into_iter()
+            .filter(|path| crate::utils::path_utils::matches_any_pattern(path, &self.files.include))
+            .filter(|path| !self.is_file_excluded(path))
+            .map(|path| crate::utils::path_utils::to_forward_slashes(&path.to_string_lossy()))
+            .collect()
+    }
+}
+
+/// A glob matches a path when it matches the path itself or one of its parent
+/// directories, so `vendor` excludes everything below `vendor/`
+fn matches_path_or_ancestor(path: &Path, patterns: &[String]) -> bool {
+    !patterns.is_empty()
+        && path
+            .ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|p| crate::utils::path_utils::matches_any_pattern(p, patterns))
+}
+
+/// A parsed project file with its validation outcome
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedProjectConfig {
+    pub path: PathBuf,
+    pub config: ProjectConfig,
+    pub validation: ValidationResult,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FileStamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+struct CachedConfig {
+    stamp: Option<FileStamp>,
+    loaded: Option<Arc<LoadedProjectConfig>>,
+}
+
+static PROJECT_CONFIGS: Lazy<Mutex<HashMap<PathBuf, CachedConfig>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn current_stamp(project_directory: &Path) -> Option<FileStamp> {
+    PROJECT_CONFIG_FILENAMES.iter().find_map(|name| {
+        let path = project_directory.join(name);
+        let metadata = std::fs::metadata(&path).ok()?;
+        metadata.is_file().then(|| FileStamp {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            path,
+        })
+    })
+}
+
+fn load_file(path: &Path) -> LoadedProjectConfig {
+    let parsed = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| ProjectConfig::parse(path, &content));
+
+    match parsed {
+        Ok(config) => {
+            let validation = validate_project_config(&config, None);
+            LoadedProjectConfig {
+                path: path.to_path_buf(),
+                config,
+                validation,
+            }
+        }
+        Err(message) => LoadedProjectConfig {
+            path: path.to_path_buf(),
+            config: ProjectConfig::default(),
+            validation: ValidationResult::parse_failure(&path.to_string_lossy(), message),
+        },
+    }
+}
+
+/// The project file of `project_directory`, parsed and structurally validated,
+/// whether or not it is valid. Re-read when the file changes.
+pub fn load_project_config(project_directory: &str) -> Option<Arc<LoadedProjectConfig>> {
+    if project_directory.trim().is_empty() {
+        return None;
+    }
+    let dir = PathBuf::from(project_directory);
+    let stamp = current_stamp(&dir);
+
+    let mut cache = PROJECT_CONFIGS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cached) = cache.get(&dir) {
+        if cached.stamp == stamp {
+            return cached.loaded.clone();
+        }
+    }
+
+    let loaded = stamp.as_ref().map(|s| Arc::new(load_file(&s.path)));
+    match &loaded {
+        Some(loaded) if loaded.validation.is_valid => {
+            info!("Loaded project config {}", loaded.path.display())
+        }
+        Some(loaded) => warn!(
+            "Ignoring invalid project config {}: {}",
+            loaded.path.display(),
+            loaded
+                .validation
+                .errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+        None => {}
+    }
+
+    cache.insert(
+        dir,
+        CachedConfig {
+            stamp,
+            loaded: loaded.clone(),
+        },
+    );
+    loaded
+}
+
+/// The project configuration to apply, if the project has a valid file
+pub fn active_project_config(project_directory: &str) -> Option<Arc<LoadedProjectConfig>> {
+    load_project_config(project_directory).filter(|loaded| loaded.validation.is_valid)
+}
+
+/// Files a new session in `project_directory` starts with, from the `files.include`
+/// globs matched against the project's non-ignored files
+pub async fn default_session_files(project_directory: &str) -> Vec<String> {
+    let Some(loaded) = active_project_config(project_directory) else {
+        return Vec::new();
+    };
+    if loaded.config.files.include.is_empty() {
+        return Vec::new();
+    }
+
+    let dir = PathBuf::from(project_directory);
+    let listed = tokio::task::spawn_blocking(move || {
+        crate::utils::git_utils::get_all_non_ignored_files(&dir)
+    })
+    .await;
+    match listed {
+        Ok(Ok((files, _))) => loaded
+            .config
+            .default_included_files(files.iter().map(|p| p.as_path())),
+        Ok(Err(e)) => {
+            warn!("Failed to list files for default session selection: {}", e);
+            Vec::new()
+        }
+        Err(e) => {
+            warn!("Failed to list files for default session selection: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Validate the project file including model availability, for reporting in the UI
+pub fn validate_with_models(
+    loaded: &LoadedProjectConfig,
+    available_models: &HashSet<String>,
+) -> ValidationResult {
+    if loaded
+        .validation
+        .errors
+        .iter()
+        .any(|e| e.field.as_deref() == Some("syntax"))
+    {
+        return loaded.validation.clone();
+    }
+    validate_project_config(&loaded.config, Some(available_models))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_TOML: &str = r#"
+[tasks.implementation_plan]
+model = "anthropic/claude-sonnet"
+temperature = 0.4
+system_prompt_file = ".plantocode/plan.md"
+
+[tasks.regex_file_filter]
+max_tokens = 4000
+
+[directory_tree]
+exclude = ["vendor", "**/*.snap"]
+
+[files]
+include = ["src/**/*.rs"]
+exclude = ["src/generated/**"]
+
+[workflows.FileFinderWorkflow]
+ExtendedPathFinder_model = "openai/gpt-5-mini"
+maxFiles = 40
+
+[plans]
+default_template = "backend"
+
+[plans.templates.backend]
+name = "Backend change"
+request_missing_sections = true
+
+[[plans.templates.backend.sections]]
+id = "rollback"
+title = "Rollback"
+instructions = "How to revert the change in production"
+
+[[plans.templates.backend.sections]]
+id = "security"
+title = "Security considerations"
+optional = true
+"#;
+
+    fn temp_project() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("plantocode-config-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_toml_and_accessors() {
+        let config = ProjectConfig::parse(Path::new(".plantocode.toml"), SAMPLE_TOML).unwrap();
+
+        let plan = config.task(TaskType::ImplementationPlan).unwrap();
+        assert_eq!(plan.model.as_deref(), Some("anthropic/claude-sonnet"));
+        assert_eq!(plan.temperature, Some(0.4));
+        assert_eq!(
+            config.task(TaskType::RegexFileFilter).unwrap().max_tokens,
+            Some(4000)
+        );
+
+        assert!(config.is_tree_excluded(Path::new("vendor/lib/a.rs")));
+        assert!(config.is_tree_excluded(Path::new("tests/__snapshots__/x.snap")));
+        assert!(!config.is_tree_excluded(Path::new("src/main.rs")));
+
+        let candidates = [
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("src/generated/schema.rs"),
+            PathBuf::from("README.md"),
+        ];
+        assert_eq!(
+            config.default_included_files(candidates.iter().map(|p| p.as_path())),
+            vec!["src/main.rs".to_string()]
+        );
+
+        assert_eq!(
+            config
+                .workflow_setting("FileFinderWorkflow", "ExtendedPathFinder_model")
+                .as_deref(),
+            Some("openai/gpt-5-mini")
+        );
+        assert_eq!(
+            config
+                .workflow_setting("FileFinderWorkflow", "maxFiles")
+                .as_deref(),
+            Some("40")
+        );
+
+        assert_eq!(config.plans.default_template.as_deref(), Some("backend"));
+        let template = &config.plans.templates["backend"];
+        assert!(template.request_missing_sections);
+        assert_eq!(template.sections.len(), 2);
+        assert!(!template.sections[0].optional);
+        assert!(template.sections[1].optional);
+    }
+
+    #[test]
+    fn test_json_and_unknown_fields() {
+        let json = r#"{ "tasks": { "task_refinement": { "temperature": 0.2 } } }"#;
+        let config = ProjectConfig::parse(Path::new(".plantocode.json"), json).unwrap();
+        assert_eq!(
+            config.task(TaskType::TaskRefinement).unwrap().temperature,
+            Some(0.2)
+        );
+
+        let typo = "[tasks.implementation_plan]\nmodle = \"x\"\n";
+        assert!(ProjectConfig::parse(Path::new(".plantocode.toml"), typo).is_err());
+    }
+
+    #[test]
+    fn test_plan_template_validation() {
+        let valid = ProjectConfig::parse(Path::new(".plantocode.toml"), SAMPLE_TOML).unwrap();
+        assert!(validate_project_config(&valid, None).is_valid);
+
+        let invalid = r#"
+[plans]
+default_template = "frontend"
+
+[[plans.templates.backend.sections]]
+id = "Rollback Plan"
+title = "Rollback"
+
+[[plans.templates.backend.sections]]
+id = "tests"
+title = ""
+"#;
+        let config = ProjectConfig::parse(Path::new(".plantocode.toml"), invalid).unwrap();
+        let result = validate_project_config(&config, None);
+        assert!(!result.is_valid);
+        let fields: Vec<&str> = result
+            .errors
+            .iter()
+            .filter_map(|e| e.field.as_deref())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["sections.id", "sections.title", "default_template"]
+        );
+    }
+
+    #[test]
+    fn test_reloads_when_file_changes() {
+        let dir = temp_project();
+        let dir_str = dir.to_string_lossy().to_string();
+        assert!(load_project_config(&dir_str).is_none());
+
+        std::fs::write(
+            dir.join(".plantocode.toml"),
+            "[tasks.implementation_plan]\ntemperature = 0.5\n",
+        )
+        .unwrap();
+        let first = active_project_config(&dir_str).unwrap();
+        assert_eq!(
+            first
+                .config
+                .task(TaskType::ImplementationPlan)
+                .unwrap()
+                .temperature,
+            Some(0.5)
+        );
+
+        std::fs::write(
+            dir.join(".plantocode.toml"),
+            "[tasks.implementation_plan]\ntemperature = 7.25\n",
+        )
+        .unwrap();
+        let invalid = load_project_config(&dir_str).unwrap();
+        assert!(!invalid.validation.is_valid);
+        assert!(active_project_config(&dir_str).is_none());
+
+        std::fs::write(dir.join(".plantocode.toml"), "this is = = not toml").unwrap();
+        assert!(!load_project_config(&dir_str).unwrap().validation.is_valid);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,871 @@
+//! Compact "repository map" of source files for prompts that cannot hold every file in full.
+//!
+//! Lightweight, line-based parsers for Rust, TypeScript/JavaScript, Python and Go keep only
+//! the module structure and the declarations of a file: signatures of functions and methods,
+//! and the bodies of types (struct fields, enum variants, interface members). Function bodies
+//! are left out. Files are then split against a token budget so that the highest-ranked files
+//! keep their full text and the rest are represented by their map.
+
+use crate::utils::token_estimator::estimate_tokens_batch;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Tokens kept free for the system prompt template and message framing
+const PROMPT_OVERHEAD_TOKENS: u32 = 8_000;
+/// Longest signature, in lines, joined into a single map entry
+const MAX_SIGNATURE_LINES: usize = 12;
+const INDENT: &str = "    ";
+
+static RUST_DECL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^(pub(?:\([^)]*\))?\s+)?(?:(?:default|const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*(fn|struct|enum|union|trait|impl|mod|type|const|static|macro_rules!|use)\b(\s+tests\b)?"#,
+    )
+    .expect("Valid Rust declaration regex")
+});
+
+static TS_DECL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(function\b|class\b|interface\b|enum\b|namespace\s+[\w$.]|module\s+[\w$.]|type\b|const\b|let\b|var\b|\{|\*)",
+    )
+    .expect("Valid TypeScript declaration regex")
+});
+
+static TS_FUNCTION_VALUE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"=\s*(?:async\s+)?(?:function\b|\(|[\w$]+\s*=>|<)")
+        .expect("Valid TypeScript function value regex")
+});
+
+static TS_MEMBER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:(?:public|private|protected|static|readonly|abstract|override|declare|async|get|set|accessor)\s+)*\*?(?:#?[\w$]+|\[[^\]]*\])\s*[?!]?\s*(?:<[^>]*>)?\s*[(:=;]",
+    )
+    .expect("Valid TypeScript member regex")
+});
+
+static GO_DECL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(func|package)\b|type\s+[\w$]+(?:\[[^\]]*\])?\s+(struct|interface)?)")
+        .expect("Valid Go declaration regex")
+});
+
+static PY_CONSTANT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Z_][A-Z0-9_]*\s*(?::[^=]+)?=").expect("Valid Python constant regex")
+});
+
+static PY_ATTRIBUTE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z_]\w*\s*:").expect("Valid Python attribute regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceLanguage {
+    Rust,
+    /// TypeScript and JavaScript
+    TypeScript,
+    Python,
+    Go,
+}
+
+impl SourceLanguage {
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "rs" => Some(Self::Rust),
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::TypeScript),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+}
+
+/// What the block opened by a line's `{` contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    /// Module, impl, trait or namespace: its items are declarations
+    Scope,
+    /// Class body: its items are members
+    Members,
+    /// Struct, enum or interface body: kept line by line
+    Shape,
+    /// Function body or value: left out
+    Body,
+}
+
+/// Blank out comments and string literal contents while keeping byte offsets, so brackets can
+/// be counted on the result and positions mapped back to the original line
+fn mask_code(
+    line: &str,
+    language: SourceLanguage,
+    open_block: &mut Option<&'static str>,
+) -> String {
+    let python = language == SourceLanguage::Python;
+    let mut out = String::with_capacity(line.len());
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+
+    while i < line.len() {
+        let rest = &line[i..];
+        let c = rest.chars().next().expect("index is on a char boundary");
+
+        if let Some(closer) = *open_block {
+            let width = if rest.starts_with(closer) {
+                *open_block = None;
+                closer.len()
+            } else {
+                c.len_utf8()
+            };
+            out.push_str(&" ".repeat(width));
+            i += width;
+            continue;
+        }
+
+        if let Some(q) = quote {
+            let mut width = c.len_utf8();
+            if c == q {
+                quote = None;
+                out.push(c);
+                i += width;
+                continue;
+            }
+            if c == '\\' {
+                width += rest[1..].chars().next().map_or(0, char::len_utf8);
+            }
+            out.push_str(&" ".repeat(width));
+            i += width;
+            continue;
+        }
+
+        if (python && c == '#') || (!python && rest.starts_with("//")) {
+            break;
+        }
+        let block_open = if python {
+            ["\"\"\"", "'''"].into_iter().find(|d| rest.starts_with(*d))
+        } else {
+            rest.starts_with("/*").then_some("/*")
+        };
+        if let Some(opener) = block_open {
+            *open_block = Some(if opener == "/*" { "*/" } else { opener });
+            out.push_str(&" ".repeat(opener.len()));
+            i += opener.len();
+            continue;
+        }
+
+        match c {
+            '"' | '`' => quote = Some(c),
+            '\'' if language != SourceLanguage::Rust || is_rust_char_literal(&rest[1..]) => {
+                quote = Some(c)
+            }
+            _ => {}
+        }
+        out.push(c);
+        i += c.len_utf8();
+    }
+    out
+}
+
+/// Whether a `'` followed by `after` starts a char literal rather than a lifetime
+fn is_rust_char_literal(after: &str) -> bool {
+    let mut chars = after.chars();
+    match chars.next() {
+        Some('\\') => true,
+        Some(_) => chars.next() == Some('\''),
+        None => false,
+    }
+}
+
+fn classify_rust(line: &str) -> Option<Block> {
+    let caps = RUST_DECL_REGEX.captures(line)?;
+    let block = match &caps[2] {
+        "impl" | "trait" => Block::Scope,
+        "mod" if caps.get(3).is_none() => Block::Scope,
+        "struct" | "enum" | "union" => Block::Shape,
+        // Private imports say nothing about the file's interface
+        "use" if caps.get(1).is_none() => return None,
+        _ => Block::Body,
+    };
+    Some(block)
+}
+
+fn classify_typescript(line: &str, parent: Option<Block>) -> Option<Block> {
+    if parent == Some(Block::Members) {
+        return TS_MEMBER_REGEX.is_match(line).then_some(Block::Body);
+    }
+    let caps = TS_DECL_REGEX.captures(line)?;
+    let keyword = &caps[2];
+    let block = match keyword {
+        "class" => Block::Members,
+        "interface" | "enum" | "type" => Block::Shape,
+        "const" | "let" | "var" => {
+            if caps.get(1).is_none() && !TS_FUNCTION_VALUE_REGEX.is_match(line) {
+                return None;
+            }
+            Block::Body
+        }
+        // Re-exports: `export { a, b } from "./x"`, `export * from "./y"`
+        "{" | "*" if caps.get(1).is_some() => Block::Shape,
+        "{" | "*" => return None,
+        _ if keyword.starts_with("namespace") || keyword.starts_with("module") => Block::Scope,
+        _ => Block::Body,
+    };
+    Some(block)
+}
+
+fn classify_go(line: &str, parent: Option<Block>) -> Option<Block> {
+    if parent.is_some() {
+        return None;
+    }
+    let caps = GO_DECL_REGEX.captures(line)?;
+    Some(if caps.get(2).is_some() {
+        Block::Shape
+    } else {
+        Block::Body
+    })
+}
+
+fn classify(language: SourceLanguage, line: &str, parent: Option<Block>) -> Option<Block> {
+    match language {
+        SourceLanguage::Rust => classify_rust(line),
+        SourceLanguage::TypeScript => classify_typescript(line, parent),
+        SourceLanguage::Go => classify_go(line, parent),
+        SourceLanguage::Python => None,
+    }
+}
+
+fn indented(depth: usize, text: &str) -> String {
+    format!("{}{}", INDENT.repeat(depth), text)
+}
+
+/// Drop a dangling `=` or `=>` left where a value or function body was cut off
+fn clean_signature(signature: &str) -> &str {
+    signature
+        .trim_end()
+        .trim_end_matches("=>")
+        .trim_end()
+        .trim_end_matches('=')
+        .trim_end()
+}
+
+/// A declaration whose signature continues on the following lines
+struct PendingDeclaration {
+    opens: Block,
+    parts: Vec<String>,
+    /// Open `(` and `[` in the signature so far
+    brackets: i32,
+}
+
+impl PendingDeclaration {
+    fn signature(&self) -> String {
+        self.parts.iter().fold(String::new(), |signature, part| {
+            join_signature(signature, part)
+        })
+    }
+}
+
+/// Append the next line of a multi-line signature, as a formatter would write it on one line
+fn join_signature(mut signature: String, part: &str) -> String {
+    if part.starts_with([')', ']']) {
+        let kept = signature.trim_end_matches(',').len();
+        signature.truncate(kept);
+    } else if !signature.is_empty() && !signature.ends_with(['(', '[']) {
+        signature.push(' ');
+    }
+    signature.push_str(part);
+    signature
+}
+
+/// Outline of a brace-delimited language (Rust, TypeScript/JavaScript, Go)
+fn outline_braced(content: &str, language: SourceLanguage) -> String {
+    // Languages where a line break can end a statement
+    let line_ends_statements = language != SourceLanguage::Rust;
+    let mut out: Vec<String> = Vec::new();
+    // Open blocks with the line they were opened on
+    let mut stack: Vec<(Block, usize)> = Vec::new();
+    let mut pending: Option<PendingDeclaration> = None;
+    let mut open_block = None;
+
+    for (line_no, raw) in content.lines().enumerate() {
+        let code = mask_code(raw, language, &mut open_block);
+        let code_end = code.trim_end().len();
+        let start = code.len() - code.trim_start().len();
+        if code_end <= start {
+            continue;
+        }
+        let code_line = &code[start..code_end];
+        let depth = stack.len();
+        let parent = stack.last().map(|(block, _)| *block);
+        let mut scan_from = start;
+
+        if pending.is_none() {
+            match parent {
+                Some(Block::Shape) => {
+                    let attribute = code_line.starts_with("#[") || code_line.starts_with('@');
+                    if !code_line.starts_with('}') && !attribute {
+                        out.push(indented(depth, raw[start..code_end].trim()));
+                    }
+                }
+                Some(Block::Body) => {}
+                _ => {
+                    pending =
+                        classify(language, code_line, parent).map(|opens| PendingDeclaration {
+                            opens,
+                            parts: Vec::new(),
+                            brackets: 0,
+                        });
+                }
+            }
+        }
+
+        if let Some(decl) = pending.as_mut() {
+            let mut terminator = None;
+            for (offset, c) in code_line.char_indices() {
+                match c {
+                    '(' | '[' => decl.brackets += 1,
+                    ')' | ']' => decl.brackets -= 1,
+                    '{' | ';' if decl.brackets <= 0 => {
+                        terminator = Some((start + offset, c));
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            match terminator {
+                Some((pos, ';')) => {
+                    decl.parts.push(raw[start..=pos].trim().to_string());
+                    out.push(indented(depth, &decl.signature()));
+                    pending = None;
+                    scan_from = pos + 1;
+                }
+                Some((pos, _)) => {
+                    decl.parts.push(raw[start..pos].trim().to_string());
+                    let opens = decl.opens;
+                    let signature = decl.signature();
+                    pending = None;
+
+                    let mut balance = 0;
+                    let mut closes_at = None;
+                    for (offset, c) in code[pos..code_end].char_indices() {
+                        match c {
+                            '{' => balance += 1,
+                            '}' => {
+                                balance -= 1;
+                                if balance == 0 {
+                                    closes_at = Some(pos + offset);
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    match (closes_at, opens) {
+                        (Some(_), Block::Body) => {
+                            out.push(indented(depth, clean_signature(&signature)));
+                        }
+                        (Some(end), _) => {
+                            let block = raw[pos..=end].trim();
+                            out.push(indented(depth, &format!("{} {}", signature, block)));
+                        }
+                        (None, Block::Body) => {
+                            out.push(indented(depth, clean_signature(&signature)));
+                            stack.push((opens, line_no));
+                        }
+                        (None, _) => {
+                            out.push(indented(depth, &format!("{} {{", signature)));
+                            stack.push((opens, line_no));
+                        }
+                    }
+                    scan_from = closes_at.unwrap_or(pos) + 1;
+                }
+                None => {
+                    decl.parts.push(raw[start..code_end].trim().to_string());
+                    let continues = decl.brackets > 0
+                        || [",", "(", "[", "=", "=>", "|", "&", ":", "<", ".", "?"]
+                            .iter()
+                            .any(|suffix| code_line.ends_with(suffix));
+                    if line_ends_statements && !continues {
+                        out.push(indented(depth, clean_signature(&decl.signature())));
+                        pending = None;
+                    } else if decl.parts.len() >= MAX_SIGNATURE_LINES {
+                        out.push(indented(depth, &format!("{} …", decl.signature())));
+                        pending = None;
+                    }
+                    continue;
+                }
+            }
+        }
+
+        for c in code[scan_from..code_end].chars() {
+            match c {
+                '{' => {
+                    let block = match stack.last() {
+                        Some((Block::Shape, _)) => Block::Shape,
+                        _ => Block::Body,
+                    };
+                    stack.push((block, line_no));
+                }
+                '}' => {
+                    if let Some((block, opened_on)) = stack.pop()
+                        && block != Block::Body
+                        && opened_on != line_no
+                    {
+                        out.push(indented(stack.len(), "}"));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    out.join("\n")
+}
+
+fn bracket_balance(code: &str) -> i32 {
+    code.chars()
+        .map(|c| match c {
+            '(' | '[' | '{' => 1,
+            ')' | ']' | '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Outline of a Python file: classes, functions and methods with their decorators, class
+/// attributes and module-level constants
+fn outline_python(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    // Indentation of each enclosing class or function, and whether it is a class
+    let mut scopes: Vec<(usize, bool)> = Vec::new();
+    let mut decorators: Vec<String> = Vec::new();
+    let mut open_block = None;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let raw = lines[i];
+        i += 1;
+        let inside_string = open_block.is_some();
+        let code = mask_code(raw, SourceLanguage::Python, &mut open_block);
+        let code_line = code.trim();
+        if inside_string || code_line.is_empty() {
+            continue;
+        }
+
+        let indent = code.len() - code.trim_start().len();
+        while scopes
+            .last()
+            .is_some_and(|&(scope_indent, _)| indent <= scope_indent)
+        {
+            scopes.pop();
+        }
+        if !scopes.last().is_none_or(|&(_, is_class)| is_class) {
+            decorators.clear();
+            continue;
+        }
+        let depth = scopes.len();
+        let in_class = !scopes.is_empty();
+
+        if code_line.starts_with('@') {
+            decorators.push(raw.trim().to_string());
+            continue;
+        }
+
+        let is_class = code_line.starts_with("class ");
+        if is_class || code_line.starts_with("def ") || code_line.starts_with("async def ") {
+            let mut signature = raw[..code.trim_end().len()].trim().to_string();
+            let mut brackets = bracket_balance(&code);
+            let mut joined = 1;
+            while brackets > 0 && i < lines.len() && joined < MAX_SIGNATURE_LINES {
+                let next = mask_code(lines[i], SourceLanguage::Python, &mut open_block);
+                brackets += bracket_balance(&next);
+                signature = join_signature(signature, lines[i][..next.trim_end().len()].trim());
+                i += 1;
+                joined += 1;
+            }
+            for decorator in decorators.drain(..) {
+                out.push(indented(depth, &decorator));
+            }
+            out.push(indented(depth, &signature));
+            scopes.push((indent, is_class));
+            continue;
+        }
+
+        decorators.clear();
+        let keep = if in_class {
+            PY_ATTRIBUTE_REGEX.is_match(code_line)
+        } else {
+            PY_CONSTANT_REGEX.is_match(code_line)
+        };
+        if keep {
+            out.push(indented(depth, raw[..code.trim_end().len()].trim()));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// Declarations-only outline of a source file, or `None` for languages without a parser
+pub fn outline_source(path: &str, content: &str) -> Option<String> {
+    let language = SourceLanguage::from_path(path)?;
+    Some(match language {
+        SourceLanguage::Python => outline_python(content),
+        _ => outline_braced(content, language),
+    })
+}
+
+/// Text sent in place of a file's full content
+pub fn repo_map_for_file(path: &str, content: &str) -> String {
+    let line_count = content.lines().count();
+    match outline_source(path, content) {
+        Some(outline) if !outline.trim().is_empty() => format!(
+            "[repository map: declarations only, full content ({} lines) omitted]\n{}",
+            line_count, outline
+        ),
+        _ => format!("[full content ({} lines) omitted]", line_count),
+    }
+}
+
+/// Order files by relevance: files named in the task first, otherwise the given order
+pub fn rank_files(paths: &[String], task_description: &str) -> Vec<String> {
+    let task = task_description.to_lowercase();
+    let mentioned = |path: &str| {
+        let normalized = path.replace('\\', "/").to_lowercase();
+        let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+        let stem = file_name.split('.').next().unwrap_or(file_name);
+        task.contains(&normalized)
+            || task.contains(file_name)
+            || (stem.len() >= 4 && task.contains(stem))
+    };
+
+    let mut ranked: Vec<String> = paths.to_vec();
+    ranked.sort_by_key(|path| !mentioned(path));
+    ranked
+}
+
+/// Tokens available for file contents in a prompt for a model with `context_window` tokens,
+/// after `reserved_tokens` for the output and the rest of the prompt, with a 10% margin
+pub fn file_token_budget(context_window: u32, reserved_tokens: u32) -> u32 {
+    let available = context_window
+        .saturating_sub(reserved_tokens)
+        .saturating_sub(PROMPT_OVERHEAD_TOKENS);
+    (available as u64 * 9 / 10) as u32
+}
+
+/// File contents for a prompt, split into full text and repository map entries
+#[derive(Debug, Clone, Default)]
+pub struct RepoMapContext {
+    /// Content per path: the full text, or the repository map for files over the budget
+    pub file_contents: HashMap<String, String>,
+    /// Paths sent in full, in rank order
+    pub full_files: Vec<String>,
+    /// Paths sent as a repository map, in rank order
+    pub mapped_files: Vec<String>,
+    pub estimated_tokens: u32,
+    pub budget_tokens: Option<u32>,
+}
+
+impl RepoMapContext {
+    /// Every file in full, without a budget
+    pub fn full(files: Vec<(String, String)>) -> Self {
+        let full_files = files.iter().map(|(path, _)| path.clone()).collect();
+        Self {
+            file_contents: files.into_iter().collect(),
+            full_files,
+            ..Default::default()
+        }
+    }
+}
+
+struct RankedFile {
+    path: String,
+    content: String,
+    map: String,
+    full_tokens: u32,
+    map_tokens: u32,
+}
+
+/// Every file starts as its map; then, in rank order, files are upgraded to their full text
+/// while the total stays within the budget
+fn split_by_budget(files: Vec<RankedFile>, budget_tokens: u32) -> RepoMapContext {
+    let mut used: u32 = files.iter().map(|f| f.map_tokens.min(f.full_tokens)).sum();
+    let mut context = RepoMapContext {
+        budget_tokens: Some(budget_tokens),
+        ..Default::default()
+    };
+
+    for file in files {
+        let extra = file.full_tokens.saturating_sub(file.map_tokens);
+        if used.saturating_add(extra) <= budget_tokens {
+            used += extra;
+            context.full_files.push(file.path.clone());
+            context.file_contents.insert(file.path, file.content);
+        } else {
+            context.mapped_files.push(file.path.clone());
+            context.file_contents.insert(file.path, file.map);
+        }
+    }
+
+    context.estimated_tokens = used;
+    context
+}
+
+/// Keep the full text of the highest-ranked `files` that fit in `budget_tokens` and send the
+/// rest as a repository map. `files` must be in rank order, most relevant first.
+pub fn build_repo_map_context(
+    files: Vec<(String, String)>,
+    budget_tokens: u32,
+    model: &str,
+) -> RepoMapContext {
+    let maps: Vec<String> = files
+        .iter()
+        .map(|(path, content)| repo_map_for_file(path, content))
+        .collect();
+    let texts: Vec<&str> = files
+        .iter()
+        .map(|(_, content)| content.as_str())
+        .chain(maps.iter().map(String::as_str))
+        .collect();
+    let tokens = estimate_tokens_batch(&texts, model);
+    let (full_tokens, map_tokens) = tokens.split_at(files.len());
+
+    let ranked = files
+        .into_iter()
+        .zip(maps)
+        .zip(full_tokens.iter().zip(map_tokens))
+        .map(
+            |(((path, content), map), (&full_tokens, &map_tokens))| RankedFile {
+                path,
+                content,
+                map,
+                full_tokens,
+                map_tokens,
+            },
+        )
+        .collect();
+    split_by_budget(ranked, budget_tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_outline_keeps_signatures_and_type_bodies() {
+        let source = r#"
+use std::fmt;
+pub use crate::error::AppError;
+
+/// A cart
+#[derive(Debug)]
+pub struct Cart {
+    #[serde(default)]
+    pub items: Vec<Item>,
+    total: u64,
+}
+
+pub enum State { Open, Closed }
+
+impl Cart {
+    pub fn new() -> Self {
+        let brace = '{';
+        Self { items: vec![], total: 0 }
+    }
+
+    pub(crate) async fn add<'a>(
+        &mut self,
+        item: &'a Item,
+    ) -> Result<(), AppError> {
+        if item.price > 0 { self.total += item.price; }
+        Ok(())
+    }
+}
+
+mod tests {
+    fn hidden() {}
+}
+"#;
+        let outline = outline_source("src/cart.rs", source).unwrap();
+        assert_eq!(
+            outline,
+            [
+                "pub use crate::error::AppError;",
+                "pub struct Cart {",
+                "    pub items: Vec<Item>,",
+                "    total: u64,",
+                "}",
+                "pub enum State { Open, Closed }",
+                "impl Cart {",
+                "    pub fn new() -> Self",
+                "    pub(crate) async fn add<'a>(&mut self, item: &'a Item) -> Result<(), AppError>",
+                "}",
+                "mod tests",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn typescript_outline_keeps_exports_classes_and_interfaces() {
+        let source = r#"
+import { invoke } from "@tauri-apps/api/core";
+export * from "./types";
+
+export interface CartProps {
+  items: Item[];
+  onCheckout(total: number): void;
+}
+
+const styles = { padding: 4 };
+
+export class CartStore {
+  private items: Item[] = [];
+  constructor(private api: Api) {}
+  async load(id: string): Promise<void> {
+    const url = `/carts/${id}`;
+  }
+}
+
+export const useCart = (id: string) => {
+  return useQuery(id);
+};
+
+function total(items: Item[]): number {
+  return items.reduce((sum, i) => sum + i.price, 0);
+}
+"#;
+        let outline = outline_source("src/cart.tsx", source).unwrap();
+        assert_eq!(
+            outline,
+            [
+                "export * from \"./types\";",
+                "export interface CartProps {",
+                "    items: Item[];",
+                "    onCheckout(total: number): void;",
+                "}",
+                "export class CartStore {",
+                "    private items: Item[] = [];",
+                "    constructor(private api: Api)",
+                "    async load(id: string): Promise<void>",
+                "}",
+                "export const useCart = (id: string)",
+                "function total(items: Item[]): number",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn python_outline_keeps_classes_functions_and_constants() {
+        let source = r#"
+import os
+
+MAX_ITEMS = 50
+
+class Cart:
+    """A cart.
+
+    def not_a_method(self): ...
+    """
+    items: list
+
+    @property
+    def total(self) -> int:
+        def helper():
+            pass
+        return sum(self.items)
+
+async def checkout(
+    cart: Cart,
+    retries: int = 3,
+) -> None:
+    pass
+"#;
+        let outline = outline_source("cart.py", source).unwrap();
+        assert_eq!(
+            outline,
+            [
+                "MAX_ITEMS = 50",
+                "class Cart:",
+                "    items: list",
+                "    @property",
+                "    def total(self) -> int:",
+                "async def checkout(cart: Cart, retries: int = 3) -> None:",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn go_outline_keeps_funcs_and_types() {
+        let source = r#"
+package cart
+
+import "fmt"
+
+type Cart struct {
+	Items []Item
+}
+
+type ID string
+
+func (c *Cart) Total() int {
+	return 0
+}
+"#;
+        let outline = outline_source("cart.go", source).unwrap();
+        assert_eq!(
+            outline,
+            [
+                "package cart",
+                "type Cart struct {",
+                "    Items []Item",
+                "}",
+                "type ID string",
+                "func (c *Cart) Total() int",
+            ]
+            .join("\n")
+        );
+        assert!(outline_source("README.md", "# Cart").is_none());
+    }
+
+    fn ranked(path: &str, full_tokens: u32, map_tokens: u32) -> RankedFile {
+        RankedFile {
+            path: path.to_string(),
+            content: format!("full {}", path),
+            map: format!("map {}", path),
+            full_tokens,
+            map_tokens,
+        }
+    }
+
+    #[test]
+    fn highest_ranked_files_that_fit_are_kept_in_full() {
+        let context = split_by_budget(
+            vec![
+                ranked("a.rs", 500, 50),
+                ranked("b.rs", 2_000, 100),
+                ranked("c.rs", 300, 30),
+            ],
+            1_000,
+        );
+
+        assert_eq!(context.full_files, vec!["a.rs", "c.rs"]);
+        assert_eq!(context.mapped_files, vec!["b.rs"]);
+        assert_eq!(context.file_contents["b.rs"], "map b.rs");
+        assert_eq!(context.file_contents["a.rs"], "full a.rs");
+        assert_eq!(context.estimated_tokens, 900);
+    }
+
+    #[test]
+    fn files_named_in_the_task_rank_first() {
+        let paths = vec![
+            "src/db.rs".to_string(),
+            "src/cart/checkout.rs".to_string(),
+            "src/ui.ts".to_string(),
+        ];
+        assert_eq!(
+            rank_files(&paths, "Retry payment in Checkout when the db is locked"),
+            vec!["src/cart/checkout.rs", "src/db.rs", "src/ui.ts"]
+        );
+        assert_eq!(file_token_budget(200_000, 32_000), 144_000);
+    }
+}
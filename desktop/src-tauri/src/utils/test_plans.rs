@@ -0,0 +1,528 @@
+//! Test plans for signed-off implementation plans: finding the existing tests near the files a
+//! plan touches, and reading and rendering the generated test plan.
+//!
+//! The model answers with
+//!
+//! ```xml
+//! <test_plan>
+//!   <step number="1">
+//!     <test>
+//!       <name>creates_invoice_for_order</name>
+//!       <file>src/billing/invoice_tests.rs</file>
+//!       <description>...</description>
+//!       <code><![CDATA[...]]></code>
+//!     </test>
+//!   </step>
+//! </test_plan>
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::utils::workspace_paths::split_repo_path;
+
+/// Directories whose files are tests regardless of their names
+const TEST_DIRECTORIES: &[&str] = &["tests", "test", "__tests__", "spec", "specs"];
+/// Directories that separate source from test trees (`src/main/java` next to `src/test/java`)
+const SOURCE_ROOT_DIRECTORIES: &[&str] = &["main"];
+/// Test files that hold shared setup rather than tests of one module
+const SHARED_TEST_FILES: &[&str] = &[
+    "mod", "index", "lib", "main", "common", "helpers", "utils", "setup", "conftest", "fixtures",
+];
+const CODE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "mjs", "cjs", "py", "go", "java", "kt", "swift", "rb", "cs",
+    "php", "scala", "ex", "exs", "c", "cc", "cpp", "dart",
+];
+/// Test files without a matching name are used as style examples up to this many directory
+/// hops away from a touched file
+const STYLE_EXAMPLE_MAX_DISTANCE: usize = 2;
+
+static TEST_PLAN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<test_plan\b[^>]*>(.*)</test_plan>").expect("Valid test plan regex")
+});
+static STEP_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<step\b([^>]*)>(.*?)</step>").expect("Valid step regex"));
+static NUMBER_ATTR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bnumber\s*=\s*["']([^"']*)["']"#).expect("Valid number attribute regex")
+});
+static TEST_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<test\b[^>]*>(.*?)</test>").expect("Valid test regex"));
+static NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<name>\s*(.*?)\s*</name>").expect("Valid name regex"));
+static FILE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<file>\s*(.*?)\s*</file>").expect("Valid file regex"));
+static DESCRIPTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<description>\s*(.*?)\s*</description>").expect("Valid description regex")
+});
+static CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<code>(.*?)</code>").expect("Valid code regex"));
+
+/// One proposed test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTestCase {
+    pub name: String,
+    /// Test file the test belongs in, relative to the project
+    pub file: String,
+    pub description: Option<String>,
+    /// Skeleton code in the style of the project's existing tests
+    pub code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStepTests {
+    pub step_number: String,
+    pub title: String,
+    pub tests: Vec<PlanTestCase>,
+}
+
+/// Test plan of an implementation plan, stored as its `testPlan` metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTestPlan {
+    /// The job that generated the test plan
+    pub test_job_id: String,
+    /// Signed-off revision the tests were planned for
+    pub plan_revision: Option<i64>,
+    pub generated_at: i64,
+    /// Existing test files the tests were modelled on
+    pub reference_files: Vec<String>,
+    pub steps: Vec<PlanStepTests>,
+}
+
+impl PlanTestPlan {
+    pub fn test_count(&self) -> usize {
+        self.steps.iter().map(|step| step.tests.len()).sum()
+    }
+
+    /// The test plan as a Markdown section for exports of the plan
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("## Test plan\n");
+        for step in &self.steps {
+            markdown.push_str(&format!("\n### Step {}", step.step_number));
+            if !step.title.is_empty() {
+                markdown.push_str(&format!(": {}", step.title));
+            }
+            markdown.push('\n');
+
+            for test in &step.tests {
+                markdown.push_str(&format!(
+                    "\n#### `{}`\n\nFile: `{}`\n",
+                    test.name, test.file
+                ));
+                if let Some(description) = &test.description {
+                    markdown.push_str(&format!("\n{}\n", description));
+                }
+                if !test.code.is_empty() {
+                    let fence = if test.code.contains("```") {
+                        "````"
+                    } else {
+                        "```"
+                    };
+                    markdown.push_str(&format!(
+                        "\n{}{}\n{}\n{}\n",
+                        fence,
+                        code_language(&test.file),
+                        test.code,
+                        fence
+                    ));
+                }
+            }
+        }
+        markdown
+    }
+}
+
+/// Code fence language for a file
+fn code_language(path: &str) -> &'static str {
+    match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("rs") => "rust",
+        Some("ts") => "typescript",
+        Some("tsx") => "tsx",
+        Some("js" | "mjs" | "cjs") => "javascript",
+        Some("jsx") => "jsx",
+        Some("py") => "python",
+        Some("go") => "go",
+        Some("java") => "java",
+        Some("kt") => "kotlin",
+        Some("swift") => "swift",
+        Some("rb") => "ruby",
+        Some("cs") => "csharp",
+        Some("php") => "php",
+        Some("scala") => "scala",
+        Some("ex" | "exs") => "elixir",
+        Some("c") => "c",
+        Some("cc" | "cpp") => "cpp",
+        Some("dart") => "dart",
+        _ => "",
+    }
+}
+
+/// Repository label, directories and file name of a project path
+fn split_path(path: &str) -> (Option<&str>, Vec<&str>, &str) {
+    let (label, relative) = split_repo_path(path);
+    let mut components: Vec<&str> = relative.split('/').filter(|c| !c.is_empty()).collect();
+    let file_name = components.pop().unwrap_or_default();
+    (label, components, file_name)
+}
+
+/// Lowercase name without separators, so `user_service`, `user-service` and `UserService` match
+fn normalize_subject(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | '.'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Strip a `Test`/`Tests`/`Spec` suffix that starts a new word of a CamelCase name
+fn strip_camel_case_suffix(stem: &str) -> Option<&str> {
+    ["Tests", "Test", "Spec"].iter().find_map(|suffix| {
+        let subject = stem.strip_suffix(suffix)?;
+        subject
+            .chars()
+            .last()
+            .is_some_and(|c| c.is_lowercase() || c.is_ascii_digit())
+            .then_some(subject)
+    })
+}
+
+/// A file that is a test by the usual conventions, with the module it tests when its name says
+struct TestFile<'a> {
+    label: Option<&'a str>,
+    /// Directories with test and source-root directories left out, for distances to sources
+    directories: Vec<&'a str>,
+    subject: Option<String>,
+}
+
+fn parse_test_file(path: &str) -> Option<TestFile<'_>> {
+    let (label, directories, file_name) = split_path(path);
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    if !CODE_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+    let in_test_directory = directories.iter().any(|d| TEST_DIRECTORIES.contains(d));
+
+    let named_subject = stem
+        .strip_suffix(".test")
+        .or_else(|| stem.strip_suffix(".spec"))
+        .or_else(|| stem.strip_suffix("_test"))
+        .or_else(|| stem.strip_suffix("_spec"))
+        .or_else(|| stem.strip_prefix("test_"))
+        .or_else(|| strip_camel_case_suffix(stem));
+    let subject = match named_subject {
+        Some(subject) => Some(subject),
+        None if in_test_directory => {
+            (!SHARED_TEST_FILES.contains(&stem.to_lowercase().as_str())).then_some(stem)
+        }
+        None => return None,
+    };
+
+    Some(TestFile {
+        label,
+        directories: directories
+            .into_iter()
+            .filter(|d| !TEST_DIRECTORIES.contains(d) && !SOURCE_ROOT_DIRECTORIES.contains(d))
+            .collect(),
+        subject: subject.map(normalize_subject),
+    })
+}
+
+/// Whether `path` is a test file by the usual naming and directory conventions
+pub fn is_test_file(path: &str) -> bool {
+    parse_test_file(path).is_some()
+}
+
+/// Whether a source file carries its own tests, like Rust's inline `#[cfg(test)]` modules
+pub fn has_inline_tests(content: &str) -> bool {
+    content.contains("#[cfg(test)]")
+}
+
+/// Directory hops between two directory lists
+fn directory_distance(a: &[&str], b: &[&str]) -> usize {
+    let common = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    (a.len() - common) + (b.len() - common)
+}
+
+/// Existing test files of the project that show how the touched files are tested, best first:
+/// touched test files, tests named after a touched file (nearest first), then other tests close
+/// to a touched file as style examples. Paths are relative to the project, repository-qualified
+/// for other repositories of a workspace.
+pub fn find_related_test_files(
+    touched_files: &[String],
+    project_files: &[String],
+    limit: usize,
+) -> Vec<String> {
+    let sources: Vec<(Option<&str>, Vec<&str>, String)> = touched_files
+        .iter()
+        .filter(|path| !is_test_file(path))
+        .filter_map(|path| {
+            let (label, directories, file_name) = split_path(path);
+            let stem = file_name
+                .split_once('.')
+                .map_or(file_name, |(stem, _)| stem);
+            // `mod.rs`, `index.ts` and `__init__.py` are tested under their directory's name
+            let subject = match stem {
+                "mod" | "index" | "__init__" => *directories.last()?,
+                _ => stem,
+            };
+            let directories = directories
+                .into_iter()
+                .filter(|d| !SOURCE_ROOT_DIRECTORIES.contains(d))
+                .collect();
+            Some((label, directories, normalize_subject(subject)))
+        })
+        .collect();
+
+    let mut ranked: Vec<((usize, usize), &String)> = project_files
+        .iter()
+        .filter_map(|path| {
+            if touched_files.contains(path) {
+                return is_test_file(path).then_some(((0, 0), path));
+            }
+            let test = parse_test_file(path)?;
+            sources
+                .iter()
+                .filter(|(label, _, _)| *label == test.label)
+                .filter_map(|(_, directories, subject)| {
+                    let distance = directory_distance(directories, &test.directories);
+                    if test.subject.as_ref() == Some(subject) {
+                        Some((1, distance))
+                    } else if distance <= STYLE_EXAMPLE_MAX_DISTANCE {
+                        Some((2, distance))
+                    } else {
+                        None
+                    }
+                })
+                .min()
+                .map(|rank| (rank, path))
+        })
+        .collect();
+
+    ranked.sort();
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, path)| path.clone())
+        .collect()
+}
+
+pub(crate) fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn tag_text(regex: &Regex, xml: &str) -> Option<String> {
+    regex
+        .captures(xml)
+        .map(|cap| unescape_xml(&cap[1]))
+        .filter(|text| !text.is_empty())
+}
+
+/// Code of a `<code>` element: CDATA content as is, escaped text unescaped, without the
+/// Markdown fence models sometimes add
+fn code_text(test_xml: &str) -> String {
+    let Some(cap) = CODE_REGEX.captures(test_xml) else {
+        return String::new();
+    };
+    let raw = cap[1].trim();
+    let code = match raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|rest| rest.strip_suffix("]]>"))
+    {
+        Some(cdata) => cdata.to_string(),
+        None => unescape_xml(raw),
+    };
+
+    let trimmed = code.trim_matches('\n').trim_end();
+    match trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+    {
+        Some(fenced) => fenced
+            .split_once('\n')
+            .map_or("", |(_, body)| body)
+            .trim_matches('\n')
+            .trim_end()
+            .to_string(),
+        None => trimmed.to_string(),
+    }
+}
+
+/// Read the steps and tests of a `<test_plan>` response. Titles come from the plan's step
+/// headers; tests without a name or file and steps without tests are left out.
+pub fn parse_test_plan_xml(
+    response: &str,
+    step_headers: &[(String, String)],
+) -> Vec<PlanStepTests> {
+    let Some(test_plan) = TEST_PLAN_REGEX.captures(response) else {
+        return Vec::new();
+    };
+    let titles: HashMap<&str, &str> = step_headers
+        .iter()
+        .map(|(number, title)| (number.as_str(), title.as_str()))
+        .collect();
+
+    STEP_REGEX
+        .captures_iter(&test_plan[1])
+        .enumerate()
+        .filter_map(|(idx, step)| {
+            let step_number = NUMBER_ATTR_REGEX
+                .captures(&step[1])
+                .map(|cap| cap[1].trim().to_string())
+                .unwrap_or_else(|| (idx + 1).to_string());
+            let tests: Vec<PlanTestCase> = TEST_REGEX
+                .captures_iter(&step[2])
+                .filter_map(|test| {
+                    let test_xml = &test[1];
+                    Some(PlanTestCase {
+                        name: tag_text(&NAME_REGEX, test_xml)?,
+                        file: tag_text(&FILE_REGEX, test_xml)?,
+                        description: tag_text(&DESCRIPTION_REGEX, test_xml),
+                        code: code_text(test_xml),
+                    })
+                })
+                .collect();
+            if tests.is_empty() {
+                return None;
+            }
+            Some(PlanStepTests {
+                title: titles
+                    .get(step_number.as_str())
+                    .map(|title| title.to_string())
+                    .unwrap_or_default(),
+                step_number,
+                tests,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(items: &[&str]) -> Vec<String> {
+        items.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_file_conventions() {
+        for path in [
+            "src/api/client.test.ts",
+            "src/api/client.spec.tsx",
+            "pkg/store/store_test.go",
+            "app/test_models.py",
+            "Sources/Core/JobsServiceTests.swift",
+            "src/test/java/com/acme/InvoiceTest.java",
+            "tests/api.rs",
+            "src/__tests__/Button.tsx",
+        ] {
+            assert!(is_test_file(path), "{} should be a test file", path);
+        }
+        for path in [
+            "src/contest.ts",
+            "src/api/client.ts",
+            "docs/testing.md",
+            "tests/fixtures/order.json",
+        ] {
+            assert!(!is_test_file(path), "{} should not be a test file", path);
+        }
+    }
+
+    #[test]
+    fn related_tests_rank_named_tests_before_style_examples() {
+        let touched = paths(&[
+            "src/billing/invoice.ts",
+            "src/billing/mod.rs",
+            "src/billing/invoice.test.ts",
+            "src/main/java/com/acme/Order.java",
+        ]);
+        let project = paths(&[
+            "src/billing/invoice.test.ts",
+            "src/billing/tax.test.ts",
+            "src/shipping/rates.test.ts",
+            "tests/billing.rs",
+            "tests/common/mod.rs",
+            "src/test/java/com/acme/OrderTest.java",
+            "e2e/deep/nested/far/away/other.test.ts",
+            "frontend:src/billing/invoice.test.ts",
+        ]);
+
+        assert_eq!(
+            find_related_test_files(&touched, &project, 10),
+            paths(&[
+                "src/billing/invoice.test.ts",
+                "src/test/java/com/acme/OrderTest.java",
+                "tests/billing.rs",
+                "src/billing/tax.test.ts",
+                "src/shipping/rates.test.ts",
+            ])
+        );
+        assert_eq!(find_related_test_files(&touched, &project, 2).len(), 2);
+    }
+
+    #[test]
+    fn parses_test_plan_and_renders_markdown() {
+        let response = r#"Here is the plan.
+```xml
+<test_plan>
+  <step number="2">
+    <test>
+      <name>rejects_negative_amounts</name>
+      <file>src/billing/invoice.test.ts</file>
+      <description>Amounts below zero fail &amp; nothing is stored</description>
+      <code><![CDATA[
+it("rejects negative amounts", () => {
+  expect(() => invoice(-1)).toThrow();
+});
+]]></code>
+    </test>
+    <test><name>missing file</name></test>
+  </step>
+  <step number="3"></step>
+</test_plan>
+```"#;
+        let headers = vec![
+            ("1".to_string(), "Add column".to_string()),
+            ("2".to_string(), "Validate amounts".to_string()),
+        ];
+
+        let steps = parse_test_plan_xml(response, &headers);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].title, "Validate amounts");
+        assert_eq!(steps[0].tests.len(), 1);
+        let test = &steps[0].tests[0];
+        assert_eq!(
+            test.description.as_deref(),
+            Some("Amounts below zero fail & nothing is stored")
+        );
+        assert!(test.code.starts_with("it(\"rejects negative amounts\""));
+        assert!(test.code.ends_with("});"));
+
+        let plan = PlanTestPlan {
+            test_job_id: "job".to_string(),
+            plan_revision: Some(2),
+            generated_at: 0,
+            reference_files: vec![],
+            steps,
+        };
+        assert_eq!(plan.test_count(), 1);
+        let markdown = plan.to_markdown();
+        assert!(markdown.contains("### Step 2: Validate amounts"));
+        assert!(markdown.contains("File: `src/billing/invoice.test.ts`"));
+        assert!(markdown.contains("```typescript\nit(\"rejects negative amounts\""));
+    }
+
+    #[test]
+    fn strips_markdown_fences_inside_code() {
+        let test =
+            "<name>a</name><file>b.py</file><code>```python\ndef test_a():\n    pass\n```</code>";
+        assert_eq!(code_text(test), "def test_a():\n    pass");
+    }
+}
@@ -0,0 +1,769 @@
+//! Replicated text for collaboratively edited task descriptions.
+//!
+//! This is an RGA (replicated growable array): every character is an element
+//! with a unique `OpId`, inserted after a reference element, and deletions only
+//! tombstone elements. Concurrent inserts after the same reference are ordered
+//! by descending `OpId`, which makes the final text independent of the order
+//! in which operations are delivered.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::hash_utils::hash_string;
+
+/// Lamport timestamp of an operation; unique per (counter, replica)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpId {
+    pub counter: u64,
+    pub replica: String,
+}
+
+/// Ids of the operations a replica has integrated: inclusive counter ranges per
+/// origin replica. Counters skip numbers and operations arrive out of order, so
+/// a single highest counter per replica would hide the gaps.
+pub type KnownOps = HashMap<String, Vec<(u64, u64)>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TextOp {
+    /// Insert `ch` after `after` (None = start of the document)
+    Insert {
+        id: OpId,
+        after: Option<OpId>,
+        ch: char,
+    },
+    /// Tombstone the element inserted by `target`
+    Delete { id: OpId, target: OpId },
+}
+
+impl TextOp {
+    pub fn id(&self) -> &OpId {
+        match self {
+            TextOp::Insert { id, .. } | TextOp::Delete { id, .. } => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    id: OpId,
+    ch: char,
+    deleted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextCrdt {
+    replica: String,
+    clock: u64,
+    elements: Vec<Element>,
+    applied: HashSet<OpId>,
+    /// Remote operations whose dependencies have not arrived yet
+    pending: Vec<TextOp>,
+    /// Integrated operations in causal order
+    log: Vec<TextOp>,
+}
+
+impl TextCrdt {
+    pub fn new(replica: impl Into<String>) -> Self {
+        Self {
+            replica: replica.into(),
+            clock: 0,
+            elements: Vec::new(),
+            applied: HashSet::new(),
+            pending: Vec::new(),
+            log: Vec::new(),
+        }
+    }
+
+    /// Rebuild a document from persisted operations (any order)
+    pub fn from_ops(replica: impl Into<String>, ops: impl IntoIterator<Item = TextOp>) -> Self {
+        let mut doc = Self::new(replica);
+        for op in ops {
+            doc.apply(op);
+        }
+        doc
+    }
+
+    /// Deterministic operations that produce `text` from an empty document.
+    ///
+    /// Devices seeding the same pre-existing description generate identical
+    /// ops, so bootstrapping on several devices does not duplicate the text.
+    pub fn snapshot_ops(text: &str) -> Vec<TextOp> {
+        let replica = format!("snapshot:{}", hash_string(text));
+        let mut after = None;
+        text.chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                let id = OpId {
+                    counter: i as u64 + 1,
+                    replica: replica.clone(),
+                };
+                TextOp::Insert {
+                    id: id.clone(),
+                    after: after.replace(id),
+                    ch,
+                }
+            })
+            .collect()
+    }
+
+    pub fn replica(&self) -> &str {
+        &self.replica
+    }
+
+    pub fn text(&self) -> String {
+        self.elements
+            .iter()
+            .filter(|e| !e.deleted)
+            .map(|e| e.ch)
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.log.is_empty()
+    }
+
+    /// Ids of the integrated operations, compressed into counter ranges
+    pub fn known_ops(&self) -> KnownOps {
+        let mut counters: HashMap<&str, Vec<u64>> = HashMap::new();
+        for op in &self.log {
+            let id = op.id();
+            counters.entry(&id.replica).or_default().push(id.counter);
+        }
+
+        counters
+            .into_iter()
+            .map(|(replica, mut counters)| {
+                counters.sort_unstable();
+                let mut ranges: Vec<(u64, u64)> = Vec::new();
+                for counter in counters {
+                    match ranges.last_mut() {
+                        Some((_, end)) if counter <= *end + 1 => *end = (*end).max(counter),
+                        _ => ranges.push((counter, counter)),
+                    }
+                }
+                (replica.to_string(), ranges)
+            })
+            .collect()
+    }
+
+    /// Integrated operations whose ids are not in `known`, in causal order
+    pub fn ops_missing_from(&self, known: &KnownOps) -> Vec<TextOp> {
+        self.log
+            .iter()
+            .filter(|op| {
+                let id = op.id();
+                !known.get(&id.replica).is_some_and(|ranges| {
+                    ranges
+                        .iter()
+                        .any(|(start, end)| (*start..=*end).contains(&id.counter))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Apply a local or remote operation. Returns false for duplicates and for
+    /// operations parked until their dependencies arrive.
+    pub fn apply(&mut self, op: TextOp) -> bool {
+        if self.applied.contains(op.id()) {
+            return false;
+        }
+        if !self.is_ready(&op) {
+            if !self.pending.contains(&op) {
+                self.pending.push(op);
+            }
+            return false;
+        }
+
+        self.integrate(op);
+        self.drain_pending();
+        true
+    }
+
+    /// Insert `text` before the visible character at `pos`
+    pub fn insert(&mut self, pos: usize, text: &str) -> Vec<TextOp> {
+        let mut index = self.element_index_before(pos);
+        let mut after = index.map(|i| self.elements[i].id.clone());
+        let mut ops = Vec::new();
+
+        for ch in text.chars() {
+            let id = self.next_id();
+            let op = TextOp::Insert {
+                id: id.clone(),
+                after: after.replace(id.clone()),
+                ch,
+            };
+            // Our clock is ahead of every known id, so the element goes
+            // directly after its reference without scanning siblings
+            let at = index.map_or(0, |i| i + 1);
+            self.elements.insert(
+                at,
+                Element {
+                    id: id.clone(),
+                    ch,
+                    deleted: false,
+                },
+            );
+            index = Some(at);
+            self.applied.insert(id);
+            self.log.push(op.clone());
+            ops.push(op);
+        }
+
+        ops
+    }
+
+    /// Delete `len` visible characters starting at `pos`
+    pub fn delete(&mut self, pos: usize, len: usize) -> Vec<TextOp> {
+        let targets: Vec<OpId> = self
+            .elements
+            .iter()
+            .filter(|e| !e.deleted)
+            .skip(pos)
+            .take(len)
+            .map(|e| e.id.clone())
+            .collect();
+
+        targets
+            .into_iter()
+            .map(|target| {
+                let op = TextOp::Delete {
+                    id: self.next_id(),
+                    target,
+                };
+                self.integrate(op.clone());
+                op
+            })
+            .collect()
+    }
+
+    /// Produce the operations that turn the current text into `new_text`.
+    ///
+    /// Only the characters that differ are touched, so unchanged text keeps its
+    /// element ids and concurrent edits to it still land.
+    pub fn set_text(&mut self, new_text: &str) -> Vec<TextOp> {
+        let old: Vec<char> = self.text().chars().collect();
+        let new: Vec<char> = new_text.chars().collect();
+
+        let mut ops = Vec::new();
+        let mut pos = 0;
+        let mut steps = diff_chars(&old, &new).into_iter().peekable();
+        while let Some(step) = steps.next() {
+            match step {
+                Step::Keep => pos += 1,
+                Step::Delete => {
+                    let mut len = 1;
+                    while steps.next_if_eq(&Step::Delete).is_some() {
+                        len += 1;
+                    }
+                    ops.extend(self.delete(pos, len));
+                }
+                Step::Insert(ch) => {
+                    let mut inserted = vec![ch];
+                    while let Some(Step::Insert(ch)) =
+                        steps.next_if(|step| matches!(step, Step::Insert(_)))
+                    {
+                        inserted.push(ch);
+                    }
+                    ops.extend(self.insert(pos, &inserted.iter().collect::<String>()));
+                    pos += inserted.len();
+                }
+            }
+        }
+        ops
+    }
+
+    fn next_id(&mut self) -> OpId {
+        self.clock += 1;
+        OpId {
+            counter: self.clock,
+            replica: self.replica.clone(),
+        }
+    }
+
+    fn is_ready(&self, op: &TextOp) -> bool {
+        match op {
+            TextOp::Insert { after, .. } => after.as_ref().is_none_or(|a| self.applied.contains(a)),
+            TextOp::Delete { target, .. } => self.applied.contains(target),
+        }
+    }
+
+    /// Index of the element holding the visible character before `pos`
+    fn element_index_before(&self, pos: usize) -> Option<usize> {
+        if pos == 0 {
+            return None;
+        }
+        self.elements
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.deleted)
+            .nth(pos - 1)
+            .map(|(i, _)| i)
+            .or_else(|| self.elements.iter().rposition(|e| !e.deleted))
+    }
+
+    fn position_of(&self, id: &OpId) -> Option<usize> {
+        // Most edits happen near the end of the text
+        self.elements.iter().rposition(|e| &e.id == id)
+    }
+
+    fn integrate(&mut self, op: TextOp) {
+        self.clock = self.clock.max(op.id().counter);
+
+        match &op {
+            TextOp::Insert { id, after, ch } => {
+                let mut at = match after {
+                    Some(after) => self.position_of(after).map_or(0, |i| i + 1),
+                    None => 0,
+                };
+                // Newer concurrent inserts at the same reference (and their
+                // descendants, which always carry larger ids) come first
+                while at < self.elements.len() && self.elements[at].id > *id {
+                    at += 1;
+                }
+                self.elements.insert(
+                    at,
+                    Element {
+                        id: id.clone(),
+                        ch: *ch,
+                        deleted: false,
+                    },
+                );
+            }
+            TextOp::Delete { target, .. } => {
+                if let Some(i) = self.position_of(target) {
+                    self.elements[i].deleted = true;
+                }
+            }
+        }
+
+        self.applied.insert(op.id().clone());
+        self.log.push(op);
+    }
+
+    fn drain_pending(&mut self) {
+        while let Some(i) = self.pending.iter().position(|op| self.is_ready(op)) {
+            let op = self.pending.swap_remove(i);
+            if !self.applied.contains(op.id()) {
+                self.integrate(op);
+            }
+        }
+    }
+}
+
+/// Merge two texts derived from `base`: characters deleted on either side are
+/// dropped and text inserted on either side is kept (ours first), matching how
+/// the CRDT merges concurrent operations.
+pub fn merge_three_way(base: &str, ours: &str, theirs: &str) -> String {
+    let base: Vec<char> = base.chars().collect();
+    let ours = SideEdits::new(&base, ours);
+    let theirs = SideEdits::new(&base, theirs);
+
+    let mut merged = String::new();
+    for i in 0..=base.len() {
+        merged.push_str(&ours.inserted[i]);
+        if theirs.inserted[i] != ours.inserted[i] {
+            merged.push_str(&theirs.inserted[i]);
+        }
+        if i < base.len() && ours.kept[i] && theirs.kept[i] {
+            merged.push(base[i]);
+        }
+    }
+    merged
+}
+
+/// How one side changed a base text: the base characters it kept and the text
+/// it inserted before each base character (the last slot is the end)
+struct SideEdits {
+    kept: Vec<bool>,
+    inserted: Vec<String>,
+}
+
+impl SideEdits {
+    fn new(base: &[char], text: &str) -> Self {
+        let text: Vec<char> = text.chars().collect();
+        let mut edits = Self {
+            kept: vec![false; base.len()],
+            inserted: vec![String::new(); base.len() + 1],
+        };
+        let mut i = 0;
+        for step in diff_chars(base, &text) {
+            match step {
+                Step::Keep => {
+                    edits.kept[i] = true;
+                    i += 1;
+                }
+                Step::Delete => i += 1,
+                Step::Insert(ch) => edits.inserted[i].push(ch),
+            }
+        }
+        edits
+    }
+}
+
+/// Above this many differing characters the changed span is replaced as a whole
+const MAX_DIFF_DISTANCE: usize = 1000;
+
+/// One step of an alignment that walks the old text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Keep,
+    Delete,
+    Insert(char),
+}
+
+/// Character alignment of `old` and `new`
+fn diff_chars(old: &[char], new: &[char]) -> Vec<Step> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut steps = vec![Step::Keep; prefix];
+    match shortest_edit(old_middle, new_middle) {
+        Some(middle) => steps.extend(middle),
+        None => {
+            steps.extend(std::iter::repeat_n(Step::Delete, old_middle.len()));
+            steps.extend(new_middle.iter().map(|&ch| Step::Insert(ch)));
+        }
+    }
+    steps.extend(std::iter::repeat_n(Step::Keep, suffix));
+    steps
+}
+
+/// Myers' shortest edit script; None when it needs more than `MAX_DIFF_DISTANCE` edits
+fn shortest_edit(old: &[char], new: &[char]) -> Option<Vec<Step>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (old.len() + new.len()).min(MAX_DIFF_DISTANCE) as isize;
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    // Furthest x per diagonal before each round, for the diagonals that round reads
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, old, new));
+            }
+        }
+    }
+    None
+}
+
+fn backtrack(trace: &[Vec<isize>], old: &[char], new: &[char]) -> Vec<Step> {
+    let (mut x, mut y) = (old.len() as isize, new.len() as isize);
+    let mut steps = Vec::new();
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let (prev_x, prev_k) = if d == 0 {
+            (0, 0)
+        } else {
+            // `v` holds diagonals -d..=d, so diagonal k is at k + d
+            let prev_k = if k == -d || (k != d && v[(k - 1 + d) as usize] < v[(k + 1 + d) as usize])
+            {
+                k + 1
+            } else {
+                k - 1
+            };
+            (v[(prev_k + d) as usize], prev_k)
+        };
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            steps.push(Step::Keep);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                steps.push(Step::Insert(new[y as usize]));
+            } else {
+                x -= 1;
+                steps.push(Step::Delete);
+            }
+        }
+    }
+
+    steps.reverse();
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    const ALPHABET: &[char] = &['a', 'b', 'c', ' ', '\n', 'é', '✓'];
+
+    fn random_edit(doc: &mut TextCrdt, rng: &mut StdRng) -> Vec<TextOp> {
+        let len = doc.text().chars().count();
+        if len > 0 && rng.random_range(0..3) == 0 {
+            let pos = rng.random_range(0..len);
+            let count = rng.random_range(1..=(len - pos).min(4));
+            doc.delete(pos, count)
+        } else {
+            let pos = rng.random_range(0..=len);
+            let text: String = (0..rng.random_range(1..4))
+                .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())])
+                .collect();
+            doc.insert(pos, &text)
+        }
+    }
+
+    #[test]
+    fn test_random_interleavings_converge() {
+        for seed in 0..200u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let seed_ops = TextCrdt::snapshot_ops("shared start");
+            let mut replicas: Vec<TextCrdt> = (0..3)
+                .map(|i| TextCrdt::from_ops(format!("device-{i}"), seed_ops.clone()))
+                .collect();
+            let mut all_ops = seed_ops.clone();
+
+            // Each replica edits offline, occasionally syncing a random subset
+            for _ in 0..rng.random_range(5..30) {
+                let r = rng.random_range(0..replicas.len());
+                let ops = random_edit(&mut replicas[r], &mut rng);
+                all_ops.extend(ops);
+
+                if rng.random_range(0..4) == 0 {
+                    let target = rng.random_range(0..replicas.len());
+                    let mut partial = all_ops.clone();
+                    partial.shuffle(&mut rng);
+                    partial.truncate(rng.random_range(0..=partial.len()));
+                    for op in partial {
+                        replicas[target].apply(op);
+                    }
+                }
+            }
+
+            // Deliver everything in a different random order to each replica,
+            // with duplicates
+            for replica in replicas.iter_mut() {
+                let mut delivery = all_ops.clone();
+                delivery.extend(all_ops.iter().take(all_ops.len() / 3).cloned());
+                delivery.shuffle(&mut rng);
+                for op in delivery {
+                    replica.apply(op);
+                }
+            }
+
+            let expected = replicas[0].text();
+            for replica in &replicas {
+                assert_eq!(replica.text(), expected, "seed {seed} diverged");
+                assert_eq!(replica.pending_len(), 0, "seed {seed} left ops pending");
+            }
+        }
+    }
+
+    #[test]
+    fn test_local_edits_match_string_edits() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut doc = TextCrdt::new("local");
+        let mut expected: Vec<char> = Vec::new();
+
+        for _ in 0..500 {
+            let len = expected.len();
+            if len > 0 && rng.random_bool(0.4) {
+                let pos = rng.random_range(0..len);
+                let count = rng.random_range(1..=(len - pos));
+                doc.delete(pos, count);
+                expected.drain(pos..pos + count);
+            } else {
+                let pos = rng.random_range(0..=len);
+                let ch = ALPHABET[rng.random_range(0..ALPHABET.len())];
+                doc.insert(pos, &ch.to_string());
+                expected.insert(pos, ch);
+            }
+            assert_eq!(doc.text(), expected.iter().collect::<String>());
+        }
+    }
+
+    #[test]
+    fn test_concurrent_edits_keep_both_changes() {
+        let base = TextCrdt::snapshot_ops("Fix the bug");
+        let mut desktop = TextCrdt::from_ops("desktop", base.clone());
+        let mut mobile = TextCrdt::from_ops("mobile", base);
+
+        let desktop_ops = desktop.set_text("Fix the login bug");
+        let mobile_ops = mobile.set_text("Fix the bug quickly");
+
+        for op in mobile_ops {
+            desktop.apply(op);
+        }
+        for op in desktop_ops {
+            mobile.apply(op);
+        }
+
+        assert_eq!(desktop.text(), "Fix the login bug quickly");
+        assert_eq!(mobile.text(), desktop.text());
+    }
+
+    #[test]
+    fn test_set_text_reaches_target_and_is_minimal() {
+        let mut doc = TextCrdt::from_ops("a", TextCrdt::snapshot_ops("hello world"));
+        let ops = doc.set_text("hello brave world");
+        assert_eq!(doc.text(), "hello brave world");
+        assert_eq!(ops.len(), "brave ".len());
+
+        assert!(doc.set_text("hello brave world").is_empty());
+        doc.set_text("");
+        assert_eq!(doc.text(), "");
+    }
+
+    #[test]
+    fn test_set_text_keeps_ids_of_unchanged_text() {
+        let base = TextCrdt::snapshot_ops("hello world");
+        let mut a = TextCrdt::from_ops("a", base.clone());
+        let mut b = TextCrdt::from_ops("b", base);
+
+        // Editing both ends must not re-create the middle that b deletes meanwhile
+        let a_ops = a.set_text("Xhello worldY");
+        let b_ops = b.delete(6, 5);
+        for op in b_ops {
+            a.apply(op);
+        }
+        for op in a_ops {
+            b.apply(op);
+        }
+
+        assert_eq!(a.text(), "Xhello Y");
+        assert_eq!(b.text(), a.text());
+    }
+
+    #[test]
+    fn test_diff_chars_reproduces_the_new_text() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..300 {
+            let old: Vec<char> = (0..rng.random_range(0..40))
+                .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())])
+                .collect();
+            let new: Vec<char> = (0..rng.random_range(0..40))
+                .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())])
+                .collect();
+
+            let mut rebuilt = Vec::new();
+            let mut old_chars = old.iter();
+            for step in diff_chars(&old, &new) {
+                match step {
+                    Step::Keep => rebuilt.push(*old_chars.next().unwrap()),
+                    Step::Delete => {
+                        old_chars.next().unwrap();
+                    }
+                    Step::Insert(ch) => rebuilt.push(ch),
+                }
+            }
+            assert!(old_chars.next().is_none());
+            assert_eq!(rebuilt, new);
+        }
+
+        let long_old: Vec<char> = "a".repeat(MAX_DIFF_DISTANCE * 2).chars().collect();
+        let long_new: Vec<char> = "b".repeat(MAX_DIFF_DISTANCE * 2).chars().collect();
+        let steps = diff_chars(&long_old, &long_new);
+        assert_eq!(steps.len(), long_old.len() + long_new.len());
+    }
+
+    #[test]
+    fn test_merge_three_way_keeps_changes_from_both_sides() {
+        assert_eq!(
+            merge_three_way("Fix the bug", "Fix the login bug", "Fix the bug quickly"),
+            "Fix the login bug quickly"
+        );
+        // A stale snapshot that changed nothing keeps our edits, and vice versa
+        assert_eq!(merge_three_way("draft", "draft v2", "draft"), "draft v2");
+        assert_eq!(merge_three_way("draft", "draft", "final"), "final");
+        // Deletions from either side apply; identical edits are not duplicated
+        assert_eq!(
+            merge_three_way("one two three", "one three", "one two three four"),
+            "one three four"
+        );
+        assert_eq!(merge_three_way("abc", "abXc", "abXc"), "abXc");
+    }
+
+    #[test]
+    fn test_snapshot_ops_are_deterministic_and_idempotent() {
+        let mut a = TextCrdt::from_ops("a", TextCrdt::snapshot_ops("same text"));
+        for op in TextCrdt::snapshot_ops("same text") {
+            assert!(!a.apply(op));
+        }
+        assert_eq!(a.text(), "same text");
+    }
+
+    #[test]
+    fn test_ops_missing_from_and_serde_round_trip() {
+        let mut a = TextCrdt::new("a");
+        a.insert(0, "abc");
+        let known = a.known_ops();
+        a.delete(1, 1);
+
+        let missing = a.ops_missing_from(&known);
+        assert_eq!(missing.len(), 1);
+
+        let json = serde_json::to_string(&a.ops_missing_from(&KnownOps::new())).unwrap();
+        assert!(json.contains("\"type\":\"insert\""));
+        let ops: Vec<TextOp> = serde_json::from_str(&json).unwrap();
+        assert_eq!(TextCrdt::from_ops("b", ops).text(), "ac");
+    }
+
+    #[test]
+    fn test_catch_up_resends_dropped_and_reordered_ops() {
+        let mut a = TextCrdt::new("a");
+        let mut c = TextCrdt::new("c");
+        let mut a_ops = a.insert(0, "hello");
+        // Integrating c's ops moves a's clock forward, so its counters skip numbers
+        for op in c.insert(0, "0123456789") {
+            a.apply(op);
+        }
+        a_ops.extend(a.insert(5, " world"));
+        a_ops.extend(a.delete(0, 1));
+
+        // b sees a's later ops first and never receives one of the early ones
+        let mut b = TextCrdt::new("b");
+        let dropped = a_ops.remove(2);
+        a_ops.reverse();
+        for op in a_ops {
+            b.apply(op);
+        }
+        assert_ne!(b.text(), a.text());
+
+        let missing = a.ops_missing_from(&b.known_ops());
+        assert!(missing.contains(&dropped));
+        for op in missing {
+            b.apply(op);
+        }
+        assert_eq!(b.text(), a.text());
+        assert_eq!(b.pending_len(), 0);
+        assert!(a.ops_missing_from(&b.known_ops()).is_empty());
+    }
+}
@@ -151,6 +151,7 @@ pub async fn generate_plan_title(
         temperature,
         request_id,
         task_type: Some("implementation_plan".to_string()),
+        response_cache: false,
     };
 
     let response = api_client.chat_completion(messages, options).await?;
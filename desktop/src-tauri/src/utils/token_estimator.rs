@@ -128,6 +128,23 @@ pub fn estimate_tokens(text: &str, model: &str) -> u32 {
     token_count
 }
 
+/// Estimate the number of tokens in each of several texts
+///
+/// Same counts as `estimate_tokens`, but the tokenizer is loaded once for all texts, which
+/// matters when sizing many files against a context budget.
+pub fn estimate_tokens_batch(texts: &[&str], model: &str) -> Vec<u32> {
+    match get_tokenizer_encoding_for_model(model) {
+        Ok(tokenizer) => texts
+            .iter()
+            .map(|text| tokenizer.encode_with_special_tokens(text).len() as u32)
+            .collect(),
+        Err(_) => texts
+            .iter()
+            .map(|text| (text.chars().count() as u32 + 3) / 4)
+            .collect(),
+    }
+}
+
 /// Estimate tokens for a complete prompt request
 ///
 /// This function estimates tokens for a complete LLM request including all messages.
@@ -237,6 +254,18 @@ mod tests {
         assert!(tokens < 20);
     }
 
+    #[test]
+    fn test_estimate_tokens_batch_matches_single_estimates() {
+        let texts = [
+            "fn main() {}",
+            "",
+            "The quick brown fox jumps over the lazy dog",
+        ];
+        let batch = estimate_tokens_batch(&texts, "gpt-4");
+        let single: Vec<u32> = texts.iter().map(|t| estimate_tokens(t, "gpt-4")).collect();
+        assert_eq!(batch, single);
+    }
+
     #[test]
     fn test_estimate_tokens_for_messages() {
         let messages = vec![
@@ -8,6 +8,8 @@ use log;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
@@ -122,12 +124,20 @@ pub struct ComposedPrompt {
 /// Unified prompt processor that handles template processing and composition
 pub struct UnifiedPromptProcessor;
 
+fn project_file_system_prompt(project_directory: &str, task_type: &str) -> Option<String> {
+    let task_type = TaskType::from_str(task_type).ok()?;
+    crate::utils::project_config::active_project_config(project_directory)?
+        .config
+        .system_prompt(Path::new(project_directory), task_type)
+}
+
 impl UnifiedPromptProcessor {
     pub fn new() -> Self {
         Self
     }
 
-    /// Get effective system prompt - first check project database, then server defaults
+    /// Get effective system prompt - first check project database, then the project's
+    /// `.plantocode` file, then server defaults
     /// Returns (resolved_prompt, original_template, prompt_id)
     async fn get_effective_system_prompt(
         &self,
@@ -150,6 +160,10 @@ impl UnifiedPromptProcessor {
             if let Ok(Some(custom_prompt)) = settings_repo.get_project_system_prompt(&project_hash, task_type).await {
                 (custom_prompt.system_prompt.clone(), custom_prompt.system_prompt, "custom".to_string())
             }
+            // Then the prompt checked into the project's .plantocode file
+            else if let Some(file_prompt) = project_file_system_prompt(project_directory, task_type) {
+                (file_prompt.clone(), file_prompt, "project_file".to_string())
+            }
             // Fall back to server default system prompt
             else if let Some(default_prompt) = crate::api_clients::client_factory::get_server_proxy_client(&app_handle).await?
                 .get_default_system_prompt(task_type).await? {
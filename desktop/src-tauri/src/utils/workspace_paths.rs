@@ -0,0 +1,235 @@
+//! Repository-qualified paths for multi-repository workspaces.
+//!
+//! Inside a workspace, paths in the session's own repository stay relative to its project
+//! directory as everywhere else. Files in the other repositories of the workspace are written
+//! as `<label>:<path relative to that repository>`, e.g. `frontend:src/api/client.ts`, so the
+//! file finder, plan generation and everything that reads their output agree on one format.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Separates the repository label from the path in a repository-qualified path
+pub const REPO_PATH_SEPARATOR: char = ':';
+
+// At least two characters, so Windows drive letters (`C:\...`) are never taken for a label
+static REPO_LABEL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z0-9][a-z0-9_-]{1,31}$").expect("Valid repo label regex"));
+
+pub fn is_valid_repo_label(label: &str) -> bool {
+    REPO_LABEL_REGEX.is_match(label)
+}
+
+/// Split `frontend:src/app.ts` into `(Some("frontend"), "src/app.ts")`; plain paths have no label
+pub fn split_repo_path(path: &str) -> (Option<&str>, &str) {
+    match path.split_once(REPO_PATH_SEPARATOR) {
+        Some((label, rest)) if is_valid_repo_label(label) => (Some(label), rest),
+        _ => (None, path),
+    }
+}
+
+pub fn repo_qualified_path(label: &str, relative_path: &str) -> String {
+    format!("{}{}{}", label, REPO_PATH_SEPARATOR, relative_path)
+}
+
+fn forward_slashes(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// One repository of a workspace, as seen from a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRepoRoot {
+    pub label: String,
+    pub directory: PathBuf,
+    /// The repository of the session's project directory; its paths are not qualified
+    pub is_current: bool,
+}
+
+/// Repositories of the workspace a session's project directory belongs to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceLayout {
+    pub workspace_id: String,
+    pub workspace_name: String,
+    pub repositories: Vec<WorkspaceRepoRoot>,
+}
+
+/// Files of one repository in a selection of pipeline paths
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoFileSelection {
+    pub label: String,
+    pub is_current: bool,
+    /// Paths relative to the repository
+    pub files: Vec<String>,
+}
+
+impl WorkspaceLayout {
+    pub fn repository(&self, label: &str) -> Option<&WorkspaceRepoRoot> {
+        self.repositories.iter().find(|r| r.label == label)
+    }
+
+    pub fn current(&self) -> Option<&WorkspaceRepoRoot> {
+        self.repositories.iter().find(|r| r.is_current)
+    }
+
+    /// Directories of the repositories other than the session's own
+    pub fn other_directories(&self) -> impl Iterator<Item = &Path> {
+        self.repositories
+            .iter()
+            .filter(|r| !r.is_current)
+            .map(|r| r.directory.as_path())
+    }
+
+    /// Pipeline form of an absolute path: relative for the session's repository, qualified for
+    /// the other repositories, `None` outside the workspace. Nested repositories win over the
+    /// repositories that contain them.
+    pub fn qualify(&self, absolute_path: &Path) -> Option<String> {
+        let (repository, relative) = self
+            .repositories
+            .iter()
+            .filter_map(|r| {
+                absolute_path
+                    .strip_prefix(&r.directory)
+                    .ok()
+                    .map(|relative| (r, relative))
+            })
+            .max_by_key(|(r, _)| r.directory.components().count())?;
+
+        let relative = forward_slashes(relative);
+        Some(if repository.is_current {
+            relative
+        } else {
+            repo_qualified_path(&repository.label, &relative)
+        })
+    }
+
+    /// Absolute location of a pipeline path. Qualified paths with an unknown label are `None`.
+    pub fn resolve(&self, path: &str) -> Option<PathBuf> {
+        match split_repo_path(path) {
+            (Some(label), relative) => self
+                .repository(label)
+                .map(|r| r.directory.join(relative.trim_start_matches('/'))),
+            (None, path) if Path::new(path).is_absolute() => Some(PathBuf::from(path)),
+            (None, relative) => self.current().map(|r| r.directory.join(relative)),
+        }
+    }
+
+    /// Split a file selection per repository, in workspace order. Paths with an unknown label
+    /// or outside the workspace are left out.
+    pub fn group_by_repository(&self, paths: &[String]) -> Vec<RepoFileSelection> {
+        self.repositories
+            .iter()
+            .map(|repository| RepoFileSelection {
+                label: repository.label.clone(),
+                is_current: repository.is_current,
+                files: paths
+                    .iter()
+                    .filter_map(|path| match split_repo_path(path) {
+                        (Some(label), relative) if label == repository.label => {
+                            Some(relative.to_string())
+                        }
+                        (None, relative)
+                            if repository.is_current && !Path::new(relative).is_absolute() =>
+                        {
+                            Some(relative.to_string())
+                        }
+                        (None, absolute) => Path::new(absolute)
+                            .strip_prefix(&repository.directory)
+                            .ok()
+                            .map(forward_slashes),
+                        _ => None,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> WorkspaceLayout {
+        let repo = |label: &str, directory: &str, is_current: bool| WorkspaceRepoRoot {
+            label: label.to_string(),
+            directory: PathBuf::from(directory),
+            is_current,
+        };
+        WorkspaceLayout {
+            workspace_id: "ws".to_string(),
+            workspace_name: "Shop".to_string(),
+            repositories: vec![
+                repo("backend", "/src/shop/backend", true),
+                repo("frontend", "/src/shop/frontend", false),
+                repo("schema", "/src/shop/backend/vendor/schema", false),
+            ],
+        }
+    }
+
+    #[test]
+    fn labels_are_only_split_off_valid_prefixes() {
+        assert_eq!(
+            split_repo_path("frontend:src/app.ts"),
+            (Some("frontend"), "src/app.ts")
+        );
+        assert_eq!(split_repo_path("src/app.ts"), (None, "src/app.ts"));
+        assert_eq!(
+            split_repo_path("C:\\work\\app.ts"),
+            (None, "C:\\work\\app.ts")
+        );
+        assert_eq!(split_repo_path("Not A Label:x"), (None, "Not A Label:x"));
+    }
+
+    #[test]
+    fn paths_round_trip_between_absolute_and_pipeline_form() {
+        let layout = layout();
+        let cases = [
+            ("/src/shop/backend/src/main.rs", "src/main.rs"),
+            ("/src/shop/frontend/src/app.ts", "frontend:src/app.ts"),
+            (
+                "/src/shop/backend/vendor/schema/user.proto",
+                "schema:user.proto",
+            ),
+        ];
+        for (absolute, pipeline) in cases {
+            assert_eq!(
+                layout.qualify(Path::new(absolute)).as_deref(),
+                Some(pipeline)
+            );
+            assert_eq!(layout.resolve(pipeline), Some(PathBuf::from(absolute)));
+        }
+        assert_eq!(layout.qualify(Path::new("/etc/hosts")), None);
+        assert_eq!(layout.resolve("mobile:app.swift"), None);
+    }
+
+    #[test]
+    fn selections_are_grouped_per_repository() {
+        let paths = vec![
+            "src/main.rs".to_string(),
+            "frontend:src/app.ts".to_string(),
+            "/src/shop/frontend/package.json".to_string(),
+            "mobile:app.swift".to_string(),
+        ];
+        let groups = layout().group_by_repository(&paths);
+        let files: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.label.as_str(),
+                    g.files.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("backend", vec!["src/main.rs"]),
+                ("frontend", vec!["src/app.ts", "package.json"]),
+                ("schema", vec![]),
+            ]
+        );
+    }
+}
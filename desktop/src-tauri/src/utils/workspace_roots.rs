@@ -36,6 +36,15 @@ pub async fn resolve_workspace_roots(
             }
         }
     }
+    // Other repositories of the project's multi-repository workspace
+    for directory in
+        crate::services::workspaces::other_workspace_directories(app_handle, project_directory)
+            .await
+    {
+        if directory != main {
+            roots.push(directory);
+        }
+    }
     // Monorepo detection (Node workspaces + common folders)
     let detected = detect_monorepo_roots(&main)?;
     roots.extend(detected);
@@ -130,6 +139,13 @@ pub async fn list_root_level_folders(
         }
     }
 
+    for directory in
+        crate::services::workspaces::other_workspace_directories(app_handle, project_directory)
+            .await
+    {
+        collect_directory_tree(&directory, 0, MAX_DEPTH, &mut seen, &mut results);
+    }
+
     Ok(results)
 }
 
@@ -1,9 +1,12 @@
 use crate::error::{AppError, AppResult};
 use crate::models::{RuntimeAIConfig, TaskSpecificModelConfig, TaskType};
 use crate::services::config_cache_service::ConfigCache;
+use crate::utils::plan_templates::is_valid_section_id;
+use crate::utils::project_config::ProjectConfig;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use tauri::{AppHandle, Manager};
 
 /// Detailed validation result with specific error information
@@ -216,6 +219,8 @@ impl ValidationRule for TaskTypeConfigurationRule {
         let all_task_types = [
             TaskType::ImplementationPlan,
             TaskType::ImplementationPlanMerge,
+            TaskType::ImplementationPlanRefinement,
+            TaskType::PlanTestGeneration,
             TaskType::VoiceTranscription,
             TaskType::TextImprovement,
             TaskType::TaskRefinement,
@@ -224,6 +229,7 @@ impl ValidationRule for TaskTypeConfigurationRule {
             TaskType::FileFinderWorkflow,
             TaskType::FileRelevanceAssessment,
             TaskType::ExtendedPathFinder,
+            TaskType::ImpactAnalysis,
             TaskType::WebSearchPromptsGeneration,
             TaskType::WebSearchExecution,
             TaskType::WebSearchWorkflow,
@@ -615,3 +621,231 @@ pub async fn validate_model_availability(app_handle: &AppHandle) -> AppResult<()
     );
     Ok(())
 }
+
+impl ValidationResult {
+    /// Result for a configuration file that could not be parsed at all
+    pub fn parse_failure(component: &str, message: String) -> Self {
+        ValidationResult {
+            is_valid: false,
+            errors: vec![ValidationError {
+                error_type: ValidationErrorType::InvalidValue,
+                component: component.to_string(),
+                field: Some("syntax".to_string()),
+                message,
+                severity: ValidationSeverity::Critical,
+            }],
+            warnings: Vec::new(),
+            summary: "Configuration file could not be parsed".to_string(),
+        }
+    }
+}
+
+/// Model ids offered by any provider in the runtime config
+pub fn available_model_ids(config: &RuntimeAIConfig) -> HashSet<String> {
+    config
+        .providers
+        .iter()
+        .flat_map(|p| p.models.iter().map(|m| m.id.clone()))
+        .collect()
+}
+
+/// Validate a per-project `.plantocode` file. Model ids are only checked
+/// against `available_models` when it is given, since the server model list
+/// may not be loaded yet when the file is first read.
+pub fn validate_project_config(
+    config: &ProjectConfig,
+    available_models: Option<&HashSet<String>>,
+) -> ValidationResult {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (task_key, task_config) in &config.tasks {
+        let component = format!("ProjectConfig.tasks[{}]", task_key);
+
+        match TaskType::from_str(task_key) {
+            Ok(task_type) if task_type != TaskType::Unknown => {
+                let sets_model_params = task_config.model.is_some()
+                    || task_config.temperature.is_some()
+                    || task_config.max_tokens.is_some();
+                if sets_model_params && !task_type.requires_llm() {
+                    warnings.push(ValidationWarning {
+                        warning_type: ValidationWarningType::UnknownConfiguration,
+                        component: component.clone(),
+                        message: "Task does not use an LLM; model settings are ignored".to_string(),
+                    });
+                }
+            }
+            _ => {
+                warnings.push(ValidationWarning {
+                    warning_type: ValidationWarningType::UnknownConfiguration,
+                    component: component.clone(),
+                    message: "No task type with this name; section is ignored".to_string(),
+                });
+            }
+        }
+
+        if let Some(model) = &task_config.model {
+            if model.trim().is_empty() {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component: component.clone(),
+                    field: Some("model".to_string()),
+                    message: "Model is empty".to_string(),
+                    severity: ValidationSeverity::High,
+                });
+            } else if available_models.is_some_and(|models| !models.contains(model)) {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::ModelNotFound,
+                    component: component.clone(),
+                    field: Some("model".to_string()),
+                    message: format!("Model '{}' not found in available providers", model),
+                    severity: ValidationSeverity::High,
+                });
+            }
+        }
+
+        if let Some(temperature) = task_config.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component: component.clone(),
+                    field: Some("temperature".to_string()),
+                    message: format!("Temperature {} is out of range [0.0, 2.0]", temperature),
+                    severity: ValidationSeverity::High,
+                });
+            }
+        }
+
+        if task_config.max_tokens == Some(0) {
+            errors.push(ValidationError {
+                error_type: ValidationErrorType::InvalidValue,
+                component: component.clone(),
+                field: Some("max_tokens".to_string()),
+                message: "Max tokens is 0".to_string(),
+                severity: ValidationSeverity::High,
+            });
+        }
+
+        if task_config.system_prompt.is_some() && task_config.system_prompt_file.is_some() {
+            errors.push(ValidationError {
+                error_type: ValidationErrorType::ConfigurationMismatch,
+                component: component.clone(),
+                field: Some("system_prompt_file".to_string()),
+                message: "Set either system_prompt or system_prompt_file, not both".to_string(),
+                severity: ValidationSeverity::High,
+            });
+        }
+
+        if let Some(prompt_file) = &task_config.system_prompt_file {
+            let path = std::path::Path::new(prompt_file);
+            let escapes_project = path.is_absolute()
+                || path
+                    .components()
+                    .any(|c| matches!(c, std::path::Component::ParentDir));
+            if escapes_project {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component,
+                    field: Some("system_prompt_file".to_string()),
+                    message: format!("'{}' must be a path inside the project", prompt_file),
+                    severity: ValidationSeverity::High,
+                });
+            }
+        }
+    }
+
+    let glob_lists = [
+        ("directory_tree.exclude", &config.directory_tree.exclude),
+        ("files.include", &config.files.include),
+        ("files.exclude", &config.files.exclude),
+    ];
+    for (field, patterns) in glob_lists {
+        for pattern in patterns {
+            if let Err(e) = glob::Pattern::new(pattern) {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component: "ProjectConfig".to_string(),
+                    field: Some(field.to_string()),
+                    message: format!("Invalid glob '{}': {}", pattern, e),
+                    severity: ValidationSeverity::High,
+                });
+            }
+        }
+    }
+
+    for (template_id, template) in &config.plans.templates {
+        let component = format!("ProjectConfig.plans.templates[{}]", template_id);
+
+        if template.sections.is_empty() {
+            warnings.push(ValidationWarning {
+                warning_type: ValidationWarningType::SuboptimalSettings,
+                component: component.clone(),
+                message: "Template has no sections; plans are not checked against it".to_string(),
+            });
+        }
+
+        let mut section_ids = HashSet::new();
+        for section in &template.sections {
+            if !is_valid_section_id(&section.id) {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component: component.clone(),
+                    field: Some("sections.id".to_string()),
+                    message: format!(
+                        "Invalid section id '{}': use lowercase letters, digits, '-' or '_'",
+                        section.id
+                    ),
+                    severity: ValidationSeverity::High,
+                });
+            } else if !section_ids.insert(section.id.as_str()) {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component: component.clone(),
+                    field: Some("sections.id".to_string()),
+                    message: format!("Section '{}' is defined twice", section.id),
+                    severity: ValidationSeverity::High,
+                });
+            }
+            if section.title.trim().is_empty() {
+                errors.push(ValidationError {
+                    error_type: ValidationErrorType::InvalidValue,
+                    component: component.clone(),
+                    field: Some("sections.title".to_string()),
+                    message: format!("Section '{}' has no title", section.id),
+                    severity: ValidationSeverity::High,
+                });
+            }
+        }
+    }
+
+    if let Some(default_template) = &config.plans.default_template {
+        if !config.plans.templates.contains_key(default_template) {
+            errors.push(ValidationError {
+                error_type: ValidationErrorType::ConfigurationMismatch,
+                component: "ProjectConfig.plans".to_string(),
+                field: Some("default_template".to_string()),
+                message: format!("No plan template named '{}'", default_template),
+                severity: ValidationSeverity::High,
+            });
+        }
+    }
+
+    let is_valid = errors.iter().all(|e| {
+        !matches!(
+            e.severity,
+            ValidationSeverity::Critical | ValidationSeverity::High
+        )
+    });
+    let summary = format!(
+        "Project configuration validation: {} errors, {} warnings",
+        errors.len(),
+        warnings.len()
+    );
+
+    ValidationResult {
+        is_valid,
+        errors,
+        warnings,
+        summary,
+    }
+}
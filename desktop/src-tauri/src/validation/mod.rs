@@ -2,6 +2,6 @@ pub mod config_validator;
 
 pub use config_validator::{
     ConfigValidator, ValidationError, ValidationErrorType, ValidationResult, ValidationSeverity,
-    ValidationWarning, ValidationWarningType, comprehensive_startup_validation,
-    validate_all_required_configs_present, validate_model_availability,
+    ValidationWarning, ValidationWarningType, available_model_ids, comprehensive_startup_validation,
+    validate_all_required_configs_present, validate_model_availability, validate_project_config,
 };
@@ -204,3 +204,114 @@ export async function generatePlanMarkdownAction(
     return handleActionError(err) as ActionState<PlanMarkdownResponse>;
   }
 }
+
+export type PlanRevisionSource = "model" | "edit" | "restore" | "refinement";
+
+export interface PlanRevisionSummary {
+  jobId: string;
+  revision: number;
+  contentHash: string;
+  source: PlanRevisionSource;
+  authorDeviceId: string | null;
+  restoredFrom: number | null;
+  signoffState: string | null;
+  signedOffAt: number | null;
+  createdAt: number;
+}
+
+export interface PlanRevision extends PlanRevisionSummary {
+  content: string;
+}
+
+export interface PlanRevisionDiff {
+  jobId: string;
+  fromRevision: number;
+  toRevision: number;
+  patch: string;
+  linesAdded: number;
+  linesRemoved: number;
+}
+
+/**
+ * List every revision of an implementation plan, oldest first
+ */
+export async function listPlanRevisionsAction(
+  jobId: string
+): Promise<ActionState<PlanRevisionSummary[]>> {
+  try {
+    const data = await invoke<PlanRevisionSummary[]>("list_plan_revisions_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (err) {
+    return handleActionError(err) as ActionState<PlanRevisionSummary[]>;
+  }
+}
+
+export async function getPlanRevisionAction(
+  jobId: string,
+  revision: number
+): Promise<ActionState<PlanRevision>> {
+  try {
+    const data = await invoke<PlanRevision>("get_plan_revision_command", { jobId, revision });
+    return { isSuccess: true, data };
+  } catch (err) {
+    return handleActionError(err) as ActionState<PlanRevision>;
+  }
+}
+
+/**
+ * Unified diff between any two revisions of a plan
+ */
+export async function diffPlanRevisionsAction(
+  jobId: string,
+  fromRevision: number,
+  toRevision: number
+): Promise<ActionState<PlanRevisionDiff>> {
+  try {
+    const data = await invoke<PlanRevisionDiff>("diff_plan_revisions_command", {
+      jobId,
+      fromRevision,
+      toRevision,
+    });
+    return { isSuccess: true, data };
+  } catch (err) {
+    return handleActionError(err) as ActionState<PlanRevisionDiff>;
+  }
+}
+
+/**
+ * Make an earlier revision the plan's current content again
+ */
+export async function restorePlanRevisionAction(
+  jobId: string,
+  revision: number
+): Promise<ActionState<PlanRevisionSummary>> {
+  try {
+    const data = await invoke<PlanRevisionSummary>("restore_plan_revision_command", {
+      jobId,
+      revision,
+    });
+    return { isSuccess: true, message: `Restored revision ${revision}`, data };
+  } catch (err) {
+    return handleActionError(err) as ActionState<PlanRevisionSummary>;
+  }
+}
+
+/**
+ * Sign off a specific plan revision, or the current one when no revision is given
+ */
+export async function signOffPlanRevisionAction(
+  jobId: string,
+  state: string,
+  revision?: number
+): Promise<ActionState<PlanRevisionSummary>> {
+  try {
+    const data = await invoke<PlanRevisionSummary>("mark_implementation_plan_signed_off_command", {
+      jobId,
+      state,
+      revision: revision ?? null,
+    });
+    return { isSuccess: true, data };
+  } catch (err) {
+    return handleActionError(err) as ActionState<PlanRevisionSummary>;
+  }
+}
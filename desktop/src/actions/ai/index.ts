@@ -1,4 +1,9 @@
 export * from "./implementation-plan.actions";
+export * from "./plan-conversation.actions";
+export * from "./plan-export.actions";
+export * from "./plan-review.actions";
+export * from "./plan-templates.actions";
+export * from "./plan-tests.actions";
 export * from "./prompt.actions";
 export * from "./regex-pattern-generation.actions";
 export * from "./task-refinement.actions";
@@ -0,0 +1,66 @@
+/**
+ * Plan Conversation Actions
+ *
+ * Actions for follow-up conversations on implementation plans. Each message asks for a change
+ * to the plan and is answered by a refinement job whose reply becomes the plan's next revision.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export interface PlanConversationMessage {
+  id: string;
+  jobId: string;
+  role: "user" | "assistant";
+  content: string;
+  /** Refinement job that answers (user) or produced (assistant) this message */
+  refinementJobId: string | null;
+  /** Revision the request was made against, or the revision the reply became */
+  planRevision: number | null;
+  authorDeviceId: string | null;
+  createdAt: number;
+}
+
+export interface PlanConversation {
+  jobId: string;
+  messages: PlanConversationMessage[];
+  /** Refinement job still working on the latest request */
+  pendingJobId: string | null;
+}
+
+export interface PlanRefinementStarted {
+  /** The refinement job answering the request */
+  jobId: string;
+  message: PlanConversationMessage;
+}
+
+export async function getPlanConversationAction(
+  jobId: string
+): Promise<ActionState<PlanConversation>> {
+  try {
+    const data = await invoke<PlanConversation>("get_plan_conversation_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanConversation>;
+  }
+}
+
+/**
+ * Ask for a change to a plan, e.g. "split step 3 into two steps"
+ */
+export async function refineImplementationPlanAction(
+  jobId: string,
+  message: string
+): Promise<ActionState<PlanRefinementStarted>> {
+  try {
+    const data = await invoke<PlanRefinementStarted>("refine_implementation_plan_command", {
+      jobId,
+      message,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanRefinementStarted>;
+  }
+}
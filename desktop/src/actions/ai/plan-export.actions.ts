@@ -0,0 +1,63 @@
+/**
+ * Plan Export Actions
+ *
+ * Actions for exporting implementation plans to issue trackers: a parent issue plus one
+ * sub-task per step, written as import files (Markdown with front matter, GitHub issue JSON,
+ * Jira CSV or any other registered format).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export interface PlanExportFormat {
+  id: string;
+  displayName: string;
+  description: string;
+}
+
+export interface ExportedFile {
+  fileName: string;
+  mediaType: string;
+  content: string;
+}
+
+export interface PlanIssueExport {
+  jobId: string;
+  format: string;
+  /** Directory the files were written to */
+  outputDirectory: string;
+  issueCount: number;
+  files: ExportedFile[];
+}
+
+export async function listPlanExportFormatsAction(): Promise<ActionState<PlanExportFormat[]>> {
+  try {
+    const data = await invoke<PlanExportFormat[]>("list_plan_export_formats_command");
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanExportFormat[]>;
+  }
+}
+
+/**
+ * Export a plan in the given format. Without an output directory the files are written to the
+ * project's `.plantocode/issue_exports` directory.
+ */
+export async function exportPlanIssuesAction(
+  jobId: string,
+  format: string,
+  outputDirectory?: string
+): Promise<ActionState<PlanIssueExport>> {
+  try {
+    const data = await invoke<PlanIssueExport>("export_plan_issues_command", {
+      jobId,
+      format,
+      outputDirectory: outputDirectory ?? null,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanIssueExport>;
+  }
+}
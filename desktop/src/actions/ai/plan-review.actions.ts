@@ -0,0 +1,195 @@
+/**
+ * Plan Review Actions
+ *
+ * Actions for per-step status, assignees and review comments on implementation plans,
+ * and for the git-based progress of signed-off plans.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export type PlanStepStatus = "pending" | "in_progress" | "done" | "skipped";
+
+export interface PlanStepComment {
+  id: string;
+  jobId: string;
+  stepNumber: string;
+  /** Plan revision the comment was written against */
+  planRevision: number | null;
+  body: string;
+  authorDeviceId: string | null;
+  resolved: boolean;
+  createdAt: number;
+  updatedAt: number;
+}
+
+export interface PlanStepState {
+  jobId: string;
+  stepNumber: string;
+  status: PlanStepStatus;
+  assignee: string | null;
+  updatedByDeviceId: string | null;
+  updatedAt: number;
+}
+
+export interface PlanStepReview {
+  stepNumber: string;
+  title: string;
+  /** False when the step has review state but is no longer in the plan content */
+  inPlan: boolean;
+  status: PlanStepStatus;
+  assignee: string | null;
+  updatedByDeviceId: string | null;
+  updatedAt: number | null;
+  comments: PlanStepComment[];
+}
+
+export interface PlanStepProgress {
+  total: number;
+  pending: number;
+  inProgress: number;
+  done: number;
+  skipped: number;
+}
+
+export interface PlanStepReviews {
+  jobId: string;
+  steps: PlanStepReview[];
+  progress: PlanStepProgress;
+}
+
+/**
+ * Get every step of a plan with its status, assignee and comments
+ */
+export async function getPlanStepReviewsAction(
+  jobId: string
+): Promise<ActionState<PlanStepReviews>> {
+  try {
+    const data = await invoke<PlanStepReviews>("get_plan_step_reviews_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanStepReviews>;
+  }
+}
+
+/**
+ * Update a step's status and/or assignee; pass `assignee: null` to unassign
+ */
+export async function updatePlanStepAction(
+  jobId: string,
+  stepNumber: string,
+  update: { status?: PlanStepStatus; assignee?: string | null }
+): Promise<ActionState<PlanStepState>> {
+  try {
+    const data = await invoke<PlanStepState>("update_plan_step_command", {
+      jobId,
+      stepNumber,
+      status: update.status ?? null,
+      assignee: update.assignee ?? null,
+      clearAssignee: update.assignee === null,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanStepState>;
+  }
+}
+
+export async function addPlanStepCommentAction(
+  jobId: string,
+  stepNumber: string,
+  body: string
+): Promise<ActionState<PlanStepComment>> {
+  try {
+    const data = await invoke<PlanStepComment>("add_plan_step_comment_command", {
+      jobId,
+      stepNumber,
+      body,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanStepComment>;
+  }
+}
+
+export async function resolvePlanStepCommentAction(
+  jobId: string,
+  commentId: string,
+  resolved: boolean
+): Promise<ActionState<PlanStepComment>> {
+  try {
+    const data = await invoke<PlanStepComment>("resolve_plan_step_comment_command", {
+      jobId,
+      commentId,
+      resolved,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanStepComment>;
+  }
+}
+
+export async function deletePlanStepCommentAction(
+  jobId: string,
+  commentId: string
+): Promise<ActionState<null>> {
+  try {
+    await invoke("delete_plan_step_comment_command", { jobId, commentId });
+    return { isSuccess: true, data: null };
+  } catch (error) {
+    return handleActionError(error) as ActionState<null>;
+  }
+}
+
+export interface StepFileProgress {
+  stepNumber: string;
+  title: string;
+  plannedFiles: string[];
+  touchedFiles: string[];
+  /** Share of planned files touched, 0 to 1 */
+  completion: number;
+}
+
+export interface PlanProgress {
+  jobId: string;
+  signedOffAt: number;
+  signedOffRevision: number | null;
+  checkedAt: number;
+  /** Commits since the sign-off, newest first */
+  commits: string[];
+  totalSteps: number;
+  stepsTouched: number;
+  stepsCompleted: number;
+  steps: StepFileProgress[];
+  /** Changed files that no step of the plan mentions */
+  unplannedFiles: string[];
+}
+
+/**
+ * Last recorded progress of a signed-off plan, or null if it has not been checked yet
+ */
+export async function getPlanProgressAction(
+  jobId: string
+): Promise<ActionState<PlanProgress | null>> {
+  try {
+    const data = await invoke<PlanProgress | null>("get_plan_progress_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanProgress | null>;
+  }
+}
+
+/**
+ * Check a signed-off plan against commits and working-tree changes now
+ */
+export async function checkPlanProgressAction(
+  jobId: string
+): Promise<ActionState<PlanProgress>> {
+  try {
+    const data = await invoke<PlanProgress>("check_plan_progress_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanProgress>;
+  }
+}
@@ -0,0 +1,120 @@
+/**
+ * Plan Template Actions
+ *
+ * Actions for team plan templates defined under [plans] in .plantocode.toml. A session
+ * generates plans with its selected template (or the project default), and the generated
+ * plan is checked for the template's required sections.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+/** Selection value that opts a session out of the project's default template */
+export const NO_PLAN_TEMPLATE = "none";
+
+export interface PlanTemplateSection {
+  id: string;
+  title: string;
+  instructions: string | null;
+  required: boolean;
+}
+
+export interface PlanTemplate {
+  id: string;
+  name: string;
+  description: string | null;
+  sections: PlanTemplateSection[];
+  requestMissingSections: boolean;
+  isDefault: boolean;
+}
+
+export interface SessionPlanTemplate {
+  /** Template id, "none", or null when the project default is used */
+  selection: string | null;
+  template: PlanTemplate | null;
+}
+
+export interface MissingPlanSection {
+  id: string;
+  title: string;
+  required: boolean;
+  /** The section is there, but has no content */
+  empty: boolean;
+}
+
+export interface PlanTemplateCheck {
+  templateId: string;
+  templateName: string;
+  presentSections: string[];
+  missingSections: MissingPlanSection[];
+  /** Every required section is present */
+  passed: boolean;
+  /** Missing sections were asked for in a follow-up turn */
+  followUpRequested: boolean;
+  checkedAt: number;
+}
+
+export async function listPlanTemplatesAction(
+  projectDirectory: string
+): Promise<ActionState<PlanTemplate[]>> {
+  try {
+    const data = await invoke<PlanTemplate[]>("list_plan_templates_command", {
+      projectDirectory,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanTemplate[]>;
+  }
+}
+
+export async function getSessionPlanTemplateAction(
+  sessionId: string
+): Promise<ActionState<SessionPlanTemplate>> {
+  try {
+    const data = await invoke<SessionPlanTemplate>("get_session_plan_template_command", {
+      sessionId,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<SessionPlanTemplate>;
+  }
+}
+
+/**
+ * Select a template for a session: a template id, NO_PLAN_TEMPLATE, or null for the project default
+ */
+export async function setSessionPlanTemplateAction(
+  sessionId: string,
+  templateId: string | null
+): Promise<ActionState<SessionPlanTemplate>> {
+  try {
+    const data = await invoke<SessionPlanTemplate>("set_session_plan_template_command", {
+      sessionId,
+      templateId,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<SessionPlanTemplate>;
+  }
+}
+
+/**
+ * Check a plan against its session's template, or against templateId when given.
+ * Resolves to null when no template applies.
+ */
+export async function checkPlanTemplateAction(
+  jobId: string,
+  templateId?: string
+): Promise<ActionState<PlanTemplateCheck | null>> {
+  try {
+    const data = await invoke<PlanTemplateCheck | null>("check_plan_template_command", {
+      jobId,
+      templateId: templateId ?? null,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanTemplateCheck | null>;
+  }
+}
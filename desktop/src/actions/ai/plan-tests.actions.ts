@@ -0,0 +1,62 @@
+/**
+ * Plan Test Actions
+ *
+ * Actions for test plans of signed-off implementation plans. A generation job drafts tests for
+ * each step in the style of the project's existing tests; the result is stored with the plan.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export interface PlanTestCase {
+  name: string;
+  /** File the test belongs in, relative to the project */
+  file: string;
+  description: string | null;
+  /** Skeleton code of the test */
+  code: string;
+}
+
+export interface PlanStepTests {
+  stepNumber: string;
+  title: string;
+  tests: PlanTestCase[];
+}
+
+export interface PlanTestPlan {
+  /** The job that generated the test plan */
+  testJobId: string;
+  /** Plan revision the tests were generated for */
+  planRevision: number | null;
+  generatedAt: number;
+  /** Existing test files used as style examples */
+  referenceFiles: string[];
+  steps: PlanStepTests[];
+}
+
+/**
+ * Queue test plan generation for the signed-off revision of a plan
+ */
+export async function generatePlanTestsAction(
+  jobId: string
+): Promise<ActionState<{ jobId: string }>> {
+  try {
+    const data = await invoke<{ jobId: string }>("generate_plan_tests_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<{ jobId: string }>;
+  }
+}
+
+export async function getPlanTestPlanAction(
+  jobId: string
+): Promise<ActionState<PlanTestPlan | null>> {
+  try {
+    const data = await invoke<PlanTestPlan | null>("get_plan_test_plan_command", { jobId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<PlanTestPlan | null>;
+  }
+}
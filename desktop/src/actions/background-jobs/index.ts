@@ -1 +1,2 @@
 export * from "./jobs.actions";
+export * from "./retry-policy.actions";
\ No newline at end of file
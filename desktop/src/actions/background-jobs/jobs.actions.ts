@@ -137,3 +137,107 @@ export async function cancelSessionRequestsAction(sessionId: string): Promise<Ac
     };
   }
 }
+
+export interface JobProvenanceNode {
+  jobId: string;
+  sessionId: string;
+  taskType: string;
+  status: string;
+  modelUsed: string | null;
+  title: string | null;
+  actualCost: number | null;
+  createdAt: number;
+}
+
+export interface JobProvenanceEdge {
+  parentJobId: string;
+  childJobId: string;
+  relation: string;
+  replayId: string | null;
+  createdAt: number;
+}
+
+export interface JobDerivationGraph {
+  rootJobId: string;
+  nodes: JobProvenanceNode[];
+  edges: JobProvenanceEdge[];
+}
+
+export interface ReplayOverrides {
+  model?: string;
+  temperature?: number;
+  maxTokens?: number;
+  taskDescription?: string;
+}
+
+export interface ReplayStarted {
+  replayId: string;
+  originalJobId: string;
+  rerunJobId: string;
+  scheduledJobIds: string[];
+}
+
+export interface ReplayJobSummary {
+  jobId: string;
+  taskType: string;
+  status: string;
+  modelUsed: string | null;
+  tokensSent: number | null;
+  tokensReceived: number | null;
+  actualCost: number | null;
+  response: string | null;
+  errorMessage: string | null;
+}
+
+export interface ReplayComparison {
+  replayId: string;
+  pairs: { original: ReplayJobSummary; rerun: ReplayJobSummary; responseDiff: string | null }[];
+}
+
+/**
+ * Gets the jobs a job was derived from and the jobs derived from it
+ */
+export async function getJobProvenanceAction(
+  jobId: string
+): Promise<ActionState<JobDerivationGraph>> {
+  try {
+    const graph = await invoke<JobDerivationGraph>("get_job_provenance_command", { jobId });
+    return { isSuccess: true, data: graph };
+  } catch (e) {
+    return handleActionError(e) as ActionState<JobDerivationGraph>;
+  }
+}
+
+/**
+ * Re-runs a job with changed inputs, then every job derived from it
+ */
+export async function rerunJobFromHereAction(
+  jobId: string,
+  overrides: ReplayOverrides = {}
+): Promise<ActionState<ReplayStarted>> {
+  try {
+    const replay = await invoke<ReplayStarted>("rerun_job_from_here_command", {
+      jobId,
+      overrides,
+    });
+    return { isSuccess: true, data: replay };
+  } catch (e) {
+    return handleActionError(e) as ActionState<ReplayStarted>;
+  }
+}
+
+/**
+ * Gets the original and re-run jobs of a replay side by side
+ */
+export async function getJobReplayComparisonAction(
+  replayId: string
+): Promise<ActionState<ReplayComparison>> {
+  try {
+    const comparison = await invoke<ReplayComparison>("get_job_replay_comparison_command", {
+      replayId,
+    });
+    return { isSuccess: true, data: comparison };
+  } catch (e) {
+    return handleActionError(e) as ActionState<ReplayComparison>;
+  }
+}
@@ -0,0 +1,95 @@
+/**
+ * Job Retry Policy Actions
+ *
+ * Actions for per-task-type retry policies and the job queue circuit breaker.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export type BackoffCurve = "fixed" | "linear" | "exponential";
+
+export interface RetryPolicy {
+  /** Retries after the first attempt */
+  maxRetries: number;
+  backoff: BackoffCurve;
+  baseDelaySecs: number;
+  maxDelaySecs: number;
+  /** Random extra delay as a fraction of the backoff, 0 to 1 */
+  jitterRatio: number;
+  honorRetryAfter: boolean;
+}
+
+export interface TaskRetryPolicy {
+  taskType: string;
+  policy: RetryPolicy;
+  customized: boolean;
+}
+
+export type CircuitState = "closed" | "open" | "half_open";
+
+export interface CircuitBreakerStatus {
+  state: CircuitState;
+  retryInSecs: number | null;
+  recentFailures: number;
+  consecutiveTrips: number;
+  lastError: string | null;
+}
+
+/**
+ * Get the retry policy in effect for every job task type
+ */
+export async function getJobRetryPoliciesAction(): Promise<ActionState<TaskRetryPolicy[]>> {
+  try {
+    const policies = await invoke<TaskRetryPolicy[]>("get_job_retry_policies_command");
+    return { isSuccess: true, data: policies };
+  } catch (error) {
+    return handleActionError(error) as ActionState<TaskRetryPolicy[]>;
+  }
+}
+
+/**
+ * Override a task type's retry policy, or pass null to restore the default
+ */
+export async function updateJobRetryPolicyAction(
+  taskType: string,
+  policy: RetryPolicy | null
+): Promise<ActionState<TaskRetryPolicy>> {
+  try {
+    const updated = await invoke<TaskRetryPolicy>("update_job_retry_policy_command", {
+      taskType,
+      policy,
+    });
+    return { isSuccess: true, data: updated };
+  } catch (error) {
+    return handleActionError(error) as ActionState<TaskRetryPolicy>;
+  }
+}
+
+/**
+ * Whether the job queue is paused because the server keeps rejecting work
+ */
+export async function getJobCircuitBreakerStatusAction(): Promise<
+  ActionState<CircuitBreakerStatus>
+> {
+  try {
+    const status = await invoke<CircuitBreakerStatus>("get_job_circuit_breaker_status_command");
+    return { isSuccess: true, data: status };
+  } catch (error) {
+    return handleActionError(error) as ActionState<CircuitBreakerStatus>;
+  }
+}
+
+/**
+ * Resume the job queue without waiting for the cooldown
+ */
+export async function resetJobCircuitBreakerAction(): Promise<ActionState<CircuitBreakerStatus>> {
+  try {
+    const status = await invoke<CircuitBreakerStatus>("reset_job_circuit_breaker_command");
+    return { isSuccess: true, data: status };
+  } catch (error) {
+    return handleActionError(error) as ActionState<CircuitBreakerStatus>;
+  }
+}
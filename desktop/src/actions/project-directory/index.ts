@@ -1 +1,2 @@
 export * from "./broadcast-project-directory.actions";
+export * from "./workspaces.actions";
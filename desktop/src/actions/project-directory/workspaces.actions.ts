@@ -0,0 +1,113 @@
+/**
+ * Workspace Actions
+ *
+ * Actions for multi-repository workspaces: groups of project directories that sessions and
+ * implementation plans can span. Files in the other repositories of a session's workspace are
+ * referenced as "label:path".
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export interface WorkspaceMember {
+  /** 2-32 lowercase letters, digits, "-" or "_" */
+  label: string;
+  projectDirectory: string;
+}
+
+export interface Workspace {
+  /** Empty when creating a new workspace */
+  id: string;
+  name: string;
+  members: WorkspaceMember[];
+  createdAt?: number;
+  updatedAt?: number;
+}
+
+export interface WorkspaceRepoRoot {
+  label: string;
+  directory: string;
+  /** The session's own repository; its paths have no label prefix */
+  isCurrent: boolean;
+}
+
+export interface WorkspaceLayout {
+  workspaceId: string;
+  workspaceName: string;
+  repositories: WorkspaceRepoRoot[];
+}
+
+export interface RepoFileSelection {
+  label: string;
+  isCurrent: boolean;
+  /** Paths relative to the repository */
+  files: string[];
+}
+
+export async function listWorkspacesAction(): Promise<ActionState<Workspace[]>> {
+  try {
+    const data = await invoke<Workspace[]>("list_workspaces_command");
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<Workspace[]>;
+  }
+}
+
+/**
+ * Create or update a workspace. A project directory can belong to one workspace only.
+ */
+export async function saveWorkspaceAction(
+  workspace: Workspace
+): Promise<ActionState<Workspace>> {
+  try {
+    const data = await invoke<Workspace>("save_workspace_command", { workspace });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<Workspace>;
+  }
+}
+
+export async function deleteWorkspaceAction(
+  workspaceId: string
+): Promise<ActionState<boolean>> {
+  try {
+    const data = await invoke<boolean>("delete_workspace_command", { workspaceId });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<boolean>;
+  }
+}
+
+/**
+ * Repositories of the workspace a project directory belongs to, or null when it has none
+ */
+export async function getWorkspaceLayoutAction(
+  projectDirectory: string
+): Promise<ActionState<WorkspaceLayout | null>> {
+  try {
+    const data = await invoke<WorkspaceLayout | null>("get_workspace_layout_command", {
+      projectDirectory,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<WorkspaceLayout | null>;
+  }
+}
+
+/**
+ * A session's included files grouped per workspace repository
+ */
+export async function getSessionRepoFileSelectionAction(
+  sessionId: string
+): Promise<ActionState<RepoFileSelection[]>> {
+  try {
+    const data = await invoke<RepoFileSelection[]>("get_session_repo_file_selection_command", {
+      sessionId,
+    });
+    return { isSuccess: true, data };
+  } catch (error) {
+    return handleActionError(error) as ActionState<RepoFileSelection[]>;
+  }
+}
@@ -251,4 +251,43 @@ export async function resetProjectTaskSetting(
     console.error("Error resetting project task setting:", error);
     return handleActionError(error) as ActionState<void>;
   }
-}
\ No newline at end of file
+}
+export interface ProjectConfigFileValidationIssue {
+  component: string;
+  field?: string | null;
+  message: string;
+  severity?: string;
+}
+
+export interface ProjectConfigFile {
+  path: string;
+  config: Record<string, unknown>;
+  validation: {
+    is_valid: boolean;
+    errors: ProjectConfigFileValidationIssue[];
+    warnings: ProjectConfigFileValidationIssue[];
+    summary: string;
+  };
+}
+
+/**
+ * Get the `.plantocode.toml` / `.plantocode.json` file checked into the project, with validation
+ */
+export async function getProjectConfigFileAction(
+  projectDirectory: string
+): Promise<ActionState<ProjectConfigFile | null>> {
+  try {
+    const configFile = await invoke<ProjectConfigFile | null>("get_project_config_command", {
+      projectDirectory,
+    });
+
+    return {
+      isSuccess: true,
+      message: configFile ? "Project config file loaded" : "No project config file",
+      data: configFile,
+    };
+  } catch (error) {
+    console.error("Error loading project config file:", error);
+    return handleActionError(error) as ActionState<ProjectConfigFile | null>;
+  }
+}
@@ -285,4 +285,192 @@ export async function duplicateSessionAction(sourceSessionId: string, newName?:
     logger.error("Failed to duplicate session:", err);
     return handleActionError(err) as ActionState<Session>;
   }
-}
\ No newline at end of file
+}
+
+export interface SessionBundleManifest {
+  format: string;
+  formatVersion: number;
+  appVersion: string;
+  exportedAt: number;
+  sourceSessionId: string;
+  sourceProjectDirectory: string;
+  sourceProjectHash: string;
+  jobCount: number;
+  fileSnapshotCount: number;
+}
+
+export interface SessionImportResult {
+  session: Session;
+  importedJobCount: number;
+  fileDrift: Array<{ path: string; status: "missing" | "modified" | "rejected" }>;
+}
+
+/**
+ * Export a session, its history and finished jobs to a portable bundle file
+ */
+export async function exportSessionBundleAction(
+  sessionId: string,
+  outputPath: string,
+  includeFileSnapshots = false
+): Promise<ActionState<SessionBundleManifest>> {
+  try {
+    const manifest = await invoke<SessionBundleManifest>("export_session_bundle_command", {
+      sessionId,
+      outputPath,
+      includeFileSnapshots,
+    });
+    return { isSuccess: true, data: manifest, message: "Session exported successfully" };
+  } catch (err) {
+    logger.error("Failed to export session bundle:", err);
+    return handleActionError(err) as ActionState<SessionBundleManifest>;
+  }
+}
+
+/**
+ * Import a session bundle into the given project directory as a new session
+ */
+export async function importSessionBundleAction(
+  bundlePath: string,
+  projectDirectory: string
+): Promise<ActionState<SessionImportResult>> {
+  try {
+    const result = await invoke<SessionImportResult>("import_session_bundle_command", {
+      bundlePath,
+      projectDirectory: await normalizePath(projectDirectory),
+    });
+    return { isSuccess: true, data: result, message: "Session imported successfully" };
+  } catch (err) {
+    logger.error("Failed to import session bundle:", err);
+    return handleActionError(err) as ActionState<SessionImportResult>;
+  }
+}
+
+export type SearchSourceType = "session" | "task_history" | "job";
+
+export interface SessionSearchHit {
+  sourceType: SearchSourceType;
+  sourceId: string;
+  sessionId: string;
+  sessionName: string;
+  taskType: string | null;
+  createdAt: number;
+  /** Matched text with terms wrapped in <mark>; the surrounding text is not HTML-escaped */
+  snippet: string;
+  rank: number;
+}
+
+export interface SessionSearchFilters {
+  projectDirectory?: string;
+  taskTypes?: string[];
+  fromMs?: number;
+  toMs?: number;
+  limit?: number;
+}
+
+/**
+ * Full-text search across session task descriptions, their history and completed job outputs
+ */
+export async function searchSessionsAction(
+  query: string,
+  filters: SessionSearchFilters = {}
+): Promise<ActionState<SessionSearchHit[]>> {
+  try {
+    const hits = await invoke<SessionSearchHit[]>("search_sessions_command", {
+      query,
+      projectDirectory: filters.projectDirectory
+        ? await normalizePath(filters.projectDirectory)
+        : null,
+      taskTypes: filters.taskTypes ?? null,
+      fromMs: filters.fromMs ?? null,
+      toMs: filters.toMs ?? null,
+      limit: filters.limit ?? null,
+    });
+    return { isSuccess: true, data: hits };
+  } catch (err) {
+    logger.error("Failed to search sessions:", err);
+    return handleActionError(err) as ActionState<SessionSearchHit[]>;
+  }
+}
+
+export interface SessionTreeNode {
+  sessionId: string;
+  name: string;
+  createdAt: number;
+  updatedAt: number;
+  forkedTaskHistoryIndex: number | null;
+  children: SessionTreeNode[];
+}
+
+export interface SessionTree {
+  rootSessionId: string;
+  roots: SessionTreeNode[];
+}
+
+export interface ForkPlan {
+  sessionId: string;
+  jobId: string;
+  planTitle: string | null;
+  createdAt: number;
+  content: string;
+}
+
+export interface PlanComparison {
+  base: ForkPlan | null;
+  other: ForkPlan | null;
+  diff: string | null;
+  linesAdded: number;
+  linesRemoved: number;
+}
+
+/**
+ * Fork a session at a task/file history index into a new linked session
+ */
+export async function forkSessionAction(
+  sessionId: string,
+  options: { taskHistoryIndex?: number; fileHistoryIndex?: number; newName?: string } = {}
+): Promise<ActionState<Session>> {
+  try {
+    const session = await invoke<Session>("fork_session_command", {
+      sessionId,
+      taskHistoryIndex: options.taskHistoryIndex ?? null,
+      fileHistoryIndex: options.fileHistoryIndex ?? null,
+      newName: options.newName ?? null,
+    });
+    return { isSuccess: true, data: session, message: "Session forked successfully" };
+  } catch (err) {
+    logger.error("Failed to fork session:", err);
+    return handleActionError(err) as ActionState<Session>;
+  }
+}
+
+/**
+ * Get the fork tree containing a session
+ */
+export async function getSessionTreeAction(sessionId: string): Promise<ActionState<SessionTree>> {
+  try {
+    const tree = await invoke<SessionTree>("get_session_tree_command", { sessionId });
+    return { isSuccess: true, data: tree };
+  } catch (err) {
+    logger.error("Failed to load session tree:", err);
+    return handleActionError(err) as ActionState<SessionTree>;
+  }
+}
+
+/**
+ * Diff the latest completed plans of two forks
+ */
+export async function compareForkPlansAction(
+  baseSessionId: string,
+  otherSessionId: string
+): Promise<ActionState<PlanComparison>> {
+  try {
+    const comparison = await invoke<PlanComparison>("compare_fork_plans_command", {
+      baseSessionId,
+      otherSessionId,
+    });
+    return { isSuccess: true, data: comparison };
+  } catch (err) {
+    logger.error("Failed to compare fork plans:", err);
+    return handleActionError(err) as ActionState<PlanComparison>;
+  }
+}
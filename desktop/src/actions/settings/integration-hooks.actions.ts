@@ -0,0 +1,143 @@
+/**
+ * Integration Hook Actions
+ *
+ * Actions for managing webhook and shell command hooks fired on application events,
+ * and for inspecting their delivery log.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export type IntegrationEventType =
+  | "job.created"
+  | "job.status_changed"
+  | "job.finalized"
+  | "workflow.status_changed"
+  | "workflow.stage_changed"
+  | "plan.created"
+  | "plan.progress_updated"
+  | "terminal.exited";
+
+export type HookTarget =
+  | { type: "webhook"; url: string; headers?: Record<string, string> }
+  | { type: "shell"; command: string };
+
+export interface IntegrationHook {
+  /** Empty when creating a new hook */
+  id: string;
+  name: string;
+  events: IntegrationEventType[];
+  target: HookTarget;
+  maxAttempts: number;
+  enabled: boolean;
+  createdAt?: number;
+  updatedAt?: number;
+}
+
+export interface HookDelivery {
+  id: number;
+  hookId: string;
+  eventType: string;
+  payload: unknown;
+  attempt: number;
+  status: "success" | "failed";
+  responseStatus: number | null;
+  error: string | null;
+  durationMs: number | null;
+  createdAt: number;
+}
+
+export interface DeliveryOutcome {
+  success: boolean;
+  responseStatus: number | null;
+  error: string | null;
+  durationMs: number;
+}
+
+/**
+ * List all configured integration hooks
+ */
+export async function listIntegrationHooksAction(): Promise<ActionState<IntegrationHook[]>> {
+  try {
+    const hooks = await invoke<IntegrationHook[]>("list_integration_hooks_command");
+    return { isSuccess: true, data: hooks };
+  } catch (error) {
+    return handleActionError(error) as ActionState<IntegrationHook[]>;
+  }
+}
+
+/**
+ * List the event types hooks can subscribe to
+ */
+export async function listIntegrationHookEventTypesAction(): Promise<
+  ActionState<IntegrationEventType[]>
+> {
+  try {
+    const types = await invoke<IntegrationEventType[]>(
+      "list_integration_hook_event_types_command"
+    );
+    return { isSuccess: true, data: types };
+  } catch (error) {
+    return handleActionError(error) as ActionState<IntegrationEventType[]>;
+  }
+}
+
+/**
+ * Create or update a hook
+ */
+export async function saveIntegrationHookAction(
+  hook: IntegrationHook
+): Promise<ActionState<IntegrationHook>> {
+  try {
+    const saved = await invoke<IntegrationHook>("save_integration_hook_command", { hook });
+    return { isSuccess: true, data: saved };
+  } catch (error) {
+    return handleActionError(error) as ActionState<IntegrationHook>;
+  }
+}
+
+/**
+ * Delete a hook and its delivery log
+ */
+export async function deleteIntegrationHookAction(hookId: string): Promise<ActionState<boolean>> {
+  try {
+    const deleted = await invoke<boolean>("delete_integration_hook_command", { hookId });
+    return { isSuccess: true, data: deleted };
+  } catch (error) {
+    return handleActionError(error) as ActionState<boolean>;
+  }
+}
+
+/**
+ * Recent delivery attempts, newest first
+ */
+export async function listIntegrationHookDeliveriesAction(
+  hookId?: string,
+  limit?: number
+): Promise<ActionState<HookDelivery[]>> {
+  try {
+    const deliveries = await invoke<HookDelivery[]>(
+      "list_integration_hook_deliveries_command",
+      { hookId: hookId ?? null, limit: limit ?? null }
+    );
+    return { isSuccess: true, data: deliveries };
+  } catch (error) {
+    return handleActionError(error) as ActionState<HookDelivery[]>;
+  }
+}
+
+/**
+ * Send a test event to a hook once
+ */
+export async function testIntegrationHookAction(
+  hookId: string
+): Promise<ActionState<DeliveryOutcome>> {
+  try {
+    const outcome = await invoke<DeliveryOutcome>("test_integration_hook_command", { hookId });
+    return { isSuccess: true, data: outcome };
+  } catch (error) {
+    return handleActionError(error) as ActionState<DeliveryOutcome>;
+  }
+}
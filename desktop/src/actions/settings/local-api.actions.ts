@@ -0,0 +1,74 @@
+/**
+ * Local API Actions
+ *
+ * Actions for the localhost HTTP/WebSocket API used by editor plugins.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+import { type ActionState } from "@/types";
+import { handleActionError } from "@/utils/action-utils";
+
+export interface LocalApiConfig {
+  enabled: boolean;
+  /** 0 lets the OS pick a free port */
+  port: number;
+}
+
+export interface LocalApiStatus {
+  running: boolean;
+  url: string | null;
+  port: number | null;
+  tokenPath: string;
+  discoveryPath: string;
+}
+
+/**
+ * Get the saved local API settings
+ */
+export async function getLocalApiConfigAction(): Promise<ActionState<LocalApiConfig>> {
+  try {
+    const config = await invoke<LocalApiConfig>("get_local_api_config_command");
+    return { isSuccess: true, data: config };
+  } catch (error) {
+    return handleActionError(error) as ActionState<LocalApiConfig>;
+  }
+}
+
+/**
+ * Save local API settings and start or stop the server accordingly
+ */
+export async function updateLocalApiConfigAction(
+  config: LocalApiConfig
+): Promise<ActionState<LocalApiStatus>> {
+  try {
+    const status = await invoke<LocalApiStatus>("update_local_api_config_command", { config });
+    return { isSuccess: true, data: status };
+  } catch (error) {
+    return handleActionError(error) as ActionState<LocalApiStatus>;
+  }
+}
+
+/**
+ * Get whether the local API is running and where
+ */
+export async function getLocalApiStatusAction(): Promise<ActionState<LocalApiStatus>> {
+  try {
+    const status = await invoke<LocalApiStatus>("get_local_api_status_command");
+    return { isSuccess: true, data: status };
+  } catch (error) {
+    return handleActionError(error) as ActionState<LocalApiStatus>;
+  }
+}
+
+/**
+ * Issue a new token; plugins must re-read it from the token file
+ */
+export async function regenerateLocalApiTokenAction(): Promise<ActionState<LocalApiStatus>> {
+  try {
+    const status = await invoke<LocalApiStatus>("regenerate_local_api_token_command");
+    return { isSuccess: true, data: status };
+  } catch (error) {
+    return handleActionError(error) as ActionState<LocalApiStatus>;
+  }
+}
@@ -425,7 +425,8 @@ export const JobCard = ({ job, handleCancel, handleDelete, isCancelling, isDelet
                         const fileFindingTasks = [
                           "extended_path_finder",
                           "file_relevance_assessment",
-                          "regex_file_filter"
+                          "regex_file_filter",
+                          "impact_analysis"
                         ];
                         
                         if (fileFindingTasks.includes(job.taskType)) {
@@ -658,7 +659,8 @@ export const JobCard = ({ job, handleCancel, handleDelete, isCancelling, isDelet
                         const fileFindingTasks = [
                           "extended_path_finder",
                           "file_relevance_assessment",
-                          "regex_file_filter"
+                          "regex_file_filter",
+                          "impact_analysis"
                         ];
                         
                         const shouldShowAddFiles = onApplyFiles && (
@@ -329,6 +329,24 @@ export function JobDetailsModal({ job, onClose }: JobDetailsModalProps) {
             );
           }
           break;
+
+        case 'impact_analysis':
+          if (response.dependents && Array.isArray(response.dependents)) {
+            return (
+              <div className="space-y-2">
+                <div className="font-semibold">{response.summary || `${response.dependents.length} dependent files found`}</div>
+                <div className="max-h-96 overflow-y-auto space-y-1">
+                  {response.dependents.map((dependent: { path: string; reason: string }, index: number) => (
+                    <div key={index} className="bg-muted p-2 rounded">
+                      <div className="text-sm font-mono">{dependent.path}</div>
+                      <div className="text-xs text-muted-foreground">{dependent.reason}</div>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            );
+          }
+          break;
           
         case 'web_search_execution': {
           try {
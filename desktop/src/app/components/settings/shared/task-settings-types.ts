@@ -12,6 +12,8 @@ export const taskSettingsKeyToTaskType: Record<keyof TaskSettings, TaskType> = {
   textImprovement: "text_improvement",
   implementationPlan: "implementation_plan",
   implementationPlanMerge: "implementation_plan_merge",
+  implementationPlanRefinement: "implementation_plan_refinement",
+  planTestGeneration: "plan_test_generation",
   extendedPathFinder: "extended_path_finder",
   fileRelevanceAssessment: "file_relevance_assessment",
   taskRefinement: "task_refinement",
@@ -250,6 +250,15 @@ export default function TaskModelSettings({
     }
   }, [projectDirectory, onRefresh]);
 
+  const handleResponseCacheChange = useCallback(async (camelCaseKey: keyof TaskSettings, enabled: boolean) => {
+    if (!projectDirectory) return;
+    
+    const result = await setProjectTaskSetting(projectDirectory, camelCaseKey, 'responseCache', enabled);
+    if (result.isSuccess && onRefresh) {
+      onRefresh();
+    }
+  }, [projectDirectory, onRefresh]);
+
   const handleCopyButtonsChange = useCallback(async (camelCaseKey: keyof TaskSettings, copyButtons: CopyButtonConfig[]) => {
     if (!projectDirectory) return;
 
@@ -749,6 +758,7 @@ export default function TaskModelSettings({
                   onMaxTokensCommit={(value: number[]) => handleMaxTokensCommit(taskSettingsKey, value)}
                   onTranscriptionLanguageChange={(languageCode: string) => handleTranscriptionLanguageChange(taskSettingsKey, languageCode)}
                   onCopyButtonsChange={(copyButtons: CopyButtonConfig[]) => handleCopyButtonsChange(taskSettingsKey, copyButtons)}
+                  onResponseCacheChange={(enabled: boolean) => handleResponseCacheChange(taskSettingsKey, enabled)}
                   onResetToDefault={(settingName) => handleResetToDefault(taskSettingsKey, settingName)}
                   onResetCopyButton={(buttonIndex: number) => handleResetCopyButton(taskSettingsKey, buttonIndex)}
                   isDifferentFromDefault={(settingName) => isDifferentFromDefault(taskSettingsKey, settingName)}
@@ -21,6 +21,7 @@ import {
 import { SystemPromptEditor } from "./system-prompt-editor";
 import { ModelSelector } from "./model-selector";
 import TaskSettingsCard from "./task-settings-card";
+import { Switch } from "@/ui/switch";
 import { CopyButtonListEditor } from "./copy-button-list-editor";
 import { ValidationResult, taskSettingsKeyToTaskType, TRANSCRIPTION_LANGUAGES } from "./shared/task-settings-types";
 import type React from "react";
@@ -39,6 +40,7 @@ interface TaskSettingsEditorProps {
   onMaxTokensCommit: (value: number[]) => void;
   onTranscriptionLanguageChange: (languageCode: string) => void;
   onCopyButtonsChange: (copyButtons: CopyButtonConfig[]) => void;
+  onResponseCacheChange: (enabled: boolean) => void;
   onResetToDefault: (settingName: 'model' | 'maxTokens' | 'temperature' | 'languageCode' | 'copyButtons') => void;
   onResetCopyButton: (buttonIndex: number) => void;
   isDifferentFromDefault: (settingName: 'model' | 'maxTokens' | 'temperature' | 'languageCode' | 'copyButtons') => boolean;
@@ -63,6 +65,7 @@ export function TaskSettingsEditor({
   onMaxTokensCommit,
   onTranscriptionLanguageChange,
   onCopyButtonsChange,
+  onResponseCacheChange,
   onResetToDefault,
   onResetCopyButton,
   isDifferentFromDefault,
@@ -344,6 +347,27 @@ export function TaskSettingsEditor({
           </div>
         )}
         </div>
+        {!isVoiceTranscription && (
+          <div className="flex items-center justify-between gap-4 mt-6">
+            <div className="space-y-1">
+              <Label
+                htmlFor={`response-cache-${taskKey}`}
+                className="text-sm font-medium"
+              >
+                Response Cache
+              </Label>
+              <p className="text-xs text-muted-foreground">
+                Reuse identical responses at a reduced cost. Only applies when temperature is 0.
+              </p>
+            </div>
+            <Switch
+              id={`response-cache-${taskKey}`}
+              checked={settings.responseCache === true}
+              onCheckedChange={onResponseCacheChange}
+              disabled={readOnly}
+            />
+          </div>
+        )}
       </TaskSettingsCard>
       ) : (
         <div className="p-6 bg-muted/30 rounded-lg text-center border border-dashed">
@@ -345,7 +345,11 @@ export function SessionProvider({ children }: SessionProviderProps) {
             // This action already handles window event dispatch
             const source = payload.taskType === 'extended_path_finder'
               ? 'AI Path Finder'
-              : (payload.taskType === 'file_relevance_assessment' ? 'AI Relevance' : 'backend');
+              : payload.taskType === 'file_relevance_assessment'
+                ? 'AI Relevance'
+                : payload.taskType === 'impact_analysis'
+                  ? 'Impact Analysis'
+                  : 'backend';
 
             sessionActions.applyBackendFileUpdate(filtered, source);
 
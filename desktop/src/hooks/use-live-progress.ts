@@ -26,10 +26,13 @@ function calculateJobProgress(job: BackgroundJob, isRunning: boolean): number |
     
     const taskDurations: Record<string, number> = {
       'extended_path_finder': 20000,
+      'impact_analysis': 10000,
       'file_relevance_assessment': 20000,
       'regex_file_filter': 20000,
       'implementation_plan': 90000,
       'implementation_plan_merge': 90000,
+      'implementation_plan_refinement': 90000,
+      'plan_test_generation': 90000,
       'web_search_prompts_generation': 30000,
       'web_search_execution': 120000,
       'text_improvement': 45000,
@@ -84,6 +84,7 @@ export interface TaskModelSettings {
   temperature: number;
   copyButtons?: CopyButtonConfig[];
   allowedModels?: string[];
+  responseCache?: boolean;
 }
 
 /**
@@ -15,6 +15,8 @@ export interface TaskSettings {
   taskRefinement: TaskModelSettings;
   implementationPlan: TaskModelSettings;
   implementationPlanMerge: TaskModelSettings;
+  implementationPlanRefinement?: TaskModelSettings;
+  planTestGeneration?: TaskModelSettings;
   genericLlmStream: TaskModelSettings;
   streaming: TaskModelSettings;
   unknown: TaskModelSettings;
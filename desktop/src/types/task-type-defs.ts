@@ -10,6 +10,8 @@
 export type TaskType =
   | "implementation_plan"
   | "implementation_plan_merge"
+  | "implementation_plan_refinement"
+  | "plan_test_generation"
   | "voice_transcription"
   | "text_improvement"
   | "task_refinement"
@@ -23,6 +25,7 @@ export type TaskType =
   | "local_file_filtering"
   | "file_relevance_assessment"
   | "extended_path_finder"
+  | "impact_analysis"
   | "web_search_prompts_generation"
   | "web_search_execution"
   | "video_analysis"
@@ -34,6 +37,8 @@ export type TaskTypeSupportingSystemPrompts =
   | "text_improvement"
   | "implementation_plan"
   | "implementation_plan_merge"
+  | "implementation_plan_refinement"
+  | "plan_test_generation"
   | "task_refinement"
   | "regex_file_filter"
   | "generic_llm_stream"
@@ -48,6 +53,8 @@ export type TaskTypeSupportingSystemPrompts =
 export const ALL_TASK_TYPES: readonly TaskType[] = [
   "implementation_plan",
   "implementation_plan_merge",
+  "implementation_plan_refinement",
+  "plan_test_generation",
   "voice_transcription",
   "text_improvement",
   "task_refinement",
@@ -60,6 +67,7 @@ export const ALL_TASK_TYPES: readonly TaskType[] = [
   "local_file_filtering",
   "file_relevance_assessment",
   "extended_path_finder",
+  "impact_analysis",
   "web_search_prompts_generation",
   "web_search_execution",
   "video_analysis",
@@ -72,6 +80,8 @@ export const SYSTEM_PROMPT_TASK_TYPES: readonly TaskTypeSupportingSystemPrompts[
   "text_improvement",
   "implementation_plan",
   "implementation_plan_merge",
+  "implementation_plan_refinement",
+  "plan_test_generation",
   "task_refinement",
   "regex_file_filter",
   "generic_llm_stream",
@@ -124,6 +134,20 @@ export const TaskTypeDetails: Record<TaskType, {
     description: "Merge multiple implementation plans into a unified plan",
     defaultProvider: "google"
   },
+  implementation_plan_refinement: {
+    requiresLlm: true,
+    displayName: "Refine Implementation Plans",
+    category: "Development",
+    description: "Revise a generated plan through follow-up conversation",
+    defaultProvider: "openai"
+  },
+  plan_test_generation: {
+    requiresLlm: true,
+    displayName: "Plan Test Generation",
+    category: "Development",
+    description: "Draft tests for each step of a signed-off plan in the project's test style",
+    defaultProvider: "anthropic"
+  },
   voice_transcription: { 
     requiresLlm: true, 
     displayName: "Voice Transcription", 
@@ -212,6 +236,13 @@ export const TaskTypeDetails: Record<TaskType, {
     description: "Comprehensive file discovery with deeper analysis",
     defaultProvider: "google"
   },
+  impact_analysis: {
+    requiresLlm: false,
+    displayName: "Impact Analysis",
+    category: "Workflow Stage",
+    description: "Adds files that import the selected files, found through the project's import graph",
+    apiType: "filesystem"
+  },
   web_search_prompts_generation: {
     requiresLlm: true,
     displayName: 'Web Search Prompts Generation',
@@ -707,7 +707,7 @@ export type TauriInvoke = {
     contentFormat?: string | null;
     createdAt: string;
   }>;
-  "update_implementation_plan_content_command": (args: UpdateImplementationPlanContentCommandArgs) => Promise<void>;
+  "update_implementation_plan_content_command": (args: UpdateImplementationPlanContentCommandArgs) => Promise<import("@/actions/ai/implementation-plan.actions").PlanRevisionSummary>;
   "get_background_job_by_id_command": (args: GetBackgroundJobByIdCommandArgs) => Promise<import("@/types").BackgroundJob | null>;
   "clear_job_history_command": (args: ClearJobHistoryCommandArgs) => Promise<void>;
   "get_all_visible_jobs_command": () => Promise<import("@/types").BackgroundJob[]>;
@@ -10,6 +10,7 @@ export type WorkflowStage =
   | 'REGEX_FILE_FILTER'
   | 'FILE_RELEVANCE_ASSESSMENT'
   | 'EXTENDED_PATH_FINDER'
+  | 'IMPACT_ANALYSIS'
   | 'PATH_CORRECTION'
   | 'WEB_SEARCH_PROMPTS_GENERATION'
   | 'WEB_SEARCH_EXECUTION';
@@ -170,11 +171,19 @@ export interface WorkflowIntermediateData {
   extendedVerifiedPaths: string[];
   extendedUnverifiedPaths: string[];
   extendedCorrectedPaths: string[];
+  impactedFiles?: DependentFile[];
   webSearchPrompts?: string[];
   webSearchResults?: string[];
   workflowCompletionMessage?: string;
 }
 
+// A file added by impact analysis because it depends on a selected file
+export interface DependentFile {
+  path: string;
+  dependsOn: string;
+  reason: string;
+}
+
 // Progress event payload from backend
 export interface WorkflowProgressEvent {
   workflowId: string;
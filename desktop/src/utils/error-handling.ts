@@ -1045,6 +1045,9 @@ export function createUserFriendlyErrorMessage(
             case 'EXTENDED_PATH_FINDER':
               stageGuidance = ' Try refining your search terms or expanding the search scope.';
               break;
+            case 'IMPACT_ANALYSIS':
+              stageGuidance = ' The project\'s imports could not be analyzed to find dependent files.';
+              break;
             case 'PATH_CORRECTION':
               stageGuidance = ' The system encountered issues validating found paths.';
               break;
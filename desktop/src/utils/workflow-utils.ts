@@ -611,6 +611,7 @@ export const WorkflowUtils = {
       'REGEX_FILE_FILTER',
       'FILE_RELEVANCE_ASSESSMENT', 
       'EXTENDED_PATH_FINDER',
+      'IMPACT_ANALYSIS',
       'PATH_CORRECTION',
       'WEB_SEARCH_PROMPTS_GENERATION',
       'WEB_SEARCH_EXECUTION'
@@ -624,7 +625,7 @@ export const WorkflowUtils = {
   calculateProgress(stageJobs: any[]): number {
     if (stageJobs.length === 0) return 0;
     
-    const totalStages = 5; // Updated to match FileFinderWorkflow: ROOT_FOLDER_SELECTION, REGEX_FILE_FILTER, FILE_RELEVANCE_ASSESSMENT, EXTENDED_PATH_FINDER, PATH_CORRECTION
+    const totalStages = 5; // Updated to match FileFinderWorkflow: ROOT_FOLDER_SELECTION, REGEX_FILE_FILTER, FILE_RELEVANCE_ASSESSMENT, EXTENDED_PATH_FINDER, IMPACT_ANALYSIS
     const completedStages = stageJobs.filter(job => job.status === 'completed' || job.status === 'completedByTag').length;
     const runningStages = stageJobs.filter(job => 
       job.status === 'running' || 
@@ -648,6 +649,7 @@ export const WorkflowUtils = {
       'REGEX_FILE_FILTER': 'Filtering Files with Regex',
       'FILE_RELEVANCE_ASSESSMENT': 'AI File Relevance Assessment',
       'EXTENDED_PATH_FINDER': 'Extended Path Finding',
+      'IMPACT_ANALYSIS': 'Impact Analysis',
       'PATH_CORRECTION': 'Path Correction',
       'WEB_SEARCH_PROMPTS_GENERATION': 'Web Search Prompts Generation',
       'WEB_SEARCH_EXECUTION': 'Web Search Execution',
@@ -664,6 +666,7 @@ export const WorkflowUtils = {
       'REGEX_FILE_FILTER': 'Creating regex patterns to filter relevant files',
       'FILE_RELEVANCE_ASSESSMENT': 'Using AI to assess relevance of filtered files to the task',
       'EXTENDED_PATH_FINDER': 'Finding additional relevant paths for comprehensive results',
+      'IMPACT_ANALYSIS': 'Adding files that depend on the selected files',
       'PATH_CORRECTION': 'Path correction and validation',
       'WEB_SEARCH_PROMPTS_GENERATION': 'Generating sophisticated research prompts for web search',
       'WEB_SEARCH_EXECUTION': 'Executing web searches and synthesizing results into actionable insights',
@@ -1463,6 +1463,248 @@ public struct CommandRouter {
         return relayClient.invoke(request: request, timeout: 180.0)
     }
 
+    // MARK: - Plan Step Reviews
+
+    /// Steps of a plan with their status, assignee and review comments
+    public static func planGetStepReviews(jobId: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.getStepReviews",
+            params: ["jobId": jobId]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Set a step's status ("pending", "in_progress", "done", "skipped") and/or assignee
+    public static func planUpdateStep(
+        jobId: String,
+        stepNumber: String,
+        status: String? = nil,
+        assignee: String? = nil,
+        clearAssignee: Bool = false
+    ) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        var params: [String: Any] = [
+            "jobId": jobId,
+            "stepNumber": stepNumber,
+            "clearAssignee": clearAssignee
+        ]
+        if let status = status {
+            params["status"] = status
+        }
+        if let assignee = assignee {
+            params["assignee"] = assignee
+        }
+
+        let request = RpcRequest(
+            method: "plan.updateStep",
+            params: params
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    public static func planAddStepComment(
+        jobId: String,
+        stepNumber: String,
+        body: String
+    ) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.addStepComment",
+            params: [
+                "jobId": jobId,
+                "stepNumber": stepNumber,
+                "body": body
+            ]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    public static func planResolveStepComment(
+        jobId: String,
+        commentId: String,
+        resolved: Bool = true
+    ) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.resolveStepComment",
+            params: [
+                "jobId": jobId,
+                "commentId": commentId,
+                "resolved": resolved
+            ]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Last recorded git progress of a signed-off plan ("7/10 steps touched")
+    public static func planGetProgress(jobId: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.getProgress",
+            params: ["jobId": jobId]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Re-check a signed-off plan's progress against git on the desktop now
+    public static func planCheckProgress(jobId: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.checkProgress",
+            params: ["jobId": jobId]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Fetch a plan's follow-up conversation and its pending refinement job
+    public static func planGetConversation(jobId: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.getConversation",
+            params: ["jobId": jobId]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Ask for a change to a plan; the desktop turns the reply into a new plan revision
+    public static func planRefine(jobId: String, message: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.refine",
+            params: [
+                "jobId": jobId,
+                "message": message
+            ]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Generate a test plan for the signed-off revision of a plan
+    public static func planGenerateTests(jobId: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.generateTests",
+            params: ["jobId": jobId]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    public static func planGetTestPlan(jobId: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.getTestPlan",
+            params: ["jobId": jobId]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    public static func planListExportFormats() -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.listExportFormats",
+            params: [:]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
+    /// Export a plan as issue-tracker import files; the response carries the file contents
+    public static func planExportIssues(jobId: String, format: String) -> AsyncThrowingStream<RpcResponse, Error> {
+        guard let usable = getUsableRelay() else {
+            return AsyncThrowingStream { continuation in
+                continuation.finish(throwing: ServerRelayError.notConnected)
+            }
+        }
+        let relayClient = usable
+
+        let request = RpcRequest(
+            method: "plan.exportIssues",
+            params: [
+                "jobId": jobId,
+                "format": format
+            ]
+        )
+
+        return relayClient.invoke(request: request)
+    }
+
     // MARK: - Account
 
     public static func accountDeleteAccount() -> AsyncThrowingStream<RpcResponse, Error> {
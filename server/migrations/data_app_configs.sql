@@ -43,6 +43,18 @@ VALUES (
         "max_tokens": 35000,
         "temperature": 0.35
       },
+      "implementation_plan_refinement": {
+        "model": "openai/gpt-5.2-2025-12-11",
+        "allowed_models": ["openai/gpt-5.2-2025-12-11", "google/gemini-3-pro-preview", "google/gemini-2.5-pro", "xai/grok-4", "openai/gpt-5.2-pro-2025-12-11", "anthropic/claude-opus-4-5-20251101"],
+        "max_tokens": 23000,
+        "temperature": 0.4
+      },
+      "plan_test_generation": {
+        "model": "anthropic/claude-opus-4-5-20251101",
+        "allowed_models": ["anthropic/claude-opus-4-5-20251101", "anthropic/claude-sonnet-4-5-20250929", "openai/gpt-5.2-2025-12-11", "google/gemini-3-pro-preview", "google/gemini-2.5-pro"],
+        "max_tokens": 20000,
+        "temperature": 0.3
+      },
       "text_improvement": {
         "model": "anthropic/claude-opus-4-5-20251101",
         "allowed_models": ["anthropic/claude-opus-4-5-20251101", "anthropic/claude-sonnet-4-5-20250929", "google/gemini-2.5-pro", "openai/gpt-5.2-2025-12-11"],
@@ -0,0 +1,27 @@
+-- Provider health history
+-- Each server instance periodically snapshots its rolling per-model error rate, latency
+-- percentiles and circuit breaker state so admins can see provider health over time.
+
+CREATE TABLE IF NOT EXISTS provider_health_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    provider_code VARCHAR(50) NOT NULL,
+    model_id VARCHAR(255) NOT NULL,
+    window_secs INTEGER NOT NULL,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    error_count INTEGER NOT NULL DEFAULT 0,
+    latency_p50_ms INTEGER,
+    latency_p95_ms INTEGER,
+    ttft_p50_ms INTEGER,
+    ttft_p95_ms INTEGER,
+    circuit_state VARCHAR(20) NOT NULL CHECK (circuit_state IN ('closed', 'open', 'half_open'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_health_snapshots_captured_at ON provider_health_snapshots(captured_at);
+CREATE INDEX IF NOT EXISTS idx_provider_health_snapshots_provider_captured ON provider_health_snapshots(provider_code, captured_at);
+
+COMMENT ON COLUMN provider_health_snapshots.window_secs IS 'Length of the rolling window the counts and percentiles were computed over.';
+COMMENT ON COLUMN provider_health_snapshots.ttft_p50_ms IS 'Time to first streamed token; NULL when the window had no streaming requests.';
+
+GRANT SELECT, INSERT, DELETE ON provider_health_snapshots TO plantocode;
+GRANT USAGE, SELECT ON SEQUENCE provider_health_snapshots_id_seq TO plantocode;
@@ -0,0 +1,81 @@
+-- Spending threshold notifications and soft limits
+-- Users configure thresholds on balance usage or daily/monthly spend; crossing one sends
+-- email/push/desktop notifications once per period and optionally enables a soft limit
+-- that blocks expensive models while cheaper ones keep working.
+
+CREATE TABLE IF NOT EXISTS user_spending_thresholds (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    threshold_type VARCHAR(30) NOT NULL CHECK (threshold_type IN ('balance_used_percent', 'daily_spend', 'monthly_spend')),
+    threshold_value DECIMAL(12, 4) NOT NULL CHECK (threshold_value > 0),
+    enabled BOOLEAN NOT NULL DEFAULT true,
+
+    -- Notification channels
+    notify_email BOOLEAN NOT NULL DEFAULT true,
+    notify_push BOOLEAN NOT NULL DEFAULT true,
+    notify_desktop BOOLEAN NOT NULL DEFAULT true,
+
+    -- Soft limit: once crossed, only models at or below this output price stay available
+    soft_limit_enabled BOOLEAN NOT NULL DEFAULT false,
+    soft_limit_max_output_per_million DECIMAL(12, 4),
+
+    -- De-duplication: the period key ('2025-01-31', '2025-01', or last purchase id) last notified for
+    last_triggered_at TIMESTAMPTZ,
+    last_triggered_period VARCHAR(64),
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT fk_user_spending_thresholds_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT user_spending_thresholds_user_type_unique UNIQUE (user_id, threshold_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_spending_thresholds_user_id ON user_spending_thresholds(user_id);
+
+COMMENT ON COLUMN user_spending_thresholds.threshold_value IS 'Percentage (0-100) for balance_used_percent, USD amount for daily_spend and monthly_spend.';
+COMMENT ON COLUMN user_spending_thresholds.last_triggered_period IS 'Period key of the last notification, used to send at most one alert per day, month or top-up cycle.';
+
+-- RLS for user_spending_thresholds table
+ALTER TABLE user_spending_thresholds ENABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS "Users can select their own spending thresholds" ON user_spending_thresholds;
+CREATE POLICY "Users can select their own spending thresholds"
+ON user_spending_thresholds FOR SELECT
+TO authenticated
+USING (user_id = get_current_user_id());
+
+DROP POLICY IF EXISTS "Users can insert their own spending thresholds" ON user_spending_thresholds;
+CREATE POLICY "Users can insert their own spending thresholds"
+ON user_spending_thresholds FOR INSERT
+TO authenticated
+WITH CHECK (user_id = get_current_user_id());
+
+DROP POLICY IF EXISTS "Users can update their own spending thresholds" ON user_spending_thresholds;
+CREATE POLICY "Users can update their own spending thresholds"
+ON user_spending_thresholds FOR UPDATE
+TO authenticated
+USING (user_id = get_current_user_id())
+WITH CHECK (user_id = get_current_user_id());
+
+DROP POLICY IF EXISTS "Users can delete their own spending thresholds" ON user_spending_thresholds;
+CREATE POLICY "Users can delete their own spending thresholds"
+ON user_spending_thresholds FOR DELETE
+TO authenticated
+USING (user_id = get_current_user_id());
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON user_spending_thresholds TO authenticated;
+
+-- Update updated_at automatically
+CREATE OR REPLACE FUNCTION update_user_spending_thresholds_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS user_spending_thresholds_updated_at_trigger ON user_spending_thresholds;
+CREATE TRIGGER user_spending_thresholds_updated_at_trigger
+    BEFORE UPDATE ON user_spending_thresholds
+    FOR EACH ROW
+    EXECUTE FUNCTION update_user_spending_thresholds_updated_at();
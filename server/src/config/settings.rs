@@ -15,6 +15,8 @@ pub struct AppSettings {
     pub stripe: StripeConfig,
     pub auth_stores: AuthStoreConfig,
     pub redis: RedisConfig,
+    pub response_cache: ResponseCacheConfig,
+    pub provider_health: ProviderHealthConfig,
     pub website_base_url: String,
     pub cdn_base_url: String,
 }
@@ -30,6 +32,29 @@ pub struct RedisConfig {
     pub url: String,
 }
 
+/// Opt-in cache for deterministic (temperature 0) LLM requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    pub enabled: bool,
+    pub ttl_secs: u64,
+    /// Fraction of the normal cost billed for a cache hit (0 = free, 1 = full price)
+    pub billing_fraction: bigdecimal::BigDecimal,
+    pub max_entry_bytes: usize,
+}
+
+/// Rolling provider/model health tracking and circuit breaker thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealthConfig {
+    pub window_secs: u64,
+    /// Minimum requests in the window before the error rate can open a circuit
+    pub min_requests: u32,
+    pub failure_rate_threshold: f64,
+    pub consecutive_failure_threshold: u32,
+    pub open_duration_secs: u64,
+    pub snapshot_interval_secs: u64,
+    pub history_retention_hours: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     pub url: String,
@@ -248,6 +273,122 @@ impl AppSettings {
 
         let rate_limit_redis_key_prefix = env::var("RATE_LIMIT_REDIS_KEY_PREFIX").ok();
 
+        // LLM response cache configuration
+        let response_cache_enabled = env::var("LLM_RESPONSE_CACHE_ENABLED")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse::<bool>()
+            .map_err(|_| {
+                AppError::Configuration("LLM_RESPONSE_CACHE_ENABLED must be true or false".to_string())
+            })?;
+
+        let response_cache_ttl_secs = env::var("LLM_RESPONSE_CACHE_TTL_SECS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse::<u64>()
+            .map_err(|_| {
+                AppError::Configuration(
+                    "LLM_RESPONSE_CACHE_TTL_SECS must be a valid number".to_string(),
+                )
+            })?;
+
+        let response_cache_billing_fraction = env::var("LLM_RESPONSE_CACHE_BILLING_FRACTION")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse::<bigdecimal::BigDecimal>()
+            .ok()
+            .filter(|fraction| {
+                *fraction >= bigdecimal::BigDecimal::from(0)
+                    && *fraction <= bigdecimal::BigDecimal::from(1)
+            })
+            .ok_or_else(|| {
+                AppError::Configuration(
+                    "LLM_RESPONSE_CACHE_BILLING_FRACTION must be a number between 0 and 1"
+                        .to_string(),
+                )
+            })?;
+
+        let response_cache_max_entry_bytes = env::var("LLM_RESPONSE_CACHE_MAX_ENTRY_BYTES")
+            .unwrap_or_else(|_| "2097152".to_string())
+            .parse::<usize>()
+            .map_err(|_| {
+                AppError::Configuration(
+                    "LLM_RESPONSE_CACHE_MAX_ENTRY_BYTES must be a valid number".to_string(),
+                )
+            })?;
+
+        // Provider health and circuit breaker configuration
+        let provider_health_window_secs = env::var("PROVIDER_HEALTH_WINDOW_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse::<u64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .ok_or_else(|| {
+                AppError::Configuration(
+                    "PROVIDER_HEALTH_WINDOW_SECS must be a positive number".to_string(),
+                )
+            })?;
+
+        let provider_health_min_requests = env::var("PROVIDER_HEALTH_MIN_REQUESTS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse::<u32>()
+            .map_err(|_| {
+                AppError::Configuration(
+                    "PROVIDER_HEALTH_MIN_REQUESTS must be a valid number".to_string(),
+                )
+            })?;
+
+        let provider_health_failure_rate_threshold =
+            env::var("PROVIDER_HEALTH_FAILURE_RATE_THRESHOLD")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| *rate > 0.0 && *rate <= 1.0)
+                .ok_or_else(|| {
+                    AppError::Configuration(
+                        "PROVIDER_HEALTH_FAILURE_RATE_THRESHOLD must be a number in (0, 1]"
+                            .to_string(),
+                    )
+                })?;
+
+        let provider_health_consecutive_failures =
+            env::var("PROVIDER_HEALTH_CONSECUTIVE_FAILURES")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse::<u32>()
+                .map_err(|_| {
+                    AppError::Configuration(
+                        "PROVIDER_HEALTH_CONSECUTIVE_FAILURES must be a valid number".to_string(),
+                    )
+                })?;
+
+        let provider_health_open_duration_secs = env::var("PROVIDER_HEALTH_OPEN_DURATION_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse::<u64>()
+            .map_err(|_| {
+                AppError::Configuration(
+                    "PROVIDER_HEALTH_OPEN_DURATION_SECS must be a valid number".to_string(),
+                )
+            })?;
+
+        let provider_health_snapshot_interval_secs =
+            env::var("PROVIDER_HEALTH_SNAPSHOT_INTERVAL_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| {
+                    AppError::Configuration(
+                        "PROVIDER_HEALTH_SNAPSHOT_INTERVAL_SECS must be a positive number"
+                            .to_string(),
+                    )
+                })?;
+
+        let provider_health_retention_hours = env::var("PROVIDER_HEALTH_RETENTION_HOURS")
+            .unwrap_or_else(|_| "168".to_string())
+            .parse::<i64>()
+            .map_err(|_| {
+                AppError::Configuration(
+                    "PROVIDER_HEALTH_RETENTION_HOURS must be a valid number".to_string(),
+                )
+            })?;
+
         let rate_limit_cleanup_interval_secs = env::var("RATE_LIMIT_CLEANUP_INTERVAL_SECS")
             .ok()
             .and_then(|s| s.parse().ok());
@@ -395,6 +536,21 @@ impl AppSettings {
                 cleanup_interval_secs: auth_store_cleanup_interval_secs,
             },
             redis: RedisConfig { url: redis_url },
+            response_cache: ResponseCacheConfig {
+                enabled: response_cache_enabled,
+                ttl_secs: response_cache_ttl_secs,
+                billing_fraction: response_cache_billing_fraction,
+                max_entry_bytes: response_cache_max_entry_bytes,
+            },
+            provider_health: ProviderHealthConfig {
+                window_secs: provider_health_window_secs,
+                min_requests: provider_health_min_requests,
+                failure_rate_threshold: provider_health_failure_rate_threshold,
+                consecutive_failure_threshold: provider_health_consecutive_failures,
+                open_duration_secs: provider_health_open_duration_secs,
+                snapshot_interval_secs: provider_health_snapshot_interval_secs,
+                history_retention_hours: provider_health_retention_hours,
+            },
             website_base_url,
             cdn_base_url,
         })
@@ -7,10 +7,12 @@ pub mod customer_billing_repository;
 pub mod device_repository;
 pub mod estimation_coefficient_repository;
 pub mod model_repository;
+pub mod provider_health_repository;
 pub mod provider_repository;
 pub mod revoked_token_repository;
 pub mod server_region_repository;
 pub mod settings_repository;
+pub mod spending_threshold_repository;
 pub mod system_prompts_repository;
 pub mod user_credit_repository;
 pub mod user_repository;
@@ -31,10 +33,12 @@ pub use estimation_coefficient_repository::{
     EstimationCoefficient, EstimationCoefficientRepository,
 };
 pub use model_repository::{Model, ModelRepository, ModelWithProvider};
+pub use provider_health_repository::{ProviderHealthRepository, ProviderHealthSnapshotRow};
 pub use provider_repository::{Provider, ProviderRepository, ProviderWithModelCount};
 pub use revoked_token_repository::{RevokedToken, RevokedTokenRepository};
 pub use server_region_repository::ServerRegionRepository;
 pub use settings_repository::{DatabaseAIModelSettings, SettingsRepository, TaskConfig};
+pub use spending_threshold_repository::{SpendingThreshold, SpendingThresholdRepository};
 pub use system_prompts_repository::{DefaultSystemPrompt, SystemPromptsRepository};
 pub use user_credit_repository::{UserCredit, UserCreditRepository};
 pub use user_repository::UserRepository;
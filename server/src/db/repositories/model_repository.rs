@@ -389,6 +389,13 @@ impl ModelRepository {
         }
     }
 
+    /// Resolve a client-supplied model name (internal, provider or native ID) to an internal model ID
+    pub async fn resolve_requested_model_id(&self, requested: &str) -> AppResult<Option<String>> {
+        self.mapping_service
+            .resolve_internal_model_id(requested)
+            .await
+    }
+
     /// Find provider model ID by internal model ID and provider code using mapping service
     #[instrument(skip(self))]
     pub async fn find_provider_model_id(
@@ -0,0 +1,117 @@
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthSnapshotRow {
+    pub captured_at: DateTime<Utc>,
+    pub provider_code: String,
+    pub model_id: String,
+    pub window_secs: i32,
+    pub request_count: i32,
+    pub error_count: i32,
+    pub latency_p50_ms: Option<i32>,
+    pub latency_p95_ms: Option<i32>,
+    pub ttft_p50_ms: Option<i32>,
+    pub ttft_p95_ms: Option<i32>,
+    pub circuit_state: String, // 'closed', 'open', 'half_open'
+}
+
+#[derive(Debug, Clone)]
+pub struct ProviderHealthRepository {
+    db_pool: PgPool,
+}
+
+impl ProviderHealthRepository {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn get_pool(&self) -> &PgPool {
+        &self.db_pool
+    }
+
+    pub async fn insert_snapshots(
+        &self,
+        snapshots: &[ProviderHealthSnapshotRow],
+    ) -> Result<(), AppError> {
+        if snapshots.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+
+        for snapshot in snapshots {
+            sqlx::query(
+                r#"
+                INSERT INTO provider_health_snapshots
+                (captured_at, provider_code, model_id, window_secs, request_count, error_count,
+                 latency_p50_ms, latency_p95_ms, ttft_p50_ms, ttft_p95_ms, circuit_state)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(snapshot.captured_at)
+            .bind(&snapshot.provider_code)
+            .bind(&snapshot.model_id)
+            .bind(snapshot.window_secs)
+            .bind(snapshot.request_count)
+            .bind(snapshot.error_count)
+            .bind(snapshot.latency_p50_ms)
+            .bind(snapshot.latency_p95_ms)
+            .bind(snapshot.ttft_p50_ms)
+            .bind(snapshot.ttft_p95_ms)
+            .bind(&snapshot.circuit_state)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::Database(format!("Failed to insert provider health snapshot: {}", e))
+            })?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            AppError::Database(format!("Failed to commit provider health snapshots: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    pub async fn get_history(
+        &self,
+        since: DateTime<Utc>,
+        provider_code: Option<&str>,
+    ) -> Result<Vec<ProviderHealthSnapshotRow>, AppError> {
+        sqlx::query_as::<_, ProviderHealthSnapshotRow>(
+            r#"
+            SELECT captured_at, provider_code, model_id, window_secs, request_count, error_count,
+                   latency_p50_ms, latency_p95_ms, ttft_p50_ms, ttft_p95_ms, circuit_state
+            FROM provider_health_snapshots
+            WHERE captured_at >= $1
+              AND ($2::text IS NULL OR provider_code = $2)
+            ORDER BY captured_at, provider_code, model_id
+            "#,
+        )
+        .bind(since)
+        .bind(provider_code)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load provider health history: {}", e)))
+    }
+
+    pub async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM provider_health_snapshots WHERE captured_at < $1")
+            .bind(cutoff)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                AppError::Database(format!("Failed to prune provider health snapshots: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+}
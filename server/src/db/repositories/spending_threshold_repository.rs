@@ -0,0 +1,226 @@
+use crate::error::AppError;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingThreshold {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub threshold_type: String, // 'balance_used_percent', 'daily_spend', 'monthly_spend'
+    pub threshold_value: BigDecimal,
+    pub enabled: bool,
+    pub notify_email: bool,
+    pub notify_push: bool,
+    pub notify_desktop: bool,
+    pub soft_limit_enabled: bool,
+    pub soft_limit_max_output_per_million: Option<BigDecimal>,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    pub last_triggered_period: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertSpendingThreshold {
+    pub threshold_type: String,
+    pub threshold_value: BigDecimal,
+    pub enabled: bool,
+    pub notify_email: bool,
+    pub notify_push: bool,
+    pub notify_desktop: bool,
+    pub soft_limit_enabled: bool,
+    pub soft_limit_max_output_per_million: Option<BigDecimal>,
+}
+
+/// Raw spend figures a threshold is evaluated against
+#[derive(Debug, Clone)]
+pub struct SpendingSnapshot {
+    pub daily_spend: BigDecimal,
+    pub monthly_spend: BigDecimal,
+    pub spent_since_last_purchase: BigDecimal,
+    pub current_balance: BigDecimal,
+    pub last_purchase_id: Option<Uuid>,
+}
+
+#[derive(Debug)]
+pub struct SpendingThresholdRepository {
+    db_pool: PgPool,
+}
+
+impl SpendingThresholdRepository {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn get_pool(&self) -> &PgPool {
+        &self.db_pool
+    }
+
+    pub async fn list_for_user_with_executor(
+        &self,
+        user_id: &Uuid,
+        executor: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Vec<SpendingThreshold>, AppError> {
+        sqlx::query_as::<_, SpendingThreshold>(
+            r#"
+            SELECT id, user_id, threshold_type, threshold_value, enabled,
+                   notify_email, notify_push, notify_desktop,
+                   soft_limit_enabled, soft_limit_max_output_per_million,
+                   last_triggered_at, last_triggered_period, created_at, updated_at
+            FROM user_spending_thresholds
+            WHERE user_id = $1
+            ORDER BY threshold_type
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&mut **executor)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to list spending thresholds: {}", e)))
+    }
+
+    pub async fn upsert_with_executor(
+        &self,
+        user_id: &Uuid,
+        threshold: &UpsertSpendingThreshold,
+        executor: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<SpendingThreshold, AppError> {
+        // Changing the configured value re-arms the alert for the current period
+        sqlx::query_as::<_, SpendingThreshold>(
+            r#"
+            INSERT INTO user_spending_thresholds
+            (user_id, threshold_type, threshold_value, enabled, notify_email, notify_push,
+             notify_desktop, soft_limit_enabled, soft_limit_max_output_per_million)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (user_id, threshold_type) DO UPDATE
+            SET threshold_value = EXCLUDED.threshold_value,
+                enabled = EXCLUDED.enabled,
+                notify_email = EXCLUDED.notify_email,
+                notify_push = EXCLUDED.notify_push,
+                notify_desktop = EXCLUDED.notify_desktop,
+                soft_limit_enabled = EXCLUDED.soft_limit_enabled,
+                soft_limit_max_output_per_million = EXCLUDED.soft_limit_max_output_per_million,
+                last_triggered_period = CASE
+                    WHEN user_spending_thresholds.threshold_value = EXCLUDED.threshold_value
+                    THEN user_spending_thresholds.last_triggered_period
+                    ELSE NULL
+                END
+            RETURNING id, user_id, threshold_type, threshold_value, enabled,
+                      notify_email, notify_push, notify_desktop,
+                      soft_limit_enabled, soft_limit_max_output_per_million,
+                      last_triggered_at, last_triggered_period, created_at, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(&threshold.threshold_type)
+        .bind(&threshold.threshold_value)
+        .bind(threshold.enabled)
+        .bind(threshold.notify_email)
+        .bind(threshold.notify_push)
+        .bind(threshold.notify_desktop)
+        .bind(threshold.soft_limit_enabled)
+        .bind(&threshold.soft_limit_max_output_per_million)
+        .fetch_one(&mut **executor)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save spending threshold: {}", e)))
+    }
+
+    pub async fn delete_with_executor(
+        &self,
+        user_id: &Uuid,
+        threshold_type: &str,
+        executor: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM user_spending_thresholds WHERE user_id = $1 AND threshold_type = $2",
+        )
+        .bind(user_id)
+        .bind(threshold_type)
+        .execute(&mut **executor)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to delete spending threshold: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records the period a threshold fired for. Returns false if another request
+    /// already claimed this period, so each period notifies exactly once.
+    pub async fn mark_triggered_with_executor(
+        &self,
+        threshold_id: &Uuid,
+        period_key: &str,
+        executor: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_spending_thresholds
+            SET last_triggered_at = NOW(), last_triggered_period = $2
+            WHERE id = $1
+              AND (last_triggered_period IS NULL OR last_triggered_period <> $2)
+            "#,
+        )
+        .bind(threshold_id)
+        .bind(period_key)
+        .execute(&mut **executor)
+        .await
+        .map_err(|e| {
+            AppError::Database(format!(
+                "Failed to mark spending threshold triggered: {}",
+                e
+            ))
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Aggregates today's, this month's and since-last-purchase spend plus the current balance
+    pub async fn get_spending_snapshot_with_executor(
+        &self,
+        user_id: &Uuid,
+        now: DateTime<Utc>,
+        executor: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<SpendingSnapshot, AppError> {
+        let row = sqlx::query(
+            r#"
+            WITH last_purchase AS (
+                SELECT id, created_at
+                FROM credit_transactions
+                WHERE user_id = $1 AND transaction_type = 'purchase'
+                ORDER BY created_at DESC
+                LIMIT 1
+            )
+            SELECT
+                COALESCE(SUM(au.cost) FILTER (WHERE au.timestamp >= date_trunc('day', $2::timestamptz)), 0)::numeric AS daily_spend,
+                COALESCE(SUM(au.cost) FILTER (WHERE au.timestamp >= date_trunc('month', $2::timestamptz)), 0)::numeric AS monthly_spend,
+                COALESCE(SUM(au.cost) FILTER (
+                    WHERE au.timestamp >= COALESCE((SELECT created_at FROM last_purchase), '-infinity'::timestamptz)
+                ), 0)::numeric AS spent_since_last_purchase,
+                (SELECT id FROM last_purchase) AS last_purchase_id,
+                COALESCE((SELECT balance + free_credit_balance FROM user_credits WHERE user_id = $1), 0)::numeric AS current_balance
+            FROM api_usage au
+            WHERE au.user_id = $1
+              AND au.status <> 'failed'
+              AND au.timestamp >= LEAST(
+                  date_trunc('month', $2::timestamptz),
+                  COALESCE((SELECT created_at FROM last_purchase), '-infinity'::timestamptz)
+              )
+            "#,
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_one(&mut **executor)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to compute spending snapshot: {}", e)))?;
+
+        Ok(SpendingSnapshot {
+            daily_spend: row.try_get("daily_spend")?,
+            monthly_spend: row.try_get("monthly_spend")?,
+            spent_since_last_purchase: row.try_get("spent_since_last_purchase")?,
+            current_balance: row.try_get("current_balance")?,
+            last_purchase_id: row.try_get("last_purchase_id")?,
+        })
+    }
+}
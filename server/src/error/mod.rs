@@ -43,6 +43,7 @@ pub enum AppError {
     SpendingLimitExceeded(String),
     CheckoutError(String),
     VideoAnalysisError(String),
+    ServiceUnavailable(String),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -96,6 +97,7 @@ impl fmt::Display for AppError {
             AppError::SpendingLimitExceeded(e) => write!(f, "Spending limit exceeded: {}", e),
             AppError::CheckoutError(e) => write!(f, "Checkout error: {}", e),
             AppError::VideoAnalysisError(e) => write!(f, "Video analysis error: {}", e),
+            AppError::ServiceUnavailable(e) => write!(f, "Service unavailable: {}", e),
         }
     }
 }
@@ -165,6 +167,9 @@ impl ResponseError for AppError {
             }
             AppError::CheckoutError(_) => (StatusCode::PAYMENT_REQUIRED, "checkout_error"),
             AppError::VideoAnalysisError(_) => (StatusCode::BAD_REQUEST, "video_analysis_error"),
+            AppError::ServiceUnavailable(_) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "service_unavailable")
+            }
         };
 
         let error_response = ErrorResponse {
@@ -216,6 +221,7 @@ impl ResponseError for AppError {
             AppError::SpendingLimitExceeded(_) => StatusCode::PAYMENT_REQUIRED,
             AppError::CheckoutError(_) => StatusCode::PAYMENT_REQUIRED,
             AppError::VideoAnalysisError(_) => StatusCode::BAD_REQUEST,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 }
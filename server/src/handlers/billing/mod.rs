@@ -6,7 +6,9 @@ pub mod credit_handlers;
 pub mod dashboard_handler;
 pub mod invoice_handlers;
 pub mod payment_handlers;
+pub mod spending_threshold_handlers;
 pub mod usage_debug_handlers;
+pub mod usage_export_handlers;
 pub mod webhook_handlers;
 
 // Re-export handlers for easier importing
@@ -16,5 +18,7 @@ pub use credit_handlers::*;
 pub use dashboard_handler::*;
 pub use invoice_handlers::*;
 pub use payment_handlers::*;
+pub use spending_threshold_handlers::*;
 pub use usage_debug_handlers::*;
+pub use usage_export_handlers::*;
 pub use webhook_handlers::*;
@@ -0,0 +1,106 @@
+use crate::db::repositories::spending_threshold_repository::UpsertSpendingThreshold;
+use crate::error::AppError;
+use crate::models::AuthenticatedUser;
+use crate::models::billing::UpdateSpendingThresholdRequest;
+use crate::services::billing_service::BillingService;
+use crate::services::spending_alert_service::SpendingAlertService;
+use actix_web::{HttpResponse, web};
+use bigdecimal::BigDecimal;
+use log::{info, warn};
+use std::sync::Arc;
+
+// ========================================
+// SPENDING THRESHOLD HANDLERS
+// ========================================
+
+fn spending_alert_service(
+    billing_service: &BillingService,
+) -> Result<&Arc<SpendingAlertService>, AppError> {
+    billing_service
+        .get_spending_alert_service()
+        .ok_or_else(|| AppError::Configuration("Spending alerts are not configured".to_string()))
+}
+
+fn parse_decimal(value: &str, field: &str) -> Result<BigDecimal, AppError> {
+    BigDecimal::parse_bytes(value.trim().as_bytes(), 10)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid {} format", field)))
+}
+
+/// List the spending thresholds configured for the user
+pub async fn get_spending_thresholds_handler(
+    user: web::ReqData<AuthenticatedUser>,
+    billing_service: web::Data<Arc<BillingService>>,
+) -> Result<HttpResponse, AppError> {
+    let thresholds = spending_alert_service(&billing_service)?
+        .get_thresholds(&user.user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(thresholds))
+}
+
+/// Create or update a spending threshold for the user
+pub async fn update_spending_threshold_handler(
+    user: web::ReqData<AuthenticatedUser>,
+    request: web::Json<UpdateSpendingThresholdRequest>,
+    billing_service: web::Data<Arc<BillingService>>,
+) -> Result<HttpResponse, AppError> {
+    let request = request.into_inner();
+    let service = spending_alert_service(&billing_service)?;
+
+    let soft_limit_max_output_per_million = request
+        .soft_limit_max_output_per_million
+        .as_deref()
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| parse_decimal(v, "soft limit price cap"))
+        .transpose()?;
+
+    let saved = service
+        .upsert_threshold(
+            &user.user_id,
+            UpsertSpendingThreshold {
+                threshold_type: request.threshold_type,
+                threshold_value: parse_decimal(&request.threshold_value, "threshold value")?,
+                enabled: request.enabled,
+                notify_email: request.notify_email,
+                notify_push: request.notify_push,
+                notify_desktop: request.notify_desktop,
+                soft_limit_enabled: request.soft_limit_enabled,
+                soft_limit_max_output_per_million,
+            },
+        )
+        .await?;
+
+    // A threshold set below current spend should alert right away rather than on the next charge
+    if saved.enabled {
+        if let Err(e) = service.evaluate_thresholds(&user.user_id).await {
+            warn!(
+                "Failed to evaluate spending thresholds after update for user {}: {}",
+                user.user_id, e
+            );
+        }
+    }
+
+    info!(
+        "Successfully updated {} spending threshold for user: {}",
+        saved.threshold_type, user.user_id
+    );
+    Ok(HttpResponse::Ok().json(saved))
+}
+
+/// Remove a spending threshold by type
+pub async fn delete_spending_threshold_handler(
+    user: web::ReqData<AuthenticatedUser>,
+    path: web::Path<String>,
+    billing_service: web::Data<Arc<BillingService>>,
+) -> Result<HttpResponse, AppError> {
+    let threshold_type = path.into_inner();
+    spending_alert_service(&billing_service)?
+        .delete_threshold(&user.user_id, &threshold_type)
+        .await?;
+
+    info!(
+        "Deleted {} spending threshold for user: {}",
+        threshold_type, user.user_id
+    );
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,61 @@
+use crate::db::connection::DatabasePools;
+use crate::error::AppError;
+use crate::models::AuthenticatedUser;
+use crate::models::billing::UsageExportQuery;
+use crate::services::usage_export_service::{UsageExportFilters, UsageExportService};
+use actix_web::{HttpResponse, http::header, web};
+use futures_util::StreamExt;
+use log::info;
+
+fn normalize_filter(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Stream API usage and credit transactions for a date range as CSV or JSON Lines
+pub async fn export_usage_handler(
+    user: web::ReqData<AuthenticatedUser>,
+    query: web::Query<UsageExportQuery>,
+    db_pools: web::Data<DatabasePools>,
+) -> Result<HttpResponse, AppError> {
+    let query = query.into_inner();
+    let filters = UsageExportFilters {
+        start_date: query.start_date,
+        end_date: query.end_date,
+        model: normalize_filter(&query.model),
+        provider: normalize_filter(&query.provider),
+        task_type: normalize_filter(&query.task_type),
+        include_transactions: query.include_transactions.unwrap_or(true),
+    };
+    filters.validate()?;
+
+    info!(
+        "Starting {:?} usage export for user {} ({} to {})",
+        query.format, user.user_id, filters.start_date, filters.end_date
+    );
+
+    let filename = format!(
+        "plantocode-usage-{}-{}.{}",
+        filters.start_date.format("%Y%m%d"),
+        filters.end_date.format("%Y%m%d"),
+        query.format.file_extension()
+    );
+
+    let export_service = UsageExportService::new(db_pools.user_pool.clone());
+    let body_stream = export_service
+        .stream_export(user.user_id, filters, query.format)
+        .map(|chunk| chunk.map_err(actix_web::Error::from));
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, query.format.content_type()))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        ))
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(body_stream))
+}
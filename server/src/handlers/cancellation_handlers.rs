@@ -1,5 +1,6 @@
 use actix_web::{HttpResponse, web};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
@@ -27,7 +28,7 @@ pub async fn cancel_request_handler(
     payload: web::Json<CancelRequestPayload>,
     user: web::ReqData<AuthenticatedUser>,
     request_tracker: web::Data<RequestTracker>,
-    billing_service: web::Data<BillingService>,
+    billing_service: web::Data<Arc<BillingService>>,
     app_settings: web::Data<AppSettings>,
 ) -> Result<HttpResponse, AppError> {
     let user_id = user.user_id;
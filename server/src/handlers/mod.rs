@@ -9,7 +9,9 @@ pub mod device_handlers;
 pub mod health;
 pub mod model_handlers;
 pub mod notification_handlers;
+pub mod openai_compat_handlers;
 pub mod provider_handlers;
+pub mod provider_health_handlers;
 pub mod provider_transformers;
 mod proxy;
 pub mod proxy_handlers;
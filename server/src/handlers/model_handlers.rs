@@ -2,6 +2,7 @@ use actix_web::{HttpResponse, web};
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use std::sync::Arc;
 use tracing::{info, instrument};
 
 use crate::clients::usage_extractor::ProviderUsage;
@@ -9,6 +10,7 @@ use crate::db::repositories::{ModelRepository, ModelWithProvider};
 use crate::error::AppError;
 use crate::models::model_pricing::ModelPricing;
 use crate::models::runtime_config::AppState;
+use crate::services::provider_health_service::{CircuitState, HealthStatus, ProviderHealthService};
 use crate::utils;
 
 #[derive(Debug, Serialize)]
@@ -24,6 +26,39 @@ pub struct ModelResponse {
     pub status: String,
     pub description: Option<String>,
     pub provider: ProviderInfo,
+    pub health: ModelHealthInfo,
+}
+
+/// Live health of a model as seen by this server's proxy
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelHealthInfo {
+    pub status: HealthStatus,
+    pub circuit_state: CircuitState,
+    pub error_rate: f64,
+}
+
+impl Default for ModelHealthInfo {
+    fn default() -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            circuit_state: CircuitState::Closed,
+            error_rate: 0.0,
+        }
+    }
+}
+
+impl ModelResponse {
+    fn with_health(mut self, provider_health: &ProviderHealthService) -> Self {
+        if let Some(health) = provider_health.model_health(&self.id) {
+            self.health = ModelHealthInfo {
+                status: health.status,
+                circuit_state: health.circuit_state,
+                error_rate: health.stats.error_rate,
+            };
+        }
+        self
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -72,17 +107,24 @@ impl From<ModelWithProvider> for ModelResponse {
                 capabilities: model.provider_capabilities,
                 status: model.provider_status,
             },
+            health: ModelHealthInfo::default(),
         }
     }
 }
 
 /// Get all active models with provider information
-#[instrument(skip(app_state))]
-pub async fn get_all_models(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+#[instrument(skip(app_state, provider_health))]
+pub async fn get_all_models(
+    app_state: web::Data<AppState>,
+    provider_health: web::Data<Arc<ProviderHealthService>>,
+) -> Result<HttpResponse, AppError> {
     info!("API request: Get all models with provider information");
 
     let models = app_state.model_repository.get_all_with_providers().await?;
-    let response: Vec<ModelResponse> = models.into_iter().map(Into::into).collect();
+    let response: Vec<ModelResponse> = models
+        .into_iter()
+        .map(|model| ModelResponse::from(model).with_health(&provider_health))
+        .collect();
 
     info!(
         "Returning {} models with provider information",
@@ -92,9 +134,10 @@ pub async fn get_all_models(app_state: web::Data<AppState>) -> Result<HttpRespon
 }
 
 /// Get model by ID with provider information
-#[instrument(skip(app_state))]
+#[instrument(skip(app_state, provider_health))]
 pub async fn get_model_by_id(
     app_state: web::Data<AppState>,
+    provider_health: web::Data<Arc<ProviderHealthService>>,
     path: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     let model_id = path.into_inner();
@@ -107,7 +150,7 @@ pub async fn get_model_by_id(
 
     match model {
         Some(model) => {
-            let response: ModelResponse = model.into();
+            let response = ModelResponse::from(model).with_health(&provider_health);
             Ok(HttpResponse::Ok().json(response))
         }
         None => {
@@ -121,9 +164,10 @@ pub async fn get_model_by_id(
 }
 
 /// Get models by provider code
-#[instrument(skip(app_state))]
+#[instrument(skip(app_state, provider_health))]
 pub async fn get_models_by_provider(
     app_state: web::Data<AppState>,
+    provider_health: web::Data<Arc<ProviderHealthService>>,
     path: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     let provider_code = path.into_inner();
@@ -133,7 +177,10 @@ pub async fn get_models_by_provider(
         .model_repository
         .get_by_provider_code(&provider_code)
         .await?;
-    let response: Vec<ModelResponse> = models.into_iter().map(Into::into).collect();
+    let response: Vec<ModelResponse> = models
+        .into_iter()
+        .map(|model| ModelResponse::from(model).with_health(&provider_health))
+        .collect();
 
     info!(
         "Returning {} models for provider: {}",
@@ -144,16 +191,20 @@ pub async fn get_models_by_provider(
 }
 
 /// Get models by type (text, transcription, etc.)
-#[instrument(skip(app_state))]
+#[instrument(skip(app_state, provider_health))]
 pub async fn get_models_by_type(
     app_state: web::Data<AppState>,
+    provider_health: web::Data<Arc<ProviderHealthService>>,
     path: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     let model_type = path.into_inner();
     info!("API request: Get models by type: {}", model_type);
 
     let models = app_state.model_repository.get_by_type(&model_type).await?;
-    let response: Vec<ModelResponse> = models.into_iter().map(Into::into).collect();
+    let response: Vec<ModelResponse> = models
+        .into_iter()
+        .map(|model| ModelResponse::from(model).with_health(&provider_health))
+        .collect();
 
     info!(
         "Returning {} models of type: {}",
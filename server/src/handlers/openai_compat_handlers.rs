@@ -0,0 +1,489 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError, web};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::config::settings::AppSettings;
+use crate::db::repositories::model_repository::{ModelRepository, ModelWithProvider};
+use crate::error::AppError;
+use crate::handlers::cancellation_handlers::{CancelRequestPayload, cancel_request_handler};
+use crate::handlers::proxy::{LlmCompletionRequest, router};
+use crate::models::AuthenticatedUser;
+use crate::services::billing_service::BillingService;
+use crate::services::request_tracker::RequestTracker;
+use crate::streaming::openai_compat::{
+    OpenAiStreamTranslator, openai_usage_from_standardized, translate_sse_body,
+};
+
+/// Response header carrying the request ID used for cancellation
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Debug, Deserialize)]
+pub struct StreamOptions {
+    #[serde(default)]
+    pub include_usage: bool,
+}
+
+/// OpenAI chat completion request. Unknown fields (tools, top_p, stop, ...) are
+/// forwarded to the provider unchanged.
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<Value>,
+    #[serde(default)]
+    pub stream: bool,
+    pub stream_options: Option<StreamOptions>,
+    pub max_tokens: Option<u32>,
+    pub max_completion_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    /// End-user identifier; accepted for compatibility but not forwarded
+    pub user: Option<String>,
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+impl ChatCompletionRequest {
+    fn into_llm_request(self, internal_model_id: String) -> LlmCompletionRequest {
+        LlmCompletionRequest {
+            model: internal_model_id,
+            messages: self.messages,
+            stream: Some(self.stream),
+            max_tokens: self.max_completion_tokens.or(self.max_tokens),
+            temperature: self.temperature,
+            task_type: None,
+            response_cache: None,
+            other: self.other,
+        }
+    }
+}
+
+/// OpenAI-compatible chat completions (`POST /v1/chat/completions`)
+///
+/// Runs through the same routing, billing, response cache and request tracking as
+/// `/api/llm/chat/completions`; only the wire format differs.
+#[instrument(skip(
+    payload,
+    user,
+    app_settings,
+    billing_service,
+    model_repository,
+    request_tracker
+))]
+pub async fn chat_completions(
+    payload: web::Json<ChatCompletionRequest>,
+    user: web::ReqData<AuthenticatedUser>,
+    app_settings: web::Data<AppSettings>,
+    billing_service: web::Data<Arc<BillingService>>,
+    model_repository: web::Data<ModelRepository>,
+    request_tracker: web::Data<RequestTracker>,
+) -> HttpResponse {
+    let request = payload.into_inner();
+    if request.messages.is_empty() {
+        return openai_error(&AppError::BadRequest(
+            "'messages' must contain at least one message".to_string(),
+        ));
+    }
+
+    let internal_model_id = match model_repository
+        .resolve_requested_model_id(&request.model)
+        .await
+    {
+        Ok(Some(id)) => id,
+        Ok(None) => {
+            return openai_error_body(
+                StatusCode::NOT_FOUND,
+                &format!("The model `{}` does not exist", request.model),
+                "invalid_request_error",
+                Some("model_not_found"),
+            );
+        }
+        Err(e) => return openai_error(&e),
+    };
+
+    let is_streaming = request.stream;
+    let include_usage = request
+        .stream_options
+        .as_ref()
+        .is_some_and(|options| options.include_usage);
+    let request_id = uuid::Uuid::new_v4().to_string();
+
+    info!(
+        "OpenAI-compatible chat completion for user {} (request_id: {}, model: {} -> {}, stream: {})",
+        user.user_id, request_id, request.model, internal_model_id, is_streaming
+    );
+
+    let result = router::route_chat_completion(
+        request.into_llm_request(internal_model_id.clone()),
+        user.user_id,
+        request_id.clone(),
+        app_settings,
+        billing_service,
+        model_repository,
+        request_tracker,
+    )
+    .await;
+
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => return openai_error(&e),
+    };
+
+    if !response.status().is_success() {
+        return translate_error_response(response).await;
+    }
+
+    if is_streaming {
+        let (_, body) = response.into_parts();
+        let translator = OpenAiStreamTranslator::new(&internal_model_id, include_usage);
+        return HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header(("cache-control", "no-cache"))
+            .insert_header((REQUEST_ID_HEADER, request_id))
+            .streaming(translate_sse_body(body, translator));
+    }
+
+    translate_json_response(response, &internal_model_id, &request_id).await
+}
+
+/// Cancel an in-flight completion by the ID returned in the `x-request-id` header
+/// (`POST /v1/chat/completions/{request_id}/cancel`)
+pub async fn cancel_chat_completion(
+    path: web::Path<String>,
+    user: web::ReqData<AuthenticatedUser>,
+    request_tracker: web::Data<RequestTracker>,
+    billing_service: web::Data<Arc<BillingService>>,
+    app_settings: web::Data<AppSettings>,
+) -> HttpResponse {
+    let payload = web::Json(CancelRequestPayload {
+        request_id: path.into_inner(),
+    });
+    cancel_request_handler(
+        payload,
+        user,
+        request_tracker,
+        billing_service,
+        app_settings,
+    )
+    .await
+    .unwrap_or_else(|e| openai_error(&e))
+}
+
+/// List active models in OpenAI's list format (`GET /v1/models`)
+#[instrument(skip(model_repository))]
+pub async fn list_models(model_repository: web::Data<ModelRepository>) -> HttpResponse {
+    match model_repository.get_all_with_providers().await {
+        Ok(models) => {
+            let data: Vec<Value> = models
+                .iter()
+                .filter(|model| model.model_type == "text")
+                .map(openai_model_object)
+                .collect();
+            HttpResponse::Ok().json(json!({ "object": "list", "data": data }))
+        }
+        Err(e) => openai_error(&e),
+    }
+}
+
+/// Retrieve a single model (`GET /v1/models/{model}`); accepts any ID form `/v1/chat/completions` does
+#[instrument(skip(model_repository))]
+pub async fn retrieve_model(
+    path: web::Path<String>,
+    model_repository: web::Data<ModelRepository>,
+) -> HttpResponse {
+    let requested = path.into_inner();
+    let model = match model_repository
+        .resolve_requested_model_id(&requested)
+        .await
+    {
+        Ok(Some(id)) => model_repository.find_by_id_with_provider(&id).await,
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match model {
+        Ok(Some(model)) => HttpResponse::Ok().json(openai_model_object(&model)),
+        Ok(None) => openai_error_body(
+            StatusCode::NOT_FOUND,
+            &format!("The model `{}` does not exist", requested),
+            "invalid_request_error",
+            Some("model_not_found"),
+        ),
+        Err(e) => openai_error(&e),
+    }
+}
+
+fn openai_model_object(model: &ModelWithProvider) -> Value {
+    json!({
+        "id": model.id,
+        "object": "model",
+        "created": model.created_at.timestamp(),
+        "owned_by": model.provider_code,
+    })
+}
+
+/// Rewrite a successful non-streaming response into OpenAI's `chat.completion` shape
+async fn translate_json_response(
+    response: HttpResponse,
+    model_id: &str,
+    request_id: &str,
+) -> HttpResponse {
+    let (head, body) = response.into_parts();
+    let bytes = match actix_web::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return openai_error(&AppError::Internal(format!(
+                "Failed to read completion response: {}",
+                e
+            )));
+        }
+    };
+
+    let mut completion: Value = match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!(
+                "Completion response for request {} is not JSON: {}",
+                request_id, e
+            );
+            return openai_error(&AppError::External(
+                "Provider returned an invalid completion response".to_string(),
+            ));
+        }
+    };
+
+    if let Some(obj) = completion.as_object_mut() {
+        let usage = obj.get("usage").and_then(openai_usage_from_standardized);
+        if let Some(usage) = usage {
+            obj.insert("usage".to_string(), usage);
+        }
+        obj.insert("object".to_string(), json!("chat.completion"));
+        obj.insert("model".to_string(), json!(model_id));
+        obj.entry("created")
+            .or_insert_with(|| json!(chrono::Utc::now().timestamp()));
+    }
+
+    let mut builder = HttpResponse::build(head.status());
+    if let Some(cache_status) = head.headers().get("x-response-cache") {
+        builder.insert_header(("x-response-cache", cache_status.clone()));
+    }
+    builder
+        .insert_header((REQUEST_ID_HEADER, request_id.to_string()))
+        .json(completion)
+}
+
+/// Re-wrap a non-2xx proxy response in OpenAI's error envelope
+async fn translate_error_response(response: HttpResponse) -> HttpResponse {
+    let status = response.status();
+    let bytes = actix_web::body::to_bytes(response.into_body())
+        .await
+        .unwrap_or_default();
+    let body: Value = serde_json::from_slice(&bytes).unwrap_or_default();
+
+    let message = body
+        .get("message")
+        .or_else(|| body.pointer("/error/message"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let text = String::from_utf8_lossy(&bytes);
+            if text.trim().is_empty() {
+                status
+                    .canonical_reason()
+                    .unwrap_or("Request failed")
+                    .to_string()
+            } else {
+                text.into_owned()
+            }
+        });
+
+    let (error_type, code) = openai_error_kind(status);
+    openai_error_body(status, &message, error_type, code)
+}
+
+fn openai_error(error: &AppError) -> HttpResponse {
+    let status = error.status_code();
+    let message = if status.is_server_error()
+        && !matches!(
+            error,
+            AppError::External(_) | AppError::ServiceUnavailable(_)
+        ) {
+        // Internal details stay in the logs
+        "The server had an error while processing your request".to_string()
+    } else {
+        error.to_string()
+    };
+    let (error_type, code) = openai_error_kind(status);
+    openai_error_body(status, &message, error_type, code)
+}
+
+/// OpenAI error `type` and `code` for an HTTP status
+fn openai_error_kind(status: StatusCode) -> (&'static str, Option<&'static str>) {
+    match status {
+        StatusCode::UNAUTHORIZED => ("invalid_request_error", Some("invalid_api_key")),
+        StatusCode::PAYMENT_REQUIRED => ("insufficient_quota", Some("insufficient_quota")),
+        StatusCode::FORBIDDEN => ("invalid_request_error", Some("permission_denied")),
+        StatusCode::NOT_FOUND => ("invalid_request_error", None),
+        StatusCode::TOO_MANY_REQUESTS => ("requests", Some("rate_limit_exceeded")),
+        s if s.is_client_error() => ("invalid_request_error", None),
+        _ => ("server_error", None),
+    }
+}
+
+fn openai_error_body(
+    status: StatusCode,
+    message: &str,
+    error_type: &str,
+    code: Option<&str>,
+) -> HttpResponse {
+    HttpResponse::build(status).json(json!({
+        "error": {
+            "message": message,
+            "type": error_type,
+            "param": null,
+            "code": code
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::*;
+    use crate::db::connection::DatabasePools;
+    use crate::routes::configure_openai_compat_routes;
+    use actix_web::dev::Service;
+    use actix_web::{App, HttpMessage, test};
+    use sqlx::postgres::PgPoolOptions;
+    use uuid::Uuid;
+
+    fn test_settings() -> AppSettings {
+        let rate_limit = RateLimitConfig {
+            window_ms: 60_000,
+            max_requests: 100,
+            redis_key_prefix: None,
+            cleanup_interval_secs: None,
+        };
+        AppSettings {
+            app: AppConfig {
+                name: "plantocode".to_string(),
+                environment: "test".to_string(),
+            },
+            database: DatabaseConfig {
+                url: "postgres://localhost/unused".to_string(),
+            },
+            server: ServerConfig {
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                cors_origins: vec!["*".to_string()],
+                url: "http://127.0.0.1:8080".to_string(),
+                auth0_callback_url: String::new(),
+                auth0_logged_out_url: String::new(),
+                client_request_timeout_secs: 60,
+            },
+            api_keys: ApiKeysConfig {
+                openrouter_api_key: None,
+                openai_api_key: None,
+                xai_api_key: None,
+                anthropic_api_key: None,
+                google_api_keys: None,
+                auth0_domain: String::new(),
+                auth0_api_audience: String::new(),
+                auth0_server_client_id: None,
+                auth0_server_client_secret: None,
+            },
+            auth: AuthConfig {
+                jwt_secret: String::new(),
+                token_duration_days: 30,
+                featurebase_sso_secret: String::new(),
+                refresh_token_encryption_key: String::new(),
+            },
+            rate_limit: rate_limit.clone(),
+            account_creation_rate_limit: rate_limit,
+            billing: BillingConfig {},
+            stripe: StripeConfig {
+                secret_key: String::new(),
+                publishable_key: String::new(),
+                webhook_secret: String::new(),
+                success_url: String::new(),
+                cancel_url: String::new(),
+                portal_return_url: String::new(),
+            },
+            auth_stores: AuthStoreConfig {
+                polling_store_expiry_mins: 10,
+                auth0_state_store_expiry_mins: 10,
+                cleanup_interval_secs: 60,
+            },
+            redis: RedisConfig {
+                url: "redis://localhost".to_string(),
+            },
+            response_cache: ResponseCacheConfig {
+                enabled: false,
+                ttl_secs: 0,
+                billing_fraction: bigdecimal::BigDecimal::from(0),
+                max_entry_bytes: 0,
+            },
+            provider_health: ProviderHealthConfig {
+                window_secs: 60,
+                min_requests: 10,
+                failure_rate_threshold: 0.5,
+                consecutive_failure_threshold: 5,
+                open_duration_secs: 60,
+                snapshot_interval_secs: 60,
+                history_retention_hours: 1,
+            },
+            website_base_url: String::new(),
+            cdn_base_url: String::new(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn cancel_route_resolves_app_data_registered_like_main() {
+        // Lazy pools never connect; the unknown request is rejected before billing runs
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let db_pools = DatabasePools {
+            system_pool: pool.clone(),
+            user_pool: pool,
+        };
+        let app_settings = test_settings();
+        let billing_service = Arc::new(BillingService::new(db_pools, app_settings.clone()));
+        let user = AuthenticatedUser {
+            user_id: Uuid::new_v4(),
+            email: "test@example.com".to_string(),
+            role: "user".to_string(),
+            device_id: None,
+            authenticated_via_api_key: true,
+            api_key_id: None,
+            api_key_label: None,
+        };
+
+        // Same registrations as the HttpServer factory in main.rs
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(billing_service.clone()))
+                .app_data(web::Data::new(RequestTracker::new()))
+                .app_data(web::Data::new(app_settings.clone()))
+                .service(
+                    web::scope("/v1")
+                        .wrap_fn(move |req, srv| {
+                            req.extensions_mut().insert(user.clone());
+                            srv.call(req)
+                        })
+                        .configure(configure_openai_compat_routes),
+                ),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/v1/chat/completions/unknown-request/cancel")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+
+        // A missing app data type would surface as a 500 before the handler ran
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,74 @@
+use actix_web::{HttpResponse, web};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::db::repositories::ProviderHealthSnapshotRow;
+use crate::error::AppError;
+use crate::models::AuthenticatedUser;
+use crate::services::provider_health_service::{ProviderHealthService, ProviderHealthSnapshot};
+
+/// History is capped at 30 days to keep responses bounded
+const MAX_HISTORY_HOURS: i64 = 24 * 30;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthQuery {
+    pub hours: Option<i64>,
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthResponse {
+    pub window_secs: u64,
+    pub since: DateTime<Utc>,
+    pub current: Vec<ProviderHealthSnapshot>,
+    pub history: Vec<ProviderHealthSnapshotRow>,
+}
+
+/// Admin view of provider and model health: live rolling stats plus persisted history
+#[instrument(skip(user, provider_health))]
+pub async fn get_provider_health(
+    user: web::ReqData<AuthenticatedUser>,
+    query: web::Query<ProviderHealthQuery>,
+    provider_health: web::Data<Arc<ProviderHealthService>>,
+) -> Result<HttpResponse, AppError> {
+    if user.role != "admin" {
+        return Err(AppError::Forbidden("Admin access required".to_string()));
+    }
+
+    let hours = query.hours.unwrap_or(24);
+    if !(1..=MAX_HISTORY_HOURS).contains(&hours) {
+        return Err(AppError::BadRequest(format!(
+            "hours must be between 1 and {}",
+            MAX_HISTORY_HOURS
+        )));
+    }
+    let since = Utc::now() - chrono::Duration::hours(hours);
+    let provider = query.provider.as_deref();
+
+    let current = provider_health
+        .snapshot()
+        .into_iter()
+        .filter(|p| provider.is_none_or(|code| p.provider_code == code))
+        .collect();
+
+    let history = match provider_health.repository() {
+        Some(repository) => repository.get_history(since, provider).await?,
+        None => Vec::new(),
+    };
+
+    info!(
+        "Returning provider health with {} history points over {}h",
+        history.len(),
+        hours
+    );
+    Ok(HttpResponse::Ok().json(ProviderHealthResponse {
+        window_secs: provider_health.window_secs(),
+        since,
+        current,
+        history,
+    }))
+}
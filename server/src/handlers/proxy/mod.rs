@@ -2,6 +2,7 @@ pub mod types;
 pub mod utils;
 pub mod router;
 pub mod providers;
+pub mod response_cache;
 pub mod specialized;
 
 // Re-export for internal use
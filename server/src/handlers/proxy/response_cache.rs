@@ -0,0 +1,210 @@
+use super::utils::create_standardized_usage_response;
+use crate::db::repositories::api_usage_repository::ApiUsageEntryDto;
+use crate::db::repositories::model_repository::ModelWithProvider;
+use crate::error::AppError;
+use crate::services::billing_service::BillingService;
+use crate::services::request_tracker::RequestTracker;
+use crate::services::response_cache_service::{
+    CachedBody, CachedCompletion, CachedUsage, ResponseCacheService,
+};
+use crate::streaming::cache_replay::{CacheReplayTransformer, replay_byte_stream};
+use crate::streaming::stream_handler::ModernStreamHandler;
+use actix_web::{HttpResponse, web};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+const RESPONSE_CACHE_HEADER: &str = "x-response-cache";
+
+/// Serve a cache hit: bill the original usage at the configured fraction and replay
+/// the stored response. Returns None when the entry does not match the request shape.
+pub(crate) async fn serve_cached_response(
+    cached: CachedCompletion,
+    cache: &ResponseCacheService,
+    model: &ModelWithProvider,
+    user_id: Uuid,
+    request_id: String,
+    task_type: Option<String>,
+    is_streaming: bool,
+    billing_service: Arc<BillingService>,
+    request_tracker: web::Data<RequestTracker>,
+) -> Option<Result<HttpResponse, AppError>> {
+    let shape_matches = matches!(
+        (&cached.body, is_streaming),
+        (CachedBody::Stream { .. }, true) | (CachedBody::Json { .. }, false)
+    );
+    if !shape_matches || cached.model_id != model.id {
+        warn!(
+            "Ignoring response cache entry for request {} that does not match the request",
+            request_id
+        );
+        return None;
+    }
+
+    info!(
+        "Serving request {} for user {} from response cache (cached at {})",
+        request_id, user_id, cached.created_at
+    );
+
+    Some(
+        replay_cached_response(
+            cached,
+            cache,
+            model,
+            user_id,
+            request_id,
+            task_type,
+            billing_service,
+            request_tracker,
+        )
+        .await,
+    )
+}
+
+async fn replay_cached_response(
+    cached: CachedCompletion,
+    cache: &ResponseCacheService,
+    model: &ModelWithProvider,
+    user_id: Uuid,
+    request_id: String,
+    task_type: Option<String>,
+    billing_service: Arc<BillingService>,
+    request_tracker: web::Data<RequestTracker>,
+) -> Result<HttpResponse, AppError> {
+    let billing_fraction = cache.billing_fraction().clone();
+    let usage = cached.usage.to_provider_usage(&model.id);
+
+    // Record the replay in api_usage with the exact token counts of the original call
+    let api_usage_entry = ApiUsageEntryDto {
+        user_id,
+        service_name: model.id.clone(),
+        tokens_input: usage.prompt_tokens as i64,
+        tokens_output: usage.completion_tokens as i64,
+        cache_write_tokens: usage.cache_write_tokens as i64,
+        cache_read_tokens: usage.cache_read_tokens as i64,
+        request_id: Some(request_id.clone()),
+        metadata: Some(serde_json::json!({
+            "billing_type": "initial_estimate",
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "response_cache": "hit",
+            "task_type": task_type.as_deref().unwrap_or("general")
+        })),
+        provider_reported_cost: None,
+    };
+    billing_service.initiate_api_charge(api_usage_entry).await?;
+
+    match cached.body {
+        CachedBody::Stream { chunks } => {
+            let replay_stream = replay_byte_stream(&chunks, &cached.usage)?;
+
+            let cancellation_token = tokio_util::sync::CancellationToken::new();
+            request_tracker
+                .track_request_with_cancellation(
+                    request_id.clone(),
+                    user_id,
+                    model.provider_code.clone(),
+                    true, // is_streaming
+                    cancellation_token.clone(),
+                )
+                .await;
+
+            let modern_handler = ModernStreamHandler::new(
+                replay_stream,
+                Box::new(CacheReplayTransformer::new(&model.id)),
+                model.clone(),
+                user_id,
+                billing_service,
+                request_id,
+                cancellation_token,
+                request_tracker,
+            )
+            .with_cache_replay(billing_fraction);
+
+            use actix_web::Responder;
+            let sse_stream = modern_handler.into_sse_stream();
+            let req = actix_web::test::TestRequest::default().to_http_request();
+            let mut response = sse_stream.respond_to(&req);
+            response.headers_mut().insert(
+                actix_web::http::header::HeaderName::from_static(RESPONSE_CACHE_HEADER),
+                actix_web::http::header::HeaderValue::from_static("hit"),
+            );
+            Ok(response)
+        }
+        CachedBody::Json { mut response } => {
+            let metadata = serde_json::json!({
+                "response_cache": "hit",
+                "response_cache_billing_fraction": billing_fraction.to_string()
+            });
+            let (api_usage_record, _user_credit) = billing_service
+                .finalize_api_charge_with_cost_multiplier(
+                    &request_id,
+                    &user_id,
+                    usage.clone(),
+                    Some(metadata),
+                    &billing_fraction,
+                )
+                .await?;
+
+            if let Some(obj) = response.as_object_mut() {
+                let usage_response =
+                    create_standardized_usage_response(&usage, &api_usage_record.cost)?;
+                obj.insert("usage".to_string(), usage_response);
+            }
+
+            Ok(HttpResponse::Ok()
+                .insert_header((RESPONSE_CACHE_HEADER, "hit"))
+                .json(response))
+        }
+    }
+}
+
+/// Store a successful non-streaming response registered as a cache miss. Streaming
+/// responses are captured by `ModernStreamHandler` once the stream completes.
+pub(crate) async fn capture_json_response(
+    cache: Arc<ResponseCacheService>,
+    request_id: &str,
+    model_id: &str,
+    response: Result<HttpResponse, AppError>,
+) -> Result<HttpResponse, AppError> {
+    let Some(cache_key) = cache.take_capture(request_id) else {
+        return response;
+    };
+    let response = response?;
+    if !response.status().is_success() {
+        return Ok(response);
+    }
+
+    let (head, body) = response.into_parts();
+    let bytes = actix_web::body::to_bytes(body)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to buffer response body: {}", e)))?;
+
+    match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(mut response_value) => {
+            let usage = response_value
+                .get("usage")
+                .and_then(CachedUsage::from_response_usage);
+            if let (Some(usage), Some(obj)) = (usage, response_value.as_object_mut()) {
+                // Usage and cost are recomputed on every replay
+                obj.remove("usage");
+                let completion = CachedCompletion {
+                    model_id: model_id.to_string(),
+                    usage,
+                    body: CachedBody::Json {
+                        response: response_value,
+                    },
+                    created_at: chrono::Utc::now(),
+                };
+                tokio::spawn(async move {
+                    cache.store(&cache_key, &completion).await;
+                });
+            }
+        }
+        Err(e) => warn!(
+            "Not caching response for request {}: body is not JSON ({})",
+            request_id, e
+        ),
+    }
+
+    Ok(head.set_body(bytes).map_into_boxed_body())
+}
@@ -1,4 +1,5 @@
 use super::providers::{anthropic, google, openai, openrouter, xai};
+use super::response_cache;
 use super::types::LlmCompletionRequest;
 use super::utils::calculate_input_tokens;
 use crate::config::settings::AppSettings;
@@ -7,6 +8,7 @@ use crate::db::repositories::model_repository::ModelRepository;
 use crate::error::AppError;
 use crate::models::AuthenticatedUser;
 use crate::services::billing_service::BillingService;
+use crate::services::provider_health_service::RequestOutcome;
 use crate::services::request_tracker::RequestTracker;
 use crate::utils::vision_capabilities::model_supports_vision;
 use crate::utils::vision_normalizer::{
@@ -18,6 +20,7 @@ use crate::utils::vision_validation::{
 use actix_web::{HttpResponse, web};
 use std::sync::Arc;
 use tracing::{error, info, instrument};
+use uuid::Uuid;
 
 /// AI proxy handler for intelligent model routing
 /// Routes requests to appropriate AI providers based on model configuration
@@ -43,6 +46,30 @@ pub async fn llm_chat_completion_handler(
     // Generate unique request ID for tracking
     let request_id = uuid::Uuid::new_v4().to_string();
 
+    route_chat_completion(
+        payload.into_inner(),
+        user_id,
+        request_id,
+        app_settings,
+        billing_service,
+        model_repository,
+        request_tracker,
+    )
+    .await
+}
+
+/// Route a chat completion for an authenticated user under a caller-assigned request ID.
+/// Shared by `/api/llm/chat/completions` and the OpenAI-compatible `/v1` endpoint so both
+/// go through the same billing, caching, health tracking and cancellation.
+pub(crate) async fn route_chat_completion(
+    payload: LlmCompletionRequest,
+    user_id: Uuid,
+    request_id: String,
+    app_settings: web::Data<AppSettings>,
+    billing_service: web::Data<Arc<BillingService>>,
+    model_repository: web::Data<ModelRepository>,
+    request_tracker: web::Data<RequestTracker>,
+) -> Result<HttpResponse, AppError> {
     info!(
         "Processing LLM chat completion request for user: {} (request_id: {})",
         user_id, request_id
@@ -177,6 +204,34 @@ pub async fn llm_chat_completion_handler(
         }
     }
 
+    // Check if request is streaming
+    let is_streaming = payload.stream.unwrap_or(false);
+
+    // Identical deterministic requests can be replayed from the per-user response cache
+    let cache_service = billing_service.get_response_cache_service().cloned();
+    let cache_key = cache_service
+        .as_ref()
+        .and_then(|cache| cache.cache_key_for(&user_id, &payload));
+    if let (Some(cache), Some(cache_key)) = (&cache_service, &cache_key) {
+        if let Some(cached) = cache.get(cache_key).await {
+            if let Some(result) = response_cache::serve_cached_response(
+                cached,
+                cache,
+                &model_with_provider,
+                user_id,
+                request_id.clone(),
+                payload.task_type.clone(),
+                is_streaming,
+                billing_service.get_ref().clone(),
+                request_tracker.clone(),
+            )
+            .await
+            {
+                return result;
+            }
+        }
+    }
+
     info!(
         "Routing to provider: {} for model: {}",
         model_with_provider.provider_code, model_with_provider.name
@@ -222,8 +277,23 @@ pub async fn llm_chat_completion_handler(
         provider_reported_cost: None,
     };
 
+    // Models with an open circuit are rejected before any charge is made
+    let provider_health = request_tracker.provider_health().cloned();
+    if let Some(provider_health) = &provider_health {
+        provider_health.begin_request(
+            &request_id,
+            &model_with_provider.provider_code,
+            &model_with_provider.id,
+        )?;
+    }
+
     // Initiate API charge with estimated usage
-    billing_service.initiate_api_charge(api_usage_entry).await?;
+    if let Err(e) = billing_service.initiate_api_charge(api_usage_entry).await {
+        if let Some(provider_health) = &provider_health {
+            provider_health.finish_request(&request_id, RequestOutcome::Ignored);
+        }
+        return Err(e);
+    }
 
     info!(
         "Initiated API charge for request {}: estimated {} input tokens, {} output tokens",
@@ -239,8 +309,10 @@ pub async fn llm_chat_completion_handler(
         })
         .unwrap_or(false);
 
-    // Check if request is streaming
-    let is_streaming = payload.stream.unwrap_or(false);
+    // On a cache miss, remember where the completed response should be stored
+    if let (Some(cache), Some(cache_key)) = (&cache_service, cache_key) {
+        cache.register_capture(&request_id, cache_key);
+    }
 
     // Track the request in the request tracker
     request_tracker
@@ -253,22 +325,32 @@ pub async fn llm_chat_completion_handler(
         .await;
 
     // Extract payload for different handler types
-    let payload_inner = payload.into_inner();
-    let payload_value = serde_json::to_value(&payload_inner)?;
+    let payload_value = serde_json::to_value(&payload)?;
 
     // Get model with mapping information for the specific provider
     let model_with_mapping = model_repository
         .find_by_id_with_mapping(&model_id, &model_with_provider.provider_code)
-        .await?
-        .ok_or_else(|| {
-            AppError::NotFound(format!(
-                "Model mapping not found for '{}' with provider '{}'",
-                model_id, model_with_provider.provider_code
-            ))
-        })?;
+        .await
+        .and_then(|mapping| {
+            mapping.ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Model mapping not found for '{}' with provider '{}'",
+                    model_id, model_with_provider.provider_code
+                ))
+            })
+        });
+    let model_with_mapping = match model_with_mapping {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            if let Some(provider_health) = &provider_health {
+                provider_health.finish_request(&request_id, RequestOutcome::Ignored);
+            }
+            return Err(e);
+        }
+    };
 
     // Route to appropriate provider based on provider_code
-    match model_with_provider.provider_code.as_str() {
+    let response = match model_with_provider.provider_code.as_str() {
         "openai" => {
             if is_streaming {
                 openai::handle_openai_streaming_request(
@@ -450,5 +532,39 @@ pub async fn llm_chat_completion_handler(
                 model_with_provider.provider_code
             )))
         }
+    };
+
+    if let Some(provider_health) = &provider_health {
+        match &response {
+            Err(e) => provider_health.finish_request(&request_id, RequestOutcome::from_error(e)),
+            Ok(resp) if resp.status().is_server_error() => {
+                provider_health.finish_request(&request_id, RequestOutcome::Failure)
+            }
+            Ok(_) if !is_streaming => {
+                provider_health.finish_request(&request_id, RequestOutcome::Success)
+            }
+            // Stream outcomes and time to first token are reported by ModernStreamHandler
+            Ok(_) => {}
+        }
+    }
+
+    match cache_service {
+        Some(cache) if !is_streaming => {
+            response_cache::capture_json_response(
+                cache,
+                &request_id,
+                &model_with_provider.id,
+                response,
+            )
+            .await
+        }
+        Some(cache) => {
+            // Streams that failed before reaching ModernStreamHandler leave nothing to capture
+            if response.is_err() {
+                cache.take_capture(&request_id);
+            }
+            response
+        }
+        None => response,
     }
 }
@@ -10,6 +10,9 @@ pub struct LlmCompletionRequest {
     pub max_tokens: Option<u32>,
     pub temperature: Option<f32>,
     pub task_type: Option<String>,
+    /// Opt in to the server-side response cache; never forwarded to providers
+    #[serde(default, skip_serializing)]
+    pub response_cache: Option<bool>,
     #[serde(flatten)]
     pub other: HashMap<String, Value>,
 }
@@ -31,16 +31,19 @@ use crate::db::connection::{DatabasePools, create_dual_pools, verify_connection}
 use crate::db::repositories::consent_repository::ConsentRepository;
 use crate::db::{
     ApiUsageRepository, CustomerBillingRepository, DeviceRepository, ModelRepository,
-    SettingsRepository, SystemPromptsRepository, UserRepository,
+    ProviderHealthRepository, SettingsRepository, SystemPromptsRepository, UserRepository,
 };
 use crate::handlers::{auth0_handlers, config_handlers, region_handlers};
 use crate::middleware::{
     create_ip_rate_limiter, create_rate_limit_storage,
     create_strict_rate_limiter, create_user_rate_limiter, start_memory_store_cleanup_task,
-    unified_auth_middleware,
+    api_key_auth_middleware, unified_auth_middleware,
 };
 use crate::models::runtime_config::{AppState, RuntimeConfigCache};
-use crate::routes::{configure_public_auth_routes, configure_routes, configure_webhook_routes};
+use crate::routes::{
+    configure_openai_compat_routes, configure_public_auth_routes, configure_routes,
+    configure_webhook_routes,
+};
 use crate::services::audit_service::AuditService;
 use crate::services::auth::jwt;
 use crate::services::auth::oauth::Auth0OAuthService;
@@ -50,7 +53,10 @@ use crate::services::credit_service::CreditService;
 use crate::services::device_connection_manager::DeviceConnectionManager;
 use crate::services::reconciliation_service::ReconciliationService;
 use crate::services::relay_session_store::RelaySessionStore;
+use crate::services::provider_health_service::ProviderHealthService;
 use crate::services::request_tracker::RequestTracker;
+use crate::services::spending_alert_service::SpendingAlertService;
+use crate::services::email_notification_service::EmailNotificationService;
 use crate::services::apns_service::ApnsServiceBuilder;
 
 /// Validates AI model configurations at startup to catch misconfigurations early
@@ -336,8 +342,16 @@ async fn main() -> std::io::Result<()> {
         log::info!("Rate limit memory store cleanup task started.");
     }
 
+    // Provider health tracking and per-model circuit breakers for the proxy path
+    let provider_health_service = Arc::new(
+        ProviderHealthService::new(app_settings.provider_health.clone())
+            .with_repository(ProviderHealthRepository::new(db_pools.system_pool.clone())),
+    );
+    provider_health_service.clone().spawn_snapshot_task();
+    log::info!("Provider health service initialized with snapshot persistence");
+
     // Initialize request tracker
-    let request_tracker = RequestTracker::new();
+    let request_tracker = RequestTracker::new().with_provider_health(provider_health_service.clone());
     log::info!("Request tracker initialized");
 
     // Start request tracker cleanup task
@@ -357,6 +371,56 @@ async fn main() -> std::io::Result<()> {
         });
     }
 
+    // Initialize APNs service for push notifications
+    let apns_service = {
+        let apns_team_id = env::var("APNS_TEAM_ID").ok();
+        let apns_key_id = env::var("APNS_KEY_ID").ok();
+        let apns_private_key = env::var("APNS_PRIVATE_KEY").ok();
+        let apns_bundle_id = env::var("APNS_BUNDLE_ID").ok();
+        let apns_production = env::var("APNS_PRODUCTION")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+
+        // Only initialize if all required APNs credentials are present
+        if let (Some(team_id), Some(key_id), Some(private_key), Some(bundle_id)) =
+            (apns_team_id, apns_key_id, apns_private_key, apns_bundle_id)
+        {
+            let device_repo_for_apns = Arc::new(DeviceRepository::new(Arc::new(db_pools.system_pool.clone())));
+
+            match ApnsServiceBuilder::new()
+                .device_repository(device_repo_for_apns)
+                .team_id(team_id)
+                .key_id(key_id)
+                .private_key(private_key)
+                .bundle_id(bundle_id)
+                .production(apns_production)
+                .build()
+            {
+                Ok(service) => {
+                    log::info!(
+                        "APNs service initialized successfully (environment: {})",
+                        if apns_production { "production" } else { "sandbox" }
+                    );
+                    Some(Arc::new(service))
+                }
+                Err(e) => {
+                    log::error!("Failed to initialize APNs service: {}", e);
+                    log::warn!("Server will start without push notification support");
+                    None
+                }
+            }
+        } else {
+            log::info!("APNs credentials not configured - push notifications disabled");
+            log::info!("Set APNS_TEAM_ID, APNS_KEY_ID, APNS_PRIVATE_KEY, and APNS_BUNDLE_ID to enable");
+            None
+        }
+    };
+
+    // Initialize DeviceConnectionManager ONCE for all workers; created before billing so
+    // spending alerts can reach connected desktops
+    let device_connection_manager = DeviceConnectionManager::new();
+
     // Initialize billing service
     let mut billing_service = BillingService::new(db_pools.clone(), app_settings.clone());
 
@@ -370,6 +434,31 @@ async fn main() -> std::io::Result<()> {
         std::process::exit(1);
     }
 
+    // Spending threshold notifications (email, push, device-link) and soft limits
+    {
+        let mut spending_alert_service = SpendingAlertService::new(
+            db_pools.clone(),
+            billing_service.get_audit_service().clone(),
+        )
+        .with_device_connection_manager(device_connection_manager.clone());
+
+        match EmailNotificationService::new(db_pools.clone()) {
+            Ok(email_service) => {
+                spending_alert_service =
+                    spending_alert_service.with_email_service(Arc::new(email_service));
+            }
+            Err(e) => {
+                log::warn!("Email notifications disabled for spending alerts: {}", e);
+            }
+        }
+
+        if let Some(apns) = &apns_service {
+            spending_alert_service = spending_alert_service.with_apns_service(apns.clone());
+        }
+
+        billing_service.set_spending_alert_service(Arc::new(spending_alert_service));
+    }
+
     // Wrap billing service in Arc for sharing across handlers
     let billing_service = Arc::new(billing_service);
 
@@ -408,52 +497,6 @@ async fn main() -> std::io::Result<()> {
         }
     };
 
-    // Initialize APNs service for push notifications
-    let apns_service = {
-        let apns_team_id = env::var("APNS_TEAM_ID").ok();
-        let apns_key_id = env::var("APNS_KEY_ID").ok();
-        let apns_private_key = env::var("APNS_PRIVATE_KEY").ok();
-        let apns_bundle_id = env::var("APNS_BUNDLE_ID").ok();
-        let apns_production = env::var("APNS_PRODUCTION")
-            .unwrap_or_else(|_| "false".to_string())
-            .parse::<bool>()
-            .unwrap_or(false);
-
-        // Only initialize if all required APNs credentials are present
-        if let (Some(team_id), Some(key_id), Some(private_key), Some(bundle_id)) =
-            (apns_team_id, apns_key_id, apns_private_key, apns_bundle_id)
-        {
-            let device_repo_for_apns = Arc::new(DeviceRepository::new(Arc::new(db_pools.system_pool.clone())));
-
-            match ApnsServiceBuilder::new()
-                .device_repository(device_repo_for_apns)
-                .team_id(team_id)
-                .key_id(key_id)
-                .private_key(private_key)
-                .bundle_id(bundle_id)
-                .production(apns_production)
-                .build()
-            {
-                Ok(service) => {
-                    log::info!(
-                        "APNs service initialized successfully (environment: {})",
-                        if apns_production { "production" } else { "sandbox" }
-                    );
-                    Some(Arc::new(service))
-                }
-                Err(e) => {
-                    log::error!("Failed to initialize APNs service: {}", e);
-                    log::warn!("Server will start without push notification support");
-                    None
-                }
-            }
-        } else {
-            log::info!("APNs credentials not configured - push notifications disabled");
-            log::info!("Set APNS_TEAM_ID, APNS_KEY_ID, APNS_PRIVATE_KEY, and APNS_BUNDLE_ID to enable");
-            None
-        }
-    };
-
     // Log one-time initialization messages before server creation
     info!("Starting HTTP server factory with shared state initialization");
     info!("Server configured with keep-alive: 5 minutes");
@@ -476,8 +519,8 @@ async fn main() -> std::io::Result<()> {
     let _cleanup_handle = relay_store.clone().start_cleanup_task();
     log::info!("RelaySessionStore initialized with 24h TTL and 5min cleanup interval");
 
-    // Initialize DeviceConnectionManager ONCE for all workers (shared via Arc)
-    let device_connection_manager = web::Data::new(DeviceConnectionManager::new());
+    // DeviceConnectionManager is shared across all workers (internally Arc-backed)
+    let device_connection_manager = web::Data::new(device_connection_manager);
     log::info!("DeviceConnectionManager initialized and shared across all workers");
 
     let server = HttpServer::new(move || {
@@ -622,6 +665,7 @@ async fn main() -> std::io::Result<()> {
             .app_data(auth0_oauth_service)
             .app_data(web::Data::new(billing_service.clone()))
             .app_data(web::Data::new(request_tracker.clone()))
+            .app_data(web::Data::new(provider_health_service.clone()))
             .app_data(app_state.clone())
             .app_data(polling_store.clone())
             .app_data(auth0_state_store.clone())
@@ -740,6 +784,19 @@ async fn main() -> std::io::Result<()> {
                 .configure(|cfg| configure_routes(cfg, api_key_rate_limiter.clone()))
         );
 
+        // OpenAI-compatible API for third-party clients and SDKs
+        // API keys only (Authorization: Bearer <key> is read as an API key); shares the
+        // API key rate limiter and the /api/llm billing and request tracking
+        app = app.service(
+            web::scope("/v1")
+                .wrap(api_key_rate_limiter.clone())
+                .wrap(api_key_auth_middleware(
+                    db_pools.user_pool.clone(),
+                    db_pools.system_pool.clone(),
+                ))
+                .configure(configure_openai_compat_routes),
+        );
+
         app
             // Public webhook routes with IP-based rate limiting (no authentication)
             .service(
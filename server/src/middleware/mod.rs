@@ -8,4 +8,4 @@ pub use rate_limiting::{
     RateLimitMiddleware, create_ip_rate_limiter, create_rate_limit_storage,
     create_strict_rate_limiter, create_user_rate_limiter, start_memory_store_cleanup_task,
 };
-pub use unified_auth::unified_auth_middleware;
+pub use unified_auth::{api_key_auth_middleware, unified_auth_middleware};
@@ -454,8 +454,65 @@ async fn unified_validator(req: ServiceRequest) -> Result<ServiceRequest, (Error
     }
 }
 
+/// OpenAI-style error body for API-key-only routes, so standard SDKs surface the message
+fn openai_auth_error(message: &str) -> Error {
+    Error::from(ErrorUnauthorized(
+        serde_json::json!({
+            "error": {
+                "message": message,
+                "type": "invalid_request_error",
+                "param": null,
+                "code": "invalid_api_key"
+            }
+        })
+        .to_string(),
+    ))
+}
+
+/// Validator for OpenAI-compatible routes: only API keys are accepted, and
+/// `Authorization: Bearer <key>` is treated as an API key the way OpenAI SDKs send it
+async fn api_key_validator(req: ServiceRequest) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    let path = req.path().to_string();
+
+    if req.method() == actix_web::http::Method::OPTIONS {
+        debug!("Skipping authentication for OPTIONS request to: {}", path);
+        return Ok(req);
+    }
+
+    let Some(api_key) = extract_api_key(&req).or_else(|| extract_bearer_token(&req)) else {
+        error!("No API key found for path: {}", path);
+        return Err((
+            openai_auth_error("Missing API key. Provide it as Authorization: Bearer <key>"),
+            req,
+        ));
+    };
+
+    match authenticate_via_api_key(&req, &api_key).await {
+        Ok(()) => {
+            info!("API key authentication successful for path: {}", path);
+            Ok(req)
+        }
+        Err(e) => {
+            error!("API key authentication failed for path {}: {}", path, e);
+            let error_message = match e {
+                AppError::Auth(msg) => msg,
+                _ => "API key authentication failed".to_string(),
+            };
+            Err((openai_auth_error(&error_message), req))
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AuthMode {
+    Unified,
+    ApiKeyOnly,
+}
+
 // Middleware transform implementation
-pub struct UnifiedAuthMiddleware;
+pub struct UnifiedAuthMiddleware {
+    mode: AuthMode,
+}
 
 impl<S, B> Transform<S, ServiceRequest> for UnifiedAuthMiddleware
 where
@@ -471,13 +528,15 @@ where
 
     fn new_transform(&self, service: S) -> Self::Future {
         ok(UnifiedAuthMiddlewareService {
-            service: std::rc::Rc::new(service)
+            service: std::rc::Rc::new(service),
+            mode: self.mode,
         })
     }
 }
 
 pub struct UnifiedAuthMiddlewareService<S> {
     service: std::rc::Rc<S>,
+    mode: AuthMode,
 }
 
 impl<S, B> Service<ServiceRequest> for UnifiedAuthMiddlewareService<S>
@@ -496,9 +555,14 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = self.service.clone();
+        let mode = self.mode;
 
         Box::pin(async move {
-            let req = unified_validator(req).await.map_err(|(err, _req)| err)?;
+            let validated = match mode {
+                AuthMode::Unified => unified_validator(req).await,
+                AuthMode::ApiKeyOnly => api_key_validator(req).await,
+            };
+            let req = validated.map_err(|(err, _req)| err)?;
             let res = service.call(req).await?;
             Ok(res)
         })
@@ -516,6 +580,27 @@ pub fn unified_auth_middleware(
     user_pool: sqlx::PgPool,
     system_pool: sqlx::PgPool,
 ) -> UnifiedAuthMiddleware {
+    init_auth_state(user_pool, system_pool);
+    UnifiedAuthMiddleware {
+        mode: AuthMode::Unified,
+    }
+}
+
+/// Middleware factory function for API-key-only authentication
+///
+/// Used by the OpenAI-compatible `/v1` routes. Accepts X-API-Key, Authorization: ApiKey
+/// and Authorization: Bearer (interpreted as an API key); JWTs are rejected.
+pub fn api_key_auth_middleware(
+    user_pool: sqlx::PgPool,
+    system_pool: sqlx::PgPool,
+) -> UnifiedAuthMiddleware {
+    init_auth_state(user_pool, system_pool);
+    UnifiedAuthMiddleware {
+        mode: AuthMode::ApiKeyOnly,
+    }
+}
+
+fn init_auth_state(user_pool: sqlx::PgPool, system_pool: sqlx::PgPool) {
     // Initialize static repositories and managers on first call
     if UNIFIED_AUTH_INIT_LOGGED
         .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
@@ -538,6 +623,4 @@ pub fn unified_auth_middleware(
 
     let user_repo = Arc::new(UserRepository::new(system_pool.clone()));
     set_user_repo(user_repo);
-
-    UnifiedAuthMiddleware
 }
@@ -152,6 +152,19 @@ pub struct UsageSummaryQuery {
     pub end_date: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct UsageExportQuery {
+    pub start_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub format: crate::services::usage_export_service::UsageExportFormat,
+    pub model: Option<String>,
+    pub provider: Option<String>,
+    pub task_type: Option<String>,
+    pub include_transactions: Option<bool>,
+}
+
 // Auto top-off models
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -169,6 +182,29 @@ pub struct UpdateAutoTopOffRequest {
     pub amount: Option<String>,
 }
 
+// Spending threshold models
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSpendingThresholdRequest {
+    pub threshold_type: String,
+    pub threshold_value: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_true")]
+    pub notify_email: bool,
+    #[serde(default = "default_true")]
+    pub notify_push: bool,
+    #[serde(default = "default_true")]
+    pub notify_desktop: bool,
+    #[serde(default)]
+    pub soft_limit_enabled: bool,
+    pub soft_limit_max_output_per_million: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 // New unified credit history entry that includes API usage token details
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -39,6 +39,10 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig, strict_rate_limiter: RateL
             // Auto top-off settings routes
             .service(handlers::billing::auto_top_off_handlers::get_auto_top_off_settings_handler)
             .service(handlers::billing::auto_top_off_handlers::update_auto_top_off_settings_handler)
+            // Spending threshold routes
+            .route("/spending-thresholds", web::get().to(handlers::billing::spending_threshold_handlers::get_spending_thresholds_handler))
+            .route("/spending-thresholds", web::put().to(handlers::billing::spending_threshold_handlers::update_spending_threshold_handler))
+            .route("/spending-thresholds/{threshold_type}", web::delete().to(handlers::billing::spending_threshold_handlers::delete_spending_threshold_handler))
             // Payment and billing portal routes
             .service(handlers::billing::payment_handlers::create_billing_portal_session)
             .service(handlers::billing::payment_handlers::get_payment_methods)
@@ -61,10 +65,11 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig, strict_rate_limiter: RateL
                     .route("/admin/adjust", web::post().to(handlers::billing::credit_handlers::admin_adjust_credits))
                     .service(handlers::billing::credit_handlers::get_credit_purchase_fee_tiers_handler)
             )
-            // Usage debug routes (/api/billing/usage/*)
+            // Usage debug and export routes (/api/billing/usage/*)
             .service(
                 web::scope("/usage")
                     .route("/providers", web::get().to(handlers::billing::usage_debug_handlers::get_usage_debug_data))
+                    .route("/export", web::get().to(handlers::billing::usage_export_handlers::export_usage_handler))
             )
             // Customer billing lifecycle actions (cancel, resume, update) are handled by the billing portal
             // This prevents future additions of direct billing modification endpoints
@@ -143,6 +148,14 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig, strict_rate_limiter: RateL
             ),
     );
 
+    // Admin routes (/api/admin/*) - role is checked in each handler
+    cfg.service(
+        web::scope("/admin").route(
+            "/provider-health",
+            web::get().to(handlers::provider_health_handlers::get_provider_health),
+        ),
+    );
+
     // LLM proxy routes (/api/llm/*)
     cfg.service(
         web::scope("/llm")
@@ -321,3 +334,24 @@ async fn auth0_logged_out_handler(
 pub fn configure_webhook_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(handlers::billing::webhook_handlers::stripe_webhook);
 }
+
+/// Configures the OpenAI-compatible API that requires API key authentication.
+/// Mounted under the "/v1" scope in main.rs so OpenAI SDKs can use it as a base URL.
+pub fn configure_openai_compat_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route(
+        "/chat/completions",
+        web::post().to(handlers::openai_compat_handlers::chat_completions),
+    )
+    .route(
+        "/chat/completions/{request_id}/cancel",
+        web::post().to(handlers::openai_compat_handlers::cancel_chat_completion),
+    )
+    .route(
+        "/models",
+        web::get().to(handlers::openai_compat_handlers::list_models),
+    )
+    .route(
+        "/models/{model:.+}",
+        web::get().to(handlers::openai_compat_handlers::retrieve_model),
+    );
+}
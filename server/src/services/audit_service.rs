@@ -778,6 +778,35 @@ impl AuditService {
         self.log_event(context, event).await
     }
 
+    /// Log a spending threshold notification being triggered
+    pub async fn log_spending_threshold_triggered(
+        &self,
+        context: &AuditContext,
+        user_id: &Uuid,
+        threshold_type: &str,
+        threshold_value: &bigdecimal::BigDecimal,
+        observed_value: &bigdecimal::BigDecimal,
+        period: &str,
+    ) -> Result<AuditLog, AppError> {
+        let metadata = serde_json::json!({
+            "threshold_type": threshold_type,
+            "period": period,
+            "currency": "USD",
+            "action_timestamp": Utc::now().to_rfc3339()
+        });
+
+        let event = AuditEvent::new("spending_threshold_triggered", "spending_limit")
+            .with_entity_id(&user_id.to_string())
+            .with_new_values(serde_json::json!({
+                "threshold": threshold_value.to_string(),
+                "observed": observed_value.to_string()
+            }))
+            .with_metadata(metadata)
+            .with_performed_by("system");
+
+        self.log_event(context, event).await
+    }
+
     /// Log spending limit reset after successful payment
     pub async fn log_spending_limit_reset(
         &self,
@@ -157,6 +157,17 @@ impl BillingService {
         });
     }
 
+    /// Evaluate spending thresholds in the background so notifications never delay the response
+    pub(crate) fn spawn_spending_threshold_check(&self, user_id: Uuid) {
+        if let Some(spending_alerts) = self.spending_alert_service.clone() {
+            tokio::spawn(async move {
+                if let Err(e) = spending_alerts.evaluate_thresholds(&user_id).await {
+                    warn!("Spending threshold check failed for user {}: {}", user_id, e);
+                }
+            });
+        }
+    }
+
     pub async fn check_service_access(
         &self,
         user_id: &Uuid,
@@ -222,6 +233,11 @@ impl BillingService {
                 AppError::NotFound(format!("Model '{}' not found", entry.service_name))
             })?;
 
+        // Soft spending limits block expensive models once a threshold is crossed
+        if let Some(spending_alerts) = &self.spending_alert_service {
+            spending_alerts.enforce_soft_limit(&user_id, &model).await?;
+        }
+
         // Create ProviderUsage for estimated cost calculation
         let estimated_usage = ProviderUsage::new(
             entry.tokens_input as i32,
@@ -400,6 +416,38 @@ impl BillingService {
         user_id: &Uuid,
         final_usage: ProviderUsage,
         metadata: Option<serde_json::Value>,
+    ) -> Result<(ApiUsageRecord, UserCredit), AppError> {
+        self.finalize_api_charge_scaled(request_id, user_id, final_usage, metadata, None)
+            .await
+    }
+
+    /// Finalize an API charge billed at a fraction of the server-calculated cost,
+    /// used when a response is replayed from the response cache
+    pub async fn finalize_api_charge_with_cost_multiplier(
+        &self,
+        request_id: &str,
+        user_id: &Uuid,
+        final_usage: ProviderUsage,
+        metadata: Option<serde_json::Value>,
+        cost_multiplier: &BigDecimal,
+    ) -> Result<(ApiUsageRecord, UserCredit), AppError> {
+        self.finalize_api_charge_scaled(
+            request_id,
+            user_id,
+            final_usage,
+            metadata,
+            Some(cost_multiplier),
+        )
+        .await
+    }
+
+    async fn finalize_api_charge_scaled(
+        &self,
+        request_id: &str,
+        user_id: &Uuid,
+        final_usage: ProviderUsage,
+        metadata: Option<serde_json::Value>,
+        cost_multiplier: Option<&BigDecimal>,
     ) -> Result<(ApiUsageRecord, UserCredit), AppError> {
         debug!(
             "Finalizing API charge with metadata for request: {}",
@@ -419,8 +467,11 @@ impl BillingService {
             })?;
 
         // Use CostResolver to calculate final cost
-        let final_cost =
+        let mut final_cost =
             crate::services::cost_resolver::CostResolver::resolve(final_usage.clone(), &model)?;
+        if let Some(multiplier) = cost_multiplier {
+            final_cost = crate::utils::financial_validation::normalize_cost(&(final_cost * multiplier));
+        }
 
         // Start transaction
         let pool = self.credit_service.get_user_credit_repository().get_pool();
@@ -486,6 +537,8 @@ impl BillingService {
             );
         }
 
+        self.spawn_spending_threshold_check(api_usage_record.user_id);
+
         Ok((api_usage_record, user_credit))
     }
 
@@ -658,6 +711,8 @@ impl BillingService {
             );
         }
 
+        self.spawn_spending_threshold_check(api_usage_record.user_id);
+
         Ok((api_usage_record, user_credit))
     }
 }
@@ -49,6 +49,10 @@ pub struct BillingService {
     pub(crate) redis_client: Option<Arc<redis::aio::ConnectionManager>>,
     pub(crate) pending_charge_manager:
         Option<Arc<crate::services::pending_charge_manager::PendingChargeManager>>,
+    pub(crate) spending_alert_service:
+        Option<Arc<crate::services::spending_alert_service::SpendingAlertService>>,
+    pub(crate) response_cache_service:
+        Option<Arc<crate::services::response_cache_service::ResponseCacheService>>,
 }
 
 impl BillingService {
@@ -109,6 +113,8 @@ impl BillingService {
             app_settings,
             redis_client: None,           // Will be set asynchronously
             pending_charge_manager: None, // Will be set asynchronously
+            spending_alert_service: None, // Set once notification channels are initialized
+            response_cache_service: None, // Will be set together with the Redis client
         }
     }
 
@@ -118,6 +124,12 @@ impl BillingService {
         default_ttl_ms: u64,
     ) {
         self.redis_client = Some(conn.clone());
+        self.response_cache_service = Some(Arc::new(
+            crate::services::response_cache_service::ResponseCacheService::new(
+                conn.clone(),
+                self.app_settings.response_cache.clone(),
+            ),
+        ));
         self.pending_charge_manager = Some(Arc::new(
             crate::services::pending_charge_manager::PendingChargeManager::new(
                 conn,
@@ -130,6 +142,28 @@ impl BillingService {
         );
     }
 
+    pub fn set_spending_alert_service(
+        &mut self,
+        service: Arc<crate::services::spending_alert_service::SpendingAlertService>,
+    ) {
+        self.spending_alert_service = Some(service);
+        info!("Spending alert service set for billing service");
+    }
+
+    /// Get access to the spending alert service, if configured
+    pub fn get_spending_alert_service(
+        &self,
+    ) -> Option<&Arc<crate::services::spending_alert_service::SpendingAlertService>> {
+        self.spending_alert_service.as_ref()
+    }
+
+    /// Get access to the LLM response cache, available once Redis is connected
+    pub fn get_response_cache_service(
+        &self,
+    ) -> Option<&Arc<crate::services::response_cache_service::ResponseCacheService>> {
+        self.response_cache_service.as_ref()
+    }
+
     // Get the database pool for use by other components
     pub fn get_db_pool(&self) -> PgPool {
         self.customer_billing_repository.get_pool().clone()
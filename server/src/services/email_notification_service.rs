@@ -81,6 +81,32 @@ impl EmailNotificationService {
             .await
     }
 
+    pub async fn send_spending_threshold_notification(
+        &self,
+        user_email: &str,
+        subject: &str,
+        message: &str,
+    ) -> Result<(), AppError> {
+        let html_content = format!(
+            r#"
+            <html>
+            <body>
+                <h2>{}</h2>
+                <p>Hi there,</p>
+                <p>{}</p>
+                <p>You can adjust your spending alerts and soft limits in your billing settings.</p>
+                <p>Thank you for using PlanToCode!</p>
+            </body>
+            </html>
+            "#,
+            escape_html(subject),
+            escape_html(message)
+        );
+
+        self.send_via_mailgun_direct(subject, &html_content, user_email)
+            .await
+    }
+
     async fn send_via_mailgun_direct(
         &self,
         subject: &str,
@@ -152,3 +178,33 @@ impl EmailNotificationService {
         }
     }
 }
+
+/// Escapes text interpolated into an email's HTML body
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_html_neutralizes_markup() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom's & co</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom&#39;s &amp; co&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("80% of $50.00 used"), "80% of $50.00 used");
+    }
+}
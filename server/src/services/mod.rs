@@ -13,10 +13,14 @@ pub mod email_notification_service;
 pub mod model_mapping_service;
 pub mod pending_charge_manager;
 pub mod pending_command_queue;
+pub mod provider_health_service;
 pub mod reconciliation_service;
 pub mod relay_session_store;
 pub mod request_tracker;
+pub mod response_cache_service;
+pub mod spending_alert_service;
 pub mod stripe_service;
+pub mod usage_export_service;
 pub mod usage_processing_service;
 
 // Re-export commonly used types
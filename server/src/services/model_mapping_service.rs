@@ -96,6 +96,50 @@ impl ModelMappingService {
         }
     }
 
+    /// Resolve a client-supplied model name to an internal model ID.
+    ///
+    /// Accepts the internal ID itself, a provider-specific ID from the mapping table,
+    /// the native API model ID, or the internal ID without its provider prefix
+    /// (e.g. `gpt-5` for `openai/gpt-5`). Only active models are considered.
+    #[instrument(skip(self))]
+    pub async fn resolve_internal_model_id(&self, requested: &str) -> AppResult<Option<String>> {
+        let resolved = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT m.id
+            FROM models m
+            LEFT JOIN model_provider_mappings mpm ON mpm.internal_model_id = m.id
+            WHERE m.status = 'active'
+              AND (m.id = $1
+                   OR mpm.provider_model_id = $1
+                   OR m.api_model_id = $1
+                   OR split_part(m.id, '/', 2) = $1)
+            ORDER BY
+                CASE
+                    WHEN m.id = $1 THEN 0
+                    WHEN mpm.provider_model_id = $1 THEN 1
+                    WHEN m.api_model_id = $1 THEN 2
+                    ELSE 3
+                END,
+                m.id
+            LIMIT 1
+            "#,
+        )
+        .bind(requested)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| {
+            AppError::Database(format!("Failed to resolve model ID {}: {}", requested, e))
+        })?;
+
+        if let Some(ref internal_id) = resolved {
+            if internal_id != requested {
+                info!("Resolved requested model {} -> {}", requested, internal_id);
+            }
+        }
+
+        Ok(resolved)
+    }
+
     /// Get a model with its provider information and resolved model ID
     #[instrument(skip(self))]
     pub async fn get_model_with_mapping(
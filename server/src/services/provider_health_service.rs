@@ -0,0 +1,632 @@
+use crate::config::settings::ProviderHealthConfig;
+use crate::db::repositories::{ProviderHealthRepository, ProviderHealthSnapshotRow};
+use crate::error::AppError;
+use chrono::Utc;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Upper bound on samples kept per model so a busy model cannot grow its window unbounded
+const MAX_SAMPLES_PER_MODEL: usize = 5_000;
+
+/// In-flight entries older than this were never finished (e.g. a panicked handler)
+const STALE_REQUEST_AGE: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Success,
+    Failure,
+    /// Cancelled, or failed for reasons unrelated to the provider
+    Ignored,
+}
+
+impl RequestOutcome {
+    /// Only upstream failures count against a provider; client and billing errors do not
+    pub fn from_error(error: &AppError) -> Self {
+        match error {
+            AppError::External(_) | AppError::Internal(_) => RequestOutcome::Failure,
+            _ => RequestOutcome::Ignored,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowStats {
+    pub request_count: u32,
+    pub error_count: u32,
+    pub error_rate: f64,
+    pub latency_p50_ms: Option<u64>,
+    pub latency_p95_ms: Option<u64>,
+    pub ttft_p50_ms: Option<u64>,
+    pub ttft_p95_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelHealthSnapshot {
+    pub model_id: String,
+    pub provider_code: String,
+    pub status: HealthStatus,
+    pub circuit_state: CircuitState,
+    #[serde(flatten)]
+    pub stats: WindowStats,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthSnapshot {
+    pub provider_code: String,
+    pub status: HealthStatus,
+    pub open_circuits: usize,
+    #[serde(flatten)]
+    pub stats: WindowStats,
+    pub models: Vec<ModelHealthSnapshot>,
+}
+
+#[derive(Debug, Clone)]
+struct Sample {
+    at: Instant,
+    success: bool,
+    latency_ms: u64,
+    ttft_ms: Option<u64>,
+}
+
+#[derive(Debug)]
+struct CircuitBreaker {
+    state: CircuitState,
+    opened_at: Option<Instant>,
+    consecutive_failures: u32,
+    probe_in_flight: bool,
+}
+
+#[derive(Debug)]
+struct ModelHealth {
+    provider_code: String,
+    samples: VecDeque<Sample>,
+    breaker: CircuitBreaker,
+}
+
+impl ModelHealth {
+    fn new(provider_code: &str) -> Self {
+        Self {
+            provider_code: provider_code.to_string(),
+            samples: VecDeque::new(),
+            breaker: CircuitBreaker {
+                state: CircuitState::Closed,
+                opened_at: None,
+                consecutive_failures: 0,
+                probe_in_flight: false,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+struct InFlightRequest {
+    provider_code: String,
+    model_id: String,
+    started_at: Instant,
+    first_token_at: Option<Instant>,
+    is_probe: bool,
+}
+
+/// Rolling per-model error rate, latency and time-to-first-token tracking with a
+/// circuit breaker per model.
+///
+/// The proxy router calls `begin_request` before contacting a provider and
+/// `finish_request` once the outcome is known; streaming responses are finished by
+/// `ModernStreamHandler`, which also reports the first content token. Stats live in
+/// memory per server instance and are periodically persisted for the admin dashboard.
+pub struct ProviderHealthService {
+    config: ProviderHealthConfig,
+    models: DashMap<String, ModelHealth>,
+    in_flight: DashMap<String, InFlightRequest>,
+    repository: Option<ProviderHealthRepository>,
+}
+
+impl ProviderHealthService {
+    pub fn new(config: ProviderHealthConfig) -> Self {
+        Self {
+            config,
+            models: DashMap::new(),
+            in_flight: DashMap::new(),
+            repository: None,
+        }
+    }
+
+    pub fn with_repository(mut self, repository: ProviderHealthRepository) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    pub fn repository(&self) -> Option<&ProviderHealthRepository> {
+        self.repository.as_ref()
+    }
+
+    pub fn window_secs(&self) -> u64 {
+        self.config.window_secs
+    }
+
+    /// Admit a request through the model's circuit breaker and start timing it
+    pub fn begin_request(
+        &self,
+        request_id: &str,
+        provider_code: &str,
+        model_id: &str,
+    ) -> Result<(), AppError> {
+        self.begin_request_at(request_id, provider_code, model_id, Instant::now())
+    }
+
+    /// Mark the arrival of the first streamed content token
+    pub fn record_first_token(&self, request_id: &str) {
+        if let Some(mut request) = self.in_flight.get_mut(request_id) {
+            if request.first_token_at.is_none() {
+                request.first_token_at = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Record the outcome of a request started with `begin_request`; later calls are no-ops
+    pub fn finish_request(&self, request_id: &str, outcome: RequestOutcome) {
+        self.finish_request_at(request_id, outcome, Instant::now());
+    }
+
+    pub fn model_health(&self, model_id: &str) -> Option<ModelHealthSnapshot> {
+        let now = Instant::now();
+        self.models
+            .get(model_id)
+            .map(|health| self.model_snapshot(model_id, &health, now))
+    }
+
+    /// Current health of every provider and model seen by this instance
+    pub fn snapshot(&self) -> Vec<ProviderHealthSnapshot> {
+        let now = Instant::now();
+        let cutoff = self.window_start(now);
+
+        let mut by_provider: BTreeMap<String, (Vec<ModelHealthSnapshot>, Vec<Sample>)> =
+            BTreeMap::new();
+        for entry in self.models.iter() {
+            let (models, samples) = by_provider.entry(entry.provider_code.clone()).or_default();
+            models.push(self.model_snapshot(entry.key(), entry.value(), now));
+            samples.extend(entry.samples.iter().filter(|s| s.at >= cutoff).cloned());
+        }
+
+        by_provider
+            .into_iter()
+            .map(|(provider_code, (mut models, samples))| {
+                models.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+                let stats = window_stats(samples.iter());
+                let open_circuits = models
+                    .iter()
+                    .filter(|m| m.circuit_state != CircuitState::Closed)
+                    .count();
+                let status = if open_circuits > 0 {
+                    HealthStatus::Degraded
+                } else {
+                    self.status_for(CircuitState::Closed, &stats)
+                };
+                ProviderHealthSnapshot {
+                    provider_code,
+                    status,
+                    open_circuits,
+                    stats,
+                    models,
+                }
+            })
+            .collect()
+    }
+
+    /// Periodically persist snapshots for the admin history view and prune old rows
+    pub fn spawn_snapshot_task(self: Arc<Self>) {
+        let Some(repository) = self.repository.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(self.config.snapshot_interval_secs);
+            loop {
+                tokio::time::sleep(interval).await;
+
+                self.expire_stale_requests(Instant::now());
+
+                let rows = self.snapshot_rows();
+                if let Err(e) = repository.insert_snapshots(&rows).await {
+                    warn!("Failed to persist provider health snapshot: {}", e);
+                }
+
+                let cutoff =
+                    Utc::now() - chrono::Duration::hours(self.config.history_retention_hours);
+                if let Err(e) = repository.delete_older_than(cutoff).await {
+                    warn!("Failed to prune provider health history: {}", e);
+                }
+            }
+        });
+    }
+
+    fn snapshot_rows(&self) -> Vec<ProviderHealthSnapshotRow> {
+        let captured_at = Utc::now();
+        let as_ms = |v: Option<u64>| v.map(|ms| ms.min(i32::MAX as u64) as i32);
+
+        self.snapshot()
+            .into_iter()
+            .flat_map(|provider| provider.models)
+            .filter(|m| m.stats.request_count > 0 || m.circuit_state != CircuitState::Closed)
+            .map(|m| ProviderHealthSnapshotRow {
+                captured_at,
+                provider_code: m.provider_code,
+                model_id: m.model_id,
+                window_secs: self.config.window_secs.min(i32::MAX as u64) as i32,
+                request_count: m.stats.request_count as i32,
+                error_count: m.stats.error_count as i32,
+                latency_p50_ms: as_ms(m.stats.latency_p50_ms),
+                latency_p95_ms: as_ms(m.stats.latency_p95_ms),
+                ttft_p50_ms: as_ms(m.stats.ttft_p50_ms),
+                ttft_p95_ms: as_ms(m.stats.ttft_p95_ms),
+                circuit_state: m.circuit_state.as_str().to_string(),
+            })
+            .collect()
+    }
+
+    fn begin_request_at(
+        &self,
+        request_id: &str,
+        provider_code: &str,
+        model_id: &str,
+        now: Instant,
+    ) -> Result<(), AppError> {
+        let is_probe = {
+            let mut health = self
+                .models
+                .entry(model_id.to_string())
+                .or_insert_with(|| ModelHealth::new(provider_code));
+            let open_duration = Duration::from_secs(self.config.open_duration_secs);
+            let breaker = &mut health.breaker;
+
+            match breaker.state {
+                CircuitState::Closed => false,
+                CircuitState::Open => {
+                    let opened_at = breaker.opened_at.unwrap_or(now);
+                    let elapsed = now.saturating_duration_since(opened_at);
+                    if elapsed < open_duration {
+                        return Err(AppError::ServiceUnavailable(format!(
+                            "Model '{}' is temporarily unavailable due to provider errors. Retry in {}s",
+                            model_id,
+                            (open_duration - elapsed).as_secs().max(1)
+                        )));
+                    }
+                    info!(
+                        "Circuit for model {} is half-open, sending probe request {}",
+                        model_id, request_id
+                    );
+                    breaker.state = CircuitState::HalfOpen;
+                    breaker.probe_in_flight = true;
+                    true
+                }
+                CircuitState::HalfOpen => {
+                    if breaker.probe_in_flight {
+                        return Err(AppError::ServiceUnavailable(format!(
+                            "Model '{}' is temporarily unavailable while provider recovery is checked",
+                            model_id
+                        )));
+                    }
+                    breaker.probe_in_flight = true;
+                    true
+                }
+            }
+        };
+
+        self.in_flight.insert(
+            request_id.to_string(),
+            InFlightRequest {
+                provider_code: provider_code.to_string(),
+                model_id: model_id.to_string(),
+                started_at: now,
+                first_token_at: None,
+                is_probe,
+            },
+        );
+        Ok(())
+    }
+
+    fn finish_request_at(&self, request_id: &str, outcome: RequestOutcome, now: Instant) {
+        let Some((_, request)) = self.in_flight.remove(request_id) else {
+            return;
+        };
+
+        let mut health = self
+            .models
+            .entry(request.model_id.clone())
+            .or_insert_with(|| ModelHealth::new(&request.provider_code));
+        let window_start = self.window_start(now);
+        while health.samples.front().is_some_and(|s| s.at < window_start) {
+            health.samples.pop_front();
+        }
+
+        if outcome == RequestOutcome::Ignored {
+            if request.is_probe {
+                health.breaker.probe_in_flight = false;
+            }
+            return;
+        }
+
+        let success = outcome == RequestOutcome::Success;
+        if health.samples.len() >= MAX_SAMPLES_PER_MODEL {
+            health.samples.pop_front();
+        }
+        health.samples.push_back(Sample {
+            at: now,
+            success,
+            latency_ms: now
+                .saturating_duration_since(request.started_at)
+                .as_millis() as u64,
+            ttft_ms: request
+                .first_token_at
+                .map(|at| at.saturating_duration_since(request.started_at).as_millis() as u64),
+        });
+
+        if request.is_probe {
+            health.breaker.probe_in_flight = false;
+            if success {
+                info!(
+                    "Circuit for model {} closed after successful probe",
+                    request.model_id
+                );
+                // Failures from before the outage ended would otherwise re-open it immediately
+                health.samples.retain(|s| s.success);
+                health.breaker.state = CircuitState::Closed;
+                health.breaker.opened_at = None;
+                health.breaker.consecutive_failures = 0;
+            } else {
+                warn!(
+                    "Probe for model {} failed, circuit re-opened",
+                    request.model_id
+                );
+                health.breaker.state = CircuitState::Open;
+                health.breaker.opened_at = Some(now);
+            }
+            return;
+        }
+
+        if health.breaker.state != CircuitState::Closed {
+            return;
+        }
+        if success {
+            health.breaker.consecutive_failures = 0;
+            return;
+        }
+
+        health.breaker.consecutive_failures += 1;
+        let stats = window_stats(health.samples.iter());
+        let consecutive_tripped = self.config.consecutive_failure_threshold > 0
+            && health.breaker.consecutive_failures >= self.config.consecutive_failure_threshold;
+        let rate_tripped = stats.request_count >= self.config.min_requests
+            && stats.error_rate >= self.config.failure_rate_threshold;
+        if consecutive_tripped || rate_tripped {
+            warn!(
+                "Opening circuit for model {} (provider {}): {} consecutive failures, error rate {:.2} over {} requests",
+                request.model_id,
+                request.provider_code,
+                health.breaker.consecutive_failures,
+                stats.error_rate,
+                stats.request_count
+            );
+            health.breaker.state = CircuitState::Open;
+            health.breaker.opened_at = Some(now);
+        }
+    }
+
+    /// Release entries that were never finished so a leaked probe cannot pin a circuit half-open
+    fn expire_stale_requests(&self, now: Instant) {
+        let stale: Vec<String> = self
+            .in_flight
+            .iter()
+            .filter(|r| now.saturating_duration_since(r.started_at) > STALE_REQUEST_AGE)
+            .map(|r| r.key().clone())
+            .collect();
+        for request_id in stale {
+            self.finish_request_at(&request_id, RequestOutcome::Ignored, now);
+        }
+    }
+
+    fn model_snapshot(
+        &self,
+        model_id: &str,
+        health: &ModelHealth,
+        now: Instant,
+    ) -> ModelHealthSnapshot {
+        let cutoff = self.window_start(now);
+        let stats = window_stats(health.samples.iter().filter(|s| s.at >= cutoff));
+        ModelHealthSnapshot {
+            model_id: model_id.to_string(),
+            provider_code: health.provider_code.clone(),
+            status: self.status_for(health.breaker.state, &stats),
+            circuit_state: health.breaker.state,
+            stats,
+        }
+    }
+
+    /// Degraded once the circuit is not closed, or the error rate reaches half the trip threshold
+    fn status_for(&self, circuit_state: CircuitState, stats: &WindowStats) -> HealthStatus {
+        let elevated_errors = stats.request_count >= self.config.min_requests
+            && stats.error_rate >= self.config.failure_rate_threshold / 2.0;
+        if circuit_state != CircuitState::Closed || elevated_errors {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+
+    fn window_start(&self, now: Instant) -> Instant {
+        now.checked_sub(Duration::from_secs(self.config.window_secs))
+            .unwrap_or(now)
+    }
+}
+
+fn window_stats<'a>(samples: impl Iterator<Item = &'a Sample>) -> WindowStats {
+    let mut request_count = 0u32;
+    let mut error_count = 0u32;
+    let mut latencies = Vec::new();
+    let mut ttfts = Vec::new();
+
+    for sample in samples {
+        request_count += 1;
+        if !sample.success {
+            error_count += 1;
+        }
+        latencies.push(sample.latency_ms);
+        if let Some(ttft) = sample.ttft_ms {
+            ttfts.push(ttft);
+        }
+    }
+    latencies.sort_unstable();
+    ttfts.sort_unstable();
+
+    WindowStats {
+        request_count,
+        error_count,
+        error_rate: if request_count > 0 {
+            error_count as f64 / request_count as f64
+        } else {
+            0.0
+        },
+        latency_p50_ms: percentile(&latencies, 0.50),
+        latency_p95_ms: percentile(&latencies, 0.95),
+        ttft_p50_ms: percentile(&ttfts, 0.50),
+        ttft_p95_ms: percentile(&ttfts, 0.95),
+    }
+}
+
+/// Nearest-rank percentile over sorted values
+fn percentile(sorted: &[u64], p: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> ProviderHealthService {
+        ProviderHealthService::new(ProviderHealthConfig {
+            window_secs: 300,
+            min_requests: 10,
+            failure_rate_threshold: 0.5,
+            consecutive_failure_threshold: 3,
+            open_duration_secs: 60,
+            snapshot_interval_secs: 60,
+            history_retention_hours: 24,
+        })
+    }
+
+    fn run(
+        service: &ProviderHealthService,
+        id: &str,
+        outcome: RequestOutcome,
+        now: Instant,
+    ) -> Result<(), AppError> {
+        service.begin_request_at(id, "openai", "openai/gpt-4.1", now)?;
+        service.finish_request_at(id, outcome, now + Duration::from_millis(100));
+        Ok(())
+    }
+
+    fn circuit_state(service: &ProviderHealthService) -> CircuitState {
+        service.models.get("openai/gpt-4.1").unwrap().breaker.state
+    }
+
+    #[test]
+    fn consecutive_failures_open_the_circuit() {
+        let service = service();
+        let now = Instant::now();
+        for i in 0..3 {
+            run(&service, &format!("r{}", i), RequestOutcome::Failure, now).unwrap();
+        }
+        assert_eq!(circuit_state(&service), CircuitState::Open);
+        assert!(matches!(
+            run(&service, "rejected", RequestOutcome::Success, now),
+            Err(AppError::ServiceUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn half_open_probe_closes_circuit_on_success() {
+        let service = service();
+        let now = Instant::now();
+        for i in 0..3 {
+            run(&service, &format!("r{}", i), RequestOutcome::Failure, now).unwrap();
+        }
+
+        let later = now + Duration::from_secs(61);
+        service
+            .begin_request_at("probe", "openai", "openai/gpt-4.1", later)
+            .unwrap();
+        assert_eq!(circuit_state(&service), CircuitState::HalfOpen);
+        // Only one probe at a time
+        assert!(
+            service
+                .begin_request_at("second", "openai", "openai/gpt-4.1", later)
+                .is_err()
+        );
+
+        service.finish_request_at("probe", RequestOutcome::Success, later);
+        assert_eq!(circuit_state(&service), CircuitState::Closed);
+        assert!(run(&service, "after", RequestOutcome::Success, later).is_ok());
+    }
+
+    #[test]
+    fn ignored_outcomes_do_not_count() {
+        let service = service();
+        let now = Instant::now();
+        for i in 0..10 {
+            run(&service, &format!("r{}", i), RequestOutcome::Ignored, now).unwrap();
+        }
+        assert_eq!(circuit_state(&service), CircuitState::Closed);
+        assert_eq!(
+            service
+                .model_health("openai/gpt-4.1")
+                .unwrap()
+                .stats
+                .request_count,
+            0
+        );
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let values: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&values, 0.50), Some(50));
+        assert_eq!(percentile(&values, 0.95), Some(95));
+        assert_eq!(percentile(&[7], 0.95), Some(7));
+        assert_eq!(percentile(&[], 0.5), None);
+    }
+}
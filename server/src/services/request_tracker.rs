@@ -1,3 +1,4 @@
+use crate::services::provider_health_service::ProviderHealthService;
 use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -19,15 +20,27 @@ pub struct TrackedRequest {
 #[derive(Clone)]
 pub struct RequestTracker {
     requests: Arc<RwLock<HashMap<String, TrackedRequest>>>,
+    provider_health: Option<Arc<ProviderHealthService>>,
 }
 
 impl RequestTracker {
     pub fn new() -> Self {
         Self {
             requests: Arc::new(RwLock::new(HashMap::new())),
+            provider_health: None,
         }
     }
 
+    /// Report request outcomes and streaming latency to the provider health service
+    pub fn with_provider_health(mut self, provider_health: Arc<ProviderHealthService>) -> Self {
+        self.provider_health = Some(provider_health);
+        self
+    }
+
+    pub fn provider_health(&self) -> Option<&Arc<ProviderHealthService>> {
+        self.provider_health.as_ref()
+    }
+
     pub async fn track_request(
         &self,
         request_id: String,
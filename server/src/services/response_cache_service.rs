@@ -0,0 +1,338 @@
+use crate::clients::open_router_client::OpenRouterStreamChunk;
+use crate::clients::usage_extractor::ProviderUsage;
+use crate::config::settings::ResponseCacheConfig;
+use crate::handlers::proxy::types::LlmCompletionRequest;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+const CACHE_KEY_PREFIX: &str = "llm_response_cache";
+
+/// Request fields that do not influence the model output and are left out of the cache key
+const NON_SEMANTIC_FIELDS: &[&str] = &["request_id", "duration_ms", "response_cache", "user"];
+
+/// Task types whose output depends on live data and must never be served from cache
+const UNCACHEABLE_TASK_TYPES: &[&str] = &["web_search_execution", "implementation_plan_with_web"];
+
+/// Token counts of the original provider call, replayed for billing on a cache hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedUsage {
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub cache_write_tokens: i32,
+    pub cache_read_tokens: i32,
+}
+
+impl CachedUsage {
+    pub fn from_provider_usage(usage: &ProviderUsage) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cache_write_tokens: usage.cache_write_tokens,
+            cache_read_tokens: usage.cache_read_tokens,
+        }
+    }
+
+    /// Reads the standardized camelCase usage object attached to non-streaming responses
+    pub fn from_response_usage(usage: &Value) -> Option<Self> {
+        let field = |name: &str| usage.get(name).and_then(|v| v.as_i64()).unwrap_or(0) as i32;
+        let cached = Self {
+            prompt_tokens: field("promptTokens"),
+            completion_tokens: field("completionTokens"),
+            cache_write_tokens: field("cacheWriteTokens"),
+            cache_read_tokens: field("cacheReadTokens"),
+        };
+        (cached.prompt_tokens > 0 || cached.completion_tokens > 0).then_some(cached)
+    }
+
+    pub fn to_provider_usage(&self, model_id: &str) -> ProviderUsage {
+        ProviderUsage::new(
+            self.prompt_tokens,
+            self.completion_tokens,
+            self.cache_write_tokens,
+            self.cache_read_tokens,
+            model_id.to_string(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CachedBody {
+    /// Standardized chunks as emitted by `ModernStreamHandler`, replayed in order
+    Stream { chunks: Vec<OpenRouterStreamChunk> },
+    /// Full JSON body of a non-streaming completion
+    Json { response: Value },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedCompletion {
+    pub model_id: String,
+    pub usage: CachedUsage,
+    pub body: CachedBody,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Per-user Redis cache for identical deterministic LLM requests.
+///
+/// A request is only cacheable when the client opts in and pins temperature to 0.
+/// Misses register the request id so the streaming handler (or the router, for
+/// non-streaming calls) can store the completed response under the same key.
+pub struct ResponseCacheService {
+    redis: Arc<redis::aio::ConnectionManager>,
+    config: ResponseCacheConfig,
+    pending_captures: DashMap<String, String>,
+}
+
+impl ResponseCacheService {
+    pub fn new(redis: Arc<redis::aio::ConnectionManager>, config: ResponseCacheConfig) -> Self {
+        Self {
+            redis,
+            config,
+            pending_captures: DashMap::new(),
+        }
+    }
+
+    pub fn billing_fraction(&self) -> &BigDecimal {
+        &self.config.billing_fraction
+    }
+
+    /// Returns the cache key for a request, or None if it must go to the provider
+    pub fn cache_key_for(&self, user_id: &Uuid, request: &LlmCompletionRequest) -> Option<String> {
+        if !self.config.enabled || !is_cacheable(request) {
+            return None;
+        }
+        Some(format!(
+            "{}:{}:{}",
+            CACHE_KEY_PREFIX,
+            user_id,
+            request_fingerprint(request)
+        ))
+    }
+
+    pub async fn get(&self, cache_key: &str) -> Option<CachedCompletion> {
+        let mut conn = self.redis.as_ref().clone();
+        let raw: Option<String> = match conn.get(cache_key).await {
+            Ok(raw) => raw,
+            Err(e) => {
+                warn!("Response cache lookup failed, treating as miss: {}", e);
+                return None;
+            }
+        };
+
+        match serde_json::from_str::<CachedCompletion>(&raw?) {
+            Ok(cached) => Some(cached),
+            Err(e) => {
+                warn!(
+                    "Discarding unreadable response cache entry {}: {}",
+                    cache_key, e
+                );
+                let _: Result<(), _> = conn.del(cache_key).await;
+                None
+            }
+        }
+    }
+
+    pub async fn store(&self, cache_key: &str, completion: &CachedCompletion) {
+        let serialized = match serde_json::to_string(completion) {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                warn!("Failed to serialize response cache entry: {}", e);
+                return;
+            }
+        };
+
+        if serialized.len() > self.config.max_entry_bytes {
+            debug!(
+                "Skipping response cache entry of {} bytes (limit {})",
+                serialized.len(),
+                self.config.max_entry_bytes
+            );
+            return;
+        }
+
+        let mut conn = self.redis.as_ref().clone();
+        match conn
+            .set_ex::<_, _, ()>(cache_key, serialized, self.config.ttl_secs)
+            .await
+        {
+            Ok(()) => info!(
+                "Stored LLM response in cache for model {} (ttl {}s)",
+                completion.model_id, self.config.ttl_secs
+            ),
+            Err(e) => warn!("Failed to store response cache entry: {}", e),
+        }
+    }
+
+    /// Remember that the response for `request_id` should be cached under `cache_key`
+    pub fn register_capture(&self, request_id: &str, cache_key: String) {
+        self.pending_captures
+            .insert(request_id.to_string(), cache_key);
+    }
+
+    pub fn is_capturing(&self, request_id: &str) -> bool {
+        self.pending_captures.contains_key(request_id)
+    }
+
+    pub fn take_capture(&self, request_id: &str) -> Option<String> {
+        self.pending_captures
+            .remove(request_id)
+            .map(|(_, cache_key)| cache_key)
+    }
+}
+
+/// Only opted-in requests pinned to temperature 0 are considered deterministic
+pub fn is_cacheable(request: &LlmCompletionRequest) -> bool {
+    if request.response_cache != Some(true) || request.temperature != Some(0.0) {
+        return false;
+    }
+    if let Some(task_type) = request.task_type.as_deref() {
+        if UNCACHEABLE_TASK_TYPES.contains(&task_type) {
+            return false;
+        }
+    }
+    // Multiple choices cannot be replayed faithfully from a single stored stream
+    !matches!(request.other.get("n").and_then(|n| n.as_u64()), Some(n) if n > 1)
+}
+
+/// SHA-256 over a canonical (key-sorted) JSON encoding of model, messages and parameters
+pub fn request_fingerprint(request: &LlmCompletionRequest) -> String {
+    let mut canonical = String::new();
+    canonical.push_str("{\"model\":");
+    write_canonical_json(&Value::String(request.model.clone()), &mut canonical);
+    canonical.push_str(",\"messages\":");
+    write_canonical_json(&Value::Array(request.messages.clone()), &mut canonical);
+    canonical.push_str(",\"params\":");
+
+    let mut params = serde_json::Map::new();
+    params.insert(
+        "stream".to_string(),
+        Value::Bool(request.stream.unwrap_or(false)),
+    );
+    if let Some(max_tokens) = request.max_tokens {
+        params.insert("max_tokens".to_string(), Value::from(max_tokens));
+    }
+    if let Some(task_type) = &request.task_type {
+        params.insert("task_type".to_string(), Value::String(task_type.clone()));
+    }
+    for (key, value) in &request.other {
+        if !NON_SEMANTIC_FIELDS.contains(&key.as_str()) {
+            params.insert(key.clone(), value.clone());
+        }
+    }
+    write_canonical_json(&Value::Object(params), &mut canonical);
+    canonical.push('}');
+
+    hex::encode(Sha256::digest(canonical.as_bytes()))
+}
+
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn request(messages: Vec<Value>, other: HashMap<String, Value>) -> LlmCompletionRequest {
+        LlmCompletionRequest {
+            model: "openai/gpt-4.1".to_string(),
+            messages,
+            stream: Some(true),
+            max_tokens: Some(4096),
+            temperature: Some(0.0),
+            task_type: Some("extended_path_finder".to_string()),
+            response_cache: Some(true),
+            other,
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_key_order_and_request_ids() {
+        let a = request(
+            vec![json!({"role": "user", "content": "hi"})],
+            HashMap::from([("request_id".to_string(), json!("a"))]),
+        );
+        let b = request(
+            vec![json!({"content": "hi", "role": "user"})],
+            HashMap::from([("request_id".to_string(), json!("b"))]),
+        );
+        assert_eq!(request_fingerprint(&a), request_fingerprint(&b));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_messages_and_params() {
+        let base = request(
+            vec![json!({"role": "user", "content": "hi"})],
+            HashMap::new(),
+        );
+        let other_message = request(
+            vec![json!({"role": "user", "content": "bye"})],
+            HashMap::new(),
+        );
+        let other_param = request(
+            vec![json!({"role": "user", "content": "hi"})],
+            HashMap::from([("top_p".to_string(), json!(0.5))]),
+        );
+        assert_ne!(
+            request_fingerprint(&base),
+            request_fingerprint(&other_message)
+        );
+        assert_ne!(
+            request_fingerprint(&base),
+            request_fingerprint(&other_param)
+        );
+    }
+
+    #[test]
+    fn only_opted_in_zero_temperature_requests_are_cacheable() {
+        let mut req = request(vec![], HashMap::new());
+        assert!(is_cacheable(&req));
+
+        req.temperature = Some(0.7);
+        assert!(!is_cacheable(&req));
+
+        req.temperature = Some(0.0);
+        req.response_cache = None;
+        assert!(!is_cacheable(&req));
+
+        req.response_cache = Some(true);
+        req.task_type = Some("web_search_execution".to_string());
+        assert!(!is_cacheable(&req));
+    }
+}
@@ -0,0 +1,578 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde_json::json;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::connection::DatabasePools;
+use crate::db::pool_ext::AcquireRetry;
+use crate::db::repositories::model_repository::ModelWithProvider;
+use crate::db::repositories::spending_threshold_repository::{
+    SpendingSnapshot, SpendingThreshold, SpendingThresholdRepository, UpsertSpendingThreshold,
+};
+use crate::db::repositories::user_repository::UserRepository;
+use crate::error::AppError;
+use crate::models::model_pricing::ModelPricing;
+use crate::services::apns_service::ApnsService;
+use crate::services::audit_service::{AuditContext, AuditService};
+use crate::services::device_connection_manager::{DeviceConnectionManager, DeviceMessage};
+use crate::services::email_notification_service::EmailNotificationService;
+
+/// Output price cap applied by a soft limit when the user did not configure one
+const DEFAULT_SOFT_LIMIT_MAX_OUTPUT_PER_MILLION: &str = "2.00";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdKind {
+    BalanceUsedPercent,
+    DailySpend,
+    MonthlySpend,
+}
+
+impl ThresholdKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThresholdKind::BalanceUsedPercent => "balance_used_percent",
+            ThresholdKind::DailySpend => "daily_spend",
+            ThresholdKind::MonthlySpend => "monthly_spend",
+        }
+    }
+
+    /// Current value of the metric this threshold watches
+    pub fn observed_value(&self, snapshot: &SpendingSnapshot) -> BigDecimal {
+        match self {
+            ThresholdKind::BalanceUsedPercent => {
+                let funded = &snapshot.spent_since_last_purchase + &snapshot.current_balance;
+                if funded <= BigDecimal::zero() {
+                    return BigDecimal::zero();
+                }
+                (&snapshot.spent_since_last_purchase * BigDecimal::from(100)) / funded
+            }
+            ThresholdKind::DailySpend => snapshot.daily_spend.clone(),
+            ThresholdKind::MonthlySpend => snapshot.monthly_spend.clone(),
+        }
+    }
+
+    /// Key identifying the period an alert belongs to, so each period alerts once
+    pub fn period_key(&self, snapshot: &SpendingSnapshot, now: DateTime<Utc>) -> String {
+        match self {
+            ThresholdKind::BalanceUsedPercent => match snapshot.last_purchase_id {
+                Some(id) => format!("purchase:{}", id),
+                None => "purchase:none".to_string(),
+            },
+            ThresholdKind::DailySpend => now.format("%Y-%m-%d").to_string(),
+            ThresholdKind::MonthlySpend => now.format("%Y-%m").to_string(),
+        }
+    }
+
+    fn describe(&self, value: &BigDecimal) -> String {
+        match self {
+            ThresholdKind::BalanceUsedPercent => format!("{:.0}% of your credit balance", value),
+            ThresholdKind::DailySpend => format!("${:.2} spent today", value),
+            ThresholdKind::MonthlySpend => format!("${:.2} spent this month", value),
+        }
+    }
+}
+
+impl FromStr for ThresholdKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "balance_used_percent" => Ok(ThresholdKind::BalanceUsedPercent),
+            "daily_spend" => Ok(ThresholdKind::DailySpend),
+            "monthly_spend" => Ok(ThresholdKind::MonthlySpend),
+            other => Err(AppError::Validation(format!(
+                "Unknown spending threshold type: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Returns true when the observed metric has reached the configured threshold
+pub fn threshold_exceeded(threshold: &SpendingThreshold, snapshot: &SpendingSnapshot) -> bool {
+    match ThresholdKind::from_str(&threshold.threshold_type) {
+        Ok(kind) => kind.observed_value(snapshot) >= threshold.threshold_value,
+        Err(_) => false,
+    }
+}
+
+/// Output price per million tokens, used to classify models as cheap or expensive
+pub fn output_price_per_million(model: &ModelWithProvider) -> Option<BigDecimal> {
+    let value = model.get_pricing_info().get("output_per_million")?;
+    match value {
+        serde_json::Value::String(s) => BigDecimal::from_str(s).ok(),
+        serde_json::Value::Number(n) => BigDecimal::from_str(&n.to_string()).ok(),
+        _ => None,
+    }
+}
+
+/// Evaluates user-configured spending thresholds after each charge, notifies the user
+/// by email, push and device-link event, and enforces soft limits on expensive models.
+pub struct SpendingAlertService {
+    db_pools: DatabasePools,
+    repository: Arc<SpendingThresholdRepository>,
+    user_repository: Arc<UserRepository>,
+    audit_service: Arc<AuditService>,
+    email_service: Option<Arc<EmailNotificationService>>,
+    apns_service: Option<Arc<ApnsService>>,
+    device_connection_manager: Option<DeviceConnectionManager>,
+}
+
+impl SpendingAlertService {
+    pub fn new(db_pools: DatabasePools, audit_service: Arc<AuditService>) -> Self {
+        Self {
+            repository: Arc::new(SpendingThresholdRepository::new(db_pools.user_pool.clone())),
+            user_repository: Arc::new(UserRepository::new(db_pools.system_pool.clone())),
+            db_pools,
+            audit_service,
+            email_service: None,
+            apns_service: None,
+            device_connection_manager: None,
+        }
+    }
+
+    pub fn with_email_service(mut self, email_service: Arc<EmailNotificationService>) -> Self {
+        self.email_service = Some(email_service);
+        self
+    }
+
+    pub fn with_apns_service(mut self, apns_service: Arc<ApnsService>) -> Self {
+        self.apns_service = Some(apns_service);
+        self
+    }
+
+    pub fn with_device_connection_manager(mut self, manager: DeviceConnectionManager) -> Self {
+        self.device_connection_manager = Some(manager);
+        self
+    }
+
+    async fn begin_user_transaction(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<sqlx::Transaction<'_, sqlx::Postgres>, AppError> {
+        let mut tx = AcquireRetry::begin_with_retry(&self.db_pools.user_pool, 3, 150)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+
+        // Set user context for RLS within the transaction
+        sqlx::query("SELECT set_config('app.current_user_id', $1, true)")
+            .bind(user_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::Database(format!("Failed to set user context in transaction: {}", e))
+            })?;
+
+        Ok(tx)
+    }
+
+    pub async fn get_thresholds(&self, user_id: &Uuid) -> Result<Vec<SpendingThreshold>, AppError> {
+        let mut tx = self.begin_user_transaction(user_id).await?;
+        let thresholds = self
+            .repository
+            .list_for_user_with_executor(user_id, &mut tx)
+            .await?;
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(thresholds)
+    }
+
+    pub async fn upsert_threshold(
+        &self,
+        user_id: &Uuid,
+        threshold: UpsertSpendingThreshold,
+    ) -> Result<SpendingThreshold, AppError> {
+        let kind = ThresholdKind::from_str(&threshold.threshold_type)?;
+
+        if threshold.threshold_value <= BigDecimal::zero() {
+            return Err(AppError::Validation(
+                "Threshold value must be greater than zero".to_string(),
+            ));
+        }
+        if kind == ThresholdKind::BalanceUsedPercent
+            && threshold.threshold_value > BigDecimal::from(100)
+        {
+            return Err(AppError::Validation(
+                "Balance usage threshold must be between 0 and 100 percent".to_string(),
+            ));
+        }
+        if let Some(max_price) = &threshold.soft_limit_max_output_per_million {
+            if *max_price < BigDecimal::zero() {
+                return Err(AppError::Validation(
+                    "Soft limit price cap cannot be negative".to_string(),
+                ));
+            }
+        }
+
+        let mut tx = self.begin_user_transaction(user_id).await?;
+        let saved = self
+            .repository
+            .upsert_with_executor(user_id, &threshold, &mut tx)
+            .await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        info!(
+            "Saved {} spending threshold for user {}",
+            saved.threshold_type, user_id
+        );
+        Ok(saved)
+    }
+
+    pub async fn delete_threshold(
+        &self,
+        user_id: &Uuid,
+        threshold_type: &str,
+    ) -> Result<(), AppError> {
+        ThresholdKind::from_str(threshold_type)?;
+
+        let mut tx = self.begin_user_transaction(user_id).await?;
+        let deleted = self
+            .repository
+            .delete_with_executor(user_id, threshold_type, &mut tx)
+            .await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        if !deleted {
+            return Err(AppError::NotFound(format!(
+                "No {} spending threshold configured",
+                threshold_type
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject requests for models priced above the soft-limit cap once a soft-limit threshold is crossed
+    pub async fn enforce_soft_limit(
+        &self,
+        user_id: &Uuid,
+        model: &ModelWithProvider,
+    ) -> Result<(), AppError> {
+        // Unpriced models are never blocked, so skip the database work for them
+        let model_price = match output_price_per_million(model) {
+            Some(price) => price,
+            None => return Ok(()),
+        };
+
+        let mut tx = self.begin_user_transaction(user_id).await?;
+        let soft_limits: Vec<SpendingThreshold> = self
+            .repository
+            .list_for_user_with_executor(user_id, &mut tx)
+            .await?
+            .into_iter()
+            .filter(|t| t.enabled && t.soft_limit_enabled)
+            .collect();
+
+        if soft_limits.is_empty() {
+            tx.commit().await.map_err(AppError::from)?;
+            return Ok(());
+        }
+
+        let snapshot = self
+            .repository
+            .get_spending_snapshot_with_executor(user_id, Utc::now(), &mut tx)
+            .await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        for threshold in soft_limits
+            .iter()
+            .filter(|t| threshold_exceeded(t, &snapshot))
+        {
+            let max_price = threshold
+                .soft_limit_max_output_per_million
+                .clone()
+                .unwrap_or_else(|| {
+                    BigDecimal::from_str(DEFAULT_SOFT_LIMIT_MAX_OUTPUT_PER_MILLION).unwrap()
+                });
+
+            if model_price > max_price {
+                info!(
+                    "Soft spending limit ({}) blocked model {} for user {}",
+                    threshold.threshold_type, model.id, user_id
+                );
+                return Err(AppError::SpendingLimitExceeded(format!(
+                    "Your {} soft limit has been reached. Models priced above ${:.2} per million output tokens are paused; '{}' costs ${:.2}. Choose a cheaper model or raise the limit in billing settings.",
+                    threshold.threshold_type.replace('_', " "),
+                    max_price,
+                    model.name,
+                    model_price
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check all thresholds for a user and send notifications for newly crossed ones
+    pub async fn evaluate_thresholds(&self, user_id: &Uuid) -> Result<usize, AppError> {
+        let now = Utc::now();
+        let mut tx = self.begin_user_transaction(user_id).await?;
+
+        let thresholds: Vec<SpendingThreshold> = self
+            .repository
+            .list_for_user_with_executor(user_id, &mut tx)
+            .await?
+            .into_iter()
+            .filter(|t| t.enabled)
+            .collect();
+
+        if thresholds.is_empty() {
+            tx.commit().await.map_err(AppError::from)?;
+            return Ok(0);
+        }
+
+        let snapshot = self
+            .repository
+            .get_spending_snapshot_with_executor(user_id, now, &mut tx)
+            .await?;
+
+        let mut triggered = Vec::new();
+        for threshold in thresholds {
+            let kind = match ThresholdKind::from_str(&threshold.threshold_type) {
+                Ok(kind) => kind,
+                Err(_) => continue,
+            };
+            if !threshold_exceeded(&threshold, &snapshot) {
+                continue;
+            }
+
+            let period_key = kind.period_key(&snapshot, now);
+            if threshold.last_triggered_period.as_deref() == Some(period_key.as_str()) {
+                continue;
+            }
+
+            // The conditional update makes concurrent charges race safely for the same period
+            if self
+                .repository
+                .mark_triggered_with_executor(&threshold.id, &period_key, &mut tx)
+                .await?
+            {
+                let observed = kind.observed_value(&snapshot);
+                triggered.push((threshold, kind, observed, period_key));
+            }
+        }
+
+        tx.commit().await.map_err(AppError::from)?;
+
+        for (threshold, kind, observed, period_key) in &triggered {
+            self.notify(user_id, threshold, *kind, observed, period_key)
+                .await;
+        }
+
+        Ok(triggered.len())
+    }
+
+    async fn notify(
+        &self,
+        user_id: &Uuid,
+        threshold: &SpendingThreshold,
+        kind: ThresholdKind,
+        observed: &BigDecimal,
+        period_key: &str,
+    ) {
+        let title = "Spending threshold reached".to_string();
+        let body = format!(
+            "You have reached {}. {}",
+            kind.describe(observed),
+            if threshold.soft_limit_enabled {
+                "Expensive models are paused until the next period or a top-up."
+            } else {
+                "Review your usage in billing settings."
+            }
+        );
+        let event_payload = json!({
+            "thresholdType": threshold.threshold_type,
+            "thresholdValue": threshold.threshold_value.to_string(),
+            "observedValue": format!("{:.4}", observed),
+            "period": period_key,
+            "softLimitEnabled": threshold.soft_limit_enabled,
+            "triggeredAt": Utc::now().to_rfc3339(),
+        });
+
+        if threshold.notify_email {
+            if let Some(email_service) = &self.email_service {
+                match self.user_repository.get_by_id(user_id).await {
+                    Ok(user) => {
+                        if let Err(e) = email_service
+                            .send_spending_threshold_notification(&user.email, &title, &body)
+                            .await
+                        {
+                            warn!(
+                                "Failed to send spending threshold email to user {}: {}",
+                                user_id, e
+                            );
+                        }
+                    }
+                    Err(e) => warn!("Failed to load user {} for spending email: {}", user_id, e),
+                }
+            } else {
+                debug!("Email service not configured, skipping spending threshold email");
+            }
+        }
+
+        if threshold.notify_push {
+            if let Some(apns_service) = &self.apns_service {
+                let custom_data = json!({
+                    "type": "spending_threshold",
+                    "thresholdType": threshold.threshold_type,
+                    "period": period_key,
+                });
+                if let Err(e) = apns_service
+                    .send_data_notification(user_id, &title, &body, custom_data)
+                    .await
+                {
+                    warn!(
+                        "Failed to send spending threshold push to user {}: {}",
+                        user_id, e
+                    );
+                }
+            }
+        }
+
+        if threshold.notify_desktop {
+            if let Some(manager) = &self.device_connection_manager {
+                let message = DeviceMessage {
+                    message_type: "event".to_string(),
+                    payload: json!({
+                        "eventType": "spending-threshold-reached",
+                        "payload": event_payload,
+                    }),
+                    event_id: None,
+                    target_device_id: None,
+                    source_device_id: None,
+                    timestamp: Utc::now(),
+                };
+                if let Err(e) = manager
+                    .broadcast_to_user_excluding(user_id, message, None)
+                    .await
+                {
+                    warn!(
+                        "Failed to broadcast spending threshold event to user {}: {}",
+                        user_id, e
+                    );
+                }
+            }
+        }
+
+        let audit_context = AuditContext::new(*user_id);
+        if let Err(e) = self
+            .audit_service
+            .log_spending_threshold_triggered(
+                &audit_context,
+                user_id,
+                &threshold.threshold_type,
+                &threshold.threshold_value,
+                observed,
+                period_key,
+            )
+            .await
+        {
+            warn!(
+                "Failed to audit spending threshold for user {}: {}",
+                user_id, e
+            );
+        }
+
+        info!(
+            "Spending threshold {} triggered for user {} (period {})",
+            threshold.threshold_type, user_id, period_key
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(daily: &str, monthly: &str, spent: &str, balance: &str) -> SpendingSnapshot {
+        SpendingSnapshot {
+            daily_spend: BigDecimal::from_str(daily).unwrap(),
+            monthly_spend: BigDecimal::from_str(monthly).unwrap(),
+            spent_since_last_purchase: BigDecimal::from_str(spent).unwrap(),
+            current_balance: BigDecimal::from_str(balance).unwrap(),
+            last_purchase_id: None,
+        }
+    }
+
+    fn threshold(threshold_type: &str, value: &str) -> SpendingThreshold {
+        SpendingThreshold {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            threshold_type: threshold_type.to_string(),
+            threshold_value: BigDecimal::from_str(value).unwrap(),
+            enabled: true,
+            notify_email: true,
+            notify_push: true,
+            notify_desktop: true,
+            soft_limit_enabled: false,
+            soft_limit_max_output_per_million: None,
+            last_triggered_at: None,
+            last_triggered_period: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_balance_used_percent_is_relative_to_last_top_up() {
+        let snap = snapshot("0", "0", "15", "5");
+        assert_eq!(
+            ThresholdKind::BalanceUsedPercent.observed_value(&snap),
+            BigDecimal::from(75)
+        );
+        assert!(threshold_exceeded(
+            &threshold("balance_used_percent", "75"),
+            &snap
+        ));
+        assert!(!threshold_exceeded(
+            &threshold("balance_used_percent", "80"),
+            &snap
+        ));
+    }
+
+    #[test]
+    fn test_balance_used_percent_without_funds_is_zero() {
+        let snap = snapshot("0", "0", "0", "0");
+        assert_eq!(
+            ThresholdKind::BalanceUsedPercent.observed_value(&snap),
+            BigDecimal::zero()
+        );
+    }
+
+    #[test]
+    fn test_daily_and_monthly_thresholds() {
+        let snap = snapshot("4.50", "30.00", "0", "10");
+        assert!(threshold_exceeded(&threshold("daily_spend", "4.50"), &snap));
+        assert!(!threshold_exceeded(&threshold("daily_spend", "5"), &snap));
+        assert!(threshold_exceeded(&threshold("monthly_spend", "25"), &snap));
+        assert!(!threshold_exceeded(&threshold("unknown", "1"), &snap));
+    }
+
+    #[test]
+    fn test_period_keys() {
+        let now = DateTime::parse_from_rfc3339("2025-03-09T23:59:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut snap = snapshot("0", "0", "0", "0");
+        assert_eq!(
+            ThresholdKind::DailySpend.period_key(&snap, now),
+            "2025-03-09"
+        );
+        assert_eq!(
+            ThresholdKind::MonthlySpend.period_key(&snap, now),
+            "2025-03"
+        );
+        assert_eq!(
+            ThresholdKind::BalanceUsedPercent.period_key(&snap, now),
+            "purchase:none"
+        );
+
+        let purchase_id = Uuid::new_v4();
+        snap.last_purchase_id = Some(purchase_id);
+        assert_eq!(
+            ThresholdKind::BalanceUsedPercent.period_key(&snap, now),
+            format!("purchase:{}", purchase_id)
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use bigdecimal::BigDecimal;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt, stream};
+use log::{debug, error, info};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::db::pool_ext::AcquireRetry;
+use crate::error::AppError;
+
+/// Maximum span a single export may cover (one year plus a leap day)
+pub const MAX_EXPORT_RANGE_DAYS: i64 = 366;
+
+/// Number of encoded rows buffered between the database cursor and the HTTP body
+const EXPORT_CHANNEL_CAPACITY: usize = 256;
+
+const CSV_HEADER: &str = "record_type,id,timestamp,request_id,model_id,provider_code,task_type,status,tokens_input,tokens_output,cache_read_tokens,cache_write_tokens,cost,amount,transaction_type,balance_after,related_usage_id,description";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageExportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl UsageExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            UsageExportFormat::Csv => "text/csv; charset=utf-8",
+            UsageExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            UsageExportFormat::Csv => "csv",
+            UsageExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UsageExportFilters {
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub model: Option<String>,
+    pub provider: Option<String>,
+    pub task_type: Option<String>,
+    pub include_transactions: bool,
+}
+
+impl UsageExportFilters {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.end_date <= self.start_date {
+            return Err(AppError::Validation(
+                "end_date must be after start_date".to_string(),
+            ));
+        }
+
+        if (self.end_date - self.start_date).num_days() > MAX_EXPORT_RANGE_DAYS {
+            return Err(AppError::Validation(format!(
+                "Export range cannot exceed {} days",
+                MAX_EXPORT_RANGE_DAYS
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// A single line of the export: either an API usage record or a credit transaction
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageExportRow {
+    pub record_type: String,
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub request_id: Option<String>,
+    pub model_id: Option<String>,
+    pub provider_code: Option<String>,
+    pub task_type: Option<String>,
+    pub status: Option<String>,
+    pub tokens_input: i64,
+    pub tokens_output: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+    pub cost: Option<BigDecimal>,
+    pub amount: Option<BigDecimal>,
+    pub transaction_type: Option<String>,
+    pub balance_after: Option<BigDecimal>,
+    pub related_usage_id: Option<Uuid>,
+    pub description: Option<String>,
+}
+
+impl UsageExportRow {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            record_type: row.try_get("record_type")?,
+            id: row.try_get("id")?,
+            timestamp: row.try_get("occurred_at")?,
+            request_id: row.try_get("request_id")?,
+            model_id: row.try_get("model_id")?,
+            provider_code: row.try_get("provider_code")?,
+            task_type: row.try_get("task_type")?,
+            status: row.try_get("status")?,
+            tokens_input: row.try_get("tokens_input")?,
+            tokens_output: row.try_get("tokens_output")?,
+            cache_read_tokens: row.try_get("cache_read_tokens")?,
+            cache_write_tokens: row.try_get("cache_write_tokens")?,
+            cost: row.try_get("cost")?,
+            amount: row.try_get("amount")?,
+            transaction_type: row.try_get("transaction_type")?,
+            balance_after: row.try_get("balance_after")?,
+            related_usage_id: row.try_get("related_usage_id")?,
+            description: row.try_get("description")?,
+        })
+    }
+
+    pub fn to_csv_line(&self) -> String {
+        let fields = [
+            csv_field(&self.record_type),
+            self.id.to_string(),
+            self.timestamp.to_rfc3339(),
+            csv_optional(self.request_id.as_deref()),
+            csv_optional(self.model_id.as_deref()),
+            csv_optional(self.provider_code.as_deref()),
+            csv_optional(self.task_type.as_deref()),
+            csv_optional(self.status.as_deref()),
+            self.tokens_input.to_string(),
+            self.tokens_output.to_string(),
+            self.cache_read_tokens.to_string(),
+            self.cache_write_tokens.to_string(),
+            self.cost
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            self.amount
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            csv_optional(self.transaction_type.as_deref()),
+            self.balance_after
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            self.related_usage_id
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            csv_optional(self.description.as_deref()),
+        ];
+
+        let mut line = fields.join(",");
+        line.push_str("\r\n");
+        line
+    }
+
+    pub fn to_json_line(&self) -> Result<String, AppError> {
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        Ok(line)
+    }
+
+    pub fn encode(&self, format: UsageExportFormat) -> Result<Bytes, AppError> {
+        match format {
+            UsageExportFormat::Csv => Ok(Bytes::from(self.to_csv_line())),
+            UsageExportFormat::Jsonl => Ok(Bytes::from(self.to_json_line()?)),
+        }
+    }
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a delimiter, quote or line break.
+/// Fields starting with formula characters are prefixed so spreadsheets treat them as text.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn csv_optional(value: Option<&str>) -> String {
+    value.map(csv_field).unwrap_or_default()
+}
+
+/// Streams API usage and credit transactions for a user as CSV or JSON Lines.
+///
+/// Uses the user pool so RLS applies; the query runs on a dedicated task that
+/// reads rows through a cursor and hands encoded chunks to the response body.
+pub struct UsageExportService {
+    user_pool: PgPool,
+}
+
+impl UsageExportService {
+    pub fn new(user_pool: PgPool) -> Self {
+        Self { user_pool }
+    }
+
+    pub fn stream_export(
+        &self,
+        user_id: Uuid,
+        filters: UsageExportFilters,
+        format: UsageExportFormat,
+    ) -> impl Stream<Item = Result<Bytes, AppError>> + 'static {
+        let (sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+        let pool = self.user_pool.clone();
+
+        tokio::spawn(async move {
+            match write_export(&pool, &user_id, &filters, format, &sender).await {
+                Ok(rows) => info!(
+                    "Usage export for user {} finished with {} rows",
+                    user_id, rows
+                ),
+                Err(e) => {
+                    error!("Usage export for user {} failed: {}", user_id, e);
+                    let _ = sender.send(Err(e)).await;
+                }
+            }
+        });
+
+        stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|item| (item, receiver))
+        })
+    }
+}
+
+async fn write_export(
+    pool: &PgPool,
+    user_id: &Uuid,
+    filters: &UsageExportFilters,
+    format: UsageExportFormat,
+    sender: &mpsc::Sender<Result<Bytes, AppError>>,
+) -> Result<u64, AppError> {
+    let mut tx = AcquireRetry::begin_with_retry(pool, 3, 100)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+
+    // Set user context for RLS policies
+    sqlx::query("SELECT set_config('app.current_user_id', $1, true)")
+        .bind(user_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to set user context: {}", e)))?;
+
+    if format == UsageExportFormat::Csv {
+        let header = format!("{}\r\n", CSV_HEADER);
+        if sender.send(Ok(Bytes::from(header))).await.is_err() {
+            return Ok(0);
+        }
+    }
+
+    let mut written = 0u64;
+    {
+        let mut rows = sqlx::query(
+            r#"
+            WITH usage_rows AS (
+                SELECT
+                    'api_usage'::text AS record_type,
+                    au.id,
+                    au.timestamp AS occurred_at,
+                    au.request_id,
+                    CASE
+                        WHEN au.service_name LIKE '%/%' THEN au.service_name
+                        WHEN au.metadata->>'modelId' IS NOT NULL THEN au.metadata->>'modelId'
+                        ELSE au.service_name
+                    END AS model_id,
+                    COALESCE(au.metadata->>'task_type', au.metadata->>'taskType') AS task_type,
+                    au.status,
+                    au.tokens_input::bigint AS tokens_input,
+                    au.tokens_output::bigint AS tokens_output,
+                    COALESCE(au.cache_read_tokens, 0)::bigint AS cache_read_tokens,
+                    COALESCE(au.cache_write_tokens, 0)::bigint AS cache_write_tokens,
+                    au.cost::numeric AS cost,
+                    NULL::numeric AS amount,
+                    NULL::text AS transaction_type,
+                    NULL::numeric AS balance_after,
+                    NULL::uuid AS related_usage_id,
+                    NULL::text AS description
+                FROM api_usage au
+                WHERE au.user_id = $1 AND au.timestamp >= $2 AND au.timestamp < $3
+            ),
+            transaction_rows AS (
+                SELECT
+                    'credit_transaction'::text AS record_type,
+                    ct.id,
+                    ct.created_at AS occurred_at,
+                    au.request_id,
+                    CASE
+                        WHEN au.service_name LIKE '%/%' THEN au.service_name
+                        WHEN au.metadata->>'modelId' IS NOT NULL THEN au.metadata->>'modelId'
+                        ELSE au.service_name
+                    END AS model_id,
+                    COALESCE(au.metadata->>'task_type', au.metadata->>'taskType') AS task_type,
+                    NULL::text AS status,
+                    0::bigint AS tokens_input,
+                    0::bigint AS tokens_output,
+                    0::bigint AS cache_read_tokens,
+                    0::bigint AS cache_write_tokens,
+                    NULL::numeric AS cost,
+                    ct.net_amount::numeric AS amount,
+                    ct.transaction_type::text AS transaction_type,
+                    ct.balance_after::numeric AS balance_after,
+                    ct.related_api_usage_id AS related_usage_id,
+                    ct.description
+                FROM credit_transactions ct
+                LEFT JOIN api_usage au ON au.id = ct.related_api_usage_id
+                WHERE $7::boolean
+                  AND ct.user_id = $1 AND ct.created_at >= $2 AND ct.created_at < $3
+            ),
+            combined AS (
+                SELECT * FROM usage_rows
+                UNION ALL
+                SELECT * FROM transaction_rows
+            )
+            SELECT c.*, p.code AS provider_code
+            FROM combined c
+            LEFT JOIN models m ON m.id = c.model_id
+            LEFT JOIN providers p ON p.id = m.provider_id
+            WHERE ($4::text IS NULL OR c.model_id = $4)
+              AND ($5::text IS NULL OR p.code = $5)
+              AND ($6::text IS NULL OR c.task_type = $6)
+            ORDER BY c.occurred_at, c.id
+            "#,
+        )
+        .bind(user_id)
+        .bind(filters.start_date)
+        .bind(filters.end_date)
+        .bind(filters.model.as_deref())
+        .bind(filters.provider.as_deref())
+        .bind(filters.task_type.as_deref())
+        .bind(filters.include_transactions)
+        .fetch(&mut *tx);
+
+        while let Some(row) = rows.next().await {
+            let row = row.map_err(|e| {
+                AppError::Database(format!("Failed to read usage export row: {}", e))
+            })?;
+            let export_row = UsageExportRow::from_row(&row).map_err(|e| {
+                AppError::Database(format!("Failed to decode usage export row: {}", e))
+            })?;
+
+            if sender.send(export_row.encode(format)).await.is_err() {
+                debug!(
+                    "Usage export client for user {} disconnected after {} rows",
+                    user_id, written
+                );
+                break;
+            }
+            written += 1;
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to commit transaction: {}", e)))?;
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn usage_row() -> UsageExportRow {
+        UsageExportRow {
+            record_type: "api_usage".to_string(),
+            id: Uuid::nil(),
+            timestamp: DateTime::parse_from_rfc3339("2025-01-31T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            request_id: Some("req-1".to_string()),
+            model_id: Some("openai/gpt-4.1".to_string()),
+            provider_code: Some("openai".to_string()),
+            task_type: Some("implementation_plan".to_string()),
+            status: Some("completed".to_string()),
+            tokens_input: 1200,
+            tokens_output: 300,
+            cache_read_tokens: 800,
+            cache_write_tokens: 50,
+            cost: Some(BigDecimal::from_str("0.012345").unwrap()),
+            amount: None,
+            transaction_type: None,
+            balance_after: None,
+            related_usage_id: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_line_matches_header_columns() {
+        let line = usage_row().to_csv_line();
+        assert!(line.ends_with("\r\n"));
+        assert_eq!(
+            line.trim_end().split(',').count(),
+            CSV_HEADER.split(',').count()
+        );
+        assert!(line.contains(",1200,300,800,50,0.012345,"));
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field("=SUM(A1)"), "'=SUM(A1)");
+    }
+
+    #[test]
+    fn test_json_line_uses_camel_case() {
+        let line = usage_row().to_json_line().unwrap();
+        assert!(line.ends_with('\n'));
+        let value: serde_json::Value = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["recordType"], "api_usage");
+        assert_eq!(value["cacheReadTokens"], 800);
+        assert_eq!(value["requestId"], "req-1");
+    }
+
+    #[test]
+    fn test_filters_reject_invalid_ranges() {
+        let start = Utc::now();
+        let mut filters = UsageExportFilters {
+            start_date: start,
+            end_date: start,
+            model: None,
+            provider: None,
+            task_type: None,
+            include_transactions: true,
+        };
+        assert!(filters.validate().is_err());
+
+        filters.end_date = start + chrono::Duration::days(MAX_EXPORT_RANGE_DAYS + 1);
+        assert!(filters.validate().is_err());
+
+        filters.end_date = start + chrono::Duration::days(31);
+        assert!(filters.validate().is_ok());
+    }
+}
@@ -0,0 +1,80 @@
+use actix_web::web;
+use futures_util::Stream;
+use serde_json::Value;
+
+use super::transformers::{StreamChunkTransformer, StreamError, TransformResult};
+use crate::clients::open_router_client::OpenRouterStreamChunk;
+use crate::clients::usage_extractor::ProviderUsage;
+use crate::error::AppError;
+use crate::services::response_cache_service::CachedUsage;
+
+/// Field carrying the original token usage in the synthetic final replay event
+const CACHED_USAGE_FIELD: &str = "cached_usage";
+
+/// Builds an SSE byte stream that replays cached chunks followed by the original usage,
+/// so a cache hit flows through `ModernStreamHandler` exactly like a provider stream
+pub fn replay_byte_stream(
+    chunks: &[OpenRouterStreamChunk],
+    usage: &CachedUsage,
+) -> Result<impl Stream<Item = Result<web::Bytes, AppError>> + Send + Unpin + 'static, AppError> {
+    let mut frames = Vec::with_capacity(chunks.len() + 2);
+    for chunk in chunks {
+        frames.push(Ok(sse_frame(&serde_json::to_string(chunk)?)));
+    }
+    let usage_event = serde_json::json!({ CACHED_USAGE_FIELD: usage });
+    frames.push(Ok(sse_frame(&usage_event.to_string())));
+    frames.push(Ok(sse_frame("[DONE]")));
+
+    Ok(futures_util::stream::iter(frames))
+}
+
+fn sse_frame(data: &str) -> web::Bytes {
+    web::Bytes::from(format!("data: {}\n\n", data))
+}
+
+/// Transformer for replayed cache entries: chunks are already in the standardized
+/// format, so they are passed through and usage is read from the final replay event
+pub struct CacheReplayTransformer {
+    model_id: String,
+}
+
+impl CacheReplayTransformer {
+    pub fn new(model_id: &str) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+        }
+    }
+}
+
+impl StreamChunkTransformer for CacheReplayTransformer {
+    fn transform_chunk(&self, chunk: &Value) -> Result<TransformResult, StreamError> {
+        if chunk.get(CACHED_USAGE_FIELD).is_some() {
+            return Ok(TransformResult::Ignore);
+        }
+
+        serde_json::from_value::<OpenRouterStreamChunk>(chunk.clone())
+            .map(TransformResult::Transformed)
+            .map_err(|e| StreamError::ParseError(format!("Invalid cached chunk: {}", e)))
+    }
+
+    fn handle_error_chunk(&self, error: &Value) -> StreamError {
+        StreamError::InternalError(format!("Unexpected error in cached response: {}", error))
+    }
+
+    fn extract_usage_from_chunk(&self, chunk: &Value) -> Option<ProviderUsage> {
+        let usage = chunk.get(CACHED_USAGE_FIELD)?;
+        serde_json::from_value::<CachedUsage>(usage.clone())
+            .ok()
+            .map(|usage| usage.to_provider_usage(&self.model_id))
+    }
+
+    fn extract_text_delta(&self, chunk: &Value) -> Option<String> {
+        chunk
+            .get("choices")?
+            .get(0)?
+            .get("delta")?
+            .get("content")?
+            .as_str()
+            .map(str::to_string)
+    }
+}
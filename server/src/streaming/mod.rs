@@ -1,3 +1,5 @@
+pub mod cache_replay;
+pub mod openai_compat;
 pub mod sse_adapter;
 pub mod stream_handler;
 pub mod transformers;
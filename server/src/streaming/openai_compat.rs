@@ -0,0 +1,284 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::web;
+use eventsource_stream::Eventsource;
+use futures_util::{Stream, StreamExt};
+use serde_json::{Value, json};
+use tracing::{debug, warn};
+
+/// Translates the internal `/api/llm` SSE stream into the OpenAI chat completion
+/// chunk format: content chunks pass through, control events are dropped, the
+/// billed usage becomes a final usage chunk and the stream ends with `[DONE]`
+pub struct OpenAiStreamTranslator {
+    model_id: String,
+    include_usage: bool,
+    completion_id: Option<String>,
+    created: Option<i64>,
+    finished: bool,
+}
+
+impl OpenAiStreamTranslator {
+    pub fn new(model_id: &str, include_usage: bool) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+            include_usage,
+            completion_id: None,
+            created: None,
+            finished: false,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Translate one internal SSE event into zero or more OpenAI SSE frames
+    pub fn translate(&mut self, event_type: &str, data: &str) -> Vec<web::Bytes> {
+        if self.finished {
+            return Vec::new();
+        }
+
+        match event_type {
+            // Unnamed events carry the standardized content chunks
+            "" | "message" => self.content_chunk(data).into_iter().collect(),
+            "stream_completed" => {
+                let mut frames = Vec::new();
+                if self.include_usage {
+                    if let Some(frame) = self.usage_chunk(data) {
+                        frames.push(frame);
+                    }
+                }
+                frames.push(done_frame());
+                self.finished = true;
+                frames
+            }
+            "stream_cancelled" => {
+                self.finished = true;
+                vec![done_frame()]
+            }
+            "error_details" => {
+                self.finished = true;
+                let details = serde_json::from_str::<Value>(data).unwrap_or_default();
+                let message = details
+                    .pointer("/error/message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("The upstream provider returned an error");
+                let code = details.pointer("/error/code").and_then(Value::as_str);
+                vec![error_frame(message, code)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Frame emitted when the internal stream fails mid-flight
+    pub fn stream_error(&mut self, message: &str) -> web::Bytes {
+        self.finished = true;
+        error_frame(message, None)
+    }
+
+    fn content_chunk(&mut self, data: &str) -> Option<web::Bytes> {
+        let mut chunk: Value = match serde_json::from_str(data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                warn!("Skipping unparseable stream chunk: {}", e);
+                return None;
+            }
+        };
+        let obj = chunk.as_object_mut()?;
+
+        // Usage is reported once, from the billed totals, in the final chunk
+        obj.remove("usage");
+        obj.insert("object".to_string(), json!("chat.completion.chunk"));
+        obj.insert("model".to_string(), json!(self.model_id));
+        if !obj.get("created").is_some_and(Value::is_i64) {
+            obj.insert("created".to_string(), json!(chrono::Utc::now().timestamp()));
+        }
+
+        if self.completion_id.is_none() {
+            self.completion_id = obj.get("id").and_then(Value::as_str).map(str::to_string);
+        }
+        self.created = obj.get("created").and_then(Value::as_i64);
+
+        Some(data_frame(&chunk))
+    }
+
+    fn usage_chunk(&self, data: &str) -> Option<web::Bytes> {
+        let completed: Value = serde_json::from_str(data).ok()?;
+        let prompt_tokens = completed.get("tokensInput").and_then(Value::as_i64)?;
+        let completion_tokens = completed.get("tokensOutput").and_then(Value::as_i64)?;
+        let cached_tokens = completed
+            .get("cacheReadTokens")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+
+        Some(data_frame(&json!({
+            "id": self.completion_id.clone().unwrap_or_else(|| {
+                format!("chatcmpl-{}", completed.get("requestId").and_then(Value::as_str).unwrap_or_default())
+            }),
+            "object": "chat.completion.chunk",
+            "created": self.created.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            "model": self.model_id,
+            "choices": [],
+            "usage": {
+                "prompt_tokens": prompt_tokens,
+                "completion_tokens": completion_tokens,
+                "total_tokens": prompt_tokens + completion_tokens,
+                "prompt_tokens_details": { "cached_tokens": cached_tokens }
+            }
+        })))
+    }
+}
+
+/// Convert the standardized camelCase usage of a non-streaming response to OpenAI's shape
+pub fn openai_usage_from_standardized(usage: &Value) -> Option<Value> {
+    let prompt_tokens = usage.get("promptTokens").and_then(Value::as_i64)?;
+    let completion_tokens = usage.get("completionTokens").and_then(Value::as_i64)?;
+    let cached_tokens = usage
+        .get("cacheReadTokens")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+
+    Some(json!({
+        "prompt_tokens": prompt_tokens,
+        "completion_tokens": completion_tokens,
+        "total_tokens": prompt_tokens + completion_tokens,
+        "prompt_tokens_details": { "cached_tokens": cached_tokens }
+    }))
+}
+
+/// Re-encode an internal SSE response body as an OpenAI-compatible SSE byte stream
+pub fn translate_sse_body(
+    body: BoxBody,
+    translator: OpenAiStreamTranslator,
+) -> impl Stream<Item = Result<web::Bytes, actix_web::Error>> + 'static {
+    let mut body = Box::pin(body);
+    let byte_stream = futures_util::stream::poll_fn(move |cx| {
+        body.as_mut().poll_next(cx).map(|item| {
+            item.map(|chunk| {
+                chunk
+                    .map(|bytes| bytes.to_vec())
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+            })
+        })
+    });
+
+    let events = Box::pin(byte_stream.eventsource());
+    futures_util::stream::unfold(
+        (events, translator),
+        |(mut events, mut translator)| async move {
+            if translator.is_finished() {
+                return None;
+            }
+            let frames = match events.next().await {
+                Some(Ok(event)) => translator.translate(&event.event, &event.data),
+                Some(Err(e)) => vec![translator.stream_error(&e.to_string())],
+                None => {
+                    // Upstream closed without a completion or cancellation event
+                    debug!("Internal stream ended without stream_completed");
+                    translator.finished = true;
+                    vec![done_frame()]
+                }
+            };
+            Some((frames, (events, translator)))
+        },
+    )
+    .flat_map(|frames| futures_util::stream::iter(frames.into_iter().map(Ok)))
+}
+
+fn data_frame(value: &Value) -> web::Bytes {
+    web::Bytes::from(format!("data: {}\n\n", value))
+}
+
+fn done_frame() -> web::Bytes {
+    web::Bytes::from_static(b"data: [DONE]\n\n")
+}
+
+fn error_frame(message: &str, code: Option<&str>) -> web::Bytes {
+    data_frame(&json!({
+        "error": {
+            "message": message,
+            "type": "server_error",
+            "param": null,
+            "code": code
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_json(frame: &web::Bytes) -> Value {
+        let text = std::str::from_utf8(frame).unwrap();
+        let data = text.strip_prefix("data: ").unwrap().trim_end();
+        serde_json::from_str(data).unwrap()
+    }
+
+    #[test]
+    fn content_chunks_are_normalized_and_control_events_dropped() {
+        let mut translator = OpenAiStreamTranslator::new("openai/gpt-5", false);
+
+        assert!(
+            translator
+                .translate("stream_started", r#"{"requestId":"req-1"}"#)
+                .is_empty()
+        );
+        assert!(
+            translator
+                .translate("usage_update", r#"{"tokensInput":3}"#)
+                .is_empty()
+        );
+
+        let frames = translator.translate(
+            "message",
+            r#"{"id":"gen-1","choices":[{"delta":{"content":"Hi"},"index":0}],"created":1700000000,"model":"gpt-5-2025","usage":{"promptTokens":3}}"#,
+        );
+        assert_eq!(frames.len(), 1);
+        let chunk = frame_json(&frames[0]);
+        assert_eq!(chunk["object"], "chat.completion.chunk");
+        assert_eq!(chunk["model"], "openai/gpt-5");
+        assert_eq!(chunk["choices"][0]["delta"]["content"], "Hi");
+        assert!(chunk.get("usage").is_none());
+    }
+
+    #[test]
+    fn completion_emits_usage_chunk_when_requested_then_done() {
+        let mut translator = OpenAiStreamTranslator::new("openai/gpt-5", true);
+        translator.translate(
+            "message",
+            r#"{"id":"gen-1","choices":[],"created":1700000000,"model":"gpt-5"}"#,
+        );
+
+        let frames = translator.translate(
+            "stream_completed",
+            r#"{"requestId":"req-1","finalCost":0.01,"tokensInput":10,"tokensOutput":5,"cacheReadTokens":4,"cacheWriteTokens":0}"#,
+        );
+        assert_eq!(frames.len(), 2);
+        let usage_chunk = frame_json(&frames[0]);
+        assert_eq!(usage_chunk["id"], "gen-1");
+        assert_eq!(usage_chunk["usage"]["total_tokens"], 15);
+        assert_eq!(
+            usage_chunk["usage"]["prompt_tokens_details"]["cached_tokens"],
+            4
+        );
+        assert_eq!(&frames[1][..], b"data: [DONE]\n\n");
+
+        assert!(translator.is_finished());
+        assert!(translator.translate("message", "{}").is_empty());
+    }
+
+    #[test]
+    fn standardized_usage_maps_to_openai_fields() {
+        let usage = json!({
+            "promptTokens": 7,
+            "completionTokens": 2,
+            "totalTokens": 9,
+            "cost": 0.001,
+            "cacheReadTokens": 0,
+            "cacheWriteTokens": 0
+        });
+        let converted = openai_usage_from_standardized(&usage).unwrap();
+        assert_eq!(converted["prompt_tokens"], 7);
+        assert_eq!(converted["completion_tokens"], 2);
+        assert_eq!(converted["total_tokens"], 9);
+    }
+}
@@ -13,13 +13,17 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::transformers::{StreamChunkTransformer, TransformResult};
+use crate::clients::open_router_client::OpenRouterStreamChunk;
 use crate::clients::usage_extractor::ProviderUsage;
 use crate::db::repositories::model_repository::ModelWithProvider;
 use crate::error::AppError;
 use crate::models::model_pricing::ModelPricing;
 use crate::models::stream_event::StreamEvent;
 use crate::services::billing_service::BillingService;
+use crate::services::provider_health_service::RequestOutcome;
 use crate::services::request_tracker::RequestTracker;
+use crate::services::response_cache_service::{CachedBody, CachedCompletion, CachedUsage};
+use crate::utils::financial_validation::normalize_cost;
 use crate::utils::stream_debug_logger::StreamDebugLogger;
 
 use super::sse_adapter::SseAdapter;
@@ -49,6 +53,11 @@ where
     keep_alive_interval: Interval,
     last_activity: std::time::Instant,
     billing_finalized: bool,
+    /// Chunks collected for the response cache when this request was registered as a cache miss
+    response_capture: Option<Vec<OpenRouterStreamChunk>>,
+    /// Set when replaying a cache hit; the final cost is scaled by this fraction
+    replay_cost_multiplier: Option<BigDecimal>,
+    first_token_recorded: bool,
 }
 
 impl<S> ModernStreamHandler<S>
@@ -73,6 +82,11 @@ where
 
         let sse_stream = SseAdapter::new(stream);
 
+        let response_capture = billing_service
+            .get_response_cache_service()
+            .filter(|cache| cache.is_capturing(&request_id))
+            .map(|_| Vec::new());
+
         Self {
             sse_stream: Box::pin(sse_stream),
             transformer: Arc::from(transformer),
@@ -90,7 +104,58 @@ where
             keep_alive_interval,
             last_activity: std::time::Instant::now(),
             billing_finalized: false,
+            response_capture,
+            replay_cost_multiplier: None,
+            first_token_recorded: false,
+        }
+    }
+
+    /// Mark this stream as a response cache replay billed at `cost_multiplier` of the normal cost
+    pub fn with_cache_replay(mut self, cost_multiplier: BigDecimal) -> Self {
+        self.replay_cost_multiplier = Some(cost_multiplier);
+        self
+    }
+
+    fn discard_response_capture(&mut self) {
+        if self.response_capture.take().is_some() {
+            if let Some(cache) = self.billing_service.get_response_cache_service() {
+                cache.take_capture(&self.request_id);
+            }
+        }
+    }
+
+    /// Report the stream outcome to provider health tracking; only the first report counts
+    fn report_provider_health(&self, outcome: RequestOutcome) {
+        if let Some(provider_health) = self.request_tracker.provider_health() {
+            provider_health.finish_request(&self.request_id, outcome);
+        }
+    }
+
+    fn spawn_response_cache_store(&mut self, usage: &ProviderUsage) {
+        let Some(mut chunks) = self.response_capture.take() else {
+            return;
+        };
+        let Some(cache) = self.billing_service.get_response_cache_service().cloned() else {
+            return;
+        };
+        let Some(cache_key) = cache.take_capture(&self.request_id) else {
+            return;
+        };
+
+        // Usage is replayed separately so the cached chunks never carry a stale cost
+        for chunk in &mut chunks {
+            chunk.usage = None;
         }
+
+        let completion = CachedCompletion {
+            model_id: self.model.id.clone(),
+            usage: CachedUsage::from_provider_usage(usage),
+            body: CachedBody::Stream { chunks },
+            created_at: chrono::Utc::now(),
+        };
+        tokio::spawn(async move {
+            cache.store(&cache_key, &completion).await;
+        });
     }
 
     /// Convert to an SSE stream for actix-web response
@@ -205,6 +270,12 @@ where
                 "Stream {} terminated without processing any tokens, marking as failed",
                 self.request_id
             );
+            self.discard_response_capture();
+            self.report_provider_health(if was_cancelled {
+                RequestOutcome::Ignored
+            } else {
+                RequestOutcome::Failure
+            });
 
             let billing_service = self.billing_service.clone();
             let request_tracker = self.request_tracker.clone();
@@ -252,29 +323,65 @@ where
             })));
         }
 
-        let final_cost = self.model.calculate_total_cost(&usage).unwrap_or_else(|e| {
+        let mut final_cost = self.model.calculate_total_cost(&usage).unwrap_or_else(|e| {
             error!(
                 "Failed to calculate cost for request {}: {}",
                 self.request_id, e
             );
             BigDecimal::from(0)
         });
+        if let Some(multiplier) = &self.replay_cost_multiplier {
+            // Match the rounding billing applies to the discounted replay charge
+            final_cost = normalize_cost(&(final_cost * multiplier));
+        }
+
+        if was_cancelled {
+            self.discard_response_capture();
+            self.report_provider_health(RequestOutcome::Ignored);
+        } else {
+            self.spawn_response_cache_store(&usage);
+            self.report_provider_health(RequestOutcome::Success);
+        }
 
         let billing_service = self.billing_service.clone();
         let request_tracker = self.request_tracker.clone();
         let request_id = self.request_id.clone();
         let user_id = self.user_id;
         let usage_clone = usage.clone();
-        let metadata = Some(serde_json::json!({
-            "streaming": true,
-            "cancelled": was_cancelled
-        }));
+        let replay_cost_multiplier = self.replay_cost_multiplier.clone();
+        let metadata = Some(match &replay_cost_multiplier {
+            Some(multiplier) => serde_json::json!({
+                "streaming": true,
+                "cancelled": was_cancelled,
+                "response_cache": "hit",
+                "response_cache_billing_fraction": multiplier.to_string()
+            }),
+            None => serde_json::json!({
+                "streaming": true,
+                "cancelled": was_cancelled
+            }),
+        });
 
         tokio::spawn(async move {
-            match billing_service
-                .finalize_api_charge_with_metadata(&request_id, &user_id, usage_clone, metadata)
-                .await
-            {
+            let result = match &replay_cost_multiplier {
+                Some(multiplier) => {
+                    billing_service
+                        .finalize_api_charge_with_cost_multiplier(
+                            &request_id,
+                            &user_id,
+                            usage_clone,
+                            metadata,
+                            multiplier,
+                        )
+                        .await
+                }
+                None => {
+                    billing_service
+                        .finalize_api_charge_with_metadata(&request_id, &user_id, usage_clone, metadata)
+                        .await
+                }
+            };
+            match result {
                 Ok(_) => {
                     tracing::debug!("Successfully finalized billing for request {}", request_id);
                 }
@@ -313,6 +420,24 @@ where
     }
 }
 
+impl<S> Drop for ModernStreamHandler<S>
+where
+    S: Stream<Item = Result<web::Bytes, AppError>>,
+{
+    fn drop(&mut self) {
+        // Streams dropped before termination (e.g. client disconnect) must not leave a pending capture behind
+        if self.response_capture.is_some() {
+            if let Some(cache) = self.billing_service.get_response_cache_service() {
+                cache.take_capture(&self.request_id);
+            }
+        }
+        // No-op when the outcome was already reported
+        if let Some(provider_health) = self.request_tracker.provider_health() {
+            provider_health.finish_request(&self.request_id, RequestOutcome::Ignored);
+        }
+    }
+}
+
 impl<S> Stream for ModernStreamHandler<S>
 where
     S: Stream<Item = Result<web::Bytes, AppError>> + Send + Unpin + 'static,
@@ -417,6 +542,8 @@ where
 
                     let stream_error = self.transformer.handle_error_chunk(error_obj);
                     let app_error: AppError = stream_error.into();
+                    self.discard_response_capture();
+                    self.report_provider_health(RequestOutcome::Failure);
 
                     let billing_service = self.billing_service.clone();
                     let request_tracker = self.request_tracker.clone();
@@ -455,6 +582,15 @@ where
 
                 match self.transformer.transform_chunk(&parsed_value) {
                     Ok(TransformResult::Transformed(chunk)) => {
+                        if !self.first_token_recorded {
+                            self.first_token_recorded = true;
+                            if let Some(provider_health) = self.request_tracker.provider_health() {
+                                provider_health.record_first_token(&self.request_id);
+                            }
+                        }
+                        if let Some(captured) = self.response_capture.as_mut() {
+                            captured.push(chunk.clone());
+                        }
                         return Poll::Ready(Some(Ok(StreamEvent::ContentChunk(chunk))));
                     }
                     Ok(TransformResult::Ignore) => {
@@ -468,6 +604,8 @@ where
                     }
                     Err(e) => {
                         error!("Transform error: {:?}", e);
+                        self.discard_response_capture();
+                        self.report_provider_health(RequestOutcome::Failure);
                         let app_error: AppError = e.into();
                         return Poll::Ready(Some(Err(app_error)));
                     }
@@ -476,6 +614,8 @@ where
             Poll::Ready(Some(Err(e))) => {
                 error!("Provider SSE stream error: {}", e);
                 self.debug_logger.log_error(&format!("Stream error: {}", e));
+                self.discard_response_capture();
+                self.report_provider_health(RequestOutcome::Failure);
 
                 let billing_service = self.billing_service.clone();
                 let request_tracker = self.request_tracker.clone();